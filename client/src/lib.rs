@@ -0,0 +1,316 @@
+//! Typed client for the relay's ZMQ protocol.
+//!
+//! Connects the same two sockets an EA uses — PUSH to the relay's receiver
+//! port and SUB to its publisher port — and handles the wire format for you:
+//! outgoing messages are MessagePack maps, incoming frames are
+//! `{topic} {MessagePack}` (see [`sankey_copier_zmq::protocol`]), and trade
+//! frames may carry several batched signals (see [`sankey_copier_zmq::batch`]).
+//!
+//! Round trip against a stand-in relay:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use sankey_copier_client::{ClientError, RelayClient};
+//! use sankey_copier_zmq::protocol::{self, MessageKind, MessageTypeDiscriminator};
+//! use sankey_copier_zmq::{OrderType, TradeAction, TradeSignal};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Stand-in relay: PULL for EA messages, PUB for configs and trade signals
+//! let ctx = zmq::Context::new();
+//! let pull = ctx.socket(zmq::PULL)?;
+//! pull.bind("tcp://127.0.0.1:*")?;
+//! let publisher = ctx.socket(zmq::PUB)?;
+//! publisher.bind("tcp://127.0.0.1:*")?;
+//! let push_addr = pull.get_last_endpoint()?.unwrap();
+//! let sub_addr = publisher.get_last_endpoint()?.unwrap();
+//!
+//! let mut client = RelayClient::connect(&push_addr, &sub_addr, "BOT_001")?;
+//! client.set_receive_timeout(Some(Duration::from_millis(100)))?;
+//! client.subscribe_trade("MASTER_001")?;
+//!
+//! // Client -> relay
+//! client.send_heartbeat(&client.heartbeat("Slave"))?;
+//! let received = pull.recv_bytes(0)?;
+//! assert_eq!(
+//!     MessageTypeDiscriminator::peek(&received)?.kind(),
+//!     MessageKind::Typed("Heartbeat".to_string())
+//! );
+//!
+//! // Relay -> client
+//! let signal = TradeSignal {
+//!     action: TradeAction::Open,
+//!     ticket: 1001,
+//!     symbol: Some("EURUSD".to_string()),
+//!     order_type: Some(OrderType::Buy),
+//!     lots: Some(0.1),
+//!     open_price: Some(1.1),
+//!     stop_loss: None,
+//!     take_profit: None,
+//!     magic_number: None,
+//!     comment: None,
+//!     timestamp: chrono::Utc::now(),
+//!     source_account: "MASTER_001".to_string(),
+//!     close_ratio: None,
+//!     seq: 1,
+//!     spread_points: None,
+//!     sl_tp_offset: false,
+//! };
+//! let frame = protocol::encode_frame(
+//!     "trade/MASTER_001/BOT_001",
+//!     &rmp_serde::to_vec_named(&signal)?,
+//! );
+//! // PUB drops frames until the subscription has reached it
+//! let (topic, received) = loop {
+//!     publisher.send(&frame, 0)?;
+//!     match client.recv_signal() {
+//!         Ok(received) => break received,
+//!         Err(ClientError::Timeout) => continue,
+//!         Err(e) => return Err(e.into()),
+//!     }
+//! };
+//! assert_eq!(topic, "trade/MASTER_001/BOT_001");
+//! assert_eq!(received.ticket, 1001);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use sankey_copier_zmq::{
+    batch, build_config_topic, protocol, HeartbeatMessage, TradeSignal, TOPIC_TRADE_PREFIX,
+};
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("ZMQ Error: {0}")]
+    Zmq(#[from] zmq::Error),
+
+    #[error("Serialization Error: {0}")]
+    Serde(#[from] rmp_serde::encode::Error),
+
+    #[error("Deserialization Error: {0}")]
+    DeSerde(#[from] rmp_serde::decode::Error),
+
+    #[error("No message received within the receive timeout")]
+    Timeout,
+
+    #[error("Malformed frame: {0}")]
+    MalformedFrame(String),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Connection to the relay acting as one account
+pub struct RelayClient {
+    _ctx: zmq::Context,
+    push: zmq::Socket,
+    sub: zmq::Socket,
+    account_id: String,
+    /// Signals of a batched frame not yet returned by `recv_signal`
+    pending_signals: VecDeque<(String, TradeSignal)>,
+}
+
+impl RelayClient {
+    /// Connect to the relay's receiver (`push_addr`) and publisher (`sub_addr`)
+    ///
+    /// Subscribes to `config/{account_id}` like an EA does.
+    pub fn connect(push_addr: &str, sub_addr: &str, account_id: &str) -> Result<Self> {
+        let ctx = zmq::Context::new();
+        let push = ctx.socket(zmq::PUSH)?;
+        let sub = ctx.socket(zmq::SUB)?;
+
+        // Do not hang on drop while the relay is unreachable
+        push.set_linger(1000)?;
+        push.connect(push_addr)?;
+        sub.connect(sub_addr)?;
+        sub.set_subscribe(build_config_topic(account_id).as_bytes())?;
+
+        Ok(Self {
+            _ctx: ctx,
+            push,
+            sub,
+            account_id: account_id.to_string(),
+            pending_signals: VecDeque::new(),
+        })
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// Receive timeout for `recv_frame` / `recv_signal` (None = block forever)
+    pub fn set_receive_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let timeout_ms = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as i32);
+        self.sub.set_rcvtimeo(timeout_ms)?;
+        Ok(())
+    }
+
+    /// Push any protocol message (Register, Heartbeat, TradeSignal, ...) to the relay
+    pub fn send<T: Serialize>(&self, message: &T) -> Result<()> {
+        let payload = rmp_serde::to_vec_named(message)?;
+        self.push.send(payload, 0)?;
+        Ok(())
+    }
+
+    pub fn send_heartbeat(&self, heartbeat: &HeartbeatMessage) -> Result<()> {
+        self.send(heartbeat)
+    }
+
+    /// Heartbeat for this account with empty account details
+    ///
+    /// The relay registers the sender as an EA of `ea_type` ("Master" or
+    /// "Slave"); fill in balance, broker etc. as needed before sending.
+    pub fn heartbeat(&self, ea_type: &str) -> HeartbeatMessage {
+        HeartbeatMessage {
+            message_type: "Heartbeat".to_string(),
+            account_id: self.account_id.clone(),
+            balance: 0.0,
+            equity: 0.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            open_positions: 0,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            ea_type: ea_type.to_string(),
+            platform: String::new(),
+            account_number: 0,
+            broker: String::new(),
+            account_name: String::new(),
+            server: String::new(),
+            currency: String::new(),
+            leverage: 0,
+            is_trade_allowed: true,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_map: None,
+        }
+    }
+
+    /// Subscribe to every trade topic of a Master (`trade/{master}/`)
+    pub fn subscribe_trade(&self, master_account: &str) -> Result<()> {
+        let topic = format!("{}{}/", TOPIC_TRADE_PREFIX, master_account);
+        self.sub.set_subscribe(topic.as_bytes())?;
+        Ok(())
+    }
+
+    /// Subscribe to an arbitrary topic prefix (e.g. `config/global`)
+    pub fn subscribe(&self, topic_prefix: &str) -> Result<()> {
+        self.sub.set_subscribe(topic_prefix.as_bytes())?;
+        Ok(())
+    }
+
+    /// Receive the next frame on any subscribed topic as (topic, MessagePack payload)
+    pub fn recv_frame(&self) -> Result<(String, Vec<u8>)> {
+        let frame = match self.sub.recv_bytes(0) {
+            Ok(frame) => frame,
+            Err(zmq::Error::EAGAIN) => return Err(ClientError::Timeout),
+            Err(e) => return Err(e.into()),
+        };
+        let (topic, payload) = protocol::split_frame(&frame)
+            .ok_or_else(|| ClientError::MalformedFrame("missing topic separator".to_string()))?;
+        Ok((
+            String::from_utf8_lossy(topic).into_owned(),
+            payload.to_vec(),
+        ))
+    }
+
+    /// Receive the next trade signal as (topic, signal)
+    ///
+    /// Frames on other topics are discarded; batched frames are returned one
+    /// signal per call in publish order.
+    pub fn recv_signal(&mut self) -> Result<(String, TradeSignal)> {
+        loop {
+            if let Some(next) = self.pending_signals.pop_front() {
+                return Ok(next);
+            }
+
+            let (topic, payload) = self.recv_frame()?;
+            if !topic.starts_with(TOPIC_TRADE_PREFIX) {
+                continue;
+            }
+            for signal in batch::split_batch(&payload).map_err(ClientError::MalformedFrame)? {
+                let signal = rmp_serde::from_slice::<TradeSignal>(signal)?;
+                self.pending_signals.push_back((topic.clone(), signal));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sankey_copier_zmq::{OrderType, TradeAction};
+
+    fn signal(ticket: i64) -> TradeSignal {
+        TradeSignal {
+            action: TradeAction::Close,
+            ticket,
+            symbol: Some("EURUSD".to_string()),
+            order_type: Some(OrderType::Buy),
+            lots: None,
+            open_price: None,
+            stop_loss: None,
+            take_profit: None,
+            magic_number: None,
+            comment: None,
+            timestamp: chrono::Utc::now(),
+            source_account: "MASTER_001".to_string(),
+            close_ratio: None,
+            seq: ticket as u64,
+            spread_points: None,
+            sl_tp_offset: false,
+        }
+    }
+
+    #[test]
+    fn test_recv_signal_splits_batches_and_skips_other_topics() {
+        let ctx = zmq::Context::new();
+        let publisher = ctx.socket(zmq::PUB).unwrap();
+        publisher.bind("tcp://127.0.0.1:*").unwrap();
+        let sub_addr = publisher.get_last_endpoint().unwrap().unwrap();
+
+        let mut client = RelayClient::connect("tcp://127.0.0.1:1", &sub_addr, "SLAVE_001").unwrap();
+        client
+            .set_receive_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        client.subscribe_trade("MASTER_001").unwrap();
+
+        // Wait until the subscription has reached the publisher
+        let probe = protocol::encode_frame("config/SLAVE_001", b"probe");
+        loop {
+            publisher.send(&probe, 0).unwrap();
+            match client.recv_frame() {
+                Ok(_) => break,
+                Err(ClientError::Timeout) => continue,
+                Err(e) => panic!("{}", e),
+            }
+        }
+
+        let payloads: Vec<Vec<u8>> = [1, 2]
+            .into_iter()
+            .map(|ticket| rmp_serde::to_vec_named(&signal(ticket)).unwrap())
+            .collect();
+        publisher.send(&probe, 0).unwrap();
+        publisher
+            .send(
+                protocol::encode_frame(
+                    "trade/MASTER_001/SLAVE_001",
+                    &batch::encode_batch(&payloads),
+                ),
+                0,
+            )
+            .unwrap();
+
+        let tickets: Vec<i64> = (0..2)
+            .map(|_| client.recv_signal().unwrap().1.ticket)
+            .collect();
+        assert_eq!(tickets, vec![1, 2]);
+        assert!(matches!(client.recv_signal(), Err(ClientError::Timeout)));
+    }
+}
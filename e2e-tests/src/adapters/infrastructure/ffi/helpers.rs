@@ -14,7 +14,8 @@
 use anyhow::Result;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::domain::models::{
-    LotCalculationMode, MasterSettings, SlaveSettings, SyncMode, TradeFilters,
+    CopyDirection, LotCalculationMode, MasterSettings, MergeStrategy, RoundingMode, SlaveSettings,
+    SyncMode, TradeFilters,
 };
 use tokio::time::{sleep, Duration};
 
@@ -47,6 +48,7 @@ pub fn default_test_slave_settings() -> SlaveSettings {
         lot_calculation_mode: LotCalculationMode::Multiplier,
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -54,6 +56,7 @@ pub fn default_test_slave_settings() -> SlaveSettings {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -62,6 +65,34 @@ pub fn default_test_slave_settings() -> SlaveSettings {
         max_retries: 3,
         max_signal_delay_ms: 30000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     }
 }
 
@@ -337,3 +368,17 @@ pub async fn start_and_wait_for_connection_default(
 ) -> Result<()> {
     start_and_wait_for_connection(master, slaves, 500).await
 }
+
+// =============================================================================
+// Helper Functions - WebSocket Events
+// =============================================================================
+
+/// Extract the `data` payload (as a JSON string) of a WebSocket event frame
+/// when its `type` matches `event_type`, e.g. `"system_snapshot"`
+pub fn ws_event_payload(text: &str, event_type: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(text).ok()?;
+    if event["type"].as_str() != Some(event_type) {
+        return None;
+    }
+    Some(event["data"].to_string())
+}
@@ -116,10 +116,14 @@ fn convert_slave_config(c: &SSlaveConfig) -> SlaveConfigMessage {
         status: c.status,
         lot_calculation_mode: match c.lot_calculation_mode {
             1 => LotCalculationMode::MarginRatio,
+            2 => LotCalculationMode::EquityRatio,
+            3 => LotCalculationMode::FixedLot,
+            4 => LotCalculationMode::RiskPercent,
             _ => LotCalculationMode::Multiplier,
         },
         lot_multiplier: Some(c.lot_multiplier),
         reverse_trade: c.reverse_trade != 0,
+        reverse_symbols: None,
         symbol_prefix: Some(bytes_to_string(&c.symbol_prefix)).filter(|s| !s.is_empty()),
         symbol_suffix: Some(bytes_to_string(&c.symbol_suffix)).filter(|s| !s.is_empty()),
         symbol_mappings: Vec::new(),
@@ -128,6 +132,7 @@ fn convert_slave_config(c: &SSlaveConfig) -> SlaveConfigMessage {
         source_lot_min: Some(c.source_lot_min),
         source_lot_max: Some(c.source_lot_max),
         master_equity: Some(c.master_equity),
+        fixed_lot: Some(c.fixed_lot).filter(|lot| *lot > 0.0),
         sync_mode: match c.sync_mode {
             1 => SyncMode::LimitOrder,
             2 => SyncMode::MarketOrder,
@@ -151,6 +156,7 @@ fn convert_symbol_mapping(c: &SSymbolMapping) -> SymbolMapping {
     SymbolMapping {
         source_symbol: bytes_to_string(&c.source),
         target_symbol: bytes_to_string(&c.target),
+        priority: 0,
     }
 }
 
@@ -190,6 +196,7 @@ fn convert_position_info(c: &SPositionInfo) -> PositionInfo {
             None
         },
         comment: Some(bytes_to_string(&c.comment)).filter(|s| !s.is_empty()),
+        profit: c.profit,
     }
 }
 
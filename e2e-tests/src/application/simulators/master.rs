@@ -372,6 +372,30 @@ impl MasterEaSimulator {
         Ok(None)
     }
 
+    /// Block until the relay has seen every subscription made so far
+    ///
+    /// Use instead of a fixed sleep before the relay publishes to this EA
+    /// (ZMQ drops frames for subscriptions that have not arrived yet).
+    pub fn wait_subscribed(&self, timeout_ms: i32) -> Result<bool> {
+        let guard = self.context.lock().unwrap();
+        let wrapper = guard
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Context not initialized"))?;
+
+        // Apply subscriptions still queued for the next OnTimer first
+        for topic in self.pending_subscriptions.lock().unwrap().drain(..) {
+            let topic_u16: Vec<u16> = topic.encode_utf16().chain(Some(0)).collect();
+            unsafe {
+                ea_subscribe_config(wrapper.raw(), topic_u16.as_ptr());
+            }
+        }
+
+        match unsafe { ea_wait_subscribed(wrapper.raw(), timeout_ms) } {
+            -1 => Err(anyhow::anyhow!("Failed to confirm subscriptions")),
+            ret => Ok(ret == 1),
+        }
+    }
+
     pub fn try_receive_sync_request(&self, timeout_ms: i32) -> Result<Option<SyncRequestMessage>> {
         let start = std::time::Instant::now();
         while start.elapsed().as_millis() < timeout_ms as u128 {
@@ -522,6 +546,9 @@ impl MasterEaSimulator {
             timestamp: Utc::now(),
             source_account: self.base.account_id().to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 
@@ -540,6 +567,9 @@ impl MasterEaSimulator {
             timestamp: Utc::now(),
             source_account: self.base.account_id().to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 
@@ -564,6 +594,9 @@ impl MasterEaSimulator {
             timestamp: Utc::now(),
             source_account: self.base.account_id().to_string(),
             close_ratio: Some(close_ratio),
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 
@@ -588,6 +621,9 @@ impl MasterEaSimulator {
             timestamp: Utc::now(),
             source_account: self.base.account_id().to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 
@@ -603,6 +639,7 @@ impl MasterEaSimulator {
             source_account: self.base.account_id().to_string(),
             positions,
             timestamp: Utc::now().to_rfc3339(),
+            ea_type: Some("Master".to_string()),
         };
         let bytes = rmp_serde::to_vec_named(&msg)?;
         self.send_raw_bytes(&bytes)
@@ -626,6 +663,7 @@ impl MasterEaSimulator {
             magic_number: Some(0),
             comment: Some("E2E Test Position".to_string()),
             open_time: Utc::now().to_rfc3339(),
+            profit: 0.0,
         }
     }
 
@@ -243,6 +243,7 @@ impl ExpertAdvisor for SlaveEaCore {
                             },
                             positions: snapshots,
                             timestamp: Utc::now().to_rfc3339(),
+                            ea_type: None,
                         };
                         self.received_position_snapshots.lock().unwrap().push(snap);
                     }
@@ -311,6 +312,9 @@ impl ExpertAdvisor for SlaveEaCore {
                             } else {
                                 Some(cmd.close_ratio)
                             },
+                            seq: 0,
+                            spread_points: None,
+                            sl_tp_offset: false,
                         };
                         self.received_trade_signals.lock().unwrap().push(signal);
                     }
@@ -566,6 +570,30 @@ impl SlaveEaSimulator {
         Ok(None)
     }
 
+    /// Block until the relay has seen every subscription made so far
+    ///
+    /// Use instead of a fixed sleep before the relay publishes to this EA
+    /// (ZMQ drops frames for subscriptions that have not arrived yet).
+    pub fn wait_subscribed(&self, timeout_ms: i32) -> Result<bool> {
+        let guard = self.context.lock().unwrap();
+        let wrapper = guard
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Context not initialized"))?;
+
+        // Apply subscriptions still queued for the next OnTimer first
+        for topic in self.pending_subscriptions.lock().unwrap().drain(..) {
+            let topic_u16: Vec<u16> = topic.encode_utf16().chain(Some(0)).collect();
+            unsafe {
+                ea_subscribe_config(wrapper.raw(), topic_u16.as_ptr());
+            }
+        }
+
+        match unsafe { ea_wait_subscribed(wrapper.raw(), timeout_ms) } {
+            -1 => Err(anyhow::anyhow!("Failed to confirm subscriptions")),
+            ret => Ok(ret == 1),
+        }
+    }
+
     pub fn wait_for_config(&self, timeout_ms: i32) -> Result<Option<SlaveConfig>> {
         let start = std::time::Instant::now();
         while start.elapsed().as_millis() < timeout_ms as u128 {
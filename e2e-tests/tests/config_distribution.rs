@@ -967,7 +967,7 @@ async fn test_master_config_not_found() {
 async fn test_master_config_update_after_creation() {
     let sandbox = TestSandbox::new().expect("Failed to start sandbox");
     let server = sandbox.server();
-    let db = Database::new(&server.db_url())
+    let _db = Database::new(&server.db_url())
         .await
         .expect("Failed to connect to test DB");
 
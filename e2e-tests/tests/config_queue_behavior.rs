@@ -454,7 +454,7 @@ mod tests {
 
         // Assert we received > 0
         assert!(
-            received_configs.len() >= 1,
+            !received_configs.is_empty(),
             "Should receive at least 1 config"
         );
         assert_eq!(
@@ -7,7 +7,7 @@
 //! 1. Master OFF → Slave gets master_web_ui_disabled warning
 //! 2. Master ON → Slave warning clears
 
-use e2e_tests::helpers::default_test_slave_settings;
+use e2e_tests::helpers::{default_test_slave_settings, ws_event_payload};
 use e2e_tests::TestSandbox;
 use e2e_tests::STATUS_DISABLED;
 use futures_util::StreamExt;
@@ -428,6 +428,12 @@ async fn seed_trade_group(
         symbol_prefix: None,
         symbol_suffix: None,
         config_version: 1,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
     db.update_master_settings(master_account, master_settings)
         .await?;
@@ -463,8 +469,8 @@ async fn wait_for_slave_warning(
 
     while let Some(msg) = read.next().await {
         if let Ok(tokio_tungstenite::tungstenite::Message::Text(text)) = msg {
-            if let Some(stripped) = text.strip_prefix("settings_updated:") {
-                if let Ok(json) = serde_json::from_str::<Value>(stripped) {
+            if let Some(stripped) = ws_event_payload(&text, "settings_updated") {
+                if let Ok(json) = serde_json::from_str::<Value>(&stripped) {
                     if json["slave_account"].as_str() == Some(slave_account) {
                         let warnings = json["warning_codes"]
                             .as_array()
@@ -476,8 +482,8 @@ async fn wait_for_slave_warning(
                         }
                     }
                 }
-            } else if let Some(stripped) = text.strip_prefix("system_snapshot:") {
-                if let Ok(json) = serde_json::from_str::<Value>(stripped) {
+            } else if let Some(stripped) = ws_event_payload(&text, "system_snapshot") {
+                if let Ok(json) = serde_json::from_str::<Value>(&stripped) {
                     if let Some(members) = json["members"].as_array() {
                         for member in members {
                             if member["slave_account"].as_str() == Some(slave_account) {
@@ -520,8 +526,8 @@ async fn wait_for_slave_warning_cleared(
 
     while let Some(msg) = read.next().await {
         if let Ok(tokio_tungstenite::tungstenite::Message::Text(text)) = msg {
-            if let Some(stripped) = text.strip_prefix("settings_updated:") {
-                if let Ok(json) = serde_json::from_str::<Value>(stripped) {
+            if let Some(stripped) = ws_event_payload(&text, "settings_updated") {
+                if let Ok(json) = serde_json::from_str::<Value>(&stripped) {
                     if json["slave_account"].as_str() == Some(slave_account) {
                         let warnings = json["warning_codes"]
                             .as_array()
@@ -533,8 +539,8 @@ async fn wait_for_slave_warning_cleared(
                         }
                     }
                 }
-            } else if let Some(stripped) = text.strip_prefix("system_snapshot:") {
-                if let Ok(json) = serde_json::from_str::<Value>(stripped) {
+            } else if let Some(stripped) = ws_event_payload(&text, "system_snapshot") {
+                if let Ok(json) = serde_json::from_str::<Value>(&stripped) {
                     if let Some(members) = json["members"].as_array() {
                         for member in members {
                             if member["slave_account"].as_str() == Some(slave_account) {
@@ -201,6 +201,8 @@ async fn test_allowed_symbols_filter() {
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         };
         settings
     })
@@ -279,6 +281,8 @@ async fn test_blocked_symbols_filter() {
             blocked_symbols: Some(vec!["XAUUSD".to_string(), "XAGUSD".to_string()]),
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         };
         settings
     })
@@ -357,6 +361,8 @@ async fn test_allowed_magic_numbers_filter() {
             blocked_symbols: None,
             allowed_magic_numbers: Some(vec![12345, 67890]),
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         };
         settings
     })
@@ -447,6 +453,8 @@ async fn test_blocked_magic_numbers_filter() {
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: Some(vec![11111, 22222]),
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         };
         settings
     })
@@ -50,7 +50,19 @@ async fn test_open_close_cycle() {
     master.start().expect("Failed to start master");
     slave.set_trade_allowed(true);
     slave.start().expect("Failed to start slave");
-    sleep(Duration::from_millis(2000)).await;
+
+    // The trade topic is subscribed on config reception; confirm it reached
+    // the relay instead of sleeping so the first signal is not dropped
+    slave
+        .wait_for_status(2, 5000)
+        .expect("Failed to wait for status")
+        .expect("Slave should become CONNECTED");
+    assert!(
+        slave
+            .wait_subscribed(2000)
+            .expect("Failed to wait for subscription"),
+        "Relay should confirm the Slave's subscriptions"
+    );
 
     // Step 1: Master sends Open signal
     let open_signal = master.create_open_signal(
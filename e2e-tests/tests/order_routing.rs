@@ -10,7 +10,9 @@ use e2e_tests::SlaveEaSimulator;
 use e2e_tests::TestSandbox;
 use e2e_tests::{OrderType, TradeSignal};
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
-use sankey_copier_relay_server::domain::models::{LotCalculationMode, SlaveSettings, SyncMode};
+use sankey_copier_relay_server::domain::models::{
+    CopyDirection, LotCalculationMode, MergeStrategy, RoundingMode, SlaveSettings, SyncMode,
+};
 use tokio::time::{sleep, Duration};
 
 // =============================================================================
@@ -343,6 +345,7 @@ async fn test_slave_individual_lot_multiplier() {
         lot_calculation_mode: LotCalculationMode::Multiplier,
         lot_multiplier: Some(2.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -350,6 +353,7 @@ async fn test_slave_individual_lot_multiplier() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -358,6 +362,34 @@ async fn test_slave_individual_lot_multiplier() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     })
     .await
     .expect("Failed to setup test scenario");
@@ -278,13 +278,21 @@ async fn test_symbol_mapping() {
     setup_test_scenario(&db, master_account, &[slave_account], |_| {
         let mut settings = default_test_slave_settings();
         settings.symbol_mappings = vec![
+            // Listed first but lower priority: must lose to the GOLD rule
+            SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "XAUUSD.bad".to_string(),
+                priority: 5,
+            },
             SymbolMapping {
                 source_symbol: "XAUUSD".to_string(),
                 target_symbol: "GOLD".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "XAGUSD".to_string(),
                 target_symbol: "SILVER".to_string(),
+                priority: 0,
             },
         ];
         settings
@@ -208,8 +208,8 @@ async fn test_same_account_id_master_and_slave_both_registered() {
 
     assert!(response.status().is_success(), "API should return success");
 
-    let connections: Vec<serde_json::Value> =
-        response.json().await.expect("Should parse JSON response");
+    let page: serde_json::Value = response.json().await.expect("Should parse JSON response");
+    let connections = page["items"].as_array().cloned().unwrap_or_default();
 
     // Filter connections for shared_account
     let shared_connections: Vec<_> = connections
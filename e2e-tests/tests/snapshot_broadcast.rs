@@ -1,5 +1,5 @@
 use anyhow::Result;
-use e2e_tests::helpers::setup_test_db;
+use e2e_tests::helpers::{setup_test_db, ws_event_payload};
 use e2e_tests::TestSandbox;
 use futures_util::StreamExt;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
@@ -76,8 +76,8 @@ async fn test_system_snapshot_broadcast() -> Result<()> {
                 if let Some(Ok(msg)) = msg {
                     if msg.is_text() {
                         let text = msg.to_text()?;
-                        if let Some(json_part) = text.strip_prefix("system_snapshot:") {
-                            match serde_json::from_str::<SystemStateSnapshot>(json_part) {
+                        if let Some(json_part) = ws_event_payload(text, "system_snapshot") {
+                            match serde_json::from_str::<SystemStateSnapshot>(&json_part) {
                                 Ok(snapshot) => {
                                     if let Some(tg) = snapshot.trade_groups.iter().find(|tg| tg.id == master_account) {
                                         println!("Snapshot received with Master: {:?}", tg);
@@ -122,8 +122,8 @@ async fn test_system_snapshot_broadcast() -> Result<()> {
                 if let Some(Ok(msg)) = msg {
                     if msg.is_text() {
                         let text = msg.to_text()?;
-                        if let Some(json_part) = text.strip_prefix("system_snapshot:") {
-                            if let Ok(snapshot) = serde_json::from_str::<SystemStateSnapshot>(json_part) {
+                        if let Some(json_part) = ws_event_payload(text, "system_snapshot") {
+                            if let Ok(snapshot) = serde_json::from_str::<SystemStateSnapshot>(&json_part) {
                                 if let Some(tg) = snapshot.trade_groups.iter().find(|tg| tg.id == master_account) {
                                     // Check for specific warning
                                     if tg.master_warning_codes.contains(&WarningCode::MasterAutoTradingDisabled) {
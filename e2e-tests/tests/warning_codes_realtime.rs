@@ -5,7 +5,7 @@
 //! 2. WebSocket broadcasts settings_updated with warning_codes within expected timeframe
 //! 3. Both Master and Slave scenarios work correctly
 
-use e2e_tests::helpers::default_test_slave_settings;
+use e2e_tests::helpers::{default_test_slave_settings, ws_event_payload};
 use e2e_tests::TestSandbox;
 use e2e_tests::STATUS_DISABLED;
 use futures_util::StreamExt;
@@ -677,9 +677,9 @@ async fn wait_for_settings_updated(
         let msg = msg_result.expect("WebSocket read error");
         if let Message::Text(text) = msg {
             // println!("[TEST] Received WebSocket message: {}", text); // Reduce noise
-            if let Some(json_str) = text.strip_prefix("settings_updated:") {
+            if let Some(json_str) = ws_event_payload(&text, "settings_updated") {
                 let settings: Value =
-                    serde_json::from_str(json_str).expect("Failed to parse settings_updated JSON");
+                    serde_json::from_str(&json_str).expect("Failed to parse settings_updated JSON");
 
                 println!("[TEST] Parsed settings_updated: slave_account={}, status={}, warning_codes={:?}",
                     settings["slave_account"].as_str().unwrap_or("N/A"),
@@ -694,10 +694,10 @@ async fn wait_for_settings_updated(
                 } else {
                     println!("[TEST] ⏭️  Skipping (different slave account)");
                 }
-            } else if let Some(json_str) = text.strip_prefix("system_snapshot:") {
+            } else if let Some(json_str) = ws_event_payload(&text, "system_snapshot") {
                 // Also check snapshots which contain the full state
                 let snapshot: Value =
-                    serde_json::from_str(json_str).expect("Failed to parse system_snapshot JSON");
+                    serde_json::from_str(&json_str).expect("Failed to parse system_snapshot JSON");
                 if let Some(members) = snapshot["members"].as_array() {
                     for member in members {
                         if member["slave_account"].as_str() == Some(expected_slave_account) {
@@ -730,8 +730,8 @@ async fn wait_for_slave_warning(
 
     while let Some(msg_result) = read.next().await {
         if let Ok(Message::Text(text)) = msg_result {
-            if let Some(json_str) = text.strip_prefix("settings_updated:") {
-                if let Ok(settings) = serde_json::from_str::<Value>(json_str) {
+            if let Some(json_str) = ws_event_payload(&text, "settings_updated") {
+                if let Ok(settings) = serde_json::from_str::<Value>(&json_str) {
                     if settings["slave_account"].as_str() == Some(slave_account) {
                         let warning_codes = settings["warning_codes"]
                             .as_array()
@@ -743,9 +743,9 @@ async fn wait_for_slave_warning(
                         }
                     }
                 }
-            } else if let Some(json_str) = text.strip_prefix("system_snapshot:") {
+            } else if let Some(json_str) = ws_event_payload(&text, "system_snapshot") {
                 // Check snapshot as well
-                if let Ok(snapshot) = serde_json::from_str::<Value>(json_str) {
+                if let Ok(snapshot) = serde_json::from_str::<Value>(&json_str) {
                     if let Some(members) = snapshot["members"].as_array() {
                         for member in members {
                             if member["slave_account"].as_str() == Some(slave_account) {
@@ -786,8 +786,8 @@ async fn wait_for_slave_warning_cleared(
 
     while let Some(msg_result) = read.next().await {
         if let Ok(Message::Text(text)) = msg_result {
-            if let Some(json_str) = text.strip_prefix("settings_updated:") {
-                if let Ok(settings) = serde_json::from_str::<Value>(json_str) {
+            if let Some(json_str) = ws_event_payload(&text, "settings_updated") {
+                if let Ok(settings) = serde_json::from_str::<Value>(&json_str) {
                     if settings["slave_account"].as_str() == Some(slave_account) {
                         let warning_codes = settings["warning_codes"]
                             .as_array()
@@ -799,9 +799,9 @@ async fn wait_for_slave_warning_cleared(
                         }
                     }
                 }
-            } else if let Some(json_str) = text.strip_prefix("system_snapshot:") {
+            } else if let Some(json_str) = ws_event_payload(&text, "system_snapshot") {
                 // Check snapshot as well
-                if let Ok(snapshot) = serde_json::from_str::<Value>(json_str) {
+                if let Ok(snapshot) = serde_json::from_str::<Value>(&json_str) {
                     if let Some(members) = snapshot["members"].as_array() {
                         for member in members {
                             if member["slave_account"].as_str() == Some(slave_account) {
@@ -0,0 +1,106 @@
+//! Multi-signal trade frames.
+//!
+//! With batching enabled the relay coalesces TradeSignals published to the
+//! same `trade/...` topic within a short window into one ZMQ frame:
+//!
+//! ```text
+//! [BATCH_MARKER] [count: u16 LE] ([len: u32 LE] [MessagePack TradeSignal])*
+//! ```
+//!
+//! `0xC1` is never used by MessagePack, so a single-signal payload (a map) can
+//! never start with it and both formats can share the same topics.
+
+/// First byte of a batched trade payload (reserved "never used" MessagePack byte)
+pub const BATCH_MARKER: u8 = 0xC1;
+
+/// Encode MessagePack payloads as one batch frame payload
+pub fn encode_batch<P: AsRef<[u8]>>(payloads: &[P]) -> Vec<u8> {
+    let size = 3 + payloads.iter().map(|p| 4 + p.as_ref().len()).sum::<usize>();
+    let mut frame = Vec::with_capacity(size);
+    frame.push(BATCH_MARKER);
+    frame.extend_from_slice(&(payloads.len() as u16).to_le_bytes());
+    for payload in payloads {
+        let payload = payload.as_ref();
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(payload);
+    }
+    frame
+}
+
+/// Whether a payload is a batch frame
+pub fn is_batch(payload: &[u8]) -> bool {
+    payload.first() == Some(&BATCH_MARKER)
+}
+
+/// Split a trade payload into its MessagePack signals.
+/// Non-batch payloads are returned as a single entry; a truncated batch is an error.
+pub fn split_batch(payload: &[u8]) -> Result<Vec<&[u8]>, String> {
+    if !is_batch(payload) {
+        return Ok(vec![payload]);
+    }
+    let count = payload
+        .get(1..3)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+        .ok_or("batch header truncated")?;
+
+    let mut signals = Vec::with_capacity(count);
+    let mut rest = &payload[3..];
+    for index in 0..count {
+        let len = rest
+            .get(..4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(|| format!("batch entry {} length truncated", index))?;
+        let signal = rest
+            .get(4..4 + len)
+            .ok_or_else(|| format!("batch entry {} truncated", index))?;
+        signals.push(signal);
+        rest = &rest[4 + len..];
+    }
+    if !rest.is_empty() {
+        return Err(format!("{} trailing bytes after batch", rest.len()));
+    }
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TradeSignal;
+
+    #[test]
+    fn test_batch_roundtrip() {
+        let signals: Vec<TradeSignal> = (1..=3)
+            .map(|ticket| TradeSignal {
+                ticket,
+                ..Default::default()
+            })
+            .collect();
+        let payloads: Vec<Vec<u8>> = signals
+            .iter()
+            .map(|s| rmp_serde::to_vec_named(s).unwrap())
+            .collect();
+
+        let frame = encode_batch(&payloads);
+        assert!(is_batch(&frame));
+        let decoded: Vec<i64> = split_batch(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|p| rmp_serde::from_slice::<TradeSignal>(p).unwrap().ticket)
+            .collect();
+        assert_eq!(decoded, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_single_payload_passes_through() {
+        let payload = rmp_serde::to_vec_named(&TradeSignal::default()).unwrap();
+        assert!(!is_batch(&payload));
+        assert_eq!(split_batch(&payload).unwrap(), vec![payload.as_slice()]);
+    }
+
+    #[test]
+    fn test_truncated_batch_is_rejected() {
+        let frame = encode_batch(&[vec![1u8, 2, 3]]);
+        assert!(split_batch(&frame[..frame.len() - 1]).is_err());
+        assert!(split_batch(&[BATCH_MARKER, 1]).is_err());
+    }
+}
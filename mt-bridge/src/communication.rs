@@ -1,8 +1,10 @@
 // mt-bridge/src/communication.rs
 
+use crate::curve::{self, CurveClientKeys};
 use crate::errors::BridgeError;
 use std::fmt::Debug;
-use zmq::{Context, Socket, PUSH, SUB};
+use std::time::{Duration, Instant};
+use zmq::{Context, Socket, PAIR, PUSH, SUB};
 
 // ===========================================================================
 // Communication Strategy Interface
@@ -41,16 +43,99 @@ pub trait CommunicationStrategy: Send + Debug {
 
     /// Subscribe to a topic on Config socket
     fn subscribe_config(&mut self, topic: &str) -> Result<(), BridgeError>;
+
+    /// Remove a subscription made with `subscribe_config`
+    fn unsubscribe_config(&mut self, _topic: &str) -> Result<(), BridgeError> {
+        Err(BridgeError::NotSupported)
+    }
+
+    /// Enable CURVE encryption for subsequent connects
+    fn set_curve_keys(&mut self, _keys: CurveClientKeys) -> Result<(), BridgeError> {
+        Err(BridgeError::NotSupported)
+    }
+
+    /// Set ZMQ_RECONNECT_IVL / ZMQ_RECONNECT_IVL_MAX (ms) for subsequent connects
+    fn set_reconnect_ivl(&mut self, _min_ms: i32, _max_ms: i32) -> Result<(), BridgeError> {
+        Err(BridgeError::NotSupported)
+    }
+
+    /// Block until both sockets completed the handshake with the relay
+    ///
+    /// Returns `Ok(false)` when `timeout_ms` elapsed first.
+    fn wait_connected(&mut self, _timeout_ms: i32) -> Result<bool, BridgeError> {
+        Err(BridgeError::NotSupported)
+    }
 }
 
 // ===========================================================================
 // Shared ZMQ Logic
 // ===========================================================================
 
+/// Socket options applied before connect
+#[derive(Debug, Default)]
+pub struct ConnectOptions {
+    pub curve_keys: Option<CurveClientKeys>,
+    /// (ZMQ_RECONNECT_IVL, ZMQ_RECONNECT_IVL_MAX) in milliseconds
+    pub reconnect_ivl: Option<(i32, i32)>,
+}
+
+impl ConnectOptions {
+    fn apply(&self, socket: &Socket) -> Result<(), BridgeError> {
+        if let Some(keys) = &self.curve_keys {
+            curve::apply_client(socket, keys)?;
+        }
+        if let Some((min_ms, max_ms)) = self.reconnect_ivl {
+            socket.set_reconnect_ivl(min_ms)?;
+            socket.set_reconnect_ivl_max(max_ms)?;
+        }
+        Ok(())
+    }
+}
+
+const PUSH_LINGER_MS: i32 = 1000;
+const PUSH_MONITOR_ENDPOINT: &str = "inproc://ea-push-monitor";
+const SUB_MONITOR_ENDPOINT: &str = "inproc://ea-sub-monitor";
+
+/// Socket monitor reporting when the connection handshake succeeded
+struct ConnectionMonitor {
+    events: Socket,
+    connected: bool,
+}
+
+impl ConnectionMonitor {
+    fn attach(ctx: &Context, socket: &Socket, endpoint: &str) -> Result<Self, BridgeError> {
+        socket.monitor(
+            endpoint,
+            zmq::SocketEvent::HANDSHAKE_SUCCEEDED.to_raw() as i32,
+        )?;
+        let events = ctx.socket(PAIR)?;
+        events.connect(endpoint)?;
+        Ok(Self {
+            events,
+            connected: false,
+        })
+    }
+
+    /// Drain pending events without blocking
+    fn drain(&mut self) -> Result<(), BridgeError> {
+        while let Ok(frames) = self.events.recv_multipart(zmq::DONTWAIT) {
+            // Frame 1: u16 event id (LE) + u32 value
+            if let Some(raw) = frames.first().and_then(|f| f.get(..2)) {
+                let event = u16::from_le_bytes([raw[0], raw[1]]);
+                if event == zmq::SocketEvent::HANDSHAKE_SUCCEEDED.to_raw() {
+                    self.connected = true;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct ZmqResources {
     _ctx: Context,
     push: Socket,
     sub: Socket,
+    monitors: Vec<ConnectionMonitor>,
 }
 
 impl Debug for ZmqResources {
@@ -64,21 +149,72 @@ impl Debug for ZmqResources {
 }
 
 impl ZmqResources {
-    fn new(push_addr: &str, sub_addr: &str) -> Result<Self, BridgeError> {
+    fn new(push_addr: &str, sub_addr: &str, options: &ConnectOptions) -> Result<Self, BridgeError> {
         let ctx = Context::new();
 
         let push = ctx.socket(PUSH)?;
-        push.connect(push_addr).map_err(BridgeError::Zmq)?;
-
         let sub = ctx.socket(SUB)?;
+
+        // CURVE / reconnect options must be set before connect
+        options.apply(&push)?;
+        options.apply(&sub)?;
+
+        // Bound how long unsent messages delay disconnect (default linger is infinite,
+        // which would hang EA deinit while the relay is unreachable)
+        push.set_linger(PUSH_LINGER_MS)?;
+
+        // Monitors must be attached before connect so no event is missed
+        let monitors = vec![
+            ConnectionMonitor::attach(&ctx, &push, PUSH_MONITOR_ENDPOINT)?,
+            ConnectionMonitor::attach(&ctx, &sub, SUB_MONITOR_ENDPOINT)?,
+        ];
+
+        push.connect(push_addr).map_err(BridgeError::Zmq)?;
         sub.connect(sub_addr).map_err(BridgeError::Zmq)?;
 
         Ok(Self {
             _ctx: ctx,
             push,
             sub,
+            monitors,
         })
     }
+
+    fn wait_connected(&mut self, timeout_ms: i32) -> Result<bool, BridgeError> {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+        loop {
+            for monitor in self.monitors.iter_mut() {
+                monitor.drain()?;
+            }
+            let pending: Vec<_> = self
+                .monitors
+                .iter()
+                .filter(|m| !m.connected)
+                .map(|m| m.events.as_poll_item(zmq::POLLIN))
+                .collect();
+            if pending.is_empty() {
+                return Ok(true);
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            let mut items = pending;
+            zmq::poll(&mut items, remaining.as_millis() as i64)?;
+        }
+    }
+}
+
+/// `max_ms == 0` disables exponential backoff (libzmq semantics)
+fn validate_reconnect_ivl(min_ms: i32, max_ms: i32) -> Result<(i32, i32), BridgeError> {
+    if min_ms <= 0 || max_ms < 0 || (max_ms > 0 && max_ms < min_ms) {
+        return Err(BridgeError::Generic(format!(
+            "Invalid reconnect interval: min={}ms max={}ms",
+            min_ms, max_ms
+        )));
+    }
+    Ok((min_ms, max_ms))
 }
 
 // ===========================================================================
@@ -88,6 +224,7 @@ impl ZmqResources {
 #[derive(Debug, Default)]
 pub struct MasterStrategy {
     resources: Option<ZmqResources>,
+    options: ConnectOptions,
 }
 
 impl CommunicationStrategy for MasterStrategy {
@@ -97,12 +234,15 @@ impl CommunicationStrategy for MasterStrategy {
         sub_addr: &str,
         account_id: &str,
     ) -> Result<(), BridgeError> {
-        let res = ZmqResources::new(push_addr, sub_addr)?;
+        let res = ZmqResources::new(push_addr, sub_addr, &self.options)?;
 
         // Master: Subscribe only to my own config
         // Topic: "config/{account_id}"
         let topic = format!("config/{}", account_id);
         res.sub.set_subscribe(topic.as_bytes())?;
+        // Latency probes from the relay
+        res.sub
+            .set_subscribe(crate::constants::build_ping_topic(account_id).as_bytes())?;
 
         self.resources = Some(res);
         Ok(())
@@ -114,7 +254,8 @@ impl CommunicationStrategy for MasterStrategy {
 
     fn send_push(&mut self, data: &[u8]) -> Result<(), BridgeError> {
         let res = self.resources.as_ref().ok_or(BridgeError::NoSocket)?;
-        res.push.send(data, 0).map_err(BridgeError::Zmq)
+        // Never block the MT terminal thread; a full queue surfaces as EAGAIN
+        res.push.send(data, zmq::DONTWAIT).map_err(BridgeError::Zmq)
     }
 
     fn subscribe_trade(&mut self, _master_id: &str) -> Result<(), BridgeError> {
@@ -149,6 +290,28 @@ impl CommunicationStrategy for MasterStrategy {
             .set_subscribe(topic.as_bytes())
             .map_err(BridgeError::Zmq)
     }
+
+    fn unsubscribe_config(&mut self, topic: &str) -> Result<(), BridgeError> {
+        let res = self.resources.as_mut().ok_or(BridgeError::NoSocket)?;
+        res.sub
+            .set_unsubscribe(topic.as_bytes())
+            .map_err(BridgeError::Zmq)
+    }
+
+    fn set_curve_keys(&mut self, keys: CurveClientKeys) -> Result<(), BridgeError> {
+        self.options.curve_keys = Some(keys);
+        Ok(())
+    }
+
+    fn set_reconnect_ivl(&mut self, min_ms: i32, max_ms: i32) -> Result<(), BridgeError> {
+        self.options.reconnect_ivl = Some(validate_reconnect_ivl(min_ms, max_ms)?);
+        Ok(())
+    }
+
+    fn wait_connected(&mut self, timeout_ms: i32) -> Result<bool, BridgeError> {
+        let res = self.resources.as_mut().ok_or(BridgeError::NoSocket)?;
+        res.wait_connected(timeout_ms)
+    }
 }
 
 // ===========================================================================
@@ -158,6 +321,7 @@ impl CommunicationStrategy for MasterStrategy {
 #[derive(Debug, Default)]
 pub struct SlaveStrategy {
     resources: Option<ZmqResources>,
+    options: ConnectOptions,
 }
 
 impl CommunicationStrategy for SlaveStrategy {
@@ -167,11 +331,14 @@ impl CommunicationStrategy for SlaveStrategy {
         sub_addr: &str,
         account_id: &str,
     ) -> Result<(), BridgeError> {
-        let res = ZmqResources::new(push_addr, sub_addr)?;
+        let res = ZmqResources::new(push_addr, sub_addr, &self.options)?;
 
         // Slave: Subscribe to my own config
         let topic = format!("config/{}", account_id);
         res.sub.set_subscribe(topic.as_bytes())?;
+        // Latency probes from the relay
+        res.sub
+            .set_subscribe(crate::constants::build_ping_topic(account_id).as_bytes())?;
 
         self.resources = Some(res);
         Ok(())
@@ -183,7 +350,8 @@ impl CommunicationStrategy for SlaveStrategy {
 
     fn send_push(&mut self, data: &[u8]) -> Result<(), BridgeError> {
         let res = self.resources.as_ref().ok_or(BridgeError::NoSocket)?;
-        res.push.send(data, 0).map_err(BridgeError::Zmq)
+        // Never block the MT terminal thread; a full queue surfaces as EAGAIN
+        res.push.send(data, zmq::DONTWAIT).map_err(BridgeError::Zmq)
     }
 
     fn subscribe_trade(&mut self, master_id: &str) -> Result<(), BridgeError> {
@@ -225,6 +393,28 @@ impl CommunicationStrategy for SlaveStrategy {
             .set_subscribe(topic.as_bytes())
             .map_err(BridgeError::Zmq)
     }
+
+    fn unsubscribe_config(&mut self, topic: &str) -> Result<(), BridgeError> {
+        let res = self.resources.as_mut().ok_or(BridgeError::NoSocket)?;
+        res.sub
+            .set_unsubscribe(topic.as_bytes())
+            .map_err(BridgeError::Zmq)
+    }
+
+    fn set_curve_keys(&mut self, keys: CurveClientKeys) -> Result<(), BridgeError> {
+        self.options.curve_keys = Some(keys);
+        Ok(())
+    }
+
+    fn set_reconnect_ivl(&mut self, min_ms: i32, max_ms: i32) -> Result<(), BridgeError> {
+        self.options.reconnect_ivl = Some(validate_reconnect_ivl(min_ms, max_ms)?);
+        Ok(())
+    }
+
+    fn wait_connected(&mut self, timeout_ms: i32) -> Result<bool, BridgeError> {
+        let res = self.resources.as_mut().ok_or(BridgeError::NoSocket)?;
+        res.wait_connected(timeout_ms)
+    }
 }
 
 // ===========================================================================
@@ -64,6 +64,14 @@ pub const TOPIC_TRADE_PREFIX: &str = "trade/";
 /// Prefix for sync protocol topics (format: "sync/{master_id}/{slave_id}")
 pub const TOPIC_SYNC_PREFIX: &str = "sync/";
 
+/// Prefix for latency probe topics (format: "ping/{account_id}")
+pub const TOPIC_PING_PREFIX: &str = "ping/";
+
+/// Prefix for subscription probe topics (format: "sub-ack/{account_id}/{seq}")
+/// The relay echoes an empty frame on a probe topic as soon as it sees the
+/// subscription, confirming every subscription made before the probe.
+pub const TOPIC_SUBSCRIBE_ACK_PREFIX: &str = "sub-ack/";
+
 // =============================================================================
 // Order Type Enum
 // =============================================================================
@@ -129,6 +137,14 @@ impl OrderType {
             OrderType::SellStop => OrderType::BuyStop,
         }
     }
+
+    /// Market order of the same direction (BuyLimit -> Buy, SellStop -> Sell, etc.)
+    pub fn to_market(&self) -> Self {
+        match self {
+            OrderType::Buy | OrderType::BuyLimit | OrderType::BuyStop => OrderType::Buy,
+            OrderType::Sell | OrderType::SellLimit | OrderType::SellStop => OrderType::Sell,
+        }
+    }
 }
 
 impl std::str::FromStr for OrderType {
@@ -205,6 +221,20 @@ pub fn build_sync_topic(master_id: &str, slave_id: &str) -> String {
     format!("{}{}/{}", TOPIC_SYNC_PREFIX, master_id, slave_id)
 }
 
+/// Build a ping topic for a specific account
+/// Returns format: "ping/{account_id}"
+#[inline]
+pub fn build_ping_topic(account_id: &str) -> String {
+    format!("{}{}", TOPIC_PING_PREFIX, account_id)
+}
+
+/// Build a subscription probe topic for a specific account
+/// Returns format: "sub-ack/{account_id}/{seq}"
+#[inline]
+pub fn build_subscribe_ack_topic(account_id: &str, seq: u64) -> String {
+    format!("{}{}/{}", TOPIC_SUBSCRIBE_ACK_PREFIX, account_id, seq)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -236,6 +266,11 @@ mod tests {
         assert_eq!(build_sync_topic("M", "S"), "sync/M/S");
     }
 
+    #[test]
+    fn test_ping_topic_generation() {
+        assert_eq!(build_ping_topic("12345"), "ping/12345");
+    }
+
     #[test]
     fn test_order_type_from_mql() {
         assert_eq!(OrderType::from_mql(0), Some(OrderType::Buy));
@@ -275,4 +310,14 @@ mod tests {
         assert_eq!(OrderType::BuyStop.reverse(), OrderType::SellStop);
         assert_eq!(OrderType::SellStop.reverse(), OrderType::BuyStop);
     }
+
+    #[test]
+    fn test_order_type_to_market() {
+        assert_eq!(OrderType::BuyLimit.to_market(), OrderType::Buy);
+        assert_eq!(OrderType::BuyStop.to_market(), OrderType::Buy);
+        assert_eq!(OrderType::SellLimit.to_market(), OrderType::Sell);
+        assert_eq!(OrderType::SellStop.to_market(), OrderType::Sell);
+        assert_eq!(OrderType::Buy.to_market(), OrderType::Buy);
+        assert_eq!(OrderType::Sell.to_market(), OrderType::Sell);
+    }
 }
@@ -0,0 +1,168 @@
+// mt-bridge/src/curve.rs
+//
+// CurveZMQ helpers shared by the DLL (client side) and relay-server (server side).
+// Keys are exchanged as Z85 text (40 characters) so they fit in INI/TOML files.
+// CURVE requires libzmq built with libsodium; check `is_supported()` before use.
+
+use crate::errors::BridgeError;
+use zmq::Socket;
+
+/// Length of a Z85-encoded Curve25519 key
+pub const CURVE_KEY_Z85_LEN: usize = 40;
+
+/// Whether the linked libzmq was built with CURVE support
+pub fn is_supported() -> bool {
+    zmq::has("curve") == Some(true)
+}
+
+fn ensure_supported() -> Result<(), BridgeError> {
+    if is_supported() {
+        Ok(())
+    } else {
+        Err(BridgeError::Init(
+            "libzmq was built without CURVE support (libsodium)".to_string(),
+        ))
+    }
+}
+
+/// A Curve25519 key pair in Z85 text form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveKeyPair {
+    pub public_key: String,
+    pub secret_key: String,
+}
+
+impl CurveKeyPair {
+    /// Generate a new random key pair
+    pub fn generate() -> Result<Self, BridgeError> {
+        ensure_supported()?;
+        let pair = zmq::CurveKeyPair::new()?;
+        Ok(Self {
+            public_key: encode_key(&pair.public_key)?,
+            secret_key: encode_key(&pair.secret_key)?,
+        })
+    }
+}
+
+/// Keys a client socket needs to talk to a CURVE-enabled relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveClientKeys {
+    /// Relay's long-term public key
+    pub server_public_key: String,
+    /// This client's key pair
+    pub client: CurveKeyPair,
+}
+
+impl CurveClientKeys {
+    /// Build client keys for a relay public key, generating an ephemeral client
+    /// key pair (the relay does not authenticate individual clients)
+    pub fn for_server(server_public_key: &str) -> Result<Self, BridgeError> {
+        let server_public_key = server_public_key.trim().to_string();
+        decode_key(&server_public_key)?;
+        Ok(Self {
+            server_public_key,
+            client: CurveKeyPair::generate()?,
+        })
+    }
+}
+
+/// Decode a Z85 key and check that it is a 32-byte Curve25519 key
+pub fn decode_key(key: &str) -> Result<Vec<u8>, BridgeError> {
+    let key = key.trim();
+    if key.len() != CURVE_KEY_Z85_LEN {
+        return Err(BridgeError::Init(format!(
+            "CURVE key must be {} Z85 characters, got {}",
+            CURVE_KEY_Z85_LEN,
+            key.len()
+        )));
+    }
+    zmq::z85_decode(key).map_err(|e| BridgeError::Init(format!("Invalid CURVE key: {}", e)))
+}
+
+fn encode_key(raw: &[u8]) -> Result<String, BridgeError> {
+    zmq::z85_encode(raw).map_err(|e| BridgeError::Init(format!("Failed to encode key: {}", e)))
+}
+
+/// Configure a bound socket (PULL/PUB) as a CURVE server. Must be called before bind.
+pub fn apply_server(socket: &Socket, secret_key: &str) -> Result<(), BridgeError> {
+    ensure_supported()?;
+    let secret = decode_key(secret_key)?;
+    socket.set_curve_server(true)?;
+    socket.set_curve_secretkey(&secret)?;
+    Ok(())
+}
+
+/// Configure a connecting socket (PUSH/SUB) as a CURVE client. Must be called before connect.
+pub fn apply_client(socket: &Socket, keys: &CurveClientKeys) -> Result<(), BridgeError> {
+    ensure_supported()?;
+    socket.set_curve_serverkey(&decode_key(&keys.server_public_key)?)?;
+    socket.set_curve_publickey(&decode_key(&keys.client.public_key)?)?;
+    socket.set_curve_secretkey(&decode_key(&keys.client.secret_key)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Z85 test vector from the ZeroMQ CURVE examples
+    const SAMPLE_KEY: &str = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7";
+
+    #[test]
+    fn test_decode_key() {
+        assert_eq!(decode_key(SAMPLE_KEY).unwrap().len(), 32);
+        assert_eq!(decode_key(&format!(" {}\n", SAMPLE_KEY)).unwrap().len(), 32);
+        assert!(decode_key("too-short").is_err());
+        assert!(CurveClientKeys::for_server("too-short").is_err());
+    }
+
+    #[test]
+    fn test_unsupported_build_fails_fast() {
+        if is_supported() {
+            return;
+        }
+        let ctx = zmq::Context::new();
+        let socket = ctx.socket(zmq::PULL).unwrap();
+        assert!(CurveKeyPair::generate().is_err());
+        assert!(apply_server(&socket, SAMPLE_KEY).is_err());
+        assert!(CurveClientKeys::for_server(SAMPLE_KEY).is_err());
+    }
+
+    #[test]
+    fn test_generate_keypair_produces_z85_keys() {
+        if !is_supported() {
+            return;
+        }
+        let pair = CurveKeyPair::generate().unwrap();
+        assert_eq!(pair.public_key.len(), CURVE_KEY_Z85_LEN);
+        assert_eq!(pair.secret_key.len(), CURVE_KEY_Z85_LEN);
+        assert_eq!(decode_key(&pair.public_key).unwrap().len(), 32);
+        assert_ne!(pair, CurveKeyPair::generate().unwrap());
+    }
+
+    #[test]
+    fn test_curve_round_trip() {
+        if !is_supported() {
+            return;
+        }
+        let ctx = zmq::Context::new();
+        let server_keys = CurveKeyPair::generate().unwrap();
+
+        let pull = ctx.socket(zmq::PULL).unwrap();
+        apply_server(&pull, &server_keys.secret_key).unwrap();
+        pull.bind("tcp://127.0.0.1:*").unwrap();
+        let endpoint = pull.get_last_endpoint().unwrap().unwrap();
+        pull.set_rcvtimeo(2000).unwrap();
+
+        let push = ctx.socket(zmq::PUSH).unwrap();
+        apply_client(
+            &push,
+            &CurveClientKeys::for_server(&server_keys.public_key).unwrap(),
+        )
+        .unwrap();
+        push.connect(&endpoint).unwrap();
+        push.send("hello", 0).unwrap();
+
+        assert_eq!(pull.recv_bytes(0).unwrap(), b"hello");
+    }
+}
@@ -7,10 +7,12 @@
 use crate::communication::{CommunicationStrategy, MasterStrategy, NoOpStrategy, SlaveStrategy};
 use crate::constants::{OrderType, TradeAction};
 use crate::errors::BridgeError;
+use crate::symbol_pattern::SymbolPatterns;
 use crate::types::{RequestConfigMessage, SlaveConfigMessage, TradeSignal};
 use chrono::{DateTime, Utc};
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 
 // Command types corresponding to MQL
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,6 +26,7 @@ pub enum EaCommandType {
     UpdateUi = 5,
     SendSnapshot = 6,
     ProcessSnapshot = 7,
+    CloseAll = 8,
 }
 
 // C-compatible Command structure
@@ -32,7 +35,7 @@ pub enum EaCommandType {
 #[derive(Debug, Clone)]
 pub struct EaCommand {
     pub command_type: i32,
-    pub algo_flags: i32, // [FIX] Replaced _pad1 with algo_flags (Bit 0: IsDelayed)
+    pub algo_flags: i32, // [FIX] Replaced _pad1 with algo_flags (Bit 0: IsDelayed, Bit 1: SlTpOffset)
 
     pub ticket: i64,
     pub symbol: [u8; 32], // 32 bytes (aligned to 8, safe)
@@ -107,6 +110,12 @@ pub struct EaContext {
     pub current_balance: f64,
     pub current_equity: f64,
     pub current_open_positions: i32,
+    // Latest margin state provided by MQL via ea_set_margin_info
+    pub current_margin: f64,
+    pub current_free_margin: f64,
+    pub current_margin_level: f64,
+    // Spread provided by MQL via ea_set_open_spread, consumed by the next Open signal
+    pub pending_spread_points: Option<u32>,
 
     // --- Cached Config ---
     pub last_master_config: Option<crate::types::MasterConfigMessage>,
@@ -120,6 +129,19 @@ pub struct EaContext {
     pub last_position_snapshot: Option<crate::types::PositionSnapshotMessage>,
     pub last_sync_request: Option<crate::types::SyncRequestMessage>,
 
+    /// Last TradeSignal sequence number sent (relay drops non-increasing values)
+    pub trade_seq: u64,
+    /// Last subscription probe number used by `wait_subscribed`
+    pub subscribe_ack_seq: u64,
+    /// Probe topic whose echo `wait_subscribed` is waiting for
+    pub pending_subscribe_ack: Option<String>,
+    /// Master open price of reversed positions, keyed by (master account, ticket),
+    /// so later Modify signals can mirror their SL/TP as well
+    pub reversed_open_prices: HashMap<(String, i64), f64>,
+    /// Positions whose SL/TP the relay sent as offsets, keyed like
+    /// `reversed_open_prices`, so later Modify signals are sent as offsets too
+    pub offset_entries: HashMap<(String, i64), OffsetEntry>,
+
     // --- Communication Layer ---
     pub strategy: Box<dyn CommunicationStrategy>,
 }
@@ -163,6 +185,10 @@ impl EaContext {
             current_balance: 0.0,
             current_equity: 0.0,
             current_open_positions: 0,
+            current_margin: 0.0,
+            current_free_margin: 0.0,
+            current_margin_level: 0.0,
+            pending_spread_points: None,
             last_master_config: None,
             pending_master_configs: VecDeque::new(),
             slave_configs: HashMap::new(),
@@ -171,6 +197,11 @@ impl EaContext {
             last_global_config: None,
             last_position_snapshot: None,
             last_sync_request: None,
+            trade_seq: 0,
+            subscribe_ack_seq: 0,
+            pending_subscribe_ack: None,
+            reversed_open_prices: HashMap::new(),
+            offset_entries: HashMap::new(),
         }
     }
 
@@ -185,10 +216,61 @@ impl EaContext {
         self.strategy.disconnect();
     }
 
+    /// Enable CURVE encryption using the relay's public key (call before connect)
+    pub fn set_curve_server_key(&mut self, server_public_key: &str) -> Result<(), BridgeError> {
+        let keys = crate::curve::CurveClientKeys::for_server(server_public_key)?;
+        self.strategy.set_curve_keys(keys)
+    }
+
+    /// Configure reconnect backoff (ms) for the next connect
+    pub fn set_reconnect_ivl(&mut self, min_ms: i32, max_ms: i32) -> Result<(), BridgeError> {
+        self.strategy.set_reconnect_ivl(min_ms, max_ms)
+    }
+
+    /// Wait until the PUSH and SUB sockets are connected to the relay
+    pub fn wait_connected(&mut self, timeout_ms: i32) -> Result<bool, BridgeError> {
+        self.strategy.wait_connected(timeout_ms)
+    }
+
     pub fn subscribe_trade(&mut self, master_id: &str) -> Result<(), BridgeError> {
         self.strategy.subscribe_trade(master_id)
     }
 
+    /// Wait until the relay has seen every subscription made so far
+    ///
+    /// Subscribes a one-off probe topic that the relay echoes back. ZMQ applies
+    /// a connection's subscriptions in order, so the echo proves the earlier
+    /// ones are active too. Frames received meanwhile are processed as usual.
+    /// Returns `Ok(false)` when `timeout_ms` elapsed first.
+    pub fn wait_subscribed(&mut self, timeout_ms: i32) -> Result<bool, BridgeError> {
+        let seq = self.subscribe_ack_seq + 1;
+        let probe = crate::constants::build_subscribe_ack_topic(&self.account_id, seq);
+        self.strategy.subscribe_config(&probe)?;
+        self.subscribe_ack_seq = seq;
+        self.pending_subscribe_ack = Some(probe.clone());
+
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms.max(0) as u64);
+        let mut buffer = [0u8; 4096];
+        let confirmed = loop {
+            if self.pending_subscribe_ack.is_none() {
+                break true;
+            }
+            match self.strategy.receive_config(&mut buffer) {
+                Ok(len) if len > 0 => self.process_incoming_message(&buffer[..len as usize]),
+                Ok(_) if Instant::now() >= deadline => break false,
+                Ok(_) => std::thread::sleep(Duration::from_millis(1)),
+                Err(e) => {
+                    self.pending_subscribe_ack = None;
+                    return Err(e);
+                }
+            }
+        };
+
+        self.pending_subscribe_ack = None;
+        let _ = self.strategy.unsubscribe_config(&probe);
+        Ok(confirmed)
+    }
+
     pub fn send_push(&mut self, data: &[u8]) -> Result<(), BridgeError> {
         self.strategy.send_push(data)
     }
@@ -232,6 +314,18 @@ impl EaContext {
         self.pending_commands.pop_front()
     }
 
+    /// Update the margin state reported by the next heartbeat
+    pub fn set_margin_info(&mut self, margin: f64, free_margin: f64, margin_level: f64) {
+        self.current_margin = margin;
+        self.current_free_margin = free_margin;
+        self.current_margin_level = margin_level;
+    }
+
+    /// Set the spread (points) attached to the next Open signal; negative values clear it
+    pub fn set_open_spread(&mut self, spread_points: i32) {
+        self.pending_spread_points = u32::try_from(spread_points).ok();
+    }
+
     /// Main Event Loop Tick (called by MQL OnTimer)
     /// Returns 1 if there is a pending command, 0 otherwise
     pub fn manager_tick(
@@ -297,6 +391,9 @@ impl EaContext {
             account_id: self.account_id.clone(),
             balance: self.current_balance,
             equity: self.current_equity,
+            margin: self.current_margin,
+            free_margin: self.current_free_margin,
+            margin_level: self.current_margin_level,
             open_positions: self.current_open_positions,
             timestamp: Utc::now().to_rfc3339(),
             version,
@@ -329,20 +426,31 @@ impl EaContext {
 
     fn process_incoming_message(&mut self, data: &[u8]) {
         // Parse Topic vs Payload (Zero allocation)
-        if let Some(space_pos) = data.iter().position(|&b| b == b' ') {
-            let topic_bytes = &data[..space_pos];
-            let payload = &data[space_pos + 1..];
-
+        if let Some((topic_bytes, payload)) = crate::protocol::split_frame(data) {
             // Check prefix directly on bytes
             if topic_bytes.starts_with(b"trade/") {
                 if self.ea_type == "Slave" {
-                    self.process_incoming_trade(payload);
+                    // Batched frames carry several signals in relay publish order
+                    match crate::batch::split_batch(payload) {
+                        Ok(signals) => {
+                            for signal in signals {
+                                self.process_incoming_trade(signal);
+                            }
+                        }
+                        Err(e) => eprintln!("Dropped malformed trade batch: {}", e),
+                    }
                 }
             } else if topic_bytes.starts_with(b"sync/") {
                 // We only convert to String if really needed (e.g. for further parsing)
                 // process_sync_message signature needs topic: &str, so we convert just here
                 let topic = String::from_utf8_lossy(topic_bytes);
                 self.process_sync_message(&topic, payload);
+            } else if topic_bytes.starts_with(b"ping/") {
+                self.process_ping(payload);
+            } else if topic_bytes.starts_with(b"sub-ack/") {
+                if self.pending_subscribe_ack.as_deref().map(str::as_bytes) == Some(topic_bytes) {
+                    self.pending_subscribe_ack = None;
+                }
             } else if topic_bytes.starts_with(b"config/") {
                 // Check for global config
                 if topic_bytes == b"config/global" {
@@ -355,6 +463,15 @@ impl EaContext {
     }
 
     fn process_config_message(&mut self, payload: &[u8]) {
+        if self.ea_type == "Slave" {
+            if let Ok(close_all) = rmp_serde::from_slice::<crate::types::CloseAllMessage>(payload) {
+                if close_all.message_type == "CloseAll" {
+                    self.process_close_all(close_all);
+                    return;
+                }
+            }
+        }
+
         // Parse and store config
         if self.ea_type == "Master" {
             if let Ok(config) = rmp_serde::from_slice::<crate::types::MasterConfigMessage>(payload)
@@ -400,6 +517,21 @@ impl EaContext {
         self.enqueue_command(cmd);
     }
 
+    /// Queue a CLOSE_ALL command; the symbol field is empty when all symbols are closed
+    fn process_close_all(&mut self, msg: crate::types::CloseAllMessage) {
+        if msg.slave_account != self.account_id {
+            return;
+        }
+        let mut cmd = EaCommand {
+            command_type: EaCommandType::CloseAll as i32,
+            ..Default::default()
+        };
+        if let Some(symbol) = &msg.symbol {
+            copy_string_to_array(symbol, &mut cmd.symbol);
+        }
+        self.enqueue_command(cmd);
+    }
+
     fn process_sync_message(&mut self, _topic: &str, payload: &[u8]) {
         if self.ea_type == "Master" {
             if let Ok(req) = rmp_serde::from_slice::<crate::types::SyncRequestMessage>(payload) {
@@ -427,6 +559,24 @@ impl EaContext {
         }
     }
 
+    /// Echo a latency probe back to the relay (server_timestamp is preserved)
+    fn process_ping(&mut self, payload: &[u8]) {
+        if let Ok(mut ping) = rmp_serde::from_slice::<crate::types::PingMessage>(payload) {
+            if ping.account_id != self.account_id {
+                return;
+            }
+            ping.ea_type = Some(self.ea_type.clone());
+            match rmp_serde::encode::to_vec_named(&ping) {
+                Ok(data) => {
+                    if let Err(e) = self.strategy.send_push(&data) {
+                        eprintln!("Failed to echo ping: {}", e);
+                    }
+                }
+                Err(e) => eprintln!("Failed to encode ping echo: {}", e),
+            }
+        }
+    }
+
     fn process_global_config(&mut self, payload: &[u8]) {
         if let Ok(config) = rmp_serde::from_slice::<crate::types::GlobalConfigMessage>(payload) {
             self.last_global_config = Some(config);
@@ -469,18 +619,17 @@ impl EaContext {
                     return;
                 }
 
-                // Check allowed symbols (if list is not empty)
+                // Check allowed symbols (if list is not empty; `*`/`?` entries are globs)
+                let symbol = signal.symbol.as_deref().unwrap_or("");
                 if let Some(allowed) = &config.filters.allowed_symbols {
-                    if !allowed.is_empty()
-                        && !allowed.contains(signal.symbol.as_ref().unwrap_or(&"".to_string()))
-                    {
+                    if !allowed.is_empty() && !SymbolPatterns::compile(allowed).matches(symbol) {
                         return; // Symbol not allowed
                     }
                 }
 
                 // Check blocked symbols
                 if let Some(blocked) = &config.filters.blocked_symbols {
-                    if blocked.contains(signal.symbol.as_ref().unwrap_or(&"".to_string())) {
+                    if SymbolPatterns::compile(blocked).matches(symbol) {
                         return; // Symbol blocked
                     }
                 }
@@ -550,12 +699,9 @@ impl EaContext {
             }
 
             // Order Type Reversal
+            let reversed = config.should_reverse(signal.symbol.as_deref());
             if let Some(ot) = signal.order_type {
-                let final_ot = if config.reverse_trade {
-                    ot.reverse()
-                } else {
-                    ot
-                };
+                let final_ot = if reversed { ot.reverse() } else { ot };
                 cmd.order_type = i32::from(final_ot);
             }
 
@@ -564,28 +710,67 @@ impl EaContext {
             let final_lots = transform_lot_size(raw_lots, config, self.current_equity);
             cmd.volume = final_lots;
 
-            // Price, SL, TP - passed as is?
-            // Ideally SL/TP distance should be respected if reversing?
-            // For now, simpler logic: pass as is (Relay server might handle reverse logic for SL/TP?)
-            // If we reverse "Buy" to "Sell", we must swap SL/TP relative to price?
-            // Actually, if we reverse, we probably shouldn't blindly copy absolute SL/TP prices.
-            // But implementing full reverse SL/TP logic here is complex.
-            // Assuming "Simple Reverse" for now or that Master/Relay handles it?
-            // In MQL implementation: `ReverseOrderType` only flipped the enum string.
-            // It did NOT seem to recalculate SL/TP prices in the snippet provided.
-            // Wait, `SlaveTrade.mqh` logic:
-            // `transformed_order_type = ReverseOrderType(...)`
-            // `ExecuteOpenTrade` receives `cmd.price, cmd.sl, cmd.tp`.
-            // If we reverse Buy to Sell, Open Price is Bid instead of Ask (handled by market execution),
-            // but SL/TP levels are absolute prices.
-            // If Buy @ 1.10, SL 1.09. If Reversed to Sell @ 1.10, SL 1.09 is PROFIT (TP).
-            // So MQL implementation was seemingly incomplete or relied on `ExecuteOpenTrade` to fix it?
-            // Actually `ExecuteOpenTrade` in `Trade.mqh` (common) likely handles minimal execution.
-            // Let's assume for this task we copy MQL logic: Just reverse the enum.
-
             cmd.price = signal.open_price.unwrap_or(0.0);
             cmd.sl = signal.stop_loss.unwrap_or(0.0);
             cmd.tp = signal.take_profit.unwrap_or(0.0);
+
+            // SL/TP sent as offsets are added by the EA to the Slave's own entry
+            // price (Bit 1). Master Modify signals carry absolute levels and no open
+            // price, so they are converted with the open price kept from the Open.
+            let key = (signal.source_account.clone(), signal.ticket);
+            let offset_entry = match signal.action {
+                TradeAction::Open if signal.sl_tp_offset => {
+                    let entry = OffsetEntry {
+                        open_price: signal.open_price.unwrap_or(0.0),
+                        reversed,
+                    };
+                    self.offset_entries.insert(key.clone(), entry);
+                    Some(entry)
+                }
+                TradeAction::Modify => self.offset_entries.get(&key).copied().or_else(|| {
+                    signal.sl_tp_offset.then_some(OffsetEntry {
+                        open_price: 0.0,
+                        reversed: false,
+                    })
+                }),
+                TradeAction::Close if signal.close_ratio.is_none() => {
+                    self.offset_entries.remove(&key);
+                    None
+                }
+                _ => None,
+            };
+            if let Some(entry) = offset_entry {
+                if !signal.sl_tp_offset {
+                    (cmd.sl, cmd.tp) = (entry.offset(cmd.sl), entry.offset(cmd.tp));
+                }
+                // A reversed position keeps the distances on the opposite side
+                if entry.reversed {
+                    (cmd.sl, cmd.tp) = (-cmd.sl, -cmd.tp);
+                }
+                cmd.algo_flags |= 2; // Bit 1: SlTpOffset
+            } else {
+                // SL/TP are absolute prices: on a reversed position they are mirrored
+                // around the Master's open price so the SL/TP distances are preserved.
+                // Close/Modify signals carry no symbol, so reversal is tracked per ticket.
+                match signal.action {
+                    TradeAction::Open if reversed => {
+                        if let Some(open_price) = signal.open_price.filter(|p| *p > 0.0) {
+                            self.reversed_open_prices.insert(key, open_price);
+                            (cmd.sl, cmd.tp) = mirror_sl_tp(open_price, cmd.sl, cmd.tp);
+                        }
+                    }
+                    TradeAction::Modify => {
+                        if let Some(&open_price) = self.reversed_open_prices.get(&key) {
+                            (cmd.sl, cmd.tp) = mirror_sl_tp(open_price, cmd.sl, cmd.tp);
+                        }
+                    }
+                    TradeAction::Close if signal.close_ratio.is_none() => {
+                        self.reversed_open_prices.remove(&key);
+                    }
+                    _ => {}
+                }
+            }
+
             cmd.magic = signal.magic_number.unwrap_or(0);
             cmd.close_ratio = signal.close_ratio.unwrap_or(0.0);
 
@@ -604,6 +789,12 @@ impl EaContext {
         }
     }
 
+    /// Advance the trade signal sequence (wraps to 1; 0 means "not numbered")
+    fn next_trade_seq(&mut self) -> u64 {
+        self.trade_seq = self.trade_seq.wrapping_add(1).max(1);
+        self.trade_seq
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn send_open_signal(
         &mut self,
@@ -631,6 +822,9 @@ impl EaContext {
             timestamp: chrono::Utc::now(),
             source_account: self.account_id.clone(),
             close_ratio: None,
+            seq: self.next_trade_seq(),
+            spread_points: self.pending_spread_points.take(),
+            sl_tp_offset: false,
         };
 
         let data = rmp_serde::encode::to_vec_named(&msg)?;
@@ -663,6 +857,9 @@ impl EaContext {
             } else {
                 Some(close_ratio)
             },
+            seq: self.next_trade_seq(),
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let data = rmp_serde::encode::to_vec_named(&msg)?;
@@ -686,6 +883,9 @@ impl EaContext {
             timestamp: chrono::Utc::now(),
             source_account: self.account_id.clone(),
             close_ratio: None,
+            seq: self.next_trade_seq(),
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let data = rmp_serde::encode::to_vec_named(&msg)?;
@@ -720,6 +920,7 @@ impl EaContext {
             source_account: self.account_id.clone(),
             positions,
             timestamp: chrono::Utc::now().to_rfc3339(),
+            ea_type: Some(self.ea_type.clone()),
         };
 
         let data = rmp_serde::encode::to_vec_named(&msg)?;
@@ -799,6 +1000,10 @@ fn transform_lot_size(lots: f64, config: &SlaveConfigMessage, slave_equity: f64)
                 new_lots = lots * mult;
             }
         }
+        // Relay-server already calculated the final lots; use them as-is
+        LotCalculationMode::EquityRatio
+        | LotCalculationMode::FixedLot
+        | LotCalculationMode::RiskPercent => {}
     }
 
     // Normalize? Rust doesn't have SymbolInfoDouble for MIN/MAX/STEP.
@@ -820,6 +1025,43 @@ fn transform_lot_size(lots: f64, config: &SlaveConfigMessage, slave_equity: f64)
     new_lots
 }
 
+/// Master open price of a position whose SL/TP are sent to the EA as offsets
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetEntry {
+    pub open_price: f64,
+    pub reversed: bool,
+}
+
+impl OffsetEntry {
+    /// Offset of an absolute Master level from the open price (0.0 = not set)
+    fn offset(&self, level: f64) -> f64 {
+        if level <= 0.0 || self.open_price <= 0.0 {
+            0.0
+        } else {
+            level - self.open_price
+        }
+    }
+}
+
+/// Mirror SL/TP levels around `open_price` for a position whose direction was
+/// reversed (0.0 = not set). A Buy SL below the open becomes a Sell SL the same
+/// distance above it, and likewise for TP. Levels that would mirror to a
+/// non-positive price are dropped.
+fn mirror_sl_tp(open_price: f64, sl: f64, tp: f64) -> (f64, f64) {
+    let mirror = |level: f64| {
+        if level <= 0.0 {
+            return 0.0;
+        }
+        let mirrored = 2.0 * open_price - level;
+        if mirrored > 0.0 {
+            mirrored
+        } else {
+            0.0
+        }
+    };
+    (mirror(sl), mirror(tp))
+}
+
 fn copy_string_to_array<const N: usize>(s: &str, arr: &mut [u8; N]) {
     // Max bytes we can safely store (leaving room for null terminator if needed,
     // though this logic guarantees 0 at N-1 if we don't fill it?
@@ -978,6 +1220,44 @@ mod tests {
         assert_eq!(data.len(), 1, "Should have sent one message");
     }
 
+    #[test]
+    fn test_heartbeat_includes_margin_info() {
+        let mut ctx = create_test_context("Slave");
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: sent_data.clone(),
+            ..Default::default()
+        });
+
+        ctx.set_margin_info(2000.0, 8000.0, 500.0);
+        ctx.send_heartbeat(true).expect("Failed to send heartbeat");
+
+        let data = sent_data.lock().unwrap();
+        let msg: crate::types::HeartbeatMessage = rmp_serde::from_slice(&data[0]).unwrap();
+        assert_eq!(msg.margin, 2000.0);
+        assert_eq!(msg.free_margin, 8000.0);
+        assert_eq!(msg.margin_level, 500.0);
+    }
+
+    #[test]
+    fn test_position_snapshot_carries_sender_ea_type() {
+        let mut ctx = create_test_context("Slave");
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: sent_data.clone(),
+            ..Default::default()
+        });
+
+        // Empty snapshots are sent too (the relay needs to see "no positions")
+        ctx.send_position_snapshot(Vec::new())
+            .expect("Failed to send position snapshot");
+
+        let data = sent_data.lock().unwrap();
+        let msg: crate::types::PositionSnapshotMessage = rmp_serde::from_slice(&data[0]).unwrap();
+        assert_eq!(msg.ea_type.as_deref(), Some("Slave"));
+        assert!(msg.positions.is_empty());
+    }
+
     #[test]
     fn test_send_open_signal() {
         let mut ctx = create_test_context("Master");
@@ -1020,7 +1300,7 @@ mod tests {
         };
         let mut config_bytes = rmp_serde::to_vec_named(&config).unwrap();
         // Prepend topic if process_incoming_message expects it?
-        // logic: `if let Some(space_pos) = data.iter().position(|&b| b == b' ')`
+        // logic: `crate::protocol::split_frame(data)`
         // We need to construct "config/global " + msgpack
         let mut payload = b"config/slave ".to_vec();
         payload.append(&mut config_bytes);
@@ -1152,6 +1432,7 @@ mod tests {
             lot_calculation_mode: crate::types::LotCalculationMode::Multiplier,
             lot_multiplier: Some(1.0),
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![],
@@ -1160,6 +1441,7 @@ mod tests {
             source_lot_min: None,
             source_lot_max: None,
             master_equity: None,
+            fixed_lot: None,
             sync_mode: Default::default(),
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -1219,6 +1501,52 @@ mod tests {
         assert_eq!(cmd.ticket, 1001);
     }
 
+    #[test]
+    fn test_batched_trade_frame_yields_every_signal() {
+        let mut ctx = create_test_context("Slave");
+        let incoming = Arc::new(Mutex::new(VecDeque::new()));
+        let master_acc = "master1";
+
+        let config = create_latency_test_config(master_acc, 1000, false);
+        let mut payload_conf = b"config/slave ".to_vec();
+        payload_conf.extend(rmp_serde::to_vec_named(&config).unwrap());
+        incoming.lock().unwrap().push_back(payload_conf);
+
+        let payloads: Vec<Vec<u8>> = (2001..=2003)
+            .map(|ticket| {
+                rmp_serde::to_vec_named(&crate::types::TradeSignal {
+                    action: crate::constants::TradeAction::Open,
+                    ticket,
+                    symbol: Some("EURUSD".to_string()),
+                    lots: Some(0.1),
+                    source_account: master_acc.to_string(),
+                    timestamp: Utc::now(),
+                    ..Default::default()
+                })
+                .unwrap()
+            })
+            .collect();
+        let mut payload_trade = format!("trade/{} ", master_acc).as_bytes().to_vec();
+        payload_trade.extend(crate::batch::encode_batch(&payloads));
+        incoming.lock().unwrap().push_back(payload_trade);
+
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: Arc::new(Mutex::new(Vec::new())),
+            incoming_data: incoming.clone(),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        ctx.get_next_command(); // Clear UI command
+
+        let pending = ctx.manager_tick(1000.0, 1000.0, 0, true);
+        assert_eq!(pending, 1);
+        let tickets: Vec<i64> = std::iter::from_fn(|| ctx.get_next_command())
+            .map(|cmd| cmd.ticket)
+            .collect();
+        assert_eq!(tickets, vec![2001, 2002, 2003]);
+    }
+
     #[test]
     fn test_latency_check_expired_signal_drop() {
         let mut ctx = create_test_context("Slave");
@@ -1313,4 +1641,383 @@ mod tests {
         let cmd = ctx.get_next_command().unwrap();
         assert_eq!(cmd.ticket, 1003);
     }
+
+    #[test]
+    fn test_reverse_symbols_limits_reversal() {
+        let mut ctx = create_test_context("Slave");
+        let incoming = Arc::new(Mutex::new(VecDeque::new()));
+        let master_acc = "master1";
+
+        // 1. Config: reverse only EURUSD
+        let mut config = create_latency_test_config(master_acc, 5000, false);
+        config.reverse_trade = true;
+        config.reverse_symbols = Some(vec!["EURUSD".to_string()]);
+        let mut config_bytes = rmp_serde::to_vec_named(&config).unwrap();
+        let mut payload_conf = b"config/slave ".to_vec();
+        payload_conf.append(&mut config_bytes);
+        incoming.lock().unwrap().push_back(payload_conf);
+
+        // 2. Buy signals for a listed and an unlisted symbol
+        for (ticket, symbol) in [(2001, "EURUSD"), (2002, "GBPUSD")] {
+            let signal = crate::types::TradeSignal {
+                action: crate::constants::TradeAction::Open,
+                ticket,
+                symbol: Some(symbol.to_string()),
+                order_type: Some(crate::constants::OrderType::Buy),
+                lots: Some(0.1),
+                open_price: Some(1.1000),
+                source_account: master_acc.to_string(),
+                timestamp: Utc::now(),
+                ..Default::default()
+            };
+            let mut signal_bytes = rmp_serde::to_vec_named(&signal).unwrap();
+            let mut payload_trade = format!("trade/{} ", master_acc).as_bytes().to_vec();
+            payload_trade.append(&mut signal_bytes);
+            incoming.lock().unwrap().push_back(payload_trade);
+        }
+
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: Arc::new(Mutex::new(Vec::new())),
+            incoming_data: incoming.clone(),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        // Tick 1: Config
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        ctx.get_next_command();
+
+        // Tick 2/3: Trades
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        let eurusd = ctx.get_next_command().unwrap();
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        let gbpusd = ctx.get_next_command().unwrap();
+
+        assert_eq!(eurusd.ticket, 2001);
+        assert_eq!(
+            eurusd.order_type,
+            i32::from(crate::constants::OrderType::Sell)
+        );
+        assert_eq!(gbpusd.ticket, 2002);
+        assert_eq!(
+            gbpusd.order_type,
+            i32::from(crate::constants::OrderType::Buy)
+        );
+    }
+
+    #[test]
+    fn test_reversed_open_mirrors_sl_tp() {
+        let mut ctx = create_test_context("Slave");
+        let incoming = Arc::new(Mutex::new(VecDeque::new()));
+        let master_acc = "master1";
+
+        let mut config = create_latency_test_config(master_acc, 5000, false);
+        config.reverse_trade = true;
+        let mut payload_conf = b"config/slave ".to_vec();
+        payload_conf.extend(rmp_serde::to_vec_named(&config).unwrap());
+        incoming.lock().unwrap().push_back(payload_conf);
+
+        // Buy @ 1.1000 with SL 20 pips below and TP 40 pips above, then a Buy
+        // without TP, then a Modify of the first position
+        let signals = [
+            (
+                TradeAction::Open,
+                3001,
+                Some(1.1000),
+                Some(1.0980),
+                Some(1.1040),
+            ),
+            (TradeAction::Open, 3002, Some(1.1000), Some(1.0950), None),
+            (TradeAction::Modify, 3001, None, Some(1.0990), Some(1.1050)),
+        ];
+        for (action, ticket, open_price, stop_loss, take_profit) in signals {
+            let signal = crate::types::TradeSignal {
+                action,
+                ticket,
+                symbol: Some("EURUSD".to_string()),
+                order_type: Some(OrderType::Buy),
+                lots: Some(0.1),
+                open_price,
+                stop_loss,
+                take_profit,
+                source_account: master_acc.to_string(),
+                timestamp: Utc::now(),
+                ..Default::default()
+            };
+            let mut payload_trade = format!("trade/{} ", master_acc).as_bytes().to_vec();
+            payload_trade.extend(rmp_serde::to_vec_named(&signal).unwrap());
+            incoming.lock().unwrap().push_back(payload_trade);
+        }
+
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: Arc::new(Mutex::new(Vec::new())),
+            incoming_data: incoming.clone(),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        ctx.get_next_command(); // Clear UI command
+
+        let mut next = || {
+            ctx.manager_tick(1000.0, 1000.0, 0, true);
+            ctx.get_next_command().unwrap()
+        };
+        let approx = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        // Sell with SL 20 pips above and TP 40 pips below the open price
+        let open = next();
+        assert_eq!(open.order_type, i32::from(OrderType::Sell));
+        assert!(approx(open.sl, 1.1020), "sl = {}", open.sl);
+        assert!(approx(open.tp, 1.0960), "tp = {}", open.tp);
+
+        // A missing TP stays unset
+        let no_tp = next();
+        assert!(approx(no_tp.sl, 1.1050), "sl = {}", no_tp.sl);
+        assert_eq!(no_tp.tp, 0.0);
+
+        // Modify signals carry no open price; the Open's price is reused
+        let modify = next();
+        assert_eq!(modify.ticket, 3001);
+        assert!(approx(modify.sl, 1.1010), "sl = {}", modify.sl);
+        assert!(approx(modify.tp, 1.0950), "tp = {}", modify.tp);
+    }
+
+    #[test]
+    fn test_offset_sl_tp_flagged_for_ea() {
+        let mut ctx = create_test_context("Slave");
+        let incoming = Arc::new(Mutex::new(VecDeque::new()));
+        let master_acc = "master1";
+
+        let mut config = create_latency_test_config(master_acc, 5000, false);
+        config.reverse_trade = true;
+        config.reverse_symbols = Some(vec!["EURUSD".to_string()]);
+        let mut payload_conf = b"config/slave ".to_vec();
+        payload_conf.extend(rmp_serde::to_vec_named(&config).unwrap());
+        incoming.lock().unwrap().push_back(payload_conf);
+
+        // Offset Opens (SL 20 pips below, TP 40 pips above) on a reversed and a
+        // plain symbol, then absolute Master Modifys, a Close and a late Modify
+        let signals = [
+            (TradeAction::Open, 4001, "EURUSD", true, -0.0020, 0.0040),
+            (TradeAction::Open, 4002, "GBPUSD", true, -0.0020, 0.0040),
+            (TradeAction::Modify, 4001, "", false, 1.0990, 1.1050),
+            (TradeAction::Modify, 4002, "", false, 1.0990, 0.0),
+            (TradeAction::Close, 4002, "", false, 0.0, 0.0),
+            (TradeAction::Modify, 4002, "", false, 1.0990, 1.1050),
+        ];
+        for (action, ticket, symbol, sl_tp_offset, sl, tp) in signals {
+            let is_open = action == TradeAction::Open;
+            let signal = crate::types::TradeSignal {
+                action,
+                ticket,
+                symbol: (!symbol.is_empty()).then(|| symbol.to_string()),
+                order_type: is_open.then_some(OrderType::Buy),
+                lots: Some(0.1),
+                open_price: is_open.then_some(1.1000),
+                stop_loss: (sl != 0.0).then_some(sl),
+                take_profit: (tp != 0.0).then_some(tp),
+                source_account: master_acc.to_string(),
+                timestamp: Utc::now(),
+                sl_tp_offset,
+                ..Default::default()
+            };
+            let mut payload_trade = format!("trade/{} ", master_acc).as_bytes().to_vec();
+            payload_trade.extend(rmp_serde::to_vec_named(&signal).unwrap());
+            incoming.lock().unwrap().push_back(payload_trade);
+        }
+
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: Arc::new(Mutex::new(Vec::new())),
+            incoming_data: incoming.clone(),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        ctx.manager_tick(1000.0, 1000.0, 0, true);
+        ctx.get_next_command(); // Clear UI command
+
+        let mut next = || {
+            ctx.manager_tick(1000.0, 1000.0, 0, true);
+            ctx.get_next_command().unwrap()
+        };
+        let approx = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let offset = |cmd: &EaCommand| cmd.algo_flags & 2 != 0;
+
+        // Reversed: the Sell keeps the distances on the opposite side
+        let reversed = next();
+        assert!(offset(&reversed));
+        assert!(approx(reversed.sl, 0.0020), "sl = {}", reversed.sl);
+        assert!(approx(reversed.tp, -0.0040), "tp = {}", reversed.tp);
+
+        let plain = next();
+        assert!(offset(&plain));
+        assert!(approx(plain.sl, -0.0020), "sl = {}", plain.sl);
+        assert!(approx(plain.tp, 0.0040), "tp = {}", plain.tp);
+
+        // Absolute Modify levels become offsets from the Master open price
+        let modify = next();
+        assert!(offset(&modify));
+        assert!(approx(modify.sl, 0.0010), "sl = {}", modify.sl);
+        assert!(approx(modify.tp, -0.0050), "tp = {}", modify.tp);
+
+        let modify = next();
+        assert!(offset(&modify));
+        assert!(approx(modify.sl, -0.0010), "sl = {}", modify.sl);
+        assert_eq!(modify.tp, 0.0);
+
+        // After the full close the ticket is forgotten
+        assert!(!offset(&next()));
+        let late = next();
+        assert!(!offset(&late));
+        assert!(approx(late.sl, 1.0990), "sl = {}", late.sl);
+    }
+
+    #[test]
+    fn test_mirror_sl_tp_handles_unset_levels() {
+        assert_eq!(mirror_sl_tp(1.1, 0.0, 0.0), (0.0, 0.0));
+        let (sl, tp) = mirror_sl_tp(100.0, 0.0, 150.0);
+        assert_eq!((sl, tp), (0.0, 50.0));
+        // A level that would mirror below zero is dropped
+        assert_eq!(mirror_sl_tp(100.0, 90.0, 250.0), (110.0, 0.0));
+    }
+
+    #[test]
+    fn test_ping_is_echoed_with_ea_type() {
+        let mut ctx = create_test_context("Slave");
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: sent_data.clone(),
+            incoming_data: Arc::new(Mutex::new(VecDeque::new())),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        let ping = crate::types::PingMessage {
+            message_type: "Ping".to_string(),
+            account_id: "test_acc".to_string(),
+            ea_type: None,
+            server_timestamp: 1_700_000_000_000,
+        };
+        let mut payload = b"ping/test_acc ".to_vec();
+        payload.extend(rmp_serde::to_vec_named(&ping).unwrap());
+        ctx.process_incoming_message(&payload);
+
+        // Probes addressed to another account are ignored
+        let mut other = b"ping/other_acc ".to_vec();
+        other.extend(
+            rmp_serde::to_vec_named(&crate::types::PingMessage {
+                account_id: "other_acc".to_string(),
+                ..ping
+            })
+            .unwrap(),
+        );
+        ctx.process_incoming_message(&other);
+
+        let data = sent_data.lock().unwrap();
+        assert_eq!(data.len(), 1);
+        let echo: crate::types::PingMessage = rmp_serde::from_slice(&data[0]).unwrap();
+        assert_eq!(echo.message_type, "Ping");
+        assert_eq!(echo.account_id, "test_acc");
+        assert_eq!(echo.ea_type.as_deref(), Some("Slave"));
+        assert_eq!(echo.server_timestamp, 1_700_000_000_000);
+        assert!(ctx.pending_commands.is_empty());
+    }
+
+    #[test]
+    fn test_close_all_queues_command_without_touching_configs() {
+        let mut ctx = create_test_context("Slave");
+        let close_all = crate::types::CloseAllMessage {
+            message_type: "CloseAll".to_string(),
+            slave_account: "test_acc".to_string(),
+            symbol: Some("EURUSD".to_string()),
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+        };
+        let mut payload = b"config/test_acc ".to_vec();
+        payload.extend(rmp_serde::to_vec_named(&close_all).unwrap());
+        ctx.process_incoming_message(&payload);
+
+        assert_eq!(ctx.pending_commands.len(), 1);
+        let cmd = ctx.pending_commands.pop_front().unwrap();
+        assert_eq!(cmd.command_type, EaCommandType::CloseAll as i32);
+        assert_eq!(&cmd.symbol[..7], b"EURUSD\0");
+        assert!(ctx.pending_slave_configs.is_empty());
+        assert!(ctx.slave_configs.is_empty());
+    }
+
+    #[test]
+    fn test_set_curve_server_key() {
+        let mut ctx = create_test_context("Slave");
+        assert!(ctx.set_curve_server_key("not-a-key").is_err());
+
+        if !crate::curve::is_supported() {
+            return;
+        }
+        let server = crate::curve::CurveKeyPair::generate().unwrap();
+        assert!(ctx.set_curve_server_key(&server.public_key).is_ok());
+
+        // Strategies without sockets cannot be encrypted
+        let mut noop = create_test_context("Unknown");
+        assert!(matches!(
+            noop.set_curve_server_key(&server.public_key),
+            Err(BridgeError::NotSupported)
+        ));
+    }
+
+    #[test]
+    fn test_trade_signals_carry_increasing_seq() {
+        let mut ctx = create_test_context("Master");
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: sent_data.clone(),
+            incoming_data: Arc::new(Mutex::new(VecDeque::new())),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        ctx.send_open_signal(1, "EURUSD", OrderType::Buy, 0.1, 1.1, 0.0, 0.0, 0, "")
+            .unwrap();
+        ctx.send_modify_signal(1, 1.0, 1.2).unwrap();
+        ctx.send_close_signal(1, 0.1, 1.0).unwrap();
+
+        let seqs: Vec<u64> = sent_data
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|d| rmp_serde::from_slice::<TradeSignal>(d).unwrap().seq)
+            .collect();
+        assert_eq!(seqs, vec![1, 2, 3]);
+
+        // Wraps past u64::MAX to 1 (0 is reserved for "not numbered")
+        ctx.trade_seq = u64::MAX;
+        assert_eq!(ctx.next_trade_seq(), 1);
+    }
+
+    #[test]
+    fn test_open_signal_carries_spread_once() {
+        let mut ctx = create_test_context("Master");
+        let sent_data = Arc::new(Mutex::new(Vec::new()));
+        ctx.strategy = Box::new(MockStrategy {
+            sent_data: sent_data.clone(),
+            incoming_data: Arc::new(Mutex::new(VecDeque::new())),
+            next_error: Arc::new(Mutex::new(None)),
+        });
+
+        ctx.set_open_spread(12);
+        ctx.send_open_signal(1, "EURUSD", OrderType::Buy, 0.1, 1.1, 0.0, 0.0, 0, "")
+            .unwrap();
+        // Not set again before the next Open: no stale spread is attached
+        ctx.send_open_signal(2, "EURUSD", OrderType::Buy, 0.1, 1.1, 0.0, 0.0, 0, "")
+            .unwrap();
+        ctx.set_open_spread(-1);
+        ctx.send_open_signal(3, "EURUSD", OrderType::Buy, 0.1, 1.1, 0.0, 0.0, 0, "")
+            .unwrap();
+
+        let spreads: Vec<Option<u32>> = sent_data
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                rmp_serde::from_slice::<TradeSignal>(d)
+                    .unwrap()
+                    .spread_points
+            })
+            .collect();
+        assert_eq!(spreads, vec![Some(12), None, None]);
+    }
 }
@@ -29,6 +29,7 @@ pub(crate) unsafe fn convert_c_position_to_rust(
     let take_profit = c_pos.take_profit;
     let magic_number = c_pos.magic_number;
     let order_type = c_pos.order_type;
+    let profit = c_pos.profit;
 
     let symbol = c_byte_array_to_string(c_pos.symbol.as_ptr(), 32);
     let comment_str = c_byte_array_to_string(c_pos.comment.as_ptr(), 64);
@@ -80,6 +81,7 @@ pub(crate) unsafe fn convert_c_position_to_rust(
         } else {
             Some(comment_str)
         },
+        profit,
     }
 }
 
@@ -164,6 +166,9 @@ pub unsafe extern "C" fn ea_context_get_slave_config(
         dest.lot_calculation_mode = match src.lot_calculation_mode {
             LotCalculationMode::Multiplier => 0,
             LotCalculationMode::MarginRatio => 1,
+            LotCalculationMode::EquityRatio => 2,
+            LotCalculationMode::FixedLot => 3,
+            LotCalculationMode::RiskPercent => 4,
         };
         dest.lot_multiplier = src.lot_multiplier.unwrap_or(0.0);
         dest.reverse_trade = if src.reverse_trade { 1 } else { 0 };
@@ -181,6 +186,7 @@ pub unsafe extern "C" fn ea_context_get_slave_config(
         dest.source_lot_min = src.source_lot_min.unwrap_or(0.0);
         dest.source_lot_max = src.source_lot_max.unwrap_or(0.0);
         dest.master_equity = src.master_equity.unwrap_or(0.0);
+        dest.fixed_lot = src.fixed_lot.unwrap_or(0.0);
 
         dest.sync_mode = match src.sync_mode {
             SyncMode::Skip => 0,
@@ -321,6 +327,7 @@ pub unsafe extern "C" fn ea_context_get_position_snapshot(
             slice[i].magic_number = src.magic_number.unwrap_or(0);
 
             copy_string_to_fixed_array(src.comment.as_deref().unwrap_or(""), &mut slice[i].comment);
+            slice[i].profit = src.profit;
         }
         count as i32
     } else {
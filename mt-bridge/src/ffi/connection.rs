@@ -1,4 +1,5 @@
 use crate::ea_context::EaContext;
+use crate::ffi::error_codes::{fail, send_result, FfiErrorCode};
 use crate::ffi::helpers::utf16_to_string;
 
 /// Connect to Relay Server (Initialize ZMQ sockets and subscribe context-specifically)
@@ -36,30 +37,137 @@ pub unsafe extern "C" fn ea_connect(
     }
 }
 
-/// Send raw data via PUSH socket
+/// Enable CURVE encryption for the next ea_connect
+///
+/// An ephemeral client key pair is generated; only the relay's public key is required.
 ///
 /// # Safety
 /// - context: Valid EaContext pointer
-/// - data: Valid buffer
+/// - server_public_key: Valid UTF-16 string (40-character Z85 key)
 #[no_mangle]
-pub unsafe extern "C" fn ea_send_push(context: *mut EaContext, data: *const u8, len: i32) -> i32 {
+pub unsafe extern "C" fn ea_set_curve_server_key(
+    context: *mut EaContext,
+    server_public_key: *const u16,
+) -> i32 {
     let ctx = match context.as_mut() {
         Some(c) => c,
         None => return 0,
     };
-    if data.is_null() || len <= 0 {
-        return 0;
+    let key = match utf16_to_string(server_public_key) {
+        Some(s) => s,
+        None => return 0,
+    };
+
+    match ctx.set_curve_server_key(&key) {
+        Ok(_) => 1,
+        Err(e) => {
+            eprintln!("ea_set_curve_server_key failed: {}", e);
+            0
+        }
     }
-    let slice = std::slice::from_raw_parts(data, len as usize);
-    match ctx.send_push(slice) {
+}
+
+/// Configure reconnect backoff for the next ea_connect
+///
+/// Maps to ZMQ_RECONNECT_IVL / ZMQ_RECONNECT_IVL_MAX. `max_ms = 0` disables
+/// exponential backoff.
+///
+/// # Safety
+/// - context: Valid EaContext pointer
+#[no_mangle]
+pub unsafe extern "C" fn ea_set_reconnect_ivl(
+    context: *mut EaContext,
+    min_ms: i32,
+    max_ms: i32,
+) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return 0,
+    };
+
+    match ctx.set_reconnect_ivl(min_ms, max_ms) {
         Ok(_) => 1,
         Err(e) => {
-            eprintln!("ea_send_push failed: {}", e);
+            eprintln!("ea_set_reconnect_ivl failed: {}", e);
             0
         }
     }
 }
 
+/// Wait until both sockets have completed the handshake with the relay
+///
+/// Replaces fixed sleeps after ea_connect (slow-joiner workaround).
+///
+/// # Returns
+/// 1 when connected, 0 on timeout, -1 on error (not connected / invalid context)
+///
+/// # Safety
+/// - context: Valid EaContext pointer
+#[no_mangle]
+pub unsafe extern "C" fn ea_wait_connected(context: *mut EaContext, timeout_ms: i32) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return -1,
+    };
+
+    match ctx.wait_connected(timeout_ms) {
+        Ok(true) => 1,
+        Ok(false) => 0,
+        Err(e) => {
+            eprintln!("ea_wait_connected failed: {}", e);
+            -1
+        }
+    }
+}
+
+/// Wait until the relay has seen every subscription made so far
+///
+/// Call after ea_wait_connected (or after a Slave subscribed to a new Master's
+/// trades) so the next frame published on those topics is not lost.
+///
+/// # Returns
+/// 1 when confirmed, 0 on timeout, -1 on error (not connected / invalid context)
+///
+/// # Safety
+/// - context: Valid EaContext pointer
+#[no_mangle]
+pub unsafe extern "C" fn ea_wait_subscribed(context: *mut EaContext, timeout_ms: i32) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return -1,
+    };
+
+    match ctx.wait_subscribed(timeout_ms) {
+        Ok(true) => 1,
+        Ok(false) => 0,
+        Err(e) => {
+            eprintln!("ea_wait_subscribed failed: {}", e);
+            -1
+        }
+    }
+}
+
+/// Send raw data via PUSH socket (non-blocking)
+///
+/// # Returns
+/// 1 on success, negative `FfiErrorCode` on failure (see `ea_last_error_string`)
+///
+/// # Safety
+/// - context: Valid EaContext pointer
+/// - data: Valid buffer
+#[no_mangle]
+pub unsafe extern "C" fn ea_send_push(context: *mut EaContext, data: *const u8, len: i32) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return fail(FfiErrorCode::InvalidHandle, "ea_send_push: null context"),
+    };
+    if data.is_null() || len <= 0 {
+        return fail(FfiErrorCode::InvalidArgument, "ea_send_push: empty buffer");
+    }
+    let slice = std::slice::from_raw_parts(data, len as usize);
+    send_result("ea_send_push", ctx.send_push(slice))
+}
+
 /// Receive message from Config socket (high-level, non-blocking)
 ///
 /// # Safety
@@ -102,3 +210,146 @@ pub unsafe extern "C" fn ea_subscribe_config(context: *mut EaContext, topic: *co
         Err(_) => 0,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::lifecycle::{ea_context_free, ea_init};
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn free_port() -> u16 {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port()
+    }
+
+    #[test]
+    fn test_reconnect_ivl_and_wait_connected() {
+        let ctx = unsafe {
+            ea_init(
+                utf16("SLAVE_001").as_ptr(),
+                utf16("Slave").as_ptr(),
+                utf16("MT5").as_ptr(),
+                12345,
+                utf16("Broker").as_ptr(),
+                utf16("Account").as_ptr(),
+                utf16("Server").as_ptr(),
+                utf16("USD").as_ptr(),
+                100,
+            )
+        };
+        assert!(!ctx.is_null());
+
+        unsafe {
+            assert_eq!(ea_set_reconnect_ivl(std::ptr::null_mut(), 100, 1000), 0);
+            assert_eq!(ea_set_reconnect_ivl(ctx, 0, 1000), 0);
+            assert_eq!(ea_set_reconnect_ivl(ctx, 500, 100), 0);
+            assert_eq!(ea_set_reconnect_ivl(ctx, 50, 1000), 1);
+
+            // Not connected yet
+            assert_eq!(ea_wait_connected(ctx, 10), -1);
+            assert_eq!(ea_wait_connected(std::ptr::null_mut(), 10), -1);
+        }
+
+        let pull_port = free_port();
+        let pub_port = free_port();
+        let push_addr = utf16(&format!("tcp://127.0.0.1:{}", pull_port));
+        let sub_addr = utf16(&format!("tcp://127.0.0.1:{}", pub_port));
+
+        unsafe {
+            assert_eq!(ea_connect(ctx, push_addr.as_ptr(), sub_addr.as_ptr()), 1);
+            // Nothing listening: times out instead of hanging
+            assert_eq!(ea_wait_connected(ctx, 100), 0);
+        }
+
+        // Relay comes up later; reconnect (50ms interval) picks it up
+        let zmq_ctx = zmq::Context::new();
+        let pull = zmq_ctx.socket(zmq::PULL).unwrap();
+        pull.bind(&format!("tcp://127.0.0.1:{}", pull_port))
+            .unwrap();
+        let publisher = zmq_ctx.socket(zmq::PUB).unwrap();
+        publisher
+            .bind(&format!("tcp://127.0.0.1:{}", pub_port))
+            .unwrap();
+
+        unsafe {
+            assert_eq!(ea_wait_connected(ctx, 5000), 1);
+            // Already connected: returns immediately
+            assert_eq!(ea_wait_connected(ctx, 0), 1);
+            ea_context_free(ctx);
+        }
+    }
+    #[test]
+    fn test_wait_subscribed_after_probe_echo() {
+        let ctx = unsafe {
+            ea_init(
+                utf16("SLAVE_001").as_ptr(),
+                utf16("Slave").as_ptr(),
+                utf16("MT5").as_ptr(),
+                12345,
+                utf16("Broker").as_ptr(),
+                utf16("Account").as_ptr(),
+                utf16("Server").as_ptr(),
+                utf16("USD").as_ptr(),
+                100,
+            )
+        };
+        unsafe {
+            assert_eq!(ea_wait_subscribed(std::ptr::null_mut(), 10), -1);
+            // Not connected yet
+            assert_eq!(ea_wait_subscribed(ctx, 10), -1);
+        }
+
+        // Stand-in relay: XPUB echoing probe subscriptions like the relay publisher
+        let zmq_ctx = zmq::Context::new();
+        let pull = zmq_ctx.socket(zmq::PULL).unwrap();
+        pull.bind("tcp://127.0.0.1:*").unwrap();
+        let xpub = zmq_ctx.socket(zmq::XPUB).unwrap();
+        xpub.set_xpub_verbose(true).unwrap();
+        xpub.bind("tcp://127.0.0.1:*").unwrap();
+        let push_addr = utf16(&pull.get_last_endpoint().unwrap().unwrap());
+        let sub_addr = utf16(&xpub.get_last_endpoint().unwrap().unwrap());
+
+        let relay = std::thread::spawn(move || {
+            let mut events = Vec::new();
+            loop {
+                let message = xpub.recv_bytes(0).unwrap();
+                let topic = String::from_utf8_lossy(&message[1..]).into_owned();
+                let subscribed = message[0] == 1;
+                if subscribed && topic.starts_with(crate::TOPIC_SUBSCRIBE_ACK_PREFIX) {
+                    xpub.send(crate::protocol::encode_frame(&topic, &[]), 0)
+                        .unwrap();
+                }
+                events.push((subscribed, topic.clone()));
+                if !subscribed && topic.starts_with(crate::TOPIC_SUBSCRIBE_ACK_PREFIX) {
+                    return events;
+                }
+            }
+        });
+
+        unsafe {
+            assert_eq!(ea_connect(ctx, push_addr.as_ptr(), sub_addr.as_ptr()), 1);
+            assert_eq!(ea_wait_subscribed(ctx, 5000), 1);
+        }
+
+        // The EA's own subscriptions reached the relay before the probe,
+        // and the probe is removed once confirmed
+        let events = relay.join().unwrap();
+        let probe = events
+            .iter()
+            .position(|(subscribed, topic)| *subscribed && topic == "sub-ack/SLAVE_001/1")
+            .unwrap();
+        assert!(events[..probe].contains(&(true, "config/SLAVE_001".to_string())));
+        assert_eq!(
+            events.last(),
+            Some(&(false, "sub-ack/SLAVE_001/1".to_string()))
+        );
+
+        unsafe { ea_context_free(ctx) };
+    }
+}
@@ -0,0 +1,191 @@
+//! Structured return codes for FFI send functions
+//!
+//! Send functions return [`FFI_OK`] (1) on success, unchanged for existing
+//! `== 1` checks on the MQL side, and a negative [`FfiErrorCode`] on failure.
+//! The message of the last failure on the calling thread is available via
+//! `ea_last_error_string`.
+
+use std::cell::RefCell;
+
+use crate::errors::BridgeError;
+
+/// Success return value
+pub const FFI_OK: i32 = 1;
+
+/// Negative error codes returned by FFI send functions
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiErrorCode {
+    /// Send queue is full (would block); retry later
+    WouldBlock = -1,
+    /// Null context or socket not connected / closed
+    InvalidHandle = -2,
+    /// ZMQ send failed for another reason
+    SendFailed = -3,
+    /// Invalid argument (null pointer, bad UTF-16, unknown order type)
+    InvalidArgument = -4,
+    /// MessagePack serialization failed
+    Serialization = -5,
+}
+
+impl From<&BridgeError> for FfiErrorCode {
+    fn from(err: &BridgeError) -> Self {
+        match err {
+            BridgeError::Zmq(zmq::Error::EAGAIN) => FfiErrorCode::WouldBlock,
+            BridgeError::Zmq(zmq::Error::ETERM | zmq::Error::ENOTSOCK) => {
+                FfiErrorCode::InvalidHandle
+            }
+            BridgeError::NoSocket => FfiErrorCode::InvalidHandle,
+            BridgeError::Serde(_) | BridgeError::DeSerde(_) => FfiErrorCode::Serialization,
+            BridgeError::Zmq(_)
+            | BridgeError::Init(_)
+            | BridgeError::NotSupported
+            | BridgeError::Generic(_) => FfiErrorCode::SendFailed,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Record `message` as the last error and return `code` as i32
+pub(crate) fn fail(code: FfiErrorCode, message: impl Into<String>) -> i32 {
+    let message = message.into();
+    eprintln!("{}", message);
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+    code as i32
+}
+
+/// Map a send result to an FFI return code, recording the error message
+pub(crate) fn send_result(function: &str, result: Result<(), BridgeError>) -> i32 {
+    match result {
+        Ok(()) => FFI_OK,
+        Err(e) => fail(
+            FfiErrorCode::from(&e),
+            format!("{} failed: {}", function, e),
+        ),
+    }
+}
+
+/// Copy the last error message on this thread into a UTF-16 buffer
+///
+/// # Returns
+/// Number of UTF-16 units written (excluding the null terminator), or 0 when
+/// there is no error or the buffer is invalid. Long messages are truncated.
+///
+/// # Safety
+/// - buffer: Valid writable buffer of at least `len` u16 elements
+#[no_mangle]
+pub unsafe extern "C" fn ea_last_error_string(buffer: *mut u16, len: i32) -> i32 {
+    if buffer.is_null() || len <= 0 {
+        return 0;
+    }
+    let out = std::slice::from_raw_parts_mut(buffer, len as usize);
+
+    LAST_ERROR.with(|last| {
+        let utf16: Vec<u16> = last.borrow().encode_utf16().collect();
+        let copy_len = utf16.len().min(out.len() - 1);
+        out[..copy_len].copy_from_slice(&utf16[..copy_len]);
+        out[copy_len] = 0;
+        copy_len as i32
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::{ea_connect, ea_context_free, ea_init, ea_send_close_signal, ea_send_push};
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn last_error() -> String {
+        let mut buffer = [0u16; 256];
+        let len = unsafe { ea_last_error_string(buffer.as_mut_ptr(), buffer.len() as i32) };
+        String::from_utf16_lossy(&buffer[..len as usize])
+    }
+
+    fn create_context(ea_type: &str) -> *mut crate::EaContext {
+        let ctx = unsafe {
+            ea_init(
+                utf16("ACCOUNT_001").as_ptr(),
+                utf16(ea_type).as_ptr(),
+                utf16("MT5").as_ptr(),
+                12345,
+                utf16("Broker").as_ptr(),
+                utf16("Account").as_ptr(),
+                utf16("Server").as_ptr(),
+                utf16("USD").as_ptr(),
+                100,
+            )
+        };
+        assert!(!ctx.is_null());
+        ctx
+    }
+
+    #[test]
+    fn test_invalid_handle_codes() {
+        let data = [0x80u8];
+        unsafe {
+            assert_eq!(
+                ea_send_push(std::ptr::null_mut(), data.as_ptr(), 1),
+                FfiErrorCode::InvalidHandle as i32
+            );
+        }
+        assert!(last_error().contains("context"));
+
+        // Valid context but never connected
+        let ctx = create_context("Master");
+        unsafe {
+            assert_eq!(
+                ea_send_close_signal(ctx, 1, 0.1, 1.0),
+                FfiErrorCode::InvalidHandle as i32
+            );
+            assert!(last_error().contains("Socket not available"));
+
+            assert_eq!(
+                ea_send_push(ctx, std::ptr::null(), 0),
+                FfiErrorCode::InvalidArgument as i32
+            );
+            ea_context_free(ctx);
+        }
+    }
+
+    #[test]
+    fn test_would_block_when_queue_is_full() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let addr = utf16(&format!("tcp://127.0.0.1:{}", port));
+        let ctx = create_context("Master");
+
+        unsafe {
+            assert_eq!(ea_connect(ctx, addr.as_ptr(), addr.as_ptr()), 1);
+
+            // Nobody is listening: messages queue up to SNDHWM, then EAGAIN
+            let data = [0x80u8];
+            let code = (0..10_000)
+                .map(|_| ea_send_push(ctx, data.as_ptr(), 1))
+                .find(|&code| code != FFI_OK);
+            assert_eq!(code, Some(FfiErrorCode::WouldBlock as i32));
+            assert!(last_error().starts_with("ea_send_push failed"));
+            ea_context_free(ctx);
+        }
+    }
+
+    #[test]
+    fn test_last_error_string_truncates() {
+        fail(FfiErrorCode::SendFailed, "abcdef");
+        let mut buffer = [0xFFFFu16; 4];
+        let len = unsafe { ea_last_error_string(buffer.as_mut_ptr(), 4) };
+        assert_eq!(len, 3);
+        assert_eq!(String::from_utf16_lossy(&buffer[..3]), "abc");
+        assert_eq!(buffer[3], 0);
+
+        assert_eq!(unsafe { ea_last_error_string(std::ptr::null_mut(), 4) }, 0);
+    }
+}
@@ -1,24 +1,26 @@
 use crate::ea_context::{EaCommand, EaContext};
+use crate::ffi::error_codes::{fail, send_result, FfiErrorCode};
 use crate::ffi::helpers::utf16_to_string;
 
 /// Send RequestConfig message (Slave only)
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - context: Valid EaContext pointer
 #[no_mangle]
 pub unsafe extern "C" fn ea_send_request_config(context: *mut EaContext, version: u32) -> i32 {
     let ctx = match context.as_mut() {
         Some(c) => c,
-        None => return 0,
+        None => {
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_request_config: null context",
+            )
+        }
     };
 
-    match ctx.send_request_config(version) {
-        Ok(_) => 1,
-        Err(e) => {
-            eprintln!("ea_send_request_config failed: {}", e);
-            0
-        }
-    }
+    send_result("ea_send_request_config", ctx.send_request_config(version))
 }
 
 /// Create and serialize a RegisterMessage using context data (using Rust-side SymbolMatcher)
@@ -135,6 +137,9 @@ pub unsafe extern "C" fn ea_send_heartbeat(
             account_id: ctx.account_id.clone(),
             balance,
             equity,
+            margin: ctx.current_margin,
+            free_margin: ctx.current_free_margin,
+            margin_level: ctx.current_margin_level,
             open_positions,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: env!("BUILD_INFO").to_string(),
@@ -190,6 +195,8 @@ pub unsafe extern "C" fn ea_send_unregister(
 
 /// Send a Sync Request (Slave -> Master)
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - `context` must be a valid pointer returned by `ea_init()`
 /// - `master_account` must be a valid null-terminated UTF-16 string
@@ -202,12 +209,20 @@ pub unsafe extern "C" fn ea_send_sync_request(
 ) -> i32 {
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         if context.is_null() {
-            return 0;
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_sync_request: null context",
+            );
         }
 
         let ma = match utf16_to_string(master_account) {
             Some(s) => s,
-            None => return 0,
+            None => {
+                return fail(
+                    FfiErrorCode::InvalidArgument,
+                    "ea_send_sync_request: invalid master_account",
+                )
+            }
         };
 
         // last_sync_time can be null or empty
@@ -218,13 +233,10 @@ pub unsafe extern "C" fn ea_send_sync_request(
         };
 
         let ctx = &mut *context;
-        match ctx.send_sync_request(&ma, lst) {
-            Ok(_) => 1,
-            Err(_) => 0,
-        }
+        send_result("ea_send_sync_request", ctx.send_sync_request(&ma, lst))
     }));
 
-    result.unwrap_or(0)
+    result.unwrap_or(FfiErrorCode::SendFailed as i32)
 }
 
 /// Main Manager Tick (replaces ea_tick_timer)
@@ -248,6 +260,26 @@ pub unsafe extern "C" fn ea_manager_tick(
     ctx.manager_tick(balance, equity, open_positions, is_trade_allowed != 0)
 }
 
+/// Set the account margin state included in subsequent heartbeats
+/// Returns 1 on success, 0 on a null context
+///
+/// # Safety
+/// - context: Valid EaContext pointer
+#[no_mangle]
+pub unsafe extern "C" fn ea_set_margin_info(
+    context: *mut EaContext,
+    margin: f64,
+    free_margin: f64,
+    margin_level: f64,
+) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return 0,
+    };
+    ctx.set_margin_info(margin, free_margin, margin_level);
+    1
+}
+
 /// Retrieve the next pending command for MQL
 /// Returns 1 if command retrieved, 0 if queue empty
 ///
@@ -1,5 +1,6 @@
 pub mod accessors;
 pub mod connection;
+pub mod error_codes;
 pub mod helpers;
 pub mod lifecycle;
 pub mod messaging;
@@ -9,6 +10,7 @@ pub mod types;
 
 pub use accessors::*;
 pub use connection::*;
+pub use error_codes::*;
 pub use helpers::*;
 pub use lifecycle::*;
 pub use messaging::*;
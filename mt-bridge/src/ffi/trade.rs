@@ -1,10 +1,13 @@
 use crate::ea_context::EaContext;
 use crate::ffi::accessors::convert_c_position_to_rust;
+use crate::ffi::error_codes::{fail, send_result, FfiErrorCode};
 use crate::ffi::helpers::utf16_to_string;
 use crate::ffi::types::SPositionInfo;
 
 /// Send an Open Trade Signal
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - `context` must be a valid pointer returned by `ea_init()`
 /// - `symbol`, `order_type`, `comment` must be valid null-terminated UTF-16 strings
@@ -23,38 +26,80 @@ pub unsafe extern "C" fn ea_send_open_signal(
 ) -> i32 {
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         if context.is_null() {
-            return 0;
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_open_signal: null context",
+            );
         }
         let ctx = &mut *context;
 
         let sym = match utf16_to_string(symbol) {
             Some(s) => s,
-            None => return 0,
+            None => {
+                return fail(
+                    FfiErrorCode::InvalidArgument,
+                    "ea_send_open_signal: invalid symbol",
+                )
+            }
         };
         let o_type_str = match utf16_to_string(order_type) {
             Some(s) => s,
-            None => return 0,
+            None => {
+                return fail(
+                    FfiErrorCode::InvalidArgument,
+                    "ea_send_open_signal: invalid order_type",
+                )
+            }
         };
         let o_type = match crate::constants::OrderType::try_parse(&o_type_str) {
             Some(ot) => ot,
-            None => return 0,
+            None => {
+                return fail(
+                    FfiErrorCode::InvalidArgument,
+                    "ea_send_open_signal: unknown order_type",
+                )
+            }
         };
         let cmt = match utf16_to_string(comment) {
             Some(s) => s,
-            None => return 0,
+            None => {
+                return fail(
+                    FfiErrorCode::InvalidArgument,
+                    "ea_send_open_signal: invalid comment",
+                )
+            }
         };
 
-        match ctx.send_open_signal(ticket, &sym, o_type, lots, price, sl, tp, magic, &cmt) {
-            Ok(_) => 1,
-            Err(_) => 0,
-        }
+        send_result(
+            "ea_send_open_signal",
+            ctx.send_open_signal(ticket, &sym, o_type, lots, price, sl, tp, magic, &cmt),
+        )
     }));
 
-    result.unwrap_or(0)
+    result.unwrap_or(FfiErrorCode::SendFailed as i32)
+}
+
+/// Set the current spread (in points) to include in the next Open signal
+///
+/// The value is consumed by the next `ea_send_open_signal`; negative values clear it.
+/// Returns 1 on success, 0 if `context` is null.
+///
+/// # Safety
+/// - `context` must be a valid pointer returned by `ea_init()`
+#[no_mangle]
+pub unsafe extern "C" fn ea_set_open_spread(context: *mut EaContext, spread_points: i32) -> i32 {
+    let ctx = match context.as_mut() {
+        Some(c) => c,
+        None => return 0,
+    };
+    ctx.set_open_spread(spread_points);
+    1
 }
 
 /// Send a Close Trade Signal
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - `context` must be a valid pointer returned by `ea_init()`
 #[no_mangle]
@@ -66,21 +111,26 @@ pub unsafe extern "C" fn ea_send_close_signal(
 ) -> i32 {
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         if context.is_null() {
-            return 0;
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_close_signal: null context",
+            );
         }
         let ctx = &mut *context;
 
-        match ctx.send_close_signal(ticket, lots, close_ratio) {
-            Ok(_) => 1,
-            Err(_) => 0,
-        }
+        send_result(
+            "ea_send_close_signal",
+            ctx.send_close_signal(ticket, lots, close_ratio),
+        )
     }));
 
-    result.unwrap_or(0)
+    result.unwrap_or(FfiErrorCode::SendFailed as i32)
 }
 
 /// Send a Modify Trade Signal
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - `context` must be a valid pointer returned by `ea_init()`
 #[no_mangle]
@@ -92,21 +142,26 @@ pub unsafe extern "C" fn ea_send_modify_signal(
 ) -> i32 {
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
         if context.is_null() {
-            return 0;
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_modify_signal: null context",
+            );
         }
         let ctx = &mut *context;
 
-        match ctx.send_modify_signal(ticket, sl, tp) {
-            Ok(_) => 1,
-            Err(_) => 0,
-        }
+        send_result(
+            "ea_send_modify_signal",
+            ctx.send_modify_signal(ticket, sl, tp),
+        )
     }));
 
-    result.unwrap_or(0)
+    result.unwrap_or(FfiErrorCode::SendFailed as i32)
 }
 
 /// Send a Position Snapshot (Master -> Slave)
 ///
+/// Returns 1 on success, negative `FfiErrorCode` on failure.
+///
 /// # Safety
 /// - context: Valid EaContext pointer
 /// - positions: Pointer to array of SPositionInfo
@@ -118,27 +173,36 @@ pub unsafe extern "C" fn ea_send_position_snapshot(
     count: i32,
 ) -> i32 {
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        if context.is_null() || count < 0 || (count > 0 && positions.is_null()) {
-            return 0;
+        if context.is_null() {
+            return fail(
+                FfiErrorCode::InvalidHandle,
+                "ea_send_position_snapshot: null context",
+            );
         }
-        if count == 0 {
-            return 1; // Success, empty snapshot
+        if count < 0 || (count > 0 && positions.is_null()) {
+            return fail(
+                FfiErrorCode::InvalidArgument,
+                "ea_send_position_snapshot: invalid positions",
+            );
         }
-
         let ctx = &mut *context;
-        let slice = std::slice::from_raw_parts(positions, count as usize);
 
-        // Convert all positions first (Conversion Layer)
-        let rust_positions: Vec<crate::types::PositionInfo> = slice
-            .iter()
-            .map(|c_pos| convert_c_position_to_rust(c_pos))
-            .collect();
+        // An empty snapshot is still sent so the relay sees that no positions are open
+        let rust_positions: Vec<crate::types::PositionInfo> = if count == 0 {
+            Vec::new()
+        } else {
+            // Convert all positions first (Conversion Layer)
+            std::slice::from_raw_parts(positions, count as usize)
+                .iter()
+                .map(|c_pos| convert_c_position_to_rust(c_pos))
+                .collect()
+        };
 
-        match ctx.send_position_snapshot(rust_positions) {
-            Ok(_) => 1,
-            Err(_) => 0,
-        }
+        send_result(
+            "ea_send_position_snapshot",
+            ctx.send_position_snapshot(rust_positions),
+        )
     }));
 
-    result.unwrap_or(0)
+    result.unwrap_or(FfiErrorCode::SendFailed as i32)
 }
@@ -14,21 +14,22 @@ pub const MAX_SERVER_LEN: usize = 64;
 
 /// SSlaveConfig - Slave configuration for FFI
 /// Layout: 8-byte types first, then 4-byte, then byte arrays - no internal padding
-/// Total size: 344 bytes (8-byte aligned)
+/// Total size: 360 bytes (8-byte aligned)
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct SSlaveConfig {
-    // 8-byte fields first (f64) - 48 bytes total
+    // 8-byte fields first (f64) - 56 bytes total
     pub timestamp: i64, // Unix timestamp in milliseconds
     pub lot_multiplier: f64,
     pub source_lot_min: f64,
     pub source_lot_max: f64,
     pub master_equity: f64,
     pub market_sync_max_pips: f64,
+    pub fixed_lot: f64, // Lot used for every Open in FixedLot mode (0 = not set)
 
     // 4-byte fields (i32/u32) - 48 bytes total
     pub status: i32,
-    pub lot_calculation_mode: i32, // 0=Multiplier, 1=MarginRatio
+    pub lot_calculation_mode: i32, // 0=Multiplier, 1=MarginRatio, 2=EquityRatio, 3=FixedLot, 4=RiskPercent
     pub reverse_trade: i32,        // bool
     pub config_version: u32,
     pub sync_mode: i32, // 0=Skip, 1=Limit, 2=Market
@@ -39,7 +40,7 @@ pub struct SSlaveConfig {
     pub max_signal_delay_ms: i32,
     pub use_pending_order_for_delayed: i32, // bool
     pub allow_new_orders: i32,              // bool
-    // No _reserved needed: 40 + 48 = 88 bytes (divisible by 8)
+    // No _reserved needed: 56 + 48 = 104 bytes (divisible by 8)
 
     // Byte arrays - 256 bytes total
     pub account_id: [u8; MAX_ACCOUNT_ID_LEN],     // 64
@@ -74,7 +75,7 @@ pub struct SSymbolMapping {
 
 /// SPositionInfo - Position data for FFI
 /// Layout: 8-byte types first, then 4-byte + padding, then byte arrays
-/// Total size: 160 bytes (8-byte aligned)
+/// Total size: 168 bytes (8-byte aligned)
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct SPositionInfo {
@@ -94,6 +95,9 @@ pub struct SPositionInfo {
     // Byte arrays (64-159) - 96 bytes
     pub symbol: [u8; MAX_SYMBOL_LEN],   // 64-95 (32 bytes)
     pub comment: [u8; MAX_COMMENT_LEN], // 96-159 (64 bytes)
+
+    // Appended 8-byte field (160-167)
+    pub profit: f64, // 160-167
 }
 
 /// SSyncRequest - Sync request for FFI
@@ -133,6 +137,7 @@ impl Default for SSlaveConfig {
             source_lot_max: 0.0,
             master_equity: 0.0,
             market_sync_max_pips: 0.0,
+            fixed_lot: 0.0,
             // 4-byte fields
             status: 0,
             lot_calculation_mode: 0,
@@ -191,6 +196,7 @@ impl Default for SPositionInfo {
             _reserved: [0; 4],
             symbol: [0; MAX_SYMBOL_LEN],
             comment: [0; MAX_COMMENT_LEN],
+            profit: 0.0,
         }
     }
 }
@@ -234,8 +240,8 @@ mod tests {
     fn test_struct_exact_sizes() {
         // MQL側とバイト単位で一致しているか確認する
 
-        // SSlaveConfig: 48(i64+f64) + 48(i32) + 256(arr) = 352
-        assert_eq!(size_of::<SSlaveConfig>(), 352, "SSlaveConfig size mismatch");
+        // SSlaveConfig: 56(i64+f64) + 48(i32) + 256(arr) = 360
+        assert_eq!(size_of::<SSlaveConfig>(), 360, "SSlaveConfig size mismatch");
 
         // SMasterConfig: 8(i32) + 128(arr) = 136
         assert_eq!(
@@ -251,10 +257,10 @@ mod tests {
             "SSymbolMapping size mismatch"
         );
 
-        // SPositionInfo: 56(i64/f64) + 4(i32) + 4(pad) + 96(arr) = 160
+        // SPositionInfo: 56(i64/f64) + 4(i32) + 4(pad) + 96(arr) + 8(profit) = 168
         assert_eq!(
             size_of::<SPositionInfo>(),
-            160,
+            168,
             "SPositionInfo size mismatch"
         );
 
@@ -1,10 +1,14 @@
+pub mod batch;
 pub mod communication;
 pub mod constants;
+pub mod curve;
 pub mod ea_context;
 pub mod errors;
 pub mod ffi;
 
 pub mod logger;
+pub mod protocol;
+pub mod symbol_pattern;
 
 pub mod traits;
 pub mod types;
@@ -15,18 +19,24 @@ mod symbol_filter_tests;
 
 // Re-export message types for use in relay-server
 pub use types::{
-    GlobalConfigMessage, HeartbeatMessage, LotCalculationMode, MasterConfigMessage, PositionInfo,
-    PositionSnapshotMessage, RegisterMessage, RequestConfigMessage, SlaveConfigMessage,
-    SymbolContext, SymbolMapping, SyncMode, SyncRequestMessage, TradeFilters, TradeSignal,
-    UnregisterMessage, WarningCode,
+    CloseAllMessage, GlobalConfigMessage, HeartbeatMessage, LotCalculationMode,
+    MasterConfigMessage, PingMessage, PositionInfo, PositionSnapshotMessage, RegisterMessage,
+    RequestConfigMessage, SlaveConfigMessage, SymbolContext, SymbolMapping, SyncMode,
+    SyncRequestMessage, TradeFilters, TradeSignal, UnregisterMessage, WarningCode,
 };
 
+// Re-export symbol allow/block list matching (shared with relay-server filters)
+pub use symbol_pattern::SymbolPatterns;
+
 // Re-export enums for TradeSignal
 pub use constants::{OrderType, TradeAction};
 
 // Re-export traits for polymorphic config handling
 pub use traits::{ConfigMessage, MasterConfig, SlaveConfig};
 
+// Re-export CURVE helpers for relay-server and tests
+pub use curve::{CurveClientKeys, CurveKeyPair};
+
 // Re-export EA state management
 pub use ea_context::EaContext;
 
@@ -34,9 +44,10 @@ pub use ea_context::EaContext;
 
 // Re-export constants for protocol consistency
 pub use constants::{
-    build_config_topic, build_sync_topic, build_trade_topic, MSG_TYPE_HEARTBEAT,
-    MSG_TYPE_POSITION_SNAPSHOT, MSG_TYPE_REGISTER, MSG_TYPE_REQUEST_CONFIG, MSG_TYPE_SYNC_REQUEST,
-    MSG_TYPE_TRADE_SIGNAL, MSG_TYPE_UNREGISTER, STATUS_CONNECTED, STATUS_DISABLED, STATUS_ENABLED,
-    STATUS_NO_CONFIG, TOPIC_CONFIG_PREFIX, TOPIC_GLOBAL_CONFIG, TOPIC_SYNC_PREFIX,
+    build_config_topic, build_ping_topic, build_subscribe_ack_topic, build_sync_topic,
+    build_trade_topic, MSG_TYPE_HEARTBEAT, MSG_TYPE_POSITION_SNAPSHOT, MSG_TYPE_REGISTER,
+    MSG_TYPE_REQUEST_CONFIG, MSG_TYPE_SYNC_REQUEST, MSG_TYPE_TRADE_SIGNAL, MSG_TYPE_UNREGISTER,
+    STATUS_CONNECTED, STATUS_DISABLED, STATUS_ENABLED, STATUS_NO_CONFIG, TOPIC_CONFIG_PREFIX,
+    TOPIC_GLOBAL_CONFIG, TOPIC_PING_PREFIX, TOPIC_SUBSCRIBE_ACK_PREFIX, TOPIC_SYNC_PREFIX,
     TOPIC_TRADE_PREFIX,
 };
@@ -0,0 +1,127 @@
+//! Wire framing shared by the relay, EAs and external clients.
+//!
+//! PUB frames sent by the relay carry the topic and the MessagePack payload in
+//! one ZMQ frame separated by a single space:
+//!
+//! ```text
+//! {topic} {MessagePack payload}
+//! ```
+//!
+//! Messages pushed to the relay are bare MessagePack maps. They are told apart
+//! by `message_type` ("Heartbeat", "Register", ...); TradeSignals have no
+//! `message_type` but an `action` field instead.
+
+use serde::Deserialize;
+
+/// Build a PUB frame: topic, one space, then the payload
+pub fn encode_frame(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(topic.len() + 1 + payload.len());
+    frame.extend_from_slice(topic.as_bytes());
+    frame.push(b' ');
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Split a PUB frame into topic bytes and payload (None when there is no space)
+pub fn split_frame(frame: &[u8]) -> Option<(&[u8], &[u8])> {
+    let space_pos = frame.iter().position(|&b| b == b' ')?;
+    Some((&frame[..space_pos], &frame[space_pos + 1..]))
+}
+
+/// Fields used to determine the type of a pushed MessagePack message
+#[derive(Debug, Deserialize)]
+pub struct MessageTypeDiscriminator {
+    pub message_type: Option<String>,
+    pub action: Option<String>,
+}
+
+/// Type of a message pushed to the relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageKind {
+    /// Message with a `message_type` field (see `MSG_TYPE_*`)
+    Typed(String),
+    /// TradeSignal (identified by its `action` field)
+    TradeSignal,
+    /// Neither `message_type` nor `action` is present
+    Unknown,
+}
+
+impl MessageTypeDiscriminator {
+    /// Peek at a MessagePack payload without decoding the full message
+    pub fn peek(payload: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(payload)
+    }
+
+    pub fn kind(self) -> MessageKind {
+        match (self.message_type, self.action) {
+            (Some(message_type), _) => MessageKind::Typed(message_type),
+            (None, Some(_)) => MessageKind::TradeSignal,
+            (None, None) => MessageKind::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{OrderType, TradeAction};
+    use crate::types::{TradeSignal, UnregisterMessage};
+
+    #[test]
+    fn test_frame_round_trip() {
+        let frame = encode_frame("trade/MASTER_001/SLAVE_001", &[0x81, 0xa1, b'a', 0x01]);
+        let (topic, payload) = split_frame(&frame).unwrap();
+        assert_eq!(topic, b"trade/MASTER_001/SLAVE_001");
+        assert_eq!(payload, &[0x81, 0xa1, b'a', 0x01]);
+
+        // Only the first space separates the topic; the payload may contain spaces
+        let frame = encode_frame("config/A", b"x y");
+        assert_eq!(split_frame(&frame), Some((&b"config/A"[..], &b"x y"[..])));
+        assert_eq!(split_frame(b"no-space"), None);
+    }
+
+    #[test]
+    fn test_discriminator_kinds() {
+        let unregister = rmp_serde::to_vec_named(&UnregisterMessage {
+            message_type: "Unregister".to_string(),
+            account_id: "SLAVE_001".to_string(),
+            timestamp: 0,
+            ea_type: None,
+        })
+        .unwrap();
+        assert_eq!(
+            MessageTypeDiscriminator::peek(&unregister).unwrap().kind(),
+            MessageKind::Typed("Unregister".to_string())
+        );
+
+        let signal = rmp_serde::to_vec_named(&TradeSignal {
+            action: TradeAction::Open,
+            ticket: 1,
+            symbol: Some("EURUSD".to_string()),
+            order_type: Some(OrderType::Buy),
+            lots: Some(0.1),
+            open_price: None,
+            stop_loss: None,
+            take_profit: None,
+            magic_number: None,
+            comment: None,
+            timestamp: chrono::Utc::now(),
+            source_account: "MASTER_001".to_string(),
+            close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
+        })
+        .unwrap();
+        assert_eq!(
+            MessageTypeDiscriminator::peek(&signal).unwrap().kind(),
+            MessageKind::TradeSignal
+        );
+
+        let empty = rmp_serde::to_vec_named(&serde_json::json!({})).unwrap();
+        assert_eq!(
+            MessageTypeDiscriminator::peek(&empty).unwrap().kind(),
+            MessageKind::Unknown
+        );
+    }
+}
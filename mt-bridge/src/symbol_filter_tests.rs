@@ -11,6 +11,9 @@ fn test_symbol_filter_fields_roundtrip() {
         account_id: "TEST_001".to_string(),
         balance: 10000.0,
         equity: 10000.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
         open_positions: 0,
         timestamp: "2025-01-01T00:00:00Z".to_string(),
         version: "1.0.0".to_string(),
@@ -62,12 +65,15 @@ fn test_config_message_none_symbol_filters() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![],
         filters: TradeFilters {
             allowed_symbols: None,
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: None,
@@ -75,6 +81,7 @@ fn test_config_message_none_symbol_filters() {
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         // Open Sync Policy defaults
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
@@ -109,12 +116,15 @@ fn test_config_message_some_symbol_filters() {
         lot_calculation_mode: LotCalculationMode::MarginRatio,
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![],
         filters: TradeFilters {
             allowed_symbols: None,
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: Some("FX.".to_string()),
@@ -122,6 +132,7 @@ fn test_config_message_some_symbol_filters() {
         source_lot_min: Some(0.01),
         source_lot_max: Some(10.0),
         master_equity: Some(50000.0),
+        fixed_lot: None,
         // Open Sync Policy defaults
         sync_mode: SyncMode::MarketOrder,
         limit_order_expiry_min: Some(60),
@@ -0,0 +1,126 @@
+//! Symbol allow/block list matching.
+//!
+//! Entries containing `*` (any run of characters) or `?` (exactly one character)
+//! are glob patterns; all other entries are matched exactly. Matching is
+//! case-sensitive, like the exact entries always were.
+
+/// One compiled glob token
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(char),
+    AnyOne,
+    AnyRun,
+}
+
+/// A symbol list compiled for matching (built once per filter evaluation)
+#[derive(Debug, Clone)]
+pub struct SymbolPatterns<'a> {
+    exact: Vec<&'a str>,
+    globs: Vec<Vec<Token>>,
+}
+
+impl<'a> SymbolPatterns<'a> {
+    /// Split a list into exact entries and compiled glob patterns
+    pub fn compile(entries: &'a [String]) -> Self {
+        let mut exact = Vec::new();
+        let mut globs = Vec::new();
+        for entry in entries {
+            if entry.contains(['*', '?']) {
+                globs.push(
+                    entry
+                        .chars()
+                        .map(|c| match c {
+                            '*' => Token::AnyRun,
+                            '?' => Token::AnyOne,
+                            c => Token::Literal(c),
+                        })
+                        .collect(),
+                );
+            } else {
+                exact.push(entry.as_str());
+            }
+        }
+        Self { exact, globs }
+    }
+
+    /// Whether the symbol equals an exact entry or matches a glob pattern
+    pub fn matches(&self, symbol: &str) -> bool {
+        if self.exact.contains(&symbol) {
+            return true;
+        }
+        if self.globs.is_empty() {
+            return false;
+        }
+        let chars: Vec<char> = symbol.chars().collect();
+        self.globs.iter().any(|glob| glob_matches(glob, &chars))
+    }
+}
+
+/// Greedy glob match with backtracking to the last `*`
+fn glob_matches(pattern: &[Token], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(Token::AnyRun) => {
+                last_star = Some((p, t));
+                p += 1;
+            }
+            Some(Token::AnyOne) => {
+                p += 1;
+                t += 1;
+            }
+            Some(Token::Literal(c)) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match last_star {
+                // Let the last `*` absorb one more character and retry
+                Some((star_p, star_t)) => {
+                    last_star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|token| *token == Token::AnyRun)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_glob_and_exact_entries() {
+        let entries = list(&["XAU*", "EUR?PY", "GBPUSD"]);
+        let patterns = SymbolPatterns::compile(&entries);
+
+        assert!(patterns.matches("XAUUSD"));
+        assert!(patterns.matches("XAU"));
+        assert!(patterns.matches("EURJPY"));
+        assert!(patterns.matches("GBPUSD"));
+        assert!(!patterns.matches("EURUSD"));
+        assert!(!patterns.matches("EURJPY.m"));
+        assert!(!patterns.matches("GBPUSD.m"));
+        assert!(!patterns.matches("xauusd"));
+    }
+
+    #[test]
+    fn test_glob_backtracking() {
+        let entries = list(&["*USD*m", "*.?"]);
+        let patterns = SymbolPatterns::compile(&entries);
+
+        assert!(patterns.matches("XAUUSD.m"));
+        assert!(patterns.matches("USDm"));
+        assert!(patterns.matches("EURGBP.a"));
+        assert!(!patterns.matches("XAUUSD.pro"));
+    }
+}
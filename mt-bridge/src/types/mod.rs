@@ -6,15 +6,25 @@ use serde::{Deserialize, Serialize};
 
 /// Symbol mapping structure
 /// Maps source symbols to target symbols for cross-broker trading
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SymbolMapping {
     pub source_symbol: String,
     pub target_symbol: String,
+    /// Lower values are matched first; ties keep list order
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl SymbolMapping {
+    /// Sort mappings into match order (stable, so equal priorities keep list order).
+    pub fn sort_by_priority(mappings: &mut [SymbolMapping]) {
+        mappings.sort_by_key(|m| m.priority);
+    }
 }
 
 /// Trade filters structure
-/// Defines allowed/blocked symbols and magic numbers for trade filtering
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Defines allowed/blocked symbols, magic numbers and comment keywords for trade filtering
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct TradeFilters {
     #[serde(default)]
     pub allowed_symbols: Option<Vec<String>>,
@@ -24,6 +34,12 @@ pub struct TradeFilters {
     pub allowed_magic_numbers: Option<Vec<i64>>,
     #[serde(default)]
     pub blocked_magic_numbers: Option<Vec<i64>>,
+    /// Copy only trades whose comment contains one of these keywords (case-insensitive)
+    #[serde(default)]
+    pub allowed_comment_substrings: Option<Vec<String>>,
+    /// Skip trades whose comment contains one of these keywords (case-insensitive)
+    #[serde(default)]
+    pub blocked_comment_substrings: Option<Vec<String>>,
 }
 
 /// Lot calculation mode
@@ -33,6 +49,12 @@ pub enum LotCalculationMode {
     #[default]
     Multiplier,
     MarginRatio,
+    /// Lots are already scaled by slave_equity / master_equity on the relay-server
+    EquityRatio,
+    /// Lots are already replaced with the configured fixed lot on the relay-server
+    FixedLot,
+    /// Lots are already sized from risk percent and stop loss on the relay-server
+    RiskPercent,
 }
 
 /// Sync mode for existing positions when slave connects
@@ -62,6 +84,9 @@ pub struct SlaveConfigMessage {
     #[serde(default)]
     pub lot_multiplier: Option<f64>,
     pub reverse_trade: bool,
+    /// Symbols (as received by the Slave) reversed when reverse_trade is true (None = all)
+    #[serde(default)]
+    pub reverse_symbols: Option<Vec<String>>,
     #[serde(default)]
     pub symbol_prefix: Option<String>,
     #[serde(default)]
@@ -78,6 +103,9 @@ pub struct SlaveConfigMessage {
     /// Master's current equity (for margin_ratio mode calculation)
     #[serde(default)]
     pub master_equity: Option<f64>,
+    /// Fixed lot for every Open (fixed_lot mode; used by the EA for position snapshot sync)
+    #[serde(default)]
+    pub fixed_lot: Option<f64>,
 
     // === Open Sync Policy Settings ===
     /// Sync mode for existing positions when slave connects
@@ -116,6 +144,20 @@ pub struct SlaveConfigMessage {
     pub warning_codes: Vec<WarningCode>,
 }
 
+impl SlaveConfigMessage {
+    /// Whether a trade on `symbol` should be reversed under this config
+    pub fn should_reverse(&self, symbol: Option<&str>) -> bool {
+        if !self.reverse_trade {
+            return false;
+        }
+        match (&self.reverse_symbols, symbol) {
+            (None, _) => true,
+            (Some(list), Some(symbol)) => list.iter().any(|s| s == symbol),
+            (Some(_), None) => false,
+        }
+    }
+}
+
 fn default_max_retries() -> i32 {
     3
 }
@@ -151,6 +193,8 @@ pub struct MasterConfigMessage {
 #[serde(rename_all = "snake_case")]
 pub enum WarningCode {
     SlaveWebUiDisabled,
+    /// Member paused: outside its `active_schedule` windows
+    OutsideActiveSchedule,
     SlaveOffline,
     SlaveAutoTradingDisabled,
     MasterWebUiDisabled,
@@ -158,6 +202,26 @@ pub enum WarningCode {
     MasterAutoTradingDisabled,
     MasterClusterDegraded,
     NoMasterAssigned,
+    /// Trade skipped: risk_percent lot sizing requires a stop loss on the Master signal
+    StopLossRequired,
+    /// Trade skipped: calculated lot rounds below the slave broker's lot_min
+    LotBelowMinimum,
+    /// Trade skipped: slave already holds max_open_positions positions
+    MaxPositionsReached,
+    /// Trade skipped: slave's loss today exceeded max_daily_loss (until UTC midnight)
+    DailyLossLimitHit,
+    /// Trade skipped: Open symbol is in global_settings.blocked_symbols
+    GloballyBlockedSymbol,
+    /// Trade skipped: Master spread at Open exceeded max_spread_points
+    SpreadTooWide,
+    /// Signals dropped: Master exceeded its max_signals_per_sec rate limit
+    MasterSignalRateExceeded,
+    /// Advisory: Slave margin level is below the relay's warning threshold
+    LowMarginLevel,
+    /// Advisory: Master and Slave report different account currencies
+    CurrencyMismatch,
+    /// Member's Slave account is the TradeGroup's own Master account (copy loop)
+    SelfCopyDetected,
 }
 
 impl WarningCode {
@@ -168,15 +232,28 @@ impl WarningCode {
         match self {
             // Slave-side issues (highest priority - user can fix these directly)
             WarningCode::SlaveWebUiDisabled => 10,
+            WarningCode::OutsideActiveSchedule => 15,
             WarningCode::SlaveOffline => 20,
             WarningCode::SlaveAutoTradingDisabled => 30,
+            WarningCode::LowMarginLevel => 35,
             // Master-side issues (medium priority)
             WarningCode::MasterWebUiDisabled => 40,
             WarningCode::MasterOffline => 50,
             WarningCode::MasterAutoTradingDisabled => 60,
             // Configuration issues (lowest priority)
+            WarningCode::SelfCopyDetected => 65,
             WarningCode::NoMasterAssigned => 70,
             WarningCode::MasterClusterDegraded => 80,
+            // Per-trade skip reasons (informational, reported with the skipped signal)
+            WarningCode::StopLossRequired => 90,
+            WarningCode::LotBelowMinimum => 100,
+            WarningCode::MaxPositionsReached => 110,
+            WarningCode::DailyLossLimitHit => 115,
+            WarningCode::GloballyBlockedSymbol => 116,
+            WarningCode::SpreadTooWide => 117,
+            WarningCode::MasterSignalRateExceeded => 118,
+            // Advisory only (copying continues)
+            WarningCode::CurrencyMismatch => 120,
         }
     }
 
@@ -250,6 +327,14 @@ pub struct HeartbeatMessage {
     pub account_id: String,
     pub balance: f64,
     pub equity: f64,
+    /// Used margin (0 when the EA does not report margin)
+    #[serde(default)]
+    pub margin: f64,
+    #[serde(default)]
+    pub free_margin: f64,
+    /// Equity / margin * 100 (0 when no margin is in use)
+    #[serde(default)]
+    pub margin_level: f64,
     pub open_positions: i32,
     pub timestamp: String,
     pub version: String, // Build version information
@@ -302,6 +387,17 @@ pub struct TradeSignal {
     /// None or 1.0 = full close, 0.0 < ratio < 1.0 = partial close
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub close_ratio: Option<f64>,
+    /// Per-Master sequence number, incremented for every signal (1-based, skips 0 on wrap)
+    /// 0 = not numbered (older EAs); the relay drops non-increasing values as redeliveries
+    #[serde(default)]
+    pub seq: u64,
+    /// Master's spread in points when an Open was sent (None for older EAs and Close/Modify)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spread_points: Option<u32>,
+    /// stop_loss/take_profit are signed price offsets from the entry (relay
+    /// `sl_tp_as_offset`); the Slave EA adds them to its own fill price
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sl_tp_offset: bool,
 }
 
 // =============================================================================
@@ -326,16 +422,24 @@ pub struct PositionInfo {
     pub magic_number: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
+    /// Floating profit/loss in account currency (0 for EAs that do not report it)
+    #[serde(default)]
+    pub profit: f64,
 }
 
 /// Position snapshot message (Master → Slave via Relay)
-/// Sent when Master restarts or in response to SyncRequest
+/// Sent when Master restarts or in response to SyncRequest.
+/// Slaves also report their own positions (ea_type = "Slave"); the relay caches those
+/// for reconciliation and does not forward them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionSnapshotMessage {
     pub message_type: String, // "PositionSnapshot"
     pub source_account: String,
     pub positions: Vec<PositionInfo>,
     pub timestamp: String, // ISO 8601 format
+    /// Sender EA type ("Master" or "Slave"); None = Master (older EAs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ea_type: Option<String>,
 }
 
 /// Sync request message (Slave → Master via Relay)
@@ -350,6 +454,33 @@ pub struct SyncRequestMessage {
     pub timestamp: String,
 }
 
+/// Latency probe message (Relay → EA → Relay)
+/// Published by the relay on "ping/{account_id}" and echoed back unchanged by the EA
+/// (with `ea_type` filled in) so the relay can measure round-trip time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PingMessage {
+    pub message_type: String, // "Ping"
+    pub account_id: String,
+    /// Set by the EA on the echo ("Master" or "Slave")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ea_type: Option<String>,
+    /// Relay clock when the ping was sent (Unix milliseconds)
+    pub server_timestamp: i64,
+}
+
+/// Close-all command (Relay → Slave)
+/// Published on the Slave's "config/{account_id}" topic when an operator flattens
+/// the account; the EA closes every position and pending order it copied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseAllMessage {
+    pub message_type: String, // "CloseAll"
+    pub slave_account: String,
+    /// Only close copies on this (Slave-side) symbol; None = all symbols
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    pub timestamp: String, // ISO 8601 format
+}
+
 // =============================================================================
 // VictoriaLogs Configuration Message
 // =============================================================================
@@ -385,6 +516,7 @@ impl Default for SlaveConfigMessage {
             lot_calculation_mode: LotCalculationMode::default(),
             lot_multiplier: None,
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: Vec::new(),
@@ -393,6 +525,7 @@ impl Default for SlaveConfigMessage {
             source_lot_min: None,
             source_lot_max: None,
             master_equity: None,
+            fixed_lot: None,
             sync_mode: SyncMode::default(),
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -423,6 +556,9 @@ impl Default for TradeSignal {
             timestamp: chrono::Utc::now(),
             source_account: String::new(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 }
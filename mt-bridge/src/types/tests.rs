@@ -59,6 +59,9 @@ fn test_heartbeat_message_serialization() {
         account_id: "test_account_123".to_string(),
         balance: 10500.75,
         equity: 10600.25,
+        margin: 2000.0,
+        free_margin: 8600.25,
+        margin_level: 530.0125,
         open_positions: 3,
         timestamp: "2025-01-01T00:00:00Z".to_string(),
         version: "test123".to_string(),
@@ -84,6 +87,9 @@ fn test_heartbeat_message_serialization() {
     assert_eq!(msg.account_id, deserialized.account_id);
     assert_eq!(msg.balance, deserialized.balance);
     assert_eq!(msg.equity, deserialized.equity);
+    assert_eq!(msg.margin, deserialized.margin);
+    assert_eq!(msg.free_margin, deserialized.free_margin);
+    assert_eq!(msg.margin_level, deserialized.margin_level);
     assert_eq!(msg.open_positions, deserialized.open_positions);
     assert_eq!(msg.timestamp, deserialized.timestamp);
     assert_eq!(msg.ea_type, deserialized.ea_type);
@@ -100,6 +106,54 @@ fn test_heartbeat_message_serialization() {
     assert_eq!(msg.symbol_map, deserialized.symbol_map);
 }
 
+#[test]
+fn test_heartbeat_without_margin_fields_defaults_to_zero() {
+    // Heartbeat from an EA built before margin fields were added
+    let legacy = serde_json::json!({
+        "message_type": "Heartbeat",
+        "account_id": "legacy_account",
+        "balance": 10000.0,
+        "equity": 9500.0,
+        "open_positions": 1,
+        "timestamp": "2025-01-01T00:00:00Z",
+        "version": "old",
+        "ea_type": "Slave",
+        "platform": "MT4",
+        "account_number": 42,
+        "broker": "Broker",
+        "account_name": "Legacy",
+        "server": "Server",
+        "currency": "USD",
+        "leverage": 100,
+        "is_trade_allowed": true
+    });
+    let bytes = rmp_serde::to_vec_named(&legacy).unwrap();
+    let msg: HeartbeatMessage = rmp_serde::from_slice(&bytes).unwrap();
+
+    assert_eq!(msg.equity, 9500.0);
+    assert_eq!(msg.margin, 0.0);
+    assert_eq!(msg.free_margin, 0.0);
+    assert_eq!(msg.margin_level, 0.0);
+}
+
+#[test]
+fn test_position_info_without_profit_defaults_to_zero() {
+    // Position from an EA built before profit was added
+    let legacy = serde_json::json!({
+        "ticket": 1001,
+        "symbol": "EURUSD",
+        "order_type": "Buy",
+        "lots": 0.1,
+        "open_price": 1.085,
+        "open_time": "2025-01-01T00:00:00Z"
+    });
+    let bytes = rmp_serde::to_vec_named(&legacy).unwrap();
+    let pos: PositionInfo = rmp_serde::from_slice(&bytes).unwrap();
+
+    assert_eq!(pos.ticket, 1001);
+    assert_eq!(pos.profit, 0.0);
+}
+
 #[test]
 fn test_trade_signal_message_serialization() {
     let msg = TradeSignal {
@@ -116,6 +170,9 @@ fn test_trade_signal_message_serialization() {
         timestamp: Utc::now(),
         source_account: "master_account".to_string(),
         close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let serialized = rmp_serde::to_vec_named(&msg).expect("Failed to serialize");
@@ -154,6 +211,9 @@ fn test_trade_signal_close_action() {
         timestamp: Utc::now(),
         source_account: "master_account".to_string(),
         close_ratio: None, // None = full close
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let serialized = rmp_serde::to_vec_named(&msg).expect("Failed to serialize");
@@ -185,6 +245,9 @@ fn test_trade_signal_partial_close() {
         timestamp: Utc::now(),
         source_account: "master_account".to_string(),
         close_ratio: Some(0.5), // 50% partial close
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let serialized = rmp_serde::to_vec_named(&msg).expect("Failed to serialize");
@@ -208,22 +271,27 @@ fn test_config_message_serialization() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![SymbolMapping {
             source_symbol: "EURUSD".to_string(),
             target_symbol: "EURUSD.raw".to_string(),
+            priority: 0,
         }],
         filters: TradeFilters {
             allowed_symbols: Some(vec!["EURUSD".to_string(), "GBPUSD".to_string()]),
             blocked_symbols: None,
             allowed_magic_numbers: Some(vec![0, 123]),
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         source_lot_min: None,
         source_lot_max: None,
         master_equity: Some(10000.0),
+        fixed_lot: None,
         // Open Sync Policy defaults
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
@@ -254,6 +322,33 @@ fn test_config_message_serialization() {
     assert_eq!(config.config_version, deserialized.config_version);
 }
 
+#[test]
+fn test_should_reverse_symbol_whitelist() {
+    let mut config: SlaveConfigMessage = serde_json::from_value(serde_json::json!({
+        "account_id": "slave",
+        "master_account": "master",
+        "timestamp": 0,
+        "trade_group_id": "master",
+        "status": 2,
+        "reverse_trade": true,
+        "symbol_mappings": [],
+        "filters": {},
+        "config_version": 1
+    }))
+    .expect("Failed to deserialize");
+
+    // No list: reverse everything (previous behavior)
+    assert!(config.should_reverse(Some("GBPUSD")));
+
+    config.reverse_symbols = Some(vec!["EURUSD".to_string()]);
+    assert!(config.should_reverse(Some("EURUSD")));
+    assert!(!config.should_reverse(Some("GBPUSD")));
+    assert!(!config.should_reverse(None));
+
+    config.reverse_trade = false;
+    assert!(!config.should_reverse(Some("EURUSD")));
+}
+
 #[test]
 fn test_messagepack_size_optimization() {
     // Test that optional None fields are omitted in serialization
@@ -271,6 +366,9 @@ fn test_messagepack_size_optimization() {
         timestamp: Utc::now(),
         source_account: "master".to_string(),
         close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let msg_minimal = TradeSignal {
@@ -287,6 +385,9 @@ fn test_messagepack_size_optimization() {
         timestamp: Utc::now(),
         source_account: "master".to_string(),
         close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let serialized_full = rmp_serde::to_vec_named(&msg_full).unwrap();
@@ -353,3 +454,28 @@ fn test_master_config_message_with_none_values() {
     assert!(deserialized.symbol_suffix.is_none());
     assert_eq!(msg.config_version, deserialized.config_version);
 }
+
+#[test]
+fn test_trade_filters_comment_substrings_backward_compatible() {
+    // Filters stored before comment keywords existed must still deserialize
+    let legacy = r#"{"allowed_symbols":["EURUSD"],"blocked_symbols":null,"allowed_magic_numbers":null,"blocked_magic_numbers":null}"#;
+    let filters: TradeFilters = serde_json::from_str(legacy).expect("Failed to deserialize");
+    assert!(filters.allowed_comment_substrings.is_none());
+    assert!(filters.blocked_comment_substrings.is_none());
+
+    let filters = TradeFilters {
+        allowed_comment_substrings: Some(vec!["scalper".to_string()]),
+        blocked_comment_substrings: Some(vec!["hedge".to_string()]),
+        ..TradeFilters::default()
+    };
+    let json = serde_json::to_string(&filters).expect("Failed to serialize");
+    let deserialized: TradeFilters = serde_json::from_str(&json).expect("Failed to deserialize");
+    assert_eq!(
+        deserialized.allowed_comment_substrings,
+        Some(vec!["scalper".to_string()])
+    );
+    assert_eq!(
+        deserialized.blocked_comment_substrings,
+        Some(vec!["hedge".to_string()])
+    );
+}
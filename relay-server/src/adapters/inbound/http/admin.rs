@@ -0,0 +1,143 @@
+//! Administrative endpoint handlers
+//!
+//! Provides a one-shot online backup of the SQLite database for disaster recovery
+//! and a runtime reload of the CORS origin set. The per-TradeGroup signal
+//! counters can also be reset here.
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::adapters::inbound::http::{AppState, ProblemDetails};
+use crate::config::Config;
+
+const BACKUP_INSTANCE: &str = "/api/admin/backup";
+const RELOAD_CORS_INSTANCE: &str = "/api/admin/reload-cors";
+
+/// Response body for POST /api/admin/backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupResult {
+    /// File name inside the configured `[database] backup_dir`
+    pub filename: String,
+    pub size_bytes: u64,
+}
+
+/// Request body for POST /api/admin/reload-cors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReloadCorsRequest {
+    /// New origin list; None re-reads config.toml (`[webui] port` + `[cors] additional_origins`)
+    #[serde(default)]
+    pub origins: Option<Vec<String>>,
+}
+
+/// Response body for POST /api/admin/reload-cors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadCorsResult {
+    /// Origins in effect after the reload
+    pub origins: Vec<String>,
+}
+
+/// Write a consistent copy of the database to the backup directory
+pub async fn create_backup(
+    State(state): State<AppState>,
+) -> Result<Json<BackupResult>, ProblemDetails> {
+    let span = tracing::info_span!("create_backup");
+    let _enter = span.enter();
+
+    let dir = Path::new(&state.config.database.backup_dir);
+    if let Err(e) = ensure_writable(dir).await {
+        tracing::error!(dir = %dir.display(), error = %e, "Backup directory is not writable");
+        return Err(ProblemDetails::internal_error(format!(
+            "Backup directory '{}' is not writable: {}",
+            dir.display(),
+            e
+        ))
+        .with_instance(BACKUP_INSTANCE));
+    }
+
+    let filename = format!(
+        "sankey_copier-{}.db",
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    );
+    let path = dir.join(&filename);
+
+    match state.db.backup_to(&path).await {
+        Ok(size_bytes) => {
+            tracing::info!(path = %path.display(), size_bytes, "Database backup written");
+            Ok(Json(BackupResult {
+                filename,
+                size_bytes,
+            }))
+        }
+        Err(e) => {
+            tracing::error!(path = %path.display(), error = %e, "Database backup failed");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to back up database: {}", e))
+                    .with_instance(BACKUP_INSTANCE),
+            )
+        }
+    }
+}
+
+/// Replace the CORS origin set from the request or the current config file
+pub async fn reload_cors(
+    State(state): State<AppState>,
+    body: Option<Json<ReloadCorsRequest>>,
+) -> Result<Json<ReloadCorsResult>, ProblemDetails> {
+    let span = tracing::info_span!("reload_cors");
+    let _enter = span.enter();
+
+    if state.cors_disabled {
+        return Err(ProblemDetails::validation_error(
+            "CORS is disabled in config.toml; there are no origins to reload",
+        )
+        .with_instance(RELOAD_CORS_INSTANCE));
+    }
+
+    let origins = match body.and_then(|Json(request)| request.origins) {
+        Some(origins) => origins,
+        None => {
+            let config_dir = std::env::var("CONFIG_DIR").unwrap_or_else(|_| ".".to_string());
+            let config_base = format!("{}/config", config_dir);
+            match Config::from_file(&config_base) {
+                Ok(config) => config.allowed_origins(),
+                Err(e) => {
+                    tracing::error!(config_base = %config_base, error = %e, "Failed to re-read config for CORS reload");
+                    return Err(ProblemDetails::internal_error(format!(
+                        "Failed to read config file: {}",
+                        e
+                    ))
+                    .with_instance(RELOAD_CORS_INSTANCE));
+                }
+            }
+        }
+    };
+
+    if let Err(e) = state.allowed_origins.replace(&origins) {
+        tracing::warn!(error = %e, "Rejected CORS reload");
+        return Err(
+            ProblemDetails::validation_error(format!("Invalid origin {}", e))
+                .with_instance(RELOAD_CORS_INSTANCE),
+        );
+    }
+
+    let origins = state.allowed_origins.current();
+    tracing::info!(?origins, "Reloaded CORS origins");
+    Ok(Json(ReloadCorsResult { origins }))
+}
+
+/// Clear the signals received/copied counters of every TradeGroup
+pub async fn reset_stats(State(state): State<AppState>) -> StatusCode {
+    state.runtime_status_metrics.reset_trade_group_stats();
+    tracing::info!("Reset TradeGroup signal counters");
+    StatusCode::NO_CONTENT
+}
+
+/// Create the directory if needed and confirm a file can be written into it
+async fn ensure_writable(dir: &Path) -> std::io::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    let probe = dir.join(".write-test");
+    tokio::fs::write(&probe, b"").await?;
+    tokio::fs::remove_file(&probe).await
+}
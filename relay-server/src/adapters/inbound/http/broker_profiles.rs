@@ -0,0 +1,211 @@
+// relay-server/src/adapters/inbound/http/broker_profiles.rs
+//
+// REST API endpoints for broker profiles: symbol prefix/suffix and mappings
+// shared by members on the same broker (`slave_settings.broker_profile`).
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::domain::models::BrokerProfile;
+
+use super::trade_group_members::send_config_to_slave;
+use super::{AppState, ProblemDetails};
+use crate::adapters::inbound::http::dtos::{ApiResponse, UpdateBrokerProfileRequest};
+
+/// List all broker profiles
+pub async fn list_broker_profiles(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<BrokerProfile>>, ProblemDetails> {
+    state
+        .db
+        .list_broker_profiles()
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!(error = %e, "Failed to list broker profiles");
+            ProblemDetails::internal_error(format!("Failed to retrieve broker profiles: {}", e))
+                .with_instance("/api/broker-profiles")
+        })
+}
+
+/// Create a broker profile
+pub async fn create_broker_profile(
+    State(state): State<AppState>,
+    Json(profile): Json<BrokerProfile>,
+) -> Result<(StatusCode, Json<BrokerProfile>), ProblemDetails> {
+    let instance = "/api/broker-profiles";
+    if profile.name.trim().is_empty() {
+        return Err(
+            ProblemDetails::validation_error("Broker profile name must not be empty")
+                .with_instance(instance),
+        );
+    }
+
+    match state.db.create_broker_profile(&profile).await {
+        Ok(created) => {
+            tracing::info!(broker_profile = %created.name, "Created broker profile");
+            Ok((StatusCode::CREATED, Json(created)))
+        }
+        Err(e) => {
+            let error_msg = e.to_string();
+            if error_msg.contains("UNIQUE constraint failed")
+                || error_msg.contains("unique constraint")
+            {
+                return Err(ProblemDetails::validation_error(format!(
+                    "Broker profile already exists: {}",
+                    profile.name
+                ))
+                .with_instance(instance));
+            }
+
+            tracing::error!(broker_profile = %profile.name, error = %e, "Failed to create broker profile");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to create broker profile: {}", e))
+                    .with_instance(instance),
+            )
+        }
+    }
+}
+
+/// Get a broker profile by name
+pub async fn get_broker_profile(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<BrokerProfile>, ProblemDetails> {
+    let instance = format!("/api/broker-profiles/{}", name);
+    match state.db.get_broker_profile(&name).await {
+        Ok(Some(profile)) => Ok(Json(profile)),
+        Ok(None) => Err(ProblemDetails::not_found(format!(
+            "Broker profile '{}' was not found",
+            name
+        ))
+        .with_instance(instance)),
+        Err(e) => {
+            tracing::error!(broker_profile = %name, error = %e, "Failed to get broker profile");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to retrieve broker profile: {}", e))
+                    .with_instance(instance),
+            )
+        }
+    }
+}
+
+/// Replace a broker profile's prefix/suffix and mappings
+///
+/// Every member referencing the profile gets its Slave config re-sent;
+/// delivery failures are reported in `warnings`.
+pub async fn update_broker_profile(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateBrokerProfileRequest>,
+) -> Result<Json<ApiResponse<BrokerProfile>>, ProblemDetails> {
+    let span = tracing::info_span!("update_broker_profile", broker_profile = %name);
+    let _enter = span.enter();
+
+    let instance = format!("/api/broker-profiles/{}", name);
+    let profile = BrokerProfile {
+        name: name.clone(),
+        symbol_prefix: request.symbol_prefix,
+        symbol_suffix: request.symbol_suffix,
+        symbol_map: request.symbol_map,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+
+    if let Err(e) = state.db.update_broker_profile(&profile).await {
+        if e.to_string().contains("BrokerProfile not found") {
+            return Err(ProblemDetails::not_found(format!(
+                "Broker profile '{}' was not found",
+                name
+            ))
+            .with_instance(instance));
+        }
+        tracing::error!(error = %e, "Failed to update broker profile");
+        return Err(ProblemDetails::internal_error(format!(
+            "Failed to update broker profile: {}",
+            e
+        ))
+        .with_instance(instance));
+    }
+
+    // Redistribute to every member whose effective symbol settings just changed
+    let mut warnings = Vec::new();
+    match state.db.get_members_using_broker_profile(&name).await {
+        Ok(members) => {
+            tracing::info!(
+                members = members.len(),
+                "Updated broker profile, redistributing Slave configs"
+            );
+            for member in &members {
+                warnings.extend(send_config_to_slave(&state, &member.trade_group_id, member).await);
+            }
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load members using broker profile");
+            warnings.push(ProblemDetails::config_delivery_failed(format!(
+                "Broker profile saved but members could not be loaded: {}",
+                e
+            )));
+        }
+    }
+
+    let updated = state
+        .db
+        .get_broker_profile(&name)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(profile);
+    Ok(Json(ApiResponse::with_warnings(updated, warnings)))
+}
+
+/// Delete a broker profile (409 while members still reference it)
+pub async fn delete_broker_profile(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ProblemDetails> {
+    let instance = format!("/api/broker-profiles/{}", name);
+
+    let members = state
+        .db
+        .get_members_using_broker_profile(&name)
+        .await
+        .map_err(|e| {
+            ProblemDetails::internal_error(format!("Failed to load members: {}", e))
+                .with_instance(instance.clone())
+        })?;
+    if !members.is_empty() {
+        let accounts: Vec<String> = members
+            .iter()
+            .map(|m| format!("{}/{}", m.trade_group_id, m.slave_account))
+            .collect();
+        return Err(ProblemDetails::conflict(format!(
+            "Broker profile '{}' is used by members: {}",
+            name,
+            accounts.join(", ")
+        ))
+        .with_instance(instance));
+    }
+
+    match state.db.delete_broker_profile(&name).await {
+        Ok(true) => {
+            tracing::info!(broker_profile = %name, "Deleted broker profile");
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(ProblemDetails::not_found(format!(
+            "Broker profile '{}' was not found",
+            name
+        ))
+        .with_instance(instance)),
+        Err(e) => {
+            tracing::error!(broker_profile = %name, error = %e, "Failed to delete broker profile");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to delete broker profile: {}", e))
+                    .with_instance(instance),
+            )
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Config export/import endpoint handlers
+//!
+//! Provides REST API endpoints for exporting all TradeGroup settings as JSON
+//! and importing them back (backup/restore, migration to another machine).
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    adapters::inbound::http::{AppState, ProblemDetails},
+    domain::models::{ConfigExport, ConfigImportResult},
+};
+
+/// Query parameters for config import
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportConfigParams {
+    /// Validate and report the outcome without writing anything
+    pub dry_run: bool,
+    /// Replace existing TradeGroups instead of reporting them as conflicts
+    pub overwrite: bool,
+}
+
+/// Export all TradeGroups, MasterSettings and member SlaveSettings
+pub async fn export_config(
+    State(state): State<AppState>,
+) -> Result<Json<ConfigExport>, ProblemDetails> {
+    let span = tracing::info_span!("export_config");
+    let _enter = span.enter();
+
+    match state.db.export_config().await {
+        Ok(export) => {
+            tracing::info!(
+                trade_groups = export.trade_groups.len(),
+                "Successfully exported config"
+            );
+            Ok(Json(export))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to export config");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to export config: {}", e))
+                    .with_instance("/api/config/export"),
+            )
+        }
+    }
+}
+
+/// Import a config export document
+/// Query params: dry_run, overwrite
+pub async fn import_config(
+    State(state): State<AppState>,
+    Query(params): Query<ImportConfigParams>,
+    Json(export): Json<ConfigExport>,
+) -> Result<Json<ConfigImportResult>, ProblemDetails> {
+    let span = tracing::info_span!(
+        "import_config",
+        dry_run = params.dry_run,
+        overwrite = params.overwrite
+    );
+    let _enter = span.enter();
+
+    if let Err(e) = export.validate() {
+        tracing::warn!(error = %e, "Rejected invalid config import");
+        return Err(ProblemDetails::validation_error(e).with_instance("/api/config/import"));
+    }
+
+    let result = match state
+        .db
+        .import_config(&export, params.overwrite, params.dry_run)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to import config");
+            return Err(
+                ProblemDetails::internal_error(format!("Failed to import config: {}", e))
+                    .with_instance("/api/config/import"),
+            );
+        }
+    };
+
+    // Dry runs report conflicts in the body so the UI can preview them
+    if !result.dry_run && !result.conflicts.is_empty() {
+        tracing::warn!(conflicts = ?result.conflicts, "Config import has conflicts");
+        return Err(ProblemDetails::conflict(format!(
+            "TradeGroups already exist (use overwrite=true to replace): {}",
+            result.conflicts.join(", ")
+        ))
+        .with_instance("/api/config/import"));
+    }
+
+    if !result.dry_run {
+        tracing::info!(
+            created = result.created.len(),
+            updated = result.updated.len(),
+            "Successfully imported config"
+        );
+        state.snapshot_broadcaster.broadcast_now().await;
+    }
+
+    Ok(Json(result))
+}
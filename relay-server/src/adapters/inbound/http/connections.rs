@@ -1,32 +1,168 @@
 //! Connection endpoint handlers
 //!
-//! Provides REST API endpoints for retrieving EA connection information.
+//! Provides REST API endpoints for retrieving EA connection information
+//! (including the cached open-position list and the connection event history),
+//! configuring per-EA heartbeat timeouts and force-disconnecting a stuck EA.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 use crate::{
     adapters::inbound::http::{AppState, ProblemDetails},
-    domain::models::EaConnection,
+    adapters::outbound::messaging::WebsocketBroadcaster,
+    application::disconnection_service::RealDisconnectionService,
+    domain::models::{ConnectionEvent, EaConnection, EaType, PositionInfo, ServerEvent},
+    ports::DisconnectionService,
 };
 
-/// List all EA connections
+/// Request body for PUT /api/connections/:id/timeout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateTimeoutRequest {
+    /// Heartbeat timeout in seconds (null = use the global timeout)
+    pub timeout_seconds: Option<i64>,
+    /// Target EA type (omitted = all EAs on the account)
+    #[serde(default)]
+    pub ea_type: Option<EaType>,
+}
+
+/// EA connection as returned by the connections API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionView {
+    #[serde(flatten)]
+    pub connection: EaConnection,
+    /// Seconds since `connected_at` while the EA is up (0 when Timeout/Offline)
+    pub uptime_seconds: i64,
+}
+
+impl ConnectionView {
+    fn new(connection: EaConnection, now: DateTime<Utc>) -> Self {
+        Self {
+            uptime_seconds: connection.uptime_seconds(now),
+            connection,
+        }
+    }
+
+    fn list(connections: Vec<EaConnection>) -> Vec<Self> {
+        let now = Utc::now();
+        connections
+            .into_iter()
+            .map(|connection| Self::new(connection, now))
+            .collect()
+    }
+}
+
+/// Largest page accepted by GET /api/connections
+const MAX_CONNECTIONS_PAGE: usize = 1000;
+
+/// Query params for GET /api/connections
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConnectionsQuery {
+    /// Page size (omitted = all remaining connections)
+    pub limit: Option<usize>,
+    /// Cursor from a previous page's `next_cursor` (a bare account_id skips all its EAs)
+    pub after: Option<String>,
+}
+
+/// Response body for GET /api/connections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionsPage {
+    /// Sorted by account_id, then ea_type (Master before Slave)
+    pub items: Vec<ConnectionView>,
+    /// Pass as `after` to fetch the next page (None on the last page)
+    pub next_cursor: Option<String>,
+}
+
+/// Query params for DELETE /api/connections/:id
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ForceDisconnectQuery {
+    /// EA to disconnect (required: an account may run both a Master and a Slave)
+    pub ea_type: Option<EaType>,
+}
+
+/// Query params for GET /api/connections/:id/positions
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PositionsQuery {
+    /// EA type whose snapshot to read (default: Master, the only sender of snapshots)
+    #[serde(default)]
+    pub ea_type: Option<EaType>,
+}
+
+/// Response body for GET /api/connections/:id/positions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionPositionsResponse {
+    pub account_id: String,
+    pub ea_type: EaType,
+    /// Arrival time of the snapshot (None when no fresh snapshot is cached)
+    pub received_at: Option<DateTime<Utc>>,
+    pub positions: Vec<PositionInfo>,
+}
+
+/// Query params for GET /api/connections/:id/history
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistoryQuery {
+    /// EA whose history to read (omitted = all EAs on the account, merged by time)
+    #[serde(default)]
+    pub ea_type: Option<EaType>,
+}
+
+/// List EA connections, one page at a time
 pub async fn list_connections(
     State(state): State<AppState>,
-) -> Result<Json<Vec<EaConnection>>, ProblemDetails> {
+    Query(query): Query<ConnectionsQuery>,
+) -> Result<Json<ConnectionsPage>, ProblemDetails> {
     let span = tracing::info_span!("list_connections");
     let _enter = span.enter();
 
-    let connections = state.connection_manager.get_all_eas().await;
+    let limit = match query.limit {
+        Some(0) => {
+            return Err(
+                ProblemDetails::validation_error("limit must be greater than 0")
+                    .with_instance("/api/connections"),
+            );
+        }
+        Some(limit) => limit.min(MAX_CONNECTIONS_PAGE),
+        None => usize::MAX,
+    };
+
+    let (items, has_more) = state
+        .connection_manager
+        .get_eas_paginated(query.after.as_deref().map(parse_cursor), limit)
+        .await;
+    let next_cursor = has_more.then(|| items.last().map(encode_cursor)).flatten();
 
     tracing::info!(
-        count = connections.len(),
+        count = items.len(),
+        has_more,
         "Successfully retrieved EA connections"
     );
 
-    Ok(Json(connections))
+    Ok(Json(ConnectionsPage {
+        items: ConnectionView::list(items),
+        next_cursor,
+    }))
+}
+
+/// Cursor format: `{account_id}:{ea_type}`
+fn encode_cursor(conn: &EaConnection) -> String {
+    format!("{}:{}", conn.account_id, conn.ea_type)
+}
+
+/// Parse `{account_id}:{ea_type}`; a bare account_id resumes after its last EA (Slave)
+fn parse_cursor(cursor: &str) -> (String, EaType) {
+    cursor
+        .rsplit_once(':')
+        .and_then(|(account_id, ea_type)| {
+            ea_type
+                .parse::<EaType>()
+                .ok()
+                .map(|ea_type| (account_id.to_string(), ea_type))
+        })
+        .unwrap_or_else(|| (cursor.to_string(), EaType::Slave))
 }
 
 /// Get EA connection(s) by account ID
@@ -34,7 +170,7 @@ pub async fn list_connections(
 pub async fn get_connection(
     State(state): State<AppState>,
     Path(account_id): Path<String>,
-) -> Result<Json<Vec<EaConnection>>, ProblemDetails> {
+) -> Result<Json<Vec<ConnectionView>>, ProblemDetails> {
     let span = tracing::info_span!("get_connection", account_id = %account_id);
     let _enter = span.enter();
 
@@ -56,6 +192,204 @@ pub async fn get_connection(
             count = connections.len(),
             "Successfully retrieved EA connection(s)"
         );
-        Ok(Json(connections))
+        Ok(Json(ConnectionView::list(connections)))
+    }
+}
+
+/// Set or clear the heartbeat timeout override for an EA connection
+pub async fn update_connection_timeout(
+    State(state): State<AppState>,
+    Path(account_id): Path<String>,
+    Json(request): Json<UpdateTimeoutRequest>,
+) -> Result<Json<Vec<ConnectionView>>, ProblemDetails> {
+    let span = tracing::info_span!("update_connection_timeout", account_id = %account_id);
+    let _enter = span.enter();
+
+    let instance = format!("/api/connections/{}/timeout", account_id);
+
+    if let Some(seconds) = request.timeout_seconds {
+        if seconds <= 0 {
+            tracing::warn!(
+                account_id = %account_id,
+                timeout_seconds = seconds,
+                "Rejected invalid timeout override"
+            );
+            return Err(
+                ProblemDetails::validation_error("timeout_seconds must be greater than 0")
+                    .with_instance(instance),
+            );
+        }
     }
+
+    let connections = state
+        .connection_manager
+        .set_timeout_override(&account_id, request.ea_type, request.timeout_seconds)
+        .await;
+
+    if connections.is_empty() {
+        tracing::warn!(
+            account_id = %account_id,
+            "EA connection not found"
+        );
+        return Err(ProblemDetails::not_found("EA connection").with_instance(instance));
+    }
+
+    tracing::info!(
+        account_id = %account_id,
+        ea_type = ?request.ea_type,
+        timeout_seconds = ?request.timeout_seconds,
+        count = connections.len(),
+        "Updated heartbeat timeout override"
+    );
+
+    Ok(Json(ConnectionView::list(connections)))
+}
+
+/// Mark an EA Offline immediately (it crashed without sending Unregister)
+/// and re-evaluate dependent members exactly as a heartbeat timeout would
+pub async fn force_disconnect_connection(
+    State(state): State<AppState>,
+    Path(account_id): Path<String>,
+    Query(query): Query<ForceDisconnectQuery>,
+) -> Result<Json<ConnectionView>, ProblemDetails> {
+    let span = tracing::info_span!("force_disconnect_connection", account_id = %account_id);
+    let _enter = span.enter();
+
+    let instance = format!("/api/connections/{}", account_id);
+
+    let Some(ea_type) = query.ea_type else {
+        return Err(
+            ProblemDetails::validation_error("ea_type query parameter is required")
+                .with_instance(instance),
+        );
+    };
+
+    let Some(connection) = state
+        .connection_manager
+        .force_disconnect(&account_id, ea_type)
+        .await
+    else {
+        tracing::warn!(
+            account_id = %account_id,
+            ea_type = %ea_type,
+            "EA connection not found"
+        );
+        return Err(ProblemDetails::not_found("EA connection").with_instance(instance));
+    };
+
+    let _ = state.tx.send(ServerEvent::EaDisconnected {
+        account_id: account_id.clone(),
+    });
+
+    let disconnection_service = RealDisconnectionService::new(
+        state.connection_manager.clone(),
+        state.db.clone(),
+        state.config_sender.clone(),
+        Arc::new(WebsocketBroadcaster::new(state.tx.clone())),
+        state.runtime_status_metrics.clone(),
+    );
+    match ea_type {
+        EaType::Master => {
+            disconnection_service
+                .handle_master_offline(&account_id)
+                .await
+        }
+        EaType::Slave => {
+            disconnection_service
+                .handle_slave_offline(&account_id)
+                .await
+        }
+    }
+
+    tracing::info!(
+        account_id = %account_id,
+        ea_type = %ea_type,
+        "Force-disconnected EA connection"
+    );
+
+    Ok(Json(ConnectionView::new(connection, Utc::now())))
+}
+
+/// Get the open positions from the EA's latest PositionSnapshot
+/// Returns an empty list when no snapshot arrived within the staleness window
+pub async fn get_connection_positions(
+    State(state): State<AppState>,
+    Path(account_id): Path<String>,
+    Query(query): Query<PositionsQuery>,
+) -> Result<Json<ConnectionPositionsResponse>, ProblemDetails> {
+    let span = tracing::info_span!("get_connection_positions", account_id = %account_id);
+    let _enter = span.enter();
+
+    let ea_type = query.ea_type.unwrap_or(EaType::Master);
+    let registered = match ea_type {
+        EaType::Master => state.connection_manager.get_master(&account_id).await,
+        EaType::Slave => state.connection_manager.get_slave(&account_id).await,
+    };
+    if registered.is_none() {
+        tracing::warn!(
+            account_id = %account_id,
+            ea_type = %ea_type,
+            "EA connection not found"
+        );
+        return Err(ProblemDetails::not_found("EA connection")
+            .with_instance(format!("/api/connections/{}/positions", account_id)));
+    }
+
+    let snapshot = state
+        .connection_manager
+        .get_position_snapshot(&account_id, ea_type)
+        .await;
+
+    let response = match snapshot {
+        Some(snapshot) => ConnectionPositionsResponse {
+            account_id,
+            ea_type,
+            received_at: Some(snapshot.received_at),
+            positions: snapshot.positions,
+        },
+        None => ConnectionPositionsResponse {
+            account_id,
+            ea_type,
+            received_at: None,
+            positions: Vec::new(),
+        },
+    };
+
+    tracing::info!(
+        account_id = %response.account_id,
+        count = response.positions.len(),
+        "Successfully retrieved connection positions"
+    );
+
+    Ok(Json(response))
+}
+
+/// Get the EA's connection events (oldest first) for debugging disconnects
+pub async fn get_connection_history(
+    State(state): State<AppState>,
+    Path(account_id): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<Vec<ConnectionEvent>>, ProblemDetails> {
+    let span = tracing::info_span!("get_connection_history", account_id = %account_id);
+    let _enter = span.enter();
+
+    let events = state
+        .connection_manager
+        .get_history(&account_id, query.ea_type);
+    if events.is_empty() {
+        tracing::warn!(
+            account_id = %account_id,
+            ea_type = ?query.ea_type,
+            "No connection history found"
+        );
+        return Err(ProblemDetails::not_found("EA connection")
+            .with_instance(format!("/api/connections/{}/history", account_id)));
+    }
+
+    tracing::info!(
+        account_id = %account_id,
+        count = events.len(),
+        "Successfully retrieved connection history"
+    );
+    Ok(Json(events))
 }
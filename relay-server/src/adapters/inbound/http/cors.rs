@@ -0,0 +1,115 @@
+//! Reloadable CORS origin set
+//!
+//! The CORS layer checks request origins against a shared list that
+//! `POST /api/admin/reload-cors` can swap at runtime, so changing the Web UI
+//! port or adding a deployment origin does not require a relay restart.
+
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use axum::http::{HeaderValue, Uri};
+
+/// Allowed CORS origins, shared between the CORS layer and the reload endpoint
+#[derive(Clone, Default)]
+pub struct CorsOrigins {
+    origins: Arc<ArcSwap<Vec<HeaderValue>>>,
+}
+
+impl CorsOrigins {
+    /// Build the initial set; entries that are not valid origins are skipped with a warning
+    pub fn new(origins: Vec<String>) -> Self {
+        let parsed = origins
+            .iter()
+            .filter_map(|origin| match parse_origin(origin) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid CORS origin {:?}: {}", origin, e);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            origins: Arc::new(ArcSwap::from_pointee(parsed)),
+        }
+    }
+
+    /// Whether a request `Origin` header is allowed
+    pub fn allows(&self, origin: &HeaderValue) -> bool {
+        self.origins.load().contains(origin)
+    }
+
+    /// Current origins as strings
+    pub fn current(&self) -> Vec<String> {
+        self.origins
+            .load()
+            .iter()
+            .filter_map(|value| value.to_str().ok().map(str::to_string))
+            .collect()
+    }
+
+    /// Validate every origin, then atomically replace the set.
+    /// Nothing changes when any entry is invalid.
+    pub fn replace(&self, origins: &[String]) -> Result<(), String> {
+        if origins.is_empty() {
+            return Err("origins must not be empty".into());
+        }
+        let parsed = origins
+            .iter()
+            .map(|origin| parse_origin(origin).map_err(|e| format!("{:?}: {}", origin, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.origins.store(Arc::new(parsed));
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for CorsOrigins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.current()).finish()
+    }
+}
+
+/// An origin is `scheme://host[:port]` with no path, query or fragment
+fn parse_origin(origin: &str) -> Result<HeaderValue, String> {
+    let uri: Uri = origin.parse().map_err(|e| format!("not a URI ({})", e))?;
+    if uri.scheme().is_none() || uri.authority().is_none() {
+        return Err("scheme and host are required".into());
+    }
+    if uri.path_and_query().is_some_and(|pq| pq.as_str() != "/") || origin.ends_with('/') {
+        return Err("must not contain a path or query".into());
+    }
+    HeaderValue::from_str(origin).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_validates_all_entries() {
+        let origins = CorsOrigins::new(vec!["https://localhost:8080".to_string()]);
+        assert!(origins.allows(&HeaderValue::from_static("https://localhost:8080")));
+
+        for bad in [
+            "localhost:3000",
+            "https://app.example.com/path",
+            "not a uri",
+        ] {
+            assert!(
+                origins
+                    .replace(&["https://ok.example.com".to_string(), bad.to_string()])
+                    .is_err(),
+                "{}",
+                bad
+            );
+        }
+        assert!(origins.replace(&[]).is_err());
+        // Rejected reloads leave the set untouched
+        assert_eq!(origins.current(), vec!["https://localhost:8080"]);
+
+        origins
+            .replace(&["https://app.example.com".to_string()])
+            .unwrap();
+        assert!(origins.allows(&HeaderValue::from_static("https://app.example.com")));
+        assert!(!origins.allows(&HeaderValue::from_static("https://localhost:8080")));
+    }
+}
@@ -1,6 +1,12 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::{MasterSettings, SlaveSettings, TradeGroup, WarningCode};
+use crate::adapters::inbound::http::ProblemDetails;
+use crate::domain::models::{
+    MasterSettings, SlaveSettings, SymbolConverter, SymbolMapping, TradeGroup, TradeGroupMember,
+    TradeSignal, WarningCode,
+};
+use crate::domain::services::position_reconciler::ReconcileReport;
 use crate::domain::services::status_calculator::MasterStatusResult;
 
 /// API response view that augments TradeGroup with runtime status evaluated by the status engine.
@@ -66,3 +72,156 @@ fn default_false() -> bool {
 pub struct ToggleStatusRequest {
     pub enabled: bool,
 }
+
+/// Request body for acknowledging (muting) member warnings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcknowledgeWarningsRequest {
+    pub warning_codes: Vec<WarningCode>,
+}
+
+/// Request body for `POST /api/trade-groups/:id/members/:slave_id/close-all` (optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloseAllRequest {
+    /// Only close copies on this Slave-side symbol; None closes everything
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// Request body for `POST /api/trade-groups/:id/test-signal` (optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestSignalRequest {
+    /// Master-side symbol (default EURUSD)
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Master-side lots (default 0.01)
+    #[serde(default)]
+    pub lots: Option<f64>,
+}
+
+/// Response body for `GET /api/trade-groups/:id/stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeGroupStats {
+    pub trade_group_id: String,
+    /// Signals accepted from the Master since startup or the last reset
+    pub signals_received: u64,
+    /// Signals sent (or scheduled) to at least one member
+    pub signals_copied: u64,
+}
+
+/// Query params for `GET /api/members`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MembersQuery {
+    /// Runtime status (0=DISABLED, 1=ENABLED, 2=CONNECTED)
+    pub status: Option<i32>,
+    /// Whether the Slave EA is online
+    pub connected: Option<bool>,
+    /// Page size (omitted = all remaining members)
+    pub limit: Option<usize>,
+    /// Cursor from a previous page's `next_cursor`
+    pub after: Option<String>,
+}
+
+/// A member in `GET /api/members`, with its Master and connection state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberListItem {
+    pub master_account: String,
+    /// True when the Slave EA is online
+    pub connected: bool,
+    #[serde(flatten)]
+    pub member: TradeGroupMember,
+}
+
+/// Response body for `GET /api/members`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembersPage {
+    /// Sorted by master_account, then slave_account
+    pub items: Vec<MemberListItem>,
+    /// Pass as `after` to fetch the next page (None on the last page)
+    pub next_cursor: Option<String>,
+}
+
+/// Response body for the Master/Slave position reconciliation report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileResponse {
+    pub master_account: String,
+    pub slave_account: String,
+    /// Arrival times of the compared PositionSnapshots
+    pub master_snapshot_at: DateTime<Utc>,
+    pub slave_snapshot_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub report: ReconcileReport,
+}
+
+/// Response body for the resolved configuration the copy engine applies to a member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveConfigResponse {
+    pub master_account: String,
+    pub slave_account: String,
+    /// Runtime status and warnings evaluated by the status engine
+    pub status: i32,
+    pub warning_codes: Vec<WarningCode>,
+    /// Stored settings with serde defaults applied
+    pub slave_settings: SlaveSettings,
+    /// Prefix/suffix actually added to symbols, after heartbeat/detected fallbacks
+    pub symbol_prefix: Option<String>,
+    pub symbol_suffix: Option<String>,
+    pub symbol_converter: SymbolConverter,
+    /// Whether the Slave EA is connected (fallbacks need its heartbeat)
+    pub slave_online: bool,
+}
+
+/// Response body for a dry-run of a Master signal against one member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulateTradeResponse {
+    pub would_copy: bool,
+    /// Skip reason as recorded in the trade history (e.g. "symbol_not_allowed")
+    pub reason: Option<String>,
+    /// Set when the signal is skipped because it cannot be executed as configured
+    pub warning: Option<WarningCode>,
+    /// Signal as it would be sent to the Slave EA
+    pub transformed: Option<TradeSignal>,
+    /// Lots the Slave would end up holding for an Open, including the EA-side multiplier
+    pub expected_lots: Option<f64>,
+}
+
+/// Result of `PUT /api/trade-groups/:id/members/:slave_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMemberResponse {
+    /// False when the settings matched the stored ones (nothing saved or sent)
+    pub changed: bool,
+    /// Stored `config_version` after the request
+    pub config_version: u32,
+}
+
+/// Request body of `PUT /api/broker-profiles/:name`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBrokerProfileRequest {
+    #[serde(default)]
+    pub symbol_prefix: Option<String>,
+    #[serde(default)]
+    pub symbol_suffix: Option<String>,
+    #[serde(default)]
+    pub symbol_map: Vec<SymbolMapping>,
+}
+
+/// Response envelope for actions that also distribute config to EAs
+///
+/// `success` is false when the action was saved but a downstream step failed;
+/// `warnings` then explains what did not reach the EA.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: T,
+    #[serde(default)]
+    pub warnings: Vec<ProblemDetails>,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn with_warnings(data: T, warnings: Vec<ProblemDetails>) -> Self {
+        Self {
+            success: warnings.is_empty(),
+            data,
+            warnings,
+        }
+    }
+}
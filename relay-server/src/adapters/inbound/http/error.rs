@@ -81,6 +81,30 @@ impl ProblemDetails {
         .with_detail(detail_text)
     }
 
+    /// 認証失敗（401 Unauthorized）
+    pub fn unauthorized(detail: impl Into<String>) -> Self {
+        let detail_text = detail.into();
+        let status = StatusCode::UNAUTHORIZED;
+        Self::new(
+            "https://sankey-copier.example.com/errors/unauthorized",
+            status.canonical_reason().unwrap_or("Unauthorized"),
+            status,
+        )
+        .with_detail(detail_text)
+    }
+
+    /// リクエスト過多（429 Too Many Requests）
+    pub fn too_many_requests(detail: impl Into<String>) -> Self {
+        let detail_text = detail.into();
+        let status = StatusCode::TOO_MANY_REQUESTS;
+        Self::new(
+            "https://sankey-copier.example.com/errors/rate-limited",
+            status.canonical_reason().unwrap_or("Too Many Requests"),
+            status,
+        )
+        .with_detail(detail_text)
+    }
+
     /// バリデーションエラー（400 Bad Request）
     #[allow(dead_code)]
     pub fn validation_error(detail: impl Into<String>) -> Self {
@@ -94,6 +118,31 @@ impl ProblemDetails {
         .with_detail(detail_text)
     }
 
+    /// EAへの設定配信失敗（502 Bad Gateway）
+    /// 操作自体は成功した場合でも、レスポンスの`warnings`として返す
+    pub fn config_delivery_failed(detail: impl Into<String>) -> Self {
+        let detail_text = detail.into();
+        let status = StatusCode::BAD_GATEWAY;
+        Self::new(
+            "https://sankey-copier.example.com/errors/config-delivery",
+            "Config Delivery Failed",
+            status,
+        )
+        .with_detail(detail_text)
+    }
+
+    /// 一時的に処理できない（503 Service Unavailable）
+    pub fn service_unavailable(detail: impl Into<String>) -> Self {
+        let detail_text = detail.into();
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+        Self::new(
+            "https://sankey-copier.example.com/errors/unavailable",
+            status.canonical_reason().unwrap_or("Service Unavailable"),
+            status,
+        )
+        .with_detail(detail_text)
+    }
+
     /// 内部サーバーエラー（500 Internal Server Error）
     pub fn internal_error(detail: impl Into<String>) -> Self {
         let detail_text = detail.into();
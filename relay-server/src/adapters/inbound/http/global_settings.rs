@@ -0,0 +1,133 @@
+//! Global settings endpoint handlers
+//!
+//! Relay-wide behaviour settings stored in the `global_settings` table
+//! (`auto_enable_on_connect`, `max_lot_multiplier`, `blocked_symbols`, ...)
+//! and the default SlaveSettings new members start from.
+
+use axum::{extract::State, Json};
+
+use crate::adapters::inbound::http::{AppState, ProblemDetails};
+use crate::domain::models::{GlobalSettings, SlaveSettings};
+
+const GLOBAL_SETTINGS_INSTANCE: &str = "/api/global-settings";
+const DEFAULT_SLAVE_SETTINGS_INSTANCE: &str = "/api/global-settings/default-slave-settings";
+
+/// GET /api/global-settings
+pub async fn get_global_settings(
+    State(state): State<AppState>,
+) -> Result<Json<GlobalSettings>, ProblemDetails> {
+    let span = tracing::info_span!("get_global_settings");
+    let _enter = span.enter();
+
+    match state.db.get_global_settings().await {
+        Ok(settings) => Ok(Json(settings)),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load global settings");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+                    .with_instance(GLOBAL_SETTINGS_INSTANCE),
+            )
+        }
+    }
+}
+
+/// PUT /api/global-settings
+/// Replaces the settings; they take effect on the next heartbeat.
+/// `default_slave_settings` is kept (see `update_default_slave_settings`).
+pub async fn update_global_settings(
+    State(state): State<AppState>,
+    Json(mut settings): Json<GlobalSettings>,
+) -> Result<Json<GlobalSettings>, ProblemDetails> {
+    let span = tracing::info_span!("update_global_settings", ?settings);
+    let _enter = span.enter();
+
+    if settings.max_lot_multiplier.is_some_and(|max| max <= 0.0) {
+        return Err(
+            ProblemDetails::validation_error("max_lot_multiplier must be greater than 0")
+                .with_instance(GLOBAL_SETTINGS_INSTANCE),
+        );
+    }
+
+    settings.default_slave_settings = load(&state, GLOBAL_SETTINGS_INSTANCE)
+        .await?
+        .default_slave_settings;
+
+    match state.db.update_global_settings(&settings).await {
+        Ok(()) => {
+            tracing::info!(
+                auto_enable_on_connect = settings.auto_enable_on_connect,
+                max_lot_multiplier = ?settings.max_lot_multiplier,
+                blocked_symbols = ?settings.blocked_symbols,
+                "Updated global settings"
+            );
+            Ok(Json(settings))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save global settings");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to save global settings: {}", e))
+                    .with_instance(GLOBAL_SETTINGS_INSTANCE),
+            )
+        }
+    }
+}
+
+/// GET /api/global-settings/default-slave-settings
+/// Returns the built-in defaults when none are configured
+pub async fn get_default_slave_settings(
+    State(state): State<AppState>,
+) -> Result<Json<SlaveSettings>, ProblemDetails> {
+    let span = tracing::info_span!("get_default_slave_settings");
+    let _enter = span.enter();
+
+    let settings = load(&state, DEFAULT_SLAVE_SETTINGS_INSTANCE).await?;
+    Ok(Json(settings.default_slave_settings.unwrap_or_default()))
+}
+
+/// PUT /api/global-settings/default-slave-settings
+/// Replaces the defaults for members added afterwards; existing members are unchanged
+pub async fn update_default_slave_settings(
+    State(state): State<AppState>,
+    Json(defaults): Json<SlaveSettings>,
+) -> Result<Json<SlaveSettings>, ProblemDetails> {
+    let span = tracing::info_span!("update_default_slave_settings");
+    let _enter = span.enter();
+
+    let mut settings = load(&state, DEFAULT_SLAVE_SETTINGS_INSTANCE).await?;
+    if let Err(reason) = defaults
+        .validate()
+        .and_then(|()| settings.check_lot_multiplier(defaults.lot_multiplier))
+    {
+        tracing::warn!(reason = %reason, "Rejected invalid default slave settings");
+        return Err(
+            ProblemDetails::validation_error(reason).with_instance(DEFAULT_SLAVE_SETTINGS_INSTANCE)
+        );
+    }
+
+    settings.default_slave_settings = Some(defaults.clone());
+    match state.db.update_global_settings(&settings).await {
+        Ok(()) => {
+            tracing::info!(
+                lot_calculation_mode = ?defaults.lot_calculation_mode,
+                lot_multiplier = ?defaults.lot_multiplier,
+                "Updated default slave settings"
+            );
+            Ok(Json(defaults))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to save default slave settings");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to save global settings: {}", e))
+                    .with_instance(DEFAULT_SLAVE_SETTINGS_INSTANCE),
+            )
+        }
+    }
+}
+
+async fn load(state: &AppState, instance: &str) -> Result<GlobalSettings, ProblemDetails> {
+    state.db.get_global_settings().await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to load global settings");
+        ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+            .with_instance(instance)
+    })
+}
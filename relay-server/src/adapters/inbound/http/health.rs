@@ -0,0 +1,62 @@
+//! Liveness and readiness probes for process supervisors
+//!
+//! Served by their own router so they stay outside CORS and any API middleware.
+
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::adapters::infrastructure::readiness::Readiness;
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub zmq_receiver: bool,
+    pub database: bool,
+}
+
+/// Router for `GET /health` and `GET /ready`
+pub fn health_router(readiness: Arc<Readiness>) -> Router {
+    Router::new()
+        .route("/health", get(get_health))
+        .route("/ready", get(get_ready))
+        .with_state(readiness)
+}
+
+/// 200 whenever the HTTP task is serving requests; `status` is "degraded"
+/// while the relay runs without its database
+async fn get_health(State(readiness): State<Arc<Readiness>>) -> Json<HealthResponse> {
+    let status = if readiness.is_database_degraded() {
+        "degraded"
+    } else {
+        "ok"
+    };
+    Json(HealthResponse { status })
+}
+
+/// 200 once the ZMQ receiver is bound and the database answers, 503 otherwise
+async fn get_ready(
+    State(readiness): State<Arc<Readiness>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let zmq_receiver = readiness.is_zmq_receiver_bound();
+    let database = readiness.is_database_ready().await;
+    let ready = zmq_receiver && database;
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ReadinessResponse {
+            ready,
+            zmq_receiver,
+            database,
+        }),
+    )
+}
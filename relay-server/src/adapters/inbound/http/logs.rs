@@ -2,21 +2,49 @@
 //!
 //! Provides REST API endpoint for retrieving server logs.
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Serialize;
 
 use crate::adapters::inbound::http::{AppState, ProblemDetails};
+use crate::adapters::infrastructure::log_buffer::{LogEntry, LogQuery};
 
 /// Get server logs from the log buffer
+/// Query params: level (minimum), contains, account, limit
 pub async fn get_logs(
     State(state): State<AppState>,
-) -> Result<Json<Vec<crate::adapters::infrastructure::log_buffer::LogEntry>>, ProblemDetails> {
+    Query(query): Query<LogQuery>,
+) -> Result<Json<Vec<LogEntry>>, ProblemDetails> {
     let span = tracing::info_span!("get_logs");
     let _enter = span.enter();
 
     let buffer = state.log_buffer.read().await;
-    let logs: Vec<_> = buffer.iter().cloned().collect();
+    let logs = query.apply(buffer.iter()).map_err(|level| {
+        tracing::warn!(%level, "Rejected log query with invalid level");
+        ProblemDetails::validation_error(format!("Invalid log level: '{}'", level))
+            .with_instance("/api/logs")
+    })?;
 
     tracing::debug!(count = logs.len(), "Successfully retrieved server logs");
 
     Ok(Json(logs))
 }
+
+/// Occupancy of the in-memory log buffer
+#[derive(Debug, Serialize)]
+pub struct LogBufferStats {
+    /// Entries currently held
+    pub size: usize,
+    /// Maximum entries kept (`[logging] buffer_capacity`)
+    pub capacity: usize,
+}
+
+/// Get the current size and capacity of the log buffer
+pub async fn get_log_buffer_stats(State(state): State<AppState>) -> Json<LogBufferStats> {
+    Json(LogBufferStats {
+        size: state.log_buffer.read().await.len(),
+        capacity: state.log_buffer.capacity(),
+    })
+}
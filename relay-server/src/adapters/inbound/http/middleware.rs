@@ -1,14 +1,22 @@
 //! Middleware functions for the Relay Server API
 //!
 //! Provides middleware for adding Private Network Access (PNA) headers
-//! to enable HTTPS pages to access local network resources.
+//! to enable HTTPS pages to access local network resources, and optional
+//! bearer-token authentication for the API and WebSocket.
 
 use axum::{
     body::Body,
-    http::{header::HeaderValue, Request},
+    extract::{Query, State},
+    http::{
+        header::{self, HeaderValue},
+        Request,
+    },
     middleware,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use serde::Deserialize;
+
+use super::{AppState, ProblemDetails};
 
 /// Middleware to add PNA (Private Network Access) headers
 ///
@@ -26,3 +34,69 @@ pub async fn add_pna_headers(request: Request<Body>, next: middleware::Next) ->
 
     response
 }
+
+/// Query string accepted on `/ws` (browsers cannot set headers on WebSocket upgrades)
+#[derive(Deserialize)]
+struct WsTokenQuery {
+    token: Option<String>,
+}
+
+/// Middleware enforcing `[security] api_token` on `/api/*` and `/ws`
+///
+/// Passes everything through when no token is configured. `/ws` also accepts
+/// the token as `?token=<token>`.
+pub async fn require_api_token(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: middleware::Next,
+) -> Response {
+    let Some(expected) = state.config.security.api_token() else {
+        return next.run(request).await;
+    };
+
+    let path = request.uri().path();
+    let is_websocket = path == "/ws";
+    if !is_websocket && !path.starts_with("/api/") {
+        return next.run(request).await;
+    }
+
+    let header_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let token = header_token.or_else(|| {
+        is_websocket
+            .then(|| Query::<WsTokenQuery>::try_from_uri(request.uri()).ok())
+            .flatten()
+            .and_then(|Query(query)| query.token)
+    });
+
+    if token.is_some_and(|token| tokens_match(token.trim(), expected)) {
+        return next.run(request).await;
+    }
+
+    tracing::warn!(
+        method = %request.method(),
+        path = %path,
+        "Rejected API request without a valid bearer token"
+    );
+    let mut response = ProblemDetails::unauthorized("A valid bearer token is required")
+        .with_instance(path)
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    response
+}
+
+/// Compare tokens without short-circuiting on the first differing byte
+fn tokens_match(actual: &str, expected: &str) -> bool {
+    actual.len() == expected.len()
+        && actual
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
@@ -11,11 +11,20 @@ mod trade_group_members;
 pub mod trade_groups;
 
 // New submodules for modular structure
+mod admin;
+mod broker_profiles;
+mod config_transfer;
 mod connections;
+mod cors;
 pub mod dtos;
+mod global_settings;
+mod health;
 mod logs;
 mod middleware;
+mod rate_limit;
 mod runtime_metrics;
+mod tls_status;
+mod trade_history;
 mod victoria_logs_settings;
 mod websocket;
 mod zeromq_settings;
@@ -24,8 +33,11 @@ mod zeromq_settings;
 pub(crate) mod test_helpers;
 
 // Public re-exports
+pub use cors::CorsOrigins;
 pub use error::ProblemDetails;
+pub use health::health_router;
 pub use middleware::*;
+pub use rate_limit::RateLimiter;
 pub use runtime_metrics::get_runtime_metrics;
 
 use axum::{
@@ -34,37 +46,47 @@ use axum::{
     Router,
 };
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tower_http::cors::CorsLayer;
-use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
+use tokio::sync::{broadcast, mpsc};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::sensitive_headers::SetSensitiveRequestHeadersLayer;
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
 use tower_http::LatencyUnit;
 
 pub use websocket::SnapshotBroadcaster;
 
 use crate::{
     adapters::infrastructure::connection_manager::ConnectionManager,
+    adapters::infrastructure::mt_catalog::MtInstallationCatalog,
+    adapters::outbound::messaging::ZmqMessage,
     adapters::outbound::observability::victoria_logs::VLogsController,
     adapters::{
         infrastructure::log_buffer::LogBuffer, infrastructure::port_resolver::ResolvedPorts,
-        outbound::messaging::ZmqConfigPublisher, outbound::persistence::Database,
+        outbound::persistence::Database,
     },
     application::runtime_status_updater::RuntimeStatusMetrics,
     config::Config,
+    domain::models::ServerEvent,
+    ports::ConfigPublisher,
 };
 
 // Import handlers from submodules
-use connections::{get_connection, list_connections};
-use logs::get_logs;
+use connections::{
+    force_disconnect_connection, get_connection, get_connection_history, get_connection_positions,
+    list_connections, update_connection_timeout,
+};
+use logs::{get_log_buffer_stats, get_logs};
+use trade_history::get_trade_history;
 use websocket::websocket_handler;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Arc<Database>,
-    pub tx: broadcast::Sender<String>,
+    pub tx: broadcast::Sender<ServerEvent>,
     pub connection_manager: Arc<ConnectionManager>,
-    pub config_sender: Arc<ZmqConfigPublisher>,
+    pub config_sender: Arc<dyn ConfigPublisher>,
     pub log_buffer: LogBuffer,
-    pub allowed_origins: Vec<String>,
+    /// CORS origins (reloadable via POST /api/admin/reload-cors)
+    pub allowed_origins: CorsOrigins,
     pub cors_disabled: bool,
     pub config: Arc<Config>,
     /// Resolved ZeroMQ ports (may be dynamically assigned)
@@ -75,9 +97,38 @@ pub struct AppState {
     pub runtime_status_metrics: Arc<RuntimeStatusMetrics>,
     /// On-demand snapshot broadcaster for WebSocket clients
     pub snapshot_broadcaster: SnapshotBroadcaster,
+    /// Detected + manually configured MT installations (POST /api/mt-installations/rescan)
+    pub mt_installations: Arc<MtInstallationCatalog>,
+    /// Queue of the ZeroMQ message handler for synthetic trade signals
+    /// (POST /api/trade-groups/:id/test-signal). Weak so shutdown can still
+    /// drain the queue once the ZeroMQ server is gone; None = not available.
+    pub signal_injector: Option<mpsc::WeakUnboundedSender<ZmqMessage>>,
+}
+
+impl AppState {
+    /// Notify WebSocket clients that a config did not reach an EA and build the
+    /// warning returned alongside the (otherwise successful) API response
+    pub(crate) fn config_delivery_failed(
+        &self,
+        trade_group_id: &str,
+        account_id: &str,
+        error: &anyhow::Error,
+    ) -> ProblemDetails {
+        let detail = format!("Failed to send config to {}: {}", account_id, error);
+        let _ = self.tx.send(ServerEvent::ConfigDeliveryFailed {
+            trade_group_id: trade_group_id.to_string(),
+            account_id: account_id.to_string(),
+            detail: detail.clone(),
+        });
+        ProblemDetails::config_delivery_failed(detail)
+    }
 }
 
 pub fn create_router(state: AppState) -> Router {
+    if state.config.security.api_token().is_some() {
+        tracing::info!("API token authentication enabled for /api/* and /ws");
+    }
+
     // Create CORS layer - either permissive (all origins) or restricted based on config
     let cors = if state.cors_disabled {
         // CORS disabled: allow all origins (development mode)
@@ -86,15 +137,12 @@ pub fn create_router(state: AppState) -> Router {
         );
         CorsLayer::permissive()
     } else {
-        // CORS enabled: restrict to configured origins
+        // CORS enabled: restrict to configured origins (looked up per request so reloads apply)
+        let origins = state.allowed_origins.clone();
         CorsLayer::new()
-            .allow_origin(
-                state
-                    .allowed_origins
-                    .iter()
-                    .filter_map(|origin| origin.parse().ok())
-                    .collect::<Vec<_>>(),
-            )
+            .allow_origin(AllowOrigin::predicate(move |origin, _| {
+                origins.allows(origin)
+            }))
             .allow_methods([
                 axum::http::Method::GET,
                 axum::http::Method::POST,
@@ -111,16 +159,21 @@ pub fn create_router(state: AppState) -> Router {
 
     // Create HTTP tracing layer for request/response logging
     // Use DEBUG level to reduce log volume (API requests are frequent)
+    // Only the path is logged: the query may carry the WebSocket token
     let trace_layer = TraceLayer::new_for_http()
-        .make_span_with(
-            DefaultMakeSpan::new()
-                .level(tracing::Level::DEBUG)
-                .include_headers(true),
-        )
+        .make_span_with(|request: &axum::http::Request<_>| {
+            tracing::debug_span!(
+                "request",
+                method = %request.method(),
+                uri = %request.uri().path(),
+                version = ?request.version(),
+                headers = ?request.headers(),
+            )
+        })
         .on_request(|request: &axum::http::Request<_>, _span: &tracing::Span| {
             tracing::debug!(
                 method = %request.method(),
-                uri = %request.uri(),
+                uri = %request.uri().path(),
                 version = ?request.version(),
                 "HTTP request started"
             );
@@ -132,20 +185,70 @@ pub fn create_router(state: AppState) -> Router {
                 .include_headers(true),
         );
 
-    Router::new()
+    let router = Router::new()
         .route("/api/connections", get(list_connections))
-        .route("/api/connections/:id", get(get_connection))
+        .route(
+            "/api/connections/:id",
+            get(get_connection).delete(force_disconnect_connection),
+        )
+        .route(
+            "/api/connections/:id/timeout",
+            axum::routing::put(update_connection_timeout),
+        )
+        .route(
+            "/api/connections/:id/positions",
+            get(get_connection_positions),
+        )
+        .route("/api/connections/:id/history", get(get_connection_history))
         .route("/api/logs", get(get_logs))
+        .route("/api/logs/stats", get(get_log_buffer_stats))
+        .route("/api/trade-history", get(get_trade_history))
+        // Config export/import API (all TradeGroups as one JSON document)
+        .route("/api/config/export", get(config_transfer::export_config))
+        .route("/api/config/import", post(config_transfer::import_config))
+        // Online SQLite backup into [database] backup_dir
+        .route("/api/admin/backup", post(admin::create_backup))
+        // Swap the CORS origin set without a restart
+        .route("/api/admin/reload-cors", post(admin::reload_cors))
+        // Clear the per-TradeGroup signal counters
+        .route("/api/admin/reset-stats", post(admin::reset_stats))
+        // Relay-wide settings
+        .route(
+            "/api/global-settings",
+            get(global_settings::get_global_settings).put(global_settings::update_global_settings),
+        )
+        .route(
+            "/api/global-settings/default-slave-settings",
+            get(global_settings::get_default_slave_settings)
+                .put(global_settings::update_default_slave_settings),
+        )
+        // Broker profiles (shared symbol prefix/suffix and mappings)
+        .route(
+            "/api/broker-profiles",
+            get(broker_profiles::list_broker_profiles).post(broker_profiles::create_broker_profile),
+        )
+        .route(
+            "/api/broker-profiles/:name",
+            get(broker_profiles::get_broker_profile)
+                .put(broker_profiles::update_broker_profile)
+                .delete(broker_profiles::delete_broker_profile),
+        )
         .route("/ws", get(websocket_handler))
         // MT installations API
         .route(
             "/api/mt-installations",
             get(mt_installations::list_mt_installations),
         )
+        .route(
+            "/api/mt-installations/rescan",
+            post(mt_installations::rescan_mt_installations),
+        )
         .route(
             "/api/mt-installations/:id/install",
             post(mt_installations::install_to_mt),
         )
+        // Members of all TradeGroups (ops dashboard)
+        .route("/api/members", get(trade_group_members::list_all_members))
         // TradeGroups API (Master settings)
         .route(
             "/api/trade-groups",
@@ -161,6 +264,14 @@ pub fn create_router(state: AppState) -> Router {
             "/api/trade-groups/:id/toggle",
             post(trade_groups::toggle_master),
         )
+        .route(
+            "/api/trade-groups/:id/test-signal",
+            post(trade_groups::inject_test_signal),
+        )
+        .route(
+            "/api/trade-groups/:id/stats",
+            get(trade_groups::get_trade_group_stats),
+        )
         // TradeGroupMembers API (Slave settings)
         .route(
             "/api/trade-groups/:id/members",
@@ -176,6 +287,26 @@ pub fn create_router(state: AppState) -> Router {
             "/api/trade-groups/:id/members/:slave_id/toggle",
             post(trade_group_members::toggle_member_status),
         )
+        .route(
+            "/api/trade-groups/:id/members/:slave_id/close-all",
+            post(trade_group_members::close_all_member_positions),
+        )
+        .route(
+            "/api/trade-groups/:id/members/:slave_id/warnings/ack",
+            post(trade_group_members::acknowledge_member_warnings),
+        )
+        .route(
+            "/api/trade-groups/:id/members/:slave_id/reconcile",
+            get(trade_group_members::reconcile_member_positions),
+        )
+        .route(
+            "/api/trade-groups/:id/members/:slave_id/effective-config",
+            get(trade_group_members::get_member_effective_config),
+        )
+        .route(
+            "/api/trade-groups/:id/members/:slave_id/simulate",
+            post(trade_group_members::simulate_member_trade),
+        )
         // VictoriaLogs API
         // GET /api/victoria-logs-config: Returns config.toml settings (read-only) + current enabled state
         .route(
@@ -197,7 +328,34 @@ pub fn create_router(state: AppState) -> Router {
             "/api/runtime-status-metrics",
             get(runtime_metrics::get_runtime_metrics),
         )
+        .route("/api/tls-status", get(tls_status::get_tls_status));
+
+    // Rate limit POST/PUT/DELETE per client IP ([security] requests_per_minute, 0 = off)
+    let router = match state.config.security.requests_per_minute {
+        0 => router,
+        requests_per_minute => {
+            tracing::info!(
+                "Rate limiting mutating API requests to {}/min per client",
+                requests_per_minute
+            );
+            router.layer(axum_middleware::from_fn_with_state(
+                Arc::new(RateLimiter::new(requests_per_minute)),
+                rate_limit::limit_mutating_requests,
+            ))
+        }
+    };
+
+    router
+        // Token check sits inside CORS so preflights are answered and 401s carry CORS headers
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            require_api_token,
+        ))
         .layer(trace_layer)
+        // Marked before tracing sees the request so the bearer token is logged as "Sensitive"
+        .layer(SetSensitiveRequestHeadersLayer::new([
+            axum::http::header::AUTHORIZATION,
+        ]))
         .layer(cors)
         // PNA headers must be added after CORS layer (outermost) so they are included
         // in CORS preflight responses. Axum layers are applied outside-in for requests
@@ -4,40 +4,49 @@ use axum::{
 };
 use std::path::PathBuf;
 
-use crate::adapters::infrastructure::mt_detector::MtDetector;
 use crate::adapters::infrastructure::mt_installer::MtInstaller;
-use crate::domain::models::{DetectionSummary, EaPortConfig, MtInstallationsResponse};
+use crate::domain::models::{
+    DetectionSummary, EaPortConfig, MtInstallation, MtInstallationsResponse,
+};
 
 use super::{AppState, ProblemDetails};
 
-/// MT4/MT5インストール一覧を取得（レジストリ検出）
+/// MT4/MT5インストール一覧を取得（初回のみレジストリ検出、以降はキャッシュ）
 pub async fn list_mt_installations(
     State(state): State<AppState>,
 ) -> Result<Json<MtInstallationsResponse>, ProblemDetails> {
     let span = tracing::info_span!("list_mt_installations");
     let _enter = span.enter();
 
-    // Windowsレジストリから MT4/MT5 を検出
-    let detector = MtDetector::new();
-    let mut installations = match detector.detect() {
-        Ok(installs) => {
-            tracing::info!(
-                count = installs.len(),
-                "Successfully detected MT installations"
-            );
-            installs
-        }
-        Err(e) => {
-            tracing::error!(
-                error = %e,
-                error_type = std::any::type_name_of_val(&e),
-                backtrace = ?std::backtrace::Backtrace::capture(),
-                "Failed to detect MT installations from registry"
-            );
-            Vec::new()
-        }
-    };
+    let installations = state.mt_installations.list();
+    Ok(Json(installations_response(&state, installations)))
+}
+
+/// MT4/MT5を再検出（手動追加パスはマージして保持）
+pub async fn rescan_mt_installations(
+    State(state): State<AppState>,
+) -> Result<Json<MtInstallationsResponse>, ProblemDetails> {
+    let span = tracing::info_span!("rescan_mt_installations");
+    let _enter = span.enter();
 
+    let installations = state.mt_installations.rescan().map_err(|e| {
+        tracing::error!(
+            error = %e,
+            error_type = std::any::type_name_of_val(&e),
+            backtrace = ?std::backtrace::Backtrace::capture(),
+            "Failed to rescan MT installations"
+        );
+        ProblemDetails::internal_error(format!("Failed to detect MT4/MT5 installations: {}", e))
+            .with_instance("/api/mt-installations/rescan")
+    })?;
+    Ok(Json(installations_response(&state, installations)))
+}
+
+/// ポートミスマッチを判定してレスポンスを組み立てる
+fn installations_response(
+    state: &AppState,
+    mut installations: Vec<MtInstallation>,
+) -> MtInstallationsResponse {
     // サーバーの期待ポート設定 (2-port architecture)
     let server_ports = EaPortConfig {
         receiver_port: state.resolved_ports.receiver_port,
@@ -70,14 +79,12 @@ pub async fn list_mt_installations(
         "MT installations detection summary"
     );
 
-    let response = MtInstallationsResponse {
+    MtInstallationsResponse {
         success: true,
         data: installations,
         detection_summary: DetectionSummary { total_found },
         server_ports: Some(server_ports),
-    };
-
-    Ok(Json(response))
+    }
 }
 
 /// MT4/MT5にコンポーネントをインストール
@@ -93,35 +100,12 @@ pub async fn install_to_mt(
         "Installation request received"
     );
 
-    // レジストリから MT4/MT5 を検出して該当のものを探す
-    let detector = MtDetector::new();
-    let installations = match detector.detect() {
-        Ok(installs) => installs,
-        Err(e) => {
-            tracing::error!(
-                installation_id = %id,
-                error = %e,
-                error_type = std::any::type_name_of_val(&e),
-                backtrace = ?std::backtrace::Backtrace::capture(),
-                "Failed to detect MT installations for install operation"
-            );
-            return Err(ProblemDetails::internal_error(format!(
-                "Failed to detect MT4/MT5 installations: {}",
-                e
-            ))
-            .with_instance(format!("/api/mt-installations/{}/install", id)));
-        }
-    };
-
-    // IDに一致するインストールを探す
-    let installation = installations.iter().find(|i| i.id == id);
-
-    let installation = match installation {
+    // 検出済み（手動追加を含む）の一覧から該当のものを探す
+    let installation = match state.mt_installations.find(&id) {
         Some(inst) => inst,
         None => {
             tracing::warn!(
                 installation_id = %id,
-                available_ids = ?installations.iter().map(|i| &i.id).collect::<Vec<_>>(),
                 "MT installation not found"
             );
             return Err(ProblemDetails::not_found("MT4/MT5 installation")
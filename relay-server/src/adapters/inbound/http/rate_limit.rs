@@ -0,0 +1,168 @@
+//! Rate limiting for mutating API requests
+//!
+//! A token bucket per client IP caps POST/PUT/DELETE requests so a misbehaving
+//! UI or script cannot flood EAs with config messages. GET and WebSocket
+//! requests are never limited.
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{header, HeaderValue, Method, Request},
+    middleware,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::ProblemDetails;
+
+/// Buckets kept before idle (full) ones are pruned
+const PRUNE_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Token buckets keyed by client IP (capacity = requests per minute)
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    /// Tokens added per second
+    refill_rate: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        let capacity = f64::from(requests_per_minute.max(1));
+        Self {
+            capacity,
+            refill_rate: capacity / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take one token for `client`. Returns the wait until the next token on rejection.
+    pub fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| self.refilled(*bucket, now) < self.capacity);
+        }
+
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: self.capacity,
+            updated_at: now,
+        });
+        bucket.tokens = self.refilled(*bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_rate,
+            ))
+        }
+    }
+
+    fn refilled(&self, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        (bucket.tokens + elapsed.as_secs_f64() * self.refill_rate).min(self.capacity)
+    }
+}
+
+/// Middleware rejecting POST/PUT/DELETE requests over the client's budget with 429
+pub async fn limit_mutating_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request<Body>,
+    next: middleware::Next,
+) -> Response {
+    if !matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::DELETE
+    ) {
+        return next.run(request).await;
+    }
+
+    // ConnectInfo is absent when the router is not served with connect info (tests)
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+
+    match limiter.check(client) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!(
+                client = %client,
+                method = %request.method(),
+                path = %request.uri().path(),
+                "Rate limit exceeded for mutating API request"
+            );
+            let mut response =
+                ProblemDetails::too_many_requests("Too many configuration changes, retry later")
+                    .with_instance(request.uri().path())
+                    .into_response();
+            let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            if let Ok(value) = HeaderValue::from_str(&seconds.to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, value);
+            }
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    #[test]
+    fn test_bucket_exhausts_and_refills() {
+        let limiter = RateLimiter::new(60); // one token per second
+        let start = Instant::now();
+
+        for _ in 0..60 {
+            assert!(limiter.check_at(CLIENT, start).is_ok());
+        }
+        let retry_after = limiter.check_at(CLIENT, start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        // Half a token is not enough
+        assert!(limiter
+            .check_at(CLIENT, start + Duration::from_millis(500))
+            .is_err());
+        assert!(limiter
+            .check_at(CLIENT, start + Duration::from_secs(1))
+            .is_ok());
+
+        // A full minute restores the whole bucket but never more
+        let later = start + Duration::from_secs(600);
+        for _ in 0..60 {
+            assert!(limiter.check_at(CLIENT, later).is_ok());
+        }
+        assert!(limiter.check_at(CLIENT, later).is_err());
+    }
+
+    #[test]
+    fn test_clients_have_separate_buckets() {
+        let limiter = RateLimiter::new(1);
+        let now = Instant::now();
+        assert!(limiter.check_at(CLIENT, now).is_ok());
+        assert!(limiter.check_at(CLIENT, now).is_err());
+        assert!(limiter
+            .check_at(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), now)
+            .is_ok());
+    }
+}
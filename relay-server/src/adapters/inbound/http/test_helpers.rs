@@ -11,7 +11,7 @@ use crate::{
     adapters::infrastructure::connection_manager::ConnectionManager,
     adapters::outbound::observability::victoria_logs::VLogsController,
     adapters::{
-        inbound::http::{AppState, SnapshotBroadcaster},
+        inbound::http::{AppState, CorsOrigins, SnapshotBroadcaster},
         infrastructure::log_buffer::LogBuffer,
         infrastructure::port_resolver::ResolvedPorts,
         outbound::{messaging::ZmqConfigPublisher, persistence::Database},
@@ -54,6 +54,7 @@ pub(crate) async fn create_test_app_state_with_vlogs(vlogs_configured: bool) ->
             flush_interval_secs: 5,
             source: "test-relay".to_string(),
             log_level: "INFO".to_string(),
+            max_buffered_batches: 100,
         };
         Some(VLogsController::new(enabled_flag, vlogs_config))
     } else {
@@ -79,12 +80,14 @@ pub(crate) async fn create_test_app_state_with_vlogs(vlogs_configured: bool) ->
         connection_manager,
         config_sender,
         log_buffer,
-        allowed_origins: vec![],
+        allowed_origins: CorsOrigins::default(),
         cors_disabled: true,
         config,
         resolved_ports,
         vlogs_controller,
         runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
         snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
     }
 }
@@ -0,0 +1,21 @@
+//! GET /api/tls-status: validity of the HTTPS certificate in use
+
+use axum::{extract::State, Json};
+use chrono::Utc;
+
+use super::{AppState, ProblemDetails};
+use crate::adapters::infrastructure::cert::{certificate_status, CertificateStatus};
+
+/// Read `[tls] cert_path` and report its validity period
+pub async fn get_tls_status(
+    State(state): State<AppState>,
+) -> Result<Json<CertificateStatus>, ProblemDetails> {
+    let tls = &state.config.tls;
+    let cert_path = std::env::current_dir()
+        .map_err(|e| ProblemDetails::internal_error(e.to_string()))?
+        .join(&tls.cert_path);
+
+    let status = certificate_status(&cert_path, tls.expiry_warning_days, Utc::now())
+        .map_err(|e| ProblemDetails::internal_error(format!("{:#}", e)))?;
+    Ok(Json(status))
+}
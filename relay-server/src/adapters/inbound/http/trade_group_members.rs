@@ -4,19 +4,36 @@
 // Provides Slave EA configuration endpoints for Web UI.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
-use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage};
+use sankey_copier_zmq::{CloseAllMessage, MasterConfigMessage, SlaveConfigMessage};
+use std::sync::Arc;
 // use serde::{Deserialize, Serialize};
 
-use crate::application::runtime_status_updater::RuntimeStatusUpdater;
-use crate::domain::models::{SlaveSettings, TradeGroupMember, STATUS_NO_CONFIG};
+use crate::adapters::outbound::persistence::Database;
+use crate::application::runtime_status_updater::{effective_slave_settings, RuntimeStatusUpdater};
+use crate::domain::models::{
+    ConnectionStatus, EaConnection, EaType, MasterSettings, MemberListFilter, ServerEvent,
+    SlaveSettings, SymbolConverter, TradeAction, TradeGroupMember, TradeSignal, WarningCode,
+    STATUS_CONNECTED, STATUS_NO_CONFIG,
+};
+use crate::domain::services::copy_engine::{
+    portfolio_weight_total, CopyContext, CopyDecision, CopyEngine,
+};
+use crate::domain::services::position_reconciler::reconcile_positions;
 use crate::domain::services::status_calculator::SlaveRuntimeTarget;
 
 use super::{AppState, ProblemDetails};
-use crate::adapters::inbound::http::dtos::{AddMemberRequest, ToggleStatusRequest};
+use crate::adapters::inbound::http::dtos::{
+    AcknowledgeWarningsRequest, AddMemberRequest, ApiResponse, CloseAllRequest,
+    EffectiveConfigResponse, MemberListItem, MembersPage, MembersQuery, ReconcileResponse,
+    SimulateTradeResponse, ToggleStatusRequest, UpdateMemberResponse,
+};
+
+/// Largest page accepted by GET /api/members
+const MAX_MEMBERS_PAGE: usize = 1000;
 
 /// List all members (Slaves) for a TradeGroup
 pub async fn list_members(
@@ -37,7 +54,7 @@ pub async fn list_members(
             let runtime_updater = runtime_status_updater_for(&state);
             let mut hydrated = Vec::with_capacity(members.len());
             for member in members {
-                hydrated.push(hydrate_member_runtime(&runtime_updater, member).await);
+                hydrated.push(hydrate_member_runtime(&state.db, &runtime_updater, member).await);
             }
 
             Ok(Json(hydrated))
@@ -59,12 +76,116 @@ pub async fn list_members(
     }
 }
 
+/// List members of all TradeGroups, one page at a time
+///
+/// `status` matches the runtime status last persisted by the status engine;
+/// returned members carry their freshly evaluated status.
+pub async fn list_all_members(
+    State(state): State<AppState>,
+    Query(query): Query<MembersQuery>,
+) -> Result<Json<MembersPage>, ProblemDetails> {
+    let span = tracing::info_span!("list_all_members");
+    let _enter = span.enter();
+
+    let limit = match query.limit {
+        Some(0) => {
+            return Err(
+                ProblemDetails::validation_error("limit must be greater than 0")
+                    .with_instance("/api/members"),
+            );
+        }
+        Some(limit) => limit.min(MAX_MEMBERS_PAGE),
+        None => usize::MAX,
+    };
+
+    let online_slaves: Vec<String> = state
+        .connection_manager
+        .get_all_eas()
+        .await
+        .into_iter()
+        .filter(|conn| conn.ea_type == EaType::Slave && conn.status == ConnectionStatus::Online)
+        .map(|conn| conn.account_id)
+        .collect();
+    let filter = MemberListFilter {
+        status: query.status,
+        connected: query.connected,
+        online_slaves,
+    };
+
+    let (members, has_more) = match state
+        .db
+        .get_members_page(
+            &filter,
+            query.after.as_deref().map(parse_member_cursor),
+            limit,
+        )
+        .await
+    {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to list members from database");
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve members from database: {}",
+                e
+            ))
+            .with_instance("/api/members"));
+        }
+    };
+    let next_cursor = has_more
+        .then(|| members.last().map(encode_member_cursor))
+        .flatten();
+
+    let runtime_updater = runtime_status_updater_for(&state);
+    let mut items = Vec::with_capacity(members.len());
+    for member in members {
+        let member = hydrate_member_runtime(&state.db, &runtime_updater, member).await;
+        items.push(MemberListItem {
+            master_account: member.trade_group_id.clone(),
+            connected: filter.online_slaves.contains(&member.slave_account),
+            member,
+        });
+    }
+
+    tracing::info!(
+        count = items.len(),
+        has_more,
+        "Successfully retrieved members of all trade groups"
+    );
+
+    Ok(Json(MembersPage { items, next_cursor }))
+}
+
+/// Cursor format: `{master_account}:{slave_account}`
+fn encode_member_cursor(member: &TradeGroupMember) -> String {
+    format!("{}:{}", member.trade_group_id, member.slave_account)
+}
+
+/// Parse `{master_account}:{slave_account}`; a bare master_account starts at its first member
+fn parse_member_cursor(cursor: &str) -> (&str, &str) {
+    cursor.split_once(':').unwrap_or((cursor, ""))
+}
+
 /// Add a new member (Slave) to a TradeGroup
+///
+/// The given `slave_settings` fields are merged over
+/// `global_settings.default_slave_settings` when defaults are configured.
 pub async fn add_member(
     State(state): State<AppState>,
     Path(trade_group_id): Path<String>,
-    Json(request): Json<AddMemberRequest>,
+    Json(mut body): Json<serde_json::Value>,
 ) -> Result<(StatusCode, Json<TradeGroupMember>), ProblemDetails> {
+    let instance = format!("/api/trade-groups/{}/members", trade_group_id);
+    let overrides = body
+        .as_object_mut()
+        .and_then(|body| body.remove("slave_settings"));
+    let mut request: AddMemberRequest = serde_json::from_value(body).map_err(|e| {
+        ProblemDetails::validation_error(format!("Invalid request body: {}", e))
+            .with_instance(instance.clone())
+    })?;
+    request.slave_settings = new_member_settings(&state, overrides)
+        .await
+        .map_err(|problem| problem.with_instance(instance.clone()))?;
+
     let span = tracing::info_span!(
         "add_member",
         trade_group_id = %trade_group_id,
@@ -103,6 +224,17 @@ pub async fn add_member(
         }
     }
 
+    validate_slave_settings(
+        &state,
+        &trade_group_id,
+        &request.slave_account,
+        &request.slave_settings,
+    )
+    .await
+    .map_err(|problem| {
+        problem.with_instance(format!("/api/trade-groups/{}/members", trade_group_id))
+    })?;
+
     // Map enabled flag to status code (0=DISABLED, 2=CONNECTED/enabled)
     let status = if request.enabled { 2 } else { 0 };
 
@@ -132,15 +264,16 @@ pub async fn add_member(
             {
                 Ok(Some(member)) => {
                     let runtime_updater = runtime_status_updater_for(&state);
-                    let hydrated_member = hydrate_member_runtime(&runtime_updater, member).await;
+                    let hydrated_member =
+                        hydrate_member_runtime(&state.db, &runtime_updater, member).await;
 
                     // Send config to Slave EA via ZMQ
                     send_config_to_slave(&state, &trade_group_id, &hydrated_member).await;
 
                     // Notify via WebSocket
-                    if let Ok(json) = serde_json::to_string(&hydrated_member) {
-                        let _ = state.tx.send(format!("member_added:{}", json));
-                    }
+                    let _ = state.tx.send(ServerEvent::MemberAdded {
+                        data: hydrated_member.clone(),
+                    });
 
                     Ok((StatusCode::CREATED, Json(hydrated_member)))
                 }
@@ -226,7 +359,7 @@ pub async fn get_member(
                 "Successfully retrieved member"
             );
             let runtime_updater = runtime_status_updater_for(&state);
-            let hydrated_member = hydrate_member_runtime(&runtime_updater, member).await;
+            let hydrated_member = hydrate_member_runtime(&state.db, &runtime_updater, member).await;
             Ok(Json(hydrated_member))
         }
         Ok(None) => {
@@ -265,12 +398,78 @@ pub async fn get_member(
     }
 }
 
+/// Slave settings for a new member: the request's fields over the configured defaults
+async fn new_member_settings(
+    state: &AppState,
+    overrides: Option<serde_json::Value>,
+) -> Result<SlaveSettings, ProblemDetails> {
+    let global_settings = state.db.get_global_settings().await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to load global settings");
+        ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+    })?;
+    let overrides = overrides.unwrap_or(serde_json::Value::Null);
+    let settings = match global_settings.default_slave_settings {
+        Some(defaults) => SlaveSettings::with_defaults(&defaults, overrides),
+        None if overrides.is_null() => Ok(SlaveSettings::default()),
+        None => serde_json::from_value(overrides),
+    };
+    settings.map_err(|e| ProblemDetails::validation_error(format!("Invalid slave_settings: {}", e)))
+}
+
+/// Validate slave settings, including the relay-wide `max_lot_multiplier` cap
+async fn validate_slave_settings(
+    state: &AppState,
+    trade_group_id: &str,
+    slave_account: &str,
+    settings: &SlaveSettings,
+) -> Result<(), ProblemDetails> {
+    let global_settings = state.db.get_global_settings().await.map_err(|e| {
+        tracing::error!(error = %e, "Failed to load global settings");
+        ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+    })?;
+
+    if let Some(name) = settings.broker_profile.as_deref() {
+        match state.db.get_broker_profile(name).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err(ProblemDetails::validation_error(format!(
+                    "Broker profile not found: {}",
+                    name
+                )))
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load broker profile");
+                return Err(ProblemDetails::internal_error(format!(
+                    "Failed to load broker profile: {}",
+                    e
+                )));
+            }
+        }
+    }
+
+    settings
+        .validate()
+        .and_then(|()| global_settings.check_lot_multiplier(settings.lot_multiplier))
+        .map_err(|reason| {
+            tracing::warn!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                reason = %reason,
+                "Rejected invalid slave settings"
+            );
+            ProblemDetails::validation_error(reason)
+        })
+}
+
 /// Update member settings
+///
+/// Settings identical to the stored ones (ignoring `config_version`) are not
+/// saved or sent to the Slave EA, and the response reports `changed: false`.
 pub async fn update_member(
     State(state): State<AppState>,
     Path((trade_group_id, slave_account)): Path<(String, String)>,
     Json(settings): Json<SlaveSettings>,
-) -> Result<StatusCode, ProblemDetails> {
+) -> Result<Json<ApiResponse<UpdateMemberResponse>>, ProblemDetails> {
     let span = tracing::info_span!(
         "update_member",
         trade_group_id = %trade_group_id,
@@ -278,9 +477,50 @@ pub async fn update_member(
     );
     let _enter = span.enter();
 
-    // Increment config_version for the update
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}",
+        trade_group_id, slave_account
+    );
+    validate_slave_settings(&state, &trade_group_id, &slave_account, &settings)
+        .await
+        .map_err(|problem| problem.with_instance(instance.clone()))?;
+
+    let stored = match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(member)) => member.slave_settings,
+        Ok(None) => {
+            return Err(
+                ProblemDetails::not_found(format!("Member not found: {}", slave_account))
+                    .with_instance(instance),
+            );
+        }
+        Err(e) => {
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to load member settings: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    // Identical saves neither bump the version nor make the EA reload
+    if stored.same_config(&settings) {
+        tracing::info!(
+            trade_group_id = %trade_group_id,
+            slave_account = %slave_account,
+            config_version = stored.config_version,
+            "Member settings unchanged; skipping update"
+        );
+        return Ok(Json(ApiResponse::with_warnings(
+            UpdateMemberResponse {
+                changed: false,
+                config_version: stored.config_version,
+            },
+            Vec::new(),
+        )));
+    }
+
     let mut updated_settings = settings;
-    updated_settings.config_version += 1;
+    updated_settings.config_version = stored.config_version + 1;
 
     match state
         .db
@@ -300,20 +540,29 @@ pub async fn update_member(
             );
 
             // Retrieve updated member for ZMQ notification
+            let mut warnings = Vec::new();
             if let Ok(Some(member)) = state.db.get_member(&trade_group_id, &slave_account).await {
                 let runtime_updater = runtime_status_updater_for(&state);
-                let hydrated_member = hydrate_member_runtime(&runtime_updater, member).await;
+                let hydrated_member =
+                    hydrate_member_runtime(&state.db, &runtime_updater, member).await;
 
                 // Send updated config to Slave EA via ZMQ
-                send_config_to_slave(&state, &trade_group_id, &hydrated_member).await;
+                warnings
+                    .extend(send_config_to_slave(&state, &trade_group_id, &hydrated_member).await);
 
                 // Notify via WebSocket
-                if let Ok(json) = serde_json::to_string(&hydrated_member) {
-                    let _ = state.tx.send(format!("member_updated:{}", json));
-                }
+                let _ = state.tx.send(ServerEvent::MemberUpdated {
+                    data: hydrated_member.clone(),
+                });
             }
 
-            Ok(StatusCode::NO_CONTENT)
+            Ok(Json(ApiResponse::with_warnings(
+                UpdateMemberResponse {
+                    changed: true,
+                    config_version: updated_settings.config_version,
+                },
+                warnings,
+            )))
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -329,10 +578,7 @@ pub async fn update_member(
                     "Member not found: {}",
                     slave_account
                 ))
-                .with_instance(format!(
-                    "/api/trade-groups/{}/members/{}",
-                    trade_group_id, slave_account
-                )));
+                .with_instance(instance));
             }
 
             tracing::error!(
@@ -345,21 +591,20 @@ pub async fn update_member(
             );
             Err(
                 ProblemDetails::internal_error(format!("Failed to update member settings: {}", e))
-                    .with_instance(format!(
-                        "/api/trade-groups/{}/members/{}",
-                        trade_group_id, slave_account
-                    )),
+                    .with_instance(instance),
             )
         }
     }
 }
 
 /// Toggle member status (ENABLED ↔ DISABLED)
+///
+/// Returns the updated member; `warnings` lists config that could not be sent to the Slave EA.
 pub async fn toggle_member_status(
     State(state): State<AppState>,
     Path((trade_group_id, slave_account)): Path<(String, String)>,
     Json(request): Json<ToggleStatusRequest>,
-) -> Result<StatusCode, ProblemDetails> {
+) -> Result<Json<ApiResponse<TradeGroupMember>>, ProblemDetails> {
     let span = tracing::info_span!(
         "toggle_member_status",
         trade_group_id = %trade_group_id,
@@ -385,20 +630,44 @@ pub async fn toggle_member_status(
             );
 
             // Retrieve updated member for ZMQ notification
-            if let Ok(Some(member)) = state.db.get_member(&trade_group_id, &slave_account).await {
-                let runtime_updater = runtime_status_updater_for(&state);
-                let hydrated_member = hydrate_member_runtime(&runtime_updater, member).await;
+            let member = match state.db.get_member(&trade_group_id, &slave_account).await {
+                Ok(Some(member)) => member,
+                Ok(None) => {
+                    return Err(ProblemDetails::not_found(format!(
+                        "Member not found: {}",
+                        slave_account
+                    ))
+                    .with_instance(format!(
+                        "/api/trade-groups/{}/members/{}/toggle",
+                        trade_group_id, slave_account
+                    )));
+                }
+                Err(e) => {
+                    return Err(ProblemDetails::internal_error(format!(
+                        "Failed to retrieve member from database: {}",
+                        e
+                    ))
+                    .with_instance(format!(
+                        "/api/trade-groups/{}/members/{}/toggle",
+                        trade_group_id, slave_account
+                    )));
+                }
+            };
+            let runtime_updater = runtime_status_updater_for(&state);
+            let hydrated_member = hydrate_member_runtime(&state.db, &runtime_updater, member).await;
 
-                // Send updated config to Slave EA via ZMQ (with updated status)
-                send_config_to_slave(&state, &trade_group_id, &hydrated_member).await;
+            // Send updated config to Slave EA via ZMQ (with updated status)
+            let warnings = send_config_to_slave(&state, &trade_group_id, &hydrated_member)
+                .await
+                .into_iter()
+                .collect();
 
-                // Notify via WebSocket
-                if let Ok(json) = serde_json::to_string(&hydrated_member) {
-                    let _ = state.tx.send(format!("member_status_changed:{}", json));
-                }
-            }
+            // Notify via WebSocket
+            let _ = state.tx.send(ServerEvent::MemberStatusChanged {
+                data: hydrated_member.clone(),
+            });
 
-            Ok(StatusCode::NO_CONTENT)
+            Ok(Json(ApiResponse::with_warnings(hydrated_member, warnings)))
         }
         Err(e) => {
             let error_msg = e.to_string();
@@ -439,6 +708,567 @@ pub async fn toggle_member_status(
     }
 }
 
+/// Acknowledge (mute) warnings for a member
+///
+/// Only currently active warnings can be acknowledged; others are ignored.
+/// Acknowledged warnings stay in the payload with `muted: true`.
+pub async fn acknowledge_member_warnings(
+    State(state): State<AppState>,
+    Path((trade_group_id, slave_account)): Path<(String, String)>,
+    Json(request): Json<AcknowledgeWarningsRequest>,
+) -> Result<Json<TradeGroupMember>, ProblemDetails> {
+    let span = tracing::info_span!(
+        "acknowledge_member_warnings",
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account
+    );
+    let _enter = span.enter();
+
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}/warnings/ack",
+        trade_group_id, slave_account
+    );
+
+    let member = match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            return Err(ProblemDetails::not_found(format!(
+                "Member '{}' not found in TradeGroup '{}'",
+                slave_account, trade_group_id
+            ))
+            .with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                error = %e,
+                "Failed to retrieve member for warning acknowledgment"
+            );
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve member from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    let runtime_updater = runtime_status_updater_for(&state);
+    let mut member = hydrate_member_runtime(&state.db, &runtime_updater, member).await;
+
+    for code in request.warning_codes {
+        if member.warning_codes.contains(&code) && !member.acknowledged_warnings.contains(&code) {
+            member.acknowledged_warnings.push(code);
+        }
+    }
+
+    if let Err(e) = state
+        .db
+        .update_member_acknowledged_warnings(
+            &trade_group_id,
+            &slave_account,
+            &member.acknowledged_warnings,
+        )
+        .await
+    {
+        tracing::error!(
+            trade_group_id = %trade_group_id,
+            slave_account = %slave_account,
+            error = %e,
+            "Failed to store acknowledged warnings"
+        );
+        return Err(ProblemDetails::internal_error(format!(
+            "Failed to store acknowledged warnings: {}",
+            e
+        ))
+        .with_instance(instance));
+    }
+
+    let warning_codes = std::mem::take(&mut member.warning_codes);
+    member.apply_warning_codes(warning_codes);
+
+    tracing::info!(
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account,
+        acknowledged = ?member.acknowledged_warnings,
+        "Acknowledged member warnings"
+    );
+
+    // Notify via WebSocket
+    let _ = state.tx.send(ServerEvent::MemberUpdated {
+        data: member.clone(),
+    });
+
+    Ok(Json(member))
+}
+
+/// Resolved configuration the copy engine applies to a member
+///
+/// Read-only view for debugging: stored settings with defaults applied, the
+/// symbol prefix/suffix after heartbeat fallbacks, the resulting
+/// `SymbolConverter`, and the current runtime status and warnings.
+pub async fn get_member_effective_config(
+    State(state): State<AppState>,
+    Path((trade_group_id, slave_account)): Path<(String, String)>,
+) -> Result<Json<EffectiveConfigResponse>, ProblemDetails> {
+    let span = tracing::info_span!(
+        "get_member_effective_config",
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account
+    );
+    let _enter = span.enter();
+
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}/effective-config",
+        trade_group_id, slave_account
+    );
+
+    let member = match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            return Err(ProblemDetails::not_found(format!(
+                "Member '{}' not found in TradeGroup '{}'",
+                slave_account, trade_group_id
+            ))
+            .with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                error = %e,
+                "Failed to retrieve member for effective config"
+            );
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve member from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    let master_settings = match state.db.get_trade_group(&trade_group_id).await {
+        Ok(Some(tg)) => tg.master_settings,
+        Ok(None) => MasterSettings::default(),
+        Err(e) => {
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve TradeGroup from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    let runtime_updater = runtime_status_updater_for(&state);
+    let mut member = hydrate_member_runtime(&state.db, &runtime_updater, member).await;
+    member.slave_settings = effective_slave_settings(state.db.as_ref(), &member.slave_settings)
+        .await
+        .into_owned();
+
+    let slave_conn = state.connection_manager.get_slave(&slave_account).await;
+    let converter = symbol_converter_for(
+        &state,
+        &master_settings,
+        &member.slave_settings,
+        slave_conn.as_ref(),
+    );
+
+    Ok(Json(EffectiveConfigResponse {
+        master_account: trade_group_id,
+        slave_account,
+        status: member.status,
+        warning_codes: member.warning_codes,
+        slave_settings: member.slave_settings,
+        symbol_prefix: converter.prefix_add.clone(),
+        symbol_suffix: converter.suffix_add.clone(),
+        symbol_converter: converter,
+        slave_online: slave_conn.is_some(),
+    }))
+}
+
+/// Dry-run a Master signal against one member
+///
+/// Applies the same filters and transformation as live copying and returns the
+/// result without publishing anything. The member is evaluated as if it were
+/// enabled and connected, so a Slave can be previewed before it is turned on.
+pub async fn simulate_member_trade(
+    State(state): State<AppState>,
+    Path((trade_group_id, slave_account)): Path<(String, String)>,
+    Json(mut signal): Json<TradeSignal>,
+) -> Result<Json<SimulateTradeResponse>, ProblemDetails> {
+    let span = tracing::info_span!(
+        "simulate_member_trade",
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account
+    );
+    let _enter = span.enter();
+
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}/simulate",
+        trade_group_id, slave_account
+    );
+
+    let mut member = match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            return Err(ProblemDetails::not_found(format!(
+                "Member '{}' not found in TradeGroup '{}'",
+                slave_account, trade_group_id
+            ))
+            .with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                error = %e,
+                "Failed to retrieve member for simulation"
+            );
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve member from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+    member.status = STATUS_CONNECTED;
+    member.slave_settings = effective_slave_settings(state.db.as_ref(), &member.slave_settings)
+        .await
+        .into_owned();
+
+    let master_settings = match state.db.get_trade_group(&trade_group_id).await {
+        Ok(Some(tg)) => tg.master_settings,
+        Ok(None) => MasterSettings::default(),
+        Err(e) => {
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve TradeGroup from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    let global_settings = state.db.get_global_settings().await.map_err(|e| {
+        ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+            .with_instance(instance.clone())
+    })?;
+
+    signal.source_account = trade_group_id.clone();
+    let skipped = |reason: &str, warning: Option<WarningCode>| SimulateTradeResponse {
+        would_copy: false,
+        reason: Some(reason.to_string()),
+        warning,
+        transformed: None,
+        expected_lots: None,
+    };
+
+    if !master_settings.allows_action(&signal.action) {
+        return Ok(Json(skipped("master_closes_only", None)));
+    }
+
+    let members = state.db.get_members(&trade_group_id).await.map_err(|e| {
+        ProblemDetails::internal_error(format!("Failed to load members: {}", e))
+            .with_instance(instance.clone())
+    })?;
+
+    // Same context as live trade copying (no per-ticket tracking for a preview)
+    let engine = CopyEngine::new().with_symbol_specs(Arc::new(state.config.symbol_spec_registry()));
+    let cm = &state.connection_manager;
+    let master_conn = cm.get_master(&trade_group_id).await;
+    let slave_conn = cm.get_slave(&slave_account).await;
+    let context = CopyContext {
+        master_equity: master_conn.map(|conn| conn.equity),
+        slave_equity: slave_conn.as_ref().map(|conn| conn.equity),
+        slave_balance: slave_conn.as_ref().map(|conn| conn.balance),
+        slave_open_positions: slave_conn
+            .as_ref()
+            .map(|conn| conn.open_positions.max(0) as u32),
+        slave_daily_loss: slave_conn
+            .as_ref()
+            .and_then(|conn| conn.daily_loss(chrono::Utc::now())),
+        slave_position_lots: None,
+        slave_position_symbol: None,
+        slave_position_order_type: None,
+        pip_value: signal
+            .symbol
+            .as_deref()
+            .map(|symbol| engine.symbol_specs().spec(symbol).pip_value),
+        max_lot_multiplier: global_settings.max_lot_multiplier,
+        global_blocked_symbols: global_settings.blocked_symbols.clone(),
+        portfolio_weight_total: portfolio_weight_total(&members),
+        total_lot_budget: master_settings.total_lot_budget,
+    };
+
+    match engine.should_copy_trade(&signal, &member, &context) {
+        CopyDecision::Copy => {}
+        CopyDecision::Skip(reason) => return Ok(Json(skipped(reason.as_str(), None))),
+        CopyDecision::SkipWithWarning(code) => {
+            let reason = serde_json::to_value(&code)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            return Ok(Json(skipped(&reason, Some(code))));
+        }
+    }
+
+    let converter = symbol_converter_for(
+        &state,
+        &master_settings,
+        &member.slave_settings,
+        slave_conn.as_ref(),
+    );
+    let expected_lots = if signal.action == TradeAction::Open {
+        engine.expected_slave_lots(&signal, &member, &context)
+    } else {
+        None
+    };
+    let transformed = engine
+        .transform_signal(signal, &member, &converter, &context)
+        .map_err(|e| {
+            ProblemDetails::validation_error(format!("Cannot transform signal: {}", e))
+                .with_instance(instance.clone())
+        })?;
+
+    Ok(Json(SimulateTradeResponse {
+        would_copy: true,
+        reason: None,
+        warning: None,
+        transformed: Some(transformed),
+        expected_lots,
+    }))
+}
+
+/// Compare the Master's and the Slave's latest PositionSnapshots
+///
+/// Reports Master positions the Slave is missing, Slave copies whose Master position
+/// is gone, and copies whose lot differs from the converted Master lot.
+/// Returns 409 when either side has no fresh snapshot.
+pub async fn reconcile_member_positions(
+    State(state): State<AppState>,
+    Path((trade_group_id, slave_account)): Path<(String, String)>,
+) -> Result<Json<ReconcileResponse>, ProblemDetails> {
+    let span = tracing::info_span!(
+        "reconcile_member_positions",
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account
+    );
+    let _enter = span.enter();
+
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}/reconcile",
+        trade_group_id, slave_account
+    );
+
+    let mut member = match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(member)) => member,
+        Ok(None) => {
+            return Err(ProblemDetails::not_found(format!(
+                "Member '{}' not found in TradeGroup '{}'",
+                slave_account, trade_group_id
+            ))
+            .with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                error = %e,
+                "Failed to retrieve member for reconciliation"
+            );
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve member from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+    member.slave_settings = effective_slave_settings(state.db.as_ref(), &member.slave_settings)
+        .await
+        .into_owned();
+
+    let master_settings = match state.db.get_trade_group(&trade_group_id).await {
+        Ok(Some(tg)) => tg.master_settings,
+        Ok(None) => MasterSettings::default(),
+        Err(e) => {
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve TradeGroup from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    };
+
+    let global_settings = state.db.get_global_settings().await.map_err(|e| {
+        ProblemDetails::internal_error(format!("Failed to load global settings: {}", e))
+            .with_instance(instance.clone())
+    })?;
+
+    let cm = &state.connection_manager;
+    let Some(master_snapshot) = cm
+        .get_position_snapshot(&trade_group_id, EaType::Master)
+        .await
+    else {
+        return Err(ProblemDetails::conflict(format!(
+            "No recent PositionSnapshot from master '{}'",
+            trade_group_id
+        ))
+        .with_instance(instance));
+    };
+    let Some(slave_snapshot) = cm
+        .get_position_snapshot(&slave_account, EaType::Slave)
+        .await
+    else {
+        return Err(ProblemDetails::conflict(format!(
+            "No recent PositionSnapshot from slave '{}'",
+            slave_account
+        ))
+        .with_instance(instance));
+    };
+
+    let members = state.db.get_members(&trade_group_id).await.map_err(|e| {
+        ProblemDetails::internal_error(format!("Failed to load members: {}", e))
+            .with_instance(instance.clone())
+    })?;
+
+    // Same symbol conversion and lot context as live trade copying
+    let master_conn = cm.get_master(&trade_group_id).await;
+    let slave_conn = cm.get_slave(&slave_account).await;
+    let converter = symbol_converter_for(
+        &state,
+        &master_settings,
+        &member.slave_settings,
+        slave_conn.as_ref(),
+    );
+    let context = CopyContext {
+        master_equity: master_conn.map(|conn| conn.equity),
+        slave_equity: slave_conn.as_ref().map(|conn| conn.equity),
+        slave_balance: slave_conn.as_ref().map(|conn| conn.balance),
+        slave_open_positions: None,
+        slave_daily_loss: None,
+        slave_position_lots: None,
+        slave_position_symbol: None,
+        slave_position_order_type: None,
+        pip_value: None,
+        max_lot_multiplier: global_settings.max_lot_multiplier,
+        global_blocked_symbols: global_settings.blocked_symbols.clone(),
+        portfolio_weight_total: portfolio_weight_total(&members),
+        total_lot_budget: master_settings.total_lot_budget,
+    };
+
+    let engine = CopyEngine::new().with_symbol_specs(Arc::new(state.config.symbol_spec_registry()));
+    let report = reconcile_positions(
+        &engine,
+        &master_snapshot.positions,
+        &slave_snapshot.positions,
+        &member,
+        &converter,
+        &context,
+        |symbol| engine.symbol_specs().spec(symbol).pip_value,
+    );
+
+    tracing::info!(
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account,
+        missing = report.missing.len(),
+        extra = report.extra.len(),
+        lot_mismatch = report.lot_mismatch.len(),
+        "Reconciled member positions"
+    );
+
+    Ok(Json(ReconcileResponse {
+        master_account: trade_group_id,
+        slave_account,
+        master_snapshot_at: master_snapshot.received_at,
+        slave_snapshot_at: slave_snapshot.received_at,
+        report,
+    }))
+}
+
+/// Ask the Slave EA to close every position it copied (optionally one symbol only)
+///
+/// The command is published on the Slave's config topic; the EA must be
+/// connected to receive it.
+pub async fn close_all_member_positions(
+    State(state): State<AppState>,
+    Path((trade_group_id, slave_account)): Path<(String, String)>,
+    body: Option<Json<CloseAllRequest>>,
+) -> Result<(StatusCode, Json<CloseAllMessage>), ProblemDetails> {
+    let span = tracing::info_span!(
+        "close_all_member_positions",
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account
+    );
+    let _enter = span.enter();
+
+    let instance = format!(
+        "/api/trade-groups/{}/members/{}/close-all",
+        trade_group_id, slave_account
+    );
+
+    match state.db.get_member(&trade_group_id, &slave_account).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(ProblemDetails::not_found(format!(
+                "Member '{}' not found in TradeGroup '{}'",
+                slave_account, trade_group_id
+            ))
+            .with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(
+                trade_group_id = %trade_group_id,
+                slave_account = %slave_account,
+                error = %e,
+                "Failed to retrieve member for close-all"
+            );
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve member from database: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    }
+
+    let symbol = body
+        .and_then(|Json(request)| request.symbol)
+        .map(|symbol| symbol.trim().to_string());
+    if symbol.as_deref() == Some("") {
+        return Err(
+            ProblemDetails::validation_error("symbol must not be empty").with_instance(instance)
+        );
+    }
+
+    let message = CloseAllMessage {
+        message_type: "CloseAll".to_string(),
+        slave_account: slave_account.clone(),
+        symbol,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Err(e) = state.config_sender.send_close_all(&message).await {
+        tracing::error!(
+            trade_group_id = %trade_group_id,
+            slave_account = %slave_account,
+            error = %e,
+            "Failed to send close-all command"
+        );
+        return Err(ProblemDetails::config_delivery_failed(format!(
+            "Failed to send close-all command to '{}': {}",
+            slave_account, e
+        ))
+        .with_instance(instance));
+    }
+
+    tracing::info!(
+        trade_group_id = %trade_group_id,
+        slave_account = %slave_account,
+        symbol = ?message.symbol,
+        "Sent close-all command"
+    );
+
+    Ok((StatusCode::ACCEPTED, Json(message)))
+}
+
 /// Delete a member
 pub async fn delete_member(
     State(state): State<AppState>,
@@ -497,13 +1327,10 @@ pub async fn delete_member(
             }
 
             // Notify via WebSocket
-            let event = serde_json::json!({
-                "trade_group_id": trade_group_id,
-                "slave_account": slave_account,
+            let _ = state.tx.send(ServerEvent::MemberDeleted {
+                trade_group_id: trade_group_id.clone(),
+                slave_account: slave_account.clone(),
             });
-            if let Ok(json) = serde_json::to_string(&event) {
-                let _ = state.tx.send(format!("member_deleted:{}", json));
-            }
 
             Ok(StatusCode::NO_CONTENT)
         }
@@ -527,6 +1354,28 @@ pub async fn delete_member(
     }
 }
 
+/// Symbol conversion as applied to live trade copying: Slave prefix/suffix fall
+/// back to the heartbeat-reported, then detected values when settings leave them unset
+fn symbol_converter_for(
+    state: &AppState,
+    master_settings: &MasterSettings,
+    slave_settings: &SlaveSettings,
+    slave_conn: Option<&EaConnection>,
+) -> SymbolConverter {
+    let converter = SymbolConverter::from_settings(master_settings, slave_settings);
+    match slave_conn {
+        Some(conn) => converter.with_auto_mapping(
+            state.config.symbol_mapping.synonym_groups.clone(),
+            conn.symbol_context
+                .as_ref()
+                .map(|ctx| ctx.available_special_symbols.clone()),
+            conn.fallback_symbol_prefix(),
+            conn.fallback_symbol_suffix(),
+        ),
+        None => converter,
+    }
+}
+
 fn runtime_status_updater_for(state: &AppState) -> RuntimeStatusUpdater {
     RuntimeStatusUpdater::with_metrics(
         state.db.clone(),
@@ -536,6 +1385,7 @@ fn runtime_status_updater_for(state: &AppState) -> RuntimeStatusUpdater {
 }
 
 async fn hydrate_member_runtime(
+    db: &Database,
     runtime_updater: &RuntimeStatusUpdater,
     member: TradeGroupMember,
 ) -> TradeGroupMember {
@@ -551,10 +1401,31 @@ async fn hydrate_member_runtime(
         .await;
 
     member.status = status_result.status;
-    member.warning_codes = status_result.warning_codes;
+    if member.apply_warning_codes(status_result.warning_codes) {
+        persist_acknowledged_warnings(db, &member).await;
+    }
     member
 }
 
+/// Store pruned acknowledgments so a cleared warning is raised again when it returns
+async fn persist_acknowledged_warnings(db: &Database, member: &TradeGroupMember) {
+    if let Err(e) = db
+        .update_member_acknowledged_warnings(
+            &member.trade_group_id,
+            &member.slave_account,
+            &member.acknowledged_warnings,
+        )
+        .await
+    {
+        tracing::warn!(
+            trade_group_id = %member.trade_group_id,
+            slave_account = %member.slave_account,
+            error = %e,
+            "Failed to persist acknowledged warnings"
+        );
+    }
+}
+
 /// Send disabled config (status=0) to Slave EA via ZMQ to remove config
 async fn send_disabled_config_to_slave(
     state: &AppState,
@@ -568,6 +1439,7 @@ async fn send_disabled_config_to_slave(
         lot_calculation_mode: sankey_copier_zmq::LotCalculationMode::default(),
         lot_multiplier: None,
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -576,6 +1448,7 @@ async fn send_disabled_config_to_slave(
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         timestamp: chrono::Utc::now().timestamp_millis(),
         trade_group_id: master_account.to_string(),
         // Open Sync Policy defaults
@@ -593,13 +1466,14 @@ async fn send_disabled_config_to_slave(
         warning_codes: Vec::new(),
     };
 
-    if let Err(e) = state.config_sender.send(&config).await {
+    if let Err(e) = state.config_sender.send_slave_config(&config).await {
         tracing::error!(
             slave_account = %slave_account,
             master_account = %master_account,
             error = %e,
             "Failed to send disabled SlaveConfigMessage via ZMQ"
         );
+        state.config_delivery_failed(master_account, slave_account, &e);
     } else {
         tracing::info!(
             slave_account = %slave_account,
@@ -621,12 +1495,13 @@ async fn send_removed_config_to_master(state: &AppState, master_account: &str) {
         warning_codes: Vec::new(),
     };
 
-    if let Err(e) = state.config_sender.send(&config).await {
+    if let Err(e) = state.config_sender.send_master_config(&config).await {
         tracing::error!(
             master_account = %master_account,
             error = %e,
             "Failed to send REMOVED MasterConfigMessage via ZMQ"
         );
+        state.config_delivery_failed(master_account, master_account, &e);
     } else {
         tracing::info!(
             master_account = %master_account,
@@ -636,7 +1511,12 @@ async fn send_removed_config_to_master(state: &AppState, master_account: &str) {
 }
 
 /// Send Slave config to Slave EA via ZMQ
-async fn send_config_to_slave(state: &AppState, master_account: &str, member: &TradeGroupMember) {
+/// Returns a warning when the config could not be published
+pub(super) async fn send_config_to_slave(
+    state: &AppState,
+    master_account: &str,
+    member: &TradeGroupMember,
+) -> Option<ProblemDetails> {
     let runtime_updater = runtime_status_updater_for(state);
     let slave_bundle = runtime_updater
         .build_slave_bundle(SlaveRuntimeTarget {
@@ -650,22 +1530,27 @@ async fn send_config_to_slave(state: &AppState, master_account: &str, member: &T
     let slave_status = slave_bundle.status_result.status;
     let config = slave_bundle.config;
 
-    if let Err(e) = state.config_sender.send(&config).await {
-        tracing::error!(
-            slave_account = %member.slave_account,
-            master_account = %master_account,
-            config_version = member.slave_settings.config_version,
-            error = %e,
-            "Failed to send SlaveConfigMessage via ZMQ"
-        );
-    } else {
-        tracing::info!(
-            slave_account = %member.slave_account,
-            master_account = %master_account,
-            config_version = member.slave_settings.config_version,
-            "Successfully sent SlaveConfigMessage via ZMQ"
-        );
-    }
+    let warning = match state.config_sender.send_slave_config(&config).await {
+        Ok(()) => {
+            tracing::info!(
+                slave_account = %member.slave_account,
+                master_account = %master_account,
+                config_version = member.slave_settings.config_version,
+                "Successfully sent SlaveConfigMessage via ZMQ"
+            );
+            None
+        }
+        Err(e) => {
+            tracing::error!(
+                slave_account = %member.slave_account,
+                master_account = %master_account,
+                config_version = member.slave_settings.config_version,
+                error = %e,
+                "Failed to send SlaveConfigMessage via ZMQ"
+            );
+            Some(state.config_delivery_failed(master_account, &member.slave_account, &e))
+        }
+    };
 
     if let Err(e) = state
         .db
@@ -680,4 +1565,6 @@ async fn send_config_to_slave(state: &AppState, master_account: &str, member: &T
             "Failed to persist Slave runtime status"
         );
     }
+
+    warning
 }
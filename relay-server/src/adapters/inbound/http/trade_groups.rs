@@ -10,16 +10,22 @@ use axum::{
 };
 // use serde::Serialize;
 
-use crate::application::runtime_status_updater::RuntimeStatusUpdater;
+use crate::adapters::outbound::messaging::ZmqMessage;
+use crate::application::runtime_status_updater::{effective_slave_settings, RuntimeStatusUpdater};
 use crate::config_builder::{ConfigBuilder, MasterConfigContext, SlaveConfigContext};
-use crate::domain::models::{MasterSettings, SlaveConfigWithMaster, TradeGroup};
+use crate::domain::models::{
+    MasterSettings, OrderType, ServerEvent, SlaveConfigWithMaster, TradeAction, TradeGroup,
+    TradeSignal,
+};
 use crate::domain::services::status_calculator::SlaveRuntimeTarget;
 use crate::domain::services::status_calculator::{
     evaluate_master_status, ConnectionSnapshot, MasterIntent, MasterStatusResult, SlaveIntent,
 };
 
 use super::{AppState, ProblemDetails};
-use crate::adapters::inbound::http::dtos::{ToggleMasterRequest, TradeGroupRuntimeView};
+use crate::adapters::inbound::http::dtos::{
+    TestSignalRequest, ToggleMasterRequest, TradeGroupRuntimeView, TradeGroupStats,
+};
 
 /// List all TradeGroups (Master accounts and their settings)
 pub async fn list_trade_groups(
@@ -127,6 +133,7 @@ pub async fn update_trade_group_settings(
                 config_version = updated_settings.config_version,
                 symbol_prefix = ?updated_settings.symbol_prefix,
                 symbol_suffix = ?updated_settings.symbol_suffix,
+                closes_only = updated_settings.closes_only,
                 "Successfully updated Master settings"
             );
 
@@ -211,6 +218,134 @@ pub async fn delete_trade_group(
     }
 }
 
+/// Inject a synthetic Open signal from the TradeGroup's Master
+/// POST /api/trade-groups/{id}/test-signal
+///
+/// The signal is queued to the ZeroMQ message handler, so it is filtered,
+/// transformed and published to the Slaves exactly like a real Master signal.
+/// Returns the injected signal (202: processing happens asynchronously).
+pub async fn inject_test_signal(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    body: Option<Json<TestSignalRequest>>,
+) -> Result<(StatusCode, Json<TradeSignal>), ProblemDetails> {
+    let span = tracing::info_span!("inject_test_signal", master_account = %id);
+    let _enter = span.enter();
+    let instance = format!("/api/trade-groups/{}/test-signal", id);
+
+    let request = body.map(|Json(request)| request).unwrap_or_default();
+    let symbol = request.symbol.unwrap_or_else(|| "EURUSD".to_string());
+    if symbol.trim().is_empty() {
+        return Err(
+            ProblemDetails::validation_error("symbol must not be empty").with_instance(instance)
+        );
+    }
+    let lots = request.lots.unwrap_or(0.01);
+    if !lots.is_finite() || lots <= 0.0 {
+        return Err(
+            ProblemDetails::validation_error("lots must be greater than 0").with_instance(instance),
+        );
+    }
+
+    match state.db.get_trade_group(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(ProblemDetails::not_found("TradeGroup").with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(master_account = %id, error = %e, "Failed to load TradeGroup");
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve TradeGroup: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    }
+
+    let Some(injector) = state
+        .signal_injector
+        .as_ref()
+        .and_then(|injector| injector.upgrade())
+    else {
+        return Err(
+            ProblemDetails::service_unavailable("Trade signal processing is not running")
+                .with_instance(instance),
+        );
+    };
+
+    let now = chrono::Utc::now();
+    let signal = TradeSignal {
+        action: TradeAction::Open,
+        // Millisecond timestamps are far above real MT ticket numbers
+        ticket: now.timestamp_millis(),
+        symbol: Some(symbol),
+        order_type: Some(OrderType::Buy),
+        lots: Some(lots),
+        open_price: None,
+        stop_loss: None,
+        take_profit: None,
+        magic_number: None,
+        comment: Some("TEST".to_string()),
+        timestamp: now,
+        source_account: id.clone(),
+        close_ratio: None,
+        // 0 = unnumbered, so the Master's sequence check is not affected
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
+    };
+
+    if injector
+        .send(ZmqMessage::TradeSignal(signal.clone()))
+        .is_err()
+    {
+        return Err(
+            ProblemDetails::service_unavailable("Trade signal processing is not running")
+                .with_instance(instance),
+        );
+    }
+
+    tracing::info!(
+        master_account = %id,
+        ticket = signal.ticket,
+        "Injected test signal"
+    );
+    Ok((StatusCode::ACCEPTED, Json(signal)))
+}
+
+/// Signals received and copied for a TradeGroup
+/// GET /api/trade-groups/{id}/stats
+pub async fn get_trade_group_stats(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<TradeGroupStats>, ProblemDetails> {
+    let span = tracing::info_span!("get_trade_group_stats", master_account = %id);
+    let _enter = span.enter();
+    let instance = format!("/api/trade-groups/{}/stats", id);
+
+    match state.db.get_trade_group(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Err(ProblemDetails::not_found("TradeGroup").with_instance(instance));
+        }
+        Err(e) => {
+            tracing::error!(master_account = %id, error = %e, "Failed to load TradeGroup");
+            return Err(ProblemDetails::internal_error(format!(
+                "Failed to retrieve TradeGroup: {}",
+                e
+            ))
+            .with_instance(instance));
+        }
+    }
+
+    let stats = state.runtime_status_metrics.trade_group_stats(&id);
+    Ok(Json(TradeGroupStats {
+        trade_group_id: id,
+        signals_received: stats.signals_received,
+        signals_copied: stats.signals_copied,
+    }))
+}
+
 /// Toggle Master enabled state
 /// POST /api/trade-groups/{id}/toggle
 pub async fn toggle_master(
@@ -322,6 +457,7 @@ async fn evaluate_master_runtime_status(
             .as_ref()
             .map(|c| c.is_trade_allowed)
             .unwrap_or(true),
+        low_margin_level: false,
     };
 
     evaluate_master_status(
@@ -342,6 +478,7 @@ async fn send_config_to_master(state: &AppState, master_account: &str, settings:
             .as_ref()
             .map(|c| c.is_trade_allowed)
             .unwrap_or(true),
+        low_margin_level: false,
     };
     let is_trade_allowed = master_snapshot.is_trade_allowed;
 
@@ -357,7 +494,7 @@ async fn send_config_to_master(state: &AppState, master_account: &str, settings:
     let status = bundle.status_result.status;
     let config = bundle.config;
 
-    if let Err(e) = state.config_sender.send(&config).await {
+    if let Err(e) = state.config_sender.send_master_config(&config).await {
         tracing::error!(
             master_account = %master_account,
             config_version = settings.config_version,
@@ -367,6 +504,7 @@ async fn send_config_to_master(state: &AppState, master_account: &str, settings:
             error = %e,
             "Failed to send MasterConfigMessage via ZMQ"
         );
+        state.config_delivery_failed(master_account, master_account, &e);
     } else {
         tracing::info!(
             master_account = %master_account,
@@ -390,6 +528,7 @@ async fn send_config_to_slaves(state: &AppState, master_account: &str, settings:
             .as_ref()
             .map(|c| c.is_trade_allowed)
             .unwrap_or(true),
+        low_margin_level: false,
     };
 
     // Calculate Master status once and reuse for every Slave notification
@@ -429,7 +568,10 @@ async fn send_config_to_slaves(state: &AppState, master_account: &str, settings:
                 .as_ref()
                 .map(|conn| conn.is_trade_allowed)
                 .unwrap_or(true),
+            low_margin_level: slave_conn.as_ref().is_some_and(|c| c.low_margin_level),
         };
+        let slave_settings =
+            effective_slave_settings(state.db.as_ref(), &member.slave_settings).await;
 
         let bundle = ConfigBuilder::build_slave_config(SlaveConfigContext {
             slave_account: member.slave_account.clone(),
@@ -440,20 +582,23 @@ async fn send_config_to_slaves(state: &AppState, master_account: &str, settings:
             },
             slave_connection_snapshot: slave_snapshot,
             master_status_result: master_status.clone(),
-            slave_settings: &member.slave_settings,
+            slave_settings: &slave_settings,
             master_equity,
+            master_currency: master_conn.as_ref().map(|conn| conn.currency.clone()),
+            slave_currency: slave_conn.as_ref().map(|conn| conn.currency.clone()),
             timestamp: chrono::Utc::now(),
         });
         let config = bundle.config;
         let new_status = bundle.status_result.status;
 
-        if let Err(e) = state.config_sender.send(&config).await {
+        if let Err(e) = state.config_sender.send_slave_config(&config).await {
             tracing::error!(
                 slave_account = %member.slave_account,
                 master_account = %master_account,
                 error = %e,
                 "Failed to send SlaveConfigMessage via ZMQ"
             );
+            state.config_delivery_failed(master_account, &member.slave_account, &e);
         } else {
             tracing::info!(
                 slave_account = %member.slave_account,
@@ -523,14 +668,14 @@ async fn reevaluate_and_broadcast_slaves(state: &AppState, master_account: &str)
         };
 
         // Broadcast settings update to WebSocket clients
-        if let Ok(json) = serde_json::to_string(&payload) {
-            let _ = state.tx.send(format!("settings_updated:{}", json));
-            tracing::info!(
-                slave = %member.slave_account,
-                master = %master_account,
-                "Broadcasted Slave warning_codes after Master toggle"
-            );
-        }
+        let _ = state
+            .tx
+            .send(ServerEvent::SettingsUpdated { data: payload });
+        tracing::info!(
+            slave = %member.slave_account,
+            master = %master_account,
+            "Broadcasted Slave warning_codes after Master toggle"
+        );
     }
 }
 
@@ -552,6 +697,17 @@ pub async fn create_trade_group(
         .with_instance(format!("/api/trade-groups/{}", payload.id)));
     }
 
+    // Validate member settings before creating anything (avoids partial creation)
+    for member_req in &payload.members {
+        if let Err(reason) = member_req.slave_settings.validate() {
+            return Err(ProblemDetails::validation_error(format!(
+                "Invalid settings for member '{}': {}",
+                member_req.slave_account, reason
+            ))
+            .with_instance("/api/trade-groups"));
+        }
+    }
+
     // 2. Create TradeGroup
     let mut tg = match state.db.create_trade_group(&payload.id).await {
         Ok(tg) => tg,
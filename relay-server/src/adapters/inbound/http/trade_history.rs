@@ -0,0 +1,51 @@
+//! Trade history endpoint handler
+//!
+//! Provides REST API endpoint for querying recorded copy decisions.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+
+use crate::{
+    adapters::inbound::http::{AppState, ProblemDetails},
+    domain::models::{TradeHistoryPage, TradeHistoryQuery},
+};
+
+/// Query trade history
+/// Query params: master, slave, from, to (RFC 3339), limit, offset
+pub async fn get_trade_history(
+    State(state): State<AppState>,
+    Query(query): Query<TradeHistoryQuery>,
+) -> Result<Json<TradeHistoryPage>, ProblemDetails> {
+    let span = tracing::info_span!("get_trade_history");
+    let _enter = span.enter();
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            tracing::warn!(%from, %to, "Rejected trade history query with from > to");
+            return Err(
+                ProblemDetails::validation_error("'from' must not be after 'to'")
+                    .with_instance("/api/trade-history"),
+            );
+        }
+    }
+
+    match state.db.query_trade_history(&query).await {
+        Ok(page) => {
+            tracing::debug!(
+                count = page.items.len(),
+                total = page.total,
+                "Successfully retrieved trade history"
+            );
+            Ok(Json(page))
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to query trade history");
+            Err(
+                ProblemDetails::internal_error(format!("Failed to query trade history: {}", e))
+                    .with_instance("/api/trade-history"),
+            )
+        }
+    }
+}
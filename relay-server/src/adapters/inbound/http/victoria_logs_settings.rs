@@ -8,7 +8,7 @@ use axum::{extract::State, http::StatusCode, Json};
 use serde::{Deserialize, Serialize};
 
 use crate::config::update_victoria_logs_config;
-use crate::domain::models::VLogsGlobalSettings;
+use crate::domain::models::{ServerEvent, VLogsGlobalSettings};
 
 use super::{AppState, ProblemDetails};
 
@@ -23,6 +23,12 @@ pub struct VLogsConfigResponse {
     pub config: Option<VLogsConfigInfo>,
     /// Current runtime enabled state
     pub enabled: bool,
+    /// Failed batches waiting for retry
+    #[serde(default)]
+    pub buffered_batches: usize,
+    /// Batches dropped because the retry buffer overflowed
+    #[serde(default)]
+    pub dropped_batches: u64,
 }
 
 /// Config information from config.toml (read-only)
@@ -34,6 +40,7 @@ pub struct VLogsConfigInfo {
     pub flush_interval_secs: u64,
     pub source: String,
     pub log_level: String,
+    pub max_buffered_batches: usize,
 }
 
 /// Request for PUT /api/victoria-logs-settings
@@ -64,13 +71,18 @@ pub async fn get_vlogs_config(
                     flush_interval_secs: config.flush_interval_secs,
                     source: config.source.clone(),
                     log_level: config.log_level.clone(),
+                    max_buffered_batches: config.max_buffered_batches,
                 }),
                 enabled: controller.is_enabled(),
+                buffered_batches: controller.buffer_stats().buffered_batches(),
+                dropped_batches: controller.buffer_stats().dropped_batches(),
             };
 
             tracing::info!(
                 configured = true,
                 enabled = response.enabled,
+                buffered_batches = response.buffered_batches,
+                dropped_batches = response.dropped_batches,
                 host = %config.host,
                 "Retrieved VictoriaLogs config"
             );
@@ -87,6 +99,8 @@ pub async fn get_vlogs_config(
                 configured: false,
                 config: None,
                 enabled: false,
+                buffered_batches: 0,
+                dropped_batches: 0,
             }))
         }
     }
@@ -169,9 +183,9 @@ pub async fn update_vlogs_settings(
     broadcast_vlogs_config(&state, &settings).await;
 
     // Notify via WebSocket
-    if let Ok(json) = serde_json::to_string(&settings) {
-        let _ = state.tx.send(format!("vlogs_settings_updated:{}", json));
-    }
+    let _ = state
+        .tx
+        .send(ServerEvent::VlogsSettingsUpdated { data: settings });
 
     Ok(StatusCode::NO_CONTENT)
 }
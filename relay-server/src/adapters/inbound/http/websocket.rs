@@ -3,24 +3,30 @@
 //! Provides WebSocket endpoint for broadcasting real-time updates
 //! to connected clients. Implements on-demand snapshot broadcasting
 //! that only runs when there are active WebSocket subscribers.
+//! Clients can narrow the stream to one trade group or account with a
+//! subscribe message (see [`Subscription`]). Every new client first gets a
+//! full system snapshot, so it never has to race `/api/connections` against
+//! the incremental events.
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::{
-    extract::{ws::WebSocket, ws::WebSocketUpgrade, State},
+    extract::{ws::Message, ws::WebSocket, ws::WebSocketUpgrade, State},
     response::Response,
 };
+use serde::Deserialize;
 use tokio::sync::{broadcast, Mutex};
 use tokio::task::JoinHandle;
 
 use crate::adapters::inbound::http::AppState;
 use crate::adapters::infrastructure::connection_manager::ConnectionManager;
 use crate::adapters::outbound::persistence::Database;
-use crate::domain::models::SystemStateSnapshot;
+use crate::domain::models::{ServerEvent, SlaveConfigWithMaster, SystemStateSnapshot};
 use crate::domain::services::status_calculator::{
-    evaluate_master_status, evaluate_member_status, ConnectionSnapshot, MasterIntent, SlaveIntent,
+    apply_currency_check, apply_schedule_check, evaluate_master_status, evaluate_member_status,
+    ConnectionSnapshot, MasterIntent, SlaveIntent,
 };
 
 /// Interval for snapshot broadcasts (in seconds)
@@ -38,7 +44,7 @@ pub struct SnapshotBroadcaster {
     /// Handle to the background timer task (None when no subscribers)
     task_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
     /// Broadcast channel for sending snapshots
-    tx: broadcast::Sender<String>,
+    tx: broadcast::Sender<ServerEvent>,
     /// Connection manager for fetching EA data
     connection_manager: Arc<ConnectionManager>,
     /// Database for fetching config
@@ -48,7 +54,7 @@ pub struct SnapshotBroadcaster {
 impl SnapshotBroadcaster {
     /// Create a new SnapshotBroadcaster
     pub fn new(
-        tx: broadcast::Sender<String>,
+        tx: broadcast::Sender<ServerEvent>,
         connection_manager: Arc<ConnectionManager>,
         db: Arc<Database>,
     ) -> Self {
@@ -122,8 +128,24 @@ impl SnapshotBroadcaster {
         }
     }
 
-    /// Build the full system snapshot (with runtime status) and broadcast it
+    /// Build the full system snapshot and broadcast it
     async fn build_and_broadcast_snapshot(&self) {
+        let Some(snapshot) = self.build_snapshot().await else {
+            return;
+        };
+        // Serialized per client in handle_websocket
+        if self
+            .tx
+            .send(ServerEvent::SystemSnapshot { data: snapshot })
+            .is_err()
+        {
+            tracing::warn!("No WebSocket receivers for system snapshot");
+        }
+    }
+
+    /// Build the full system snapshot (connections, trade groups and members with
+    /// runtime status); None when the database could not be read
+    pub async fn build_snapshot(&self) -> Option<SystemStateSnapshot> {
         // 1. Fetch raw data from in-memory and DB
         let connections = self.connection_manager.get_all_eas().await;
 
@@ -142,6 +164,7 @@ impl SnapshotBroadcaster {
                 let snapshot = ConnectionSnapshot {
                     connection_status: master_conn.map(|c| c.status),
                     is_trade_allowed: master_conn.map(|c| c.is_trade_allowed).unwrap_or(false),
+                    low_margin_level: false,
                 };
 
                 let result = evaluate_master_status(
@@ -167,6 +190,7 @@ impl SnapshotBroadcaster {
                 let slave_snapshot = ConnectionSnapshot {
                     connection_status: slave_conn.map(|c| c.status),
                     is_trade_allowed: slave_conn.map(|c| c.is_trade_allowed).unwrap_or(false),
+                    low_margin_level: slave_conn.is_some_and(|c| c.low_margin_level),
                 };
 
                 // Get result of the specific Master this member is connected to
@@ -175,40 +199,50 @@ impl SnapshotBroadcaster {
                     .cloned()
                     .unwrap_or_default();
 
-                let result = evaluate_member_status(
+                let mut result = evaluate_member_status(
                     SlaveIntent {
                         web_ui_enabled: member.enabled_flag,
                     },
                     slave_snapshot,
                     &master_result,
                 );
+                let master_currency = connections
+                    .iter()
+                    .find(|c| c.account_id == member.trade_group_id)
+                    .map(|c| c.currency.as_str());
+                apply_currency_check(
+                    &mut result,
+                    master_currency,
+                    slave_conn.map(|c| c.currency.as_str()),
+                );
+                apply_schedule_check(&mut result, &member.slave_settings, chrono::Utc::now());
 
-                // Populate runtime fields on Member
+                // Populate runtime fields on Member (acknowledged warnings are muted)
                 member.status = result.status;
-                member.warning_codes = result.warning_codes;
+                if member.apply_warning_codes(result.warning_codes) {
+                    if let Err(e) = self
+                        .db
+                        .update_member_acknowledged_warnings(
+                            &member.trade_group_id,
+                            &member.slave_account,
+                            &member.acknowledged_warnings,
+                        )
+                        .await
+                    {
+                        tracing::warn!("Failed to persist acknowledged warnings: {}", e);
+                    }
+                }
             }
 
             // 4. Construct Snapshot
-            let snapshot = SystemStateSnapshot {
+            Some(SystemStateSnapshot {
                 connections, // The original connections list (status is sufficient here)
                 trade_groups,
                 members,
-            };
-
-            // 5. Serialize and Broadcast
-            match serde_json::to_string(&snapshot) {
-                Ok(json) => {
-                    let message = format!("system_snapshot:{}", json);
-                    if self.tx.send(message).is_err() {
-                        tracing::warn!("No WebSocket receivers for system snapshot");
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Failed to serialize system snapshot: {}", e);
-                }
-            }
+            })
         } else {
             tracing::error!("Failed to fetch data for system snapshot");
+            None
         }
     }
 }
@@ -223,13 +257,99 @@ impl crate::ports::UpdateBroadcaster for SnapshotBroadcaster {
     }
 
     async fn broadcast_ea_disconnected(&self, account_id: &str) {
-        let msg = format!("ea_disconnected:{}", account_id);
-        let _ = self.tx.send(msg);
+        let _ = self.tx.send(ServerEvent::EaDisconnected {
+            account_id: account_id.to_string(),
+        });
     }
 
-    async fn broadcast_settings_updated(&self, json: &str) {
-        let msg = format!("settings_updated:{}", json);
-        let _ = self.tx.send(msg);
+    async fn broadcast_settings_updated(&self, settings: &SlaveConfigWithMaster) {
+        let _ = self.tx.send(ServerEvent::SettingsUpdated {
+            data: settings.clone(),
+        });
+    }
+}
+
+/// Event filter requested by a WebSocket client
+/// (`{"subscribe": {"trade_group": "MASTER_001"}}` or `{"subscribe": {"account": "SLAVE_001"}}`).
+/// `{"subscribe": {}}` resets to receiving every event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Subscription {
+    /// Only events of this trade group (Master account)
+    #[serde(default)]
+    pub trade_group: Option<String>,
+    /// Only events concerning this account (Master or Slave)
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ClientMessage {
+    subscribe: Subscription,
+}
+
+impl Subscription {
+    /// Parse a client text frame; `None` when it is not a subscribe message
+    pub fn from_client_message(text: &str) -> Option<Self> {
+        serde_json::from_str::<ClientMessage>(text)
+            .ok()
+            .map(|message| message.subscribe)
+    }
+
+    /// Event to forward to this client, or `None` when filtered out.
+    /// Relay-wide events always pass; system snapshots are narrowed to the subscription.
+    pub fn filter(&self, event: ServerEvent) -> Option<ServerEvent> {
+        if let ServerEvent::SystemSnapshot { data } = event {
+            return Some(ServerEvent::SystemSnapshot {
+                data: self.scope_snapshot(data),
+            });
+        }
+
+        let accounts = event.accounts();
+        let relay_wide = event.trade_group().is_none() && accounts.is_empty();
+        if relay_wide {
+            return Some(event);
+        }
+        if let Some(trade_group) = &self.trade_group {
+            let in_group = match event.trade_group() {
+                Some(group) => group == trade_group,
+                None => accounts.contains(&trade_group.as_str()),
+            };
+            if !in_group {
+                return None;
+            }
+        }
+        if let Some(account) = &self.account {
+            if !accounts.contains(&account.as_str()) {
+                return None;
+            }
+        }
+        Some(event)
+    }
+
+    fn scope_snapshot(&self, mut snapshot: SystemStateSnapshot) -> SystemStateSnapshot {
+        if let Some(trade_group) = &self.trade_group {
+            snapshot.trade_groups.retain(|tg| &tg.id == trade_group);
+            snapshot
+                .members
+                .retain(|member| &member.trade_group_id == trade_group);
+            let members = &snapshot.members;
+            snapshot.connections.retain(|conn| {
+                &conn.account_id == trade_group
+                    || members
+                        .iter()
+                        .any(|member| member.slave_account == conn.account_id)
+            });
+        }
+        if let Some(account) = &self.account {
+            snapshot.trade_groups.retain(|tg| &tg.id == account);
+            snapshot.members.retain(|member| {
+                &member.trade_group_id == account || &member.slave_account == account
+            });
+            snapshot
+                .connections
+                .retain(|conn| &conn.account_id == account);
+        }
+        snapshot
     }
 }
 
@@ -245,15 +365,42 @@ async fn handle_websocket(mut socket: WebSocket, state: AppState) {
 
     // Subscribe to broadcast channel
     let mut rx = state.tx.subscribe();
+    let legacy = state.config.server.legacy_ws_events;
+    let mut subscription = Subscription::default();
 
-    // Relay messages to WebSocket client
-    while let Ok(msg) = rx.recv().await {
+    // Initial snapshot first; events published meanwhile wait in `rx`
+    if let Some(snapshot) = state.snapshot_broadcaster.build_snapshot().await {
+        let event = ServerEvent::SystemSnapshot { data: snapshot };
         if socket
-            .send(axum::extract::ws::Message::Text(msg))
+            .send(Message::Text(event.render(legacy)))
             .await
             .is_err()
         {
-            break;
+            state.snapshot_broadcaster.on_disconnect().await;
+            return;
+        }
+    }
+
+    // Relay messages to WebSocket client, applying its subscription filter
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(requested) = Subscription::from_client_message(&text) {
+                        tracing::debug!("WebSocket client subscribed: {:?}", requested);
+                        subscription = requested;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            event = rx.recv() => {
+                let Ok(event) = event else { break };
+                let Some(event) = subscription.filter(event) else { continue };
+                if socket.send(Message::Text(event.render(legacy))).await.is_err() {
+                    break;
+                }
+            }
         }
     }
 
@@ -278,6 +425,9 @@ mod tests {
             account_id: account_id.to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: "test".to_string(),
@@ -364,17 +514,18 @@ mod tests {
 
         // Verify we received a snapshot
         assert!(result.is_ok(), "Should receive a snapshot within timeout");
-        let message = result.unwrap().unwrap();
-        // Changed from connections_snapshot to system_snapshot
-        assert!(
-            message.starts_with("system_snapshot:"),
+        let event = result.unwrap().unwrap();
+        let message = event.to_json();
+        let snapshot: serde_json::Value = serde_json::from_str(&message).unwrap();
+        assert_eq!(
+            snapshot["type"],
+            "system_snapshot",
             "Message should be a system snapshot, got: {}",
             &message[..message.len().min(50)]
         );
 
-        // Parse the snapshot and verify content
-        let json_part = message.strip_prefix("system_snapshot:").unwrap();
-        let snapshot: serde_json::Value = serde_json::from_str(json_part).unwrap();
+        // Verify content
+        let snapshot = &snapshot["data"];
         let connections = snapshot["connections"].as_array().unwrap();
         assert_eq!(connections.len(), 1);
         assert_eq!(connections[0]["account_id"], "TEST_123");
@@ -407,4 +558,139 @@ mod tests {
             "Should not receive snapshots after last subscriber disconnects"
         );
     }
+
+    /// Next text frame of a test WebSocket client, parsed as JSON
+    async fn next_json<S>(ws: &mut S) -> serde_json::Value
+    where
+        S: futures_util::Stream<
+                Item = Result<
+                    tokio_tungstenite::tungstenite::Message,
+                    tokio_tungstenite::tungstenite::Error,
+                >,
+            > + Unpin,
+    {
+        use futures_util::StreamExt;
+
+        let message = tokio::time::timeout(Duration::from_secs(5), ws.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    }
+
+    /// Test that a new client gets a snapshot before any incremental event
+    #[tokio::test]
+    async fn test_new_client_receives_snapshot_before_events() {
+        let state = crate::adapters::inbound::http::test_helpers::create_test_app_state().await;
+        state
+            .connection_manager
+            .update_heartbeat(create_test_heartbeat("MASTER_001", "Master"))
+            .await;
+        let tx = state.tx.clone();
+        let app = crate::adapters::inbound::http::create_router(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // Incremental events keep flowing while the client connects
+        let events = tokio::spawn(async move {
+            loop {
+                let _ = tx.send(ServerEvent::EaDisconnected {
+                    account_id: "SLAVE_001".to_string(),
+                });
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        });
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", address))
+            .await
+            .unwrap();
+        let first = next_json(&mut ws).await;
+        assert_eq!(first["type"], "system_snapshot");
+        assert_eq!(first["data"]["connections"][0]["account_id"], "MASTER_001");
+
+        // The incremental stream follows the snapshot
+        let mut saw_event = false;
+        for _ in 0..10 {
+            if next_json(&mut ws).await["type"] == "ea_disconnected" {
+                saw_event = true;
+                break;
+            }
+        }
+        events.abort();
+        assert!(saw_event);
+    }
+
+    fn trade_copied(master: &str, slave: &str) -> ServerEvent {
+        ServerEvent::TradeCopied {
+            master_account: master.to_string(),
+            slave_account: slave.to_string(),
+            symbol: Some("EURUSD".to_string()),
+            lots: Some(0.1),
+            member_id: 1,
+        }
+    }
+
+    /// Test that a subscribed client only receives events of its trade group
+    #[tokio::test]
+    async fn test_subscription_filters_unrelated_events() {
+        let subscription =
+            Subscription::from_client_message(r#"{"subscribe": {"trade_group": "MASTER_001"}}"#)
+                .expect("subscribe message should parse");
+        assert_eq!(subscription.trade_group.as_deref(), Some("MASTER_001"));
+        assert_eq!(Subscription::from_client_message(r#"{"ping": 1}"#), None);
+
+        assert!(subscription
+            .filter(trade_copied("MASTER_001", "SLAVE_001"))
+            .is_some());
+        assert!(subscription
+            .filter(trade_copied("MASTER_002", "SLAVE_001"))
+            .is_none());
+        assert!(subscription
+            .filter(ServerEvent::EaDisconnected {
+                account_id: "MASTER_001".to_string(),
+            })
+            .is_some());
+        assert!(subscription
+            .filter(ServerEvent::EaDisconnected {
+                account_id: "MASTER_002".to_string(),
+            })
+            .is_none());
+
+        // Snapshots are narrowed to the subscribed group
+        let connection_manager = ConnectionManager::new(30);
+        connection_manager
+            .update_heartbeat(create_test_heartbeat("MASTER_001", "Master"))
+            .await;
+        connection_manager
+            .update_heartbeat(create_test_heartbeat("MASTER_002", "Master"))
+            .await;
+        let snapshot = ServerEvent::SystemSnapshot {
+            data: SystemStateSnapshot {
+                connections: connection_manager.get_all_eas().await,
+                trade_groups: vec![],
+                members: vec![],
+            },
+        };
+        let Some(ServerEvent::SystemSnapshot { data }) = subscription.filter(snapshot) else {
+            panic!("snapshot should be forwarded");
+        };
+        assert_eq!(data.connections.len(), 1);
+        assert_eq!(data.connections[0].account_id, "MASTER_001");
+
+        // Account subscriptions match either side of a copy; no subscription passes everything
+        let by_slave =
+            Subscription::from_client_message(r#"{"subscribe": {"account": "SLAVE_002"}}"#)
+                .unwrap();
+        assert!(by_slave
+            .filter(trade_copied("MASTER_002", "SLAVE_002"))
+            .is_some());
+        assert!(by_slave
+            .filter(trade_copied("MASTER_002", "SLAVE_001"))
+            .is_none());
+        assert!(Subscription::default()
+            .filter(trade_copied("MASTER_002", "SLAVE_001"))
+            .is_some());
+    }
 }
@@ -46,6 +46,7 @@ impl MessageHandler {
                         .as_ref()
                         .map(|c| c.is_trade_allowed)
                         .unwrap_or(true),
+                    low_margin_level: false,
                 };
 
                 let bundle = ConfigBuilder::build_master_config(MasterConfigContext {
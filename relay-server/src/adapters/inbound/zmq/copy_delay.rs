@@ -0,0 +1,140 @@
+//! Delayed publishing of copied signals
+//!
+//! Members with `slave_settings.copy_delay_ms` have each copied signal published
+//! after a random delay in the configured range, so Slave executions do not line
+//! up exactly with the Master's. Publishes for the same Master/Slave pair are
+//! chained: a signal is never sent before an earlier one for that Slave, even
+//! when its own delay is shorter (e.g. a Close that skips the delay), so a
+//! Close cannot overtake its Open. Different pairs do not wait on each other.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+/// (master_account, slave_account)
+type PairKey = (String, String);
+
+/// Completion of the latest publish scheduled for a pair
+struct Pending {
+    id: u64,
+    done: oneshot::Receiver<()>,
+}
+
+/// Random delay in `[min, max]` milliseconds
+pub(crate) fn pick_delay((min, max): (u64, u64)) -> Duration {
+    let ms = if min >= max {
+        min
+    } else {
+        rand::rng().random_range(min..=max)
+    };
+    Duration::from_millis(ms)
+}
+
+/// Runs publish tasks after their delay, in scheduling order per Master/Slave pair
+#[derive(Default)]
+pub(crate) struct CopyDelayScheduler {
+    pending: Arc<Mutex<HashMap<PairKey, Pending>>>,
+    next_id: AtomicU64,
+}
+
+impl CopyDelayScheduler {
+    /// Whether a publish for this pair is still waiting
+    pub(crate) fn has_pending(&self, master: &str, slave: &str) -> bool {
+        self.pending
+            .lock()
+            .unwrap()
+            .contains_key(&(master.to_string(), slave.to_string()))
+    }
+
+    /// Spawn `publish` to run `delay` from now, but not before the previously
+    /// scheduled publish for the same pair has finished
+    pub(crate) fn schedule<F>(&self, master: &str, slave: &str, delay: Duration, publish: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let key = (master.to_string(), slave.to_string());
+        let deadline = Instant::now() + delay;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (done_tx, done_rx) = oneshot::channel();
+        let previous = self
+            .pending
+            .lock()
+            .unwrap()
+            .insert(key.clone(), Pending { id, done: done_rx });
+
+        let pending = self.pending.clone();
+        tokio::spawn(async move {
+            if let Some(previous) = previous {
+                // A dropped sender (panicked task) also releases the chain
+                let _ = previous.done.await;
+            }
+            tokio::time::sleep_until(deadline).await;
+            publish.await;
+
+            let mut pending = pending.lock().unwrap();
+            if pending.get(&key).is_some_and(|latest| latest.id == id) {
+                pending.remove(&key);
+            }
+            let _ = done_tx.send(());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_delay_stays_within_bounds() {
+        for _ in 0..200 {
+            let delay = pick_delay((50, 80));
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(80));
+        }
+        assert_eq!(pick_delay((30, 30)), Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn test_delayed_publish_keeps_pair_order() {
+        let scheduler = CopyDelayScheduler::default();
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let start = Instant::now();
+
+        // Open of ticket 1 delayed within the configured range, then signals for the
+        // same Slave with shorter delays: a Close that skips the delay and ticket 2
+        let open_delay = pick_delay((50, 80));
+        for (label, delay) in [
+            ("open", open_delay),
+            ("close", Duration::ZERO),
+            ("ticket2", Duration::from_millis(10)),
+        ] {
+            let published = published.clone();
+            scheduler.schedule("MASTER_001", "SLAVE_001", delay, async move {
+                published.lock().unwrap().push((label, start.elapsed()));
+            });
+        }
+        // Another Slave is not held back by the first one
+        let other = published.clone();
+        scheduler.schedule("MASTER_001", "SLAVE_002", Duration::ZERO, async move {
+            other.lock().unwrap().push(("other_slave", start.elapsed()));
+        });
+        assert!(scheduler.has_pending("MASTER_001", "SLAVE_001"));
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let published = published.lock().unwrap();
+        let labels: Vec<_> = published.iter().map(|(label, _)| *label).collect();
+        assert_eq!(labels, vec!["other_slave", "open", "close", "ticket2"]);
+        let open_elapsed = published[1].1;
+        assert!(open_elapsed >= Duration::from_millis(50));
+        assert!(open_elapsed < Duration::from_millis(80) + Duration::from_millis(100));
+        // Chained publishes do not add up their delays
+        assert!(published[3].1 < open_elapsed + Duration::from_millis(100));
+        assert!(!scheduler.has_pending("MASTER_001", "SLAVE_001"));
+    }
+}
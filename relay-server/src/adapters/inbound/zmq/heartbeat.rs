@@ -5,24 +5,119 @@
 //! (Delegated to StatusService logic:
 //!  - Master: DISABLED (web_ui OFF or !is_trade_allowed) or CONNECTED
 //!  - Slave: DISABLED (web_ui OFF or !is_trade_allowed) or ENABLED (master not connected) or CONNECTED)
+//!
+//! After a Slave heartbeat, signals buffered while the Slave was offline are
+//! replayed for every member that is CONNECTED again.
+
+use tracing::instrument;
 
-use super::MessageHandler;
-use crate::domain::models::HeartbeatMessage;
+use super::{record_master_trade_group, MessageHandler};
+use crate::domain::models::{GlobalSettings, HeartbeatMessage, MasterSettings, TradeGroupMember};
 
 impl MessageHandler {
     /// Handle heartbeat messages
     /// Delegates logic to StatusService
+    #[instrument(skip_all, fields(
+        account_id = %msg.account_id,
+        ea_type = %msg.ea_type,
+        trade_group = tracing::field::Empty,
+    ))]
     pub(super) async fn handle_heartbeat(&self, msg: HeartbeatMessage) {
+        record_master_trade_group(&msg.account_id, &msg.ea_type);
+        let replay_slave = (msg.ea_type == "Slave").then(|| msg.account_id.clone());
+
         // Delegate to StatusService
         self.status_service.handle_heartbeat(msg).await;
+
+        if let Some(slave_account) = replay_slave {
+            self.replay_buffered_signals(&slave_account).await;
+        }
+    }
+
+    /// Copy buffered signals in arrival order to members that are connected again
+    async fn replay_buffered_signals(&self, slave_account: &str) {
+        let pending = self.replay_buffer.pending_masters(slave_account);
+        if pending.is_empty() {
+            return;
+        }
+        let global_settings = self.load_global_settings().await;
+        for master_account in pending {
+            let member = match self.db.get_member(&master_account, slave_account).await {
+                Ok(Some(member)) => member,
+                Ok(None) => {
+                    // Member was removed while the Slave was offline
+                    self.replay_buffer.take(&master_account, slave_account);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to get member {} -> {} for replay: {}",
+                        master_account,
+                        slave_account,
+                        e
+                    );
+                    continue;
+                }
+            };
+            if !member.is_connected() {
+                continue;
+            }
+            let Some(master_settings) = self.load_master_settings(&master_account).await else {
+                continue;
+            };
+            // Siblings only feed the portfolio_weight split; replay without it on error
+            let members = self
+                .db
+                .get_members(&master_account)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::error!("Failed to get members for {}: {}", master_account, e);
+                    Vec::new()
+                });
+
+            self.replay_member(&member, &master_settings, &members, &global_settings)
+                .await;
+        }
+    }
+
+    /// Copy a connected member's buffered signals in arrival order
+    pub(super) async fn replay_member(
+        &self,
+        member: &TradeGroupMember,
+        master_settings: &MasterSettings,
+        members: &[TradeGroupMember],
+        global_settings: &GlobalSettings,
+    ) {
+        let (master_account, slave_account) = (&member.trade_group_id, &member.slave_account);
+        let signals = self.replay_buffer.take(master_account, slave_account);
+        tracing::info!(
+            "Replaying {} buffered signal(s) from {} to {}",
+            signals.len(),
+            master_account,
+            slave_account
+        );
+        // Opens buffered before closes-only mode was switched on are dropped
+        for signal in signals
+            .iter()
+            .filter(|s| master_settings.allows_action(&s.action))
+        {
+            self.copy_to_member(signal, member, master_settings, members, global_settings)
+                .await;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     // use super::*;
-    use crate::adapters::inbound::zmq::test_helpers::{build_heartbeat, create_test_context};
-    use crate::domain::models::{SlaveSettings, STATUS_CONNECTED, STATUS_ENABLED};
+    use crate::adapters::inbound::zmq::test_helpers::{
+        build_heartbeat, create_test_context, create_test_context_with_config,
+        create_test_trade_signal, TestContext,
+    };
+    use crate::domain::models::{
+        GlobalSettings, MasterSettings, SlaveSettings, TradeAction, TradeHistoryQuery,
+        STATUS_CONNECTED, STATUS_DISABLED, STATUS_ENABLED,
+    };
 
     #[tokio::test]
     async fn test_handle_heartbeat_master_new_registration() {
@@ -89,4 +184,228 @@ mod tests {
 
         ctx.cleanup().await;
     }
+
+    /// (ticket, copied) pairs recorded for SLAVE_001 in insertion order
+    async fn slave_history(ctx: &TestContext) -> Vec<(i64, bool)> {
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                slave: Some("SLAVE_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        // Replays keep the original signal timestamp, so order by row id
+        let mut items = page.items;
+        items.sort_by_key(|entry| entry.id);
+        items
+            .iter()
+            .map(|entry| (entry.ticket, entry.copied))
+            .collect()
+    }
+
+    /// Enabled Master online, member SLAVE_001 waiting for its Slave EA
+    async fn setup_offline_member(ctx: &TestContext) {
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .update_master_settings(
+                "MASTER_001",
+                MasterSettings {
+                    enabled: true,
+                    config_version: 1,
+                    ..MasterSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        ctx.db
+            .add_member(
+                "MASTER_001",
+                "SLAVE_001",
+                SlaveSettings::default(),
+                STATUS_ENABLED,
+            )
+            .await
+            .unwrap();
+        ctx.handle_heartbeat(build_heartbeat("MASTER_001", "Master", true))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_slave_heartbeat_replays_buffered_signals() {
+        let mut config = crate::config::Config::default();
+        config.zeromq.offline_replay_buffer_size = 10;
+        let ctx = create_test_context_with_config(config).await;
+
+        setup_offline_member(&ctx).await;
+
+        // Slave EA is offline: both tickets are skipped and buffered
+        let mut first = create_test_trade_signal();
+        first.ticket = 1;
+        let mut second = create_test_trade_signal();
+        second.ticket = 2;
+        ctx.handle_trade_signal(first).await;
+        ctx.handle_trade_signal(second.clone()).await;
+        // Ticket 2 is closed before the Slave ever opened it
+        second.action = TradeAction::Close;
+        ctx.handle_trade_signal(second).await;
+        assert_eq!(
+            slave_history(&ctx).await,
+            vec![(1, false), (2, false), (2, false)]
+        );
+
+        // Slave comes back: only ticket 1 is replayed
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+        let member = ctx
+            .db
+            .get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(member.status, STATUS_CONNECTED);
+        assert_eq!(
+            slave_history(&ctx).await,
+            vec![(1, false), (2, false), (2, false), (1, true)]
+        );
+
+        // The buffer was drained
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+        assert_eq!(slave_history(&ctx).await.len(), 4);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_live_signal_waits_for_buffered_signals() {
+        let mut config = crate::config::Config::default();
+        config.zeromq.offline_replay_buffer_size = 10;
+        let ctx = create_test_context_with_config(config).await;
+
+        setup_offline_member(&ctx).await;
+        ctx.handle_trade_signal(create_test_trade_signal()).await;
+
+        // Member turns CONNECTED without a Slave heartbeat replaying the Open
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", STATUS_CONNECTED)
+            .await
+            .unwrap();
+        let mut close = create_test_trade_signal();
+        close.action = TradeAction::Close;
+        ctx.handle_trade_signal(close).await;
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                slave: Some("SLAVE_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        let mut items = page.items;
+        items.sort_by_key(|entry| entry.id);
+        let sent: Vec<_> = items
+            .iter()
+            .map(|entry| (entry.action.clone(), entry.copied))
+            .collect();
+        assert_eq!(
+            sent,
+            vec![
+                (TradeAction::Open, false),
+                (TradeAction::Open, true),
+                (TradeAction::Close, true),
+            ]
+        );
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_replay_disabled_by_default() {
+        let ctx = create_test_context().await;
+
+        setup_offline_member(&ctx).await;
+        ctx.handle_trade_signal(create_test_trade_signal()).await;
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        assert_eq!(slave_history(&ctx).await, vec![(12345, false)]);
+
+        ctx.cleanup().await;
+    }
+
+    /// Enabled Master online, member SLAVE_001 added with `status`, auto-enable turned on
+    async fn setup_auto_enable(ctx: &TestContext, status: i32) {
+        ctx.db
+            .update_global_settings(&GlobalSettings {
+                auto_enable_on_connect: true,
+                ..GlobalSettings::default()
+            })
+            .await
+            .unwrap();
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .update_master_settings(
+                "MASTER_001",
+                MasterSettings {
+                    enabled: true,
+                    config_version: 1,
+                    ..MasterSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), status)
+            .await
+            .unwrap();
+        ctx.handle_heartbeat(build_heartbeat("MASTER_001", "Master", true))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_auto_enable_on_first_connect() {
+        let ctx = create_test_context().await;
+        setup_auto_enable(&ctx, STATUS_DISABLED).await;
+
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        let member = ctx
+            .db
+            .get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(member.enabled_flag);
+        assert_eq!(member.status, STATUS_CONNECTED);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_auto_enable_skips_member_disabled_by_user() {
+        let ctx = create_test_context().await;
+        setup_auto_enable(&ctx, STATUS_ENABLED).await;
+        // User turns the member off via the toggle API
+        ctx.db
+            .update_member_enabled_flag("MASTER_001", "SLAVE_001", false)
+            .await
+            .unwrap();
+
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        let member = ctx
+            .db
+            .get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!member.enabled_flag);
+        assert_eq!(member.status, STATUS_DISABLED);
+
+        ctx.cleanup().await;
+    }
 }
@@ -12,14 +12,21 @@ use crate::{
     adapters::outbound::observability::victoria_logs::VLogsController,
     adapters::outbound::persistence::Database,
     application::runtime_status_updater::{RuntimeStatusMetrics, RuntimeStatusUpdater},
+    domain::models::ServerEvent,
     domain::services::copy_engine::CopyEngine,
 };
 
 // Handler submodules
 mod config_request;
+mod copy_delay;
 mod heartbeat;
+mod ping;
+mod position_lots;
 mod position_snapshot;
 mod register;
+mod replay_buffer;
+mod sequence;
+mod signal_throttle;
 mod sync_request;
 mod trade_signal;
 pub(crate) mod unregister;
@@ -31,7 +38,7 @@ pub(crate) mod test_helpers;
 pub struct MessageHandler {
     connection_manager: Arc<ConnectionManager>,
     copy_engine: Arc<CopyEngine>,
-    broadcast_tx: broadcast::Sender<String>,
+    broadcast_tx: broadcast::Sender<ServerEvent>,
     db: Arc<Database>,
     /// Unified ZMQ publisher for all outgoing messages (trade signals + config)
     publisher: Arc<ZmqConfigPublisher>,
@@ -46,6 +53,16 @@ pub struct MessageHandler {
     /// Application configuration (for symbol mappings)
     #[allow(dead_code)]
     config: Arc<crate::config::Config>,
+    /// Last accepted TradeSignal seq per Master (drops ZMQ redeliveries)
+    trade_sequences: sequence::SequenceTracker,
+    /// Signals held for members whose Slave is offline (replayed on reconnect)
+    replay_buffer: replay_buffer::ReplayBuffer,
+    /// Lots sent to each Slave per copied ticket (for partial_close_by_lots)
+    position_lots: position_lots::PositionLotTracker,
+    /// Copied signals waiting for their member's `copy_delay_ms`
+    copy_delays: copy_delay::CopyDelayScheduler,
+    /// Per-Master token buckets for `max_signals_per_sec`
+    signal_throttle: signal_throttle::SignalThrottle,
 }
 
 impl MessageHandler {
@@ -53,7 +70,7 @@ impl MessageHandler {
     pub fn new(
         connection_manager: Arc<ConnectionManager>,
         copy_engine: Arc<CopyEngine>,
-        broadcast_tx: broadcast::Sender<String>,
+        broadcast_tx: broadcast::Sender<ServerEvent>,
         db: Arc<Database>,
         publisher: Arc<ZmqConfigPublisher>,
         vlogs_controller: Option<VLogsController>,
@@ -73,7 +90,14 @@ impl MessageHandler {
 
             status_service,
             disconnection_service,
+            replay_buffer: replay_buffer::ReplayBuffer::new(
+                config.zeromq.offline_replay_buffer_size,
+            ),
             config,
+            trade_sequences: sequence::SequenceTracker::default(),
+            position_lots: position_lots::PositionLotTracker::default(),
+            copy_delays: copy_delay::CopyDelayScheduler::default(),
+            signal_throttle: signal_throttle::SignalThrottle::default(),
         }
     }
 
@@ -89,6 +113,7 @@ impl MessageHandler {
             // Position sync protocol messages
             ZmqMessage::PositionSnapshot(snapshot) => self.handle_position_snapshot(snapshot).await,
             ZmqMessage::SyncRequest(request) => self.handle_sync_request(request).await,
+            ZmqMessage::Ping(ping) => self.handle_ping(ping).await,
         }
     }
 
@@ -100,3 +125,159 @@ impl MessageHandler {
         )
     }
 }
+
+/// A Master's account is also its TradeGroup ID; record it on the handler span
+fn record_master_trade_group(account_id: &str, ea_type: &str) {
+    if ea_type == "Master" {
+        tracing::Span::current().record("trade_group", account_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_helpers::{build_heartbeat, create_test_context, create_test_trade_signal};
+    use crate::adapters::outbound::messaging::ZmqMessage;
+    use crate::domain::models::{RegisterMessage, UnregisterMessage};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    type Fields = HashMap<String, String>;
+
+    #[derive(Default)]
+    struct FieldCollector(Fields);
+
+    impl Visit for FieldCollector {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(
+                field.name().to_string(),
+                format!("{:?}", value).replace('"', ""),
+            );
+        }
+    }
+
+    /// Records, for every event, its own fields merged with those of the enclosing spans
+    #[derive(Clone, Default)]
+    struct CaptureLayer(Arc<Mutex<Vec<Fields>>>);
+
+    impl<S> Layer<S> for CaptureLayer
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: Context<'_, S>,
+        ) {
+            let mut fields = FieldCollector::default();
+            attrs.record(&mut fields);
+            ctx.span(id).unwrap().extensions_mut().insert(fields);
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            ctx: Context<'_, S>,
+        ) {
+            let span = ctx.span(id).unwrap();
+            let mut extensions = span.extensions_mut();
+            if let Some(fields) = extensions.get_mut::<FieldCollector>() {
+                values.record(fields);
+            }
+        }
+
+        fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+            let mut fields = FieldCollector::default();
+            if let Some(scope) = ctx.event_scope(event) {
+                for span in scope.from_root() {
+                    if let Some(span_fields) = span.extensions().get::<FieldCollector>() {
+                        fields.0.extend(span_fields.0.clone());
+                    }
+                }
+            }
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_events_carry_account_fields() {
+        let capture = CaptureLayer::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+        let ctx = create_test_context().await;
+        let cases = [
+            (
+                ZmqMessage::Register(RegisterMessage {
+                    message_type: "Register".to_string(),
+                    account_id: "MASTER_001".to_string(),
+                    ea_type: "Master".to_string(),
+                    platform: "MT5".to_string(),
+                    account_number: 12345,
+                    broker: "Test Broker".to_string(),
+                    account_name: "Test Account".to_string(),
+                    server: "Test-Server".to_string(),
+                    currency: "USD".to_string(),
+                    leverage: 100,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    symbol_context: None,
+                    is_trade_allowed: false,
+                }),
+                "MASTER_001",
+                "Master",
+                None,
+            ),
+            (
+                ZmqMessage::Heartbeat(build_heartbeat("SLAVE_001", "Slave", true)),
+                "SLAVE_001",
+                "Slave",
+                None,
+            ),
+            (
+                ZmqMessage::TradeSignal(create_test_trade_signal()),
+                "MASTER_001",
+                "Master",
+                Some("Open"),
+            ),
+            (
+                ZmqMessage::Unregister(UnregisterMessage {
+                    message_type: "Unregister".to_string(),
+                    account_id: "MASTER_001".to_string(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    ea_type: Some("Master".to_string()),
+                }),
+                "MASTER_001",
+                "Master",
+                None,
+            ),
+        ];
+
+        for (msg, account_id, ea_type, action) in cases {
+            capture.0.lock().unwrap().clear();
+            ctx.handle_message(msg).await;
+
+            // Skip the dispatch log emitted before the handler span is entered
+            let events = capture.0.lock().unwrap().clone();
+            let in_handler: Vec<&Fields> = events
+                .iter()
+                .filter(|fields| fields.contains_key("account_id"))
+                .collect();
+            assert!(!in_handler.is_empty(), "no events for {}", account_id);
+            for fields in in_handler {
+                assert_eq!(fields["account_id"], account_id);
+                assert_eq!(fields["ea_type"], ea_type);
+                if ea_type == "Master" {
+                    assert_eq!(fields["trade_group"], account_id);
+                }
+                assert_eq!(fields.get("action").map(String::as_str), action);
+            }
+        }
+        ctx.cleanup().await;
+    }
+}
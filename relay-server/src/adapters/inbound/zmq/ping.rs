@@ -0,0 +1,101 @@
+//! Ping echo handler
+//!
+//! Handles latency probes echoed back by EAs and records the measured
+//! round-trip time on the EA connection.
+
+use chrono::Utc;
+
+use super::MessageHandler;
+use crate::domain::models::{EaType, PingMessage};
+
+/// Round-trip time in milliseconds for a probe sent at `server_timestamp_ms`.
+/// Returns None for timestamps in the future (clock changes or forged echoes).
+pub(crate) fn round_trip_ms(server_timestamp_ms: i64, received_at_ms: i64) -> Option<i64> {
+    let rtt = received_at_ms - server_timestamp_ms;
+    (rtt >= 0).then_some(rtt)
+}
+
+impl MessageHandler {
+    /// Handle a ping echoed back by an EA
+    pub(super) async fn handle_ping(&self, msg: PingMessage) {
+        let Some(ea_type) = msg
+            .ea_type
+            .as_deref()
+            .and_then(|t| t.parse::<EaType>().ok())
+        else {
+            tracing::debug!("Ping echo without ea_type from {}", msg.account_id);
+            return;
+        };
+
+        let Some(rtt) = round_trip_ms(msg.server_timestamp, Utc::now().timestamp_millis()) else {
+            tracing::debug!(
+                "Ignoring ping echo with future timestamp from {} ({})",
+                msg.account_id,
+                ea_type
+            );
+            return;
+        };
+
+        if self
+            .connection_manager
+            .record_latency(&msg.account_id, ea_type, rtt)
+            .await
+        {
+            tracing::debug!("Ping RTT: {} ({}) {}ms", msg.account_id, ea_type, rtt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::inbound::zmq::test_helpers::{build_heartbeat, create_test_context};
+
+    #[test]
+    fn test_round_trip_ms() {
+        assert_eq!(round_trip_ms(1_000, 1_042), Some(42));
+        assert_eq!(round_trip_ms(1_000, 1_000), Some(0));
+        assert_eq!(round_trip_ms(1_000, 999), None);
+    }
+
+    #[tokio::test]
+    async fn test_handle_ping_records_latency() {
+        let ctx = create_test_context().await;
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        ctx.handle_ping(PingMessage {
+            message_type: "Ping".to_string(),
+            account_id: "SLAVE_001".to_string(),
+            ea_type: Some("Slave".to_string()),
+            server_timestamp: Utc::now().timestamp_millis() - 50,
+        })
+        .await;
+
+        let conn = ctx.connection_manager.get_slave("SLAVE_001").await.unwrap();
+        let latency = conn.last_latency_ms.expect("latency should be recorded");
+        assert!(latency >= 50, "latency was {}", latency);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_ping_without_ea_type_is_ignored() {
+        let ctx = create_test_context().await;
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        ctx.handle_ping(PingMessage {
+            message_type: "Ping".to_string(),
+            account_id: "SLAVE_001".to_string(),
+            ea_type: None,
+            server_timestamp: Utc::now().timestamp_millis(),
+        })
+        .await;
+
+        let conn = ctx.connection_manager.get_slave("SLAVE_001").await.unwrap();
+        assert_eq!(conn.last_latency_ms, None);
+
+        ctx.cleanup().await;
+    }
+}
@@ -0,0 +1,162 @@
+//! Copied position lot tracking
+//!
+//! Remembers the lots (and slave symbol/order type) the relay sent to each Slave
+//! for every copied Master ticket, so partial closes can be expressed as
+//! slave-side lots (`slave_settings.partial_close_by_lots`), Closes under
+//! `reverse_trade` can name the position they target, and Opens from a second
+//! Master on a symbol the Slave already holds can be detected
+//! (`MergeStrategy::PriorityFirst`).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::domain::models::OrderType;
+
+/// (master_account, slave_account, master ticket)
+type PositionKey = (String, String, i64);
+
+/// A copied position still open on the Slave
+#[derive(Debug, Clone)]
+struct CopiedPosition {
+    /// Symbol as sent to the Slave (after conversion)
+    symbol: Option<String>,
+    /// Order type as sent with the Open (before the EA applies `reverse_trade`)
+    order_type: Option<OrderType>,
+    /// Remaining copied lots
+    lots: f64,
+}
+
+/// Remaining copied lots per Master/Slave/ticket (in memory only)
+#[derive(Debug, Default)]
+pub(crate) struct PositionLotTracker {
+    lots: Mutex<HashMap<PositionKey, CopiedPosition>>,
+}
+
+impl PositionLotTracker {
+    /// Lots the Slave still holds for a copied ticket
+    pub(crate) fn get(&self, master: &str, slave: &str, ticket: i64) -> Option<f64> {
+        self.lots
+            .lock()
+            .unwrap()
+            .get(&(master.to_string(), slave.to_string(), ticket))
+            .map(|position| position.lots)
+    }
+
+    /// Symbol and order type the copied Open of a ticket was sent with
+    pub(crate) fn opened_as(
+        &self,
+        master: &str,
+        slave: &str,
+        ticket: i64,
+    ) -> Option<(Option<String>, Option<OrderType>)> {
+        self.lots
+            .lock()
+            .unwrap()
+            .get(&(master.to_string(), slave.to_string(), ticket))
+            .map(|position| (position.symbol.clone(), position.order_type.clone()))
+    }
+
+    /// Whether the Slave holds a copied position on `symbol` from a Master other than `master`
+    pub(crate) fn held_by_other_master(&self, master: &str, slave: &str, symbol: &str) -> bool {
+        self.lots
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|((m, s, _), position)| {
+                m != master && s == slave && position.symbol.as_deref() == Some(symbol)
+            })
+    }
+
+    /// Record the symbol, order type and lots sent with a copied Open
+    pub(crate) fn record_open(
+        &self,
+        master: &str,
+        slave: &str,
+        ticket: i64,
+        symbol: Option<&str>,
+        order_type: Option<OrderType>,
+        lots: f64,
+    ) {
+        self.lots.lock().unwrap().insert(
+            (master.to_string(), slave.to_string(), ticket),
+            CopiedPosition {
+                symbol: symbol.map(str::to_string),
+                order_type,
+                lots,
+            },
+        );
+    }
+
+    /// Apply a copied Close: partial ratios (0 < r < 1) shrink the remaining lots,
+    /// anything else is a full close and forgets the ticket
+    pub(crate) fn record_close(
+        &self,
+        master: &str,
+        slave: &str,
+        ticket: i64,
+        close_ratio: Option<f64>,
+    ) {
+        let key = (master.to_string(), slave.to_string(), ticket);
+        let mut lots = self.lots.lock().unwrap();
+        match close_ratio.filter(|r| *r > 0.0 && *r < 1.0) {
+            Some(ratio) => {
+                if let Some(position) = lots.get_mut(&key) {
+                    position.lots *= 1.0 - ratio;
+                }
+            }
+            None => {
+                lots.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_closes_shrink_remaining_lots() {
+        let tracker = PositionLotTracker::default();
+        tracker.record_open(
+            "MASTER_001",
+            "SLAVE_001",
+            1,
+            Some("EURUSD"),
+            Some(OrderType::Buy),
+            0.4,
+        );
+
+        tracker.record_close("MASTER_001", "SLAVE_001", 1, Some(0.5));
+        let remaining = tracker.get("MASTER_001", "SLAVE_001", 1).unwrap();
+        assert!((remaining - 0.2).abs() < 1e-9);
+
+        // Other slaves of the same master are tracked separately
+        assert_eq!(tracker.get("MASTER_001", "SLAVE_002", 1), None);
+
+        tracker.record_close("MASTER_001", "SLAVE_001", 1, None);
+        assert_eq!(tracker.get("MASTER_001", "SLAVE_001", 1), None);
+    }
+
+    #[test]
+    fn test_held_by_other_master() {
+        let tracker = PositionLotTracker::default();
+        tracker.record_open(
+            "MASTER_001",
+            "SLAVE_001",
+            1,
+            Some("EURUSD"),
+            Some(OrderType::Buy),
+            0.1,
+        );
+
+        assert!(tracker.held_by_other_master("MASTER_002", "SLAVE_001", "EURUSD"));
+        // Own positions, other symbols and other slaves do not count
+        assert!(!tracker.held_by_other_master("MASTER_001", "SLAVE_001", "EURUSD"));
+        assert!(!tracker.held_by_other_master("MASTER_002", "SLAVE_001", "GBPUSD"));
+        assert!(!tracker.held_by_other_master("MASTER_002", "SLAVE_002", "EURUSD"));
+
+        tracker.record_close("MASTER_001", "SLAVE_001", 1, None);
+        assert!(!tracker.held_by_other_master("MASTER_002", "SLAVE_001", "EURUSD"));
+    }
+}
@@ -1,12 +1,13 @@
 // relay-server/src/message_handler/position_snapshot.rs
 //
-// Handler for PositionSnapshot messages from Master EAs.
-// Routes position snapshots to all connected Slave EAs for synchronization.
+// Handler for PositionSnapshot messages from Master and Slave EAs.
+// Routes Master snapshots to all connected Slave EAs for synchronization;
+// Slave snapshots are only cached for position reconciliation.
 
 use sankey_copier_zmq::build_sync_topic;
 
 use super::MessageHandler;
-use crate::domain::models::PositionSnapshotMessage;
+use crate::domain::models::{EaType, PositionSnapshotMessage, ServerEvent};
 
 impl MessageHandler {
     /// Handle PositionSnapshot message from Master EA
@@ -14,18 +15,38 @@ impl MessageHandler {
     /// When a Master EA sends its current positions (e.g., after restart),
     /// this handler routes the snapshot to all connected Slave EAs.
     pub(super) async fn handle_position_snapshot(&self, snapshot: PositionSnapshotMessage) {
+        if snapshot.ea_type.as_deref() == Some("Slave") {
+            self.cache_slave_position_snapshot(snapshot).await;
+            return;
+        }
+
         tracing::info!(
             "Processing PositionSnapshot from {}: {} positions",
             snapshot.source_account,
             snapshot.positions.len()
         );
 
+        // Cache the position list for the connections API
+        if !self
+            .connection_manager
+            .record_position_snapshot(
+                &snapshot.source_account,
+                EaType::Master,
+                snapshot.positions.clone(),
+            )
+            .await
+        {
+            tracing::debug!(
+                "PositionSnapshot from unregistered Master {}, not cached",
+                snapshot.source_account
+            );
+        }
+
         // Notify WebSocket clients
-        let _ = self.broadcast_tx.send(format!(
-            "position_snapshot:{}:{}",
-            snapshot.source_account,
-            snapshot.positions.len()
-        ));
+        let _ = self.broadcast_tx.send(ServerEvent::PositionSnapshot {
+            source_account: snapshot.source_account.clone(),
+            positions: snapshot.positions.len(),
+        });
 
         // Get all members (slaves) for this master account
         let members = match self.db.get_members(&snapshot.source_account).await {
@@ -73,4 +94,97 @@ impl MessageHandler {
             members.len()
         );
     }
+
+    /// Cache a Slave's own positions (reported for reconciliation, never forwarded)
+    async fn cache_slave_position_snapshot(&self, snapshot: PositionSnapshotMessage) {
+        tracing::debug!(
+            "Caching PositionSnapshot from slave {}: {} positions",
+            snapshot.source_account,
+            snapshot.positions.len()
+        );
+        if !self
+            .connection_manager
+            .record_position_snapshot(&snapshot.source_account, EaType::Slave, snapshot.positions)
+            .await
+        {
+            tracing::debug!(
+                "PositionSnapshot from unregistered Slave {}, not cached",
+                snapshot.source_account
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::inbound::zmq::test_helpers::{build_heartbeat, create_test_context};
+    use crate::domain::models::{EaType, PositionInfo, PositionSnapshotMessage};
+
+    #[tokio::test]
+    async fn test_position_snapshot_is_cached_on_master_connection() {
+        let ctx = create_test_context().await;
+        ctx.handle_heartbeat(build_heartbeat("MASTER_001", "Master", true))
+            .await;
+
+        ctx.handle_position_snapshot(PositionSnapshotMessage {
+            message_type: "PositionSnapshot".to_string(),
+            source_account: "MASTER_001".to_string(),
+            positions: vec![PositionInfo {
+                ticket: 42,
+                symbol: "USDJPY".to_string(),
+                order_type: "Sell".to_string(),
+                lots: 0.5,
+                open_price: 150.25,
+                open_time: chrono::Utc::now().to_rfc3339(),
+                stop_loss: Some(151.0),
+                take_profit: None,
+                magic_number: None,
+                comment: None,
+                profit: -3.2,
+            }],
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            ea_type: None,
+        })
+        .await;
+
+        let cached = ctx
+            .connection_manager
+            .get_position_snapshot("MASTER_001", EaType::Master)
+            .await
+            .unwrap();
+        assert_eq!(cached.positions.len(), 1);
+        assert_eq!(cached.positions[0].ticket, 42);
+        assert_eq!(cached.positions[0].profit, -3.2);
+
+        ctx.cleanup().await;
+    }
+    #[tokio::test]
+    async fn test_slave_position_snapshot_is_cached_not_routed() {
+        let ctx = create_test_context().await;
+        ctx.handle_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+
+        ctx.handle_position_snapshot(PositionSnapshotMessage {
+            message_type: "PositionSnapshot".to_string(),
+            source_account: "SLAVE_001".to_string(),
+            positions: vec![],
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            ea_type: Some("Slave".to_string()),
+        })
+        .await;
+
+        assert!(ctx
+            .connection_manager
+            .get_position_snapshot("SLAVE_001", EaType::Slave)
+            .await
+            .is_some());
+        // Not treated as a Master snapshot
+        assert!(ctx
+            .connection_manager
+            .get_position_snapshot("SLAVE_001", EaType::Master)
+            .await
+            .is_none());
+
+        ctx.cleanup().await;
+    }
 }
@@ -8,7 +8,9 @@ use crate::domain::models::{RegisterMessage, VLogsGlobalSettings};
 use crate::domain::services::status_calculator::SlaveRuntimeTarget;
 use crate::domain::services::status_calculator::{ConnectionSnapshot, MasterIntent};
 
-use super::MessageHandler;
+use tracing::instrument;
+
+use super::{record_master_trade_group, MessageHandler};
 
 impl MessageHandler {
     /// Handle an explicit Register message from an EA
@@ -23,7 +25,13 @@ impl MessageHandler {
     ///
     /// The first Heartbeat will then update is_trade_allowed to the actual value
     /// and trigger a proper StatusEngine evaluation with accurate status.
+    #[instrument(skip_all, fields(
+        account_id = %msg.account_id,
+        ea_type = %msg.ea_type,
+        trade_group = tracing::field::Empty,
+    ))]
     pub async fn handle_register(&self, msg: RegisterMessage) {
+        record_master_trade_group(&msg.account_id, &msg.ea_type);
         let account_id = &msg.account_id;
         let ea_type = &msg.ea_type;
 
@@ -46,6 +54,11 @@ impl MessageHandler {
             );
         }
 
+        // A (re)started Master numbers its trade signals from 1 again
+        if ea_type == "Master" {
+            self.trade_sequences.reset(account_id);
+        }
+
         // 1. Register the EA with ConnectionManager (is_trade_allowed=false)
         self.connection_manager.register_ea(&msg).await;
 
@@ -141,6 +154,7 @@ impl MessageHandler {
             connection_snapshot: ConnectionSnapshot {
                 connection_status: Some(crate::domain::models::ConnectionStatus::Online),
                 is_trade_allowed,
+                low_margin_level: false,
             },
             settings: &trade_group.master_settings,
             timestamp: chrono::Utc::now(),
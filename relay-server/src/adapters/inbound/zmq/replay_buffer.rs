@@ -0,0 +1,265 @@
+//! Replay buffer for temporarily-offline Slaves
+//!
+//! Holds trade signals that could not be copied because the member was not
+//! connected, and hands them back in order once the Slave reconnects.
+//! Each Master/Slave pair has its own bounded queue.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+use crate::domain::models::{TradeAction, TradeSignal};
+
+/// Buffered signals for one Master/Slave pair
+#[derive(Debug, Default)]
+struct MemberQueue {
+    signals: VecDeque<TradeSignal>,
+    /// Tickets whose Open was evicted: later signals for them cannot be replayed
+    evicted_opens: HashSet<i64>,
+}
+
+/// Bounded per-member queues keyed by `(master_account, slave_account)`
+#[derive(Debug)]
+pub(crate) struct ReplayBuffer {
+    /// Maximum signals kept per member (0 disables buffering)
+    capacity: usize,
+    queues: Mutex<HashMap<(String, String), MemberQueue>>,
+}
+
+impl ReplayBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            queues: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Buffer a signal for later replay. Returns false if it was discarded.
+    ///
+    /// - Modify replaces an earlier buffered Modify of the same ticket (newest wins)
+    /// - A full Close whose Open is still buffered cancels both: the Slave never
+    ///   opened the position, so nothing is replayed for that ticket
+    /// - Signals for a ticket whose Open was evicted are discarded
+    /// - When the queue is full the oldest signal is evicted
+    pub(crate) fn push(
+        &self,
+        master_account: &str,
+        slave_account: &str,
+        signal: TradeSignal,
+    ) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues
+            .entry((master_account.to_string(), slave_account.to_string()))
+            .or_default();
+
+        if signal.action != TradeAction::Open && queue.evicted_opens.contains(&signal.ticket) {
+            if is_full_close(&signal) {
+                queue.evicted_opens.remove(&signal.ticket);
+            }
+            return false;
+        }
+
+        match signal.action {
+            TradeAction::Open => {}
+            TradeAction::Modify => queue.signals.retain(|queued| {
+                !(queued.ticket == signal.ticket && queued.action == TradeAction::Modify)
+            }),
+            TradeAction::Close => {
+                let open_buffered = queue.signals.iter().any(|queued| {
+                    queued.ticket == signal.ticket && queued.action == TradeAction::Open
+                });
+                if open_buffered && is_full_close(&signal) {
+                    queue
+                        .signals
+                        .retain(|queued| queued.ticket != signal.ticket);
+                    return false;
+                }
+            }
+        }
+
+        if queue.signals.len() >= self.capacity {
+            if let Some(evicted) = queue.signals.pop_front() {
+                tracing::warn!(
+                    master = %master_account,
+                    slave = %slave_account,
+                    ticket = evicted.ticket,
+                    "Replay buffer full, dropping oldest signal"
+                );
+                if evicted.action == TradeAction::Open {
+                    queue
+                        .signals
+                        .retain(|queued| queued.ticket != evicted.ticket);
+                    queue.evicted_opens.insert(evicted.ticket);
+                }
+            }
+        }
+        queue.signals.push_back(signal);
+        true
+    }
+
+    /// Remove and return a member's buffered signals in arrival order
+    pub(crate) fn take(&self, master_account: &str, slave_account: &str) -> Vec<TradeSignal> {
+        self.queues
+            .lock()
+            .unwrap()
+            .remove(&(master_account.to_string(), slave_account.to_string()))
+            .map(|queue| queue.signals.into())
+            .unwrap_or_default()
+    }
+
+    /// Whether signals are waiting for this Master/Slave pair
+    pub(crate) fn has_pending(&self, master_account: &str, slave_account: &str) -> bool {
+        self.queues
+            .lock()
+            .unwrap()
+            .get(&(master_account.to_string(), slave_account.to_string()))
+            .is_some_and(|queue| !queue.signals.is_empty())
+    }
+
+    /// Masters with signals waiting for this Slave
+    pub(crate) fn pending_masters(&self, slave_account: &str) -> Vec<String> {
+        self.queues
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((_, slave), queue)| slave == slave_account && !queue.signals.is_empty())
+            .map(|((master, _), _)| master.clone())
+            .collect()
+    }
+}
+
+/// `close_ratio` None or >= 1.0 closes the whole position
+fn is_full_close(signal: &TradeSignal) -> bool {
+    signal.close_ratio.is_none_or(|ratio| ratio >= 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(action: TradeAction, ticket: i64) -> TradeSignal {
+        TradeSignal {
+            action,
+            ticket,
+            symbol: Some("EURUSD".to_string()),
+            order_type: None,
+            lots: Some(0.1),
+            open_price: None,
+            stop_loss: None,
+            take_profit: None,
+            magic_number: None,
+            comment: None,
+            timestamp: chrono::Utc::now(),
+            source_account: "MASTER_001".to_string(),
+            close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
+        }
+    }
+
+    fn queued(buffer: &ReplayBuffer) -> Vec<(TradeAction, i64)> {
+        buffer
+            .take("MASTER_001", "SLAVE_001")
+            .into_iter()
+            .map(|s| (s.action, s.ticket))
+            .collect()
+    }
+
+    #[test]
+    fn test_disabled_buffer_keeps_nothing() {
+        let buffer = ReplayBuffer::new(0);
+        assert!(!buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1)));
+        assert!(buffer.pending_masters("SLAVE_001").is_empty());
+    }
+
+    #[test]
+    fn test_keeps_order_and_newest_modify() {
+        let buffer = ReplayBuffer::new(10);
+        let mut first_modify = signal(TradeAction::Modify, 1);
+        first_modify.stop_loss = Some(1.0);
+        let mut second_modify = signal(TradeAction::Modify, 1);
+        second_modify.stop_loss = Some(2.0);
+
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1));
+        buffer.push("MASTER_001", "SLAVE_001", first_modify);
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 2));
+        buffer.push("MASTER_001", "SLAVE_001", second_modify);
+
+        assert_eq!(buffer.pending_masters("SLAVE_001"), vec!["MASTER_001"]);
+        let signals = buffer.take("MASTER_001", "SLAVE_001");
+        let order: Vec<_> = signals
+            .iter()
+            .map(|s| (s.action.clone(), s.ticket))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (TradeAction::Open, 1),
+                (TradeAction::Open, 2),
+                (TradeAction::Modify, 1)
+            ]
+        );
+        assert_eq!(signals[2].stop_loss, Some(2.0));
+        // Taking empties the queue
+        assert!(buffer.take("MASTER_001", "SLAVE_001").is_empty());
+    }
+
+    #[test]
+    fn test_close_cancels_buffered_open() {
+        let buffer = ReplayBuffer::new(10);
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1));
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Modify, 1));
+        assert!(!buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Close, 1)));
+
+        // Close for a position the Slave already holds is replayed
+        assert!(buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Close, 7)));
+        assert_eq!(queued(&buffer), vec![(TradeAction::Close, 7)]);
+    }
+
+    #[test]
+    fn test_partial_close_is_replayed_after_open() {
+        let buffer = ReplayBuffer::new(10);
+        let mut partial = signal(TradeAction::Close, 1);
+        partial.close_ratio = Some(0.5);
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1));
+        assert!(buffer.push("MASTER_001", "SLAVE_001", partial));
+        assert_eq!(
+            queued(&buffer),
+            vec![(TradeAction::Open, 1), (TradeAction::Close, 1)]
+        );
+    }
+
+    #[test]
+    fn test_overflow_evicts_oldest_and_drops_its_close() {
+        let buffer = ReplayBuffer::new(2);
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1));
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 2));
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 3));
+
+        // Ticket 1 never reaches the Slave, so its Close is meaningless
+        assert!(!buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Close, 1)));
+        assert_eq!(
+            queued(&buffer),
+            vec![(TradeAction::Open, 2), (TradeAction::Open, 3)]
+        );
+    }
+
+    #[test]
+    fn test_queues_are_per_member() {
+        let buffer = ReplayBuffer::new(10);
+        buffer.push("MASTER_001", "SLAVE_001", signal(TradeAction::Open, 1));
+        buffer.push("MASTER_002", "SLAVE_002", signal(TradeAction::Open, 2));
+
+        assert_eq!(buffer.pending_masters("SLAVE_001"), vec!["MASTER_001"]);
+        assert_eq!(buffer.take("MASTER_002", "SLAVE_001").len(), 0);
+        assert_eq!(buffer.take("MASTER_002", "SLAVE_002").len(), 1);
+    }
+}
@@ -0,0 +1,89 @@
+//! Trade signal sequence tracking
+//!
+//! Drops TradeSignals that ZeroMQ redelivers after a reconnect by remembering
+//! the last accepted `seq` per Master account.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Last accepted sequence number per `source_account`
+#[derive(Debug, Default)]
+pub(crate) struct SequenceTracker {
+    last_seen: Mutex<HashMap<String, u64>>,
+}
+
+impl SequenceTracker {
+    /// Returns true if the signal should be processed (and records its seq)
+    ///
+    /// - `seq == 0` means the sender does not number signals: always accepted
+    /// - The first numbered signal from an account is always accepted
+    /// - Otherwise `seq` must be newer than the last one using serial-number
+    ///   arithmetic, so a counter wrapping past `u64::MAX` keeps working
+    pub(crate) fn accept(&self, source_account: &str, seq: u64) -> bool {
+        if seq == 0 {
+            return true;
+        }
+
+        let mut last_seen = self.last_seen.lock().unwrap();
+        match last_seen.get(source_account) {
+            Some(&last) if !is_newer(seq, last) => false,
+            _ => {
+                last_seen.insert(source_account.to_string(), seq);
+                true
+            }
+        }
+    }
+
+    /// Forget an account's sequence (EA restarted and numbers from 1 again)
+    pub(crate) fn reset(&self, source_account: &str) {
+        self.last_seen.lock().unwrap().remove(source_account);
+    }
+}
+
+/// Serial-number comparison (RFC 1982 style) over u64
+fn is_newer(seq: u64, last: u64) -> bool {
+    let delta = seq.wrapping_sub(last);
+    delta != 0 && delta < (1 << 63)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_only_increasing_sequences() {
+        let tracker = SequenceTracker::default();
+        let accepted: Vec<u64> = [1, 2, 2, 3, 1]
+            .into_iter()
+            .filter(|&seq| tracker.accept("MASTER_001", seq))
+            .collect();
+        assert_eq!(accepted, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_first_signal_and_unsequenced_signals() {
+        let tracker = SequenceTracker::default();
+        // First numbered signal is accepted whatever its value
+        assert!(tracker.accept("MASTER_001", 42));
+        // Accounts are tracked independently
+        assert!(tracker.accept("MASTER_002", 1));
+        // seq 0 (legacy sender) is never deduplicated
+        assert!(tracker.accept("MASTER_001", 0));
+        assert!(tracker.accept("MASTER_001", 0));
+        assert!(!tracker.accept("MASTER_001", 42));
+    }
+
+    #[test]
+    fn test_wraparound_and_reset() {
+        let tracker = SequenceTracker::default();
+        assert!(tracker.accept("MASTER_001", u64::MAX - 1));
+        assert!(tracker.accept("MASTER_001", u64::MAX));
+        // Counter wrapped (0 is skipped by senders)
+        assert!(tracker.accept("MASTER_001", 1));
+        assert!(!tracker.accept("MASTER_001", u64::MAX));
+
+        // After reset (EA re-registered) numbering may restart
+        tracker.reset("MASTER_001");
+        assert!(tracker.accept("MASTER_001", 1));
+    }
+}
@@ -0,0 +1,104 @@
+//! Per-Master trade signal rate limiting
+//!
+//! A malfunctioning Master EA can emit signals far faster than any Slave can
+//! execute them. `master_settings.max_signals_per_sec` caps the rate with a
+//! token bucket per Master: the bucket holds up to one second of signals and
+//! refills continuously, so short bursts up to the limit pass and the excess
+//! is dropped.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Outcome of offering one signal to the throttle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Throttle {
+    /// Within the rate limit
+    Allowed,
+    /// Over the limit; `first` is true for the first drop since a signal was last allowed
+    Dropped { first: bool },
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    throttling: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct SignalThrottle {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl SignalThrottle {
+    /// Take one token from `master`'s bucket, refilled at `max_per_sec` tokens per second
+    pub(crate) fn check(&self, master: &str, max_per_sec: u32, now: Instant) -> Throttle {
+        let capacity = f64::from(max_per_sec.max(1));
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(master.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+            throttling: false,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * capacity).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.throttling = false;
+            Throttle::Allowed
+        } else {
+            let first = !bucket.throttling;
+            bucket.throttling = true;
+            Throttle::Dropped { first }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_burst_is_capped_at_rate() {
+        let throttle = SignalThrottle::default();
+        let start = Instant::now();
+
+        // 100 signals within 50ms at 10/sec: the full bucket plus half a token of refill
+        let outcomes: Vec<Throttle> = (0..100)
+            .map(|i| throttle.check("MASTER_001", 10, start + Duration::from_micros(i * 500)))
+            .collect();
+        let allowed = outcomes.iter().filter(|o| **o == Throttle::Allowed).count();
+        assert_eq!(allowed, 10);
+        assert_eq!(outcomes[10], Throttle::Dropped { first: true });
+        assert_eq!(outcomes[11], Throttle::Dropped { first: false });
+
+        // Other Masters have their own bucket
+        assert_eq!(throttle.check("MASTER_002", 10, start), Throttle::Allowed);
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let throttle = SignalThrottle::default();
+        let start = Instant::now();
+        for _ in 0..5 {
+            throttle.check("MASTER_001", 5, start);
+        }
+        assert!(matches!(
+            throttle.check("MASTER_001", 5, start),
+            Throttle::Dropped { .. }
+        ));
+
+        // 400ms at 5/sec refills two tokens
+        let later = start + Duration::from_millis(400);
+        assert_eq!(throttle.check("MASTER_001", 5, later), Throttle::Allowed);
+        assert_eq!(throttle.check("MASTER_001", 5, later), Throttle::Allowed);
+        assert_eq!(
+            throttle.check("MASTER_001", 5, later),
+            Throttle::Dropped { first: true }
+        );
+    }
+}
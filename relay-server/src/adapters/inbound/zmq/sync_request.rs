@@ -6,7 +6,7 @@
 use sankey_copier_zmq::build_sync_topic;
 
 use super::MessageHandler;
-use crate::domain::models::SyncRequestMessage;
+use crate::domain::models::{ServerEvent, SyncRequestMessage};
 
 impl MessageHandler {
     /// Handle SyncRequest message from Slave EA
@@ -21,10 +21,10 @@ impl MessageHandler {
         );
 
         // Notify WebSocket clients
-        let _ = self.broadcast_tx.send(format!(
-            "sync_request:{}:{}",
-            request.slave_account, request.master_account
-        ));
+        let _ = self.broadcast_tx.send(ServerEvent::SyncRequest {
+            slave_account: request.slave_account.clone(),
+            master_account: request.master_account.clone(),
+        });
 
         // Verify the slave is actually a member of this master's trade group
         let members = match self.db.get_members(&request.master_account).await {
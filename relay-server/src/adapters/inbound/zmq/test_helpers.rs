@@ -5,6 +5,7 @@ use crate::adapters::outbound::persistence::Database;
 use crate::domain::models::{
     HeartbeatMessage,
     OrderType,
+    ServerEvent,
     TradeAction,
     TradeSignal,
     // LotCalculationMode, // Unused
@@ -28,7 +29,7 @@ pub(crate) struct TestContext {
     _publisher: Arc<ZmqConfigPublisher>,
     /// Broadcast receiver for testing WebSocket notifications
     #[allow(dead_code)]
-    pub _broadcast_rx: broadcast::Receiver<String>, // Prefixed with _ to suppress warning
+    pub _broadcast_rx: broadcast::Receiver<ServerEvent>, // Prefixed with _ to suppress warning
 }
 
 impl TestContext {
@@ -38,7 +39,7 @@ impl TestContext {
         let connection_manager = Arc::new(ConnectionManagerImpl::new(30));
         let copy_engine = Arc::new(CopyEngine::new());
 
-        let (broadcast_tx, broadcast_rx) = broadcast::channel::<String>(100);
+        let (broadcast_tx, broadcast_rx) = broadcast::channel::<ServerEvent>(100);
 
         // Create test database (in-memory)
         let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
@@ -115,8 +116,9 @@ impl TestContext {
     #[allow(dead_code)]
     pub async fn with_config(config: crate::config::Config) -> Self {
         let connection_manager = Arc::new(ConnectionManagerImpl::new(30));
-        let copy_engine = Arc::new(CopyEngine::new());
-        let (broadcast_tx, broadcast_rx) = broadcast::channel::<String>(100);
+        let copy_engine =
+            Arc::new(CopyEngine::new().with_symbol_specs(Arc::new(config.symbol_spec_registry())));
+        let (broadcast_tx, broadcast_rx) = broadcast::channel::<ServerEvent>(100);
         let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
         // Dynamic port publisher
         let publisher = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:*").unwrap());
@@ -226,6 +228,9 @@ pub(crate) fn build_heartbeat(
         account_id: account_id.to_string(),
         balance: 10_000.0,
         equity: 10_000.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
         open_positions: 0,
         timestamp: Utc::now().to_rfc3339(),
         version: "1.0.0".to_string(),
@@ -260,5 +265,8 @@ pub(crate) fn create_test_trade_signal() -> TradeSignal {
         timestamp: Utc::now(),
         source_account: "MASTER_001".to_string(),
         close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     }
 }
@@ -1,44 +1,72 @@
 //! Trade signal handler
 //!
 //! Handles incoming trade signals from Master EAs, applies filters,
-//! transforms signals, and distributes them to Slave EAs.
+//! transforms signals, distributes them to Slave EAs and records each
+//! copy decision in the trade history.
 
-use super::MessageHandler;
-use crate::domain::models::{MasterSettings, SymbolConverter, TradeGroupMember, TradeSignal};
+use std::borrow::Cow;
+
+use chrono::Utc;
+use tracing::instrument;
+
+use super::signal_throttle::Throttle;
+use super::{copy_delay, MessageHandler};
+use crate::application::runtime_status_updater::effective_slave_settings;
+use crate::domain::models::{
+    ConnectionStatus, GlobalSettings, MasterSettings, MergeStrategy, ServerEvent, SymbolConverter,
+    TradeAction, TradeGroupMember, TradeHistoryEntry, TradeSignal, WarningCode,
+};
+use crate::domain::services::copy_engine::{
+    portfolio_weight_total, CopyContext, CopyDecision, SkipReason,
+};
 
 impl MessageHandler {
     /// Handle trade signals and process copying
+    #[instrument(skip_all, fields(
+        account_id = %signal.source_account,
+        trade_group = %signal.source_account,
+        ea_type = "Master",
+        action = ?signal.action,
+    ))]
     pub(super) async fn handle_trade_signal(&self, signal: TradeSignal) {
+        if !self
+            .trade_sequences
+            .accept(&signal.source_account, signal.seq)
+        {
+            tracing::debug!(
+                master = %signal.source_account,
+                seq = signal.seq,
+                ticket = signal.ticket,
+                "Dropping duplicate trade signal (non-increasing seq)"
+            );
+            return;
+        }
+
         tracing::info!("Processing trade signal: {:?}", signal);
+        self.runtime_status_metrics
+            .record_signal_received(&signal.source_account);
 
         // Notify WebSocket clients
-        let _ = self.broadcast_tx.send(format!(
-            "trade_received:{}:{}:{}",
-            signal.source_account,
-            signal.symbol.as_deref().unwrap_or("?"),
-            signal.lots.unwrap_or(0.0)
-        ));
-
-        // Get master settings for symbol prefix/suffix
-        let master_settings = match self.db.get_trade_group(&signal.source_account).await {
-            Ok(Some(tg)) => tg.master_settings,
-            Ok(None) => {
-                tracing::warn!(
-                    "TradeGroup not found for master {}, using defaults",
-                    signal.source_account
-                );
-                MasterSettings::default()
-            }
-            Err(e) => {
-                tracing::error!(
-                    "Failed to get TradeGroup for master {}: {}",
-                    signal.source_account,
-                    e
-                );
-                return;
-            }
+        let _ = self.broadcast_tx.send(ServerEvent::TradeReceived {
+            master_account: signal.source_account.clone(),
+            symbol: signal.symbol.clone(),
+            lots: signal.lots,
+        });
+
+        let Some(master_settings) = self.load_master_settings(&signal.source_account).await else {
+            return;
         };
 
+        if !master_settings.allows_action(&signal.action) {
+            tracing::debug!(
+                master = %signal.source_account,
+                ticket = signal.ticket,
+                "Skipping {:?} signal: master is in closes-only mode",
+                signal.action
+            );
+            return;
+        }
+
         // Get all members (slaves) for this master account
         let members = match self.db.get_members(&signal.source_account).await {
             Ok(members) => members,
@@ -52,29 +80,399 @@ impl MessageHandler {
             }
         };
 
+        // Rate limit per Master; Closes are exempt so exits always propagate
+        if let Some(max_per_sec) = master_settings.max_signals_per_sec {
+            if signal.action != TradeAction::Close {
+                let outcome = self.signal_throttle.check(
+                    &signal.source_account,
+                    max_per_sec,
+                    std::time::Instant::now(),
+                );
+                if let Throttle::Dropped { first } = outcome {
+                    self.runtime_status_metrics.record_throttled_signal();
+                    if first {
+                        self.report_signal_rate_exceeded(&signal, &members, max_per_sec);
+                    }
+                    return;
+                }
+            }
+        }
+
+        // Stale Opens are dropped here; Close/Modify always pass so exits are never blocked
+        if signal.action == TradeAction::Open {
+            if let Some(max_age_ms) = master_settings.max_signal_age_ms {
+                let age_ms = (Utc::now() - signal.timestamp).num_milliseconds();
+                if age_ms > max_age_ms {
+                    tracing::warn!(
+                        master = %signal.source_account,
+                        ticket = signal.ticket,
+                        age_ms,
+                        max_age_ms,
+                        "Dropping stale Open signal"
+                    );
+                    let reason = format!("stale_signal:{}ms", age_ms);
+                    for member in &members {
+                        self.record_trade_history(&signal, member, Some(reason.clone()))
+                            .await;
+                    }
+                    return;
+                }
+            }
+        }
+
+        let global_settings = self.load_global_settings().await;
+
+        // A member can turn CONNECTED without the Slave heartbeat that replays its
+        // buffered signals (e.g. on the Master's heartbeat); send those first so
+        // this signal cannot overtake them
+        for member in &members {
+            if member.is_connected()
+                && self
+                    .replay_buffer
+                    .has_pending(&signal.source_account, &member.slave_account)
+            {
+                self.replay_member(member, &master_settings, &members, &global_settings)
+                    .await;
+            }
+        }
+
+        let mut copied = false;
         for member in &members {
-            // Apply filters
-            if !self.copy_engine.should_copy_trade(&signal, member) {
+            copied |= self
+                .copy_to_member(
+                    &signal,
+                    member,
+                    &master_settings,
+                    &members,
+                    &global_settings,
+                )
+                .await;
+        }
+        if copied {
+            self.runtime_status_metrics
+                .record_signal_copied(&signal.source_account);
+        }
+    }
+
+    /// Warn once per throttled burst; the dropped signals themselves are only
+    /// counted so a spamming Master does not also flood the UI and trade history
+    fn report_signal_rate_exceeded(
+        &self,
+        signal: &TradeSignal,
+        members: &[TradeGroupMember],
+        max_per_sec: u32,
+    ) {
+        tracing::warn!(
+            master = %signal.source_account,
+            ticket = signal.ticket,
+            max_per_sec,
+            "Master exceeded its signal rate limit; dropping signals"
+        );
+        let reason = serde_json::to_value(WarningCode::MasterSignalRateExceeded)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        for member in members {
+            let _ = self.broadcast_tx.send(ServerEvent::TradeSkipped {
+                master_account: member.trade_group_id.clone(),
+                slave_account: member.slave_account.clone(),
+                ticket: signal.ticket,
+                reason: reason.clone(),
+                member_id: member.id,
+            });
+        }
+    }
+
+    /// Relay-wide settings shared by every member of a signal (defaults on database error).
+    /// A missing cap only loses the clamp; member saves already enforce it.
+    /// The global blacklist is skipped too, leaving the member filters in charge.
+    pub(super) async fn load_global_settings(&self) -> GlobalSettings {
+        match self.db.get_global_settings().await {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::error!("Failed to load global settings: {}", e);
+                GlobalSettings::default()
+            }
+        }
+    }
+
+    /// Get master settings for symbol prefix/suffix (None on database error)
+    pub(super) async fn load_master_settings(
+        &self,
+        master_account: &str,
+    ) -> Option<MasterSettings> {
+        match self.db.get_trade_group(master_account).await {
+            Ok(Some(tg)) => Some(tg.master_settings),
+            Ok(None) => {
+                tracing::warn!(
+                    "TradeGroup not found for master {}, using defaults",
+                    master_account
+                );
+                Some(MasterSettings::default())
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to get TradeGroup for master {}: {}",
+                    master_account,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Apply filters to one member, copy the signal and record the decision.
+    /// Returns whether the signal was sent (or scheduled) to the member.
+    /// `members` is the whole TradeGroup, used to split lots by `portfolio_weight`;
+    /// `global_settings` is loaded once per signal (or replay) by the caller.
+    pub(super) async fn copy_to_member(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        master_settings: &MasterSettings,
+        members: &[TradeGroupMember],
+        global_settings: &GlobalSettings,
+    ) -> bool {
+        // Filters and symbol conversion see the broker profile's prefix/suffix/mappings
+        let resolved;
+        let member = match effective_slave_settings(self.db.as_ref(), &member.slave_settings).await
+        {
+            Cow::Borrowed(_) => member,
+            Cow::Owned(slave_settings) => {
+                resolved = TradeGroupMember {
+                    slave_settings,
+                    ..member.clone()
+                };
+                &resolved
+            }
+        };
+        let copy_context = self
+            .build_copy_context(signal, member, master_settings, members, global_settings)
+            .await;
+
+        // Apply filters
+        match self
+            .copy_engine
+            .should_copy_trade(signal, member, &copy_context)
+        {
+            CopyDecision::Copy => {}
+            CopyDecision::Skip(reason) => {
                 tracing::debug!(
-                    "Trade filtered out for slave account: {}",
-                    member.slave_account
+                    "Trade filtered out for slave account {}: {}",
+                    member.slave_account,
+                    reason.as_str()
+                );
+                if reason == SkipReason::NotConnected {
+                    self.buffer_for_offline_slave(signal, member).await;
+                }
+                self.record_trade_history(signal, member, Some(reason.as_str().to_string()))
+                    .await;
+                return false;
+            }
+            CopyDecision::SkipWithWarning(code) => {
+                tracing::warn!(
+                    "Trade skipped for slave account {}: {:?}",
+                    member.slave_account,
+                    code
                 );
-                continue;
+                // Notify WebSocket clients so the UI can explain the missing copy
+                let code = serde_json::to_value(&code)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                let _ = self.broadcast_tx.send(ServerEvent::TradeSkipped {
+                    master_account: member.trade_group_id.clone(),
+                    slave_account: member.slave_account.clone(),
+                    ticket: signal.ticket,
+                    reason: code.to_string(),
+                    member_id: member.id,
+                });
+                self.record_trade_history(signal, member, Some(code)).await;
+                return false;
             }
+        }
 
-            // Process the trade copy
-            self.process_trade_copy(&signal, member, &master_settings)
-                .await;
+        // Process the trade copy
+        let failure = self
+            .process_trade_copy(signal, member, master_settings, &copy_context)
+            .await
+            .err();
+        let copied = failure.is_none();
+        self.record_trade_history(signal, member, failure.map(str::to_string))
+            .await;
+        copied
+    }
+
+    /// Keep a signal for replay when the member is enabled but its Slave EA is offline.
+    /// Members disabled by the user or by the Slave's auto-trading switch are not buffered.
+    async fn buffer_for_offline_slave(&self, signal: &TradeSignal, member: &TradeGroupMember) {
+        if !self.replay_buffer.is_enabled() || !member.enabled_flag {
+            return;
+        }
+        let slave_online = self
+            .connection_manager
+            .get_slave(&member.slave_account)
+            .await
+            .is_some_and(|conn| conn.status == ConnectionStatus::Online);
+        if slave_online {
+            return;
+        }
+        if self.replay_buffer.push(
+            &signal.source_account,
+            &member.slave_account,
+            signal.clone(),
+        ) {
+            tracing::info!(
+                "Buffered {:?} for offline slave {} (ticket {})",
+                signal.action,
+                member.slave_account,
+                signal.ticket
+            );
+        }
+    }
+
+    /// Keep the copied lots per ticket up to date after a successful send
+    /// (`close_ratio` comes from the Master signal; lots mode clears it on the sent copy)
+    fn track_position_lots(
+        &self,
+        signal: &TradeSignal,
+        sent: &TradeSignal,
+        member: &TradeGroupMember,
+    ) {
+        let (master, slave) = (&signal.source_account, &member.slave_account);
+        match signal.action {
+            TradeAction::Open => {
+                if let Some(lots) = sent.lots {
+                    self.position_lots.record_open(
+                        master,
+                        slave,
+                        signal.ticket,
+                        sent.symbol.as_deref(),
+                        sent.order_type.clone(),
+                        lots,
+                    );
+                }
+            }
+            TradeAction::Close => {
+                self.position_lots
+                    .record_close(master, slave, signal.ticket, signal.close_ratio);
+            }
+            TradeAction::Modify => {}
+        }
+    }
+
+    /// Under `MergeStrategy::PriorityFirst`, an Open conflicts when the Slave already
+    /// holds the converted symbol from another Master (the first Master keeps it)
+    fn is_merge_conflict(
+        &self,
+        signal: &TradeSignal,
+        sent: &TradeSignal,
+        member: &TradeGroupMember,
+    ) -> bool {
+        if signal.action != TradeAction::Open
+            || member.slave_settings.merge_strategy != MergeStrategy::PriorityFirst
+        {
+            return false;
+        }
+        sent.symbol.as_deref().is_some_and(|symbol| {
+            self.position_lots.held_by_other_master(
+                &signal.source_account,
+                &member.slave_account,
+                symbol,
+            )
+        })
+    }
+
+    /// Persist the copy decision for one slave (`filter_reason = None` means copied).
+    /// Failures are logged only; history must never block trade copying.
+    async fn record_trade_history(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        filter_reason: Option<String>,
+    ) {
+        let entry = TradeHistoryEntry {
+            id: 0,
+            master_account: signal.source_account.clone(),
+            slave_account: member.slave_account.clone(),
+            ticket: signal.ticket,
+            action: signal.action.clone(),
+            symbol: signal.symbol.clone(),
+            lots: signal.lots,
+            timestamp: signal.timestamp,
+            copied: filter_reason.is_none(),
+            filter_reason,
+        };
+        if let Err(e) = self.db.insert_trade_history(&entry).await {
+            tracing::error!(
+                "Failed to record trade history for ticket {} ({} -> {}): {}",
+                signal.ticket,
+                signal.source_account,
+                member.slave_account,
+                e
+            );
+        }
+    }
+
+    /// Collect runtime account data for relay-side lot calculation
+    /// (latest heartbeat equity/balance and the configured pip value)
+    async fn build_copy_context(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        master_settings: &MasterSettings,
+        members: &[TradeGroupMember],
+        global_settings: &GlobalSettings,
+    ) -> CopyContext {
+        let slave_conn = self
+            .connection_manager
+            .get_slave(&member.slave_account)
+            .await;
+        let master_conn = self
+            .connection_manager
+            .get_master(&signal.source_account)
+            .await;
+        let (slave_position_symbol, slave_position_order_type) = self
+            .position_lots
+            .opened_as(&signal.source_account, &member.slave_account, signal.ticket)
+            .unwrap_or_default();
+        CopyContext {
+            master_equity: master_conn.map(|conn| conn.equity),
+            slave_equity: slave_conn.as_ref().map(|conn| conn.equity),
+            slave_balance: slave_conn.as_ref().map(|conn| conn.balance),
+            slave_open_positions: slave_conn
+                .as_ref()
+                .map(|conn| conn.open_positions.max(0) as u32),
+            slave_daily_loss: slave_conn
+                .as_ref()
+                .and_then(|conn| conn.daily_loss(Utc::now())),
+            slave_position_lots: self.position_lots.get(
+                &signal.source_account,
+                &member.slave_account,
+                signal.ticket,
+            ),
+            slave_position_symbol,
+            slave_position_order_type,
+            pip_value: signal
+                .symbol
+                .as_deref()
+                .map(|symbol| self.copy_engine.symbol_specs().spec(symbol).pip_value),
+            max_lot_multiplier: global_settings.max_lot_multiplier,
+            global_blocked_symbols: global_settings.blocked_symbols.clone(),
+            portfolio_weight_total: portfolio_weight_total(members),
+            total_lot_budget: master_settings.total_lot_budget,
         }
     }
 
     /// Process a single trade copy for a specific member
+    /// Returns the failure (or merge_conflict skip) reason recorded in the trade history on error
     async fn process_trade_copy(
         &self,
         signal: &TradeSignal,
         member: &TradeGroupMember,
         master_settings: &MasterSettings,
-    ) {
+        copy_context: &CopyContext,
+    ) -> Result<(), &'static str> {
         // Transform signal
         // SymbolConverter removes master's prefix/suffix and applies slave's prefix/suffix + mappings
         let mut converter = SymbolConverter::from_settings(master_settings, &member.slave_settings);
@@ -85,30 +483,35 @@ impl MessageHandler {
             .get_slave(&member.slave_account)
             .await
         {
-            let (detected_symbols, detected_prefix, detected_suffix) =
-                if let Some(ctx) = &slave_conn.symbol_context {
-                    (
-                        Some(ctx.available_special_symbols.clone()),
-                        Some(ctx.detected_prefix.clone()),
-                        Some(ctx.detected_suffix.clone()),
-                    )
-                } else {
-                    (None, None, None)
-                };
+            let detected_symbols = slave_conn
+                .symbol_context
+                .as_ref()
+                .map(|ctx| ctx.available_special_symbols.clone());
 
+            // Prefix/suffix fall back to heartbeat-reported, then detected values
+            // when slave_settings leaves them unset
             converter = converter.with_auto_mapping(
                 self.config.symbol_mapping.synonym_groups.clone(),
                 detected_symbols,
-                detected_prefix,
-                detected_suffix,
+                slave_conn.fallback_symbol_prefix(),
+                slave_conn.fallback_symbol_suffix(),
             );
         }
 
         match self
             .copy_engine
-            .transform_signal(signal.clone(), member, &converter)
+            .transform_signal(signal.clone(), member, &converter, copy_context)
         {
             Ok(transformed) => {
+                if self.is_merge_conflict(signal, &transformed, member) {
+                    tracing::info!(
+                        "Skipping Open for {}: {} is already held from another master (priority_first)",
+                        member.slave_account,
+                        transformed.symbol.as_deref().unwrap_or("?")
+                    );
+                    return Err("merge_conflict");
+                }
+
                 tracing::info!(
                     "Copying trade to {}: {} {} lots",
                     member.slave_account,
@@ -116,6 +519,28 @@ impl MessageHandler {
                     transformed.lots.unwrap_or(0.0)
                 );
 
+                let settings = &member.slave_settings;
+                let delay = settings
+                    .copy_delay_ms
+                    .filter(|_| {
+                        !(signal.action == TradeAction::Close && settings.copy_delay_skip_closes)
+                    })
+                    .map(copy_delay::pick_delay);
+                // An undelayed signal still waits for a pending one of the same Slave
+                if delay.is_some()
+                    || self
+                        .copy_delays
+                        .has_pending(&signal.source_account, &member.slave_account)
+                {
+                    self.schedule_delayed_copy(
+                        signal,
+                        member,
+                        transformed,
+                        delay.unwrap_or_default(),
+                    );
+                    return Ok(());
+                }
+
                 // Send to specific Master-Slave pair using trade/{master}/{slave} topic
                 // Each slave subscribes to their specific topic for precise filtering
                 if let Err(e) = self
@@ -124,7 +549,9 @@ impl MessageHandler {
                     .await
                 {
                     tracing::error!("Failed to send signal to trade group: {}", e);
+                    Err("publish_failed")
                 } else {
+                    self.track_position_lots(signal, &transformed, member);
                     tracing::debug!(
                         "Sent signal on topic 'trade/{}/{}' for slave '{}'",
                         signal.source_account,
@@ -133,29 +560,82 @@ impl MessageHandler {
                     );
 
                     // Notify WebSocket clients
-                    let _ = self.broadcast_tx.send(format!(
-                        "trade_copied:{}:{}:{}:{}",
-                        member.slave_account,
-                        transformed.symbol.as_deref().unwrap_or("?"),
-                        transformed.lots.unwrap_or(0.0),
-                        member.id
-                    ));
+                    let _ = self
+                        .broadcast_tx
+                        .send(trade_copied_event(member, &transformed));
+                    Ok(())
                 }
             }
             Err(e) => {
                 tracing::error!("Failed to transform signal: {}", e);
+                Err("transform_failed")
             }
         }
     }
+
+    /// Publish a transformed signal after `delay` (copy_delay_ms). The copy is recorded
+    /// as sent when scheduled; a later publish failure is only logged.
+    fn schedule_delayed_copy(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        transformed: TradeSignal,
+        delay: std::time::Duration,
+    ) {
+        tracing::debug!(
+            slave = %member.slave_account,
+            ticket = signal.ticket,
+            delay_ms = delay.as_millis() as u64,
+            "Delaying copied signal"
+        );
+        self.track_position_lots(signal, &transformed, member);
+
+        let publisher = self.publisher.clone();
+        let broadcast_tx = self.broadcast_tx.clone();
+        let master = signal.source_account.clone();
+        let owned_member = member.clone();
+        self.copy_delays.schedule(
+            &signal.source_account,
+            &member.slave_account,
+            delay,
+            async move {
+                let member = owned_member;
+                if let Err(e) = publisher
+                    .send_trade_signal(&master, &member.slave_account, &transformed)
+                    .await
+                {
+                    tracing::error!("Failed to send delayed signal to trade group: {}", e);
+                } else {
+                    let _ = broadcast_tx.send(trade_copied_event(&member, &transformed));
+                }
+            },
+        );
+    }
+}
+
+/// WebSocket notification for a signal published to a Slave
+fn trade_copied_event(member: &TradeGroupMember, transformed: &TradeSignal) -> ServerEvent {
+    ServerEvent::TradeCopied {
+        master_account: member.trade_group_id.clone(),
+        slave_account: member.slave_account.clone(),
+        symbol: transformed.symbol.clone(),
+        lots: transformed.lots,
+        member_id: member.id,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     // use super::*;
     use crate::adapters::inbound::zmq::test_helpers::{
-        create_test_context, create_test_trade_signal,
+        build_heartbeat, create_test_context, create_test_context_with_config,
+        create_test_trade_signal,
     };
-    use crate::domain::models::{LotCalculationMode, SlaveSettings};
+    use crate::domain::models::{
+        GlobalSettings, LotCalculationMode, MasterSettings, MergeStrategy, ServerEvent,
+        SlaveSettings, TradeAction, TradeHistoryQuery,
+    };
+    use crate::domain::services::symbol_specs::SymbolSpec;
 
     #[tokio::test]
     async fn test_handle_trade_signal_with_matching_setting() {
@@ -233,4 +713,599 @@ mod tests {
         // Process trade signal (should be filtered out, no panic)
         ctx.handle_trade_signal(signal).await;
     }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_applies_heartbeat_symbol_suffix() {
+        let mut ctx = create_test_context().await;
+        let signal = create_test_trade_signal(); // EURUSD
+
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        // Slave settings leave prefix/suffix unset
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        // Slave heartbeat reports its broker suffix
+        let mut hb = build_heartbeat("SLAVE_001", "Slave", true);
+        hb.symbol_suffix = Some(".m".to_string());
+        ctx.connection_manager.update_heartbeat(hb).await;
+
+        ctx.handle_trade_signal(signal).await;
+
+        let mut copied = None;
+        while let Ok(event) = ctx._broadcast_rx.try_recv() {
+            if let ServerEvent::TradeCopied { symbol, .. } = event {
+                copied = Some(symbol);
+            }
+        }
+        let symbol = copied.expect("trade should be copied");
+        assert_eq!(symbol.as_deref(), Some("EURUSD.m"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_copy_delay_defers_publish() {
+        let mut ctx = create_test_context().await;
+        let signal = create_test_trade_signal();
+
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        let slave_settings = SlaveSettings {
+            copy_delay_ms: Some((100, 150)),
+            ..SlaveSettings::default()
+        };
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", slave_settings, 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        let copied = |ctx: &mut crate::adapters::inbound::zmq::test_helpers::TestContext| {
+            let mut copied = false;
+            while let Ok(event) = ctx._broadcast_rx.try_recv() {
+                copied |= matches!(event, ServerEvent::TradeCopied { .. });
+            }
+            copied
+        };
+
+        ctx.handle_trade_signal(signal).await;
+        assert!(!copied(&mut ctx), "publish must wait for copy_delay_ms");
+
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        assert!(copied(&mut ctx));
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_records_trade_history() {
+        let ctx = create_test_context().await;
+        let signal = create_test_trade_signal(); // EURUSD
+
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_OK", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        let mut blocked = SlaveSettings::default();
+        blocked.filters.blocked_symbols = Some(vec!["EURUSD".to_string()]);
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_BLOCKED", blocked, 0)
+            .await
+            .unwrap();
+        for slave in ["SLAVE_OK", "SLAVE_BLOCKED"] {
+            ctx.db
+                .update_member_runtime_status("MASTER_001", slave, 2)
+                .await
+                .unwrap(); // STATUS_CONNECTED = 2
+        }
+
+        ctx.handle_trade_signal(signal.clone()).await;
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+
+        let ok = page
+            .items
+            .iter()
+            .find(|e| e.slave_account == "SLAVE_OK")
+            .unwrap();
+        assert!(ok.copied);
+        assert_eq!(ok.filter_reason, None);
+        assert_eq!(ok.ticket, signal.ticket);
+        assert_eq!(ok.symbol.as_deref(), Some("EURUSD"));
+
+        let skipped = page
+            .items
+            .iter()
+            .find(|e| e.slave_account == "SLAVE_BLOCKED")
+            .unwrap();
+        assert!(!skipped.copied);
+        assert_eq!(skipped.filter_reason.as_deref(), Some("symbol_blocked"));
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_counts_received_and_copied_per_group() {
+        let ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        let mut settings = SlaveSettings::default();
+        settings.filters.blocked_symbols = Some(vec!["EURUSD".to_string()]);
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", settings, 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        let mut blocked = create_test_trade_signal(); // EURUSD
+        blocked.seq = 1;
+        ctx.handle_trade_signal(blocked.clone()).await;
+        let mut allowed = blocked;
+        allowed.ticket += 1;
+        allowed.symbol = Some("GBPUSD".to_string());
+        allowed.seq = 2;
+        ctx.handle_trade_signal(allowed).await;
+
+        let stats = ctx.runtime_status_metrics.trade_group_stats("MASTER_001");
+        assert_eq!(stats.signals_received, 2);
+        assert_eq!(stats.signals_copied, 1);
+
+        ctx.runtime_status_metrics.reset_trade_group_stats();
+        assert_eq!(
+            ctx.runtime_status_metrics.trade_group_stats("MASTER_001"),
+            Default::default()
+        );
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_splits_lots_by_portfolio_weight() {
+        let ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        for (slave, weight) in [("SLAVE_001", 1.0), ("SLAVE_002", 3.0)] {
+            let settings = SlaveSettings {
+                portfolio_weight: Some(weight),
+                ..SlaveSettings::default()
+            };
+            ctx.db
+                .add_member("MASTER_001", slave, settings, 0)
+                .await
+                .unwrap();
+            ctx.db
+                .update_member_runtime_status("MASTER_001", slave, 2)
+                .await
+                .unwrap(); // STATUS_CONNECTED = 2
+        }
+
+        let mut signal = create_test_trade_signal();
+        signal.source_account = "MASTER_001".to_string();
+        signal.lots = Some(0.4);
+        ctx.handle_trade_signal(signal.clone()).await;
+
+        let copied = |slave: &str| ctx.position_lots.get("MASTER_001", slave, signal.ticket);
+        assert_eq!(copied("SLAVE_001"), Some(0.1));
+        assert_eq!(copied("SLAVE_002"), Some(0.3));
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_copy_context_pip_value_comes_from_symbol_specs() {
+        let mut config = crate::config::Config::default();
+        config
+            .symbol_specs
+            .insert("XAUUSD".to_string(), SymbolSpec::new(100.0, 2.5, 2));
+        let ctx = create_test_context_with_config(config).await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        let members = ctx.db.get_members("MASTER_001").await.unwrap();
+
+        // Broker suffixes resolve to the configured or built-in base symbol
+        for (symbol, expected) in [("XAUUSD.pro", 2.5), ("USDJPYm", 6.7), ("US30", 10.0)] {
+            let mut signal = create_test_trade_signal();
+            signal.symbol = Some(symbol.to_string());
+            let context = ctx
+                .build_copy_context(
+                    &signal,
+                    &members[0],
+                    &MasterSettings::default(),
+                    &members,
+                    &GlobalSettings::default(),
+                )
+                .await;
+            assert_eq!(context.pip_value, Some(expected), "{}", symbol);
+        }
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_drops_duplicate_seq() {
+        let ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_OK", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_OK", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        // seq 2 is redelivered after a reconnect
+        for (seq, ticket) in [(1, 101), (2, 102), (2, 102), (3, 103)] {
+            let mut signal = create_test_trade_signal();
+            signal.source_account = "MASTER_001".to_string();
+            signal.seq = seq;
+            signal.ticket = ticket;
+            ctx.handle_trade_signal(signal).await;
+        }
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        let mut tickets: Vec<i64> = page.items.iter().map(|e| e.ticket).collect();
+        tickets.sort();
+        assert_eq!(tickets, vec![101, 102, 103]);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_closes_only_skips_opens() {
+        let mut ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .update_master_settings(
+                "MASTER_001",
+                MasterSettings {
+                    enabled: true,
+                    closes_only: true,
+                    ..MasterSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        let mut open = create_test_trade_signal();
+        open.seq = 1;
+        ctx.handle_trade_signal(open.clone()).await;
+
+        let mut close = open.clone();
+        close.action = TradeAction::Close;
+        close.seq = 2;
+        ctx.handle_trade_signal(close).await;
+
+        let mut copied = Vec::new();
+        while let Ok(event) = ctx._broadcast_rx.try_recv() {
+            if matches!(event, ServerEvent::TradeCopied { .. }) {
+                copied.push(event);
+            }
+        }
+        assert_eq!(copied.len(), 1, "only the Close should be copied");
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].action, TradeAction::Close);
+        assert_eq!(page.items[0].ticket, open.ticket);
+        assert!(page.items[0].copied);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_daily_loss_limit_blocks_opens() {
+        let ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        let settings = SlaveSettings {
+            max_daily_loss: Some(500.0),
+            ..SlaveSettings::default()
+        };
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", settings, 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        // Day starts at 10,000; equity then drops 800 below it
+        ctx.connection_manager
+            .update_heartbeat(build_heartbeat("SLAVE_001", "Slave", true))
+            .await;
+        let mut hb = build_heartbeat("SLAVE_001", "Slave", true);
+        hb.equity = 9_200.0;
+        ctx.connection_manager.update_heartbeat(hb).await;
+
+        let mut open = create_test_trade_signal();
+        open.seq = 1;
+        ctx.handle_trade_signal(open.clone()).await;
+
+        let mut close = open;
+        close.action = TradeAction::Close;
+        close.seq = 2;
+        ctx.handle_trade_signal(close).await;
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        let open_entry = page
+            .items
+            .iter()
+            .find(|e| e.action == TradeAction::Open)
+            .unwrap();
+        assert!(!open_entry.copied);
+        assert_eq!(
+            open_entry.filter_reason.as_deref(),
+            Some("daily_loss_limit_hit")
+        );
+        let close_entry = page
+            .items
+            .iter()
+            .find(|e| e.action == TradeAction::Close)
+            .unwrap();
+        assert!(close_entry.copied);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_drops_stale_open_but_forwards_close() {
+        let mut ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .update_master_settings(
+                "MASTER_001",
+                MasterSettings {
+                    enabled: true,
+                    max_signal_age_ms: Some(5_000),
+                    webhook_url: None,
+                    ..MasterSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        let ten_seconds_ago = chrono::Utc::now() - chrono::Duration::seconds(10);
+        let mut open = create_test_trade_signal();
+        open.timestamp = ten_seconds_ago;
+        open.seq = 1;
+        ctx.handle_trade_signal(open.clone()).await;
+
+        let mut close = open.clone();
+        close.action = TradeAction::Close;
+        close.seq = 2;
+        ctx.handle_trade_signal(close).await;
+
+        let mut copied = Vec::new();
+        while let Ok(event) = ctx._broadcast_rx.try_recv() {
+            if matches!(event, ServerEvent::TradeCopied { .. }) {
+                copied.push(event);
+            }
+        }
+        assert_eq!(copied.len(), 1, "only the Close should be copied");
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_001".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        let open_entry = page
+            .items
+            .iter()
+            .find(|e| e.action == TradeAction::Open)
+            .unwrap();
+        assert!(!open_entry.copied);
+        let reason = open_entry.filter_reason.as_deref().unwrap();
+        let age_ms: i64 = reason
+            .strip_prefix("stale_signal:")
+            .and_then(|r| r.strip_suffix("ms"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(age_ms >= 10_000, "unexpected reason: {}", reason);
+        let close_entry = page
+            .items
+            .iter()
+            .find(|e| e.action == TradeAction::Close)
+            .unwrap();
+        assert!(close_entry.copied);
+
+        ctx.cleanup().await;
+    }
+
+    #[tokio::test]
+    async fn test_handle_trade_signal_throttles_master_burst() {
+        let mut ctx = create_test_context().await;
+        ctx.db.create_trade_group("MASTER_001").await.unwrap();
+        ctx.db
+            .update_master_settings(
+                "MASTER_001",
+                MasterSettings {
+                    enabled: true,
+                    max_signals_per_sec: Some(10),
+                    ..MasterSettings::default()
+                },
+            )
+            .await
+            .unwrap();
+        ctx.db
+            .add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        ctx.db
+            .update_member_runtime_status("MASTER_001", "SLAVE_001", 2)
+            .await
+            .unwrap(); // STATUS_CONNECTED = 2
+
+        let mut signal = create_test_trade_signal();
+        for seq in 1..=100 {
+            signal.ticket = seq as i64;
+            signal.seq = seq;
+            ctx.handle_trade_signal(signal.clone()).await;
+        }
+        let mut copied_opens = 0;
+        let mut rate_warnings = 0;
+        while let Ok(event) = ctx._broadcast_rx.try_recv() {
+            match event {
+                ServerEvent::TradeCopied { .. } => copied_opens += 1,
+                ServerEvent::TradeSkipped { reason, .. } => {
+                    assert_eq!(reason, "master_signal_rate_exceeded");
+                    rate_warnings += 1;
+                }
+                _ => {}
+            }
+        }
+        // The bucket holds one second of signals; a slow run may refill a token or two
+        assert!(
+            (10..=12).contains(&copied_opens),
+            "copied {} of 100 Opens",
+            copied_opens
+        );
+        assert_eq!(rate_warnings, 1, "one warning per throttled burst");
+        assert_eq!(
+            ctx.runtime_status_metrics.snapshot().throttled_signals,
+            100 - copied_opens as u64
+        );
+
+        // Closes are never throttled
+        signal.action = TradeAction::Close;
+        signal.seq = 101;
+        ctx.handle_trade_signal(signal).await;
+        let close_copied = std::iter::from_fn(|| ctx._broadcast_rx.try_recv().ok())
+            .any(|event| matches!(event, ServerEvent::TradeCopied { .. }));
+        assert!(close_copied);
+
+        ctx.cleanup().await;
+    }
+
+    /// Two masters open EURUSD on the same slave; returns the number of copied Opens
+    async fn copied_opens_from_two_masters(strategy: MergeStrategy) -> usize {
+        let mut ctx = create_test_context().await;
+        for master in ["MASTER_001", "MASTER_002"] {
+            ctx.db.create_trade_group(master).await.unwrap();
+            let settings = SlaveSettings {
+                merge_strategy: strategy,
+                ..SlaveSettings::default()
+            };
+            ctx.db
+                .add_member(master, "SLAVE_001", settings, 0)
+                .await
+                .unwrap();
+            ctx.db
+                .update_member_runtime_status(master, "SLAVE_001", 2)
+                .await
+                .unwrap(); // STATUS_CONNECTED = 2
+        }
+
+        // The slave appears under both trade groups
+        let configs = ctx.db.get_settings_for_slave("SLAVE_001").await.unwrap();
+        assert_eq!(configs.len(), 2);
+
+        let first = create_test_trade_signal();
+        ctx.handle_trade_signal(first).await;
+
+        let mut second = create_test_trade_signal();
+        second.source_account = "MASTER_002".to_string();
+        second.ticket = 67890;
+        ctx.handle_trade_signal(second).await;
+
+        let mut copied = 0;
+        while let Ok(event) = ctx._broadcast_rx.try_recv() {
+            if matches!(
+                &event,
+                ServerEvent::TradeCopied { slave_account, symbol, .. }
+                    if slave_account == "SLAVE_001" && symbol.as_deref() == Some("EURUSD")
+            ) {
+                copied += 1;
+            }
+        }
+
+        let page = ctx
+            .db
+            .query_trade_history(&TradeHistoryQuery {
+                master: Some("MASTER_002".to_string()),
+                ..TradeHistoryQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page.total, 1);
+        if strategy == MergeStrategy::PriorityFirst {
+            assert_eq!(
+                page.items[0].filter_reason.as_deref(),
+                Some("merge_conflict")
+            );
+        }
+
+        ctx.cleanup().await;
+        copied
+    }
+
+    #[tokio::test]
+    async fn test_two_masters_same_symbol_copy_all_vs_priority_first() {
+        assert_eq!(
+            copied_opens_from_two_masters(MergeStrategy::CopyAll).await,
+            2
+        );
+        assert_eq!(
+            copied_opens_from_two_masters(MergeStrategy::PriorityFirst).await,
+            1
+        );
+    }
 }
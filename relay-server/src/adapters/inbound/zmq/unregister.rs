@@ -4,14 +4,22 @@
 //! When a Master EA disconnects, notifies all Slaves so they can update their status.
 //! When a Slave EA disconnects, updates runtime status and notifies WebSocket clients.
 
-use super::MessageHandler;
-use crate::domain::models::{EaType, UnregisterMessage};
+use tracing::instrument;
+
+use super::{record_master_trade_group, MessageHandler};
+use crate::domain::models::{EaType, ServerEvent, UnregisterMessage};
 
 impl MessageHandler {
     /// Handle EA unregistration
     /// When a Master disconnects, notify all Slaves to update their status from CONNECTED to ENABLED
     /// When a Slave disconnects, update runtime status and notify WebSocket clients
+    #[instrument(skip_all, fields(
+        account_id = %msg.account_id,
+        ea_type = msg.ea_type.as_deref().unwrap_or_default(),
+        trade_group = tracing::field::Empty,
+    ))]
     pub(super) async fn handle_unregister(&self, msg: UnregisterMessage) {
+        record_master_trade_group(&msg.account_id, msg.ea_type.as_deref().unwrap_or_default());
         let account_id = &msg.account_id;
 
         // Get EA type before unregistering
@@ -40,9 +48,9 @@ impl MessageHandler {
         // Let's keep the generic broadcast here using the broadcaster injected into MessageHandler?
         // MessageHandler has broadcast_tx.
 
-        let _ = self
-            .broadcast_tx
-            .send(format!("ea_disconnected:{}", account_id));
+        let _ = self.broadcast_tx.send(ServerEvent::EaDisconnected {
+            account_id: account_id.to_string(),
+        });
 
         match ea_type {
             Some(EaType::Master) => {
@@ -86,6 +94,9 @@ mod tests {
             account_id: account_id.clone(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: "test".to_string(),
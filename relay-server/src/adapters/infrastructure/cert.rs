@@ -2,13 +2,19 @@
 //!
 //! Provides functionality to generate self-signed certificates and
 //! register them in the Windows certificate store for PNA compliance.
+//! The validity period of the certificate in use is read back from the PEM
+//! file so the server can warn before it expires (also exposed through
+//! `GET /api/tls-status`).
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use rcgen::{CertificateParams, DnType, Ia5String, KeyPair, SanType};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
-use std::time::SystemTime;
+use yasna::tags::TAG_UTCTIME;
+use yasna::{BERReader, Tag};
 
 use crate::config::TlsConfig;
 
@@ -19,14 +25,13 @@ const CERT_COMMON_NAME: &str = "SANKEY Copier Local Server";
 /// Certificate Organization Name
 const CERT_ORG_NAME: &str = "SANKEY Copier";
 
-/// Number of days before expiry to warn user
-const EXPIRY_WARNING_DAYS: u64 = 30;
-
 /// Ensure certificate exists, generating and registering if necessary
 ///
 /// This function checks if the certificate files exist. If not, it generates
 /// a new self-signed certificate and registers it in the Windows trusted
-/// root certificate store.
+/// root certificate store. An existing certificate is checked for expiry;
+/// an expired self-signed one is regenerated when `auto_regenerate_expired`
+/// is set.
 ///
 /// # Arguments
 /// * `config` - TLS configuration containing paths and validity settings
@@ -43,14 +48,14 @@ pub fn ensure_certificate(config: &TlsConfig, base_path: &Path) -> Result<()> {
     if cert_path.exists() && key_path.exists() {
         tracing::info!("Certificate files found at {:?}", cert_path);
 
-        // Check certificate expiry based on file modification time
-        check_certificate_expiry(&cert_path, config.validity_days);
-
-        return Ok(());
+        if !should_regenerate(config, &cert_path) {
+            return Ok(());
+        }
+        tracing::warn!("Regenerating expired self-signed TLS certificate");
+    } else {
+        tracing::info!("Certificate not found, generating new self-signed certificate");
     }
 
-    tracing::info!("Certificate not found, generating new self-signed certificate");
-
     // Create parent directories if they don't exist
     if let Some(parent) = cert_path.parent() {
         fs::create_dir_all(parent)
@@ -85,6 +90,23 @@ pub fn ensure_certificate(config: &TlsConfig, base_path: &Path) -> Result<()> {
 /// # Returns
 /// * Tuple of (certificate PEM, private key PEM)
 fn generate_self_signed_cert(validity_days: u32) -> Result<(String, String)> {
+    let not_before = time::OffsetDateTime::now_utc();
+    let not_after = not_before + time::Duration::days(validity_days as i64);
+    let pems = build_self_signed_cert(not_before, not_after)?;
+
+    tracing::info!(
+        "Generated self-signed certificate valid for {} days",
+        validity_days
+    );
+
+    Ok(pems)
+}
+
+/// Self-signed localhost certificate valid from `not_before` to `not_after`
+fn build_self_signed_cert(
+    not_before: time::OffsetDateTime,
+    not_after: time::OffsetDateTime,
+) -> Result<(String, String)> {
     // Generate a new key pair
     let key_pair = KeyPair::generate().context("Failed to generate key pair")?;
 
@@ -109,9 +131,6 @@ fn generate_self_signed_cert(validity_days: u32) -> Result<(String, String)> {
     ];
 
     // Set validity period
-    // rcgen uses time::Duration, convert days to duration
-    let not_before = time::OffsetDateTime::now_utc();
-    let not_after = not_before + time::Duration::days(validity_days as i64);
     params.not_before = not_before;
     params.not_after = not_after;
 
@@ -123,64 +142,156 @@ fn generate_self_signed_cert(validity_days: u32) -> Result<(String, String)> {
     let cert_pem = cert.pem();
     let key_pem = key_pair.serialize_pem();
 
-    tracing::info!(
-        "Generated self-signed certificate valid for {} days",
-        validity_days
-    );
-
     Ok((cert_pem, key_pem))
 }
 
-/// Check certificate expiry and warn if expiring soon
-///
-/// Estimates expiry date based on file modification time and validity_days.
-/// Logs a warning if the certificate will expire within EXPIRY_WARNING_DAYS.
-fn check_certificate_expiry(cert_path: &Path, validity_days: u32) {
-    let metadata = match fs::metadata(cert_path) {
-        Ok(m) => m,
-        Err(e) => {
-            tracing::debug!("Could not read certificate metadata: {}", e);
-            return;
-        }
-    };
+/// Validity of the certificate the HTTPS server uses
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CertificateStatus {
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// Whole days until `not_after` (negative once expired)
+    pub days_remaining: i64,
+    /// Expires within `expiry_warning_days`
+    pub expiring_soon: bool,
+    pub expired: bool,
+    /// Issuer equals subject (the auto-generated certificate)
+    pub self_signed: bool,
+    pub expiry_warning_days: u32,
+}
+
+/// Outcome of the startup expiry check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExpiryCheck {
+    Valid,
+    ExpiringSoon { days_remaining: i64 },
+    Expired,
+}
+
+/// Read the validity period of the PEM certificate at `cert_path`
+pub fn certificate_status(
+    cert_path: &Path,
+    expiry_warning_days: u32,
+    now: DateTime<Utc>,
+) -> Result<CertificateStatus> {
+    let pem = fs::read(cert_path)
+        .with_context(|| format!("Failed to read certificate {:?}", cert_path))?;
+    let parsed = parse_certificate(&pem)?;
+
+    let days_remaining = (parsed.not_after - now).num_days();
+    let expired = parsed.not_after <= now;
+    Ok(CertificateStatus {
+        not_before: parsed.not_before,
+        not_after: parsed.not_after,
+        days_remaining,
+        expiring_soon: !expired && days_remaining < i64::from(expiry_warning_days),
+        expired,
+        self_signed: parsed.self_signed,
+        expiry_warning_days,
+    })
+}
 
-    let modified = match metadata.modified() {
-        Ok(t) => t,
+/// Check the existing certificate at startup and decide whether to replace it
+fn should_regenerate(config: &TlsConfig, cert_path: &Path) -> bool {
+    let status = match certificate_status(cert_path, config.expiry_warning_days, Utc::now()) {
+        Ok(status) => status,
         Err(e) => {
-            tracing::debug!("Could not get certificate modification time: {}", e);
-            return;
+            tracing::warn!("Could not read TLS certificate validity: {:#}", e);
+            return false;
         }
     };
 
-    // Calculate estimated expiry time
-    let validity_secs = (validity_days as u64) * 24 * 60 * 60;
-    let expiry_time = modified + std::time::Duration::from_secs(validity_secs);
-
-    let now = SystemTime::now();
-    if let Ok(time_until_expiry) = expiry_time.duration_since(now) {
-        let days_until_expiry = time_until_expiry.as_secs() / (24 * 60 * 60);
+    check_certificate_expiry(&status, cert_path) == ExpiryCheck::Expired
+        && status.self_signed
+        && config.auto_regenerate_expired
+}
 
-        if days_until_expiry <= EXPIRY_WARNING_DAYS {
-            tracing::warn!(
-                "TLS certificate will expire in {} days. Consider regenerating by deleting {:?}",
-                days_until_expiry,
-                cert_path
-            );
-        } else {
-            tracing::debug!(
-                "Certificate valid for approximately {} more days",
-                days_until_expiry
-            );
-        }
-    } else {
-        // Expiry time is in the past
+/// Log the certificate validity, warning if it expires within `expiry_warning_days`
+fn check_certificate_expiry(status: &CertificateStatus, cert_path: &Path) -> ExpiryCheck {
+    if status.expired {
         tracing::error!(
+            not_after = %status.not_after,
             "TLS certificate has expired! Delete {:?} to regenerate",
             cert_path
         );
+        ExpiryCheck::Expired
+    } else if status.expiring_soon {
+        tracing::warn!(
+            not_after = %status.not_after,
+            "TLS certificate will expire in {} days. Consider regenerating by deleting {:?}",
+            status.days_remaining,
+            cert_path
+        );
+        ExpiryCheck::ExpiringSoon {
+            days_remaining: status.days_remaining,
+        }
+    } else {
+        tracing::debug!(
+            not_after = %status.not_after,
+            "Certificate valid for {} more days",
+            status.days_remaining
+        );
+        ExpiryCheck::Valid
     }
 }
 
+struct ParsedCertificate {
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    self_signed: bool,
+}
+
+/// Extract the validity period from the first certificate in `pem`
+///
+/// Only the TBSCertificate fields up to the subject are decoded; the rest of
+/// the structure is skipped as raw DER.
+fn parse_certificate(pem: &[u8]) -> Result<ParsedCertificate> {
+    let der = rustls_pemfile::certs(&mut &pem[..])
+        .next()
+        .context("No certificate found in PEM data")?
+        .context("Invalid PEM certificate")?;
+
+    let parsed = yasna::parse_der(&der, |r| {
+        r.read_sequence(|r| {
+            let parsed = r.next().read_sequence(|r| {
+                // version [0] EXPLICIT is absent in v1 certificates
+                r.read_optional(|r| r.read_tagged(Tag::context(0), |r| r.read_der()))?;
+                r.next().read_der()?; // serialNumber
+                r.next().read_der()?; // signature
+                let issuer = r.next().read_der()?;
+                let (not_before, not_after) = r
+                    .next()
+                    .read_sequence(|r| Ok((read_time(r.next())?, read_time(r.next())?)))?;
+                let subject = r.next().read_der()?;
+                // subjectPublicKeyInfo, unique identifiers and extensions
+                while r.read_optional(|r| r.read_der())?.is_some() {}
+                Ok(ParsedCertificate {
+                    not_before,
+                    not_after,
+                    self_signed: issuer == subject,
+                })
+            })?;
+            r.next().read_der()?; // signatureAlgorithm
+            r.next().read_der()?; // signatureValue
+            Ok(parsed)
+        })
+    })
+    .context("Invalid X.509 certificate")?;
+
+    Ok(parsed)
+}
+
+/// X.509 `Time`: UTCTime or GeneralizedTime
+fn read_time(r: BERReader) -> yasna::ASN1Result<DateTime<Utc>> {
+    let datetime = if r.lookahead_tag()? == TAG_UTCTIME {
+        *r.read_utctime()?.datetime()
+    } else {
+        *r.read_generalized_time()?.datetime()
+    };
+    DateTime::from_timestamp(datetime.unix_timestamp(), datetime.nanosecond())
+        .ok_or_else(|| yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid))
+}
+
 /// Register certificate in Windows trusted root certificate store
 ///
 /// Uses certutil.exe to add the certificate to the machine's trusted
@@ -287,6 +398,7 @@ mod tests {
             cert_path: "certs/test.pem".to_string(),
             key_path: "certs/test-key.pem".to_string(),
             validity_days: 30,
+            ..TlsConfig::default()
         };
 
         // Note: This test will fail on registration step without admin privileges
@@ -302,4 +414,79 @@ mod tests {
             assert!(key_path.exists());
         }
     }
+
+    #[test]
+    fn test_certificate_status_reads_validity() {
+        let temp_dir = TempDir::new().unwrap();
+        let cert_path = temp_dir.path().join("server.pem");
+        let (cert_pem, _) = generate_self_signed_cert(365).unwrap();
+        fs::write(&cert_path, cert_pem).unwrap();
+
+        let now = Utc::now();
+        let status = certificate_status(&cert_path, 30, now).unwrap();
+
+        assert!(status.self_signed);
+        assert!(!status.expired);
+        assert!(!status.expiring_soon);
+        assert_eq!(status.days_remaining, 364);
+        assert!((status.not_before - now).num_seconds().abs() < 60);
+        assert_eq!((status.not_after - status.not_before).num_days(), 365);
+    }
+
+    #[test]
+    fn test_short_lived_certificate_triggers_expiry_warning() {
+        let temp_dir = TempDir::new().unwrap();
+        let cert_path = temp_dir.path().join("server.pem");
+        let (cert_pem, _) = generate_self_signed_cert(1).unwrap();
+        fs::write(&cert_path, cert_pem).unwrap();
+
+        let status = certificate_status(&cert_path, 30, Utc::now()).unwrap();
+        assert!(status.expiring_soon);
+        assert_eq!(
+            check_certificate_expiry(&status, &cert_path),
+            ExpiryCheck::ExpiringSoon { days_remaining: 0 }
+        );
+
+        // Outside the warning window the same certificate is fine
+        let status = certificate_status(&cert_path, 0, Utc::now()).unwrap();
+        assert_eq!(
+            check_certificate_expiry(&status, &cert_path),
+            ExpiryCheck::Valid
+        );
+    }
+
+    #[test]
+    fn test_expired_self_signed_certificate_is_regenerated() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = TlsConfig {
+            cert_path: "certs/test.pem".to_string(),
+            key_path: "certs/test-key.pem".to_string(),
+            validity_days: 30,
+            ..TlsConfig::default()
+        };
+        let cert_path = temp_dir.path().join(&config.cert_path);
+        let key_path = temp_dir.path().join(&config.key_path);
+
+        let now = time::OffsetDateTime::now_utc();
+        let (cert_pem, key_pem) = build_self_signed_cert(
+            now - time::Duration::days(10),
+            now - time::Duration::days(1),
+        )
+        .unwrap();
+        fs::create_dir_all(cert_path.parent().unwrap()).unwrap();
+        fs::write(&cert_path, cert_pem).unwrap();
+        fs::write(&key_path, key_pem).unwrap();
+        assert!(
+            certificate_status(&cert_path, 30, Utc::now())
+                .unwrap()
+                .expired
+        );
+
+        // Registration may fail without certutil, but the files are replaced first
+        let _ = ensure_certificate(&config, temp_dir.path());
+
+        let status = certificate_status(&cert_path, 30, Utc::now()).unwrap();
+        assert!(!status.expired);
+        assert_eq!(status.days_remaining, 29);
+    }
 }
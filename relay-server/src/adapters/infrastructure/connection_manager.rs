@@ -1,29 +1,160 @@
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
-use std::collections::HashMap;
-use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::Bound;
+use std::sync::{Arc, Mutex, PoisonError};
 use tokio::sync::RwLock;
 
-use crate::domain::models::{ConnectionStatus, EaConnection, EaType, HeartbeatMessage, Platform};
+use crate::domain::models::{
+    ConnectionEvent, ConnectionEventKind, ConnectionStatus, EaConnection, EaType, HeartbeatMessage,
+    HeartbeatOutcome, Platform, PositionInfo, PositionSnapshotCache,
+};
+
+/// Connection events kept per EA (oldest dropped first)
+pub const CONNECTION_HISTORY_CAPACITY: usize = 100;
 
 /// EA connection key: (account_id, ea_type)
-/// Allows same account to have both Master and Slave EAs running simultaneously
+/// Allows same account to have both Master and Slave EAs running simultaneously.
+/// Keys are ordered (account_id, then Master before Slave) for stable pagination.
 type ConnectionKey = (String, EaType);
 
 /// EA接続を管理するマネージャー
 #[derive(Clone)]
 pub struct ConnectionManager {
-    connections: Arc<RwLock<HashMap<ConnectionKey, EaConnection>>>,
+    connections: Arc<RwLock<BTreeMap<ConnectionKey, EaConnection>>>,
+    /// Per-EA ring buffer of connection events (kept after the EA goes offline)
+    history: Arc<Mutex<HashMap<ConnectionKey, VecDeque<ConnectionEvent>>>>,
     timeout_seconds: i64,
+    missed_heartbeat_threshold: u32,
+    low_margin_level_threshold: f64,
+    position_snapshot_stale_secs: i64,
+    max_heartbeat_skew_secs: i64,
+    reconnect_grace_secs: i64,
 }
 
 impl ConnectionManager {
     /// 新しいConnectionManagerを作成
     pub fn new(timeout_seconds: i64) -> Self {
         Self {
-            connections: Arc::new(RwLock::new(HashMap::new())),
+            connections: Arc::new(RwLock::new(BTreeMap::new())),
+            history: Arc::new(Mutex::new(HashMap::new())),
             timeout_seconds,
+            missed_heartbeat_threshold: 1,
+            low_margin_level_threshold: 0.0,
+            position_snapshot_stale_secs: 0,
+            max_heartbeat_skew_secs: 0,
+            reconnect_grace_secs: 0,
+        }
+    }
+
+    /// タイムアウト判定までに許容するHeartbeat欠落回数を設定（最小1）
+    pub fn with_missed_heartbeat_threshold(mut self, threshold: u32) -> Self {
+        self.missed_heartbeat_threshold = threshold.max(1);
+        self
+    }
+
+    /// 証拠金維持率の警告閾値（%）を設定（0以下で無効）
+    pub fn with_low_margin_level_threshold(mut self, threshold: f64) -> Self {
+        self.low_margin_level_threshold = threshold;
+        self
+    }
+
+    /// ポジションスナップショットの有効期間（秒）を設定（0以下で期限なし）
+    pub fn with_position_snapshot_stale_secs(mut self, secs: i64) -> Self {
+        self.position_snapshot_stale_secs = secs;
+        self
+    }
+
+    /// Heartbeatのタイムスタンプがサーバー時刻より先行してよい上限（秒）を設定（0以下で無効）
+    pub fn with_max_heartbeat_skew_secs(mut self, secs: i64) -> Self {
+        self.max_heartbeat_skew_secs = secs;
+        self
+    }
+
+    /// Timeout/Offline からの復帰をフラップ（同一セッションの再接続）とみなす猶予（秒）を設定
+    ///
+    /// 最後のHeartbeatからこの時間を超えて復帰した場合は新しい接続として `connected_at` と
+    /// `flap_count` をリセットする（0以下では常に新しい接続）
+    pub fn with_reconnect_grace_secs(mut self, secs: i64) -> Self {
+        self.reconnect_grace_secs = secs;
+        self
+    }
+
+    /// EAが送ったHeartbeatタイムスタンプを検証する
+    ///
+    /// 前回受理したものより古い（重複・遅延）か、サーバー時刻より許容スキュー以上
+    /// 未来のものは理由を返す。解釈できないタイムスタンプ（旧EA）は検証しない。
+    fn check_heartbeat_timestamp(
+        &self,
+        sent_at: Option<DateTime<Utc>>,
+        last_accepted: Option<DateTime<Utc>>,
+    ) -> Result<(), String> {
+        let Some(sent_at) = sent_at else {
+            return Ok(());
+        };
+        if self.max_heartbeat_skew_secs > 0 {
+            let ahead = sent_at - Utc::now();
+            if ahead > Duration::seconds(self.max_heartbeat_skew_secs) {
+                return Err(format!(
+                    "timestamp {} is {}s ahead of server time (max {}s)",
+                    sent_at,
+                    ahead.num_seconds(),
+                    self.max_heartbeat_skew_secs
+                ));
+            }
+        }
+        if let Some(last) = last_accepted {
+            if sent_at < last {
+                return Err(format!(
+                    "timestamp {} is older than last accepted {}",
+                    sent_at, last
+                ));
+            }
         }
+        Ok(())
+    }
+
+    /// 接続イベントを履歴に追加（容量を超えたら古いものから破棄）
+    fn record_event(
+        &self,
+        account_id: &str,
+        ea_type: EaType,
+        event: ConnectionEventKind,
+        reason: String,
+    ) {
+        let mut history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+        let events = history
+            .entry((account_id.to_string(), ea_type))
+            .or_default();
+        if events.len() >= CONNECTION_HISTORY_CAPACITY {
+            events.pop_front();
+        }
+        events.push_back(ConnectionEvent {
+            at: Utc::now(),
+            ea_type,
+            event,
+            reason,
+        });
+    }
+
+    /// 接続イベントの履歴を古い順に取得
+    /// `ea_type` が None の場合は account_id に紐づく全EAの履歴をまとめて返す
+    pub fn get_history(&self, account_id: &str, ea_type: Option<EaType>) -> Vec<ConnectionEvent> {
+        let history = self.history.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut events: Vec<ConnectionEvent> = history
+            .iter()
+            .filter(|((acc_id, t), _)| acc_id == account_id && ea_type.is_none_or(|et| et == *t))
+            .flat_map(|(_, events)| events.iter().cloned())
+            .collect();
+        events.sort_by_key(|event| event.at);
+        events
+    }
+
+    /// 証拠金維持率が閾値を下回っているか（ポジション無し＝margin 0 の場合は対象外）
+    fn is_low_margin_level(&self, msg: &HeartbeatMessage) -> bool {
+        self.low_margin_level_threshold > 0.0
+            && msg.margin > 0.0
+            && msg.margin_level < self.low_margin_level_threshold
     }
 
     /// RegisterMessageからEAを登録（is_trade_allowed=false初期値）
@@ -54,6 +185,14 @@ impl ConnectionManager {
             conn.server = msg.server.clone();
             conn.broker = msg.broker.clone();
             conn.account_name = msg.account_name.clone();
+            // EA restarted: its clock may have been corrected, so restart replay tracking
+            conn.last_heartbeat_sent_at = None;
+            self.record_event(
+                &msg.account_id,
+                ea_type,
+                ConnectionEventKind::Registered,
+                format!("Register message while {:?} (EA restarted)", conn.status),
+            );
             return;
         }
 
@@ -77,6 +216,11 @@ impl ConnectionManager {
             server: msg.server.clone(),
             balance: 0.0, // 初期値、Heartbeatで更新
             equity: 0.0,  // 初期値、Heartbeatで更新
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            low_margin_level: false,
+            open_positions: 0,
             currency: msg.currency.clone(),
             leverage: msg.leverage,
             last_heartbeat: now,
@@ -84,9 +228,25 @@ impl ConnectionManager {
             connected_at: now,
             is_trade_allowed: msg.is_trade_allowed, // Updated checking
             symbol_context: msg.symbol_context.clone(),
+            symbol_prefix: None, // Reported by Heartbeat
+            symbol_suffix: None,
+            symbol_map: None,
+            timeout_override: None,
+            missed_heartbeats: 0,
+            last_latency_ms: None,
+            position_snapshot: None,
+            daily_loss: None,
+            last_heartbeat_sent_at: None,
+            flap_count: 0,
         };
 
         connections.insert(key, connection);
+        self.record_event(
+            &msg.account_id,
+            ea_type,
+            ConnectionEventKind::Registered,
+            "Register message".to_string(),
+        );
     }
 
     /// EAの登録を解除 (特定のEA種別)
@@ -97,28 +257,93 @@ impl ConnectionManager {
         let mut connections = self.connections.write().await;
         if let Some(conn) = connections.get_mut(&key) {
             conn.status = ConnectionStatus::Offline;
+            self.record_event(
+                account_id,
+                ea_type,
+                ConnectionEventKind::Unregistered,
+                "Unregister message".to_string(),
+            );
         }
         // Note: オフライン状態で保持（完全削除はしない）
     }
 
+    /// EAを即座にオフライン扱いにする（Unregisterを送らずに停止したEA向け）
+    /// 更新後の接続情報を返す。未登録のEAならNone
+    pub async fn force_disconnect(
+        &self,
+        account_id: &str,
+        ea_type: EaType,
+    ) -> Option<EaConnection> {
+        let key = (account_id.to_string(), ea_type);
+        let mut connections = self.connections.write().await;
+        let conn = connections.get_mut(&key)?;
+        conn.status = ConnectionStatus::Offline;
+        tracing::warn!("EA force-disconnected: {} ({})", account_id, ea_type);
+        self.record_event(
+            account_id,
+            ea_type,
+            ConnectionEventKind::Unregistered,
+            "Force-disconnected via API".to_string(),
+        );
+        Some(conn.clone())
+    }
+
     /// Heartbeatを更新（自動登録機能付き）
     /// Uses (account_id, ea_type) as composite key
-    /// Returns true if this was a new registration (auto-registered), false otherwise
-    pub async fn update_heartbeat(&self, msg: HeartbeatMessage) -> bool {
+    ///
+    /// Liveness always uses server time; the EA timestamp is only checked for
+    /// replays and clock skew, and rejected heartbeats leave the connection untouched.
+    pub async fn update_heartbeat(&self, msg: HeartbeatMessage) -> HeartbeatOutcome {
         let account_id = &msg.account_id;
         let ea_type: EaType = msg.ea_type.parse().unwrap_or(EaType::Master);
         let key = (account_id.clone(), ea_type);
+        let sent_at = DateTime::parse_from_rfc3339(&msg.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc));
 
         let mut connections = self.connections.write().await;
 
+        let last_accepted = connections
+            .get(&key)
+            .and_then(|conn| conn.last_heartbeat_sent_at);
+        if let Err(reason) = self.check_heartbeat_timestamp(sent_at, last_accepted) {
+            tracing::warn!(
+                "Rejected heartbeat from {} ({}): {}",
+                account_id,
+                ea_type,
+                reason
+            );
+            return HeartbeatOutcome::Rejected;
+        }
+
         if let Some(conn) = connections.get_mut(&key) {
             // 既存のEA: ハートビート情報を更新
-            conn.last_heartbeat = Utc::now();
+            let now = Utc::now();
+            if matches!(
+                conn.status,
+                ConnectionStatus::Timeout | ConnectionStatus::Offline
+            ) {
+                self.record_reconnect(conn, now);
+            }
+            conn.last_heartbeat = now;
+            if sent_at.is_some() {
+                conn.last_heartbeat_sent_at = sent_at;
+            }
+            conn.missed_heartbeats = 0;
             conn.balance = msg.balance;
             conn.equity = msg.equity;
+            conn.margin = msg.margin;
+            conn.free_margin = msg.free_margin;
+            conn.margin_level = msg.margin_level;
+            conn.low_margin_level = self.is_low_margin_level(&msg);
+            conn.open_positions = msg.open_positions;
+            conn.symbol_prefix = msg.symbol_prefix;
+            conn.symbol_suffix = msg.symbol_suffix;
+            conn.symbol_map = msg.symbol_map;
             conn.status = ConnectionStatus::Online;
             conn.is_trade_allowed = msg.is_trade_allowed;
             conn.platform = msg.platform.parse().unwrap_or(conn.platform);
+            conn.record_daily_loss(conn.last_heartbeat);
 
             tracing::debug!(
                 "Heartbeat received: {} ({}) (Balance: {:.2} {}, Equity: {:.2}, EA Version: {}, TradeAllowed: {})",
@@ -130,7 +355,7 @@ impl ConnectionManager {
                 msg.version,
                 msg.is_trade_allowed
             );
-            false
+            HeartbeatOutcome::Updated
         } else {
             // 未登録のEA: Heartbeatの情報から自動登録
             tracing::info!(
@@ -143,7 +368,8 @@ impl ConnectionManager {
             );
 
             let now = Utc::now();
-            let connection = EaConnection {
+            let low_margin_level = self.is_low_margin_level(&msg);
+            let mut connection = EaConnection {
                 account_id: msg.account_id.clone(),
                 ea_type,
                 platform: msg.platform.parse().unwrap_or(Platform::MT5),
@@ -153,6 +379,11 @@ impl ConnectionManager {
                 server: msg.server,
                 balance: msg.balance,
                 equity: msg.equity,
+                margin: msg.margin,
+                free_margin: msg.free_margin,
+                margin_level: msg.margin_level,
+                low_margin_level,
+                open_positions: msg.open_positions,
                 currency: msg.currency,
                 leverage: msg.leverage,
                 last_heartbeat: now,
@@ -160,19 +391,95 @@ impl ConnectionManager {
                 connected_at: now,
                 is_trade_allowed: msg.is_trade_allowed,
                 symbol_context: None, // Heartbeat does not carry detection info
+                symbol_prefix: msg.symbol_prefix,
+                symbol_suffix: msg.symbol_suffix,
+                symbol_map: msg.symbol_map,
+                timeout_override: None,
+                missed_heartbeats: 0,
+                last_latency_ms: None,
+                position_snapshot: None,
+                daily_loss: None,
+                last_heartbeat_sent_at: sent_at,
+                flap_count: 0,
             };
+            connection.record_daily_loss(now);
 
             connections.insert(key, connection);
-            true
+            self.record_event(
+                account_id,
+                ea_type,
+                ConnectionEventKind::Registered,
+                "Auto-registered from heartbeat".to_string(),
+            );
+            HeartbeatOutcome::Registered
         }
     }
 
+    /// Timeout/Offline からの復帰: 猶予内ならフラップとして数え、超えていれば新しい接続として扱う
+    fn record_reconnect(&self, conn: &mut EaConnection, now: DateTime<Utc>) {
+        let down_secs = now.signed_duration_since(conn.last_heartbeat).num_seconds();
+        let reason = if down_secs <= self.reconnect_grace_secs {
+            conn.flap_count += 1;
+            tracing::info!(
+                "EA reconnected: {} ({}) after {}s (flap_count: {})",
+                conn.account_id,
+                conn.ea_type,
+                down_secs,
+                conn.flap_count
+            );
+            format!(
+                "Heartbeat after {}s {:?} (flap_count: {})",
+                down_secs, conn.status, conn.flap_count
+            )
+        } else {
+            conn.connected_at = now;
+            conn.flap_count = 0;
+            tracing::info!(
+                "EA reconnected: {} ({}) after {}s, starting a new session",
+                conn.account_id,
+                conn.ea_type,
+                down_secs
+            );
+            format!(
+                "Heartbeat after {}s {:?}, new session",
+                down_secs, conn.status
+            )
+        };
+        self.record_event(
+            &conn.account_id,
+            conn.ea_type,
+            ConnectionEventKind::Reconnected,
+            reason,
+        );
+    }
+
     /// すべてのEA（オンライン・オフライン含む）を取得
     pub async fn get_all_eas(&self) -> Vec<EaConnection> {
         let connections = self.connections.read().await;
         connections.values().cloned().collect()
     }
 
+    /// account_id 順（同一口座は Master → Slave）で最大 limit 件のEAを取得
+    ///
+    /// `after` を指定した場合はそのキーより後のEAのみを返す。
+    /// 戻り値の bool は後続のEAが残っているかどうか。
+    pub async fn get_eas_paginated(
+        &self,
+        after: Option<(String, EaType)>,
+        limit: usize,
+    ) -> (Vec<EaConnection>, bool) {
+        let connections = self.connections.read().await;
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut page: Vec<EaConnection> = connections
+            .range((start, Bound::Unbounded))
+            .take(limit.saturating_add(1))
+            .map(|(_, conn)| conn.clone())
+            .collect();
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+        (page, has_more)
+    }
+
     /// Master EAを取得
     pub async fn get_master(&self, account_id: &str) -> Option<EaConnection> {
         let connections = self.connections.read().await;
@@ -208,11 +515,84 @@ impl ConnectionManager {
         self.get_slave(account_id).await
     }
 
+    /// EAごとのHeartbeatタイムアウトを設定（Noneでグローバル設定に戻す）
+    /// `ea_type` が None の場合は account_id に紐づく全EAに適用
+    /// Returns the updated connections (empty if no EA matched)
+    pub async fn set_timeout_override(
+        &self,
+        account_id: &str,
+        ea_type: Option<EaType>,
+        timeout_seconds: Option<i64>,
+    ) -> Vec<EaConnection> {
+        let mut connections = self.connections.write().await;
+        connections
+            .iter_mut()
+            .filter(|((acc_id, t), _)| acc_id == account_id && ea_type.is_none_or(|et| et == *t))
+            .map(|(_, conn)| {
+                conn.timeout_override = timeout_seconds;
+                conn.clone()
+            })
+            .collect()
+    }
+
+    /// Ping往復時間を記録
+    /// Returns false if the EA is not registered
+    pub async fn record_latency(&self, account_id: &str, ea_type: EaType, latency_ms: i64) -> bool {
+        let mut connections = self.connections.write().await;
+        match connections.get_mut(&(account_id.to_string(), ea_type)) {
+            Some(conn) => {
+                conn.last_latency_ms = Some(latency_ms);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// PositionSnapshotの内容をEA接続にキャッシュ
+    /// Returns false if the EA is not registered
+    pub async fn record_position_snapshot(
+        &self,
+        account_id: &str,
+        ea_type: EaType,
+        positions: Vec<PositionInfo>,
+    ) -> bool {
+        let mut connections = self.connections.write().await;
+        match connections.get_mut(&(account_id.to_string(), ea_type)) {
+            Some(conn) => {
+                conn.position_snapshot = Some(PositionSnapshotCache {
+                    positions,
+                    received_at: Utc::now(),
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// キャッシュ済みのポジション一覧を取得（有効期間を過ぎたものはNone）
+    pub async fn get_position_snapshot(
+        &self,
+        account_id: &str,
+        ea_type: EaType,
+    ) -> Option<PositionSnapshotCache> {
+        let connections = self.connections.read().await;
+        let snapshot = connections
+            .get(&(account_id.to_string(), ea_type))?
+            .position_snapshot
+            .clone()?;
+        if self.position_snapshot_stale_secs > 0
+            && Utc::now() - snapshot.received_at
+                > Duration::seconds(self.position_snapshot_stale_secs)
+        {
+            return None;
+        }
+        Some(snapshot)
+    }
+
     /// タイムアウトをチェックして、応答のないEAをタイムアウト状態にする
     /// Returns a list of (account_id, ea_type) for timed-out EAs
     pub async fn check_timeouts(&self) -> Vec<(String, EaType)> {
         let now = Utc::now();
-        let timeout_duration = Duration::seconds(self.timeout_seconds);
 
         let mut connections = self.connections.write().await;
         let mut timed_out_accounts = Vec::new();
@@ -222,15 +602,52 @@ impl ConnectionManager {
                 || conn.status == ConnectionStatus::Registered
             {
                 let elapsed = now.signed_duration_since(conn.last_heartbeat);
+                let timeout_duration =
+                    Duration::seconds(conn.timeout_override.unwrap_or(self.timeout_seconds));
 
                 if elapsed > timeout_duration {
+                    conn.missed_heartbeats += 1;
+                    if conn.missed_heartbeats < self.missed_heartbeat_threshold {
+                        tracing::debug!(
+                            "EA missed heartbeat: {} (ea_type: {}, missed: {}/{})",
+                            account_id,
+                            ea_type,
+                            conn.missed_heartbeats,
+                            self.missed_heartbeat_threshold
+                        );
+                        self.record_event(
+                            account_id,
+                            *ea_type,
+                            ConnectionEventKind::HeartbeatGap,
+                            format!(
+                                "No heartbeat for {}s (missed {}/{})",
+                                elapsed.num_seconds(),
+                                conn.missed_heartbeats,
+                                self.missed_heartbeat_threshold
+                            ),
+                        );
+                        continue;
+                    }
+
                     tracing::warn!(
-                        "EA timed out: {} (ea_type: {}, last heartbeat: {:?} ago)",
+                        "EA timed out: {} (ea_type: {}, last heartbeat: {:?} ago, missed: {})",
                         account_id,
                         ea_type,
-                        elapsed
+                        elapsed,
+                        conn.missed_heartbeats
                     );
                     conn.status = ConnectionStatus::Timeout;
+                    self.record_event(
+                        account_id,
+                        *ea_type,
+                        ConnectionEventKind::Timeout,
+                        format!(
+                            "No heartbeat for {}s (timeout {}s, missed {})",
+                            elapsed.num_seconds(),
+                            timeout_duration.num_seconds(),
+                            conn.missed_heartbeats
+                        ),
+                    );
                     timed_out_accounts.push((account_id.clone(), *ea_type));
                 }
             }
@@ -261,7 +678,7 @@ impl crate::ports::ConnectionManager for ConnectionManager {
         self.get_slave(account_id).await
     }
 
-    async fn update_heartbeat(&self, msg: HeartbeatMessage) -> bool {
+    async fn update_heartbeat(&self, msg: HeartbeatMessage) -> HeartbeatOutcome {
         self.update_heartbeat(msg).await
     }
 }
@@ -277,33 +694,222 @@ pub trait TimeoutActionHandler: Send + Sync {
     async fn handle_slave_timeout(&self, account_id: &str);
 }
 
+use crate::adapters::outbound::persistence::Database;
+use crate::domain::models::{ServerEvent, TimeoutAction, TimeoutAffectedMember};
 use crate::ports::DisconnectionService;
+use tokio::sync::broadcast;
+
+/// A member of a timed-out EA with its effective timeout action
+struct MemberTimeout {
+    trade_group_id: String,
+    slave_account: String,
+    enabled_flag: bool,
+    action: TimeoutAction,
+}
+
+/// Members to re-evaluate after a timeout
+enum ReevaluationScope {
+    /// Every member (no member resolved to MarkOnly)
+    All,
+    /// Only these keys (some members resolved to MarkOnly)
+    Only(Vec<String>),
+    None,
+}
+
+/// MarkOnly members keep their status, so they are left out of the
+/// re-evaluation. Without overrides this matches the global action.
+fn reevaluation_scope(
+    global: TimeoutAction,
+    members: &[MemberTimeout],
+    key: impl Fn(&MemberTimeout) -> &String,
+) -> ReevaluationScope {
+    let pending: Vec<String> = members
+        .iter()
+        .filter(|m| m.action != TimeoutAction::MarkOnly)
+        .map(|m| key(m).clone())
+        .collect();
+    if pending.len() == members.len() && (global != TimeoutAction::MarkOnly || !members.is_empty())
+    {
+        ReevaluationScope::All
+    } else if pending.is_empty() {
+        ReevaluationScope::None
+    } else {
+        ReevaluationScope::Only(pending)
+    }
+}
 
-/// Real implementation with DB and ZMQ dependencies
+/// Real implementation with DB and ZMQ dependencies.
+/// Applies `global_settings.timeout_action` (or a member's own
+/// `slave_settings.timeout_action`) to the members that depend on the
+/// timed-out EA and broadcasts the outcome.
 pub struct RealTimeoutActionHandler {
     disconnection_service: Arc<dyn DisconnectionService>,
+    db: Arc<Database>,
+    broadcast_tx: broadcast::Sender<ServerEvent>,
 }
 
 impl RealTimeoutActionHandler {
-    pub fn new(disconnection_service: Arc<dyn DisconnectionService>) -> Self {
+    pub fn new(
+        disconnection_service: Arc<dyn DisconnectionService>,
+        db: Arc<Database>,
+        broadcast_tx: broadcast::Sender<ServerEvent>,
+    ) -> Self {
         Self {
             disconnection_service,
+            db,
+            broadcast_tx,
+        }
+    }
+
+    async fn timeout_action(&self) -> TimeoutAction {
+        match self.db.get_global_settings().await {
+            Ok(settings) => settings.timeout_action,
+            Err(e) => {
+                tracing::error!("Failed to load global settings for timeout action: {}", e);
+                TimeoutAction::default()
+            }
+        }
+    }
+
+    /// Apply each member's action (its `timeout_action` override, else the
+    /// global one) to the enabled members, returning the ones affected.
+    /// `DisableMember` turns them off here; re-evaluation is left to the caller.
+    async fn apply_to_members(&self, members: &[MemberTimeout]) -> Vec<TimeoutAffectedMember> {
+        let mut affected = Vec::new();
+        for member in members {
+            if member.action == TimeoutAction::MarkOnly || !member.enabled_flag {
+                continue;
+            }
+            if member.action == TimeoutAction::DisableMember {
+                if let Err(e) = self
+                    .db
+                    .update_member_enabled_flag(
+                        &member.trade_group_id,
+                        &member.slave_account,
+                        false,
+                    )
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to disable member {}/{} after timeout: {}",
+                        member.trade_group_id,
+                        member.slave_account,
+                        e
+                    );
+                    continue;
+                }
+            }
+            affected.push(TimeoutAffectedMember {
+                trade_group_id: member.trade_group_id.clone(),
+                slave_account: member.slave_account.clone(),
+                action: member.action,
+            });
         }
+        affected
+    }
+
+    fn broadcast(
+        &self,
+        account_id: &str,
+        ea_type: EaType,
+        action: TimeoutAction,
+        members: Vec<TimeoutAffectedMember>,
+    ) {
+        tracing::info!(
+            "Timeout action {:?} applied to {} {} ({} members)",
+            action,
+            ea_type,
+            account_id,
+            members.len()
+        );
+        let _ = self.broadcast_tx.send(ServerEvent::TimeoutActionApplied {
+            account_id: account_id.to_string(),
+            ea_type,
+            action,
+            members,
+        });
     }
 }
 
 #[async_trait]
 impl TimeoutActionHandler for RealTimeoutActionHandler {
     async fn handle_master_timeout(&self, account_id: &str) {
-        self.disconnection_service
-            .handle_master_offline(account_id)
-            .await;
+        let action = self.timeout_action().await;
+        let members: Vec<MemberTimeout> = match self.db.get_members(account_id).await {
+            Ok(members) => members
+                .into_iter()
+                .map(|m| MemberTimeout {
+                    action: m.slave_settings.timeout_action.unwrap_or(action),
+                    trade_group_id: m.trade_group_id,
+                    slave_account: m.slave_account,
+                    enabled_flag: m.enabled_flag,
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to get members of timed-out Master {}: {}",
+                    account_id,
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+        let affected = self.apply_to_members(&members).await;
+        match reevaluation_scope(action, &members, |m| &m.slave_account) {
+            ReevaluationScope::All => {
+                self.disconnection_service
+                    .handle_master_offline(account_id)
+                    .await
+            }
+            ReevaluationScope::Only(slaves) => {
+                self.disconnection_service
+                    .handle_master_offline_for(account_id, &slaves)
+                    .await
+            }
+            ReevaluationScope::None => {}
+        }
+        self.broadcast(account_id, EaType::Master, action, affected);
     }
 
     async fn handle_slave_timeout(&self, account_id: &str) {
-        self.disconnection_service
-            .handle_slave_offline(account_id)
-            .await;
+        let action = self.timeout_action().await;
+        let memberships: Vec<MemberTimeout> = match self.db.get_settings_for_slave(account_id).await
+        {
+            Ok(list) => list
+                .into_iter()
+                .map(|s| MemberTimeout {
+                    action: s.slave_settings.timeout_action.unwrap_or(action),
+                    trade_group_id: s.master_account,
+                    slave_account: s.slave_account,
+                    enabled_flag: s.enabled_flag,
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to get memberships of timed-out Slave {}: {}",
+                    account_id,
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+        let affected = self.apply_to_members(&memberships).await;
+        match reevaluation_scope(action, &memberships, |m| &m.trade_group_id) {
+            ReevaluationScope::All => {
+                self.disconnection_service
+                    .handle_slave_offline(account_id)
+                    .await
+            }
+            ReevaluationScope::Only(trade_groups) => {
+                self.disconnection_service
+                    .handle_slave_offline_for(account_id, &trade_groups)
+                    .await
+            }
+            ReevaluationScope::None => {}
+        }
+        self.broadcast(account_id, EaType::Slave, action, affected);
     }
 }
 
@@ -376,6 +982,9 @@ mod tests {
             account_id: account_id.to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: "test".to_string(),
@@ -425,10 +1034,12 @@ mod tests {
         let hb_msg = create_test_heartbeat_message(&account_id, "Master");
         manager.update_heartbeat(hb_msg).await;
 
-        // Second heartbeat: updates balance and equity
+        // Second heartbeat: updates balance, equity and open positions
         let mut hb_msg2 = create_test_heartbeat_message(&account_id, "Master");
         hb_msg2.balance = 12000.0;
         hb_msg2.equity = 11500.0;
+        hb_msg2.open_positions = 4;
+        hb_msg2.symbol_suffix = Some(".m".to_string());
         manager.update_heartbeat(hb_msg2).await;
 
         let ea = manager.get_master(&account_id).await;
@@ -436,9 +1047,140 @@ mod tests {
         let ea = ea.unwrap();
         assert_eq!(ea.balance, 12000.0);
         assert_eq!(ea.equity, 11500.0);
+        assert_eq!(ea.open_positions, 4);
+        assert_eq!(ea.symbol_suffix, Some(".m".to_string()));
+        assert_eq!(ea.status, ConnectionStatus::Online);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_heartbeat_is_rejected() {
+        let manager = ConnectionManager::new(30);
+        let sent_at = Utc::now();
+
+        let mut hb = create_test_heartbeat_message("TEST_001", "Master");
+        hb.timestamp = sent_at.to_rfc3339();
+        assert_eq!(
+            manager.update_heartbeat(hb).await,
+            HeartbeatOutcome::Registered
+        );
+
+        // The EA times out, then a delayed duplicate from before the timeout arrives
+        manager
+            .connections
+            .write()
+            .await
+            .get_mut(&("TEST_001".to_string(), EaType::Master))
+            .unwrap()
+            .status = ConnectionStatus::Timeout;
+        let mut delayed = create_test_heartbeat_message("TEST_001", "Master");
+        delayed.timestamp = (sent_at - Duration::seconds(10)).to_rfc3339();
+        delayed.balance = 1.0;
+        assert_eq!(
+            manager.update_heartbeat(delayed).await,
+            HeartbeatOutcome::Rejected
+        );
+
+        let ea = manager.get_master("TEST_001").await.unwrap();
+        assert_eq!(ea.status, ConnectionStatus::Timeout);
+        assert_eq!(ea.balance, 10000.0);
+
+        // A newer heartbeat is accepted; unparseable timestamps skip the check
+        let mut fresh = create_test_heartbeat_message("TEST_001", "Master");
+        fresh.timestamp = (sent_at + Duration::seconds(1)).to_rfc3339();
+        assert_eq!(
+            manager.update_heartbeat(fresh).await,
+            HeartbeatOutcome::Updated
+        );
+        let mut legacy = create_test_heartbeat_message("TEST_001", "Master");
+        legacy.timestamp = "n/a".to_string();
+        assert_eq!(
+            manager.update_heartbeat(legacy).await,
+            HeartbeatOutcome::Updated
+        );
+        let ea = manager.get_master("TEST_001").await.unwrap();
         assert_eq!(ea.status, ConnectionStatus::Online);
     }
 
+    #[tokio::test]
+    async fn test_future_heartbeat_is_rejected() {
+        let manager = ConnectionManager::new(30).with_max_heartbeat_skew_secs(60);
+
+        let mut hb = create_test_heartbeat_message("TEST_001", "Slave");
+        hb.timestamp = (Utc::now() + Duration::minutes(10)).to_rfc3339();
+        assert_eq!(
+            manager.update_heartbeat(hb).await,
+            HeartbeatOutcome::Rejected
+        );
+        assert!(manager.get_slave("TEST_001").await.is_none());
+
+        // Small skew within the allowance is fine
+        let mut hb = create_test_heartbeat_message("TEST_001", "Slave");
+        hb.timestamp = (Utc::now() + Duration::seconds(30)).to_rfc3339();
+        assert_eq!(
+            manager.update_heartbeat(hb).await,
+            HeartbeatOutcome::Registered
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flap_count_across_timeout_and_reconnect() {
+        let manager = ConnectionManager::new(1).with_reconnect_grace_secs(60);
+        let key = ("TEST_001".to_string(), EaType::Master);
+        let sent_at = Utc::now();
+
+        let mut hb = create_test_heartbeat_message("TEST_001", "Master");
+        hb.timestamp = sent_at.to_rfc3339();
+        manager.update_heartbeat(hb).await;
+        let connected_at = manager.get_master("TEST_001").await.unwrap().connected_at;
+
+        // Silent for longer than the timeout (but within the grace period), then back twice
+        for (i, silent_secs) in [(1, 5), (2, 30)] {
+            manager
+                .connections
+                .write()
+                .await
+                .get_mut(&key)
+                .unwrap()
+                .last_heartbeat = Utc::now() - Duration::seconds(silent_secs);
+            assert_eq!(manager.check_timeouts().await.len(), 1);
+            let ea = manager.get_master("TEST_001").await.unwrap();
+            assert_eq!(ea.status, ConnectionStatus::Timeout);
+            assert_eq!(ea.uptime_seconds(Utc::now()), 0);
+
+            let mut hb = create_test_heartbeat_message("TEST_001", "Master");
+            hb.timestamp = (sent_at + Duration::seconds(i)).to_rfc3339();
+            manager.update_heartbeat(hb).await;
+
+            let ea = manager.get_master("TEST_001").await.unwrap();
+            assert_eq!(ea.status, ConnectionStatus::Online);
+            assert_eq!(ea.flap_count, i as u32);
+            assert_eq!(ea.connected_at, connected_at);
+        }
+
+        // Heartbeats while Online are not flaps
+        let mut hb = create_test_heartbeat_message("TEST_001", "Master");
+        hb.timestamp = (sent_at + Duration::seconds(3)).to_rfc3339();
+        manager.update_heartbeat(hb).await;
+        assert_eq!(manager.get_master("TEST_001").await.unwrap().flap_count, 2);
+
+        // Back after more than the grace period: a new session
+        manager
+            .connections
+            .write()
+            .await
+            .get_mut(&key)
+            .unwrap()
+            .last_heartbeat = Utc::now() - Duration::seconds(120);
+        manager.check_timeouts().await;
+        let mut hb = create_test_heartbeat_message("TEST_001", "Master");
+        hb.timestamp = (sent_at + Duration::seconds(4)).to_rfc3339();
+        manager.update_heartbeat(hb).await;
+
+        let ea = manager.get_master("TEST_001").await.unwrap();
+        assert_eq!(ea.flap_count, 0);
+        assert!(ea.connected_at > connected_at);
+    }
+
     #[tokio::test]
     async fn test_get_all_eas() {
         let manager = ConnectionManager::new(30);
@@ -484,6 +1226,212 @@ mod tests {
         assert_eq!(ea.unwrap().status, ConnectionStatus::Timeout);
     }
 
+    #[tokio::test]
+    async fn test_timeout_override_extends_grace_period() {
+        let manager = ConnectionManager::new(10); // 10 second global timeout
+        manager
+            .update_heartbeat(create_test_heartbeat_message("MASTER_001", "Master"))
+            .await;
+        manager
+            .update_heartbeat(create_test_heartbeat_message("SLAVE_001", "Slave"))
+            .await;
+
+        let updated = manager
+            .set_timeout_override("SLAVE_001", Some(EaType::Slave), Some(60))
+            .await;
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].timeout_override, Some(60));
+
+        // Simulate 30 seconds without heartbeats
+        {
+            let mut connections = manager.connections.write().await;
+            for conn in connections.values_mut() {
+                conn.last_heartbeat = Utc::now() - Duration::seconds(30);
+            }
+        }
+
+        let timed_out = manager.check_timeouts().await;
+        assert_eq!(timed_out, vec![("MASTER_001".to_string(), EaType::Master)]);
+
+        let slave = manager.get_slave("SLAVE_001").await.unwrap();
+        assert_eq!(slave.status, ConnectionStatus::Online);
+
+        // Override survives heartbeats
+        manager
+            .update_heartbeat(create_test_heartbeat_message("SLAVE_001", "Slave"))
+            .await;
+        let slave = manager.get_slave("SLAVE_001").await.unwrap();
+        assert_eq!(slave.timeout_override, Some(60));
+
+        // Clearing the override and unknown accounts
+        let cleared = manager.set_timeout_override("SLAVE_001", None, None).await;
+        assert_eq!(cleared[0].timeout_override, None);
+        assert!(manager
+            .set_timeout_override("UNKNOWN", None, Some(60))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_margin_and_low_margin_flag() {
+        let manager = ConnectionManager::new(30).with_low_margin_level_threshold(150.0);
+
+        // Old EA without margin data: stored as 0 and never flagged
+        manager
+            .update_heartbeat(create_test_heartbeat_message("SLAVE_001", "Slave"))
+            .await;
+        let conn = manager.get_slave("SLAVE_001").await.unwrap();
+        assert_eq!(conn.margin_level, 0.0);
+        assert!(!conn.low_margin_level);
+
+        let mut msg = create_test_heartbeat_message("SLAVE_001", "Slave");
+        msg.equity = 1200.0;
+        msg.margin = 1000.0;
+        msg.free_margin = 200.0;
+        msg.margin_level = 120.0;
+        manager.update_heartbeat(msg.clone()).await;
+        let conn = manager.get_slave("SLAVE_001").await.unwrap();
+        assert_eq!(conn.margin, 1000.0);
+        assert_eq!(conn.free_margin, 200.0);
+        assert!(conn.low_margin_level);
+
+        msg.margin_level = 300.0;
+        manager.update_heartbeat(msg).await;
+        assert!(
+            !manager
+                .get_slave("SLAVE_001")
+                .await
+                .unwrap()
+                .low_margin_level
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_eas_paginated() {
+        let manager = ConnectionManager::new(30);
+        for (account_id, ea_type) in [("B", "Slave"), ("A", "Slave"), ("B", "Master")] {
+            manager
+                .update_heartbeat(create_test_heartbeat_message(account_id, ea_type))
+                .await;
+        }
+
+        let (page, has_more) = manager.get_eas_paginated(None, 2).await;
+        let keys: Vec<_> = page
+            .iter()
+            .map(|conn| (conn.account_id.as_str(), conn.ea_type))
+            .collect();
+        assert_eq!(keys, vec![("A", EaType::Slave), ("B", EaType::Master)]);
+        assert!(has_more);
+
+        let (page, has_more) = manager
+            .get_eas_paginated(Some(("B".to_string(), EaType::Master)), 2)
+            .await;
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].ea_type, EaType::Slave);
+        assert!(!has_more);
+    }
+
+    #[tokio::test]
+    async fn test_position_snapshot_cache_expires() {
+        let manager = ConnectionManager::new(30).with_position_snapshot_stale_secs(60);
+
+        // Unregistered EA: nothing to attach the snapshot to
+        assert!(
+            !manager
+                .record_position_snapshot("MASTER_001", EaType::Master, vec![])
+                .await
+        );
+
+        manager
+            .update_heartbeat(create_test_heartbeat_message("MASTER_001", "Master"))
+            .await;
+        let position = PositionInfo {
+            ticket: 1001,
+            symbol: "EURUSD".to_string(),
+            order_type: "Buy".to_string(),
+            lots: 0.1,
+            open_price: 1.1,
+            open_time: Utc::now().to_rfc3339(),
+            stop_loss: None,
+            take_profit: None,
+            magic_number: None,
+            comment: None,
+            profit: 12.5,
+        };
+        assert!(
+            manager
+                .record_position_snapshot("MASTER_001", EaType::Master, vec![position])
+                .await
+        );
+        let cached = manager
+            .get_position_snapshot("MASTER_001", EaType::Master)
+            .await
+            .unwrap();
+        assert_eq!(cached.positions.len(), 1);
+        assert_eq!(cached.positions[0].profit, 12.5);
+
+        // Age the snapshot past the staleness window
+        {
+            let mut connections = manager.connections.write().await;
+            let conn = connections
+                .get_mut(&("MASTER_001".to_string(), EaType::Master))
+                .unwrap();
+            conn.position_snapshot.as_mut().unwrap().received_at =
+                Utc::now() - Duration::seconds(61);
+        }
+        assert!(manager
+            .get_position_snapshot("MASTER_001", EaType::Master)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missed_heartbeat_threshold() {
+        let manager = ConnectionManager::new(10).with_missed_heartbeat_threshold(3);
+        manager
+            .update_heartbeat(create_test_heartbeat_message("TEST_001", "Master"))
+            .await;
+
+        let expire_heartbeat = || async {
+            let mut connections = manager.connections.write().await;
+            for conn in connections.values_mut() {
+                conn.last_heartbeat = Utc::now() - Duration::seconds(30);
+            }
+        };
+
+        // Two missed beats stay below the threshold
+        expire_heartbeat().await;
+        assert!(manager.check_timeouts().await.is_empty());
+        assert!(manager.check_timeouts().await.is_empty());
+        let ea = manager.get_master("TEST_001").await.unwrap();
+        assert_eq!(ea.status, ConnectionStatus::Online);
+        assert_eq!(ea.missed_heartbeats, 2);
+
+        // A heartbeat resets the counter
+        manager
+            .update_heartbeat(create_test_heartbeat_message("TEST_001", "Master"))
+            .await;
+        assert_eq!(
+            manager
+                .get_master("TEST_001")
+                .await
+                .unwrap()
+                .missed_heartbeats,
+            0
+        );
+
+        // After the reset, only the third consecutive miss times out
+        expire_heartbeat().await;
+        assert!(manager.check_timeouts().await.is_empty());
+        assert!(manager.check_timeouts().await.is_empty());
+        let timed_out = manager.check_timeouts().await;
+        assert_eq!(timed_out, vec![("TEST_001".to_string(), EaType::Master)]);
+        assert_eq!(
+            manager.get_master("TEST_001").await.unwrap().status,
+            ConnectionStatus::Timeout
+        );
+    }
+
     #[tokio::test]
     async fn test_heartbeat_prevents_timeout() {
         let manager = ConnectionManager::new(2); // 2 second timeout
@@ -714,6 +1662,9 @@ mod tests {
             message_type: "HEARTBEAT".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: "2023-01-01T00:00:00Z".to_string(),
             account_number: 123456,
@@ -741,6 +1692,188 @@ mod tests {
         assert_eq!(timeouts.len(), 1);
         assert_eq!(timeouts[0], "master_1");
     }
+
+    /// Master and Slave online with a CONNECTED member; the Master then times
+    /// out under `action`. Returns the member afterwards and the broadcast event.
+    async fn master_timeout_with(
+        action: TimeoutAction,
+    ) -> (crate::domain::models::TradeGroupMember, ServerEvent) {
+        use crate::adapters::outbound::messaging::{WebsocketBroadcaster, ZmqConfigPublisher};
+        use crate::application::disconnection_service::RealDisconnectionService;
+        use crate::application::runtime_status_updater::RuntimeStatusMetrics;
+        use crate::domain::models::{GlobalSettings, SlaveSettings, STATUS_CONNECTED};
+
+        let cm = Arc::new(ConnectionManager::new(1));
+        cm.update_heartbeat(create_test_heartbeat_message("MASTER_001", "Master"))
+            .await;
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        cm.update_heartbeat(create_test_heartbeat_message("SLAVE_001", "Slave"))
+            .await;
+
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        db.create_trade_group("MASTER_001").await.unwrap();
+        db.add_member(
+            "MASTER_001",
+            "SLAVE_001",
+            SlaveSettings::default(),
+            STATUS_CONNECTED,
+        )
+        .await
+        .unwrap();
+        db.update_global_settings(&GlobalSettings {
+            timeout_action: action,
+            ..GlobalSettings::default()
+        })
+        .await
+        .unwrap();
+
+        let (tx, mut rx) = broadcast::channel(100);
+        let disconnection_service = Arc::new(RealDisconnectionService::new(
+            cm.clone(),
+            db.clone(),
+            Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:*").unwrap()),
+            Arc::new(WebsocketBroadcaster::new(tx.clone())),
+            Arc::new(RuntimeStatusMetrics::default()),
+        ));
+        let handler = RealTimeoutActionHandler::new(disconnection_service, db.clone(), tx);
+        TimeoutMonitor::new(cm, Arc::new(handler))
+            .check_timeouts()
+            .await;
+
+        let member = db
+            .get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .unwrap();
+        let event = std::iter::from_fn(|| rx.try_recv().ok())
+            .find(|e| matches!(e, ServerEvent::TimeoutActionApplied { .. }))
+            .expect("timeout action should be broadcast");
+        (member, event)
+    }
+
+    fn affected_slaves(event: &ServerEvent) -> Vec<&str> {
+        match event {
+            ServerEvent::TimeoutActionApplied { members, .. } => {
+                members.iter().map(|m| m.slave_account.as_str()).collect()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_master_timeout_mark_only_keeps_slave_connected() {
+        let (member, event) = master_timeout_with(TimeoutAction::MarkOnly).await;
+
+        assert_eq!(member.status, crate::domain::models::STATUS_CONNECTED);
+        assert!(member.enabled_flag);
+        assert!(affected_slaves(&event).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_master_timeout_pause_copying_keeps_member_enabled() {
+        let (member, event) = master_timeout_with(TimeoutAction::PauseCopying).await;
+
+        // Leaves CONNECTED, so the copy engine stops copying until the Master is back
+        assert_eq!(member.status, crate::domain::models::STATUS_ENABLED);
+        assert!(member.enabled_flag);
+        assert_eq!(affected_slaves(&event), vec!["SLAVE_001"]);
+    }
+
+    #[tokio::test]
+    async fn test_master_timeout_disable_member_turns_member_off() {
+        let (member, event) = master_timeout_with(TimeoutAction::DisableMember).await;
+
+        assert_eq!(member.status, crate::domain::models::STATUS_DISABLED);
+        assert!(!member.enabled_flag);
+        assert_eq!(affected_slaves(&event), vec!["SLAVE_001"]);
+        assert!(matches!(
+            event,
+            ServerEvent::TimeoutActionApplied {
+                ea_type: EaType::Master,
+                action: TimeoutAction::DisableMember,
+                ..
+            }
+        ));
+    }
+    #[tokio::test]
+    async fn test_master_timeout_applies_member_overrides() {
+        use crate::adapters::outbound::messaging::{WebsocketBroadcaster, ZmqConfigPublisher};
+        use crate::application::disconnection_service::RealDisconnectionService;
+        use crate::application::runtime_status_updater::RuntimeStatusMetrics;
+        use crate::domain::models::{SlaveSettings, STATUS_CONNECTED, STATUS_DISABLED};
+
+        let cm = Arc::new(ConnectionManager::new(1));
+        cm.update_heartbeat(create_test_heartbeat_message("MASTER_001", "Master"))
+            .await;
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+        // Global action stays at the default (PauseCopying)
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        db.create_trade_group("MASTER_001").await.unwrap();
+        for (slave, action) in [
+            ("SLAVE_CAUTIOUS", TimeoutAction::DisableMember),
+            ("SLAVE_RELAXED", TimeoutAction::MarkOnly),
+        ] {
+            cm.update_heartbeat(create_test_heartbeat_message(slave, "Slave"))
+                .await;
+            let settings = SlaveSettings {
+                timeout_action: Some(action),
+                ..SlaveSettings::default()
+            };
+            db.add_member("MASTER_001", slave, settings, STATUS_CONNECTED)
+                .await
+                .unwrap();
+        }
+
+        let (tx, mut rx) = broadcast::channel(100);
+        let disconnection_service = Arc::new(RealDisconnectionService::new(
+            cm.clone(),
+            db.clone(),
+            Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:*").unwrap()),
+            Arc::new(WebsocketBroadcaster::new(tx.clone())),
+            Arc::new(RuntimeStatusMetrics::default()),
+        ));
+        let handler = RealTimeoutActionHandler::new(disconnection_service, db.clone(), tx);
+        TimeoutMonitor::new(cm, Arc::new(handler))
+            .check_timeouts()
+            .await;
+
+        let cautious = db
+            .get_member("MASTER_001", "SLAVE_CAUTIOUS")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!cautious.enabled_flag);
+        assert_eq!(cautious.status, STATUS_DISABLED);
+
+        let relaxed = db
+            .get_member("MASTER_001", "SLAVE_RELAXED")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(relaxed.enabled_flag);
+        assert_eq!(relaxed.status, STATUS_CONNECTED);
+
+        let event = std::iter::from_fn(|| rx.try_recv().ok())
+            .find(|e| matches!(e, ServerEvent::TimeoutActionApplied { .. }))
+            .expect("timeout action should be broadcast");
+        let ServerEvent::TimeoutActionApplied {
+            action, members, ..
+        } = event
+        else {
+            unreachable!()
+        };
+        assert_eq!(action, TimeoutAction::PauseCopying);
+        assert_eq!(
+            members,
+            vec![TimeoutAffectedMember {
+                trade_group_id: "MASTER_001".to_string(),
+                slave_account: "SLAVE_CAUTIOUS".to_string(),
+                action: TimeoutAction::DisableMember,
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_register_ea_with_symbol_context() {
         let manager = ConnectionManager::new(30);
@@ -816,4 +1949,68 @@ mod tests {
         assert_eq!(ea.status, ConnectionStatus::Registered);
         assert!(ea.is_trade_allowed); // Should be true from Register
     }
+
+    #[tokio::test]
+    async fn test_history_records_register_timeout_reconnect_in_order() {
+        let manager = ConnectionManager::new(1);
+        let register_msg = crate::domain::models::RegisterMessage {
+            message_type: "Register".to_string(),
+            account_id: "HIST_001".to_string(),
+            ea_type: "Slave".to_string(),
+            platform: "MT5".to_string(),
+            account_number: 12345,
+            broker: "Test Broker".to_string(),
+            account_name: "Test Account".to_string(),
+            server: "Test-Server".to_string(),
+            currency: "USD".to_string(),
+            leverage: 100,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            symbol_context: None,
+            is_trade_allowed: true,
+        };
+        manager.register_ea(&register_msg).await;
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        assert_eq!(manager.check_timeouts().await.len(), 1);
+        manager
+            .update_heartbeat(create_test_heartbeat_message("HIST_001", "Slave"))
+            .await;
+
+        let history = manager.get_history("HIST_001", Some(EaType::Slave));
+        let kinds: Vec<ConnectionEventKind> = history.iter().map(|e| e.event).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ConnectionEventKind::Registered,
+                ConnectionEventKind::Timeout,
+                ConnectionEventKind::Reconnected,
+            ]
+        );
+        assert!(history.windows(2).all(|w| w[0].at <= w[1].at));
+        assert!(history[1].reason.contains("timeout 1s"));
+        assert!(manager
+            .get_history("HIST_001", Some(EaType::Master))
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded_and_records_heartbeat_gaps() {
+        let manager = ConnectionManager::new(0).with_missed_heartbeat_threshold(1000);
+        manager
+            .update_heartbeat(create_test_heartbeat_message("HIST_002", "Master"))
+            .await;
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        for _ in 0..CONNECTION_HISTORY_CAPACITY {
+            manager.check_timeouts().await;
+        }
+
+        let history = manager.get_history("HIST_002", None);
+        assert_eq!(history.len(), CONNECTION_HISTORY_CAPACITY);
+        // The auto-registration was pushed out by the gaps
+        assert!(history
+            .iter()
+            .all(|e| e.event == ConnectionEventKind::HeartbeatGap));
+        assert!(history[0].reason.contains("missed 1/1000"));
+    }
 }
@@ -0,0 +1,121 @@
+//! CurveZMQ key management for the ZeroMQ transport
+//!
+//! Loads the relay's long-term CURVE key pair from the paths in
+//! `[zeromq.security]` and generates new key files for the `keygen` subcommand.
+
+use anyhow::{anyhow, bail, Context, Result};
+use sankey_copier_zmq::curve::{self, CurveKeyPair};
+use std::fs;
+use std::path::Path;
+
+use crate::config::ZmqSecurityConfig;
+
+/// Load the server key pair when CURVE is enabled
+///
+/// # Returns
+/// * `Ok(None)` when `[zeromq.security]` is disabled (plaintext)
+/// * `Err` when enabled but the keys are missing/invalid or libzmq lacks CURVE
+pub fn load_server_keys(
+    config: &ZmqSecurityConfig,
+    base_path: &Path,
+) -> Result<Option<CurveKeyPair>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    if !curve::is_supported() {
+        bail!("[zeromq.security] is enabled but libzmq was built without CURVE support");
+    }
+
+    let public_key = read_key(&base_path.join(&config.server_public_key_path))?;
+    let secret_key = read_key(&base_path.join(&config.server_secret_key_path))?;
+
+    tracing::info!(
+        "CURVE server keys loaded from {}",
+        config.server_secret_key_path
+    );
+
+    Ok(Some(CurveKeyPair {
+        public_key,
+        secret_key,
+    }))
+}
+
+/// Read the server public key for distribution to EAs (None when disabled or missing)
+pub fn read_server_public_key(config: &ZmqSecurityConfig, base_path: &Path) -> Option<String> {
+    if !config.enabled {
+        return None;
+    }
+    read_key(&base_path.join(&config.server_public_key_path)).ok()
+}
+
+/// Generate a new key pair and write it as `<name>.pub` / `<name>.key` in `out_dir`
+pub fn generate_key_files(out_dir: &Path, name: &str) -> Result<CurveKeyPair> {
+    let pair = CurveKeyPair::generate().map_err(|e| anyhow!("{}", e))?;
+
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create key directory: {:?}", out_dir))?;
+
+    let public_path = out_dir.join(format!("{}.pub", name));
+    let secret_path = out_dir.join(format!("{}.key", name));
+    if public_path.exists() || secret_path.exists() {
+        bail!(
+            "Key files already exist in {:?}; refusing to overwrite",
+            out_dir
+        );
+    }
+
+    fs::write(&public_path, format!("{}\n", pair.public_key))
+        .with_context(|| format!("Failed to write public key to {:?}", public_path))?;
+    fs::write(&secret_path, format!("{}\n", pair.secret_key))
+        .with_context(|| format!("Failed to write secret key to {:?}", secret_path))?;
+
+    Ok(pair)
+}
+
+fn read_key(path: &Path) -> Result<String> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read CURVE key from {:?}", path))?;
+    let key = text.trim().to_string();
+    curve::decode_key(&key).map_err(|e| anyhow!("{:?}: {}", path, e))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_security_loads_nothing() {
+        let config = ZmqSecurityConfig::default();
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_server_keys(&config, dir.path()).unwrap().is_none());
+        assert!(read_server_public_key(&config, dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_enabled_security_requires_valid_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ZmqSecurityConfig {
+            enabled: true,
+            ..ZmqSecurityConfig::default()
+        };
+
+        // Missing key files (or a libzmq without CURVE) must fail startup
+        assert!(load_server_keys(&config, dir.path()).is_err());
+
+        if !curve::is_supported() {
+            return;
+        }
+        let pair = generate_key_files(&dir.path().join("keys"), "zmq_server").unwrap();
+        let loaded = load_server_keys(&config, dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, pair);
+        assert_eq!(
+            read_server_public_key(&config, dir.path()),
+            Some(pair.public_key)
+        );
+
+        // Existing key files are never overwritten
+        assert!(generate_key_files(&dir.path().join("keys"), "zmq_server").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Latency probe
+//!
+//! Periodically publishes a `PingMessage` to every connected EA on
+//! "ping/{account_id}". EAs that support it echo the message back and the
+//! round-trip time is recorded by the ping handler. EAs that do not answer
+//! simply keep `last_latency_ms = None`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Utc;
+use sankey_copier_zmq::build_ping_topic;
+
+use crate::adapters::infrastructure::connection_manager::ConnectionManager;
+use crate::adapters::outbound::messaging::ZmqConfigPublisher;
+use crate::domain::models::{ConnectionStatus, PingMessage};
+
+/// Sends latency probes to connected EAs
+pub struct LatencyProbe {
+    connection_manager: Arc<ConnectionManager>,
+    publisher: Arc<ZmqConfigPublisher>,
+    interval: std::time::Duration,
+}
+
+impl LatencyProbe {
+    pub fn new(
+        connection_manager: Arc<ConnectionManager>,
+        publisher: Arc<ZmqConfigPublisher>,
+    ) -> Self {
+        Self {
+            connection_manager,
+            publisher,
+            interval: std::time::Duration::from_secs(10), // Default 10s interval
+        }
+    }
+
+    /// Set a custom probe interval
+    #[allow(dead_code)]
+    pub fn with_interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Start the probe loop
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+            self.send_pings().await;
+        }
+    }
+
+    /// Publish one ping per connected account (public for testing)
+    /// Master and Slave EAs on the same account share the topic and both echo.
+    /// Returns the number of accounts pinged
+    pub async fn send_pings(&self) -> usize {
+        let accounts: HashSet<String> = self
+            .connection_manager
+            .get_all_eas()
+            .await
+            .into_iter()
+            .filter(|conn| {
+                matches!(
+                    conn.status,
+                    ConnectionStatus::Online | ConnectionStatus::Registered
+                )
+            })
+            .map(|conn| conn.account_id)
+            .collect();
+
+        for account_id in &accounts {
+            let ping = PingMessage {
+                message_type: "Ping".to_string(),
+                account_id: account_id.clone(),
+                ea_type: None,
+                server_timestamp: Utc::now().timestamp_millis(),
+            };
+            if let Err(e) = self
+                .publisher
+                .publish_to_topic(&build_ping_topic(account_id), &ping)
+                .await
+            {
+                tracing::warn!("Failed to send ping to {}: {}", account_id, e);
+            }
+        }
+
+        accounts.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{EaType, HeartbeatMessage};
+
+    fn heartbeat(account_id: &str, ea_type: &str) -> HeartbeatMessage {
+        HeartbeatMessage {
+            message_type: "Heartbeat".to_string(),
+            account_id: account_id.to_string(),
+            balance: 10000.0,
+            equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            open_positions: 0,
+            timestamp: Utc::now().to_rfc3339(),
+            version: "test".to_string(),
+            ea_type: ea_type.to_string(),
+            platform: "MT5".to_string(),
+            account_number: 12345,
+            broker: "Test Broker".to_string(),
+            account_name: "Test Account".to_string(),
+            server: "Test-Server".to_string(),
+            currency: "USD".to_string(),
+            leverage: 100,
+            is_trade_allowed: true,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_map: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_pings_skips_offline_and_dedupes_accounts() {
+        let cm = Arc::new(ConnectionManager::new(30));
+        cm.update_heartbeat(heartbeat("ACC_1", "Master")).await;
+        cm.update_heartbeat(heartbeat("ACC_1", "Slave")).await;
+        cm.update_heartbeat(heartbeat("ACC_2", "Slave")).await;
+        cm.unregister_ea("ACC_2", EaType::Slave).await;
+
+        let publisher = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+        let probe = LatencyProbe::new(cm, publisher);
+
+        assert_eq!(probe.send_pings().await, 1);
+    }
+}
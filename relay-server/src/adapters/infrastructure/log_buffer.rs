@@ -2,12 +2,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::Subscriber;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tracing::{Level, Subscriber};
 use tracing_subscriber::Layer;
 
-const MAX_LOG_ENTRIES: usize = 1000;
+/// Default number of entries kept in memory (`[logging] buffer_capacity`)
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 1000;
 
 /// A structured log entry with comprehensive metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,14 +29,117 @@ pub struct LogEntry {
     pub fields: HashMap<String, JsonValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub span_name: Option<String>,
+    /// Account the entry relates to (`account_id` field of the event or an enclosing span)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub account_id: Option<String>,
 }
 
-/// Thread-safe log buffer
-pub type LogBuffer = Arc<RwLock<VecDeque<LogEntry>>>;
+/// Filter for `GET /api/logs` (all conditions are ANDed)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LogQuery {
+    /// Minimum level (e.g. `warn` returns WARN and ERROR)
+    pub level: Option<String>,
+    /// Case-insensitive substring of the message
+    pub contains: Option<String>,
+    /// Exact `account_id`
+    pub account: Option<String>,
+    /// Maximum number of entries (newest first)
+    pub limit: Option<usize>,
+}
+
+impl LogQuery {
+    /// Parse the minimum level; `Err` carries the invalid value
+    pub fn min_level(&self) -> Result<Option<Level>, String> {
+        self.level
+            .as_deref()
+            .map(|level| Level::from_str(level).map_err(|_| level.to_string()))
+            .transpose()
+    }
+
+    /// Collect matching entries, newest first, up to `limit`
+    pub fn apply<'a>(
+        &self,
+        entries: impl IntoIterator<Item = &'a LogEntry>,
+    ) -> Result<Vec<LogEntry>, String> {
+        let min_level = self.min_level()?;
+        let contains = self.contains.as_deref().map(str::to_lowercase);
+
+        Ok(entries
+            .into_iter()
+            .filter(|entry| {
+                // tracing orders more verbose levels as greater
+                min_level
+                    .is_none_or(|min| Level::from_str(&entry.level).is_ok_and(|level| level <= min))
+            })
+            .filter(|entry| {
+                contains
+                    .as_deref()
+                    .is_none_or(|needle| entry.message.to_lowercase().contains(needle))
+            })
+            .filter(|entry| {
+                self.account
+                    .as_deref()
+                    .is_none_or(|account| entry.account_id.as_deref() == Some(account))
+            })
+            .take(self.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+}
 
-/// Create a new empty log buffer
-pub fn create_log_buffer() -> LogBuffer {
-    Arc::new(RwLock::new(VecDeque::with_capacity(MAX_LOG_ENTRIES)))
+/// Thread-safe ring buffer of the newest log entries (newest first)
+#[derive(Clone)]
+pub struct LogBuffer {
+    entries: Arc<RwLock<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Buffer keeping at most `capacity` entries (at least 1)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            entries: Arc::new(RwLock::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Maximum number of entries kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Read access to the entries, newest first
+    pub async fn read(&self) -> RwLockReadGuard<'_, VecDeque<LogEntry>> {
+        self.entries.read().await
+    }
+
+    /// Blocking read access for non-async contexts
+    pub fn blocking_read(&self) -> RwLockReadGuard<'_, VecDeque<LogEntry>> {
+        self.entries.blocking_read()
+    }
+
+    /// Add an entry, dropping the oldest ones beyond the capacity.
+    /// Returns false without waiting when the lock is contended (the entry is dropped).
+    pub fn try_push(&self, entry: LogEntry) -> bool {
+        let Ok(mut entries) = self.entries.try_write() else {
+            return false;
+        };
+        entries.push_front(entry);
+        entries.truncate(self.capacity);
+        true
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_CAPACITY)
+    }
+}
+
+/// Create a new empty log buffer holding at most `capacity` entries
+pub fn create_log_buffer(capacity: usize) -> LogBuffer {
+    LogBuffer::new(capacity)
 }
 
 /// Custom tracing layer that captures logs into a buffer
@@ -48,10 +153,26 @@ impl LogBufferLayer {
     }
 }
 
+/// `account_id` recorded on a span, stored in its extensions
+struct SpanAccountId(String);
+
 impl<S> Layer<S> for LogBufferLayer
 where
     S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
 {
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(account_id), Some(span)) = (visitor.account_id(), ctx.span(id)) {
+            span.extensions_mut().insert(SpanAccountId(account_id));
+        }
+    }
+
     fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
         let metadata = event.metadata();
         let level = format!("{}", metadata.level());
@@ -63,6 +184,15 @@ where
         // Get span information
         let span_name = ctx.event_span(event).map(|span| span.name().to_string());
 
+        // Prefer the event's own account_id, then the innermost span that has one
+        let account_id = visitor.account_id().or_else(|| {
+            ctx.event_scope(event)?.find_map(|span| {
+                span.extensions()
+                    .get::<SpanAccountId>()
+                    .map(|account| account.0.clone())
+            })
+        });
+
         let entry = LogEntry {
             timestamp: Utc::now(),
             level,
@@ -73,17 +203,11 @@ where
             line: metadata.line(),
             fields: visitor.fields,
             span_name,
+            account_id,
         };
 
-        // Add to buffer (blocking is acceptable for logging)
-        if let Ok(mut buffer) = self.buffer.try_write() {
-            buffer.push_front(entry); // Add to front for reverse chronological order
-
-            // Keep buffer size bounded
-            if buffer.len() > MAX_LOG_ENTRIES {
-                buffer.pop_back();
-            }
-        }
+        // The entry is built before locking so the write lock is held only for the push
+        self.buffer.try_push(entry);
     }
 }
 
@@ -94,6 +218,15 @@ struct FieldVisitor {
     fields: HashMap<String, JsonValue>,
 }
 
+impl FieldVisitor {
+    fn account_id(&self) -> Option<String> {
+        match self.fields.get("account_id")? {
+            JsonValue::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+}
+
 impl tracing::field::Visit for FieldVisitor {
     fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
         let field_name = field.name();
@@ -172,10 +305,10 @@ mod tests {
 
     #[test]
     fn test_create_log_buffer() {
-        let buffer = create_log_buffer();
-        let buffer_guard = buffer.blocking_read();
-        assert_eq!(buffer_guard.len(), 0);
-        assert_eq!(buffer_guard.capacity(), MAX_LOG_ENTRIES);
+        let buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+        assert_eq!(buffer.blocking_read().len(), 0);
+        assert_eq!(buffer.capacity(), DEFAULT_LOG_BUFFER_CAPACITY);
+        assert_eq!(create_log_buffer(0).capacity(), 1);
     }
 
     #[test]
@@ -197,6 +330,7 @@ mod tests {
             line: Some(42),
             fields,
             span_name: Some("test_span".to_string()),
+            account_id: None,
         };
 
         // Should serialize to JSON successfully
@@ -218,6 +352,7 @@ mod tests {
             line: None,
             fields: HashMap::new(),
             span_name: None,
+            account_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -319,67 +454,37 @@ mod tests {
 
     #[test]
     fn test_log_buffer_layer_creation() {
-        let buffer = create_log_buffer();
+        let buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
         let _layer = LogBufferLayer::new(buffer.clone());
 
         // Layer should be created successfully
         // This is primarily a smoke test
-        assert!(Arc::strong_count(&buffer) >= 2); // Buffer is shared
+        assert!(Arc::strong_count(&buffer.entries) >= 2); // Buffer is shared
     }
 
     #[test]
-    fn test_buffer_size_limit() {
-        let buffer = create_log_buffer();
-
-        {
-            let mut buffer_guard = buffer.blocking_write();
-
-            // Add more than MAX_LOG_ENTRIES
-            for i in 0..(MAX_LOG_ENTRIES + 100) {
-                buffer_guard.push_front(LogEntry {
-                    timestamp: Utc::now(),
-                    level: "INFO".to_string(),
-                    message: format!("Message {}", i),
-                    target: None,
-                    module_path: None,
-                    file: None,
-                    line: None,
-                    fields: HashMap::new(),
-                    span_name: None,
-                });
-
-                // Simulate buffer size limit
-                if buffer_guard.len() > MAX_LOG_ENTRIES {
-                    buffer_guard.pop_back();
-                }
-            }
+    fn test_buffer_keeps_newest_entries_up_to_capacity() {
+        let capacity = 50;
+        let buffer = create_log_buffer(capacity);
 
-            // Should not exceed MAX_LOG_ENTRIES
-            assert_eq!(buffer_guard.len(), MAX_LOG_ENTRIES);
+        for i in 0..(capacity + 10) {
+            assert!(buffer.try_push(entry("INFO", &format!("Message {}", i), None)));
         }
+
+        let entries = buffer.blocking_read();
+        assert_eq!(entries.len(), capacity);
+        // Newest first; the 10 oldest were dropped
+        assert_eq!(entries.front().unwrap().message, "Message 59");
+        assert_eq!(entries.back().unwrap().message, "Message 10");
     }
 
     #[test]
     fn test_log_entry_reverse_chronological_order() {
-        let buffer = create_log_buffer();
-
-        {
-            let mut buffer_guard = buffer.blocking_write();
-
-            // Add entries in order
-            for i in 0..5 {
-                buffer_guard.push_front(LogEntry {
-                    timestamp: Utc::now(),
-                    level: "INFO".to_string(),
-                    message: format!("Message {}", i),
-                    target: None,
-                    module_path: None,
-                    file: None,
-                    line: None,
-                    fields: HashMap::new(),
-                    span_name: None,
-                });
-            }
+        let buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+
+        // Add entries in order
+        for i in 0..5 {
+            buffer.try_push(entry("INFO", &format!("Message {}", i), None));
         }
 
         let buffer_guard = buffer.blocking_read();
@@ -409,6 +514,7 @@ mod tests {
             line: None,
             fields,
             span_name: None,
+            account_id: None,
         };
 
         assert_eq!(entry.fields.len(), 3);
@@ -418,8 +524,8 @@ mod tests {
     }
 
     #[test]
-    fn test_max_log_entries_constant() {
-        assert_eq!(MAX_LOG_ENTRIES, 1000);
+    fn test_default_log_buffer_capacity_constant() {
+        assert_eq!(DEFAULT_LOG_BUFFER_CAPACITY, 1000);
     }
 
     #[test]
@@ -434,6 +540,7 @@ mod tests {
             line: Some(100),
             fields: HashMap::new(),
             span_name: Some("test_span".to_string()),
+            account_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -443,4 +550,97 @@ mod tests {
         assert_eq!(deserialized.message, "Warning message");
         assert_eq!(deserialized.line, Some(100));
     }
+
+    fn entry(level: &str, message: &str, account_id: Option<&str>) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            level: level.to_string(),
+            message: message.to_string(),
+            target: None,
+            module_path: None,
+            file: None,
+            line: None,
+            fields: HashMap::new(),
+            span_name: None,
+            account_id: account_id.map(String::from),
+        }
+    }
+
+    fn sample_entries() -> Vec<LogEntry> {
+        vec![
+            entry("ERROR", "Failed to send config", Some("SLAVE_001")),
+            entry("WARN", "Heartbeat timeout", Some("MASTER_001")),
+            entry("INFO", "Heartbeat received", Some("SLAVE_001")),
+            entry("DEBUG", "Polling", None),
+        ]
+    }
+
+    fn messages(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_log_query_contains_narrows_results() {
+        let entries = sample_entries();
+        let query = LogQuery {
+            contains: Some("heartbeat".to_string()),
+            ..Default::default()
+        };
+        let result = query.apply(&entries).unwrap();
+        assert_eq!(
+            messages(&result),
+            vec!["Heartbeat timeout", "Heartbeat received"]
+        );
+    }
+
+    #[test]
+    fn test_log_query_min_level_excludes_info() {
+        let entries = sample_entries();
+        let query = LogQuery {
+            level: Some("warn".to_string()),
+            ..Default::default()
+        };
+        let result = query.apply(&entries).unwrap();
+        assert_eq!(
+            messages(&result),
+            vec!["Failed to send config", "Heartbeat timeout"]
+        );
+
+        let invalid = LogQuery {
+            level: Some("loud".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(invalid.apply(&entries).unwrap_err(), "loud");
+    }
+
+    #[test]
+    fn test_log_query_account_and_limit() {
+        let entries = sample_entries();
+        let query = LogQuery {
+            account: Some("SLAVE_001".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let result = query.apply(&entries).unwrap();
+        assert_eq!(messages(&result), vec!["Failed to send config"]);
+    }
+
+    #[test]
+    fn test_layer_takes_account_id_from_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+        let subscriber = tracing_subscriber::registry().with(LogBufferLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("handle_heartbeat", account_id = "SLAVE_001");
+            let _enter = span.enter();
+            tracing::info!("inside span");
+            tracing::info!(account_id = "MASTER_001", "explicit field wins");
+        });
+
+        let entries = buffer.blocking_read();
+        assert_eq!(entries[1].account_id.as_deref(), Some("SLAVE_001"));
+        assert_eq!(entries[0].account_id.as_deref(), Some("MASTER_001"));
+    }
 }
@@ -1,6 +1,12 @@
 pub mod cert;
 pub mod connection_manager;
+pub mod curve_keys;
+pub mod latency_probe;
 pub mod log_buffer;
+pub mod mt_catalog;
 pub mod mt_detector;
 pub mod mt_installer;
 pub mod port_resolver;
+pub mod readiness;
+pub mod rolling_file;
+pub mod schedule_monitor;
@@ -0,0 +1,217 @@
+//! Cached list of MT4/MT5 installations
+//!
+//! Registry detection runs on the first listing and again on
+//! `POST /api/mt-installations/rescan`, so a terminal installed after startup
+//! shows up without restarting the server. Directories from
+//! `[installer] manual_paths` are inspected on every scan and merged in;
+//! entries are deduplicated by data directory path, detected ones first.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use crate::adapters::infrastructure::mt_detector::MtDetector;
+use crate::config::InstallerConfig;
+use crate::domain::models::MtInstallation;
+
+/// Source of MT4/MT5 installations
+pub trait InstallationDetector: Send + Sync {
+    /// Installations registered on this machine
+    fn detect(&self) -> Result<Vec<MtInstallation>>;
+    /// Installation at a manually configured directory, None if it is not a terminal
+    fn inspect(&self, install_path: &Path) -> Option<MtInstallation>;
+}
+
+impl InstallationDetector for MtDetector {
+    fn detect(&self) -> Result<Vec<MtInstallation>> {
+        MtDetector::detect(self)
+    }
+
+    fn inspect(&self, install_path: &Path) -> Option<MtInstallation> {
+        let name = install_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| install_path.to_string_lossy().into_owned());
+        MtDetector::inspect(self, &name, install_path)
+    }
+}
+
+pub struct MtInstallationCatalog {
+    detector: Arc<dyn InstallationDetector>,
+    manual_paths: Vec<String>,
+    /// None until the first scan
+    installations: RwLock<Option<Vec<MtInstallation>>>,
+}
+
+impl MtInstallationCatalog {
+    pub fn new(detector: Arc<dyn InstallationDetector>, manual_paths: Vec<String>) -> Self {
+        Self {
+            detector,
+            manual_paths,
+            installations: RwLock::new(None),
+        }
+    }
+
+    /// Registry detector plus `[installer] manual_paths`
+    pub fn from_config(config: &InstallerConfig) -> Self {
+        Self::new(Arc::new(MtDetector::new()), config.manual_paths.clone())
+    }
+
+    /// Installations from the last scan, scanning first if none has run yet.
+    /// A failed first scan lists the manual entries only.
+    pub fn list(&self) -> Vec<MtInstallation> {
+        if let Some(installations) = self.installations.read().unwrap().as_ref() {
+            return installations.clone();
+        }
+
+        match self.rescan() {
+            Ok(installations) => installations,
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    "Failed to detect MT installations from registry"
+                );
+                let installations = self.merge(Vec::new());
+                *self.installations.write().unwrap() = Some(installations.clone());
+                installations
+            }
+        }
+    }
+
+    /// Run detection again and replace the cached list
+    pub fn rescan(&self) -> Result<Vec<MtInstallation>> {
+        let detected = self.detector.detect()?;
+        tracing::info!(
+            count = detected.len(),
+            "Successfully detected MT installations"
+        );
+
+        let installations = self.merge(detected);
+        *self.installations.write().unwrap() = Some(installations.clone());
+        Ok(installations)
+    }
+
+    /// Installation with `id`, from the cached list
+    pub fn find(&self, id: &str) -> Option<MtInstallation> {
+        self.list()
+            .into_iter()
+            .find(|installation| installation.id == id)
+    }
+
+    /// Detected installations followed by the manual ones not already detected
+    fn merge(&self, detected: Vec<MtInstallation>) -> Vec<MtInstallation> {
+        let manual = self.manual_paths.iter().filter_map(|path| {
+            let installation = self.detector.inspect(Path::new(path));
+            if installation.is_none() {
+                tracing::warn!(path = %path, "Manual MT installation path is not a terminal");
+            }
+            installation
+        });
+
+        let mut seen = HashSet::new();
+        detected
+            .into_iter()
+            .chain(manual)
+            .filter(|installation| seen.insert(path_key(&installation.path)))
+            .collect()
+    }
+}
+
+impl Default for MtInstallationCatalog {
+    fn default() -> Self {
+        Self::from_config(&InstallerConfig::default())
+    }
+}
+
+/// Windows paths compare case-insensitively and with either separator
+fn path_key(path: &str) -> String {
+    path.trim_end_matches(['\\', '/'])
+        .replace('/', "\\")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{Architecture, InstalledComponents, MtType};
+    use std::sync::Mutex;
+
+    fn installation(name: &str, path: &str) -> MtInstallation {
+        MtInstallation {
+            id: MtInstallation::generate_id(&MtType::MT5, path),
+            name: name.to_string(),
+            mt_type: MtType::MT5,
+            platform: Architecture::Bit64,
+            path: path.to_string(),
+            executable: format!("{}\\terminal64.exe", path),
+            version: None,
+            components: InstalledComponents::default(),
+            port_config: None,
+            port_mismatch: None,
+        }
+    }
+
+    /// Returns the queued detection results in order
+    struct MockDetector {
+        scans: Mutex<Vec<Vec<MtInstallation>>>,
+    }
+
+    impl InstallationDetector for MockDetector {
+        fn detect(&self) -> Result<Vec<MtInstallation>> {
+            Ok(self.scans.lock().unwrap().remove(0))
+        }
+
+        fn inspect(&self, install_path: &Path) -> Option<MtInstallation> {
+            let path = install_path.to_string_lossy();
+            Some(installation("Portable", &path))
+        }
+    }
+
+    #[test]
+    fn test_rescan_keeps_manual_entries() {
+        let first = vec![installation("MT5 A", "C:\\MT5 A")];
+        let second = vec![
+            installation("MT5 A", "C:\\MT5 A"),
+            installation("MT5 B", "C:\\MT5 B"),
+        ];
+        let catalog = MtInstallationCatalog::new(
+            Arc::new(MockDetector {
+                scans: Mutex::new(vec![first, second]),
+            }),
+            vec!["D:\\Portable\\MT5".to_string()],
+        );
+
+        let names = |list: Vec<MtInstallation>| -> Vec<String> {
+            list.into_iter()
+                .map(|installation| installation.name)
+                .collect()
+        };
+        assert_eq!(names(catalog.list()), vec!["MT5 A", "Portable"]);
+        // Listing again uses the cached scan
+        assert_eq!(names(catalog.list()), vec!["MT5 A", "Portable"]);
+
+        let rescanned = catalog.rescan().unwrap();
+        assert_eq!(names(rescanned), vec!["MT5 A", "MT5 B", "Portable"]);
+        assert!(catalog
+            .find(&MtInstallation::generate_id(
+                &MtType::MT5,
+                "D:\\Portable\\MT5"
+            ))
+            .is_some());
+    }
+
+    #[test]
+    fn test_manual_entry_matching_detected_path_is_deduplicated() {
+        let catalog = MtInstallationCatalog::new(
+            Arc::new(MockDetector {
+                scans: Mutex::new(vec![vec![installation("MT5 A", "C:\\MT5 A")]]),
+            }),
+            vec!["c:/mt5 a/".to_string()],
+        );
+
+        let installations = catalog.rescan().unwrap();
+        assert_eq!(installations.len(), 1);
+        assert_eq!(installations[0].name, "MT5 A");
+    }
+}
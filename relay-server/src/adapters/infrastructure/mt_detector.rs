@@ -151,14 +151,20 @@ impl MtDetector {
             return None;
         }
 
+        self.inspect(&display_name, &install_path)
+    }
+
+    /// インストールディレクトリからMtInstallation情報を生成
+    ///
+    /// レジストリ検出と、レジストリに登録されない手動追加パス（ポータブル版など）の両方で使う
+    pub fn inspect(&self, name: &str, install_path: &Path) -> Option<MtInstallation> {
         // MT4/MT5のタイプとアーキテクチャを判定
-        let (mt_type, platform, executable) = match self.detect_mt_type_and_platform(&install_path)
-        {
+        let (mt_type, platform, executable) = match self.detect_mt_type_and_platform(install_path) {
             Some(result) => result,
             None => {
                 tracing::warn!(
                     "Could not detect MT type/platform for '{}' at {:?}",
-                    display_name,
+                    name,
                     install_path
                 );
                 return None;
@@ -169,16 +175,16 @@ impl MtDetector {
             "Detected MT type: {:?}, platform: {:?} for '{}'",
             mt_type,
             platform,
-            display_name
+            name
         );
 
         // データディレクトリを検出
-        let data_path = match self.find_data_directory(&install_path, &mt_type) {
+        let data_path = match self.find_data_directory(install_path, &mt_type) {
             Some(path) => path,
             None => {
                 tracing::warn!(
                     "Could not find data directory for '{}' at {:?}",
-                    display_name,
+                    name,
                     install_path
                 );
                 return None;
@@ -186,18 +192,11 @@ impl MtDetector {
         };
         let data_path_str = data_path.to_string_lossy().to_string();
 
-        tracing::debug!(
-            "Found data directory for '{}': {}",
-            display_name,
-            data_path_str
-        );
+        tracing::debug!("Found data directory for '{}': {}", name, data_path_str);
 
         // IDを生成
         let id = MtInstallation::generate_id(&mt_type, &data_path_str);
 
-        // 名前を生成（DisplayNameから）
-        let name = display_name.clone();
-
         // インストールされたコンポーネントをチェック
         let (components, version, port_config) = self
             .check_installed_components(&data_path, &mt_type)
@@ -216,7 +215,7 @@ impl MtDetector {
 
         Some(MtInstallation {
             id,
-            name,
+            name: name.to_string(),
             mt_type,
             platform,
             path: data_path_str,
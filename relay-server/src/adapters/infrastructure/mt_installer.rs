@@ -21,6 +21,8 @@ pub struct MtInstaller {
     components_base_path: PathBuf,
     /// シンボル検索候補リスト
     symbol_search_candidates: Vec<String>,
+    /// CURVE サーバー公開鍵（[zeromq.security] 有効時のみ）
+    curve_server_public_key: Option<String>,
 }
 
 impl MtInstaller {
@@ -29,9 +31,16 @@ impl MtInstaller {
         Self {
             components_base_path,
             symbol_search_candidates,
+            curve_server_public_key: None,
         }
     }
 
+    /// EA設定ファイルに書き込む CURVE サーバー公開鍵を設定
+    pub fn with_curve_server_public_key(mut self, public_key: Option<String>) -> Self {
+        self.curve_server_public_key = public_key;
+        self
+    }
+
     /// 設定ファイルからインストーラーを作成
     ///
     /// components_base_path が設定されていればそれを使用、
@@ -63,7 +72,12 @@ impl MtInstaller {
         candidates.sort();
         candidates.dedup();
 
-        Self::new(base_path, candidates)
+        // CURVE public key is resolved relative to the working directory like other key paths
+        let key_base = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let curve_server_public_key =
+            super::curve_keys::read_server_public_key(&config.zeromq.security, &key_base);
+
+        Self::new(base_path, candidates).with_curve_server_public_key(curve_server_public_key)
     }
 
     /// MT4/MT5にコンポーネントをインストール
@@ -129,6 +143,12 @@ impl MtInstaller {
             );
         }
 
+        // CURVE server public key (EAs generate their own ephemeral client keys)
+        let security_section = match &self.curve_server_public_key {
+            Some(key) => format!("{le}[Security]{le}ServerPublicKey={}{le}", key),
+            None => String::new(),
+        };
+
         let ini_content = format!(
             "# SANKEY Copier Configuration{le}\
 # Auto-generated by relay-server installer{le}\
@@ -137,8 +157,11 @@ impl MtInstaller {
 [ZeroMQ]{le}\
 ReceiverPort={}{le}\
 PublisherPort={}{le}\
-{}",
-            resolved_ports.receiver_port, resolved_ports.sender_port, candidates_section
+{}{}",
+            resolved_ports.receiver_port,
+            resolved_ports.sender_port,
+            candidates_section,
+            security_section
         );
 
         let mut file = fs::File::create(&config_path)
@@ -596,6 +619,28 @@ mod tests {
         // Verify Symbol Search Candidates
         assert!(content.contains("[SymbolSearch]"));
         assert!(content.contains("Candidates=GOLD,XAUUSD"));
+
+        // Plaintext by default: no security section
+        assert!(!content.contains("[Security]"));
+    }
+
+    #[test]
+    fn test_install_config_with_curve_key() {
+        let temp_mt = TempDir::new().unwrap();
+        let mql_path = temp_mt.path().join("MQL5");
+        fs::create_dir_all(&mql_path).unwrap();
+
+        let key = "rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7".to_string();
+        let installer = MtInstaller::new(temp_mt.path().to_path_buf(), vec![])
+            .with_curve_server_public_key(Some(key.clone()));
+
+        installer
+            .install_config(&mql_path, &create_test_resolved_ports())
+            .unwrap();
+
+        let content = fs::read_to_string(mql_path.join("Files").join(EA_CONFIG_FILENAME)).unwrap();
+        assert!(content.contains("[Security]"));
+        assert!(content.contains(&format!("ServerPublicKey={}", key)));
     }
 
     /// Helper function to create test ResolvedPorts (2-port architecture)
@@ -180,12 +180,14 @@ pub fn reset_ports<P: AsRef<Path>>(runtime_path: P) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ZmqSecurityConfig;
     use tempfile::tempdir;
 
     fn default_server_config(port: u16) -> ServerConfig {
         ServerConfig {
             host: "127.0.0.1".to_string(),
             port,
+            legacy_ws_events: false,
         }
     }
 
@@ -210,6 +212,17 @@ mod tests {
             receiver_port: 5555,
             sender_port: 5556,
             timeout_seconds: 30,
+            missed_heartbeat_threshold: 1,
+            low_margin_level_threshold: 150.0,
+            position_snapshot_stale_secs: 300,
+            max_heartbeat_skew_secs: 300,
+            reconnect_grace_secs: 300,
+            offline_replay_buffer_size: 0,
+            send_hwm: 1000,
+            recv_hwm: 1000,
+            max_message_bytes: 1024 * 1024,
+            trade_batch_window_ms: 0,
+            security: ZmqSecurityConfig::default(),
         };
 
         let resolved = resolve_ports(&server_config, &zmq_config, &runtime_path).unwrap();
@@ -232,6 +245,17 @@ mod tests {
             receiver_port: 0, // dynamic
             sender_port: 0,   // dynamic
             timeout_seconds: 30,
+            missed_heartbeat_threshold: 1,
+            low_margin_level_threshold: 150.0,
+            position_snapshot_stale_secs: 300,
+            max_heartbeat_skew_secs: 300,
+            reconnect_grace_secs: 300,
+            offline_replay_buffer_size: 0,
+            send_hwm: 1000,
+            recv_hwm: 1000,
+            max_message_bytes: 1024 * 1024,
+            trade_batch_window_ms: 0,
+            security: ZmqSecurityConfig::default(),
         };
 
         let resolved = resolve_ports(&server_config, &zmq_config, &runtime_path).unwrap();
@@ -270,6 +294,17 @@ mod tests {
             receiver_port: 5555,
             sender_port: 5556,
             timeout_seconds: 30,
+            missed_heartbeat_threshold: 1,
+            low_margin_level_threshold: 150.0,
+            position_snapshot_stale_secs: 300,
+            max_heartbeat_skew_secs: 300,
+            reconnect_grace_secs: 300,
+            offline_replay_buffer_size: 0,
+            send_hwm: 1000,
+            recv_hwm: 1000,
+            max_message_bytes: 1024 * 1024,
+            trade_batch_window_ms: 0,
+            security: ZmqSecurityConfig::default(),
         };
 
         let resolved = resolve_ports(&server_config, &zmq_config, &runtime_path).unwrap();
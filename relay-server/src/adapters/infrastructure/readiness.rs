@@ -0,0 +1,57 @@
+//! Startup readiness flags
+//!
+//! Set by `bootstrap` as each subsystem comes up and read by `GET /ready`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use crate::adapters::outbound::persistence::Database;
+
+/// Subsystems `GET /ready` waits for
+#[derive(Default)]
+pub struct Readiness {
+    zmq_receiver_bound: AtomicBool,
+    /// Set once the database is initialized; probed on every readiness check
+    database: OnceLock<Arc<Database>>,
+    /// Startup gave up on the database; set until a reconnect succeeds
+    database_degraded: AtomicBool,
+}
+
+impl Readiness {
+    pub fn mark_zmq_receiver_bound(&self) {
+        self.zmq_receiver_bound.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_zmq_receiver_bound(&self) -> bool {
+        self.zmq_receiver_bound.load(Ordering::SeqCst)
+    }
+
+    pub fn set_database(&self, db: Arc<Database>) {
+        let _ = self.database.set(db);
+    }
+
+    pub fn set_database_degraded(&self, degraded: bool) {
+        self.database_degraded.store(degraded, Ordering::SeqCst);
+    }
+
+    pub fn is_database_degraded(&self) -> bool {
+        self.database_degraded.load(Ordering::SeqCst)
+    }
+
+    /// True when the database is initialized (not degraded) and answers a trivial query
+    pub async fn is_database_ready(&self) -> bool {
+        if self.is_database_degraded() {
+            return false;
+        }
+        match self.database.get() {
+            Some(db) => match db.ping().await {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!("Readiness database check failed: {}", e);
+                    false
+                }
+            },
+            None => false,
+        }
+    }
+}
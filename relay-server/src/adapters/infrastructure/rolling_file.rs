@@ -0,0 +1,155 @@
+//! Size-based log file rotation
+//!
+//! `[logging] rotation = "size"` writes to `{file_prefix}.{n}` and moves on to
+//! `{file_prefix}.{n + 1}` once the current file would exceed `max_file_bytes`.
+//! Files are never renamed, so a rolled file keeps its name while another
+//! process (or a log viewer) holds it open. After each roll the usual
+//! `max_files` / `max_age_days` retention runs, pruning the oldest files.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::LoggingConfig;
+
+/// Numbered suffix of a rolled log file name (`{prefix}.{n}`)
+pub(crate) fn file_index(name: &str, prefix: &str) -> Option<u64> {
+    name.strip_prefix(prefix)?.strip_prefix('.')?.parse().ok()
+}
+
+/// `io::Write` sink that starts a new numbered file when the size limit is reached
+pub struct SizeRollingWriter {
+    config: LoggingConfig,
+    directory: PathBuf,
+    index: u64,
+    file: File,
+    written: u64,
+}
+
+impl SizeRollingWriter {
+    /// Continue appending to the highest-numbered existing file, or start at `.1`
+    pub fn new(config: &LoggingConfig) -> io::Result<Self> {
+        let directory = PathBuf::from(&config.directory);
+        std::fs::create_dir_all(&directory)?;
+
+        let index = std::fs::read_dir(&directory)?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                file_index(name.to_str()?, &config.file_prefix)
+            })
+            .max()
+            .unwrap_or(1);
+        let file = open_append(&directory, &config.file_prefix, index)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            config: config.clone(),
+            directory,
+            index,
+            file,
+            written,
+        })
+    }
+
+    /// Path of the file currently written to
+    pub fn current_path(&self) -> PathBuf {
+        file_path(&self.directory, &self.config.file_prefix, self.index)
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.index += 1;
+        self.file = open_append(&self.directory, &self.config.file_prefix, self.index)?;
+        self.written = 0;
+        crate::logging::cleanup_old_logs(&self.config);
+        Ok(())
+    }
+}
+
+impl Write for SizeRollingWriter {
+    /// Each call carries one formatted log line; a line is never split across files
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.config.max_file_bytes {
+            self.roll()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn file_path(directory: &Path, prefix: &str, index: u64) -> PathBuf {
+    directory.join(format!("{}.{}", prefix, index))
+}
+
+fn open_append(directory: &Path, prefix: &str, index: u64) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path(directory, prefix, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(dir: &Path, max_file_bytes: u64, max_files: u32) -> LoggingConfig {
+        LoggingConfig {
+            directory: dir.to_string_lossy().into_owned(),
+            file_prefix: "relay".to_string(),
+            rotation: "size".to_string(),
+            max_file_bytes,
+            max_files,
+            max_age_days: 0,
+            ..LoggingConfig::default()
+        }
+    }
+
+    fn log_files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort_by_key(|name| file_index(name, "relay"));
+        names
+    }
+
+    #[test]
+    fn test_rolls_to_numbered_file_past_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = SizeRollingWriter::new(&config(dir.path(), 100, 0)).unwrap();
+
+        let line = [b'x'; 60];
+        writer.write_all(&line).unwrap();
+        assert_eq!(log_files(dir.path()), vec!["relay.1"]);
+
+        // The second line would take the file past 100 bytes
+        writer.write_all(&line).unwrap();
+        assert_eq!(log_files(dir.path()), vec!["relay.1", "relay.2"]);
+        assert_eq!(
+            std::fs::metadata(dir.path().join("relay.1")).unwrap().len(),
+            60
+        );
+        assert_eq!(writer.current_path(), dir.path().join("relay.2"));
+
+        // A restart keeps appending to the newest file
+        drop(writer);
+        let writer = SizeRollingWriter::new(&config(dir.path(), 100, 0)).unwrap();
+        assert_eq!(writer.current_path(), dir.path().join("relay.2"));
+    }
+
+    #[test]
+    fn test_retention_prunes_oldest_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = SizeRollingWriter::new(&config(dir.path(), 10, 2)).unwrap();
+
+        for _ in 0..4 {
+            writer.write_all(&[b'x'; 10]).unwrap();
+        }
+        assert_eq!(log_files(dir.path()), vec!["relay.3", "relay.4"]);
+    }
+}
@@ -0,0 +1,313 @@
+//! Active schedule monitor
+//!
+//! Members with `slave_settings.active_schedule` copy only inside their
+//! windows. Status evaluation already reports such a member DISABLED outside
+//! them, but nothing else happens at the moment a window opens or closes, so
+//! this task re-evaluates scheduled members periodically. When a member moves
+//! in or out of its schedule, its Slave config is resent (the EA sees the new
+//! status) and `member_status_changed` is broadcast to the Web UI.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+use crate::adapters::outbound::persistence::Database;
+use crate::application::runtime_status_updater::RuntimeStatusUpdater;
+use crate::domain::models::{ServerEvent, TradeGroupMember};
+use crate::domain::services::status_calculator::SlaveRuntimeTarget;
+use crate::ports::ConfigPublisher;
+
+/// (trade_group_id, slave_account)
+type MemberKey = (String, String);
+
+/// Re-evaluates scheduled members when their windows open or close
+pub struct ScheduleMonitor {
+    db: Arc<Database>,
+    runtime_updater: RuntimeStatusUpdater,
+    publisher: Arc<dyn ConfigPublisher>,
+    broadcast_tx: broadcast::Sender<ServerEvent>,
+    interval: Duration,
+    /// Last observed in-schedule state of each scheduled member
+    active: Mutex<HashMap<MemberKey, bool>>,
+}
+
+impl ScheduleMonitor {
+    pub fn new(
+        db: Arc<Database>,
+        runtime_updater: RuntimeStatusUpdater,
+        publisher: Arc<dyn ConfigPublisher>,
+        broadcast_tx: broadcast::Sender<ServerEvent>,
+    ) -> Self {
+        Self {
+            db,
+            runtime_updater,
+            publisher,
+            broadcast_tx,
+            interval: Duration::from_secs(15),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start the monitor loop
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+            self.check(Utc::now()).await;
+        }
+    }
+
+    /// Apply schedule transitions since the previous check (public for testing).
+    /// The first check of a member only records its state: configs sent before
+    /// it were already evaluated against the schedule.
+    /// Returns the members whose schedule state changed, with refreshed status.
+    pub async fn check(&self, now: DateTime<Utc>) -> Vec<TradeGroupMember> {
+        let members = match self.db.get_all_members().await {
+            Ok(members) => members,
+            Err(e) => {
+                tracing::error!("Failed to load members for active schedule check: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut changed = Vec::new();
+        {
+            let mut active = self.active.lock().unwrap();
+            let mut seen = HashSet::new();
+            for member in members {
+                if member.slave_settings.active_schedule.is_none() {
+                    continue;
+                }
+                let key = (member.trade_group_id.clone(), member.slave_account.clone());
+                let in_schedule = member.slave_settings.in_active_schedule(now);
+                let previous = active.insert(key.clone(), in_schedule);
+                seen.insert(key);
+                if previous.is_some_and(|previous| previous != in_schedule) {
+                    changed.push((member, in_schedule));
+                }
+            }
+            // Forget members that were deleted or lost their schedule
+            active.retain(|key, _| seen.contains(key));
+        }
+
+        let mut updated = Vec::with_capacity(changed.len());
+        for (member, in_schedule) in changed {
+            tracing::info!(
+                trade_group_id = %member.trade_group_id,
+                slave_account = %member.slave_account,
+                in_schedule,
+                "Member active schedule {}",
+                if in_schedule { "opened" } else { "closed" }
+            );
+            updated.push(self.redistribute(member, now).await);
+        }
+        updated
+    }
+
+    /// Resend the member's Slave config and notify the Web UI
+    async fn redistribute(
+        &self,
+        mut member: TradeGroupMember,
+        now: DateTime<Utc>,
+    ) -> TradeGroupMember {
+        let bundle = self
+            .runtime_updater
+            .build_slave_bundle_at(
+                SlaveRuntimeTarget {
+                    master_account: &member.trade_group_id,
+                    slave_account: &member.slave_account,
+                    trade_group_id: &member.trade_group_id,
+                    enabled_flag: member.enabled_flag,
+                    slave_settings: &member.slave_settings,
+                },
+                now,
+            )
+            .await;
+
+        if let Err(e) = self.publisher.send_slave_config(&bundle.config).await {
+            tracing::error!(
+                trade_group_id = %member.trade_group_id,
+                slave_account = %member.slave_account,
+                error = %e,
+                "Failed to send config after active schedule change"
+            );
+        }
+
+        member.status = bundle.status_result.status;
+        if member.apply_warning_codes(bundle.status_result.warning_codes) {
+            if let Err(e) = self
+                .db
+                .update_member_acknowledged_warnings(
+                    &member.trade_group_id,
+                    &member.slave_account,
+                    &member.acknowledged_warnings,
+                )
+                .await
+            {
+                tracing::warn!("Failed to persist acknowledged warnings: {}", e);
+            }
+        }
+
+        let _ = self.broadcast_tx.send(ServerEvent::MemberStatusChanged {
+            data: member.clone(),
+        });
+        member
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::infrastructure::connection_manager::ConnectionManager;
+    use crate::application::runtime_status_updater::RuntimeStatusMetrics;
+    use crate::domain::models::{
+        HeartbeatMessage, MasterSettings, ScheduleWindow, SlaveSettings, VLogsGlobalSettings,
+        WarningCode, STATUS_CONNECTED, STATUS_DISABLED,
+    };
+    use chrono::{NaiveTime, TimeZone, Weekday};
+    use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage};
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        slave_configs: Mutex<Vec<SlaveConfigMessage>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConfigPublisher for RecordingPublisher {
+        async fn send_master_config(&self, _config: &MasterConfigMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_slave_config(&self, config: &SlaveConfigMessage) -> anyhow::Result<()> {
+            self.slave_configs.lock().unwrap().push(config.clone());
+            Ok(())
+        }
+
+        async fn broadcast_vlogs_config(
+            &self,
+            _config: &VLogsGlobalSettings,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_close_all(
+            &self,
+            _message: &sankey_copier_zmq::CloseAllMessage,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn heartbeat(account_id: &str, ea_type: &str) -> HeartbeatMessage {
+        HeartbeatMessage {
+            message_type: "Heartbeat".to_string(),
+            account_id: account_id.to_string(),
+            balance: 10000.0,
+            equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            open_positions: 0,
+            timestamp: Utc::now().to_rfc3339(),
+            version: "test".to_string(),
+            ea_type: ea_type.to_string(),
+            platform: "MT5".to_string(),
+            account_number: 12345,
+            broker: "Test Broker".to_string(),
+            account_name: "Test Account".to_string(),
+            server: "Test-Server".to_string(),
+            currency: "USD".to_string(),
+            leverage: 100,
+            is_trade_allowed: true,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_map: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_window_boundary_enables_then_disables_member() {
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        let trade_group = db.create_trade_group("MASTER_001").await.unwrap();
+        let master_settings = MasterSettings {
+            enabled: true,
+            ..trade_group.master_settings
+        };
+        db.update_master_settings("MASTER_001", master_settings)
+            .await
+            .unwrap();
+        // London/NY overlap
+        let settings = SlaveSettings {
+            active_schedule: Some(vec![ScheduleWindow {
+                days: vec![
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                ],
+                start: NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+                tz: "UTC".to_string(),
+            }]),
+            ..SlaveSettings::default()
+        };
+        db.add_member("MASTER_001", "SLAVE_001", settings, STATUS_CONNECTED)
+            .await
+            .unwrap();
+
+        let cm = Arc::new(ConnectionManager::new(30));
+        cm.update_heartbeat(heartbeat("MASTER_001", "Master")).await;
+        cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+
+        let publisher = Arc::new(RecordingPublisher::default());
+        let (tx, mut rx) = broadcast::channel(16);
+        let monitor = ScheduleMonitor::new(
+            db.clone(),
+            RuntimeStatusUpdater::with_metrics(
+                db.clone(),
+                cm,
+                Arc::new(RuntimeStatusMetrics::default()),
+            ),
+            publisher.clone(),
+            tx,
+        );
+
+        // Wednesday: the first check only records the state
+        let at = |h, m, s| Utc.with_ymd_and_hms(2025, 1, 15, h, m, s).unwrap();
+        assert!(monitor.check(at(11, 59, 59)).await.is_empty());
+        assert!(publisher.slave_configs.lock().unwrap().is_empty());
+
+        // Window opens
+        let opened = monitor.check(at(12, 0, 0)).await;
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].status, STATUS_CONNECTED);
+        assert!(!opened[0]
+            .warning_codes
+            .contains(&WarningCode::OutsideActiveSchedule));
+        let ServerEvent::MemberStatusChanged { data } = rx.try_recv().unwrap() else {
+            panic!("expected member_status_changed");
+        };
+        assert_eq!(data.status, STATUS_CONNECTED);
+
+        // Still inside: nothing to do
+        assert!(monitor.check(at(15, 59, 59)).await.is_empty());
+
+        // Window closes
+        let closed = monitor.check(at(16, 0, 0)).await;
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].status, STATUS_DISABLED);
+        assert!(closed[0]
+            .warning_codes
+            .contains(&WarningCode::OutsideActiveSchedule));
+
+        let configs = publisher.slave_configs.lock().unwrap();
+        let statuses: Vec<i32> = configs.iter().map(|config| config.status).collect();
+        assert_eq!(statuses, vec![STATUS_CONNECTED, STATUS_DISABLED]);
+        assert!(!configs[1].allow_new_orders);
+    }
+}
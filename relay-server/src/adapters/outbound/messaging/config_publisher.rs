@@ -1,19 +1,34 @@
 // relay-server/src/zeromq/config_publisher.rs
 //
 // ZeroMQ unified publisher for all outgoing messages (config + trade signals)
-// 2-port architecture: This single PUB socket handles all Server → EA messages
+// 2-port architecture: This single XPUB socket handles all Server → EA messages
+// and reports the subscriptions EAs make (see wait_for_subscriber)
 
 use anyhow::{Context, Result};
-use sankey_copier_zmq::{build_trade_topic, ConfigMessage}; // Trait
-use tokio::sync::mpsc;
+use sankey_copier_zmq::{
+    build_config_topic, build_trade_topic, ConfigMessage, TOPIC_SUBSCRIBE_ACK_PREFIX,
+}; // Trait
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
+use crate::application::runtime_status_updater::RuntimeStatusMetrics;
 use crate::domain::models::TradeSignal;
 
+/// Signals per topic after which a batch is sent without waiting for the window
+const MAX_BATCH_SIGNALS: usize = 256;
+
+/// How often the sending thread checks for subscription changes while idle
+const SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Pre-serialized message ready for ZMQ transmission
 struct SerializedMessage {
     topic: String,
     payload: Vec<u8>, // MessagePack bytes
+    /// Trade signals may be coalesced with others to the same topic
+    batchable: bool,
 }
 
 /// Unified ZeroMQ publisher for all outgoing messages
@@ -21,7 +36,11 @@ struct SerializedMessage {
 /// - Config messages (to Master/Slave EAs via account_id topic)
 /// - VLogs config broadcasts (to all EAs via vlogs_config topic)
 pub struct ZmqPublisher {
-    tx: mpsc::UnboundedSender<SerializedMessage>,
+    tx: mpsc::Sender<SerializedMessage>,
+    /// Counts messages that could not be queued or sent
+    metrics: Arc<RuntimeStatusMetrics>,
+    /// Topic prefixes currently subscribed by at least one EA
+    subscriptions: Arc<Subscriptions>,
     _handle: JoinHandle<()>,
 }
 
@@ -30,53 +49,150 @@ pub type ZmqConfigPublisher = ZmqPublisher;
 
 impl ZmqPublisher {
     pub fn new(bind_address: &str) -> Result<Self> {
+        Self::new_with_options(
+            bind_address,
+            None,
+            None,
+            None,
+            Arc::new(RuntimeStatusMetrics::default()),
+        )
+    }
+
+    /// Create the publisher with optional CURVE server secret key (Z85), send
+    /// high-water mark (None = libzmq default) and trade batching window
+    /// (None = every signal is sent as its own frame). Failed publishes are
+    /// recorded in `metrics`.
+    pub fn new_with_options(
+        bind_address: &str,
+        curve_secret_key: Option<&str>,
+        send_hwm: Option<i32>,
+        trade_batch_window: Option<Duration>,
+        metrics: Arc<RuntimeStatusMetrics>,
+    ) -> Result<Self> {
         let context = zmq::Context::new();
         let socket = context
-            .socket(zmq::PUB)
-            .context("Failed to create PUB socket")?;
+            .socket(zmq::XPUB)
+            .context("Failed to create XPUB socket")?;
+
+        // Report every subscription, not only the first per topic, so a
+        // reconnecting EA can be confirmed again
+        socket
+            .set_xpub_verbose(true)
+            .context("Failed to enable XPUB_VERBOSE")?;
+
+        // Bounds per-subscriber buffering so a stalled Slave cannot grow memory unbounded
+        if let Some(send_hwm) = send_hwm {
+            socket
+                .set_sndhwm(send_hwm)
+                .context("Failed to set send high-water mark")?;
+        }
+
+        if let Some(secret_key) = curve_secret_key {
+            sankey_copier_zmq::curve::apply_server(&socket, secret_key)
+                .map_err(|e| anyhow::anyhow!("Failed to enable CURVE on PUB socket: {}", e))?;
+        }
 
         socket
             .bind(bind_address)
             .context(format!("Failed to bind to {}", bind_address))?;
 
         tracing::info!(
+            curve = curve_secret_key.is_some(),
+            trade_batch_window = ?trade_batch_window,
             "ZeroMQ unified publisher (MessagePack) bound to {}",
             bind_address
         );
 
-        let (tx, mut rx) = mpsc::unbounded_channel::<SerializedMessage>();
+        let (tx, rx) = mpsc::channel::<SerializedMessage>();
+        let sender = FrameSender {
+            socket,
+            metrics: metrics.clone(),
+        };
+        let subscriptions = Arc::new(Subscriptions::default());
+        let thread_subscriptions = subscriptions.clone();
 
         // Spawn dedicated task for ZMQ sending
         let handle = tokio::task::spawn_blocking(move || {
-            while let Some(msg) = rx.blocking_recv() {
-                // Build ZMQ message: topic + space + MessagePack
-                let mut zmq_message = msg.topic.as_bytes().to_vec();
-                zmq_message.push(b' ');
-                zmq_message.extend_from_slice(&msg.payload);
-
-                if let Err(e) = socket.send(&zmq_message, 0) {
-                    tracing::error!("Failed to send ZMQ message to topic '{}': {}", msg.topic, e);
-                } else {
-                    tracing::debug!(
-                        "Sent MessagePack message to topic '{}': {} bytes",
-                        msg.topic,
-                        zmq_message.len()
-                    );
+            let mut batches = trade_batch_window.map(TradeBatches::new);
+            loop {
+                let wait = match batches.as_ref().and_then(TradeBatches::deadline) {
+                    Some(deadline) => deadline
+                        .saturating_duration_since(Instant::now())
+                        .min(SUBSCRIPTION_POLL_INTERVAL),
+                    None => SUBSCRIPTION_POLL_INTERVAL,
+                };
+                match rx.recv_timeout(wait) {
+                    Ok(msg) => match batches.as_mut() {
+                        Some(batches) if msg.batchable => batches.push(msg, &sender),
+                        Some(batches) => {
+                            // Keep publish order: queued signals go out before this message
+                            batches.flush(&sender);
+                            sender.send_frame(&msg.topic, &msg.payload);
+                        }
+                        None => sender.send_frame(&msg.topic, &msg.payload),
+                    },
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
                 }
+
+                if let Some(batches) = batches.as_mut() {
+                    if batches
+                        .deadline()
+                        .is_some_and(|deadline| deadline <= Instant::now())
+                    {
+                        batches.flush(&sender);
+                    }
+                }
+                sender.drain_subscriptions(&thread_subscriptions);
+            }
+
+            if let Some(batches) = batches.as_mut() {
+                batches.flush(&sender);
             }
 
             // Explicitly drop socket before context is destroyed
-            drop(socket);
+            drop(sender);
             drop(context);
             tracing::info!("ZMQ unified publisher shut down cleanly");
         });
 
         Ok(Self {
             tx,
+            metrics,
+            subscriptions,
             _handle: handle,
         })
     }
 
+    /// Wait until some EA subscribes to a prefix of `topic`
+    ///
+    /// Frames published before that are dropped by ZMQ (slow joiner), so
+    /// callers that must not lose the first message wait here instead of
+    /// sleeping. Returns false when `timeout` elapsed first.
+    pub async fn wait_for_subscriber(&self, topic: &str, timeout: Duration) -> bool {
+        // Subscribe to changes before checking so none is missed in between
+        let mut changes = self.subscriptions.changes.subscribe();
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if self.subscriptions.matches(topic) {
+                return true;
+            }
+            match tokio::time::timeout_at(deadline, changes.changed()).await {
+                Ok(Ok(())) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    /// Hand a message to the sending thread
+    fn enqueue(&self, message: SerializedMessage) -> Result<()> {
+        let topic = message.topic.clone();
+        self.tx.send(message).map_err(|e| {
+            self.metrics.record_send_failure(&topic);
+            anyhow::anyhow!("Failed to queue message for '{}': {}", topic, e)
+        })
+    }
+
     /// Unified send method for all ConfigMessage types
     /// Uses trait-based interface for type safety and extensibility
     pub async fn send<T>(&self, message: &T) -> Result<()>
@@ -90,13 +206,10 @@ impl ZmqPublisher {
         let serialized = SerializedMessage {
             topic: message.zmq_topic().to_string(),
             payload,
+            batchable: false,
         };
 
-        self.tx
-            .send(serialized)
-            .map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))?;
-
-        Ok(())
+        self.enqueue(serialized)
     }
 
     /// Publish any serializable message to a specific topic
@@ -111,13 +224,10 @@ impl ZmqPublisher {
         let serialized = SerializedMessage {
             topic: topic.to_string(),
             payload,
+            batchable: false,
         };
 
-        self.tx
-            .send(serialized)
-            .map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))?;
-
-        Ok(())
+        self.enqueue(serialized)
     }
 
     /// Broadcast VictoriaLogs configuration to all EAs
@@ -155,27 +265,173 @@ impl ZmqPublisher {
         slave_id: &str,
         signal: &TradeSignal,
     ) -> Result<()> {
+        let topic = build_trade_topic(master_id, slave_id);
+
         // Use rmp_serde::to_vec_named to match the previous ZmqSender serialization format
         let payload = rmp_serde::to_vec_named(signal)
             .context("Failed to serialize TradeSignal to MessagePack")?;
 
         let serialized = SerializedMessage {
-            topic: build_trade_topic(master_id, slave_id),
+            topic,
             payload,
+            batchable: true,
         };
 
-        self.tx
-            .send(serialized)
-            .map_err(|e| anyhow::anyhow!("Failed to send trade signal: {}", e))?;
+        self.enqueue(serialized)
+    }
+}
 
-        Ok(())
+/// Subscriptions reported by the XPUB socket
+#[derive(Default)]
+struct Subscriptions {
+    topics: Mutex<HashSet<Vec<u8>>>,
+    /// Bumped on every change to wake `wait_for_subscriber`
+    changes: watch::Sender<u64>,
+}
+
+impl Subscriptions {
+    /// True when a subscribed prefix matches `topic` (ZMQ prefix matching)
+    fn matches(&self, topic: &str) -> bool {
+        self.topics
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|prefix| topic.as_bytes().starts_with(prefix))
+    }
+
+    fn update(&self, topic: &[u8], subscribed: bool) {
+        let changed = {
+            let mut topics = self.topics.lock().unwrap();
+            if subscribed {
+                topics.insert(topic.to_vec())
+            } else {
+                topics.remove(topic)
+            }
+        };
+        if changed {
+            self.changes.send_modify(|version| *version += 1);
+        }
+    }
+}
+
+/// XPUB socket owned by the sending thread
+struct FrameSender {
+    socket: zmq::Socket,
+    metrics: Arc<RuntimeStatusMetrics>,
+}
+
+impl FrameSender {
+    /// Build and send one ZMQ frame: topic + space + payload
+    fn send_frame(&self, topic: &str, payload: &[u8]) {
+        let zmq_message = sankey_copier_zmq::protocol::encode_frame(topic, payload);
+
+        if let Err(e) = self.socket.send(&zmq_message, 0) {
+            self.metrics.record_send_failure(topic);
+            tracing::error!("Failed to send ZMQ message to topic '{}': {}", topic, e);
+        } else {
+            tracing::debug!(
+                "Sent MessagePack message to topic '{}': {} bytes",
+                topic,
+                zmq_message.len()
+            );
+        }
+    }
+
+    /// Read pending (un)subscription messages from the XPUB socket
+    ///
+    /// Probe topics (`sub-ack/...`) are answered with an empty frame right
+    /// away instead of being tracked: the EA sees the echo only after all of
+    /// its earlier subscriptions are active (see `EaContext::wait_subscribed`).
+    fn drain_subscriptions(&self, subscriptions: &Subscriptions) {
+        while let Ok(message) = self.socket.recv_bytes(zmq::DONTWAIT) {
+            let Some((&kind, topic)) = message.split_first() else {
+                continue;
+            };
+            let subscribed = kind == 1;
+            if topic.starts_with(TOPIC_SUBSCRIBE_ACK_PREFIX.as_bytes()) {
+                if subscribed {
+                    self.send_frame(&String::from_utf8_lossy(topic), &[]);
+                }
+                continue;
+            }
+            tracing::debug!(
+                subscribed,
+                "Subscription change on '{}'",
+                String::from_utf8_lossy(topic)
+            );
+            subscriptions.update(topic, subscribed);
+        }
+    }
+
+    /// A lone signal keeps the single-message format; two or more share one frame
+    fn send_batch(&self, topic: &str, payloads: &[Vec<u8>]) {
+        match payloads {
+            [] => {}
+            [payload] => self.send_frame(topic, payload),
+            _ => {
+                tracing::debug!("Batched {} trade signals to '{}'", payloads.len(), topic);
+                self.send_frame(topic, &sankey_copier_zmq::batch::encode_batch(payloads));
+            }
+        }
+    }
+}
+
+/// Trade signals waiting for the batching window, grouped by topic
+struct TradeBatches {
+    window: Duration,
+    /// Flush time of the signals currently queued (set by the first one)
+    deadline: Option<Instant>,
+    /// Topics in first-arrival order, with their queued payloads
+    topics: Vec<(String, Vec<Vec<u8>>)>,
+    index: HashMap<String, usize>,
+}
+
+impl TradeBatches {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            deadline: None,
+            topics: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn push(&mut self, msg: SerializedMessage, sender: &FrameSender) {
+        self.deadline
+            .get_or_insert_with(|| Instant::now() + self.window);
+        let slot = match self.index.get(&msg.topic) {
+            Some(&slot) => slot,
+            None => {
+                self.index.insert(msg.topic.clone(), self.topics.len());
+                self.topics.push((msg.topic, Vec::new()));
+                self.topics.len() - 1
+            }
+        };
+        let (topic, payloads) = &mut self.topics[slot];
+        payloads.push(msg.payload);
+        if payloads.len() >= MAX_BATCH_SIGNALS {
+            sender.send_batch(topic, payloads);
+            payloads.clear();
+        }
+    }
+
+    fn flush(&mut self, sender: &FrameSender) {
+        for (topic, payloads) in self.topics.drain(..) {
+            sender.send_batch(&topic, &payloads);
+        }
+        self.index.clear();
+        self.deadline = None;
     }
 }
 
 // Adapter implementation for Outbound Port
 use crate::domain::models::VLogsGlobalSettings;
 use async_trait::async_trait;
-use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage};
+use sankey_copier_zmq::{CloseAllMessage, MasterConfigMessage, SlaveConfigMessage};
 
 #[async_trait]
 impl crate::ports::ConfigPublisher for ZmqPublisher {
@@ -190,6 +446,11 @@ impl crate::ports::ConfigPublisher for ZmqPublisher {
     async fn broadcast_vlogs_config(&self, config: &VLogsGlobalSettings) -> anyhow::Result<()> {
         self.broadcast_vlogs_config(config).await
     }
+
+    async fn send_close_all(&self, message: &CloseAllMessage) -> anyhow::Result<()> {
+        self.publish_to_topic(&build_config_topic(&message.slave_account), message)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +500,7 @@ mod tests {
             lot_calculation_mode: sankey_copier_zmq::LotCalculationMode::default(),
             lot_multiplier: Some(2.0),
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![],
@@ -247,6 +509,7 @@ mod tests {
             source_lot_min: None,
             source_lot_max: None,
             master_equity: Some(10000.0),
+            fixed_lot: None,
             // Open Sync Policy defaults
             sync_mode: sankey_copier_zmq::SyncMode::default(),
             limit_order_expiry_min: None,
@@ -328,6 +591,7 @@ mod tests {
                     lot_calculation_mode: sankey_copier_zmq::LotCalculationMode::default(),
                     lot_multiplier: Some(1.0),
                     reverse_trade: false,
+                    reverse_symbols: None,
                     symbol_prefix: None,
                     symbol_suffix: None,
                     symbol_mappings: vec![],
@@ -336,6 +600,7 @@ mod tests {
                     source_lot_min: None,
                     source_lot_max: None,
                     master_equity: None,
+                    fixed_lot: None,
                     // Open Sync Policy defaults
                     sync_mode: sankey_copier_zmq::SyncMode::default(),
                     limit_order_expiry_min: None,
@@ -384,6 +649,9 @@ mod tests {
             timestamp: Utc::now(),
             source_account: "MASTER_001".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let result = publisher
@@ -392,6 +660,294 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_rapid_trade_signals_are_batched_into_one_frame() {
+        use std::sync::atomic::{AtomicU16, Ordering};
+        static PORT: AtomicU16 = AtomicU16::new(31557);
+        let port = PORT.fetch_add(1, Ordering::SeqCst);
+        let address = format!("tcp://127.0.0.1:{}", port);
+
+        let publisher = ZmqPublisher::new_with_options(
+            &address,
+            None,
+            None,
+            Some(Duration::from_millis(100)),
+            Arc::new(RuntimeStatusMetrics::default()),
+        )
+        .unwrap();
+
+        let context = zmq::Context::new();
+        let subscriber = context.socket(zmq::SUB).unwrap();
+        subscriber.connect(&address).unwrap();
+        subscriber.set_subscribe(b"trade/").unwrap();
+        subscriber.set_rcvtimeo(2000).unwrap();
+        assert!(
+            publisher
+                .wait_for_subscriber("trade/MASTER_001/SLAVE_001", Duration::from_secs(2))
+                .await
+        );
+
+        for ticket in 1..=10 {
+            let signal = TradeSignal {
+                ticket,
+                source_account: "MASTER_001".to_string(),
+                ..Default::default()
+            };
+            publisher
+                .send_trade_signal("MASTER_001", "SLAVE_001", &signal)
+                .await
+                .unwrap();
+        }
+
+        let frame = tokio::task::spawn_blocking(move || {
+            let frame = subscriber.recv_bytes(0).unwrap();
+            // Nothing else follows the batch
+            subscriber.set_rcvtimeo(300).unwrap();
+            assert!(subscriber.recv_bytes(0).is_err());
+            frame
+        })
+        .await
+        .unwrap();
+
+        let payload = frame
+            .strip_prefix(b"trade/MASTER_001/SLAVE_001 ".as_slice())
+            .expect("frame should carry the trade topic");
+        assert!(sankey_copier_zmq::batch::is_batch(payload));
+        let tickets: Vec<i64> = sankey_copier_zmq::batch::split_batch(payload)
+            .unwrap()
+            .into_iter()
+            .map(|p| rmp_serde::from_slice::<TradeSignal>(p).unwrap().ticket)
+            .collect();
+        assert_eq!(tickets, (1..=10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_pair_order_survives_batch_splitting() {
+        use std::sync::atomic::{AtomicU16, Ordering};
+        static PORT: AtomicU16 = AtomicU16::new(31657);
+        let port = PORT.fetch_add(1, Ordering::SeqCst);
+        let address = format!("tcp://127.0.0.1:{}", port);
+
+        let publisher = ZmqPublisher::new_with_options(
+            &address,
+            None,
+            None,
+            Some(Duration::from_millis(100)),
+            Arc::new(RuntimeStatusMetrics::default()),
+        )
+        .unwrap();
+
+        let context = zmq::Context::new();
+        let subscriber = context.socket(zmq::SUB).unwrap();
+        subscriber.connect(&address).unwrap();
+        subscriber.set_subscribe(b"trade/").unwrap();
+        subscriber.set_rcvtimeo(2000).unwrap();
+        assert!(
+            publisher
+                .wait_for_subscriber("trade/MASTER_001/SLAVE_001", Duration::from_secs(2))
+                .await
+        );
+
+        // Replayed signals are published before live ones (see replay_member), then
+        // enough live signals to split the pair's batch, interleaved with another Slave
+        let total = MAX_BATCH_SIGNALS as i64 + 20;
+        for ticket in 1..=total {
+            for slave in ["SLAVE_001", "SLAVE_002"] {
+                let signal = TradeSignal {
+                    ticket,
+                    source_account: "MASTER_001".to_string(),
+                    ..Default::default()
+                };
+                publisher
+                    .send_trade_signal("MASTER_001", slave, &signal)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let frames = tokio::task::spawn_blocking(move || {
+            let mut frames = Vec::new();
+            while let Ok(frame) = subscriber.recv_bytes(0) {
+                frames.push(frame);
+                subscriber.set_rcvtimeo(300).unwrap();
+            }
+            frames
+        })
+        .await
+        .unwrap();
+
+        let mut tickets: HashMap<Vec<u8>, Vec<i64>> = HashMap::new();
+        for frame in &frames {
+            let (topic, payload) = sankey_copier_zmq::protocol::split_frame(frame).unwrap();
+            let payloads = if sankey_copier_zmq::batch::is_batch(payload) {
+                sankey_copier_zmq::batch::split_batch(payload).unwrap()
+            } else {
+                vec![payload]
+            };
+            tickets.entry(topic.to_vec()).or_default().extend(
+                payloads
+                    .into_iter()
+                    .map(|p| rmp_serde::from_slice::<TradeSignal>(p).unwrap().ticket),
+            );
+        }
+        for slave in ["SLAVE_001", "SLAVE_002"] {
+            let topic = format!("trade/MASTER_001/{}", slave).into_bytes();
+            assert_eq!(
+                tickets[&topic],
+                (1..=total).collect::<Vec<_>>(),
+                "{}",
+                slave
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_signal_is_delivered_after_wait_for_subscriber() {
+        use std::sync::atomic::{AtomicU16, Ordering};
+        static PORT: AtomicU16 = AtomicU16::new(32557);
+        let port = PORT.fetch_add(1, Ordering::SeqCst);
+        let address = format!("tcp://127.0.0.1:{}", port);
+
+        let publisher = ZmqPublisher::new(&address).unwrap();
+        // Nobody has subscribed yet
+        assert!(
+            !publisher
+                .wait_for_subscriber("trade/MASTER_001/SLAVE_001", Duration::from_millis(50))
+                .await
+        );
+
+        let context = zmq::Context::new();
+        let subscriber = context.socket(zmq::SUB).unwrap();
+        subscriber.connect(&address).unwrap();
+        subscriber.set_subscribe(b"trade/MASTER_001/").unwrap();
+        subscriber.set_rcvtimeo(2000).unwrap();
+
+        // No sleep: the first publish after the wait must not be dropped
+        assert!(
+            publisher
+                .wait_for_subscriber("trade/MASTER_001/SLAVE_001", Duration::from_secs(2))
+                .await
+        );
+        let signal = TradeSignal {
+            ticket: 1,
+            source_account: "MASTER_001".to_string(),
+            ..Default::default()
+        };
+        publisher
+            .send_trade_signal("MASTER_001", "SLAVE_001", &signal)
+            .await
+            .unwrap();
+
+        let frame = tokio::task::spawn_blocking(move || subscriber.recv_bytes(0).unwrap())
+            .await
+            .unwrap();
+        let (topic, payload) = sankey_copier_zmq::protocol::split_frame(&frame).unwrap();
+        assert_eq!(topic, b"trade/MASTER_001/SLAVE_001");
+        assert_eq!(
+            rmp_serde::from_slice::<TradeSignal>(payload)
+                .unwrap()
+                .ticket,
+            1
+        );
+
+        // Other Masters' topics are not matched by that subscription
+        assert!(
+            !publisher
+                .wait_for_subscriber("trade/MASTER_002/SLAVE_001", Duration::from_millis(50))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_ack_probe_is_echoed() {
+        use std::sync::atomic::{AtomicU16, Ordering};
+        static PORT: AtomicU16 = AtomicU16::new(33557);
+        let port = PORT.fetch_add(1, Ordering::SeqCst);
+        let address = format!("tcp://127.0.0.1:{}", port);
+
+        let publisher = ZmqPublisher::new(&address).unwrap();
+
+        let frame = tokio::task::spawn_blocking(move || {
+            let context = zmq::Context::new();
+            let subscriber = context.socket(zmq::SUB).unwrap();
+            subscriber.connect(&address).unwrap();
+            subscriber.set_subscribe(b"config/SLAVE_001").unwrap();
+            subscriber
+                .set_subscribe(
+                    sankey_copier_zmq::build_subscribe_ack_topic("SLAVE_001", 1).as_bytes(),
+                )
+                .unwrap();
+            subscriber.set_rcvtimeo(2000).unwrap();
+            subscriber.recv_bytes(0).unwrap()
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(frame, b"sub-ack/SLAVE_001/1 ");
+        // The probe itself is not tracked, the real subscription is
+        assert!(publisher.subscriptions.matches("config/SLAVE_001"));
+        assert!(!publisher.subscriptions.matches("sub-ack/SLAVE_001/1"));
+    }
+
+    #[tokio::test]
+    async fn test_close_all_is_published_on_slave_config_topic() {
+        use crate::ports::ConfigPublisher;
+        use std::sync::atomic::{AtomicU16, Ordering};
+        static PORT: AtomicU16 = AtomicU16::new(34557);
+        let port = PORT.fetch_add(1, Ordering::SeqCst);
+        let address = format!("tcp://127.0.0.1:{}", port);
+
+        let publisher = ZmqPublisher::new(&address).unwrap();
+        let context = zmq::Context::new();
+        let subscriber = context.socket(zmq::SUB).unwrap();
+        subscriber.connect(&address).unwrap();
+        subscriber.set_subscribe(b"config/SLAVE_001").unwrap();
+        subscriber.set_rcvtimeo(2000).unwrap();
+        assert!(
+            publisher
+                .wait_for_subscriber("config/SLAVE_001", Duration::from_secs(2))
+                .await
+        );
+
+        let message = CloseAllMessage {
+            message_type: "CloseAll".to_string(),
+            slave_account: "SLAVE_001".to_string(),
+            symbol: Some("EURUSD".to_string()),
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        publisher.send_close_all(&message).await.unwrap();
+
+        let frame = tokio::task::spawn_blocking(move || subscriber.recv_bytes(0).unwrap())
+            .await
+            .unwrap();
+        let (topic, payload) = sankey_copier_zmq::protocol::split_frame(&frame).unwrap();
+        assert_eq!(topic, b"config/SLAVE_001");
+        let received: CloseAllMessage = rmp_serde::from_slice(payload).unwrap();
+        assert_eq!(received.message_type, "CloseAll");
+        assert_eq!(received.slave_account, "SLAVE_001");
+        assert_eq!(received.symbol.as_deref(), Some("EURUSD"));
+    }
+
+    #[test]
+    fn test_failed_send_is_counted_in_metrics() {
+        let metrics = Arc::new(RuntimeStatusMetrics::default());
+        // A REP socket refuses to send before it has received (EFSM)
+        let context = zmq::Context::new();
+        let sender = FrameSender {
+            socket: context.socket(zmq::REP).unwrap(),
+            metrics: metrics.clone(),
+        };
+
+        sender.send_frame("trade/MASTER_001/SLAVE_001", b"payload");
+        sender.send_batch("config/SLAVE_002", &[b"a".to_vec(), b"b".to_vec()]);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.send_failures, 2);
+        let last = snapshot.last_send_failure.unwrap();
+        assert_eq!(last.topic, "config/SLAVE_002");
+        assert!(last.at <= Utc::now());
+    }
+
     #[tokio::test]
     async fn test_publish_to_topic() {
         // Test publishing arbitrary message to custom topic
@@ -455,6 +1011,7 @@ mod tests {
             lot_calculation_mode: sankey_copier_zmq::LotCalculationMode::default(),
             lot_multiplier: None,
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![],
@@ -463,6 +1020,7 @@ mod tests {
             source_lot_min: None,
             source_lot_max: None,
             master_equity: None,
+            fixed_lot: None,
             sync_mode: sankey_copier_zmq::SyncMode::default(),
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -523,6 +1081,9 @@ mod tests {
             timestamp: Utc::now(),
             source_account: "MASTER_CLOSE".to_string(),
             close_ratio: Some(0.5),
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let bytes = rmp_serde::to_vec_named(&signal).unwrap();
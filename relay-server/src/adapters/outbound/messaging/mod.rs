@@ -2,10 +2,11 @@ mod config_publisher;
 pub mod websocket_broadcaster;
 
 use crate::domain::models::{
-    HeartbeatMessage, PositionSnapshotMessage, RegisterMessage, RequestConfigMessage,
+    HeartbeatMessage, PingMessage, PositionSnapshotMessage, RegisterMessage, RequestConfigMessage,
     SyncRequestMessage, TradeSignal, UnregisterMessage,
 };
 use anyhow::{Context, Result};
+use sankey_copier_zmq::protocol::{MessageKind, MessageTypeDiscriminator};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -28,13 +29,8 @@ pub enum ZmqMessage {
     // Position sync protocol messages
     PositionSnapshot(PositionSnapshotMessage),
     SyncRequest(SyncRequestMessage),
-}
-
-/// Helper struct to determine message type from MessagePack data
-#[derive(Debug, Deserialize)]
-struct MessageTypeDiscriminator {
-    message_type: Option<String>,
-    action: Option<String>,
+    // Latency probe echoed back by the EA
+    Ping(PingMessage),
 }
 
 // Flexible struct to extract partial heartbeat data
@@ -48,6 +44,12 @@ pub struct ZmqServer {
     context: Arc<zmq::Context>,
     rx_sender: mpsc::UnboundedSender<ZmqMessage>,
     shutdown: Arc<AtomicBool>,
+    /// CURVE server secret key (Z85); plaintext when None
+    curve_secret_key: Option<String>,
+    /// ZMQ_RCVHWM for the PULL socket (None = libzmq default)
+    recv_hwm: Option<i32>,
+    /// Frames larger than this are dropped before deserialization
+    max_message_bytes: Option<usize>,
 }
 
 impl ZmqServer {
@@ -57,15 +59,47 @@ impl ZmqServer {
             context,
             rx_sender,
             shutdown: Arc::new(AtomicBool::new(false)),
+            curve_secret_key: None,
+            recv_hwm: None,
+            max_message_bytes: None,
         })
     }
 
+    /// Set the receive high-water mark of the PULL socket
+    pub fn with_recv_hwm(mut self, recv_hwm: i32) -> Self {
+        self.recv_hwm = Some(recv_hwm);
+        self
+    }
+
+    /// Drop (and log) frames larger than `max_message_bytes`
+    pub fn with_max_message_bytes(mut self, max_message_bytes: usize) -> Self {
+        self.max_message_bytes = Some(max_message_bytes);
+        self
+    }
+
+    /// Enable CURVE encryption on the PULL socket (plaintext clients are rejected)
+    pub fn with_curve_secret_key(mut self, secret_key: Option<String>) -> Self {
+        self.curve_secret_key = secret_key;
+        self
+    }
+
     pub async fn start_receiver(&self, bind_address: &str) -> Result<JoinHandle<()>> {
         let socket = self
             .context
             .socket(zmq::PULL)
             .context("Failed to create ZMQ PULL socket")?;
 
+        if let Some(secret_key) = &self.curve_secret_key {
+            sankey_copier_zmq::curve::apply_server(&socket, secret_key)
+                .map_err(|e| anyhow::anyhow!("Failed to enable CURVE on PULL socket: {}", e))?;
+        }
+
+        if let Some(recv_hwm) = self.recv_hwm {
+            socket
+                .set_rcvhwm(recv_hwm)
+                .context("Failed to set receive high-water mark")?;
+        }
+
         socket
             .bind(bind_address)
             .context(format!("Failed to bind to {}", bind_address))?;
@@ -75,10 +109,15 @@ impl ZmqServer {
             .set_rcvtimeo(100)
             .context("Failed to set receive timeout")?;
 
-        tracing::info!("ZeroMQ receiver started on {}", bind_address);
+        tracing::info!(
+            curve = self.curve_secret_key.is_some(),
+            "ZeroMQ receiver started on {}",
+            bind_address
+        );
 
         let tx = self.rx_sender.clone();
         let shutdown = self.shutdown.clone();
+        let max_message_bytes = self.max_message_bytes;
 
         // Run ZMQ in blocking thread since it's not async
         let handle = tokio::task::spawn_blocking(move || {
@@ -88,12 +127,20 @@ impl ZmqServer {
                         // Timeout - continue checking shutdown flag
                         continue;
                     }
+                    Ok(bytes) if max_message_bytes.is_some_and(|max| bytes.len() > max) => {
+                        tracing::warn!(
+                            size = bytes.len(),
+                            max = max_message_bytes.unwrap_or_default(),
+                            "Dropping oversized ZMQ frame"
+                        );
+                        continue;
+                    }
                     Ok(bytes) => {
                         // First, peek at the message to determine its type
-                        match rmp_serde::from_slice::<MessageTypeDiscriminator>(&bytes) {
-                            Ok(discriminator) => {
+                        match MessageTypeDiscriminator::peek(&bytes) {
+                            Ok(discriminator) => match discriminator.kind() {
                                 // Check message_type field first
-                                if let Some(msg_type) = discriminator.message_type {
+                                MessageKind::Typed(msg_type) => {
                                     match msg_type.as_str() {
                                         "RequestConfig" => {
                                             match rmp_serde::from_slice::<RequestConfigMessage>(
@@ -261,11 +308,31 @@ impl ZmqServer {
                                                 }
                                             }
                                         }
+                                        "Ping" => {
+                                            match rmp_serde::from_slice::<PingMessage>(&bytes) {
+                                                Ok(ping) => {
+                                                    if let Err(e) = tx.send(ZmqMessage::Ping(ping))
+                                                    {
+                                                        tracing::error!(
+                                                            "Failed to send Ping to channel: {}",
+                                                            e
+                                                        );
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    tracing::error!(
+                                                        "Failed to deserialize Ping message: {}",
+                                                        e
+                                                    );
+                                                }
+                                            }
+                                        }
                                         _ => {
                                             tracing::warn!("Unknown message_type: {}", msg_type);
                                         }
                                     }
-                                } else if discriminator.action.is_some() {
+                                }
+                                MessageKind::TradeSignal => {
                                     // Message has 'action' field - it's a TradeSignal
                                     match rmp_serde::from_slice::<TradeSignal>(&bytes) {
                                         Ok(signal) => {
@@ -284,12 +351,13 @@ impl ZmqServer {
                                             );
                                         }
                                     }
-                                } else {
+                                }
+                                MessageKind::Unknown => {
                                     tracing::error!(
                                         "Message has neither message_type nor action field"
                                     );
                                 }
-                            }
+                            },
                             Err(e) => {
                                 tracing::error!(
                                     "Failed to deserialize message discriminator: {}",
@@ -318,7 +386,6 @@ impl ZmqServer {
     }
 
     /// Shutdown the ZMQ receiver gracefully
-    #[allow(dead_code)]
     pub fn shutdown(&self) {
         self.shutdown.store(true, Ordering::Relaxed);
     }
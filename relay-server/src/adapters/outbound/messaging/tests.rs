@@ -1,6 +1,7 @@
 use super::*;
 use crate::domain::models::{
-    HeartbeatMessage, OrderType, RequestConfigMessage, TradeAction, TradeSignal, UnregisterMessage,
+    HeartbeatMessage, OrderType, PingMessage, RequestConfigMessage, TradeAction, TradeSignal,
+    UnregisterMessage,
 };
 use chrono::Utc;
 
@@ -21,6 +22,9 @@ fn test_message_discriminator_trade_signal() {
         timestamp: Utc::now(),
         source_account: "MASTER_001".to_string(),
         close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
     };
 
     let bytes = rmp_serde::to_vec_named(&signal).unwrap();
@@ -44,6 +48,9 @@ fn test_message_discriminator_heartbeat() {
         account_id: "TEST_001".to_string(),
         balance: 10000.0,
         equity: 9800.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
         open_positions: 2,
         timestamp: Utc::now().to_rfc3339(),
         version: "1.0.0".to_string(),
@@ -74,6 +81,27 @@ fn test_message_discriminator_heartbeat() {
     assert_eq!(deserialized.balance, 10000.0);
 }
 
+/// Test that Ping echoes can be distinguished by message_type field
+#[test]
+fn test_message_discriminator_ping() {
+    let ping = PingMessage {
+        message_type: "Ping".to_string(),
+        account_id: "TEST_001".to_string(),
+        ea_type: Some("Slave".to_string()),
+        server_timestamp: 1_700_000_000_000,
+    };
+
+    let bytes = rmp_serde::to_vec_named(&ping).unwrap();
+
+    let discriminator: MessageTypeDiscriminator = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(discriminator.message_type, Some("Ping".to_string()));
+    assert!(discriminator.action.is_none());
+
+    let deserialized: PingMessage = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(deserialized.ea_type.as_deref(), Some("Slave"));
+    assert_eq!(deserialized.server_timestamp, 1_700_000_000_000);
+}
+
 /// Test that RequestConfig messages can be distinguished by message_type field
 #[test]
 fn test_message_discriminator_request_config() {
@@ -193,6 +221,9 @@ fn test_publish_message_topic_format() {
             timestamp: Utc::now(),
             source_account: "MASTER_001".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         },
     };
 
@@ -221,6 +252,9 @@ fn test_trade_action_variants() {
             timestamp: Utc::now(),
             source_account: "MASTER_001".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let bytes = rmp_serde::to_vec_named(&signal).unwrap();
@@ -261,6 +295,9 @@ fn test_order_type_variants() {
             timestamp: Utc::now(),
             source_account: "MASTER_001".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let bytes = rmp_serde::to_vec_named(&signal).unwrap();
@@ -273,3 +310,64 @@ fn test_order_type_variants() {
         );
     }
 }
+
+/// Test that the receiver loop exits after shutdown() instead of blocking forever
+#[tokio::test]
+async fn test_receiver_starts_and_stops_cleanly() {
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let server = ZmqServer::new(tx).unwrap();
+
+    let handle = server.start_receiver("tcp://127.0.0.1:*").await.unwrap();
+    assert!(!handle.is_finished());
+
+    server.shutdown();
+
+    // Receive timeout is 100ms, so the loop must notice the flag quickly
+    tokio::time::timeout(std::time::Duration::from_secs(2), handle)
+        .await
+        .expect("receiver did not stop after shutdown")
+        .expect("receiver task panicked");
+}
+
+#[tokio::test]
+async fn test_receiver_drops_oversized_frames() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let server = ZmqServer::new(tx).unwrap().with_max_message_bytes(256);
+    let handle = server
+        .start_receiver(&format!("tcp://127.0.0.1:{}", port))
+        .await
+        .unwrap();
+
+    let context = zmq::Context::new();
+    let push = context.socket(zmq::PUSH).unwrap();
+    push.connect(&format!("tcp://127.0.0.1:{}", port)).unwrap();
+
+    // Oversized frame is dropped before deserialization
+    push.send(vec![0u8; 1024], 0).unwrap();
+    let msg = UnregisterMessage {
+        message_type: "Unregister".to_string(),
+        account_id: "SLAVE_001".to_string(),
+        timestamp: Utc::now().timestamp_millis(),
+        ea_type: None,
+    };
+    push.send(rmp_serde::to_vec_named(&msg).unwrap(), 0)
+        .unwrap();
+
+    let received = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+        .await
+        .expect("normal frame was not delivered")
+        .unwrap();
+    match received {
+        ZmqMessage::Unregister(m) => assert_eq!(m.account_id, "SLAVE_001"),
+        _ => panic!("expected Unregister message"),
+    }
+    assert!(rx.try_recv().is_err());
+
+    server.shutdown();
+    let _ = handle.await;
+}
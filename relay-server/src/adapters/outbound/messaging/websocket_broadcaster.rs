@@ -1,3 +1,4 @@
+use crate::domain::models::{ServerEvent, SlaveConfigWithMaster};
 use crate::ports::UpdateBroadcaster;
 use async_trait::async_trait;
 use tokio::sync::broadcast;
@@ -5,11 +6,11 @@ use tokio::sync::broadcast;
 /// Implementation of UpdateBroadcaster using tokio broadcast channel
 #[derive(Clone)]
 pub struct WebsocketBroadcaster {
-    tx: broadcast::Sender<String>,
+    tx: broadcast::Sender<ServerEvent>,
 }
 
 impl WebsocketBroadcaster {
-    pub fn new(tx: broadcast::Sender<String>) -> Self {
+    pub fn new(tx: broadcast::Sender<ServerEvent>) -> Self {
         Self { tx }
     }
 }
@@ -22,10 +23,14 @@ impl UpdateBroadcaster for WebsocketBroadcaster {
     }
 
     async fn broadcast_ea_disconnected(&self, account_id: &str) {
-        let _ = self.tx.send(format!("ea_disconnected:{}", account_id));
+        let _ = self.tx.send(ServerEvent::EaDisconnected {
+            account_id: account_id.to_string(),
+        });
     }
 
-    async fn broadcast_settings_updated(&self, json: &str) {
-        let _ = self.tx.send(format!("settings_updated:{}", json));
+    async fn broadcast_settings_updated(&self, settings: &SlaveConfigWithMaster) {
+        let _ = self.tx.send(ServerEvent::SettingsUpdated {
+            data: settings.clone(),
+        });
     }
 }
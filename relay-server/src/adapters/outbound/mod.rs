@@ -1,3 +1,4 @@
 pub mod messaging;
+pub mod notification;
 pub mod observability;
 pub mod persistence;
@@ -0,0 +1,431 @@
+//! Webhook alerts on TradeGroup status transitions
+//!
+//! Posts a JSON event to the TradeGroup's `master_settings.webhook_url` when
+//! its Master goes online/offline or a member gets a warning it did not have
+//! before. The summary is sent as both `text` (Slack) and `content` (Discord)
+//! so either incoming webhook accepts the payload as-is.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::domain::models::WarningCode;
+use crate::ports::{StatusNotifier, TradeGroupRepository};
+
+/// The same event for the same account is not re-sent within this period
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Wait before the single retry of a failed POST
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    MasterOnline,
+    MasterOffline,
+    Warning,
+}
+
+/// JSON body posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub trade_group_id: String,
+    /// Account the event is about (the Master, or the member's Slave)
+    pub account_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning_code: Option<WarningCode>,
+    pub timestamp: String,
+    /// Human-readable summary (Slack)
+    pub text: String,
+    /// Same summary (Discord)
+    pub content: String,
+}
+
+impl WebhookEvent {
+    fn new(
+        event: WebhookEventKind,
+        trade_group_id: &str,
+        account_id: &str,
+        warning_code: Option<WarningCode>,
+    ) -> Self {
+        let summary = match (&event, &warning_code) {
+            (WebhookEventKind::MasterOnline, _) => {
+                format!("[SANKEY Copier] Master {} is online", account_id)
+            }
+            (WebhookEventKind::MasterOffline, _) => {
+                format!("[SANKEY Copier] Master {} went offline", account_id)
+            }
+            (WebhookEventKind::Warning, code) => format!(
+                "[SANKEY Copier] {} (Master {}): {}",
+                account_id,
+                trade_group_id,
+                code.as_ref()
+                    .and_then(|c| serde_json::to_value(c).ok())
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default()
+            ),
+        };
+        Self {
+            event,
+            trade_group_id: trade_group_id.to_string(),
+            account_id: account_id.to_string(),
+            warning_code,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            text: summary.clone(),
+            content: summary,
+        }
+    }
+}
+
+/// Rate-limit key: event, TradeGroup, account and warning code
+type EventKey = (WebhookEventKind, String, String, Option<WarningCode>);
+
+/// Last reported state, used to detect transitions
+#[derive(Default)]
+struct NotifierState {
+    /// Unknown Masters count as offline, so the first heartbeat reports online
+    masters_online: HashMap<String, bool>,
+    member_warnings: HashMap<(String, String), HashSet<WarningCode>>,
+    last_sent: HashMap<EventKey, Instant>,
+}
+
+/// StatusNotifier posting to the per-TradeGroup webhook
+pub struct WebhookNotifier {
+    repository: Arc<dyn TradeGroupRepository>,
+    client: reqwest::Client,
+    state: Mutex<NotifierState>,
+    cooldown: Duration,
+    retry_delay: Duration,
+}
+
+impl WebhookNotifier {
+    pub fn new(repository: Arc<dyn TradeGroupRepository>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            repository,
+            client,
+            state: Mutex::new(NotifierState::default()),
+            cooldown: DEFAULT_COOLDOWN,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+
+    /// Minimum interval between two identical events
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Wait before retrying a failed POST
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Post the event to the TradeGroup's webhook, if configured and not rate-limited
+    async fn dispatch(&self, event: WebhookEvent) {
+        let webhook_url = match self.repository.get_trade_group(&event.trade_group_id).await {
+            Ok(Some(tg)) => tg.master_settings.webhook_url,
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!(
+                    trade_group_id = %event.trade_group_id,
+                    error = %e,
+                    "Failed to load TradeGroup for webhook notification"
+                );
+                None
+            }
+        };
+        let Some(webhook_url) = webhook_url.filter(|url| !url.is_empty()) else {
+            return;
+        };
+
+        let key = (
+            event.event,
+            event.trade_group_id.clone(),
+            event.account_id.clone(),
+            event.warning_code.clone(),
+        );
+        {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            if state
+                .last_sent
+                .get(&key)
+                .is_some_and(|sent| now.duration_since(*sent) < self.cooldown)
+            {
+                tracing::debug!(event = ?event.event, account = %event.account_id, "Webhook notification rate-limited");
+                return;
+            }
+            state.last_sent.insert(key, now);
+        }
+
+        // Do not hold up the status engine on a slow endpoint
+        let client = self.client.clone();
+        let retry_delay = self.retry_delay;
+        tokio::spawn(async move {
+            if post(&client, &webhook_url, &event).await {
+                return;
+            }
+            tokio::time::sleep(retry_delay).await;
+            if !post(&client, &webhook_url, &event).await {
+                tracing::error!(
+                    event = ?event.event,
+                    trade_group_id = %event.trade_group_id,
+                    "Webhook notification failed after retry"
+                );
+            }
+        });
+    }
+}
+
+/// POST the event as JSON; true on a 2xx response
+async fn post(client: &reqwest::Client, url: &str, event: &WebhookEvent) -> bool {
+    let body = match serde_json::to_string(event) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to serialize webhook event");
+            return false;
+        }
+    };
+
+    match client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => true,
+        Ok(response) => {
+            tracing::warn!(status = %response.status(), "Webhook returned an error status");
+            false
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to send webhook notification");
+            false
+        }
+    }
+}
+
+#[async_trait]
+impl StatusNotifier for WebhookNotifier {
+    async fn master_connection(&self, master_account: &str, online: bool) {
+        let previous = self
+            .state
+            .lock()
+            .unwrap()
+            .masters_online
+            .insert(master_account.to_string(), online)
+            .unwrap_or(false);
+        if previous == online {
+            return;
+        }
+
+        let kind = if online {
+            WebhookEventKind::MasterOnline
+        } else {
+            WebhookEventKind::MasterOffline
+        };
+        self.dispatch(WebhookEvent::new(
+            kind,
+            master_account,
+            master_account,
+            None,
+        ))
+        .await;
+    }
+
+    async fn member_warnings(
+        &self,
+        master_account: &str,
+        slave_account: &str,
+        warning_codes: &[WarningCode],
+    ) {
+        // MasterOffline is already reported once for the whole TradeGroup
+        let current: HashSet<WarningCode> = warning_codes
+            .iter()
+            .filter(|code| **code != WarningCode::MasterOffline)
+            .cloned()
+            .collect();
+        let mut appeared: Vec<WarningCode> = {
+            let mut state = self.state.lock().unwrap();
+            let previous = state
+                .member_warnings
+                .insert(
+                    (master_account.to_string(), slave_account.to_string()),
+                    current.clone(),
+                )
+                .unwrap_or_default();
+            current.difference(&previous).cloned().collect()
+        };
+        appeared.sort_by_key(WarningCode::priority);
+
+        for code in appeared {
+            self.dispatch(WebhookEvent::new(
+                WebhookEventKind::Warning,
+                master_account,
+                slave_account,
+                Some(code),
+            ))
+            .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::test_helpers::create_test_db;
+    use mockito::Matcher;
+
+    async fn notifier_for(webhook_url: Option<String>) -> WebhookNotifier {
+        let db = create_test_db().await;
+        let tg = db.create_trade_group("MASTER_001").await.unwrap();
+        let mut settings = tg.master_settings;
+        settings.webhook_url = webhook_url;
+        db.update_master_settings("MASTER_001", settings)
+            .await
+            .unwrap();
+        WebhookNotifier::new(Arc::new(db)).with_retry_delay(Duration::from_millis(10))
+    }
+
+    /// Wait for the spawned POSTs to reach the mock
+    async fn wait_matched(mock: &mockito::Mock) {
+        for _ in 0..100 {
+            if mock.matched_async().await {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_master_offline_transition_posts_payload() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "event": "master_offline",
+                "trade_group_id": "MASTER_001",
+                "account_id": "MASTER_001",
+                "text": "[SANKEY Copier] Master MASTER_001 went offline",
+            })))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        let online = server
+            .mock("POST", "/hook")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"event": "master_online"}),
+            ))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let notifier = notifier_for(Some(format!("{}/hook", server.url()))).await;
+        notifier.master_connection("MASTER_001", true).await;
+        // Still online: no transition
+        notifier.master_connection("MASTER_001", true).await;
+        notifier.master_connection("MASTER_001", false).await;
+
+        wait_matched(&mock).await;
+        wait_matched(&online).await;
+        mock.assert_async().await;
+        online.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_new_warning_is_posted_once_and_rate_limited() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "event": "warning",
+                "account_id": "SLAVE_001",
+                "warning_code": "slave_auto_trading_disabled",
+            })))
+            .with_status(200)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let notifier = notifier_for(Some(format!("{}/hook", server.url()))).await;
+        let codes = [
+            WarningCode::SlaveAutoTradingDisabled,
+            WarningCode::MasterOffline,
+        ];
+        notifier
+            .member_warnings("MASTER_001", "SLAVE_001", &codes)
+            .await;
+        // Unchanged warnings are not re-sent
+        notifier
+            .member_warnings("MASTER_001", "SLAVE_001", &codes)
+            .await;
+        // Cleared and raised again within the cooldown
+        notifier
+            .member_warnings("MASTER_001", "SLAVE_001", &[])
+            .await;
+        notifier
+            .member_warnings("MASTER_001", "SLAVE_001", &codes)
+            .await;
+
+        wait_matched(&mock).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_post_is_retried_once() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .with_status(500)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let notifier = notifier_for(Some(format!("{}/hook", server.url()))).await;
+        notifier.master_connection("MASTER_001", true).await;
+
+        for _ in 0..100 {
+            if mock.matched_async().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_no_webhook_url_sends_nothing() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let notifier = notifier_for(None).await;
+        notifier.master_connection("MASTER_001", true).await;
+        notifier
+            .member_warnings("MASTER_001", "SLAVE_001", &[WarningCode::SlaveOffline])
+            .await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock.assert_async().await;
+    }
+}
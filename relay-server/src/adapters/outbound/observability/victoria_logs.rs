@@ -7,17 +7,23 @@
 //! - VictoriaLogsLayer captures tracing events
 //! - Events are sent to a background task via mpsc channel
 //! - Background task batches and sends logs via HTTP
+//! - Failed batches are kept in a bounded queue and retried with exponential
+//!   backoff; the oldest batch is dropped (and counted) on overflow
 
 #![allow(clippy::type_complexity)]
 
 use crate::config::VictoriaLogsConfig;
 use chrono::Utc;
 use serde::Serialize;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 use tracing::{Event, Level, Subscriber};
 use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::Layer;
 
 /// Log entry structure matching VictoriaLogs JSON Line format
@@ -34,6 +40,52 @@ pub struct LogEntry {
     pub file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<u32>,
+    /// Fields of the enclosing spans (e.g. `account_id`, `trade_group`), sent as
+    /// separate VictoriaLogs fields so they can be filtered on
+    #[serde(flatten)]
+    pub span_fields: BTreeMap<String, String>,
+}
+
+/// Keys of `LogEntry` itself, which span fields must not shadow
+const RESERVED_KEYS: &[&str] = &["_time", "_msg", "level", "source", "target", "file", "line"];
+
+/// Fields recorded on a span, stored in its extensions
+#[derive(Default)]
+struct SpanFields(BTreeMap<String, String>);
+
+impl tracing::field::Visit for SpanFields {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// First retry delay after a failed batch
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound for the retry delay
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Counters for batches waiting for retry, shared with the HTTP API
+#[derive(Debug, Default)]
+pub struct VLogsBufferStats {
+    buffered_batches: AtomicUsize,
+    dropped_batches: AtomicU64,
+}
+
+impl VLogsBufferStats {
+    /// Batches currently waiting to be (re)sent
+    pub fn buffered_batches(&self) -> usize {
+        self.buffered_batches.load(Ordering::Relaxed)
+    }
+
+    /// Batches dropped because the retry buffer overflowed
+    pub fn dropped_batches(&self) -> u64 {
+        self.dropped_batches.load(Ordering::Relaxed)
+    }
 }
 
 /// Internal message type for the background task
@@ -55,6 +107,8 @@ pub struct VictoriaLogsLayer {
     source: String,
     /// Runtime toggle for enabling/disabling log capture
     enabled: Arc<AtomicBool>,
+    /// Retry buffer counters updated by the background task
+    stats: Arc<VLogsBufferStats>,
 }
 
 /// Initialize VictoriaLogs layer if configured.
@@ -107,16 +161,19 @@ impl VictoriaLogsLayer {
         enabled: Arc<AtomicBool>,
     ) -> (Self, tokio::task::JoinHandle<()>) {
         let (tx, rx) = mpsc::channel(1000);
+        let stats = Arc::new(VLogsBufferStats::default());
 
         let task_config = config.clone();
+        let task_stats = stats.clone();
         let handle = tokio::spawn(async move {
-            background_sender(rx, task_config).await;
+            background_sender(rx, task_config, task_stats).await;
         });
 
         let layer = Self {
             sender: tx,
             source: config.source.clone(),
             enabled,
+            stats,
         };
 
         (layer, handle)
@@ -137,9 +194,35 @@ impl VictoriaLogsLayer {
 
 impl<S> Layer<S> for VictoriaLogsLayer
 where
-    S: Subscriber,
+    S: Subscriber + for<'a> LookupSpan<'a>,
 {
-    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: Context<'_, S>,
+    ) {
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        ctx: Context<'_, S>,
+    ) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+                values.record(fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
         // Early return if disabled at runtime
         if !self.enabled.load(Ordering::Relaxed) {
             return;
@@ -160,6 +243,17 @@ where
         let mut visitor = MessageVisitor(&mut message);
         event.record(&mut visitor);
 
+        // Outer spans first so inner spans override fields of the same name
+        let mut span_fields = BTreeMap::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    span_fields.extend(fields.0.clone());
+                }
+            }
+        }
+        span_fields.retain(|key, _| !RESERVED_KEYS.contains(&key.as_str()));
+
         let entry = LogEntry {
             time: Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
             msg: message,
@@ -168,6 +262,7 @@ where
             target: metadata.target().to_string(),
             file: metadata.file().map(String::from),
             line: metadata.line(),
+            span_fields,
         };
 
         // Non-blocking send - drop if channel is full
@@ -234,7 +329,11 @@ impl<'a> tracing::field::Visit for MessageVisitor<'a> {
 }
 
 /// Background task that batches and sends logs to VictoriaLogs
-async fn background_sender(mut rx: mpsc::Receiver<LogMessage>, config: VictoriaLogsConfig) {
+async fn background_sender(
+    mut rx: mpsc::Receiver<LogMessage>,
+    config: VictoriaLogsConfig,
+    stats: Arc<VLogsBufferStats>,
+) {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .build()
@@ -244,6 +343,12 @@ async fn background_sender(mut rx: mpsc::Receiver<LogMessage>, config: VictoriaL
     let endpoint = config.endpoint();
 
     let mut buffer: Vec<LogEntry> = Vec::with_capacity(config.batch_size);
+    let mut queue = BatchQueue::new(
+        config.max_buffered_batches,
+        INITIAL_RETRY_BACKOFF,
+        MAX_RETRY_BACKOFF,
+        stats,
+    );
     let flush_interval = tokio::time::Duration::from_secs(config.flush_interval_secs);
     let mut flush_timer = tokio::time::interval(flush_interval);
 
@@ -254,32 +359,111 @@ async fn background_sender(mut rx: mpsc::Receiver<LogMessage>, config: VictoriaL
                     Some(LogMessage::Entry(entry)) => {
                         buffer.push(entry);
                         if buffer.len() >= config.batch_size {
-                            send_batch(&client, &endpoint, &mut buffer).await;
+                            queue.push(std::mem::take(&mut buffer));
+                            queue.flush_if_due(&client, &endpoint).await;
                         }
                     }
                     Some(LogMessage::Flush) => {
-                        send_batch(&client, &endpoint, &mut buffer).await;
+                        queue.push(std::mem::take(&mut buffer));
+                        queue.flush_if_due(&client, &endpoint).await;
                     }
                     Some(LogMessage::Shutdown) | None => {
-                        // Final flush before shutdown
-                        send_batch(&client, &endpoint, &mut buffer).await;
+                        // Final attempt before shutdown, ignoring backoff
+                        queue.push(std::mem::take(&mut buffer));
+                        queue.flush(&client, &endpoint).await;
                         break;
                     }
                 }
             }
             _ = flush_timer.tick() => {
-                if !buffer.is_empty() {
-                    send_batch(&client, &endpoint, &mut buffer).await;
-                }
+                queue.push(std::mem::take(&mut buffer));
+                queue.flush_if_due(&client, &endpoint).await;
+            }
+        }
+    }
+}
+
+/// Bounded FIFO of batches waiting for delivery
+///
+/// Batches are sent in order; after a failure nothing is sent until the
+/// backoff delay has elapsed, which doubles on each consecutive failure.
+struct BatchQueue {
+    pending: VecDeque<Vec<LogEntry>>,
+    capacity: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff: Duration,
+    retry_at: Option<Instant>,
+    stats: Arc<VLogsBufferStats>,
+}
+
+impl BatchQueue {
+    fn new(
+        capacity: usize,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        stats: Arc<VLogsBufferStats>,
+    ) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            capacity: capacity.max(1),
+            initial_backoff,
+            max_backoff,
+            backoff: initial_backoff,
+            retry_at: None,
+            stats,
+        }
+    }
+
+    /// Queue a batch, dropping the oldest one when full
+    fn push(&mut self, batch: Vec<LogEntry>) {
+        if batch.is_empty() {
+            return;
+        }
+        if self.pending.len() >= self.capacity {
+            self.pending.pop_front();
+            self.stats.dropped_batches.fetch_add(1, Ordering::Relaxed);
+        }
+        self.pending.push_back(batch);
+        self.update_stats();
+    }
+
+    /// Flush unless a retry backoff is still running
+    async fn flush_if_due(&mut self, client: &reqwest::Client, endpoint: &str) {
+        if self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return;
+        }
+        self.flush(client, endpoint).await;
+    }
+
+    /// Send queued batches in order until one fails
+    async fn flush(&mut self, client: &reqwest::Client, endpoint: &str) {
+        while let Some(batch) = self.pending.front_mut() {
+            if !send_batch(client, endpoint, batch).await {
+                self.retry_at = Some(Instant::now() + self.backoff);
+                self.backoff = (self.backoff * 2).min(self.max_backoff);
+                break;
             }
+            self.pending.pop_front();
+            self.retry_at = None;
+            self.backoff = self.initial_backoff;
         }
+        self.update_stats();
+    }
+
+    fn update_stats(&self) {
+        self.stats
+            .buffered_batches
+            .store(self.pending.len(), Ordering::Relaxed);
     }
 }
 
 /// Send a batch of log entries to VictoriaLogs
-async fn send_batch(client: &reqwest::Client, endpoint: &str, buffer: &mut Vec<LogEntry>) {
+///
+/// Clears `buffer` and returns true on success; keeps it for retry otherwise.
+async fn send_batch(client: &reqwest::Client, endpoint: &str, buffer: &mut Vec<LogEntry>) -> bool {
     if buffer.is_empty() {
-        return;
+        return true;
     }
 
     // Build JSON Lines body
@@ -300,22 +484,25 @@ async fn send_batch(client: &reqwest::Client, endpoint: &str, buffer: &mut Vec<L
         Ok(response) => {
             if response.status().is_success() {
                 buffer.clear();
+                true
             } else {
-                // Keep buffer for retry on next interval
+                // Keep buffer for retry after backoff
                 tracing::warn!(
                     "VictoriaLogs HTTP error: {} - keeping {} entries for retry",
                     response.status(),
                     buffer.len()
                 );
+                false
             }
         }
         Err(e) => {
-            // Keep buffer for retry on next interval
+            // Keep buffer for retry after backoff
             tracing::warn!(
                 "VictoriaLogs send failed: {} - keeping {} entries for retry",
                 e,
                 buffer.len()
             );
+            false
         }
     }
 }
@@ -330,12 +517,29 @@ pub struct VLogsController {
     enabled: Arc<AtomicBool>,
     /// Config from config.toml (read-only)
     config: VictoriaLogsConfig,
+    /// Retry buffer counters of the background sender
+    buffer_stats: Arc<VLogsBufferStats>,
 }
 
 impl VLogsController {
     /// Create a new VLogsController
     pub fn new(enabled: Arc<AtomicBool>, config: VictoriaLogsConfig) -> Self {
-        Self { enabled, config }
+        Self {
+            enabled,
+            config,
+            buffer_stats: Arc::new(VLogsBufferStats::default()),
+        }
+    }
+
+    /// Share the retry buffer counters of a running layer
+    pub fn with_buffer_stats(mut self, buffer_stats: Arc<VLogsBufferStats>) -> Self {
+        self.buffer_stats = buffer_stats;
+        self
+    }
+
+    /// Retry buffer counters (buffered / dropped batches)
+    pub fn buffer_stats(&self) -> &VLogsBufferStats {
+        &self.buffer_stats
     }
 
     /// Set the enabled state at runtime
@@ -383,6 +587,7 @@ impl crate::ports::VLogsConfigProvider for VLogsController {
 #[allow(dead_code)]
 pub struct VictoriaLogsHandle {
     sender: mpsc::Sender<LogMessage>,
+    stats: Arc<VLogsBufferStats>,
 }
 
 impl VictoriaLogsHandle {
@@ -390,9 +595,15 @@ impl VictoriaLogsHandle {
     pub fn new(layer: &VictoriaLogsLayer) -> Self {
         Self {
             sender: layer.sender.clone(),
+            stats: layer.stats.clone(),
         }
     }
 
+    /// Retry buffer counters of the background task
+    pub fn buffer_stats(&self) -> Arc<VLogsBufferStats> {
+        self.stats.clone()
+    }
+
     /// Request a flush of buffered logs
     #[allow(dead_code)]
     pub async fn flush(&self) {
@@ -400,7 +611,6 @@ impl VictoriaLogsHandle {
     }
 
     /// Request shutdown of the background task
-    #[allow(dead_code)]
     pub async fn shutdown(&self) {
         let _ = self.sender.send(LogMessage::Shutdown).await;
     }
@@ -423,6 +633,7 @@ mod tests {
             flush_interval_secs: 1,
             source: "test-relay".to_string(),
             log_level: "INFO".to_string(),
+            max_buffered_batches: 100,
         }
     }
 
@@ -436,6 +647,7 @@ mod tests {
             target: "relay_server::test".to_string(),
             file: Some("test.rs".to_string()),
             line: Some(42),
+            span_fields: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -458,6 +670,7 @@ mod tests {
             target: "relay_server::test".to_string(),
             file: None,
             line: None,
+            span_fields: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -465,6 +678,45 @@ mod tests {
         assert!(!json.contains("\"line\""));
     }
 
+    #[test]
+    fn test_layer_exports_span_fields() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let (tx, mut rx) = mpsc::channel(10);
+        let layer = VictoriaLogsLayer {
+            sender: tx,
+            source: "test-relay".to_string(),
+            enabled: Arc::new(AtomicBool::new(true)),
+            stats: Arc::default(),
+        };
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            let span = tracing::info_span!(
+                "handle_trade_signal",
+                account_id = "MASTER_001",
+                trade_group = tracing::field::Empty,
+                level = "shadowed",
+            );
+            span.record("trade_group", "MASTER_001");
+            let _enter = span.enter();
+            tracing::info!("inside span");
+        });
+
+        let Ok(LogMessage::Entry(entry)) = rx.try_recv() else {
+            panic!("expected a log entry");
+        };
+        assert_eq!(entry.level, "INFO");
+        assert_eq!(
+            entry.span_fields,
+            BTreeMap::from([
+                ("account_id".to_string(), "MASTER_001".to_string()),
+                ("trade_group".to_string(), "MASTER_001".to_string()),
+            ])
+        );
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(json.contains("\"account_id\":\"MASTER_001\""));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_send_batch_to_mockito() {
@@ -488,6 +740,7 @@ mod tests {
                 target: "test".to_string(),
                 file: None,
                 line: None,
+                span_fields: BTreeMap::new(),
             },
             LogEntry {
                 time: "2025-01-15T10:30:46.123Z".to_string(),
@@ -497,10 +750,11 @@ mod tests {
                 target: "test".to_string(),
                 file: None,
                 line: None,
+                span_fields: BTreeMap::new(),
             },
         ];
 
-        send_batch(&client, &endpoint, &mut buffer).await;
+        assert!(send_batch(&client, &endpoint, &mut buffer).await);
 
         mock.assert_async().await;
         assert!(buffer.is_empty());
@@ -524,14 +778,84 @@ mod tests {
             target: "test".to_string(),
             file: None,
             line: None,
+            span_fields: BTreeMap::new(),
         }];
 
-        send_batch(&client, endpoint, &mut buffer).await;
+        assert!(!send_batch(&client, endpoint, &mut buffer).await);
 
         // Buffer should be preserved on failure
         assert_eq!(buffer.len(), 1);
     }
 
+    fn test_entry(msg: &str) -> LogEntry {
+        LogEntry {
+            time: "2025-01-15T10:30:45.123Z".to_string(),
+            msg: msg.to_string(),
+            level: "INFO".to_string(),
+            source: "test".to_string(),
+            target: "test".to_string(),
+            file: None,
+            line: None,
+            span_fields: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_batch_queue_retries_until_sink_recovers() {
+        let mut server = mockito::Server::new_async().await;
+        let endpoint = format!("{}/insert/jsonline", server.url());
+        let client = reqwest::Client::new();
+        let stats = Arc::new(VLogsBufferStats::default());
+        let mut queue = BatchQueue::new(
+            2,
+            Duration::from_millis(500),
+            Duration::from_secs(2),
+            stats.clone(),
+        );
+
+        // Sink is down: batches stay buffered, oldest dropped on overflow
+        let failing = server
+            .mock("POST", "/insert/jsonline")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        queue.push(vec![test_entry("batch 1")]);
+        queue.flush_if_due(&client, &endpoint).await;
+        queue.push(vec![test_entry("batch 2")]);
+        // Still backing off: no request is made
+        queue.flush_if_due(&client, &endpoint).await;
+        queue.push(vec![test_entry("batch 3")]);
+        failing.assert_async().await;
+        failing.remove_async().await;
+        assert_eq!(stats.buffered_batches(), 2);
+        assert_eq!(stats.dropped_batches(), 1);
+
+        // Sink recovers: buffered batches flush in order after the backoff
+        let recovered_2 = server
+            .mock("POST", "/insert/jsonline")
+            .match_body(mockito::Matcher::Regex("batch 2".to_string()))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        let recovered_3 = server
+            .mock("POST", "/insert/jsonline")
+            .match_body(mockito::Matcher::Regex("batch 3".to_string()))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        queue.flush_if_due(&client, &endpoint).await;
+
+        recovered_2.assert_async().await;
+        recovered_3.assert_async().await;
+        assert_eq!(stats.buffered_batches(), 0);
+        assert_eq!(stats.dropped_batches(), 1);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_layer_creation() {
@@ -0,0 +1,222 @@
+//! BrokerProfile CRUD operations
+//!
+//! Implementation of Database methods for managing broker profiles,
+//! the symbol prefix/suffix and mappings shared by members on one broker.
+
+use crate::domain::models::{BrokerProfile, SymbolMapping, TradeGroupMember};
+use anyhow::{anyhow, Result};
+use sqlx::Row;
+
+use super::Database;
+
+impl Database {
+    // ============================================================================
+    // BrokerProfile CRUD Operations
+    // ============================================================================
+
+    /// Create a broker profile (fails when the name is already taken)
+    pub async fn create_broker_profile(&self, profile: &BrokerProfile) -> Result<BrokerProfile> {
+        let symbol_map_json = serde_json::to_string(&profile.symbol_map)?;
+
+        with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "INSERT INTO broker_profiles (name, symbol_prefix, symbol_suffix, symbol_map)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(&profile.name)
+            .bind(&profile.symbol_prefix)
+            .bind(&profile.symbol_suffix)
+            .bind(&symbol_map_json)
+            .execute(pool)
+            .await?;
+        });
+
+        self.get_broker_profile(&profile.name)
+            .await?
+            .ok_or_else(|| anyhow!("Failed to retrieve created broker profile"))
+    }
+
+    /// Get a broker profile by name
+    pub async fn get_broker_profile(&self, name: &str) -> Result<Option<BrokerProfile>> {
+        with_pool!(&self.pool, |pool| {
+            let row = sqlx::query(
+                "SELECT name, symbol_prefix, symbol_suffix, symbol_map, created_at, updated_at
+                 FROM broker_profiles WHERE name = $1",
+            )
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+
+            match row {
+                Some(row) => {
+                    let symbol_map_json: String = row.get("symbol_map");
+                    let symbol_map: Vec<SymbolMapping> = serde_json::from_str(&symbol_map_json)?;
+                    Ok(Some(BrokerProfile {
+                        name: row.get("name"),
+                        symbol_prefix: row.get("symbol_prefix"),
+                        symbol_suffix: row.get("symbol_suffix"),
+                        symbol_map,
+                        created_at: row.get("created_at"),
+                        updated_at: row.get("updated_at"),
+                    }))
+                }
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// List all broker profiles by name
+    pub async fn list_broker_profiles(&self) -> Result<Vec<BrokerProfile>> {
+        with_pool!(&self.pool, |pool| {
+            let rows = sqlx::query(
+                "SELECT name, symbol_prefix, symbol_suffix, symbol_map, created_at, updated_at
+                 FROM broker_profiles ORDER BY name",
+            )
+            .fetch_all(pool)
+            .await?;
+
+            let mut result = Vec::with_capacity(rows.len());
+            for row in rows {
+                let symbol_map_json: String = row.get("symbol_map");
+                let symbol_map: Vec<SymbolMapping> = serde_json::from_str(&symbol_map_json)?;
+                result.push(BrokerProfile {
+                    name: row.get("name"),
+                    symbol_prefix: row.get("symbol_prefix"),
+                    symbol_suffix: row.get("symbol_suffix"),
+                    symbol_map,
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                });
+            }
+
+            Ok(result)
+        })
+    }
+
+    /// Replace the prefix/suffix and mappings of an existing profile
+    pub async fn update_broker_profile(&self, profile: &BrokerProfile) -> Result<()> {
+        let symbol_map_json = serde_json::to_string(&profile.symbol_map)?;
+
+        let rows_affected = with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "UPDATE broker_profiles
+                 SET symbol_prefix = $1, symbol_suffix = $2, symbol_map = $3,
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE name = $4",
+            )
+            .bind(&profile.symbol_prefix)
+            .bind(&profile.symbol_suffix)
+            .bind(&symbol_map_json)
+            .bind(&profile.name)
+            .execute(pool)
+            .await?
+            .rows_affected()
+        });
+
+        if rows_affected == 0 {
+            anyhow::bail!("BrokerProfile not found: name={}", profile.name);
+        }
+
+        Ok(())
+    }
+
+    /// Delete a broker profile; false when it did not exist
+    pub async fn delete_broker_profile(&self, name: &str) -> Result<bool> {
+        let rows_affected = with_pool!(&self.pool, |pool| {
+            sqlx::query("DELETE FROM broker_profiles WHERE name = $1")
+                .bind(name)
+                .execute(pool)
+                .await?
+                .rows_affected()
+        });
+        Ok(rows_affected > 0)
+    }
+
+    /// Members whose `slave_settings.broker_profile` references the profile
+    pub async fn get_members_using_broker_profile(
+        &self,
+        name: &str,
+    ) -> Result<Vec<TradeGroupMember>> {
+        Ok(self
+            .get_all_members()
+            .await?
+            .into_iter()
+            .filter(|member| member.slave_settings.broker_profile.as_deref() == Some(name))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::outbound::persistence::test_helpers::{
+        create_test_db, create_test_slave_settings,
+    };
+    use crate::domain::models::{BrokerProfile, SymbolMapping};
+
+    fn profile(name: &str) -> BrokerProfile {
+        BrokerProfile {
+            name: name.to_string(),
+            symbol_prefix: None,
+            symbol_suffix: Some("#".to_string()),
+            symbol_map: vec![SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD".to_string(),
+                priority: 0,
+            }],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broker_profile_crud() {
+        let db = create_test_db().await;
+
+        let created = db.create_broker_profile(&profile("xm")).await.unwrap();
+        assert_eq!(created.symbol_suffix, Some("#".to_string()));
+        assert_eq!(created.symbol_map.len(), 1);
+        assert!(db.create_broker_profile(&profile("xm")).await.is_err());
+
+        let mut updated = created.clone();
+        updated.symbol_prefix = Some("x.".to_string());
+        updated.symbol_map.clear();
+        db.update_broker_profile(&updated).await.unwrap();
+        let stored = db.get_broker_profile("xm").await.unwrap().unwrap();
+        assert_eq!(stored.symbol_prefix, Some("x.".to_string()));
+        assert!(stored.symbol_map.is_empty());
+        assert!(db.update_broker_profile(&profile("missing")).await.is_err());
+
+        db.create_broker_profile(&profile("axi")).await.unwrap();
+        let names: Vec<String> = db
+            .list_broker_profiles()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["axi", "xm"]);
+
+        assert!(db.delete_broker_profile("xm").await.unwrap());
+        assert!(!db.delete_broker_profile("xm").await.unwrap());
+        assert!(db.get_broker_profile("xm").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_members_using_broker_profile() {
+        let db = create_test_db().await;
+        db.create_trade_group("MASTER_001").await.unwrap();
+
+        let mut settings = create_test_slave_settings();
+        settings.broker_profile = Some("xm".to_string());
+        db.add_member("MASTER_001", "SLAVE_001", settings, 0)
+            .await
+            .unwrap();
+        db.add_member("MASTER_001", "SLAVE_002", create_test_slave_settings(), 0)
+            .await
+            .unwrap();
+
+        let members = db.get_members_using_broker_profile("xm").await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].slave_account, "SLAVE_001");
+    }
+}
@@ -0,0 +1,134 @@
+//! Config export/import operations
+//!
+//! Implementation of Database methods for exporting all TradeGroups with
+//! their members as one document and importing such a document back.
+
+use crate::domain::models::{
+    ConfigExport, ConfigImportResult, MemberExport, TradeGroupExport, CONFIG_EXPORT_VERSION,
+};
+use anyhow::Result;
+
+use super::Database;
+
+impl Database {
+    // ============================================================================
+    // Config Export/Import Operations
+    // ============================================================================
+
+    /// Export every TradeGroup with its MasterSettings and members (sorted by ID)
+    pub async fn export_config(&self) -> Result<ConfigExport> {
+        let mut groups = self.list_trade_groups().await?;
+        groups.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut trade_groups = Vec::with_capacity(groups.len());
+        for group in groups {
+            let members = self
+                .get_members(&group.id)
+                .await?
+                .into_iter()
+                .map(|m| MemberExport {
+                    slave_account: m.slave_account,
+                    slave_settings: m.slave_settings,
+                    enabled_flag: m.enabled_flag,
+                })
+                .collect();
+
+            trade_groups.push(TradeGroupExport {
+                id: group.id,
+                master_settings: group.master_settings,
+                members,
+            });
+        }
+
+        Ok(ConfigExport {
+            version: CONFIG_EXPORT_VERSION,
+            exported_at: chrono::Utc::now(),
+            trade_groups,
+        })
+    }
+
+    /// Import an export document in a single transaction
+    ///
+    /// Existing TradeGroups are reported as conflicts unless `overwrite` is set;
+    /// when any conflict remains, nothing is written. Overwritten groups get
+    /// their MasterSettings replaced and their member list replaced by the
+    /// exported one. With `dry_run` the outcome is computed and rolled back.
+    /// The document must already be validated (`ConfigExport::validate`).
+    pub async fn import_config(
+        &self,
+        export: &ConfigExport,
+        overwrite: bool,
+        dry_run: bool,
+    ) -> Result<ConfigImportResult> {
+        with_pool!(&self.pool, |pool| {
+            let mut tx = pool.begin().await?;
+            let mut result = ConfigImportResult {
+                dry_run,
+                ..Default::default()
+            };
+
+            for group in &export.trade_groups {
+                let exists = sqlx::query("SELECT 1 FROM trade_groups WHERE id = $1")
+                    .bind(&group.id)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    .is_some();
+
+                if !exists {
+                    result.created.push(group.id.clone());
+                } else if overwrite {
+                    result.updated.push(group.id.clone());
+                } else {
+                    result.conflicts.push(group.id.clone());
+                }
+            }
+
+            if dry_run || !result.conflicts.is_empty() {
+                tx.rollback().await?;
+                return Ok(result);
+            }
+
+            for group in &export.trade_groups {
+                let settings_json = serde_json::to_string(&group.master_settings)?;
+                sqlx::query(
+                    "INSERT INTO trade_groups (id, master_settings) VALUES ($1, $2)
+                     ON CONFLICT(id) DO UPDATE SET
+                        master_settings = excluded.master_settings,
+                        updated_at = CURRENT_TIMESTAMP",
+                )
+                .bind(&group.id)
+                .bind(&settings_json)
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query("DELETE FROM trade_group_members WHERE trade_group_id = $1")
+                    .bind(&group.id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                for member in &group.members {
+                    let member_json = serde_json::to_string(&member.slave_settings)?;
+                    // Runtime status starts at DISABLED; the status engine recalculates it
+                    sqlx::query(
+                        "INSERT INTO trade_group_members (
+                            trade_group_id,
+                            slave_account,
+                            slave_settings,
+                            status,
+                            enabled_flag
+                        ) VALUES ($1, $2, $3, 0, $4)",
+                    )
+                    .bind(&group.id)
+                    .bind(&member.slave_account)
+                    .bind(&member_json)
+                    .bind(if member.enabled_flag { 1 } else { 0 })
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+
+            tx.commit().await?;
+            Ok(result)
+        })
+    }
+}
@@ -3,9 +3,75 @@
 //! Implementation of Database methods for managing global settings.
 //! Note: VictoriaLogs settings have been moved to config.toml (no longer stored in DB).
 
+use crate::domain::models::GlobalSettings;
+use anyhow::Result;
+
 use super::Database;
 
+/// global_settings key holding the serialized `GlobalSettings`
+const GENERAL_KEY: &str = "general";
+
 impl Database {
-    // Global settings operations can be added here in the future
-    // VictoriaLogs settings are now managed via config.toml
+    /// Get relay-wide settings (defaults when never saved)
+    pub async fn get_global_settings(&self) -> Result<GlobalSettings> {
+        let value: Option<String> = with_pool!(&self.pool, |pool| {
+            sqlx::query_scalar("SELECT value FROM global_settings WHERE key = $1")
+                .bind(GENERAL_KEY)
+                .fetch_optional(pool)
+                .await?
+        });
+
+        match value {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(GlobalSettings::default()),
+        }
+    }
+
+    /// Save relay-wide settings
+    pub async fn update_global_settings(&self, settings: &GlobalSettings) -> Result<()> {
+        let json = serde_json::to_string(settings)?;
+        with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "INSERT INTO global_settings (key, value) VALUES ($1, $2)
+                 ON CONFLICT (key) DO UPDATE
+                 SET value = excluded.value, updated_at = CURRENT_TIMESTAMP",
+            )
+            .bind(GENERAL_KEY)
+            .bind(&json)
+            .execute(pool)
+            .await?;
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::outbound::persistence::test_helpers::create_test_db;
+    use crate::domain::models::{GlobalSettings, SlaveSettings, TimeoutAction};
+
+    #[tokio::test]
+    async fn test_global_settings_default_and_update() {
+        let db = create_test_db().await;
+        assert!(
+            !db.get_global_settings()
+                .await
+                .unwrap()
+                .auto_enable_on_connect
+        );
+
+        let settings = GlobalSettings {
+            auto_enable_on_connect: true,
+            max_lot_multiplier: Some(4.0),
+            blocked_symbols: vec!["USDTRY".to_string(), "*ZAR".to_string()],
+            timeout_action: TimeoutAction::DisableMember,
+            default_slave_settings: Some(SlaveSettings {
+                lot_multiplier: Some(2.0),
+                ..SlaveSettings::default()
+            }),
+        };
+        db.update_global_settings(&settings).await.unwrap();
+        db.update_global_settings(&settings).await.unwrap(); // upsert
+        assert_eq!(db.get_global_settings().await.unwrap(), settings);
+    }
 }
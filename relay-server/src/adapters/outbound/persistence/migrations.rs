@@ -0,0 +1,488 @@
+//! Versioned schema migrations
+//!
+//! Each migration runs once, inside a transaction, and its version is recorded
+//! in `schema_migrations`. New schema changes are appended to `MIGRATIONS` with
+//! the next version number; applied migrations must never be edited.
+//!
+//! A migration is written once when SQLite and PostgreSQL agree on the syntax,
+//! and per backend where they do not (e.g. `AUTOINCREMENT` vs `SERIAL`).
+
+use anyhow::{Context, Result};
+use sqlx::Executor;
+
+use super::DbPool;
+
+/// SQL for one migration
+pub(crate) enum MigrationSql {
+    /// Runs unchanged on every backend
+    Shared(&'static str),
+    /// Backend-specific variants
+    PerBackend {
+        sqlite: &'static str,
+        postgres: &'static str,
+    },
+    /// `ALTER TABLE ... ADD COLUMN`, skipped when the column already exists
+    /// (databases that gained it before versioning).
+    /// `backfill` runs in the same transaction, only when the column was added.
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+        backfill: Option<&'static str>,
+    },
+}
+
+impl MigrationSql {
+    /// Statement to run on this backend, None when there is nothing to do
+    async fn statement(&self, pool: &DbPool) -> Result<Option<String>> {
+        Ok(match self {
+            Self::AddColumn {
+                table,
+                column,
+                definition,
+                backfill,
+            } => (!column_exists(pool, table, column).await?).then(|| {
+                format!(
+                    "ALTER TABLE {} ADD COLUMN {} {};\n{}",
+                    table,
+                    column,
+                    definition,
+                    backfill.unwrap_or_default()
+                )
+            }),
+            _ => Some(self.for_backend(is_postgres(pool)).to_string()),
+        })
+    }
+
+    /// SQL that needs no database lookup (everything but `AddColumn`)
+    fn for_backend(&self, postgres: bool) -> &'static str {
+        match self {
+            Self::Shared(sql) => sql,
+            Self::PerBackend { sqlite, .. } if !postgres => sqlite,
+            Self::PerBackend { postgres, .. } => postgres,
+            Self::AddColumn { .. } => unreachable!("AddColumn SQL depends on the schema"),
+        }
+    }
+}
+
+/// A single schema change
+pub(crate) struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    pub up_sql: MigrationSql,
+}
+
+/// All migrations in ascending version order
+pub(crate) const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema",
+        up_sql: MigrationSql::PerBackend {
+            // The schema before versioning, with IF NOT EXISTS so those databases are
+            // adopted as-is. Columns the old startup code added to existing tables
+            // (`status`, `enabled_flag`), the index on `status` and later additions go
+            // in their own migrations so adopted databases receive them too.
+            sqlite: r#"
+            CREATE TABLE IF NOT EXISTS trade_groups (
+                id TEXT PRIMARY KEY,
+                master_settings TEXT NOT NULL DEFAULT '{}',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS trade_group_members (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trade_group_id TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                slave_settings TEXT NOT NULL DEFAULT '{}',
+                status INTEGER NOT NULL DEFAULT 0,
+                enabled_flag INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE (trade_group_id, slave_account),
+                FOREIGN KEY (trade_group_id) REFERENCES trade_groups(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_trade_group_members_slave
+                ON trade_group_members(slave_account);
+
+            CREATE TABLE IF NOT EXISTS global_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+        "#,
+            // Timestamps stay TEXT and 64-bit columns are BIGINT so rows decode
+            // into the same Rust types as on SQLite
+            postgres: r#"
+            CREATE TABLE IF NOT EXISTS trade_groups (
+                id TEXT PRIMARY KEY,
+                master_settings TEXT NOT NULL DEFAULT '{}',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS trade_group_members (
+                id SERIAL PRIMARY KEY,
+                trade_group_id TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                slave_settings TEXT NOT NULL DEFAULT '{}',
+                status INTEGER NOT NULL DEFAULT 0,
+                enabled_flag INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE (trade_group_id, slave_account),
+                FOREIGN KEY (trade_group_id) REFERENCES trade_groups(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_trade_group_members_slave
+                ON trade_group_members(slave_account);
+
+            CREATE TABLE IF NOT EXISTS global_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+        "#,
+        },
+    },
+    Migration {
+        version: 2,
+        description: "member explicitly_disabled flag",
+        up_sql: MigrationSql::Shared(
+            "ALTER TABLE trade_group_members
+                ADD COLUMN explicitly_disabled INTEGER NOT NULL DEFAULT 0;",
+        ),
+    },
+    Migration {
+        version: 3,
+        description: "broker profiles",
+        up_sql: MigrationSql::PerBackend {
+            sqlite: r#"
+            CREATE TABLE broker_profiles (
+                name TEXT PRIMARY KEY,
+                symbol_prefix TEXT,
+                symbol_suffix TEXT,
+                symbol_map TEXT NOT NULL DEFAULT '[]',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+        "#,
+            postgres: r#"
+            CREATE TABLE broker_profiles (
+                name TEXT PRIMARY KEY,
+                symbol_prefix TEXT,
+                symbol_suffix TEXT,
+                symbol_map TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            );
+        "#,
+        },
+    },
+    Migration {
+        version: 4,
+        description: "member acknowledged_warnings",
+        up_sql: MigrationSql::AddColumn {
+            table: "trade_group_members",
+            column: "acknowledged_warnings",
+            definition: "TEXT NOT NULL DEFAULT '[]'",
+            backfill: None,
+        },
+    },
+    Migration {
+        version: 5,
+        description: "trade history",
+        up_sql: MigrationSql::PerBackend {
+            sqlite: r#"
+            CREATE TABLE trade_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                master_account TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                ticket INTEGER NOT NULL,
+                action TEXT NOT NULL,
+                symbol TEXT,
+                lots REAL,
+                timestamp TEXT NOT NULL,
+                copied INTEGER NOT NULL,
+                filter_reason TEXT
+            );
+
+            CREATE INDEX idx_trade_history_master_time
+                ON trade_history(master_account, timestamp);
+
+            CREATE INDEX idx_trade_history_slave_time
+                ON trade_history(slave_account, timestamp);
+        "#,
+            postgres: r#"
+            CREATE TABLE trade_history (
+                id BIGSERIAL PRIMARY KEY,
+                master_account TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                ticket BIGINT NOT NULL,
+                action TEXT NOT NULL,
+                symbol TEXT,
+                lots DOUBLE PRECISION,
+                timestamp TEXT NOT NULL,
+                copied INTEGER NOT NULL,
+                filter_reason TEXT
+            );
+
+            CREATE INDEX idx_trade_history_master_time
+                ON trade_history(master_account, timestamp);
+
+            CREATE INDEX idx_trade_history_slave_time
+                ON trade_history(slave_account, timestamp);
+        "#,
+        },
+    },
+    Migration {
+        version: 6,
+        description: "member status column",
+        up_sql: MigrationSql::AddColumn {
+            table: "trade_group_members",
+            column: "status",
+            definition: "INTEGER NOT NULL DEFAULT 0",
+            backfill: None,
+        },
+    },
+    Migration {
+        version: 7,
+        description: "member enabled_flag column",
+        // Members of databases older than the flag were enabled iff they had a status
+        up_sql: MigrationSql::AddColumn {
+            table: "trade_group_members",
+            column: "enabled_flag",
+            definition: "INTEGER NOT NULL DEFAULT 0",
+            backfill: Some(
+                "UPDATE trade_group_members
+                    SET enabled_flag = CASE WHEN status > 0 THEN 1 ELSE 0 END;",
+            ),
+        },
+    },
+    Migration {
+        version: 8,
+        description: "member status index",
+        // After version 6: the column may not exist before it
+        up_sql: MigrationSql::Shared(
+            "CREATE INDEX IF NOT EXISTS idx_trade_group_members_status
+                ON trade_group_members(status);",
+        ),
+    },
+];
+
+const SCHEMA_MIGRATIONS_SQL: MigrationSql = MigrationSql::PerBackend {
+    sqlite: r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+    "#,
+    postgres: r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version BIGINT PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )
+    "#,
+};
+
+/// Version the schema reaches once every migration has been applied
+pub(crate) fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Highest applied version (0 for an empty database)
+pub(crate) async fn current_version(pool: &DbPool) -> Result<i64> {
+    ensure_migrations_table(pool).await?;
+    let version: Option<i64> = with_pool!(pool, |pool| {
+        sqlx::query_scalar("SELECT MAX(version) FROM schema_migrations")
+            .fetch_one(pool)
+            .await?
+    });
+    Ok(version.unwrap_or(0))
+}
+
+/// Apply pending migrations in order. Returns how many were applied.
+pub(crate) async fn run(pool: &DbPool) -> Result<usize> {
+    apply(pool, MIGRATIONS).await
+}
+
+async fn apply(pool: &DbPool, migrations: &[Migration]) -> Result<usize> {
+    let current = current_version(pool).await?;
+    let mut applied = 0;
+
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        // An empty statement only records the version (nothing left to change)
+        let sql = migration.up_sql.statement(pool).await?.unwrap_or_default();
+        let result: Result<()> = with_pool!(pool, |pool| {
+            let mut tx = pool.begin().await?;
+            // Via Executor (boxed Send future): `RawSql::execute` is not Send under
+            // tokio::spawn, which the degraded-startup recovery task needs
+            if let Err(e) = (&mut *tx).execute(sqlx::raw_sql(&sql)).await {
+                tx.rollback().await?;
+                Err(e.into())
+            } else {
+                sqlx::query("INSERT INTO schema_migrations (version, description) VALUES ($1, $2)")
+                    .bind(migration.version)
+                    .bind(migration.description)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+                Ok(())
+            }
+        });
+        result.with_context(|| {
+            format!(
+                "migration {} ({}) failed",
+                migration.version, migration.description
+            )
+        })?;
+
+        tracing::info!(
+            version = migration.version,
+            description = migration.description,
+            "Applied database migration"
+        );
+        applied += 1;
+    }
+
+    Ok(applied)
+}
+
+async fn ensure_migrations_table(pool: &DbPool) -> Result<()> {
+    let sql = SCHEMA_MIGRATIONS_SQL.for_backend(is_postgres(pool));
+    with_pool!(pool, |pool| {
+        sqlx::query(sql).execute(pool).await?;
+    });
+    Ok(())
+}
+
+/// Whether `table` already has `column`
+async fn column_exists(pool: &DbPool, table: &str, column: &str) -> Result<bool> {
+    let sql = if is_postgres(pool) {
+        "SELECT COUNT(*) FROM information_schema.columns WHERE table_name = $1 AND column_name = $2"
+    } else {
+        "SELECT COUNT(*) FROM pragma_table_info($1) WHERE name = $2"
+    };
+    let count: i64 = with_pool!(pool, |pool| {
+        sqlx::query_scalar(sql)
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await?
+    });
+    Ok(count > 0)
+}
+
+fn is_postgres(pool: &DbPool) -> bool {
+    match pool {
+        DbPool::Sqlite(_) => false,
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_strictly_increasing() {
+        assert!(MIGRATIONS.first().is_some_and(|m| m.version == 1));
+        assert!(MIGRATIONS
+            .windows(2)
+            .all(|pair| pair[0].version < pair[1].version));
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_is_not_recorded() {
+        let sqlite = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let pool = DbPool::Sqlite(sqlite.clone());
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "ok",
+                up_sql: MigrationSql::Shared("CREATE TABLE first (id INTEGER);"),
+            },
+            Migration {
+                version: 2,
+                description: "broken",
+                up_sql: MigrationSql::Shared(
+                    "CREATE TABLE second (id INTEGER); SELECT * FROM missing;",
+                ),
+            },
+        ];
+
+        assert!(apply(&pool, &migrations).await.is_err());
+        assert_eq!(current_version(&pool).await.unwrap(), 1);
+
+        // The rollback also discards the statements of the failed migration that did succeed
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type='table' AND name IN ('first', 'second')",
+        )
+        .fetch_all(&sqlite)
+        .await
+        .unwrap();
+        assert_eq!(tables, vec!["first".to_string()]);
+
+        // Only the pending migration is retried
+        let fixed = [
+            Migration {
+                version: 1,
+                description: "ok",
+                up_sql: MigrationSql::Shared("CREATE TABLE first (id INTEGER);"),
+            },
+            Migration {
+                version: 2,
+                description: "fixed",
+                up_sql: MigrationSql::Shared("CREATE TABLE second (id INTEGER);"),
+            },
+        ];
+        assert_eq!(apply(&pool, &fixed).await.unwrap(), 1);
+        assert_eq!(current_version(&pool).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_column_skipped_when_column_exists() {
+        let sqlite = sqlx::sqlite::SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let pool = DbPool::Sqlite(sqlite.clone());
+        sqlx::raw_sql(
+            "CREATE TABLE has_it (id INTEGER, extra TEXT); CREATE TABLE lacks_it (id INTEGER);
+             INSERT INTO has_it VALUES (1, 'kept'); INSERT INTO lacks_it VALUES (1);",
+        )
+        .execute(&sqlite)
+        .await
+        .unwrap();
+        let add_extra = |version, table, backfill| Migration {
+            version,
+            description: "extra column",
+            up_sql: MigrationSql::AddColumn {
+                table,
+                column: "extra",
+                definition: "TEXT NOT NULL DEFAULT ''",
+                backfill: Some(backfill),
+            },
+        };
+
+        let migrations = [
+            add_extra(1, "has_it", "UPDATE has_it SET extra = 'filled';"),
+            add_extra(2, "lacks_it", "UPDATE lacks_it SET extra = 'filled';"),
+        ];
+        assert_eq!(apply(&pool, &migrations).await.unwrap(), 2);
+        assert_eq!(current_version(&pool).await.unwrap(), 2);
+        // The backfill only runs where the column was added
+        for (table, expected) in [("has_it", "kept"), ("lacks_it", "filled")] {
+            let extra: String = sqlx::query_scalar(&format!("SELECT extra FROM {}", table))
+                .fetch_one(&sqlite)
+                .await
+                .unwrap();
+            assert_eq!(extra, expected);
+        }
+    }
+}
@@ -2,15 +2,40 @@
 //!
 //! This module provides the main Database struct and initialization logic,
 //! with CRUD operations split into separate submodules.
+//!
+//! The backend is chosen by the database URL: `sqlite:` by default, or
+//! `postgres://` when built with the `postgres` feature. Queries are shared
+//! between backends and use `$N` placeholders, which both accept.
 
 use anyhow::Result;
-use sqlx::{sqlite::SqlitePool, Row};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqliteSynchronous};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Run the same query code against whichever backend is active.
+///
+/// The body is compiled once per backend with `$pool` bound to that backend's
+/// pool, so row decoding and bound values must type-check for both.
+macro_rules! with_pool {
+    ($db_pool:expr, |$pool:ident| $body:expr) => {
+        match $db_pool {
+            $crate::adapters::outbound::persistence::DbPool::Sqlite($pool) => $body,
+            #[cfg(feature = "postgres")]
+            $crate::adapters::outbound::persistence::DbPool::Postgres($pool) => $body,
+        }
+    };
+}
 
 // Submodule declarations
+mod backup;
+mod broker_profiles;
 mod config_distribution;
+mod config_transfer;
 mod global_settings;
+mod migrations;
 mod trade_group_members;
 mod trade_groups;
+mod trade_history;
 
 // Re-export all public items
 
@@ -18,152 +43,186 @@ mod trade_groups;
 #[cfg(test)]
 pub(crate) mod test_helpers;
 
-pub struct Database {
-    pool: SqlitePool,
+/// SQLite connection tuning (ignored on PostgreSQL)
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteTuning {
+    /// Use `journal_mode=WAL` with `synchronous=NORMAL` so readers do not block
+    /// the writer (in-memory databases keep their memory journal)
+    pub wal: bool,
+    /// How long a connection waits on a locked database before `database is locked`
+    pub busy_timeout: Duration,
 }
 
-#[allow(dead_code)]
-impl Database {
-    /// Get a reference to the underlying connection pool
-    pub fn pool(&self) -> &SqlitePool {
-        &self.pool
+impl Default for SqliteTuning {
+    fn default() -> Self {
+        Self {
+            wal: true,
+            busy_timeout: Duration::from_millis(5000),
+        }
     }
+}
 
-    pub async fn new(database_url: &str) -> Result<Self> {
-        let pool = SqlitePool::connect(database_url).await?;
-
-        // Drop old connections table (clean migration, no data preservation)
-        sqlx::query("DROP TABLE IF EXISTS connections")
-            .execute(&pool)
-            .await?;
+/// Connection pool of the configured backend
+pub(crate) enum DbPool {
+    Sqlite(SqlitePool),
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::postgres::PgPool),
+}
 
-        // Create trade_groups table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS trade_groups (
-                id TEXT PRIMARY KEY,
-                master_settings TEXT NOT NULL DEFAULT '{}',
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            )
-            "#,
-        )
-        .execute(&pool)
-        .await?;
+impl DbPool {
+    async fn connect(database_url: &str, tuning: &SqliteTuning) -> Result<Self> {
+        if is_postgres_url(database_url) {
+            #[cfg(feature = "postgres")]
+            return Ok(Self::Postgres(
+                sqlx::postgres::PgPool::connect(database_url).await?,
+            ));
+            #[cfg(not(feature = "postgres"))]
+            anyhow::bail!("PostgreSQL database URL requires building with the `postgres` feature");
+        }
+        let options = sqlite_options(database_url, tuning)?;
+        Ok(Self::Sqlite(SqlitePool::connect_with(options).await?))
+    }
 
-        // Create trade_group_members table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS trade_group_members (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                trade_group_id TEXT NOT NULL,
-                slave_account TEXT NOT NULL,
-                slave_settings TEXT NOT NULL DEFAULT '{}',
-                status INTEGER NOT NULL DEFAULT 0,
-                enabled_flag INTEGER NOT NULL DEFAULT 0,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                UNIQUE (trade_group_id, slave_account),
-                FOREIGN KEY (trade_group_id) REFERENCES trade_groups(id) ON DELETE CASCADE
-            )
-            "#,
-        )
-        .execute(&pool)
-        .await?;
-
-        let enabled_added = Self::ensure_column(
-            &pool,
-            "trade_group_members",
-            "enabled_flag",
-            "INTEGER NOT NULL DEFAULT 0",
-        )
-        .await?;
-
-        if enabled_added {
-            sqlx::query(
-                "UPDATE trade_group_members
-                 SET enabled_flag = CASE WHEN status > 0 THEN 1 ELSE 0 END",
-            )
-            .execute(&pool)
-            .await?;
+    /// Pool that opens connections on first use, so an unreachable database
+    /// fails each query instead of construction
+    fn connect_lazy(database_url: &str, tuning: &SqliteTuning) -> Result<Self> {
+        if is_postgres_url(database_url) {
+            #[cfg(feature = "postgres")]
+            return Ok(Self::Postgres(sqlx::postgres::PgPool::connect_lazy(
+                database_url,
+            )?));
+            #[cfg(not(feature = "postgres"))]
+            anyhow::bail!("PostgreSQL database URL requires building with the `postgres` feature");
         }
+        Ok(Self::Sqlite(SqlitePool::connect_lazy_with(sqlite_options(
+            database_url,
+            tuning,
+        )?)))
+    }
+}
 
-        let runtime_added = Self::ensure_column(
-            &pool,
-            "trade_group_members",
-            "status",
-            "INTEGER NOT NULL DEFAULT 0",
-        )
-        .await?;
-
-        if runtime_added {
-            sqlx::query(
-                "UPDATE trade_group_members
-                 SET status = status",
-            )
-            .execute(&pool)
-            .await?;
+fn sqlite_options(database_url: &str, tuning: &SqliteTuning) -> Result<SqliteConnectOptions> {
+    let mut options =
+        SqliteConnectOptions::from_str(database_url)?.busy_timeout(tuning.busy_timeout);
+    if tuning.wal {
+        options = options
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal);
+    }
+    Ok(options)
+}
+
+fn is_postgres_url(database_url: &str) -> bool {
+    database_url.starts_with("postgres://") || database_url.starts_with("postgresql://")
+}
+
+/// Upper bound on the delay between startup connection attempts
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+pub struct Database {
+    pool: DbPool,
+}
+
+#[allow(dead_code)]
+impl Database {
+    /// Get the underlying pool when running on SQLite
+    pub fn sqlite_pool(&self) -> Option<&SqlitePool> {
+        match &self.pool {
+            DbPool::Sqlite(pool) => Some(pool),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(_) => None,
         }
+    }
 
-        // Create indexes for performance
-        sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_trade_group_members_slave
-             ON trade_group_members(slave_account)",
-        )
-        .execute(&pool)
-        .await?;
+    /// Get the underlying pool when running on PostgreSQL
+    #[cfg(feature = "postgres")]
+    pub fn postgres_pool(&self) -> Option<&sqlx::postgres::PgPool> {
+        match &self.pool {
+            DbPool::Postgres(pool) => Some(pool),
+            DbPool::Sqlite(_) => None,
+        }
+    }
 
-        sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_trade_group_members_status
-             ON trade_group_members(status)",
-        )
-        .execute(&pool)
-        .await?;
+    /// Trivial round-trip used by the readiness probe
+    pub async fn ping(&self) -> Result<()> {
+        with_pool!(&self.pool, |pool| {
+            sqlx::query("SELECT 1").execute(pool).await?;
+        });
+        Ok(())
+    }
 
-        // Create global_settings table for system-wide configuration
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS global_settings (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            )
-            "#,
-        )
-        .execute(&pool)
-        .await?;
+    pub async fn new(database_url: &str) -> Result<Self> {
+        Self::with_tuning(database_url, &SqliteTuning::default()).await
+    }
 
+    /// Connect with explicit SQLite tuning (`[database]` in config.toml)
+    pub async fn with_tuning(database_url: &str, tuning: &SqliteTuning) -> Result<Self> {
+        let pool = DbPool::connect(database_url, tuning).await?;
+        migrations::run(&pool).await?;
         Ok(Self { pool })
     }
-}
 
-impl Database {
-    async fn ensure_column(
-        pool: &SqlitePool,
-        table: &str,
-        column: &str,
-        definition: &str,
-    ) -> Result<bool> {
-        if Self::column_exists(pool, table, column).await? {
-            return Ok(false);
+    /// Connect like `with_tuning`, retrying up to `attempts` times with a delay
+    /// starting at `backoff` and doubling after each failure (capped at 30s).
+    /// Returns the last error once every attempt failed.
+    pub async fn connect_with_retry(
+        database_url: &str,
+        tuning: &SqliteTuning,
+        attempts: u32,
+        backoff: Duration,
+    ) -> Result<Self> {
+        let attempts = attempts.max(1);
+        let mut delay = backoff;
+        let mut attempt = 1;
+        loop {
+            match Self::with_tuning(database_url, tuning).await {
+                Ok(db) => return Ok(db),
+                Err(e) if attempt < attempts => {
+                    tracing::warn!(
+                        attempt,
+                        attempts,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Failed to open database: {}",
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_CONNECT_BACKOFF);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
         }
+    }
+
+    /// Database whose pool connects on first use and whose schema is not
+    /// migrated yet: every query fails until the backend becomes reachable and
+    /// `run_migrations` succeeds (degraded startup)
+    pub fn lazy(database_url: &str, tuning: &SqliteTuning) -> Result<Self> {
+        Ok(Self {
+            pool: DbPool::connect_lazy(database_url, tuning)?,
+        })
+    }
+
+    /// Apply any pending schema migrations. Returns how many were applied.
+    pub async fn run_migrations(&self) -> Result<usize> {
+        migrations::run(&self.pool).await
+    }
 
-        let sql = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
-        sqlx::query(&sql).execute(pool).await?;
-        Ok(true)
+    /// Highest schema version recorded in `schema_migrations`
+    pub async fn schema_version(&self) -> Result<i64> {
+        migrations::current_version(&self.pool).await
     }
 
-    async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> Result<bool> {
-        let pragma = format!("PRAGMA table_info({})", table);
-        let rows = sqlx::query(&pragma).fetch_all(pool).await?;
-        Ok(rows
-            .iter()
-            .any(|row| row.get::<String, _>("name") == column))
+    /// Schema version this build migrates to
+    pub fn latest_schema_version() -> i64 {
+        migrations::latest_version()
     }
 }
 
 // Adapter implementation for Outbound Port
-use crate::domain::models::{SlaveConfigWithMaster, TradeGroup, TradeGroupMember};
+use crate::domain::models::{
+    BrokerProfile, GlobalSettings, SlaveConfigWithMaster, TradeGroup, TradeGroupMember,
+};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -200,4 +259,244 @@ impl crate::ports::TradeGroupRepository for Database {
     async fn get_masters_for_slave(&self, slave_account: &str) -> anyhow::Result<Vec<String>> {
         self.get_masters_for_slave(slave_account).await
     }
+
+    async fn get_global_settings(&self) -> anyhow::Result<GlobalSettings> {
+        self.get_global_settings().await
+    }
+
+    async fn auto_enable_member(&self, master_id: &str, slave_id: &str) -> anyhow::Result<bool> {
+        self.auto_enable_member(master_id, slave_id).await
+    }
+
+    async fn get_broker_profile(&self, name: &str) -> anyhow::Result<Option<BrokerProfile>> {
+        self.get_broker_profile(name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_postgres_url() {
+        assert!(is_postgres_url("postgres://user@localhost/sankey"));
+        assert!(is_postgres_url("postgresql://localhost/sankey"));
+        assert!(!is_postgres_url("sqlite://sankey_copier.db?mode=rwc"));
+        assert!(!is_postgres_url("sqlite::memory:"));
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    #[tokio::test]
+    async fn test_postgres_url_requires_feature() {
+        let err = Database::new("postgres://localhost/sankey")
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("`postgres` feature"));
+    }
+
+    #[tokio::test]
+    async fn test_opens_database_created_before_versioning() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("legacy.db").display()
+        );
+
+        // Schema and data as written by the relay before schema_migrations existed
+        let legacy = SqlitePool::connect(&url).await.unwrap();
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE trade_groups (
+                id TEXT PRIMARY KEY,
+                master_settings TEXT NOT NULL DEFAULT '{}',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE trade_group_members (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trade_group_id TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                slave_settings TEXT NOT NULL DEFAULT '{}',
+                status INTEGER NOT NULL DEFAULT 0,
+                enabled_flag INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE (trade_group_id, slave_account),
+                FOREIGN KEY (trade_group_id) REFERENCES trade_groups(id) ON DELETE CASCADE
+            );
+            CREATE INDEX idx_trade_group_members_slave ON trade_group_members(slave_account);
+            CREATE INDEX idx_trade_group_members_status ON trade_group_members(status);
+            CREATE TABLE global_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO trade_groups (id) VALUES ('MASTER_001');
+            INSERT INTO trade_group_members (trade_group_id, slave_account, status, enabled_flag)
+                VALUES ('MASTER_001', 'SLAVE_001', 2, 1);
+            "#,
+        )
+        .execute(&legacy)
+        .await
+        .unwrap();
+        legacy.close().await;
+
+        let db = Database::new(&url).await.unwrap();
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            Database::latest_schema_version()
+        );
+
+        let members = db.get_members("MASTER_001").await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].slave_account, "SLAVE_001");
+        assert!(members[0].enabled_flag);
+        assert!(members[0].acknowledged_warnings.is_empty());
+        assert!(db
+            .get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(db
+            .query_trade_history(&crate::domain::models::TradeHistoryQuery::default())
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+    }
+
+    /// Database with the trade_group_members columns `member_columns`, as written by
+    /// releases older than the `status`/`enabled_flag` columns. Returns it with its URL.
+    async fn open_legacy_members(
+        dir: &tempfile::TempDir,
+        member_columns: &str,
+        rows: &str,
+    ) -> (Database, String) {
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("legacy.db").display()
+        );
+        let legacy = SqlitePool::connect(&url).await.unwrap();
+        sqlx::raw_sql(&format!(
+            r#"
+            CREATE TABLE connections (id TEXT PRIMARY KEY);
+            CREATE TABLE trade_groups (
+                id TEXT PRIMARY KEY,
+                master_settings TEXT NOT NULL DEFAULT '{{}}',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE trade_group_members (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trade_group_id TEXT NOT NULL,
+                slave_account TEXT NOT NULL,
+                slave_settings TEXT NOT NULL DEFAULT '{{}}',
+                {member_columns}
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE (trade_group_id, slave_account),
+                FOREIGN KEY (trade_group_id) REFERENCES trade_groups(id) ON DELETE CASCADE
+            );
+            INSERT INTO trade_groups (id) VALUES ('MASTER_001');
+            {rows}
+            "#
+        ))
+        .execute(&legacy)
+        .await
+        .unwrap();
+        legacy.close().await;
+
+        let db = Database::new(&url).await.unwrap();
+        assert_eq!(
+            db.schema_version().await.unwrap(),
+            Database::latest_schema_version()
+        );
+        (db, url)
+    }
+
+    #[tokio::test]
+    async fn test_legacy_members_without_enabled_flag_are_backfilled() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, url) = open_legacy_members(
+            &dir,
+            "status INTEGER NOT NULL DEFAULT 0,",
+            "INSERT INTO trade_group_members (trade_group_id, slave_account, status)
+                VALUES ('MASTER_001', 'SLAVE_ON', 2), ('MASTER_001', 'SLAVE_OFF', 0);",
+        )
+        .await;
+
+        // Members that had a status were enabled
+        for (slave, enabled) in [("SLAVE_ON", true), ("SLAVE_OFF", false)] {
+            let member = db.get_member("MASTER_001", slave).await.unwrap().unwrap();
+            assert_eq!(member.enabled_flag, enabled, "{}", slave);
+        }
+
+        // The obsolete connections table is left alone
+        let pool = SqlitePool::connect(&url).await.unwrap();
+        let tables: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'connections'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(tables, 1);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_members_without_status_gain_column_and_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let (db, url) = open_legacy_members(
+            &dir,
+            "enabled_flag INTEGER NOT NULL DEFAULT 0,",
+            "INSERT INTO trade_group_members (trade_group_id, slave_account, enabled_flag)
+                VALUES ('MASTER_001', 'SLAVE_001', 1);",
+        )
+        .await;
+
+        let members = db.get_members("MASTER_001").await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].status, 0);
+        assert!(members[0].enabled_flag);
+
+        let pool = SqlitePool::connect(&url).await.unwrap();
+        let indexes: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master
+             WHERE type = 'index' AND name = 'idx_trade_group_members_status'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(indexes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writes_succeed_with_wal() {
+        use crate::domain::models::MasterSettings;
+        use test_helpers::create_test_slave_settings;
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("wal.db").display());
+        let db = Database::with_tuning(&url, &SqliteTuning::default())
+            .await
+            .unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(db.sqlite_pool().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+
+        db.create_trade_group("MASTER_001").await.unwrap();
+        db.add_member("MASTER_001", "SLAVE_001", create_test_slave_settings(), 0)
+            .await
+            .unwrap();
+
+        let (member, master) = tokio::join!(
+            db.update_member_settings("MASTER_001", "SLAVE_001", create_test_slave_settings()),
+            db.update_master_settings("MASTER_001", MasterSettings::default()),
+        );
+        member.unwrap();
+        master.unwrap();
+    }
 }
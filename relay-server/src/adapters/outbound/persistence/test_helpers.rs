@@ -14,15 +14,19 @@ pub(crate) fn create_test_slave_settings() -> SlaveSettings {
         symbol_suffix: None,
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![],
         filters: TradeFilters {
             allowed_symbols: None,
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: crate::domain::models::SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -31,5 +35,33 @@ pub(crate) fn create_test_slave_settings() -> SlaveSettings {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: crate::domain::models::RoundingMode::Nearest,
+        merge_strategy: crate::domain::models::MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: crate::domain::models::CopyDirection::Both,
     }
 }
@@ -3,9 +3,9 @@
 //! Implementation of Database methods for managing TradeGroupMembers,
 //! which represent Slave EA accounts and their relationship to Master accounts.
 
-use crate::domain::models::{SlaveSettings, TradeGroupMember};
+use crate::domain::models::{MemberListFilter, SlaveSettings, TradeGroupMember, WarningCode};
 use anyhow::Result;
-use sqlx::Row;
+use sqlx::{QueryBuilder, Row};
 
 use super::Database;
 
@@ -32,106 +32,198 @@ impl Database {
 
         let enabled_flag = if status > 0 { 1 } else { 0 };
 
-        sqlx::query(
-            "INSERT INTO trade_group_members (
-                trade_group_id,
-                slave_account,
-                slave_settings,
-                status,
-                enabled_flag,
-                status
-            ) VALUES (?, ?, ?, ?, ?, ?)",
-        )
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .bind(&settings_json)
-        .bind(status)
-        .bind(enabled_flag)
-        .bind(status)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
+        with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "INSERT INTO trade_group_members (
+                    trade_group_id,
+                    slave_account,
+                    slave_settings,
+                    status,
+                    enabled_flag
+                ) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .bind(&settings_json)
+            .bind(status)
+            .bind(enabled_flag)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
     }
 
     /// Get all members for a TradeGroup
     pub async fn get_members(&self, trade_group_id: &str) -> Result<Vec<TradeGroupMember>> {
-        let rows = sqlx::query(
-            "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, created_at, updated_at
-             FROM trade_group_members
-             WHERE trade_group_id = ?
-             ORDER BY slave_account"
-        )
-        .bind(trade_group_id)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut members = Vec::new();
-        for row in rows {
-            let id: i32 = row.get("id");
-            let trade_group_id: String = row.get("trade_group_id");
-            let slave_account: String = row.get("slave_account");
-            let settings_json: String = row.get("slave_settings");
-            let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
-            let status: i32 = row.get("status");
-            let enabled_flag: bool = row.get::<i64, _>("enabled_flag") != 0;
-            let status: i32 = row.try_get("status").unwrap_or(status);
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            members.push(TradeGroupMember {
-                id,
-                trade_group_id,
-                slave_account,
-                slave_settings,
-                status,
-                warning_codes: Vec::new(),
-                enabled_flag,
-                created_at,
-                updated_at,
-            });
-        }
-
-        Ok(members)
+        with_pool!(&self.pool, |pool| {
+            let rows = sqlx::query(
+                "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, acknowledged_warnings, created_at, updated_at
+                 FROM trade_group_members
+                 WHERE trade_group_id = $1
+                 ORDER BY slave_account"
+            )
+            .bind(trade_group_id)
+            .fetch_all(pool)
+            .await?;
+
+            let mut members = Vec::new();
+            for row in rows {
+                let id: i32 = row.get("id");
+                let trade_group_id: String = row.get("trade_group_id");
+                let slave_account: String = row.get("slave_account");
+                let settings_json: String = row.get("slave_settings");
+                let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
+                let status: i32 = row.get("status");
+                let enabled_flag: bool = row.get::<i32, _>("enabled_flag") != 0;
+                let status: i32 = row.try_get("status").unwrap_or(status);
+                let acknowledged_json: String = row.get("acknowledged_warnings");
+                let acknowledged_warnings = serde_json::from_str(&acknowledged_json)?;
+                let created_at: String = row.get("created_at");
+                let updated_at: String = row.get("updated_at");
+
+                members.push(TradeGroupMember {
+                    id,
+                    trade_group_id,
+                    slave_account,
+                    slave_settings,
+                    status,
+                    warning_codes: Vec::new(),
+                    acknowledged_warnings,
+                    warnings: Vec::new(),
+                    enabled_flag,
+                    created_at,
+                    updated_at,
+                });
+            }
+
+            Ok(members)
+        })
     }
 
     /// Get all members for all TradeGroups (efficiently)
     pub async fn get_all_members(&self) -> Result<Vec<TradeGroupMember>> {
-        let rows = sqlx::query(
-            "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, created_at, updated_at
-             FROM trade_group_members
-             ORDER BY trade_group_id, slave_account"
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut members = Vec::new();
-        for row in rows {
-            let id: i32 = row.get("id");
-            let trade_group_id: String = row.get("trade_group_id");
-            let slave_account: String = row.get("slave_account");
-            let settings_json: String = row.get("slave_settings");
-            let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
-            let status: i32 = row.get("status");
-            let enabled_flag: bool = row.get::<i64, _>("enabled_flag") != 0;
-            // let status: i32 = row.try_get("status").unwrap_or(status); // redundant
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            members.push(TradeGroupMember {
-                id,
-                trade_group_id,
-                slave_account,
-                slave_settings,
-                status,
-                warning_codes: Vec::new(), // Warning codes are runtime only, not in DB for now (except if we persisted them, but typically they are transient or re-evaluated)
-                enabled_flag,
-                created_at,
-                updated_at,
-            });
-        }
-
-        Ok(members)
+        with_pool!(&self.pool, |pool| {
+            let rows = sqlx::query(
+                "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, acknowledged_warnings, created_at, updated_at
+                 FROM trade_group_members
+                 ORDER BY trade_group_id, slave_account"
+            )
+            .fetch_all(pool)
+            .await?;
+
+            let mut members = Vec::new();
+            for row in rows {
+                let id: i32 = row.get("id");
+                let trade_group_id: String = row.get("trade_group_id");
+                let slave_account: String = row.get("slave_account");
+                let settings_json: String = row.get("slave_settings");
+                let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
+                let status: i32 = row.get("status");
+                let enabled_flag: bool = row.get::<i32, _>("enabled_flag") != 0;
+                // let status: i32 = row.try_get("status").unwrap_or(status); // redundant
+                let acknowledged_json: String = row.get("acknowledged_warnings");
+                let acknowledged_warnings = serde_json::from_str(&acknowledged_json)?;
+                let created_at: String = row.get("created_at");
+                let updated_at: String = row.get("updated_at");
+
+                members.push(TradeGroupMember {
+                    id,
+                    trade_group_id,
+                    slave_account,
+                    slave_settings,
+                    status,
+                    warning_codes: Vec::new(), // Warning codes are runtime only, not in DB for now (except if we persisted them, but typically they are transient or re-evaluated)
+                    acknowledged_warnings,
+                    warnings: Vec::new(),
+                    enabled_flag,
+                    created_at,
+                    updated_at,
+                });
+            }
+
+            Ok(members)
+        })
+    }
+
+    /// Get one page of members across all TradeGroups, ordered by Master then Slave
+    ///
+    /// `after` is the (trade_group_id, slave_account) of the previous page's last member.
+    /// Members are joined with their TradeGroup, so members of a deleted Master are skipped.
+    /// The returned bool tells whether more members match after this page.
+    pub async fn get_members_page(
+        &self,
+        filter: &MemberListFilter,
+        after: Option<(&str, &str)>,
+        limit: usize,
+    ) -> Result<(Vec<TradeGroupMember>, bool)> {
+        with_pool!(&self.pool, |pool| {
+            let mut builder = QueryBuilder::new(
+                "SELECT m.id, m.trade_group_id, m.slave_account, m.slave_settings, m.status, m.enabled_flag, m.acknowledged_warnings, m.created_at, m.updated_at
+                 FROM trade_group_members m
+                 INNER JOIN trade_groups tg ON tg.id = m.trade_group_id
+                 WHERE 1 = 1",
+            );
+            if let Some(status) = filter.status {
+                builder.push(" AND m.status = ").push_bind(status);
+            }
+            match filter.connected {
+                Some(true) if filter.online_slaves.is_empty() => {
+                    builder.push(" AND 1 = 0");
+                }
+                Some(connected) if !filter.online_slaves.is_empty() => {
+                    builder.push(if connected {
+                        " AND m.slave_account IN ("
+                    } else {
+                        " AND m.slave_account NOT IN ("
+                    });
+                    let mut accounts = builder.separated(", ");
+                    for account in &filter.online_slaves {
+                        accounts.push_bind(account.clone());
+                    }
+                    builder.push(")");
+                }
+                _ => {}
+            }
+            if let Some((trade_group_id, slave_account)) = after {
+                builder
+                    .push(" AND (m.trade_group_id > ")
+                    .push_bind(trade_group_id.to_string())
+                    .push(" OR (m.trade_group_id = ")
+                    .push_bind(trade_group_id.to_string())
+                    .push(" AND m.slave_account > ")
+                    .push_bind(slave_account.to_string())
+                    .push("))");
+            }
+            builder
+                .push(" ORDER BY m.trade_group_id, m.slave_account LIMIT ")
+                .push_bind(i64::try_from(limit.saturating_add(1)).unwrap_or(i64::MAX));
+
+            let rows = builder.build().fetch_all(pool).await?;
+
+            let mut members = Vec::with_capacity(rows.len());
+            for row in rows {
+                let settings_json: String = row.get("slave_settings");
+                let acknowledged_json: String = row.get("acknowledged_warnings");
+                members.push(TradeGroupMember {
+                    id: row.get("id"),
+                    trade_group_id: row.get("trade_group_id"),
+                    slave_account: row.get("slave_account"),
+                    slave_settings: serde_json::from_str(&settings_json)?,
+                    status: row.get("status"),
+                    warning_codes: Vec::new(),
+                    acknowledged_warnings: serde_json::from_str(&acknowledged_json)?,
+                    warnings: Vec::new(),
+                    enabled_flag: row.get::<i32, _>("enabled_flag") != 0,
+                    created_at: row.get("created_at"),
+                    updated_at: row.get("updated_at"),
+                });
+            }
+
+            let has_more = members.len() > limit;
+            members.truncate(limit);
+            Ok((members, has_more))
+        })
     }
 
     /// Get a specific member
@@ -140,42 +232,48 @@ impl Database {
         trade_group_id: &str,
         slave_account: &str,
     ) -> Result<Option<TradeGroupMember>> {
-        let row = sqlx::query(
-            "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, created_at, updated_at
-             FROM trade_group_members
-             WHERE trade_group_id = ? AND slave_account = ?"
-        )
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(row) = row {
-            let id: i32 = row.get("id");
-            let trade_group_id: String = row.get("trade_group_id");
-            let slave_account: String = row.get("slave_account");
-            let settings_json: String = row.get("slave_settings");
-            let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
-            let status: i32 = row.get("status");
-            let enabled_flag: bool = row.get::<i64, _>("enabled_flag") != 0;
-            let status: i32 = row.try_get("status").unwrap_or(status);
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            Ok(Some(TradeGroupMember {
-                id,
-                trade_group_id,
-                slave_account,
-                slave_settings,
-                status,
-                warning_codes: Vec::new(),
-                enabled_flag,
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+        with_pool!(&self.pool, |pool| {
+            let row = sqlx::query(
+                "SELECT id, trade_group_id, slave_account, slave_settings, status, enabled_flag, acknowledged_warnings, created_at, updated_at
+                 FROM trade_group_members
+                 WHERE trade_group_id = $1 AND slave_account = $2"
+            )
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .fetch_optional(pool)
+            .await?;
+
+            if let Some(row) = row {
+                let id: i32 = row.get("id");
+                let trade_group_id: String = row.get("trade_group_id");
+                let slave_account: String = row.get("slave_account");
+                let settings_json: String = row.get("slave_settings");
+                let slave_settings: SlaveSettings = serde_json::from_str(&settings_json)?;
+                let status: i32 = row.get("status");
+                let enabled_flag: bool = row.get::<i32, _>("enabled_flag") != 0;
+                let status: i32 = row.try_get("status").unwrap_or(status);
+                let acknowledged_json: String = row.get("acknowledged_warnings");
+                let acknowledged_warnings = serde_json::from_str(&acknowledged_json)?;
+                let created_at: String = row.get("created_at");
+                let updated_at: String = row.get("updated_at");
+
+                Ok(Some(TradeGroupMember {
+                    id,
+                    trade_group_id,
+                    slave_account,
+                    slave_settings,
+                    status,
+                    warning_codes: Vec::new(),
+                    acknowledged_warnings,
+                    warnings: Vec::new(),
+                    enabled_flag,
+                    created_at,
+                    updated_at,
+                }))
+            } else {
+                Ok(None)
+            }
+        })
     }
 
     /// Update member settings
@@ -187,29 +285,32 @@ impl Database {
     ) -> Result<()> {
         let settings_json = serde_json::to_string(&settings)?;
 
-        let result = sqlx::query(
-            "UPDATE trade_group_members
-             SET slave_settings = ?, updated_at = CURRENT_TIMESTAMP
-             WHERE trade_group_id = ? AND slave_account = ?",
-        )
-        .bind(&settings_json)
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .execute(&self.pool)
-        .await?;
-
-        if result.rows_affected() == 0 {
-            anyhow::bail!(
-                "Member not found: trade_group_id={}, slave_account={}",
-                trade_group_id,
-                slave_account
-            );
-        }
-
-        Ok(())
+        with_pool!(&self.pool, |pool| {
+            let result = sqlx::query(
+                "UPDATE trade_group_members
+                 SET slave_settings = $1, updated_at = CURRENT_TIMESTAMP
+                 WHERE trade_group_id = $2 AND slave_account = $3",
+            )
+            .bind(&settings_json)
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!(
+                    "Member not found: trade_group_id={}, slave_account={}",
+                    trade_group_id,
+                    slave_account
+                );
+            }
+
+            Ok(())
+        })
     }
 
     /// Update the user intent flag for a member
+    /// (turning it off also marks the member `explicitly_disabled` so it is never auto-enabled)
     pub async fn update_member_enabled_flag(
         &self,
         trade_group_id: &str,
@@ -217,26 +318,51 @@ impl Database {
         enabled: bool,
     ) -> Result<()> {
         let flag = if enabled { 1 } else { 0 };
-        let result = sqlx::query(
-            "UPDATE trade_group_members
-             SET enabled_flag = ?, updated_at = CURRENT_TIMESTAMP
-             WHERE trade_group_id = ? AND slave_account = ?",
-        )
-        .bind(flag)
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .execute(&self.pool)
-        .await?;
-
-        if result.rows_affected() == 0 {
-            anyhow::bail!(
-                "Member not found: trade_group_id={}, slave_account={}",
-                trade_group_id,
-                slave_account
-            );
-        }
+        with_pool!(&self.pool, |pool| {
+            let result = sqlx::query(
+                "UPDATE trade_group_members
+                 SET enabled_flag = $1, explicitly_disabled = 1 - $1, updated_at = CURRENT_TIMESTAMP
+                 WHERE trade_group_id = $2 AND slave_account = $3",
+            )
+            .bind(flag)
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!(
+                    "Member not found: trade_group_id={}, slave_account={}",
+                    trade_group_id,
+                    slave_account
+                );
+            }
+
+            Ok(())
+        })
+    }
 
-        Ok(())
+    /// Turn on a disabled member that the user never explicitly disabled
+    /// (`global_settings.auto_enable_on_connect`). Returns true when the member was enabled.
+    pub async fn auto_enable_member(
+        &self,
+        trade_group_id: &str,
+        slave_account: &str,
+    ) -> Result<bool> {
+        with_pool!(&self.pool, |pool| {
+            let result = sqlx::query(
+                "UPDATE trade_group_members
+                 SET enabled_flag = 1, updated_at = CURRENT_TIMESTAMP
+                 WHERE trade_group_id = $1 AND slave_account = $2
+                   AND enabled_flag = 0 AND explicitly_disabled = 0",
+            )
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
     }
 
     /// Update runtime status (calculated by the status engine)
@@ -246,26 +372,60 @@ impl Database {
         slave_account: &str,
         status: i32,
     ) -> Result<()> {
-        let result = sqlx::query(
-            "UPDATE trade_group_members
-             SET status = ?, updated_at = CURRENT_TIMESTAMP
-             WHERE trade_group_id = ? AND slave_account = ?",
-        )
-        .bind(status)
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .execute(&self.pool)
-        .await?;
-
-        if result.rows_affected() == 0 {
-            anyhow::bail!(
-                "Member not found: trade_group_id={}, slave_account={}",
-                trade_group_id,
-                slave_account
-            );
-        }
+        with_pool!(&self.pool, |pool| {
+            let result = sqlx::query(
+                "UPDATE trade_group_members
+                 SET status = $1, updated_at = CURRENT_TIMESTAMP
+                 WHERE trade_group_id = $2 AND slave_account = $3",
+            )
+            .bind(status)
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!(
+                    "Member not found: trade_group_id={}, slave_account={}",
+                    trade_group_id,
+                    slave_account
+                );
+            }
+
+            Ok(())
+        })
+    }
 
-        Ok(())
+    /// Replace the acknowledged (muted) warning codes for a member
+    pub async fn update_member_acknowledged_warnings(
+        &self,
+        trade_group_id: &str,
+        slave_account: &str,
+        warning_codes: &[WarningCode],
+    ) -> Result<()> {
+        let json = serde_json::to_string(warning_codes)?;
+        with_pool!(&self.pool, |pool| {
+            let result = sqlx::query(
+                "UPDATE trade_group_members
+                 SET acknowledged_warnings = $1, updated_at = CURRENT_TIMESTAMP
+                 WHERE trade_group_id = $2 AND slave_account = $3",
+            )
+            .bind(&json)
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                anyhow::bail!(
+                    "Member not found: trade_group_id={}, slave_account={}",
+                    trade_group_id,
+                    slave_account
+                );
+            }
+
+            Ok(())
+        })
     }
 
     /// Get all Masters (trade_group_ids) that a Slave is connected to
@@ -277,31 +437,35 @@ impl Database {
     /// Vector of master account IDs (trade_group_ids) that this Slave is connected to
     #[allow(dead_code)]
     pub async fn get_masters_for_slave(&self, slave_account: &str) -> Result<Vec<String>> {
-        let rows = sqlx::query(
-            "SELECT DISTINCT trade_group_id
-             FROM trade_group_members
-             WHERE slave_account = ?
-             ORDER BY trade_group_id",
-        )
-        .bind(slave_account)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let masters: Vec<String> = rows.iter().map(|row| row.get("trade_group_id")).collect();
-        Ok(masters)
+        with_pool!(&self.pool, |pool| {
+            let rows = sqlx::query(
+                "SELECT DISTINCT trade_group_id
+                 FROM trade_group_members
+                 WHERE slave_account = $1
+                 ORDER BY trade_group_id",
+            )
+            .bind(slave_account)
+            .fetch_all(pool)
+            .await?;
+
+            let masters: Vec<String> = rows.iter().map(|row| row.get("trade_group_id")).collect();
+            Ok(masters)
+        })
     }
 
     /// Delete a member
     pub async fn delete_member(&self, trade_group_id: &str, slave_account: &str) -> Result<()> {
-        sqlx::query(
-            "DELETE FROM trade_group_members
-             WHERE trade_group_id = ? AND slave_account = ?",
-        )
-        .bind(trade_group_id)
-        .bind(slave_account)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
+        with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "DELETE FROM trade_group_members
+                 WHERE trade_group_id = $1 AND slave_account = $2",
+            )
+            .bind(trade_group_id)
+            .bind(slave_account)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
     }
 }
@@ -19,11 +19,13 @@ impl Database {
         let master_settings = MasterSettings::default();
         let settings_json = serde_json::to_string(&master_settings)?;
 
-        sqlx::query("INSERT INTO trade_groups (id, master_settings) VALUES (?, ?)")
-            .bind(master_account)
-            .bind(&settings_json)
-            .execute(&self.pool)
-            .await?;
+        with_pool!(&self.pool, |pool| {
+            sqlx::query("INSERT INTO trade_groups (id, master_settings) VALUES ($1, $2)")
+                .bind(master_account)
+                .bind(&settings_json)
+                .execute(pool)
+                .await?;
+        });
 
         self.get_trade_group(master_account)
             .await?
@@ -32,32 +34,34 @@ impl Database {
 
     /// Get a TradeGroup by master_account
     pub async fn get_trade_group(&self, master_account: &str) -> Result<Option<TradeGroup>> {
-        let row = sqlx::query(
-            "SELECT id, master_settings, created_at, updated_at
-             FROM trade_groups WHERE id = ?",
-        )
-        .bind(master_account)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(row) = row {
-            let id: String = row.get("id");
-            let settings_json: String = row.get("master_settings");
-            let master_settings: MasterSettings = serde_json::from_str(&settings_json)?;
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            Ok(Some(TradeGroup {
-                id,
-                master_settings,
-                master_runtime_status: 0, // Runtime field, not stored in DB
-                master_warning_codes: Vec::new(), // Runtime field, not stored in DB
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+        with_pool!(&self.pool, |pool| {
+            let row = sqlx::query(
+                "SELECT id, master_settings, created_at, updated_at
+                 FROM trade_groups WHERE id = $1",
+            )
+            .bind(master_account)
+            .fetch_optional(pool)
+            .await?;
+
+            if let Some(row) = row {
+                let id: String = row.get("id");
+                let settings_json: String = row.get("master_settings");
+                let master_settings: MasterSettings = serde_json::from_str(&settings_json)?;
+                let created_at: String = row.get("created_at");
+                let updated_at: String = row.get("updated_at");
+
+                Ok(Some(TradeGroup {
+                    id,
+                    master_settings,
+                    master_runtime_status: 0, // Runtime field, not stored in DB
+                    master_warning_codes: Vec::new(), // Runtime field, not stored in DB
+                    created_at,
+                    updated_at,
+                }))
+            } else {
+                Ok(None)
+            }
+        })
     }
 
     /// Update Master settings for a TradeGroup
@@ -68,17 +72,20 @@ impl Database {
     ) -> Result<()> {
         let settings_json = serde_json::to_string(&settings)?;
 
-        let result = sqlx::query(
-            "UPDATE trade_groups
-             SET master_settings = ?, updated_at = CURRENT_TIMESTAMP
-             WHERE id = ?",
-        )
-        .bind(&settings_json)
-        .bind(master_account)
-        .execute(&self.pool)
-        .await?;
-
-        if result.rows_affected() == 0 {
+        let rows_affected = with_pool!(&self.pool, |pool| {
+            sqlx::query(
+                "UPDATE trade_groups
+                 SET master_settings = $1, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $2",
+            )
+            .bind(&settings_json)
+            .bind(master_account)
+            .execute(pool)
+            .await?
+            .rows_affected()
+        });
+
+        if rows_affected == 0 {
             anyhow::bail!("TradeGroup not found: id={}", master_account);
         }
 
@@ -87,40 +94,44 @@ impl Database {
 
     /// Delete a TradeGroup (CASCADE deletes all members)
     pub async fn delete_trade_group(&self, master_account: &str) -> Result<()> {
-        sqlx::query("DELETE FROM trade_groups WHERE id = ?")
-            .bind(master_account)
-            .execute(&self.pool)
-            .await?;
+        with_pool!(&self.pool, |pool| {
+            sqlx::query("DELETE FROM trade_groups WHERE id = $1")
+                .bind(master_account)
+                .execute(pool)
+                .await?;
+        });
         Ok(())
     }
 
     /// List all TradeGroups
     pub async fn list_trade_groups(&self) -> Result<Vec<TradeGroup>> {
-        let rows = sqlx::query(
-            "SELECT id, master_settings, created_at, updated_at
-             FROM trade_groups ORDER BY created_at DESC",
-        )
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut result = Vec::new();
-        for row in rows {
-            let id: String = row.get("id");
-            let settings_json: String = row.get("master_settings");
-            let master_settings: MasterSettings = serde_json::from_str(&settings_json)?;
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            result.push(TradeGroup {
-                id,
-                master_settings,
-                master_runtime_status: 0, // Runtime field, not stored in DB
-                master_warning_codes: Vec::new(), // Runtime field, not stored in DB
-                created_at,
-                updated_at,
-            });
-        }
+        with_pool!(&self.pool, |pool| {
+            let rows = sqlx::query(
+                "SELECT id, master_settings, created_at, updated_at
+                 FROM trade_groups ORDER BY created_at DESC",
+            )
+            .fetch_all(pool)
+            .await?;
 
-        Ok(result)
+            let mut result = Vec::new();
+            for row in rows {
+                let id: String = row.get("id");
+                let settings_json: String = row.get("master_settings");
+                let master_settings: MasterSettings = serde_json::from_str(&settings_json)?;
+                let created_at: String = row.get("created_at");
+                let updated_at: String = row.get("updated_at");
+
+                result.push(TradeGroup {
+                    id,
+                    master_settings,
+                    master_runtime_status: 0, // Runtime field, not stored in DB
+                    master_warning_codes: Vec::new(), // Runtime field, not stored in DB
+                    created_at,
+                    updated_at,
+                });
+            }
+
+            Ok(result)
+        })
     }
 }
@@ -0,0 +1,155 @@
+//! Trade history operations
+//!
+//! Implementation of Database methods for recording copy decisions
+//! and querying them with optional master/slave/time-range filters.
+
+use crate::domain::models::{TradeAction, TradeHistoryEntry, TradeHistoryPage, TradeHistoryQuery};
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::{QueryBuilder, Row};
+
+use super::Database;
+
+/// Fixed-width UTC format so timestamps compare correctly as TEXT
+fn format_timestamp(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn action_to_str(action: &TradeAction) -> &'static str {
+    match action {
+        TradeAction::Open => "Open",
+        TradeAction::Close => "Close",
+        TradeAction::Modify => "Modify",
+    }
+}
+
+fn action_from_str(action: &str) -> Result<TradeAction> {
+    match action {
+        "Open" => Ok(TradeAction::Open),
+        "Close" => Ok(TradeAction::Close),
+        "Modify" => Ok(TradeAction::Modify),
+        other => Err(anyhow::anyhow!(
+            "Unknown trade action in history: {}",
+            other
+        )),
+    }
+}
+
+/// Append the WHERE clause shared by the page and count queries
+fn push_filters<'a, DB>(builder: &mut QueryBuilder<'a, DB>, query: &TradeHistoryQuery)
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    builder.push(" WHERE 1 = 1");
+    if let Some(master) = &query.master {
+        builder
+            .push(" AND master_account = ")
+            .push_bind(master.clone());
+    }
+    if let Some(slave) = &query.slave {
+        builder
+            .push(" AND slave_account = ")
+            .push_bind(slave.clone());
+    }
+    if let Some(from) = &query.from {
+        builder
+            .push(" AND timestamp >= ")
+            .push_bind(format_timestamp(from));
+    }
+    if let Some(to) = &query.to {
+        builder
+            .push(" AND timestamp <= ")
+            .push_bind(format_timestamp(to));
+    }
+}
+
+impl Database {
+    // ============================================================================
+    // Trade History Operations
+    // ============================================================================
+
+    /// Record a copy decision. Returns the new row ID (`entry.id` is ignored)
+    pub async fn insert_trade_history(&self, entry: &TradeHistoryEntry) -> Result<i64> {
+        let id = with_pool!(&self.pool, |pool| {
+            sqlx::query_scalar(
+                "INSERT INTO trade_history (
+                    master_account,
+                    slave_account,
+                    ticket,
+                    action,
+                    symbol,
+                    lots,
+                    timestamp,
+                    copied,
+                    filter_reason
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id",
+            )
+            .bind(&entry.master_account)
+            .bind(&entry.slave_account)
+            .bind(entry.ticket)
+            .bind(action_to_str(&entry.action))
+            .bind(&entry.symbol)
+            .bind(entry.lots)
+            .bind(format_timestamp(&entry.timestamp))
+            .bind(i32::from(entry.copied))
+            .bind(&entry.filter_reason)
+            .fetch_one(pool)
+            .await?
+        });
+
+        Ok(id)
+    }
+
+    /// Query trade history, newest first
+    pub async fn query_trade_history(&self, query: &TradeHistoryQuery) -> Result<TradeHistoryPage> {
+        let limit = query.effective_limit();
+        let offset = query.offset.unwrap_or(0);
+
+        with_pool!(&self.pool, |pool| {
+            let mut count_builder =
+                QueryBuilder::new("SELECT COUNT(*) AS total FROM trade_history");
+            push_filters(&mut count_builder, query);
+            let total: i64 = count_builder.build().fetch_one(pool).await?.get("total");
+
+            let mut builder = QueryBuilder::new(
+                "SELECT id, master_account, slave_account, ticket, action, symbol, lots, timestamp, copied, filter_reason
+                 FROM trade_history",
+            );
+            push_filters(&mut builder, query);
+            builder
+                .push(" ORDER BY timestamp DESC, id DESC LIMIT ")
+                .push_bind(limit as i64)
+                .push(" OFFSET ")
+                .push_bind(offset as i64);
+
+            let rows = builder.build().fetch_all(pool).await?;
+
+            let mut items = Vec::with_capacity(rows.len());
+            for row in rows {
+                let timestamp: String = row.get("timestamp");
+                let action: String = row.get("action");
+                items.push(TradeHistoryEntry {
+                    id: row.get("id"),
+                    master_account: row.get("master_account"),
+                    slave_account: row.get("slave_account"),
+                    ticket: row.get("ticket"),
+                    action: action_from_str(&action)?,
+                    symbol: row.get("symbol"),
+                    lots: row.get("lots"),
+                    timestamp: DateTime::parse_from_rfc3339(&timestamp)?.with_timezone(&Utc),
+                    copied: row.get::<i32, _>("copied") != 0,
+                    filter_reason: row.get("filter_reason"),
+                });
+            }
+
+            Ok(TradeHistoryPage {
+                items,
+                total,
+                limit,
+                offset,
+            })
+        })
+    }
+}
@@ -7,7 +7,9 @@ use crate::{
     adapters::outbound::persistence::Database,
     domain::models::SlaveConfigWithMaster,
     domain::services::status_calculator::SlaveRuntimeTarget,
-    ports::{ConfigPublisher, ConnectionManager, DisconnectionService, UpdateBroadcaster},
+    ports::{
+        ConfigPublisher, ConnectionManager, DisconnectionService, StatusNotifier, UpdateBroadcaster,
+    },
 };
 
 use super::runtime_status_updater::{
@@ -20,6 +22,8 @@ pub struct RealDisconnectionService {
     publisher: Arc<dyn ConfigPublisher>,
     broadcaster: Arc<dyn UpdateBroadcaster>,
     metrics: Arc<RuntimeStatusMetrics>,
+    /// Alerts on Master offline and new member warnings
+    notifier: Option<Arc<dyn StatusNotifier>>,
 }
 
 impl RealDisconnectionService {
@@ -36,9 +40,15 @@ impl RealDisconnectionService {
             publisher,
             broadcaster,
             metrics,
+            notifier: None,
         }
     }
 
+    pub fn with_notifier(mut self, notifier: Arc<dyn StatusNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
     fn runtime_updater(&self) -> RuntimeStatusUpdater {
         RuntimeStatusUpdater::with_metrics(
             self.db.clone(),
@@ -70,10 +80,38 @@ impl DisconnectionService for RealDisconnectionService {
             }
         }
 
+        self.master_offline(master_account, None).await;
+    }
+
+    async fn handle_slave_offline(&self, slave_account: &str) {
+        self.slave_offline(slave_account, None).await;
+    }
+
+    async fn handle_master_offline_for(&self, master_account: &str, slave_accounts: &[String]) {
+        self.master_offline(master_account, Some(slave_accounts))
+            .await;
+    }
+
+    async fn handle_slave_offline_for(&self, slave_account: &str, trade_group_ids: &[String]) {
+        self.slave_offline(slave_account, Some(trade_group_ids))
+            .await;
+    }
+}
+
+impl RealDisconnectionService {
+    /// Re-evaluate and notify the Master's members (`only` = subset of Slaves)
+    async fn master_offline(&self, master_account: &str, only: Option<&[String]>) {
+        if let Some(notifier) = &self.notifier {
+            notifier.master_connection(master_account, false).await;
+        }
+
         let runtime_updater = self.runtime_updater();
 
         match self.db.get_members(master_account).await {
             Ok(members) => {
+                let members = members
+                    .into_iter()
+                    .filter(|m| only.is_none_or(|only| only.contains(&m.slave_account)));
                 for member in members {
                     let slave_bundle = runtime_updater
                         .build_slave_bundle(SlaveRuntimeTarget {
@@ -99,6 +137,16 @@ impl DisconnectionService for RealDisconnectionService {
                         new_status == crate::domain::models::STATUS_CONNECTED,
                     );
 
+                    if let Some(notifier) = &self.notifier {
+                        notifier
+                            .member_warnings(
+                                master_account,
+                                &member.slave_account,
+                                &slave_bundle.status_result.warning_codes,
+                            )
+                            .await;
+                    }
+
                     if let Err(e) = self.publisher.send_slave_config(&config).await {
                         error!(
                             "Failed to send config to {} on Master disconnect: {}",
@@ -139,9 +187,7 @@ impl DisconnectionService for RealDisconnectionService {
                         slave_settings: member.slave_settings.clone(),
                     };
 
-                    if let Ok(json) = serde_json::to_string(&payload) {
-                        self.broadcaster.broadcast_settings_updated(&json).await;
-                    }
+                    self.broadcaster.broadcast_settings_updated(&payload).await;
                 }
             }
             Err(e) => {
@@ -153,12 +199,16 @@ impl DisconnectionService for RealDisconnectionService {
         }
     }
 
-    async fn handle_slave_offline(&self, slave_account: &str) {
+    /// Re-evaluate the Slave's memberships (`only` = subset of trade groups)
+    async fn slave_offline(&self, slave_account: &str, only: Option<&[String]>) {
         let runtime_updater = self.runtime_updater();
 
         // Get all trade group memberships for this Slave
         let settings_list = match self.db.get_settings_for_slave(slave_account).await {
-            Ok(list) => list,
+            Ok(list) => list
+                .into_iter()
+                .filter(|s| only.is_none_or(|only| only.contains(&s.master_account)))
+                .collect::<Vec<_>>(),
             Err(err) => {
                 error!(
                     "Failed to fetch settings for Slave {} during offline notification: {}",
@@ -202,6 +252,16 @@ impl DisconnectionService for RealDisconnectionService {
                 new_status == crate::domain::models::STATUS_CONNECTED,
             );
 
+            if let Some(notifier) = &self.notifier {
+                notifier
+                    .member_warnings(
+                        &settings.master_account,
+                        &settings.slave_account,
+                        &slave_bundle.status_result.warning_codes,
+                    )
+                    .await;
+            }
+
             // Update database with new status
             if let Err(err) = self
                 .db
@@ -226,13 +286,11 @@ impl DisconnectionService for RealDisconnectionService {
                     slave_settings: settings.slave_settings.clone(),
                 };
 
-                if let Ok(json) = serde_json::to_string(&payload) {
-                    self.broadcaster.broadcast_settings_updated(&json).await;
-                    info!(
-                        "Slave {} offline: broadcast sent (status {} -> {}, master: {})",
-                        slave_account, previous_status, new_status, settings.master_account
-                    );
-                }
+                self.broadcaster.broadcast_settings_updated(&payload).await;
+                info!(
+                    "Slave {} offline: broadcast sent (status {} -> {}, master: {})",
+                    slave_account, previous_status, new_status, settings.master_account
+                );
             }
         }
     }
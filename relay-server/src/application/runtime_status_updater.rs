@@ -1,19 +1,45 @@
-use std::sync::{atomic::AtomicU64, atomic::Ordering, Arc};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{atomic::AtomicU64, atomic::Ordering, Arc, Mutex, PoisonError};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
 use crate::{
     config_builder::{ConfigBuilder, SlaveConfigBundle, SlaveConfigContext},
     domain::services::status_calculator::{
-        evaluate_master_status, evaluate_member_status, ConnectionSnapshot, MasterClusterSnapshot,
-        MasterIntent, MasterStatusResult, MemberStatusResult, SlaveIntent, SlaveRuntimeTarget,
+        apply_currency_check, apply_schedule_check, apply_self_copy_check, evaluate_master_status,
+        evaluate_member_status, ConnectionSnapshot, MasterClusterSnapshot, MasterIntent,
+        MasterStatusResult, MemberStatusResult, SlaveIntent, SlaveRuntimeTarget,
     },
     ports::outbound::{ConnectionManager, TradeGroupRepository},
 };
 
-use crate::domain::models::WarningCode;
+use crate::domain::models::{SlaveSettings, WarningCode};
+
+/// Slave settings with the referenced broker profile applied (see
+/// `BrokerProfile::apply_to`). A missing or unreadable profile is logged and
+/// the member's own settings are used.
+pub async fn effective_slave_settings<'a>(
+    db: &dyn TradeGroupRepository,
+    settings: &'a SlaveSettings,
+) -> Cow<'a, SlaveSettings> {
+    let Some(name) = settings.broker_profile.as_deref() else {
+        return Cow::Borrowed(settings);
+    };
+    match db.get_broker_profile(name).await {
+        Ok(Some(profile)) => Cow::Owned(profile.apply_to(settings)),
+        Ok(None) => {
+            tracing::warn!(broker_profile = %name, "Broker profile not found, using member settings");
+            Cow::Borrowed(settings)
+        }
+        Err(e) => {
+            tracing::error!(broker_profile = %name, error = %e, "Failed to load broker profile");
+            Cow::Borrowed(settings)
+        }
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
 pub fn log_slave_runtime_trace(
@@ -75,6 +101,9 @@ impl RuntimeStatusUpdater {
                 .as_ref()
                 .map(|conn| conn.is_trade_allowed)
                 .unwrap_or(false),
+            low_margin_level: slave_conn
+                .as_ref()
+                .is_some_and(|conn| conn.low_margin_level),
         };
         tracing::debug!(
             target: "status",
@@ -119,6 +148,7 @@ impl RuntimeStatusUpdater {
                 .as_ref()
                 .map(|conn| conn.is_trade_allowed)
                 .unwrap_or(false),
+            low_margin_level: false,
         };
 
         let result = evaluate_master_status(
@@ -143,6 +173,16 @@ impl RuntimeStatusUpdater {
 
     #[instrument(skip(self, target), fields(slave_account = %target.slave_account, master_account = %target.master_account))]
     pub async fn build_slave_bundle(&self, target: SlaveRuntimeTarget<'_>) -> SlaveConfigBundle {
+        self.build_slave_bundle_at(target, Utc::now()).await
+    }
+
+    /// `build_slave_bundle` evaluated at `now` (time-dependent settings such as
+    /// `active_schedule` are judged at this instant)
+    pub async fn build_slave_bundle_at(
+        &self,
+        target: SlaveRuntimeTarget<'_>,
+        now: DateTime<Utc>,
+    ) -> SlaveConfigBundle {
         // Get the specific Master's status (not the entire cluster)
         let master_result = self
             .evaluate_master_runtime_status(target.master_account)
@@ -155,6 +195,11 @@ impl RuntimeStatusUpdater {
             .get_master(target.master_account)
             .await
             .map(|conn| conn.equity);
+        let (master_currency, slave_currency) = self
+            .account_currencies(target.master_account, target.slave_account)
+            .await;
+        let slave_settings =
+            effective_slave_settings(self.db.as_ref(), target.slave_settings).await;
 
         let bundle = ConfigBuilder::build_slave_config(SlaveConfigContext {
             slave_account: target.slave_account.to_string(),
@@ -165,9 +210,11 @@ impl RuntimeStatusUpdater {
             },
             slave_connection_snapshot: slave_snapshot,
             master_status_result: master_result.clone(),
-            slave_settings: target.slave_settings,
+            slave_settings: &slave_settings,
             master_equity,
-            timestamp: Utc::now(),
+            master_currency,
+            slave_currency,
+            timestamp: now,
         });
 
         tracing::debug!(
@@ -209,13 +256,23 @@ impl RuntimeStatusUpdater {
             .await
             .unwrap_or_default();
 
-        let result = evaluate_member_status(
+        let mut result = evaluate_member_status(
             SlaveIntent {
                 web_ui_enabled: target.enabled_flag,
             },
             slave_snapshot,
             &master_result,
         );
+        let (master_currency, slave_currency) = self
+            .account_currencies(target.master_account, target.slave_account)
+            .await;
+        apply_currency_check(
+            &mut result,
+            master_currency.as_deref(),
+            slave_currency.as_deref(),
+        );
+        apply_self_copy_check(&mut result, target.master_account, target.slave_account);
+        apply_schedule_check(&mut result, target.slave_settings, Utc::now());
 
         tracing::debug!(
             target: "status",
@@ -232,6 +289,20 @@ impl RuntimeStatusUpdater {
         result
     }
 
+    /// Account currencies reported by the latest Master/Slave Heartbeats
+    async fn account_currencies(
+        &self,
+        master_account: &str,
+        slave_account: &str,
+    ) -> (Option<String>, Option<String>) {
+        let master = self.connection_manager.get_master(master_account).await;
+        let slave = self.connection_manager.get_slave(slave_account).await;
+        (
+            master.map(|conn| conn.currency),
+            slave.map(|conn| conn.currency),
+        )
+    }
+
     /// Build a cluster snapshot for all Masters connected to a Slave.
     /// This is kept for account-level aggregation (e.g., Web UI Slave node badge).
     #[allow(dead_code)]
@@ -270,6 +341,11 @@ pub struct RuntimeStatusMetrics {
     slave_evaluations_failed: AtomicU64,
     slave_bundles_built: AtomicU64,
     last_cluster_size: AtomicU64,
+    send_failures: AtomicU64,
+    throttled_signals: AtomicU64,
+    last_send_failure: Mutex<Option<SendFailure>>,
+    /// Per trade_group_id signal counters, cleared by `reset_trade_group_stats`
+    trade_group_signals: Mutex<HashMap<String, TradeGroupSignalStats>>,
 }
 
 impl RuntimeStatusMetrics {
@@ -302,6 +378,61 @@ impl RuntimeStatusMetrics {
             .store(cluster_size, Ordering::Relaxed);
     }
 
+    /// ZMQ publish that failed (queueing or the socket send itself)
+    pub fn record_send_failure(&self, topic: &str) {
+        self.send_failures.fetch_add(1, Ordering::Relaxed);
+        *self
+            .last_send_failure
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SendFailure {
+            topic: topic.to_string(),
+            at: Utc::now(),
+        });
+    }
+
+    /// Trade signal dropped by a Master's `max_signals_per_sec`
+    pub fn record_throttled_signal(&self) {
+        self.throttled_signals.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Trade signal accepted for a trade group (after duplicate detection)
+    pub fn record_signal_received(&self, trade_group_id: &str) {
+        self.trade_group_signals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(trade_group_id.to_string())
+            .or_default()
+            .signals_received += 1;
+    }
+
+    /// Trade signal forwarded to at least one member of the trade group
+    pub fn record_signal_copied(&self, trade_group_id: &str) {
+        self.trade_group_signals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(trade_group_id.to_string())
+            .or_default()
+            .signals_copied += 1;
+    }
+
+    /// Signal counters of a trade group (zero when nothing was counted yet)
+    pub fn trade_group_stats(&self, trade_group_id: &str) -> TradeGroupSignalStats {
+        self.trade_group_signals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(trade_group_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Clear the signal counters of every trade group
+    pub fn reset_trade_group_stats(&self) {
+        self.trade_group_signals
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
     pub fn snapshot(&self) -> RuntimeStatusMetricsSnapshot {
         RuntimeStatusMetricsSnapshot {
             master_evaluations_total: self.master_evaluations_total.load(Ordering::Relaxed),
@@ -310,11 +441,18 @@ impl RuntimeStatusMetrics {
             slave_evaluations_failed: self.slave_evaluations_failed.load(Ordering::Relaxed),
             slave_bundles_built: self.slave_bundles_built.load(Ordering::Relaxed),
             last_cluster_size: self.last_cluster_size.load(Ordering::Relaxed),
+            send_failures: self.send_failures.load(Ordering::Relaxed),
+            throttled_signals: self.throttled_signals.load(Ordering::Relaxed),
+            last_send_failure: self
+                .last_send_failure
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuntimeStatusMetricsSnapshot {
     pub master_evaluations_total: u64,
     pub master_evaluations_failed: u64,
@@ -322,6 +460,28 @@ pub struct RuntimeStatusMetricsSnapshot {
     pub slave_evaluations_failed: u64,
     pub slave_bundles_built: u64,
     pub last_cluster_size: u64,
+    /// ZMQ publishes that failed since startup
+    #[serde(default)]
+    pub send_failures: u64,
+    #[serde(default)]
+    pub last_send_failure: Option<SendFailure>,
+    /// Trade signals dropped by `max_signals_per_sec` since startup
+    #[serde(default)]
+    pub throttled_signals: u64,
+}
+
+/// Signals received from a trade group's Master and signals copied to its members
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeGroupSignalStats {
+    pub signals_received: u64,
+    pub signals_copied: u64,
+}
+
+/// Most recent failed ZMQ publish
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendFailure {
+    pub topic: String,
+    pub at: DateTime<Utc>,
 }
 
 #[cfg(test)]
@@ -346,6 +506,9 @@ mod tests {
             async fn get_settings_for_slave(&self, slave_id: &str) -> anyhow::Result<Vec<crate::domain::models::SlaveConfigWithMaster>>;
             async fn update_member_runtime_status(&self, master_id: &str, slave_id: &str, status: i32) -> anyhow::Result<()>;
             async fn get_masters_for_slave(&self, slave_account: &str) -> anyhow::Result<Vec<String>>;
+            async fn get_global_settings(&self) -> anyhow::Result<crate::domain::models::GlobalSettings>;
+            async fn auto_enable_member(&self, master_id: &str, slave_id: &str) -> anyhow::Result<bool>;
+            async fn get_broker_profile(&self, name: &str) -> anyhow::Result<Option<crate::domain::models::BrokerProfile>>;
         }
     }
 
@@ -356,7 +519,7 @@ mod tests {
             async fn get_master(&self, account_id: &str) -> Option<EaConnection>;
             async fn get_slave(&self, account_id: &str) -> Option<EaConnection>;
 
-            async fn update_heartbeat(&self, msg: crate::domain::models::HeartbeatMessage) -> bool;
+            async fn update_heartbeat(&self, msg: crate::domain::models::HeartbeatMessage) -> crate::domain::models::HeartbeatOutcome;
         }
     }
 
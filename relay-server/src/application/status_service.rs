@@ -1,7 +1,7 @@
 use crate::application::runtime_status_updater::RuntimeStatusUpdater;
-use crate::domain::models::HeartbeatMessage;
+use crate::domain::models::{ConnectionStatus, HeartbeatMessage, HeartbeatOutcome};
 use crate::ports::outbound::{
-    ConfigPublisher, ConnectionManager, TradeGroupRepository, UpdateBroadcaster,
+    ConfigPublisher, ConnectionManager, StatusNotifier, TradeGroupRepository, UpdateBroadcaster,
     VLogsConfigProvider,
 };
 use std::sync::Arc;
@@ -14,6 +14,8 @@ pub struct StatusService {
     runtime_status_updater: Arc<RuntimeStatusUpdater>,
     broadcaster: Option<Arc<dyn UpdateBroadcaster>>,
     vlogs_provider: Option<Arc<dyn VLogsConfigProvider>>,
+    /// Alerts on Master online/offline and new member warnings
+    notifier: Option<Arc<dyn StatusNotifier>>,
 }
 
 impl StatusService {
@@ -32,9 +34,15 @@ impl StatusService {
             runtime_status_updater,
             broadcaster,
             vlogs_provider,
+            notifier: None,
         }
     }
 
+    pub fn with_notifier(mut self, notifier: Arc<dyn StatusNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
     pub async fn handle_heartbeat(&self, msg: HeartbeatMessage) {
         let account_id = msg.account_id.clone();
         let ea_type = msg.ea_type.clone();
@@ -54,9 +62,13 @@ impl StatusService {
         };
 
         // Update heartbeat
-        let is_new_registration = self.connection_manager.update_heartbeat(msg.clone()).await;
+        let outcome = self.connection_manager.update_heartbeat(msg.clone()).await;
+        if outcome == HeartbeatOutcome::Rejected {
+            // Replayed or clock-skewed heartbeat: must not resurrect or re-evaluate the EA
+            return;
+        }
 
-        if is_new_registration {
+        if outcome == HeartbeatOutcome::Registered {
             tracing::info!(
                 account = %account_id,
                 "New EA registration detected, sending VictoriaLogs config"
@@ -117,6 +129,7 @@ impl StatusService {
             connection_snapshot: crate::domain::services::status_calculator::ConnectionSnapshot {
                 connection_status: master_conn.as_ref().map(|c| c.status),
                 is_trade_allowed: msg.is_trade_allowed,
+                low_margin_level: false,
             },
             settings: &trade_group.master_settings,
             timestamp: chrono::Utc::now(),
@@ -124,11 +137,19 @@ impl StatusService {
 
         let bundle = crate::config_builder::ConfigBuilder::build_master_config(context);
 
+        if let Some(notifier) = &self.notifier {
+            let online = master_conn
+                .as_ref()
+                .is_some_and(|c| c.status == ConnectionStatus::Online);
+            notifier.master_connection(account_id, online).await;
+        }
+
         // Calculate OLD Master Status
         let old_master_status = if let Some(conn) = old_conn.as_ref() {
             let old_snapshot = crate::domain::services::status_calculator::ConnectionSnapshot {
                 connection_status: Some(conn.status),
                 is_trade_allowed: conn.is_trade_allowed,
+                low_margin_level: false,
             };
             crate::domain::services::status_calculator::evaluate_master_status(
                 crate::domain::services::status_calculator::MasterIntent {
@@ -260,9 +281,10 @@ impl StatusService {
                     .as_ref()
                     .map(|c| c.is_trade_allowed)
                     .unwrap_or(false),
+                low_margin_level: slave_conn.as_ref().is_some_and(|c| c.low_margin_level),
             };
 
-            let old_slave_result =
+            let mut old_slave_result =
                 crate::domain::services::status_calculator::evaluate_member_status(
                     crate::domain::services::status_calculator::SlaveIntent {
                         web_ui_enabled: member.enabled_flag,
@@ -270,9 +292,29 @@ impl StatusService {
                     slave_snapshot,
                     old_master_status,
                 );
+            crate::domain::services::status_calculator::apply_self_copy_check(
+                &mut old_slave_result,
+                master_account,
+                &slave_account,
+            );
+            crate::domain::services::status_calculator::apply_schedule_check(
+                &mut old_slave_result,
+                &member.slave_settings,
+                chrono::Utc::now(),
+            );
 
             let slave_changed = slave_bundle.status_result.has_changed(&old_slave_result);
 
+            if let Some(notifier) = &self.notifier {
+                notifier
+                    .member_warnings(
+                        master_account,
+                        &slave_account,
+                        &slave_bundle.status_result.warning_codes,
+                    )
+                    .await;
+            }
+
             if slave_changed {
                 any_state_changed = true;
                 tracing::info!(
@@ -320,6 +362,17 @@ impl StatusService {
         }
     }
 
+    /// `global_settings.auto_enable_on_connect` (false when it cannot be read)
+    async fn auto_enable_on_connect(&self) -> bool {
+        match self.repository.get_global_settings().await {
+            Ok(settings) => settings.auto_enable_on_connect,
+            Err(err) => {
+                tracing::error!("Failed to load global settings: {}", err);
+                false
+            }
+        }
+    }
+
     async fn handle_slave_heartbeat(
         &self,
         msg: HeartbeatMessage,
@@ -350,7 +403,36 @@ impl StatusService {
 
         let updater = &self.runtime_status_updater;
 
-        for settings in settings_list {
+        let newly_connected = old_conn
+            .as_ref()
+            .is_none_or(|conn| conn.status != ConnectionStatus::Online);
+        let auto_enable = newly_connected && self.auto_enable_on_connect().await;
+
+        for mut settings in settings_list {
+            if auto_enable && !settings.enabled_flag {
+                match self
+                    .repository
+                    .auto_enable_member(&settings.master_account, slave_account)
+                    .await
+                {
+                    Ok(true) => {
+                        tracing::info!(
+                            slave = %slave_account,
+                            master = %settings.master_account,
+                            "Auto-enabled member on Slave connect"
+                        );
+                        settings.enabled_flag = true;
+                    }
+                    Ok(false) => {}
+                    Err(err) => tracing::error!(
+                        "Failed to auto-enable Slave {} (master {}): {}",
+                        slave_account,
+                        settings.master_account,
+                        err
+                    ),
+                }
+            }
+
             // Build new bundle
             let slave_bundle = updater
                 .build_slave_bundle(
@@ -369,6 +451,7 @@ impl StatusService {
                 let old_snapshot = crate::domain::services::status_calculator::ConnectionSnapshot {
                     connection_status: Some(conn.status),
                     is_trade_allowed: conn.is_trade_allowed,
+                    low_margin_level: conn.low_margin_level,
                 };
                 updater
                     .evaluate_member_runtime_status_with_snapshot(
@@ -389,6 +472,16 @@ impl StatusService {
             let previous_status = settings.status;
             let evaluated_status = slave_bundle.status_result.status;
 
+            if let Some(notifier) = &self.notifier {
+                notifier
+                    .member_warnings(
+                        &settings.master_account,
+                        slave_account,
+                        &slave_bundle.status_result.warning_codes,
+                    )
+                    .await;
+            }
+
             // Detect changes
             let state_changed = slave_bundle.status_result.has_changed(&old_status_result)
                 || previous_status != evaluated_status;
@@ -467,7 +560,7 @@ mod tests {
             async fn get_master(&self, account_id: &str) -> Option<EaConnection>;
             async fn get_slave(&self, account_id: &str) -> Option<EaConnection>;
 
-            async fn update_heartbeat(&self, msg: HeartbeatMessage) -> bool;
+            async fn update_heartbeat(&self, msg: HeartbeatMessage) -> crate::domain::models::HeartbeatOutcome;
         }
     }
 
@@ -481,6 +574,9 @@ mod tests {
             async fn get_settings_for_slave(&self, slave_id: &str) -> anyhow::Result<Vec<crate::domain::models::SlaveConfigWithMaster>>;
             async fn update_member_runtime_status(&self, master_id: &str, slave_id: &str, status: i32) -> anyhow::Result<()>;
             async fn get_masters_for_slave(&self, slave_account: &str) -> anyhow::Result<Vec<String>>;
+            async fn get_global_settings(&self) -> anyhow::Result<crate::domain::models::GlobalSettings>;
+            async fn auto_enable_member(&self, master_id: &str, slave_id: &str) -> anyhow::Result<bool>;
+            async fn get_broker_profile(&self, name: &str) -> anyhow::Result<Option<crate::domain::models::BrokerProfile>>;
         }
     }
 
@@ -491,6 +587,7 @@ mod tests {
             async fn send_master_config(&self, config: &MasterConfigMessage) -> anyhow::Result<()>;
             async fn send_slave_config(&self, config: &SlaveConfigMessage) -> anyhow::Result<()>;
             async fn broadcast_vlogs_config(&self, config: &VLogsGlobalSettings) -> anyhow::Result<()>;
+            async fn send_close_all(&self, message: &sankey_copier_zmq::CloseAllMessage) -> anyhow::Result<()>;
         }
     }
 
@@ -500,7 +597,16 @@ mod tests {
         impl UpdateBroadcaster for UpdateBroadcaster {
             async fn broadcast_snapshot(&self);
             async fn broadcast_ea_disconnected(&self, account_id: &str);
-            async fn broadcast_settings_updated(&self, json: &str);
+            async fn broadcast_settings_updated(&self, settings: &crate::domain::models::SlaveConfigWithMaster);
+        }
+    }
+
+    mock! {
+        pub StatusNotifier {}
+        #[async_trait]
+        impl StatusNotifier for StatusNotifier {
+            async fn master_connection(&self, master_account: &str, online: bool);
+            async fn member_warnings(&self, master_account: &str, slave_account: &str, warning_codes: &[crate::domain::models::WarningCode]);
         }
     }
 
@@ -526,6 +632,9 @@ mod tests {
             message_type: "Heartbeat".to_string(),
             balance: 500.0,
             equity: 500.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: "2023-01-01T00:00:00Z".to_string(),
             version: "1.0.0".to_string(),
@@ -550,7 +659,7 @@ mod tests {
                 },
             ))
             .times(1)
-            .returning(|_| true);
+            .returning(|_| HeartbeatOutcome::Registered);
 
         // EXPECT: get_slave called (to check old status)
         mock_conn_manager
@@ -631,6 +740,9 @@ mod tests {
             message_type: "Heartbeat".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: "2023-01-01T00:00:00Z".to_string(),
             version: "1.0.0".to_string(),
@@ -653,7 +765,7 @@ mod tests {
                 msg.account_id == "MASTER_123" && msg.ea_type == "Master" && msg.is_trade_allowed
             }))
             .times(1)
-            .return_const(false);
+            .return_const(HeartbeatOutcome::Updated);
 
         // Initial connection lookup
         let existing_conn = EaConnection {
@@ -700,6 +812,85 @@ mod tests {
         service.handle_heartbeat(heartbeat).await;
     }
 
+    #[tokio::test]
+    async fn test_handle_heartbeat_master_reports_connection_to_notifier() {
+        let mut mock_conn_manager = MockConnectionManager::new();
+        let mut mock_repo = MockTradeGroupRepository::new();
+        let mut mock_notifier = MockStatusNotifier::new();
+
+        let account_id = "MASTER_123";
+        let heartbeat = HeartbeatMessage {
+            account_id: account_id.to_string(),
+            ea_type: "Master".to_string(),
+            is_trade_allowed: true,
+            message_type: "Heartbeat".to_string(),
+            balance: 10000.0,
+            equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            open_positions: 0,
+            timestamp: "2023-01-01T00:00:00Z".to_string(),
+            version: "1.0.0".to_string(),
+            platform: "MT5".to_string(),
+            account_number: 123456,
+            broker: "TestBroker".to_string(),
+            account_name: "TestAccount".to_string(),
+            server: "TestServer".to_string(),
+            currency: "USD".to_string(),
+            leverage: 100,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_map: None,
+        };
+
+        mock_conn_manager
+            .expect_update_heartbeat()
+            .return_const(HeartbeatOutcome::Updated);
+        mock_conn_manager
+            .expect_get_master()
+            .with(eq(account_id))
+            .return_const(Some(EaConnection {
+                account_id: account_id.to_string(),
+                ea_type: crate::domain::models::EaType::Master,
+                status: ConnectionStatus::Online,
+                is_trade_allowed: true,
+                ..Default::default()
+            }));
+        let trade_group = TradeGroup::new(account_id.to_string());
+        mock_repo
+            .expect_get_trade_group()
+            .return_once(|_| Ok(Some(trade_group)));
+        mock_repo.expect_get_members().returning(|_| Ok(vec![]));
+
+        // The notifier decides whether this is a transition
+        mock_notifier
+            .expect_master_connection()
+            .with(eq(account_id), eq(true))
+            .times(1)
+            .return_const(());
+
+        let service = StatusService::new(
+            Arc::new(mock_conn_manager),
+            Arc::new(mock_repo),
+            Arc::new(MockConfigPublisher::new()),
+            Arc::new(
+                crate::application::runtime_status_updater::RuntimeStatusUpdater::with_metrics(
+                    Arc::new(MockTradeGroupRepository::new()),
+                    Arc::new(MockConnectionManager::new()),
+                    Arc::new(
+                        crate::application::runtime_status_updater::RuntimeStatusMetrics::default(),
+                    ),
+                ),
+            ),
+            None,
+            None,
+        )
+        .with_notifier(Arc::new(mock_notifier));
+
+        service.handle_heartbeat(heartbeat).await;
+    }
+
     #[tokio::test]
     async fn test_handle_heartbeat_master_no_tradegroup_no_action() {
         let mut mock_conn_manager = MockConnectionManager::new();
@@ -714,6 +905,9 @@ mod tests {
             message_type: "Heartbeat".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: "2023-01-01T00:00:00Z".to_string(),
             version: "1.0.0".to_string(),
@@ -745,7 +939,7 @@ mod tests {
         // Mock connection manager calls (might happen before trade group check)
         mock_conn_manager
             .expect_update_heartbeat()
-            .returning(|_| true);
+            .returning(|_| HeartbeatOutcome::Registered);
 
         // OLD connection check
         mock_conn_manager.expect_get_master().returning(|_| None);
@@ -801,6 +995,9 @@ mod tests {
             leverage: 100,
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             symbol_prefix: None,
             symbol_suffix: None,
@@ -839,7 +1036,7 @@ mod tests {
         mock_conn_manager
             .expect_update_heartbeat()
             .times(1)
-            .return_const(true);
+            .return_const(HeartbeatOutcome::Registered);
 
         // 2. get_slave (StatusService: old_conn lookup - returns None as if first time or lost track)
         // Actually if we want to simulate change from DISABLED(0) to ENABLED(2),
@@ -959,6 +1156,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: Utc::now().to_rfc3339(),
             version: "1.0.0".to_string(),
@@ -988,7 +1188,7 @@ mod tests {
             .expect_update_heartbeat()
             .times(1)
             .in_sequence(&mut seq)
-            .return_const(false);
+            .return_const(HeartbeatOutcome::Updated);
 
         // Step 3: get_master returns NEW state (Used for new_status calculation)
         let mut conn_updated = conn_initial.clone();
@@ -1069,6 +1269,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: Utc::now().to_rfc3339(),
             version: "1.0.0".to_string(),
@@ -1095,7 +1298,7 @@ mod tests {
             .expect_update_heartbeat()
             .times(1)
             .in_sequence(&mut seq)
-            .return_const(false); // Valid update
+            .return_const(HeartbeatOutcome::Updated); // Valid update
 
         let conn_updated = EaConnection {
             account_id: account_id.to_string(),
@@ -1182,6 +1385,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: (Utc::now() + chrono::Duration::seconds(1)).to_rfc3339(), // 1s later
             version: "1.0.0".to_string(),
@@ -1211,7 +1417,7 @@ mod tests {
             .expect_update_heartbeat()
             .times(1)
             .in_sequence(&mut seq)
-            .return_const(false);
+            .return_const(HeartbeatOutcome::Updated);
 
         // Step 3: get_master for NEW state (Identical)
         mock_conn_manager
@@ -1360,6 +1566,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: Utc::now().to_rfc3339(),
             version: "1.0.0".to_string(),
@@ -1386,6 +1595,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: (Utc::now() + chrono::Duration::seconds(1)).to_rfc3339(), // 1s later
             version: "1.0.0".to_string(),
@@ -1499,6 +1711,9 @@ mod tests {
             platform: "MT5".to_string(),
             balance: 10000.0,
             equity: 10000.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
             open_positions: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
             version: "1.0.0".to_string(),
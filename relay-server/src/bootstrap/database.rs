@@ -0,0 +1,70 @@
+//! Database startup with retry and degraded fallback
+//!
+//! A database that cannot be opened at startup no longer aborts the relay:
+//! after `connect_attempts` it starts degraded on a lazily connecting pool, so
+//! ZMQ and heartbeat tracking keep running while every query fails, and a
+//! background task retries until the schema can be migrated.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::adapters::infrastructure::readiness::Readiness;
+use crate::adapters::outbound::persistence::Database;
+use crate::config::DatabaseConfig;
+
+/// Open the database at `database_url`, falling back to degraded mode.
+/// Returns the recovery task when degraded; it finishes once the database is up.
+pub async fn open(
+    database_url: &str,
+    config: &DatabaseConfig,
+    readiness: Arc<Readiness>,
+) -> Result<(Arc<Database>, Option<JoinHandle<()>>)> {
+    let tuning = config.sqlite_tuning();
+    match Database::connect_with_retry(
+        database_url,
+        &tuning,
+        config.connect_attempts,
+        Duration::from_millis(config.connect_backoff_ms),
+    )
+    .await
+    {
+        Ok(db) => {
+            tracing::info!("Database initialized: {}", database_url);
+            let db = Arc::new(db);
+            readiness.set_database(db.clone());
+            Ok((db, None))
+        }
+        Err(e) => {
+            tracing::error!(
+                "Database unavailable after {} attempt(s), starting degraded: {}",
+                config.connect_attempts.max(1),
+                e
+            );
+            // Fails only on a malformed URL, which no retry can fix
+            let db = Arc::new(Database::lazy(database_url, &tuning)?);
+            readiness.set_database(db.clone());
+            readiness.set_database_degraded(true);
+            let interval = Duration::from_millis(config.reconnect_interval_ms);
+            let task = tokio::spawn(recover(db.clone(), readiness, interval));
+            Ok((db, Some(task)))
+        }
+    }
+}
+
+/// Retry the schema migrations every `interval` until they succeed, then
+/// leave degraded mode
+async fn recover(db: Arc<Database>, readiness: Arc<Readiness>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        match db.run_migrations().await {
+            Ok(_) => {
+                readiness.set_database_degraded(false);
+                tracing::info!("Database reachable again, leaving degraded mode");
+                return;
+            }
+            Err(e) => tracing::warn!("Database still unavailable: {}", e),
+        }
+    }
+}
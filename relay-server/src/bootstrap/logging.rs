@@ -1,15 +1,20 @@
 use crate::adapters::infrastructure::log_buffer::{create_log_buffer, LogBuffer};
-use crate::adapters::outbound::observability::victoria_logs;
+use crate::adapters::outbound::observability::victoria_logs::{
+    self, VLogsController, VictoriaLogsHandle,
+};
 use crate::logging;
-use std::sync::atomic::AtomicBool;
-use std::sync::Arc;
 
-pub fn setup(config: &crate::config::Config) -> (LogBuffer, Option<Arc<AtomicBool>>) {
+/// Background flusher of the VictoriaLogs layer, stopped on shutdown
+pub type VLogsTask = (VictoriaLogsHandle, tokio::task::JoinHandle<()>);
+
+pub fn setup(
+    config: &crate::config::Config,
+) -> (LogBuffer, Option<VLogsController>, Option<VLogsTask>) {
     // Create log buffer
-    let log_buffer = create_log_buffer();
+    let log_buffer = create_log_buffer(config.logging.buffer_capacity);
 
     // Initialize VictoriaLogs
-    let (vlogs_layer, _vlogs_handles, vlogs_enabled_flag) =
+    let (vlogs_layer, vlogs_handles, vlogs_enabled_flag) =
         victoria_logs::init(&config.victoria_logs);
 
     // Initialize logging
@@ -49,9 +54,15 @@ pub fn setup(config: &crate::config::Config) -> (LogBuffer, Option<Arc<AtomicBoo
         );
     }
 
-    // We don't really need to return vlogs_layer since it's already installed globally.
-    // But we return None for the 3rd tuple element to match signature if needed, or change signature.
-    // Changing signature to: (LogBuffer, Option<Arc<AtomicBool>>, ())
+    // The layer is installed globally; the controller shares its enabled flag
+    // and retry buffer counters with the HTTP API
+    let vlogs_controller = vlogs_enabled_flag.map(|enabled_flag| {
+        let controller = VLogsController::new(enabled_flag, config.victoria_logs.clone());
+        match &vlogs_handles {
+            Some((handle, _)) => controller.with_buffer_stats(handle.buffer_stats()),
+            None => controller,
+        }
+    });
 
-    (log_buffer, vlogs_enabled_flag)
+    (log_buffer, vlogs_controller, vlogs_handles)
 }
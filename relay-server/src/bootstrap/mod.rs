@@ -2,16 +2,23 @@ use anyhow::Result;
 use axum::Router;
 use axum_server::tls_rustls::RustlsConfig;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use crate::adapters::infrastructure::readiness::Readiness;
+use crate::bootstrap::shutdown::BackgroundTasks;
+
+pub mod database;
 pub mod logging;
 pub mod server;
 pub mod services;
+pub mod shutdown;
 
 pub struct Application {
     pub router: Router,
     pub tls_config: RustlsConfig,
     pub bind_address: String,
     pub socket_addr: SocketAddr,
+    pub background: BackgroundTasks,
 }
 
 pub async fn setup() -> Result<Application> {
@@ -24,15 +31,21 @@ pub async fn setup() -> Result<Application> {
     let config = load_config();
 
     // 2. Setup Logging
-    // 2. Setup Logging
-    let (log_buffer, vlogs_enabled_flag) = logging::setup(&config);
+    let (log_buffer, vlogs_controller, vlogs_task) = logging::setup(&config);
 
-    // 3. Setup Services & Background Tasks
-    let service_registry =
-        services::setup(&config, log_buffer.clone(), vlogs_enabled_flag.clone()).await?;
+    // 3. Setup Services & Background Tasks (marks readiness as subsystems come up)
+    let readiness = Arc::new(Readiness::default());
+    let service_registry = services::setup(
+        &config,
+        log_buffer.clone(),
+        vlogs_controller,
+        vlogs_task,
+        readiness.clone(),
+    )
+    .await?;
 
     // 4. Setup Server (API & TLS)
-    server::setup(config, service_registry).await
+    server::setup(config, service_registry, readiness).await
 }
 
 fn load_config() -> crate::config::Config {
@@ -4,12 +4,14 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use crate::adapters;
-use crate::adapters::inbound::http::{create_router, AppState};
+use crate::adapters::inbound::http::{create_router, health_router, AppState, CorsOrigins};
+use crate::adapters::infrastructure::readiness::Readiness;
 use crate::bootstrap::{services::ServiceRegistry, Application};
 
 pub async fn setup(
     config: crate::config::Config,
     registry: ServiceRegistry,
+    readiness: Arc<Readiness>,
 ) -> Result<Application> {
     // Update server address
     let server_address = if registry.resolved_ports.is_dynamic && config.server.port == 0 {
@@ -42,13 +44,19 @@ pub async fn setup(
         connection_manager: registry.connection_manager,
         config_sender: registry.config_sender,
         log_buffer: registry.log_buffer,
-        allowed_origins: allowed_origins.clone(),
+        allowed_origins: CorsOrigins::new(allowed_origins.clone()),
         cors_disabled,
         config: Arc::new(config.clone()),
         resolved_ports: Arc::new(registry.resolved_ports),
         vlogs_controller: registry.vlogs_controller,
         runtime_status_metrics: registry.runtime_status_metrics,
         snapshot_broadcaster,
+        signal_injector: Some(registry.signal_injector),
+        mt_installations: Arc::new(
+            adapters::infrastructure::mt_catalog::MtInstallationCatalog::from_config(
+                &config.installer,
+            ),
+        ),
     };
 
     if cors_disabled {
@@ -59,7 +67,8 @@ pub async fn setup(
 
     // Build API router
     tracing::info!("Building API router...");
-    let app = create_router(app_state);
+    // Health probes are merged after the API layers so they bypass CORS
+    let app = create_router(app_state).merge(health_router(readiness));
     tracing::info!("API router built");
 
     // Load TLS certificate and key
@@ -88,5 +97,6 @@ pub async fn setup(
         tls_config,
         bind_address: server_address,
         socket_addr,
+        background: registry.background,
     })
 }
@@ -6,11 +6,15 @@ use crate::adapters;
 use crate::adapters::inbound::zmq::MessageHandler;
 use crate::adapters::infrastructure::connection_manager;
 use crate::adapters::infrastructure::connection_manager::ConnectionManager;
+use crate::adapters::infrastructure::readiness::Readiness;
 use crate::adapters::outbound::messaging::{ZmqConfigPublisher, ZmqMessage, ZmqServer};
 use crate::adapters::outbound::observability::victoria_logs::VLogsController;
 use crate::adapters::outbound::persistence::Database;
 use crate::application::runtime_status_updater::{RuntimeStatusMetrics, RuntimeStatusUpdater};
 use crate::application::status_service::StatusService;
+use crate::bootstrap::logging::VLogsTask;
+use crate::bootstrap::shutdown::BackgroundTasks;
+use crate::domain::models::ServerEvent;
 use crate::domain::services::copy_engine::CopyEngine;
 use crate::ports;
 
@@ -18,18 +22,23 @@ pub struct ServiceRegistry {
     pub db: Arc<Database>,
     pub connection_manager: Arc<ConnectionManager>,
     pub config_sender: Arc<ZmqConfigPublisher>,
-    pub broadcast_tx: broadcast::Sender<String>,
+    pub broadcast_tx: broadcast::Sender<ServerEvent>,
     pub resolved_ports: adapters::infrastructure::port_resolver::ResolvedPorts,
     pub vlogs_controller: Option<VLogsController>,
     pub runtime_status_metrics: Arc<RuntimeStatusMetrics>,
     // Add other needed fields for AppState
     pub log_buffer: crate::adapters::infrastructure::log_buffer::LogBuffer,
+    /// Weak handle to the ZeroMQ message queue (test signal injection)
+    pub signal_injector: mpsc::WeakUnboundedSender<ZmqMessage>,
+    pub background: BackgroundTasks,
 }
 
 pub async fn setup(
     config: &crate::config::Config,
     log_buffer: crate::adapters::infrastructure::log_buffer::LogBuffer,
-    vlogs_enabled_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    vlogs_controller: Option<VLogsController>,
+    vlogs_task: Option<VLogsTask>,
+    readiness: Arc<Readiness>,
 ) -> Result<ServiceRegistry> {
     // Determine config directory for runtime.toml
     let config_dir = std::env::var("CONFIG_DIR").unwrap_or_else(|_| {
@@ -70,48 +79,85 @@ pub async fn setup(
     // Initialize database
     let database_url =
         std::env::var("DATABASE_URL").unwrap_or_else(|_| config.database.url.clone());
-    let db = Arc::new(Database::new(&database_url).await?);
-    tracing::info!("Database initialized: {}", database_url);
-
-    // Create VLogsController
-    let vlogs_controller = vlogs_enabled_flag
-        .map(|enabled_flag| VLogsController::new(enabled_flag, config.victoria_logs.clone()));
+    let (db, db_recovery) =
+        crate::bootstrap::database::open(&database_url, &config.database, readiness.clone())
+            .await?;
 
     // Initialize ConnectionManager
-    let connection_manager = Arc::new(ConnectionManager::new(config.zeromq.timeout_seconds));
+    let connection_manager = Arc::new(
+        ConnectionManager::new(config.zeromq.timeout_seconds)
+            .with_missed_heartbeat_threshold(config.zeromq.missed_heartbeat_threshold)
+            .with_low_margin_level_threshold(config.zeromq.low_margin_level_threshold)
+            .with_position_snapshot_stale_secs(config.zeromq.position_snapshot_stale_secs)
+            .with_max_heartbeat_skew_secs(config.zeromq.max_heartbeat_skew_secs)
+            .with_reconnect_grace_secs(config.zeromq.reconnect_grace_secs),
+    );
     tracing::info!(
-        "Connection manager initialized with {}s timeout",
-        config.zeromq.timeout_seconds
+        "Connection manager initialized with {}s timeout (missed heartbeat threshold: {})",
+        config.zeromq.timeout_seconds,
+        config.zeromq.missed_heartbeat_threshold
     );
 
     // Create channels
     let (zmq_tx, mut zmq_rx) = mpsc::unbounded_channel::<ZmqMessage>();
-    let (broadcast_tx, _) = broadcast::channel::<String>(100);
+    let signal_injector = zmq_tx.downgrade();
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+
+    // Load CURVE keys when [zeromq.security] is enabled (fails fast on bad keys)
+    let curve_keys = adapters::infrastructure::curve_keys::load_server_keys(
+        &config.zeromq.security,
+        &base_path,
+    )?;
+    let curve_secret_key = curve_keys.as_ref().map(|k| k.secret_key.clone());
+    if curve_keys.is_some() {
+        tracing::info!("ZeroMQ CURVE encryption enabled");
+    }
 
     // Initialize ZeroMQ server
-    let zmq_server = ZmqServer::new(zmq_tx)?;
-    zmq_server
+    let zmq_server = ZmqServer::new(zmq_tx)?
+        .with_curve_secret_key(curve_secret_key.clone())
+        .with_recv_hwm(config.zeromq.recv_hwm)
+        .with_max_message_bytes(config.zeromq.max_message_bytes);
+    let zmq_receiver = zmq_server
         .start_receiver(&resolved_ports.receiver_address())
         .await?;
+    readiness.mark_zmq_receiver_bound();
     tracing::info!(
         "ZeroMQ receiver started on {}",
         resolved_ports.receiver_address()
     );
 
+    // Shared by the publisher (send failures) and the status engine
+    let runtime_status_metrics = Arc::new(RuntimeStatusMetrics::default());
+
     // Initialize unified ZeroMQ publisher
-    let zmq_publisher = Arc::new(ZmqConfigPublisher::new(&resolved_ports.sender_address())?);
+    let zmq_publisher = Arc::new(ZmqConfigPublisher::new_with_options(
+        &resolved_ports.sender_address(),
+        curve_secret_key.as_deref(),
+        Some(config.zeromq.send_hwm),
+        (config.zeromq.trade_batch_window_ms > 0)
+            .then(|| std::time::Duration::from_millis(config.zeromq.trade_batch_window_ms)),
+        runtime_status_metrics.clone(),
+    )?);
     tracing::info!(
         "ZeroMQ unified publisher started on {}",
         resolved_ports.sender_address()
     );
 
     // Initialize copy engine
-    let copy_engine = Arc::new(CopyEngine::new());
-    let runtime_status_metrics = Arc::new(RuntimeStatusMetrics::default());
+    let copy_engine =
+        Arc::new(CopyEngine::new().with_symbol_specs(Arc::new(config.symbol_spec_registry())));
+
+    // Webhook alerts, shared so transitions are detected across heartbeat and timeout paths
+    let status_notifier: Arc<dyn ports::StatusNotifier> = Arc::new(
+        adapters::outbound::notification::webhook::WebhookNotifier::new(
+            db.clone() as Arc<dyn ports::TradeGroupRepository>
+        ),
+    );
 
     // Spawn ZeroMQ message processing task
     tracing::info!("Creating MessageHandler...");
-    {
+    let message_processor = {
         // Construct StatusService with Hexagonal adapters
         let snapshot_broadcaster = adapters::inbound::http::SnapshotBroadcaster::new(
             broadcast_tx.clone(),
@@ -134,7 +180,8 @@ pub async fn setup(
             vlogs_controller
                 .clone()
                 .map(|c| Arc::new(c) as Arc<dyn crate::ports::outbound::VLogsConfigProvider>),
-        );
+        )
+        .with_notifier(status_notifier.clone());
 
         // Create WebSocket broadcaster for DisconnectionService
         let ws_broadcaster = Arc::new(
@@ -149,7 +196,8 @@ pub async fn setup(
                 zmq_publisher.clone(),
                 ws_broadcaster.clone(),
                 runtime_status_metrics.clone(),
-            ),
+            )
+            .with_notifier(status_notifier.clone()),
         );
 
         let handler = MessageHandler::new(
@@ -168,17 +216,18 @@ pub async fn setup(
             "MessageHandler created with StatusService, spawning message processing task..."
         );
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             while let Some(msg) = zmq_rx.recv().await {
                 handler.handle_message(msg).await;
             }
         });
         tracing::info!("Message processing task spawned");
-    }
+        task
+    };
 
     // Spawn timeout checker task
     tracing::info!("Spawning timeout checker task...");
-    {
+    let timeout_checker = {
         // Re-create broadcaster/service instances for the timeout handler closure if needed?
         // Actually, we can just share the Arc if we lift the definition out of the MessageHandler block.
         // But since we are inside `setup` and moved handler into spawn, we need to create service outside or handle cloning.
@@ -201,20 +250,58 @@ pub async fn setup(
                 zmq_publisher.clone(),
                 ws_broadcaster.clone(),
                 runtime_status_metrics.clone(),
-            ),
+            )
+            .with_notifier(status_notifier.clone()),
         );
 
-        let handler = connection_manager::RealTimeoutActionHandler::new(disconnection_service);
+        let handler = connection_manager::RealTimeoutActionHandler::new(
+            disconnection_service,
+            db.clone(),
+            broadcast_tx.clone(),
+        );
         let monitor = connection_manager::TimeoutMonitor::new(
             connection_manager.clone(),
             std::sync::Arc::new(handler),
         );
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             monitor.run().await;
         });
         tracing::info!("Timeout checker task spawned");
-    }
+        task
+    };
+
+    // Spawn latency probe task
+    let latency_probe = {
+        let probe = adapters::infrastructure::latency_probe::LatencyProbe::new(
+            connection_manager.clone(),
+            zmq_publisher.clone(),
+        );
+        let task = tokio::spawn(async move {
+            probe.run().await;
+        });
+        tracing::info!("Latency probe task spawned");
+        task
+    };
+
+    // Spawn active schedule monitor task
+    let schedule_monitor = {
+        let monitor = adapters::infrastructure::schedule_monitor::ScheduleMonitor::new(
+            db.clone(),
+            RuntimeStatusUpdater::with_metrics(
+                db.clone(),
+                connection_manager.clone(),
+                runtime_status_metrics.clone(),
+            ),
+            zmq_publisher.clone() as Arc<dyn ports::ConfigPublisher>,
+            broadcast_tx.clone(),
+        );
+        let task = tokio::spawn(async move {
+            monitor.run().await;
+        });
+        tracing::info!("Active schedule monitor task spawned");
+        task
+    };
 
     Ok(ServiceRegistry {
         db,
@@ -225,5 +312,16 @@ pub async fn setup(
         vlogs_controller,
         runtime_status_metrics,
         log_buffer,
+        signal_injector,
+        background: BackgroundTasks {
+            zmq_server,
+            zmq_receiver,
+            message_processor,
+            periodic: [timeout_checker, latency_probe, schedule_monitor]
+                .into_iter()
+                .chain(db_recovery)
+                .collect(),
+            vlogs: vlogs_task,
+        },
     })
 }
@@ -0,0 +1,154 @@
+//! Graceful shutdown
+//!
+//! `main` races the HTTPS server against `shutdown_signal()`; on a stop
+//! request the background tasks spawned during bootstrap are stopped in
+//! dependency order (ZMQ receiver -> message processing -> VictoriaLogs).
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::adapters::outbound::messaging::ZmqServer;
+use crate::adapters::outbound::observability::victoria_logs::VictoriaLogsHandle;
+
+/// Resolves when the process is asked to stop (Ctrl-C, or SIGTERM on unix)
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to install SIGTERM handler: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    wait_for_signal(tokio::signal::ctrl_c(), terminate).await;
+}
+
+/// Resolve on whichever trigger fires first. A Ctrl-C handler that fails to
+/// install is logged and ignored so it cannot trigger a shutdown by itself.
+async fn wait_for_signal(
+    ctrl_c: impl Future<Output = std::io::Result<()>>,
+    terminate: impl Future<Output = ()>,
+) {
+    let ctrl_c = async {
+        match ctrl_c.await {
+            Ok(()) => "Ctrl-C",
+            Err(e) => {
+                tracing::error!("Failed to listen for Ctrl-C: {}", e);
+                std::future::pending().await
+            }
+        }
+    };
+    let terminate = async {
+        terminate.await;
+        "SIGTERM"
+    };
+
+    let signal = tokio::select! {
+        signal = ctrl_c => signal,
+        signal = terminate => signal,
+    };
+    tracing::info!("Received {}, shutting down", signal);
+}
+
+/// Background tasks spawned during bootstrap
+pub struct BackgroundTasks {
+    pub zmq_server: ZmqServer,
+    pub zmq_receiver: JoinHandle<()>,
+    pub message_processor: JoinHandle<()>,
+    /// Loops that never finish on their own (timeout monitor, latency probe,
+    /// degraded database recovery until it reconnects)
+    pub periodic: Vec<JoinHandle<()>>,
+    pub vlogs: Option<(VictoriaLogsHandle, JoinHandle<()>)>,
+}
+
+impl BackgroundTasks {
+    /// Stop every task, waiting up to `timeout` for each one that drains.
+    /// Tasks still running after the timeout are abandoned with a warning.
+    pub async fn shutdown(self, timeout: Duration) {
+        // The receiver thread polls its flag every 100ms
+        self.zmq_server.shutdown();
+        join_with_timeout("ZMQ receiver", self.zmq_receiver, timeout).await;
+
+        // Dropping the last sender lets the processor drain the queue and exit
+        drop(self.zmq_server);
+        join_with_timeout("Message processing", self.message_processor, timeout).await;
+
+        for task in self.periodic {
+            task.abort();
+        }
+
+        // Flush buffered logs last so the shutdown messages above are included
+        if let Some((handle, task)) = self.vlogs {
+            handle.shutdown().await;
+            join_with_timeout("VictoriaLogs flusher", task, timeout).await;
+        }
+    }
+}
+
+async fn join_with_timeout(name: &str, task: JoinHandle<()>, timeout: Duration) {
+    match tokio::time::timeout(timeout, task).await {
+        Ok(Ok(())) => tracing::info!("{} stopped", name),
+        Ok(Err(e)) => tracing::warn!("{} task failed: {}", name, e),
+        Err(_) => tracing::warn!("{} did not stop within {:?}", name, timeout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn test_shutdown_signal_resolves_on_trigger() {
+        let (ctrl_c_tx, ctrl_c_rx) = oneshot::channel::<()>();
+        let (_terminate_tx, terminate_rx) = oneshot::channel::<()>();
+        let ctrl_c = async move { ctrl_c_rx.await.map_err(std::io::Error::other) };
+        let terminate = async move {
+            let _ = terminate_rx.await;
+        };
+        let mut waiting = Box::pin(wait_for_signal(ctrl_c, terminate));
+
+        // Nothing fired yet
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut waiting)
+                .await
+                .is_err()
+        );
+
+        ctrl_c_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("shutdown signal should resolve after the trigger");
+    }
+
+    #[tokio::test]
+    async fn test_failed_ctrl_c_handler_does_not_trigger_shutdown() {
+        let ctrl_c = async { Err(std::io::Error::other("unsupported")) };
+        let (terminate_tx, terminate_rx) = oneshot::channel::<()>();
+        let terminate = async move {
+            let _ = terminate_rx.await;
+        };
+        let mut waiting = Box::pin(wait_for_signal(ctrl_c, terminate));
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut waiting)
+                .await
+                .is_err()
+        );
+
+        terminate_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("SIGTERM should still trigger shutdown");
+    }
+}
@@ -1,6 +1,8 @@
+use crate::domain::services::symbol_specs::{SymbolSpec, SymbolSpecRegistry};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +24,37 @@ pub struct Config {
     pub victoria_logs: VictoriaLogsConfig,
     #[serde(default)]
     pub symbol_mapping: SymbolMappingConfig,
+    /// Per-symbol contract specifications added to or replacing the built-in table
+    #[serde(default)]
+    pub symbol_specs: HashMap<String, SymbolSpec>,
+    #[serde(default)]
+    pub security: SecurityConfig,
+}
+
+/// REST API access control
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SecurityConfig {
+    /// Bearer token required on `/api/*` and `/ws` (empty = no authentication)
+    #[serde(default)]
+    pub api_token: String,
+    /// POST/PUT/DELETE requests allowed per client IP per minute (0 = unlimited)
+    #[serde(default)]
+    pub requests_per_minute: u32,
+}
+
+impl SecurityConfig {
+    /// Token to enforce, or None when authentication is disabled
+    pub fn api_token(&self) -> Option<&str> {
+        let token = self.api_token.trim();
+        (!token.is_empty()).then_some(token)
+    }
+}
+
+impl Config {
+    /// Built-in symbol specifications overlaid with `[symbol_specs]`
+    pub fn symbol_spec_registry(&self) -> SymbolSpecRegistry {
+        SymbolSpecRegistry::new(&self.symbol_specs)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +90,10 @@ fn default_synonym_groups() -> Vec<Vec<String>> {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Send WebSocket events as legacy colon-delimited strings (e.g. `trade_copied:SLAVE:EURUSD:0.1:1`)
+    /// instead of JSON objects, for Web UI builds that predate JSON events
+    #[serde(default)]
+    pub legacy_ws_events: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,15 +133,21 @@ pub struct LoggingConfig {
     /// Prefix for log file names
     #[serde(default = "default_log_file_prefix")]
     pub file_prefix: String,
-    /// Rotation strategy: "daily", "hourly", or "never"
+    /// Rotation strategy: "daily", "hourly", "minutely", "never", or "size"
     #[serde(default = "default_log_rotation")]
     pub rotation: String,
+    /// Size limit of one log file with `rotation = "size"` (bytes)
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
     /// Maximum number of log files to keep (0 = unlimited)
     #[serde(default = "default_max_files")]
     pub max_files: u32,
     /// Maximum age of log files in days (0 = unlimited)
     #[serde(default = "default_max_age_days")]
     pub max_age_days: u32,
+    /// Entries kept in the in-memory buffer served by `/api/logs` (oldest dropped first)
+    #[serde(default = "default_log_buffer_capacity")]
+    pub buffer_capacity: usize,
 }
 
 fn default_logging_enabled() -> bool {
@@ -119,12 +162,18 @@ fn default_log_file_prefix() -> String {
 fn default_log_rotation() -> String {
     "daily".to_string()
 }
+fn default_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
 fn default_max_files() -> u32 {
     30
 }
 fn default_max_age_days() -> u32 {
     90
 }
+fn default_log_buffer_capacity() -> usize {
+    crate::adapters::infrastructure::log_buffer::DEFAULT_LOG_BUFFER_CAPACITY
+}
 
 impl Default for LoggingConfig {
     fn default() -> Self {
@@ -133,8 +182,10 @@ impl Default for LoggingConfig {
             directory: default_log_directory(),
             file_prefix: default_log_file_prefix(),
             rotation: default_log_rotation(),
+            max_file_bytes: default_max_file_bytes(),
             max_files: default_max_files(),
             max_age_days: default_max_age_days(),
+            buffer_capacity: default_log_buffer_capacity(),
         }
     }
 }
@@ -145,6 +196,10 @@ pub struct InstallerConfig {
     /// If not set, uses current_dir() (production default)
     #[serde(default)]
     pub components_base_path: Option<String>,
+    /// MT4/MT5 install directories not registered in the Windows registry
+    /// (portable terminals), listed alongside the detected installations
+    #[serde(default)]
+    pub manual_paths: Vec<String>,
 }
 
 /// TLS configuration for HTTPS server
@@ -160,6 +215,13 @@ pub struct TlsConfig {
     /// Certificate validity period in days
     #[serde(default = "default_cert_validity_days")]
     pub validity_days: u32,
+    /// Warn at startup (and flag in GET /api/tls-status) when the certificate
+    /// expires within this many days
+    #[serde(default = "default_cert_expiry_warning_days")]
+    pub expiry_warning_days: u32,
+    /// Regenerate an expired self-signed certificate at startup
+    #[serde(default = "default_auto_regenerate_expired")]
+    pub auto_regenerate_expired: bool,
 }
 
 fn default_cert_path() -> String {
@@ -174,12 +236,22 @@ fn default_cert_validity_days() -> u32 {
     3650 // 10 years
 }
 
+fn default_cert_expiry_warning_days() -> u32 {
+    30
+}
+
+fn default_auto_regenerate_expired() -> bool {
+    true
+}
+
 impl Default for TlsConfig {
     fn default() -> Self {
         Self {
             cert_path: default_cert_path(),
             key_path: default_key_path(),
             validity_days: default_cert_validity_days(),
+            expiry_warning_days: default_cert_expiry_warning_days(),
+            auto_regenerate_expired: default_auto_regenerate_expired(),
         }
     }
 }
@@ -208,6 +280,9 @@ pub struct VictoriaLogsConfig {
     /// Minimum log level to send (DEBUG, INFO, WARN, ERROR)
     #[serde(default = "default_vlogs_log_level")]
     pub log_level: String,
+    /// Failed batches kept for retry while VictoriaLogs is unreachable (oldest dropped first)
+    #[serde(default = "default_vlogs_max_buffered_batches")]
+    pub max_buffered_batches: usize,
 }
 
 impl VictoriaLogsConfig {
@@ -241,6 +316,10 @@ fn default_vlogs_log_level() -> String {
     "INFO".to_string()
 }
 
+fn default_vlogs_max_buffered_batches() -> usize {
+    100
+}
+
 impl Default for VictoriaLogsConfig {
     fn default() -> Self {
         Self {
@@ -250,6 +329,7 @@ impl Default for VictoriaLogsConfig {
             flush_interval_secs: default_vlogs_flush_interval(),
             source: default_vlogs_source(),
             log_level: default_vlogs_log_level(),
+            max_buffered_batches: default_vlogs_max_buffered_batches(),
         }
     }
 }
@@ -257,6 +337,57 @@ impl Default for VictoriaLogsConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     pub url: String,
+    /// Directory where `POST /api/admin/backup` writes database copies
+    #[serde(default = "default_backup_dir")]
+    pub backup_dir: String,
+    /// SQLite: use WAL journaling with `synchronous=NORMAL`
+    #[serde(default = "default_wal_mode")]
+    pub wal_mode: bool,
+    /// SQLite: milliseconds to wait on a locked database before failing
+    #[serde(default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+    /// Attempts to open the database at startup before running degraded
+    #[serde(default = "default_connect_attempts")]
+    pub connect_attempts: u32,
+    /// Delay before the first startup retry (doubled after each failure)
+    #[serde(default = "default_connect_backoff_ms")]
+    pub connect_backoff_ms: u64,
+    /// How often a degraded relay retries the database
+    #[serde(default = "default_reconnect_interval_ms")]
+    pub reconnect_interval_ms: u64,
+}
+
+impl DatabaseConfig {
+    pub fn sqlite_tuning(&self) -> crate::adapters::outbound::persistence::SqliteTuning {
+        crate::adapters::outbound::persistence::SqliteTuning {
+            wal: self.wal_mode,
+            busy_timeout: std::time::Duration::from_millis(self.busy_timeout_ms),
+        }
+    }
+}
+
+fn default_backup_dir() -> String {
+    "backups".to_string()
+}
+
+fn default_wal_mode() -> bool {
+    true
+}
+
+fn default_busy_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_connect_attempts() -> u32 {
+    5
+}
+
+fn default_connect_backoff_ms() -> u64 {
+    500
+}
+
+fn default_reconnect_interval_ms() -> u64 {
+    10_000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -269,6 +400,102 @@ pub struct ZeroMqConfig {
     /// Set to 0 for dynamic port assignment
     pub sender_port: u16,
     pub timeout_seconds: i64,
+    /// Consecutive timeout checks past `timeout_seconds` before an EA is marked Timeout
+    #[serde(default = "default_missed_heartbeat_threshold")]
+    pub missed_heartbeat_threshold: u32,
+    /// Slave margin level (%) below which `low_margin_level` is raised (0 disables)
+    #[serde(default = "default_low_margin_level_threshold")]
+    pub low_margin_level_threshold: f64,
+    /// Seconds a cached PositionSnapshot stays valid for the positions API (0 = never expires)
+    #[serde(default = "default_position_snapshot_stale_secs")]
+    pub position_snapshot_stale_secs: i64,
+    /// Heartbeats whose EA timestamp is more than this many seconds ahead of
+    /// server time are rejected (0 disables the check)
+    #[serde(default = "default_max_heartbeat_skew_secs")]
+    pub max_heartbeat_skew_secs: i64,
+    /// EAs returning from Timeout/Offline within this many seconds of their last
+    /// heartbeat count as a flap and keep `connected_at` (0 = every return is a new session)
+    #[serde(default = "default_reconnect_grace_secs")]
+    pub reconnect_grace_secs: i64,
+    /// Trade signals buffered per member while its Slave is offline and replayed
+    /// on reconnect (0 disables replay)
+    #[serde(default)]
+    pub offline_replay_buffer_size: usize,
+    /// Send high-water mark for the PUB socket (messages queued per subscriber)
+    #[serde(default = "default_zmq_hwm")]
+    pub send_hwm: i32,
+    /// Receive high-water mark for the PULL socket
+    #[serde(default = "default_zmq_hwm")]
+    pub recv_hwm: i32,
+    /// Frames larger than this are dropped by the receiver without deserializing
+    #[serde(default = "default_max_message_bytes")]
+    pub max_message_bytes: usize,
+    /// Coalesce trade signals to the same Slave topic published within this many
+    /// milliseconds into one frame (0 = one frame per signal)
+    #[serde(default)]
+    pub trade_batch_window_ms: u64,
+    /// Optional CurveZMQ encryption ([zeromq.security])
+    #[serde(default)]
+    pub security: ZmqSecurityConfig,
+}
+
+fn default_missed_heartbeat_threshold() -> u32 {
+    1
+}
+
+fn default_low_margin_level_threshold() -> f64 {
+    150.0
+}
+
+fn default_position_snapshot_stale_secs() -> i64 {
+    300
+}
+
+fn default_max_heartbeat_skew_secs() -> i64 {
+    300
+}
+
+fn default_reconnect_grace_secs() -> i64 {
+    300
+}
+
+fn default_zmq_hwm() -> i32 {
+    1000 // libzmq default
+}
+
+fn default_max_message_bytes() -> usize {
+    1024 * 1024
+}
+
+/// CurveZMQ settings for the PULL/PUB sockets (plaintext when disabled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZmqSecurityConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path to the server public key (Z85 text, distributed to EAs)
+    #[serde(default = "default_curve_public_key_path")]
+    pub server_public_key_path: String,
+    /// Path to the server secret key (Z85 text, never leaves the relay)
+    #[serde(default = "default_curve_secret_key_path")]
+    pub server_secret_key_path: String,
+}
+
+fn default_curve_public_key_path() -> String {
+    "keys/zmq_server.pub".to_string()
+}
+
+fn default_curve_secret_key_path() -> String {
+    "keys/zmq_server.key".to_string()
+}
+
+impl Default for ZmqSecurityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            server_public_key_path: default_curve_public_key_path(),
+            server_secret_key_path: default_curve_secret_key_path(),
+        }
+    }
 }
 
 impl ZeroMqConfig {
@@ -413,15 +640,33 @@ impl Default for Config {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
+                legacy_ws_events: false,
             },
             webui: WebUIConfig::default(),
             database: DatabaseConfig {
                 url: "sqlite://sankey_copier.db?mode=rwc".to_string(),
+                backup_dir: default_backup_dir(),
+                wal_mode: default_wal_mode(),
+                busy_timeout_ms: default_busy_timeout_ms(),
+                connect_attempts: default_connect_attempts(),
+                connect_backoff_ms: default_connect_backoff_ms(),
+                reconnect_interval_ms: default_reconnect_interval_ms(),
             },
             zeromq: ZeroMqConfig {
                 receiver_port: 5555,
                 sender_port: 5556,
                 timeout_seconds: 30,
+                missed_heartbeat_threshold: 1,
+                low_margin_level_threshold: default_low_margin_level_threshold(),
+                position_snapshot_stale_secs: default_position_snapshot_stale_secs(),
+                max_heartbeat_skew_secs: default_max_heartbeat_skew_secs(),
+                reconnect_grace_secs: default_reconnect_grace_secs(),
+                offline_replay_buffer_size: 0,
+                send_hwm: default_zmq_hwm(),
+                recv_hwm: default_zmq_hwm(),
+                max_message_bytes: default_max_message_bytes(),
+                trade_batch_window_ms: 0,
+                security: ZmqSecurityConfig::default(),
             },
             cors: CorsConfig::default(),
             logging: LoggingConfig::default(),
@@ -429,6 +674,8 @@ impl Default for Config {
             tls: TlsConfig::default(),
             victoria_logs: VictoriaLogsConfig::default(),
             symbol_mapping: SymbolMappingConfig::default(),
+            symbol_specs: HashMap::new(),
+            security: SecurityConfig::default(),
         }
     }
 }
@@ -550,15 +797,33 @@ mod tests {
             server: ServerConfig {
                 host: "127.0.0.1".to_string(),
                 port: 9090,
+                legacy_ws_events: false,
             },
             webui: WebUIConfig::default(),
             database: DatabaseConfig {
                 url: "sqlite://test.db".to_string(),
+                backup_dir: default_backup_dir(),
+                wal_mode: default_wal_mode(),
+                busy_timeout_ms: default_busy_timeout_ms(),
+                connect_attempts: default_connect_attempts(),
+                connect_backoff_ms: default_connect_backoff_ms(),
+                reconnect_interval_ms: default_reconnect_interval_ms(),
             },
             zeromq: ZeroMqConfig {
                 receiver_port: 6666,
                 sender_port: 6667,
                 timeout_seconds: 60,
+                missed_heartbeat_threshold: 1,
+                low_margin_level_threshold: default_low_margin_level_threshold(),
+                position_snapshot_stale_secs: default_position_snapshot_stale_secs(),
+                max_heartbeat_skew_secs: default_max_heartbeat_skew_secs(),
+                reconnect_grace_secs: default_reconnect_grace_secs(),
+                offline_replay_buffer_size: 0,
+                send_hwm: default_zmq_hwm(),
+                recv_hwm: default_zmq_hwm(),
+                max_message_bytes: default_max_message_bytes(),
+                trade_batch_window_ms: 0,
+                security: ZmqSecurityConfig::default(),
             },
             cors: CorsConfig::default(),
             logging: LoggingConfig::default(),
@@ -569,6 +834,8 @@ mod tests {
                 ..VictoriaLogsConfig::default()
             },
             symbol_mapping: SymbolMappingConfig::default(),
+            symbol_specs: HashMap::new(),
+            security: SecurityConfig::default(),
         };
 
         assert_eq!(config.server_address(), "127.0.0.1:9090");
@@ -608,10 +875,65 @@ timeout_seconds = 45
         assert_eq!(config.server.host, "127.0.0.1");
         assert_eq!(config.server.port, 9000);
         assert_eq!(config.database.url, "sqlite://custom.db");
+        assert_eq!(config.database.backup_dir, "backups");
+        assert!(config.database.wal_mode);
+        assert_eq!(config.database.busy_timeout_ms, 5000);
+        assert_eq!(config.database.connect_attempts, 5);
+        assert_eq!(config.database.reconnect_interval_ms, 10_000);
         assert_eq!(config.zeromq.receiver_port, 7777);
         assert_eq!(config.zeromq.sender_port, 7778);
         assert_eq!(config.zeromq.timeout_seconds, 45);
+        assert_eq!(config.zeromq.missed_heartbeat_threshold, 1);
+        assert!(!config.zeromq.security.enabled);
+        assert_eq!(config.zeromq.send_hwm, 1000);
+        assert_eq!(config.zeromq.recv_hwm, 1000);
+        assert_eq!(config.zeromq.max_message_bytes, 1024 * 1024);
     }
+
+    #[test]
+    fn test_zeromq_security_deserialization() {
+        let toml_str = r#"
+[server]
+host = "127.0.0.1"
+port = 9000
+
+[database]
+url = "sqlite://custom.db"
+
+[zeromq]
+receiver_port = 7777
+sender_port = 7778
+timeout_seconds = 45
+
+[zeromq.security]
+enabled = true
+server_secret_key_path = "secrets/relay.key"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.zeromq.security.enabled);
+        assert_eq!(
+            config.zeromq.security.server_secret_key_path,
+            "secrets/relay.key"
+        );
+        assert_eq!(
+            config.zeromq.security.server_public_key_path,
+            "keys/zmq_server.pub"
+        );
+    }
+
+    #[test]
+    fn test_security_api_token() {
+        let mut config = Config::default();
+        assert_eq!(config.security.api_token(), None);
+
+        config.security.api_token = "  ".to_string();
+        assert_eq!(config.security.api_token(), None);
+
+        config.security.api_token = " token ".to_string();
+        assert_eq!(config.security.api_token(), Some("token"));
+    }
+
     #[test]
     fn test_update_victoria_logs_enabled() {
         use std::io::Write;
@@ -761,4 +1083,30 @@ synonym_groups = [
             .iter()
             .any(|g| g.contains(&"BTCUSD".to_string())));
     }
+
+    #[test]
+    fn test_symbol_specs_config_parsing() {
+        let toml_str = r#"
+[server]
+host = "127.0.0.1"
+port = 8080
+
+[database]
+url = "sqlite::memory:"
+
+[zeromq]
+receiver_port = 5555
+sender_port = 5556
+timeout_seconds = 30
+
+[symbol_specs]
+US30 = { contract_size = 1.0, pip_value = 1.0, digits = 1 }
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let registry = config.symbol_spec_registry();
+        assert_eq!(registry.spec("US30.").contract_size, 1.0);
+        assert_eq!(registry.spec("US30.").pip_value, 1.0);
+        assert_eq!(registry.spec("XAUUSD").contract_size, 100.0);
+        assert!(Config::default().symbol_specs.is_empty());
+    }
 }
@@ -1,10 +1,11 @@
 use chrono::{DateTime, Utc};
-use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage};
+use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage, SymbolMapping};
 
 use crate::domain::models::{MasterSettings, SlaveSettings};
 use crate::domain::services::status_calculator::{
-    evaluate_master_status, evaluate_member_status, ConnectionSnapshot, MasterIntent,
-    MasterStatusResult, MemberStatusResult, SlaveIntent,
+    apply_currency_check, apply_schedule_check, apply_self_copy_check, evaluate_master_status,
+    evaluate_member_status, ConnectionSnapshot, MasterIntent, MasterStatusResult,
+    MemberStatusResult, SlaveIntent,
 };
 
 /// Context needed to build a MasterConfigMessage.
@@ -27,6 +28,9 @@ pub struct SlaveConfigContext<'a> {
     pub master_status_result: MasterStatusResult,
     pub slave_settings: &'a SlaveSettings,
     pub master_equity: Option<f64>,
+    /// Account currencies from the latest Heartbeats (None when unknown)
+    pub master_currency: Option<String>,
+    pub slave_currency: Option<String>,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -69,13 +73,41 @@ impl ConfigBuilder {
     /// Build a SlaveConfigMessage for a single Member (Master-Slave connection).
     /// Uses `evaluate_member_status` to evaluate based on the specific Master's state.
     pub fn build_slave_config(context: SlaveConfigContext) -> SlaveConfigBundle {
-        let status_result = evaluate_member_status(
+        let mut status_result = evaluate_member_status(
             context.intent,
             context.slave_connection_snapshot,
             &context.master_status_result,
         );
+        apply_currency_check(
+            &mut status_result,
+            context.master_currency.as_deref(),
+            context.slave_currency.as_deref(),
+        );
+        apply_self_copy_check(
+            &mut status_result,
+            &context.master_account,
+            &context.slave_account,
+        );
+        apply_schedule_check(
+            &mut status_result,
+            context.slave_settings,
+            context.timestamp,
+        );
 
         let settings = context.slave_settings;
+        // The EA takes the first matching mapping, so send them in matching order
+        let mut symbol_mappings = settings.symbol_mappings.clone();
+        SymbolMapping::sort_by_priority(&mut symbol_mappings);
+        // Symbols arrive verbatim, so the EA must not apply prefix/suffix/mappings either
+        let (symbol_prefix, symbol_suffix) = if settings.disable_symbol_conversion {
+            symbol_mappings.clear();
+            (None, None)
+        } else {
+            (
+                settings.symbol_prefix.clone(),
+                settings.symbol_suffix.clone(),
+            )
+        };
         let config = SlaveConfigMessage {
             account_id: context.slave_account,
             master_account: context.master_account,
@@ -85,14 +117,16 @@ impl ConfigBuilder {
             lot_calculation_mode: settings.lot_calculation_mode.clone().into(),
             lot_multiplier: settings.lot_multiplier,
             reverse_trade: settings.reverse_trade,
-            symbol_mappings: settings.symbol_mappings.clone(),
+            reverse_symbols: settings.reverse_symbols.clone(),
+            symbol_mappings,
             filters: settings.filters.clone(),
             config_version: settings.config_version,
-            symbol_prefix: settings.symbol_prefix.clone(),
-            symbol_suffix: settings.symbol_suffix.clone(),
+            symbol_prefix,
+            symbol_suffix,
             source_lot_min: settings.source_lot_min,
             source_lot_max: settings.source_lot_max,
             master_equity: context.master_equity,
+            fixed_lot: settings.fixed_lot,
             // Open Sync Policy settings
             sync_mode: settings.sync_mode.clone().into(),
             limit_order_expiry_min: settings.limit_order_expiry_min,
@@ -118,8 +152,8 @@ impl ConfigBuilder {
 mod tests {
     use super::*;
     use crate::domain::models::{
-        ConnectionStatus, MasterSettings, SlaveSettings, WarningCode, STATUS_CONNECTED,
-        STATUS_DISABLED, STATUS_ENABLED,
+        ConnectionStatus, LotCalculationMode, MasterSettings, SlaveSettings, WarningCode,
+        STATUS_CONNECTED, STATUS_DISABLED, STATUS_ENABLED,
     };
     use crate::domain::services::status_calculator::{
         ConnectionSnapshot, MasterIntent, SlaveIntent,
@@ -129,6 +163,7 @@ mod tests {
         ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         }
     }
 
@@ -153,6 +188,12 @@ mod tests {
             symbol_prefix: Some("pre.".into()),
             symbol_suffix: Some(".suf".into()),
             config_version: 7,
+            case_insensitive_symbols: false,
+            closes_only: false,
+            max_signal_age_ms: None,
+            max_signals_per_sec: None,
+            webhook_url: None,
+            total_lot_budget: None,
         };
 
         let context = MasterConfigContext {
@@ -179,6 +220,12 @@ mod tests {
             symbol_prefix: None,
             symbol_suffix: None,
             config_version: 4,
+            case_insensitive_symbols: false,
+            closes_only: false,
+            max_signal_age_ms: None,
+            max_signals_per_sec: None,
+            webhook_url: None,
+            total_lot_budget: None,
         };
 
         let context = MasterConfigContext {
@@ -213,6 +260,8 @@ mod tests {
             master_status_result: connected_master(),
             slave_settings: &SlaveSettings::default(),
             master_equity: Some(1000.0),
+            master_currency: None,
+            slave_currency: None,
             timestamp: chrono::Utc::now(),
         };
 
@@ -238,6 +287,8 @@ mod tests {
             master_status_result: offline_master(),
             slave_settings: &SlaveSettings::default(),
             master_equity: Some(500.0),
+            master_currency: None,
+            slave_currency: None,
             timestamp: chrono::Utc::now(),
         };
 
@@ -258,6 +309,7 @@ mod tests {
         let offline_slave = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Offline),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
 
         let context = SlaveConfigContext {
@@ -271,6 +323,8 @@ mod tests {
             master_status_result: connected_master(),
             slave_settings: &SlaveSettings::default(),
             master_equity: Some(500.0),
+            master_currency: None,
+            slave_currency: None,
             timestamp: chrono::Utc::now(),
         };
 
@@ -282,4 +336,67 @@ mod tests {
             .warning_codes
             .contains(&WarningCode::SlaveOffline));
     }
+
+    #[test]
+    fn slave_builder_omits_symbol_settings_when_conversion_disabled() {
+        let settings = SlaveSettings {
+            symbol_prefix: Some("fx.".into()),
+            symbol_suffix: Some(".m".into()),
+            symbol_mappings: vec![SymbolMapping {
+                source_symbol: "XAUUSD".into(),
+                target_symbol: "GOLD".into(),
+                priority: 0,
+            }],
+            disable_symbol_conversion: true,
+            ..SlaveSettings::default()
+        };
+        let context = SlaveConfigContext {
+            slave_account: "SLAVE_001".into(),
+            master_account: "MASTER_001".into(),
+            trade_group_id: "MASTER_001".into(),
+            intent: SlaveIntent {
+                web_ui_enabled: true,
+            },
+            slave_connection_snapshot: online_snapshot(),
+            master_status_result: connected_master(),
+            slave_settings: &settings,
+            master_equity: None,
+            master_currency: None,
+            slave_currency: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        let bundle = ConfigBuilder::build_slave_config(context);
+        assert!(bundle.config.symbol_mappings.is_empty());
+        assert_eq!(bundle.config.symbol_prefix, None);
+        assert_eq!(bundle.config.symbol_suffix, None);
+    }
+
+    #[test]
+    fn slave_builder_carries_fixed_lot() {
+        // The EA sizes position snapshot syncs with this value in fixed_lot mode
+        let settings = SlaveSettings {
+            lot_calculation_mode: LotCalculationMode::FixedLot,
+            fixed_lot: Some(0.01),
+            ..SlaveSettings::default()
+        };
+        let context = SlaveConfigContext {
+            slave_account: "SLAVE_001".into(),
+            master_account: "MASTER_001".into(),
+            trade_group_id: "MASTER_001".into(),
+            intent: SlaveIntent {
+                web_ui_enabled: true,
+            },
+            slave_connection_snapshot: online_snapshot(),
+            master_status_result: connected_master(),
+            slave_settings: &settings,
+            master_equity: None,
+            master_currency: None,
+            slave_currency: None,
+            timestamp: chrono::Utc::now(),
+        };
+
+        let bundle = ConfigBuilder::build_slave_config(context);
+        assert_eq!(bundle.config.fixed_lot, Some(0.01));
+    }
 }
@@ -0,0 +1,126 @@
+// relay-server/src/domain/models/broker_profile.rs
+//
+// BrokerProfile model: symbol prefix/suffix and mappings shared by every
+// member on the same broker. Members reference a profile by name through
+// `slave_settings.broker_profile`; their own values take precedence.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{SlaveSettings, SymbolMapping};
+
+/// Reusable symbol defaults for Slaves on one broker
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BrokerProfile {
+    /// Unique profile name referenced by `slave_settings.broker_profile`
+    pub name: String,
+
+    /// Symbol prefix added to Slave symbols unless the member sets its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_prefix: Option<String>,
+
+    /// Symbol suffix added to Slave symbols unless the member sets its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol_suffix: Option<String>,
+
+    /// Symbol mappings used for Master symbols the member does not map itself
+    #[serde(default)]
+    pub symbol_map: Vec<SymbolMapping>,
+
+    /// Timestamp when the profile was created
+    #[serde(default)]
+    pub created_at: String,
+
+    /// Timestamp when the profile was last updated
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+impl BrokerProfile {
+    /// Settings of a member referencing this profile as used for copying and
+    /// config distribution. Non-empty member prefix/suffix win, and member
+    /// mappings replace profile mappings for the same source symbol.
+    pub fn apply_to(&self, settings: &SlaveSettings) -> SlaveSettings {
+        fn member_or_profile(member: &Option<String>, profile: &Option<String>) -> Option<String> {
+            match member {
+                Some(value) if !value.is_empty() => member.clone(),
+                _ => profile.clone().or_else(|| member.clone()),
+            }
+        }
+
+        let mut effective = settings.clone();
+        effective.symbol_prefix = member_or_profile(&settings.symbol_prefix, &self.symbol_prefix);
+        effective.symbol_suffix = member_or_profile(&settings.symbol_suffix, &self.symbol_suffix);
+        effective.symbol_mappings.extend(
+            self.symbol_map
+                .iter()
+                .filter(|mapping| {
+                    !settings
+                        .symbol_mappings
+                        .iter()
+                        .any(|own| own.source_symbol == mapping.source_symbol)
+                })
+                .cloned(),
+        );
+        effective
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(source: &str, target: &str) -> SymbolMapping {
+        SymbolMapping {
+            source_symbol: source.to_string(),
+            target_symbol: target.to_string(),
+            priority: 0,
+        }
+    }
+
+    fn profile() -> BrokerProfile {
+        BrokerProfile {
+            name: "xm".to_string(),
+            symbol_prefix: Some("x.".to_string()),
+            symbol_suffix: Some("#".to_string()),
+            symbol_map: vec![mapping("XAUUSD", "GOLD"), mapping("US30", "DJ30")],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_profile_supplies_unset_member_values() {
+        let settings = SlaveSettings {
+            broker_profile: Some("xm".to_string()),
+            symbol_prefix: Some(String::new()),
+            ..SlaveSettings::default()
+        };
+
+        let effective = profile().apply_to(&settings);
+        assert_eq!(effective.symbol_prefix, Some("x.".to_string()));
+        assert_eq!(effective.symbol_suffix, Some("#".to_string()));
+        assert_eq!(
+            effective.symbol_mappings,
+            vec![mapping("XAUUSD", "GOLD"), mapping("US30", "DJ30")]
+        );
+    }
+
+    #[test]
+    fn test_member_values_override_profile() {
+        let settings = SlaveSettings {
+            broker_profile: Some("xm".to_string()),
+            symbol_suffix: Some(".pro".to_string()),
+            symbol_mappings: vec![mapping("US30", "WS30")],
+            ..SlaveSettings::default()
+        };
+
+        let effective = profile().apply_to(&settings);
+        assert_eq!(effective.symbol_prefix, Some("x.".to_string()));
+        assert_eq!(effective.symbol_suffix, Some(".pro".to_string()));
+        // The member's US30 mapping replaces the profile's; other profile mappings remain
+        assert_eq!(
+            effective.symbol_mappings,
+            vec![mapping("US30", "WS30"), mapping("XAUUSD", "GOLD")]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// relay-server/src/domain/models/config_export.rs
+//
+// Config export model: a portable JSON document holding every TradeGroup,
+// its MasterSettings and all member SlaveSettings. Used for backup/restore
+// and for moving a setup to another machine.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::{MasterSettings, SlaveSettings};
+
+/// Current export document format version
+pub const CONFIG_EXPORT_VERSION: u32 = 1;
+
+/// Full settings export (all TradeGroups)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigExport {
+    /// Document format version (must equal CONFIG_EXPORT_VERSION on import)
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub trade_groups: Vec<TradeGroupExport>,
+}
+
+/// One TradeGroup (Master) with its members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeGroupExport {
+    /// Master account ID
+    pub id: String,
+    pub master_settings: MasterSettings,
+    #[serde(default)]
+    pub members: Vec<MemberExport>,
+}
+
+/// One Slave member (runtime status is not exported)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberExport {
+    pub slave_account: String,
+    pub slave_settings: SlaveSettings,
+    /// User intent flag (Web UI toggle)
+    #[serde(default)]
+    pub enabled_flag: bool,
+}
+
+impl ConfigExport {
+    /// Validate the document before it is written to the database
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != CONFIG_EXPORT_VERSION {
+            return Err(format!(
+                "Unsupported export version {} (expected {})",
+                self.version, CONFIG_EXPORT_VERSION
+            ));
+        }
+
+        let mut group_ids = HashSet::new();
+        for group in &self.trade_groups {
+            if group.id.trim().is_empty() {
+                return Err("TradeGroup id must not be empty".to_string());
+            }
+            if !group_ids.insert(group.id.as_str()) {
+                return Err(format!("Duplicate TradeGroup id '{}'", group.id));
+            }
+
+            let mut slaves = HashSet::new();
+            for member in &group.members {
+                if member.slave_account.trim().is_empty() {
+                    return Err(format!(
+                        "TradeGroup '{}': slave_account must not be empty",
+                        group.id
+                    ));
+                }
+                if !slaves.insert(member.slave_account.as_str()) {
+                    return Err(format!(
+                        "TradeGroup '{}': duplicate member '{}'",
+                        group.id, member.slave_account
+                    ));
+                }
+                member.slave_settings.validate().map_err(|e| {
+                    format!(
+                        "TradeGroup '{}', member '{}': {}",
+                        group.id, member.slave_account, e
+                    )
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of a config import
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigImportResult {
+    /// True when nothing was written
+    pub dry_run: bool,
+    /// TradeGroup IDs that did not exist and were (or would be) created
+    pub created: Vec<String>,
+    /// TradeGroup IDs that existed and were (or would be) overwritten
+    pub updated: Vec<String>,
+    /// TradeGroup IDs that already exist and were not overwritten
+    pub conflicts: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export_with(groups: Vec<TradeGroupExport>) -> ConfigExport {
+        ConfigExport {
+            version: CONFIG_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            trade_groups: groups,
+        }
+    }
+
+    fn group(id: &str, slaves: &[&str]) -> TradeGroupExport {
+        TradeGroupExport {
+            id: id.to_string(),
+            master_settings: MasterSettings::default(),
+            members: slaves
+                .iter()
+                .map(|s| MemberExport {
+                    slave_account: s.to_string(),
+                    slave_settings: SlaveSettings::default(),
+                    enabled_flag: true,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_export() {
+        let export = export_with(vec![group("M1", &["S1", "S2"]), group("M2", &["S1"])]);
+        assert!(export.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_documents() {
+        let mut export = export_with(vec![group("M1", &[])]);
+        export.version = 99;
+        assert!(export.validate().is_err());
+
+        let export = export_with(vec![group("M1", &[]), group("M1", &[])]);
+        assert!(export.validate().is_err());
+
+        let export = export_with(vec![group("M1", &["S1", "S1"])]);
+        assert!(export.validate().is_err());
+
+        let export = export_with(vec![group(" ", &[])]);
+        assert!(export.validate().is_err());
+
+        let mut export = export_with(vec![group("M1", &["S1"])]);
+        export.trade_groups[0].members[0].slave_settings.lot_step = Some(0.0);
+        let err = export.validate().unwrap_err();
+        assert!(err.contains("'M1'") && err.contains("'S1'"));
+    }
+}
@@ -1,10 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 // Re-export shared message types from DLL
 pub use sankey_copier_zmq::{
-    HeartbeatMessage, PositionSnapshotMessage, RegisterMessage, RequestConfigMessage,
+    HeartbeatMessage, PositionInfo, PositionSnapshotMessage, RegisterMessage, RequestConfigMessage,
     SymbolContext, SyncRequestMessage, UnregisterMessage,
 };
 
@@ -20,6 +20,20 @@ pub struct EaConnection {
     pub server: String,
     pub balance: f64,
     pub equity: f64,
+    /// Used margin reported by the latest heartbeat (0 for EAs that do not report margin)
+    #[serde(default)]
+    pub margin: f64,
+    #[serde(default)]
+    pub free_margin: f64,
+    /// Margin level in percent (0 when no margin is in use)
+    #[serde(default)]
+    pub margin_level: f64,
+    /// True when `margin_level` is below the relay's warning threshold
+    #[serde(default)]
+    pub low_margin_level: bool,
+    /// Number of open positions reported by the latest heartbeat
+    #[serde(default)]
+    pub open_positions: i32,
     pub currency: String,
     pub leverage: i64,
     pub last_heartbeat: DateTime<Utc>,
@@ -29,6 +43,89 @@ pub struct EaConnection {
     /// Detected symbol context reported by the EA (for auto-mapping suggestions)
     #[serde(default)]
     pub symbol_context: Option<SymbolContext>,
+    /// Symbol prefix reported by the latest heartbeat (EA input setting)
+    #[serde(default)]
+    pub symbol_prefix: Option<String>,
+    /// Symbol suffix reported by the latest heartbeat (EA input setting)
+    #[serde(default)]
+    pub symbol_suffix: Option<String>,
+    /// Symbol map reported by the latest heartbeat (e.g. "XAUUSD=GOLD,EURUSD=EUR")
+    #[serde(default)]
+    pub symbol_map: Option<String>,
+    /// Per-EA heartbeat timeout in seconds (overrides the global `timeout_seconds`)
+    #[serde(default)]
+    pub timeout_override: Option<i64>,
+    /// Consecutive timeout checks without a heartbeat (reset on every heartbeat)
+    #[serde(default)]
+    pub missed_heartbeats: u32,
+    /// Latest measured ping round-trip time in milliseconds (None if the EA never echoed a ping)
+    #[serde(default)]
+    pub last_latency_ms: Option<i64>,
+    /// Latest PositionSnapshot received from this EA (served by /api/connections/:id/positions)
+    #[serde(skip)]
+    pub position_snapshot: Option<PositionSnapshotCache>,
+    /// Loss since the first heartbeat of the current UTC day (for `max_daily_loss`)
+    #[serde(skip)]
+    pub daily_loss: Option<DailyLoss>,
+    /// EA-reported timestamp of the last accepted heartbeat (older ones are rejected as replays)
+    #[serde(skip)]
+    pub last_heartbeat_sent_at: Option<DateTime<Utc>>,
+    /// Reconnections after a Timeout/Offline within the reconnect grace period
+    /// (reset together with `connected_at` when the EA returns after a longer outage)
+    #[serde(default)]
+    pub flap_count: u32,
+}
+
+/// Result of applying a heartbeat to the connection table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatOutcome {
+    /// Unknown EA, auto-registered from the heartbeat
+    Registered,
+    /// Known EA, liveness and account info refreshed
+    Updated,
+    /// Heartbeat discarded (out of order or timestamp too far in the future)
+    Rejected,
+}
+
+/// Start-of-day balance and the largest loss seen since, for one UTC day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyLoss {
+    pub date: NaiveDate,
+    pub start_balance: f64,
+    /// Largest `start_balance - equity` seen today; never decreases within the day
+    pub max_loss: f64,
+}
+
+/// Position list from the latest PositionSnapshot, with its arrival time for staleness checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSnapshotCache {
+    pub positions: Vec<PositionInfo>,
+    pub received_at: DateTime<Utc>,
+}
+
+/// Connection lifecycle event kept in an EA's history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionEventKind {
+    /// First seen (Register, or a heartbeat from an unknown EA) or re-registered
+    Registered,
+    /// Heartbeat overdue but still below `missed_heartbeat_threshold`
+    HeartbeatGap,
+    Timeout,
+    /// Heartbeat received while Timeout/Offline
+    Reconnected,
+    /// Unregister message or forced disconnect
+    Unregistered,
+}
+
+/// Entry of an EA's connection history (`GET /api/connections/:id/history`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionEvent {
+    pub at: DateTime<Utc>,
+    pub ea_type: EaType,
+    pub event: ConnectionEventKind,
+    /// What triggered the event (human-readable)
+    pub reason: String,
 }
 
 impl Default for EaConnection {
@@ -43,6 +140,11 @@ impl Default for EaConnection {
             server: String::new(),
             balance: 0.0,
             equity: 0.0,
+            margin: 0.0,
+            free_margin: 0.0,
+            margin_level: 0.0,
+            low_margin_level: false,
+            open_positions: 0,
             currency: "USD".to_string(),
             leverage: 100,
             last_heartbeat: Utc::now(),
@@ -50,12 +152,86 @@ impl Default for EaConnection {
             connected_at: Utc::now(),
             is_trade_allowed: false,
             symbol_context: None,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_map: None,
+            timeout_override: None,
+            missed_heartbeats: 0,
+            last_latency_ms: None,
+            position_snapshot: None,
+            daily_loss: None,
+            last_heartbeat_sent_at: None,
+            flap_count: 0,
+        }
+    }
+}
+
+impl EaConnection {
+    /// Seconds since `connected_at` while the EA is up (0 when Timeout/Offline)
+    pub fn uptime_seconds(&self, now: DateTime<Utc>) -> i64 {
+        match self.status {
+            ConnectionStatus::Online | ConnectionStatus::Registered => {
+                (now - self.connected_at).num_seconds().max(0)
+            }
+            ConnectionStatus::Timeout | ConnectionStatus::Offline => 0,
+        }
+    }
+
+    /// Update today's loss from the current balance/equity.
+    /// The first call on a new UTC day restarts tracking from the current balance.
+    pub fn record_daily_loss(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.daily_loss.as_ref().is_none_or(|d| d.date != today) {
+            self.daily_loss = Some(DailyLoss {
+                date: today,
+                start_balance: self.balance,
+                max_loss: 0.0,
+            });
+        }
+        if let Some(daily) = self.daily_loss.as_mut() {
+            daily.max_loss = daily.max_loss.max(daily.start_balance - self.equity);
         }
     }
+
+    /// Largest loss seen today (None when no heartbeat has arrived yet this UTC day)
+    pub fn daily_loss(&self, now: DateTime<Utc>) -> Option<f64> {
+        self.daily_loss
+            .as_ref()
+            .filter(|d| d.date == now.date_naive())
+            .map(|d| d.max_loss)
+    }
+
+    /// Prefix to use when slave settings leave it unset:
+    /// the heartbeat-reported value first, then the prefix detected at registration.
+    pub fn fallback_symbol_prefix(&self) -> Option<String> {
+        non_empty(self.symbol_prefix.as_deref()).or_else(|| {
+            non_empty(
+                self.symbol_context
+                    .as_ref()
+                    .map(|ctx| ctx.detected_prefix.as_str()),
+            )
+        })
+    }
+
+    /// Suffix to use when slave settings leave it unset:
+    /// the heartbeat-reported value first, then the suffix detected at registration.
+    pub fn fallback_symbol_suffix(&self) -> Option<String> {
+        non_empty(self.symbol_suffix.as_deref()).or_else(|| {
+            non_empty(
+                self.symbol_context
+                    .as_ref()
+                    .map(|ctx| ctx.detected_suffix.as_str()),
+            )
+        })
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|v| !v.is_empty()).map(str::to_string)
 }
 
 /// EAの種類
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum EaType {
     Master,
@@ -128,12 +304,15 @@ mod tests {
             lot_calculation_mode: LotCalculationMode::default(),
             lot_multiplier: Some(2.0),
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_mappings: vec![],
             filters: TradeFilters {
                 allowed_symbols: None,
                 blocked_symbols: None,
                 allowed_magic_numbers: None,
                 blocked_magic_numbers: None,
+                allowed_comment_substrings: None,
+                blocked_comment_substrings: None,
             },
             config_version: 1,
             symbol_prefix: None,
@@ -141,6 +320,7 @@ mod tests {
             source_lot_min: None,
             source_lot_max: None,
             master_equity: None,
+            fixed_lot: None,
             // Open Sync Policy defaults
             sync_mode: SyncMode::default(),
             limit_order_expiry_min: None,
@@ -163,4 +343,68 @@ mod tests {
         assert_eq!(deserialized.status, 2);
         assert_eq!(deserialized.config_version, 1);
     }
+
+    #[test]
+    fn test_fallback_symbol_prefix_suffix_precedence() {
+        use super::{EaConnection, SymbolContext};
+
+        let mut conn = EaConnection {
+            symbol_context: Some(SymbolContext {
+                detected_prefix: "pro.".to_string(),
+                detected_suffix: ".raw".to_string(),
+                available_special_symbols: vec![],
+            }),
+            ..EaConnection::default()
+        };
+
+        // Registration detection is used when the heartbeat reports nothing
+        assert_eq!(conn.fallback_symbol_prefix(), Some("pro.".to_string()));
+        assert_eq!(conn.fallback_symbol_suffix(), Some(".raw".to_string()));
+
+        // Heartbeat-reported values take precedence; empty strings are ignored
+        conn.symbol_prefix = Some(String::new());
+        conn.symbol_suffix = Some(".m".to_string());
+        assert_eq!(conn.fallback_symbol_prefix(), Some("pro.".to_string()));
+        assert_eq!(conn.fallback_symbol_suffix(), Some(".m".to_string()));
+
+        assert_eq!(EaConnection::default().fallback_symbol_suffix(), None);
+    }
+
+    #[test]
+    fn test_daily_loss_is_sticky_and_resets_at_utc_midnight() {
+        use super::EaConnection;
+        use chrono::TimeZone;
+
+        let morning = Utc.with_ymd_and_hms(2025, 1, 6, 1, 0, 0).unwrap();
+        let mut conn = EaConnection {
+            balance: 10000.0,
+            equity: 10000.0,
+            ..EaConnection::default()
+        };
+        assert_eq!(conn.daily_loss(morning), None);
+
+        // First heartbeat of the day fixes the start balance
+        conn.record_daily_loss(morning);
+        assert_eq!(conn.daily_loss(morning), Some(0.0));
+
+        // Realized loss (balance) plus floating loss (equity) both count
+        conn.balance = 9700.0;
+        conn.equity = 9400.0;
+        conn.record_daily_loss(morning + chrono::Duration::hours(2));
+        assert_eq!(conn.daily_loss(morning), Some(600.0));
+
+        // Equity recovery does not lower today's loss
+        conn.equity = 9900.0;
+        conn.record_daily_loss(morning + chrono::Duration::hours(3));
+        assert_eq!(conn.daily_loss(morning), Some(600.0));
+
+        // Without a heartbeat after midnight there is no loss for the new day
+        let next_day = Utc.with_ymd_and_hms(2025, 1, 7, 0, 0, 1).unwrap();
+        assert_eq!(conn.daily_loss(next_day), None);
+
+        // The first heartbeat of the new day restarts from the current balance
+        conn.balance = 9900.0;
+        conn.record_daily_loss(next_day);
+        assert_eq!(conn.daily_loss(next_day), Some(0.0));
+    }
 }
@@ -1,10 +1,12 @@
 // relay-server/src/models/global_settings.rs
 //
-// Global settings model for VictoriaLogs configuration.
-// These settings are shared across all EAs (Master and Slave).
+// Global settings models: VictoriaLogs configuration shared across all EAs
+// (Master and Slave) and relay-wide behaviour settings.
 
 use serde::{Deserialize, Serialize};
 
+use super::SlaveSettings;
+
 /// VictoriaLogs global settings
 /// Stored in the global_settings table with key "victoria_logs"
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -31,6 +33,65 @@ pub struct VLogsGlobalSettings {
     pub log_level: String,
 }
 
+/// Relay-wide behaviour settings
+/// Stored in the global_settings table with key "general"
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GlobalSettings {
+    /// Enable a disabled member automatically when its Slave EA comes online.
+    /// Members the user turned off via the toggle API are never re-enabled.
+    #[serde(default)]
+    pub auto_enable_on_connect: bool,
+
+    /// Upper bound for every member's `lot_multiplier` (None = no cap).
+    /// Saving a member above the cap is rejected; values saved before the cap
+    /// was lowered are clamped by the copy engine.
+    #[serde(default)]
+    pub max_lot_multiplier: Option<f64>,
+
+    /// Symbols (Master-side, `*` wildcards allowed) no member may open.
+    /// Checked before the per-member filters; Close/Modify still pass.
+    #[serde(default)]
+    pub blocked_symbols: Vec<String>,
+
+    /// What happens to dependent members when an EA's heartbeat times out
+    #[serde(default)]
+    pub timeout_action: TimeoutAction,
+
+    /// Settings new members start from; the `slave_settings` given when adding
+    /// a member are merged over them (None = built-in defaults).
+    /// Managed via `/api/global-settings/default-slave-settings`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_slave_settings: Option<SlaveSettings>,
+}
+
+/// Reaction to an EA heartbeat timeout
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeoutAction {
+    /// Only mark the EA as timed out; members keep their status and config
+    MarkOnly,
+    /// Re-evaluate members so they leave CONNECTED and stop copying Opens.
+    /// Settings are kept and copying resumes when the EA reconnects.
+    #[default]
+    PauseCopying,
+    /// Turn members off (`enabled_flag = false`, `explicitly_disabled`);
+    /// they stay off until re-enabled from the Web UI
+    DisableMember,
+}
+
+impl GlobalSettings {
+    /// Reject a `lot_multiplier` above `max_lot_multiplier`
+    pub fn check_lot_multiplier(&self, lot_multiplier: Option<f64>) -> Result<(), String> {
+        match (lot_multiplier, self.max_lot_multiplier) {
+            (Some(multiplier), Some(max)) if multiplier > max => Err(format!(
+                "lot_multiplier {} exceeds max_lot_multiplier {}",
+                multiplier, max
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Default log level (DEBUG = all logs)
 fn default_log_level() -> String {
     "DEBUG".to_string()
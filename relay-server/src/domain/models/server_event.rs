@@ -0,0 +1,351 @@
+// relay-server/src/domain/models/server_event.rs
+//
+// Events pushed to Web UI clients over the WebSocket broadcast channel.
+// Serialized as JSON objects tagged by `type`; `to_legacy` renders the old
+// colon-delimited strings for clients that still parse them
+// (`server.legacy_ws_events = true`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{
+    EaType, SlaveConfigWithMaster, SystemStateSnapshot, TimeoutAction, TradeGroupMember,
+    VLogsGlobalSettings,
+};
+
+/// WebSocket event sent to Web UI clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerEvent {
+    /// Full system state (connections, trade groups, members)
+    SystemSnapshot {
+        data: SystemStateSnapshot,
+    },
+    /// Slave config re-evaluated after a Master toggle or disconnect
+    SettingsUpdated {
+        data: SlaveConfigWithMaster,
+    },
+    /// VictoriaLogs settings changed
+    VlogsSettingsUpdated {
+        data: VLogsGlobalSettings,
+    },
+    MemberAdded {
+        data: TradeGroupMember,
+    },
+    MemberUpdated {
+        data: TradeGroupMember,
+    },
+    MemberStatusChanged {
+        data: TradeGroupMember,
+    },
+    MemberDeleted {
+        trade_group_id: String,
+        slave_account: String,
+    },
+    /// EA went offline (timeout, unregister or forced disconnect)
+    EaDisconnected {
+        account_id: String,
+    },
+    /// Trade signal received from a Master
+    TradeReceived {
+        master_account: String,
+        symbol: Option<String>,
+        lots: Option<f64>,
+    },
+    /// Trade signal copied to a Slave
+    TradeCopied {
+        master_account: String,
+        slave_account: String,
+        symbol: Option<String>,
+        lots: Option<f64>,
+        member_id: i32,
+    },
+    /// Trade signal filtered out for a Slave
+    TradeSkipped {
+        master_account: String,
+        slave_account: String,
+        ticket: i64,
+        reason: String,
+        member_id: i32,
+    },
+    /// Position snapshot received from a Master
+    PositionSnapshot {
+        source_account: String,
+        positions: usize,
+    },
+    /// Sync request received from a Slave
+    SyncRequest {
+        slave_account: String,
+        master_account: String,
+    },
+    /// Config triggered by an API action could not be published to an EA
+    ConfigDeliveryFailed {
+        trade_group_id: String,
+        account_id: String,
+        detail: String,
+    },
+    /// Timeout actions applied after an EA heartbeat timeout
+    TimeoutActionApplied {
+        account_id: String,
+        ea_type: EaType,
+        /// `global_settings.timeout_action` (members may override it)
+        action: TimeoutAction,
+        /// Members an action changed (trade group ID, Slave account and action)
+        members: Vec<TimeoutAffectedMember>,
+    },
+}
+
+/// Member touched by a timeout action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimeoutAffectedMember {
+    pub trade_group_id: String,
+    pub slave_account: String,
+    /// Action applied to this member (its override or the global action)
+    #[serde(default)]
+    pub action: TimeoutAction,
+}
+
+impl ServerEvent {
+    /// Render for a WebSocket text frame (JSON, or the legacy colon-delimited form)
+    pub fn render(&self, legacy: bool) -> String {
+        if legacy {
+            self.to_legacy()
+        } else {
+            self.to_json()
+        }
+    }
+
+    /// Trade group (Master account) the event belongs to; `None` for events
+    /// not tied to a single group (EA disconnects, relay-wide settings)
+    pub fn trade_group(&self) -> Option<&str> {
+        match self {
+            Self::SettingsUpdated { data } => Some(&data.master_account),
+            Self::MemberAdded { data }
+            | Self::MemberUpdated { data }
+            | Self::MemberStatusChanged { data } => Some(&data.trade_group_id),
+            Self::MemberDeleted { trade_group_id, .. } => Some(trade_group_id),
+            Self::TradeReceived { master_account, .. }
+            | Self::TradeCopied { master_account, .. }
+            | Self::TradeSkipped { master_account, .. }
+            | Self::SyncRequest { master_account, .. } => Some(master_account),
+            Self::ConfigDeliveryFailed { trade_group_id, .. } => Some(trade_group_id),
+            Self::PositionSnapshot { source_account, .. } => Some(source_account),
+            Self::TimeoutActionApplied {
+                account_id,
+                ea_type: EaType::Master,
+                ..
+            } => Some(account_id),
+            Self::TimeoutActionApplied { .. }
+            | Self::SystemSnapshot { .. }
+            | Self::VlogsSettingsUpdated { .. }
+            | Self::EaDisconnected { .. } => None,
+        }
+    }
+
+    /// Accounts (Master and/or Slave) the event concerns; empty for relay-wide events
+    pub fn accounts(&self) -> Vec<&str> {
+        match self {
+            Self::SettingsUpdated { data } => vec![&data.master_account, &data.slave_account],
+            Self::MemberAdded { data }
+            | Self::MemberUpdated { data }
+            | Self::MemberStatusChanged { data } => {
+                vec![&data.trade_group_id, &data.slave_account]
+            }
+            Self::MemberDeleted {
+                trade_group_id,
+                slave_account,
+            } => vec![trade_group_id, slave_account],
+            Self::EaDisconnected { account_id } => vec![account_id],
+            Self::TradeReceived { master_account, .. } => vec![master_account],
+            Self::TradeCopied {
+                master_account,
+                slave_account,
+                ..
+            }
+            | Self::TradeSkipped {
+                master_account,
+                slave_account,
+                ..
+            }
+            | Self::SyncRequest {
+                master_account,
+                slave_account,
+            } => vec![master_account, slave_account],
+            Self::PositionSnapshot { source_account, .. } => vec![source_account],
+            Self::ConfigDeliveryFailed {
+                trade_group_id,
+                account_id,
+                ..
+            } => vec![trade_group_id, account_id],
+            Self::TimeoutActionApplied { account_id, .. } => vec![account_id],
+            Self::SystemSnapshot { .. } | Self::VlogsSettingsUpdated { .. } => vec![],
+        }
+    }
+
+    /// JSON object tagged by `type`
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|e| {
+            tracing::error!("Failed to serialize WebSocket event: {}", e);
+            String::new()
+        })
+    }
+
+    /// Colon-delimited string as sent before JSON events were introduced
+    pub fn to_legacy(&self) -> String {
+        fn json<T: Serialize>(value: &T) -> String {
+            serde_json::to_string(value).unwrap_or_default()
+        }
+
+        match self {
+            Self::SystemSnapshot { data } => format!("system_snapshot:{}", json(data)),
+            Self::SettingsUpdated { data } => format!("settings_updated:{}", json(data)),
+            Self::VlogsSettingsUpdated { data } => {
+                format!("vlogs_settings_updated:{}", json(data))
+            }
+            Self::MemberAdded { data } => format!("member_added:{}", json(data)),
+            Self::MemberUpdated { data } => format!("member_updated:{}", json(data)),
+            Self::MemberStatusChanged { data } => {
+                format!("member_status_changed:{}", json(data))
+            }
+            Self::MemberDeleted {
+                trade_group_id,
+                slave_account,
+            } => format!(
+                "member_deleted:{}",
+                serde_json::json!({
+                    "trade_group_id": trade_group_id,
+                    "slave_account": slave_account,
+                })
+            ),
+            Self::EaDisconnected { account_id } => format!("ea_disconnected:{}", account_id),
+            Self::TradeReceived {
+                master_account,
+                symbol,
+                lots,
+            } => format!(
+                "trade_received:{}:{}:{}",
+                master_account,
+                symbol.as_deref().unwrap_or("?"),
+                lots.unwrap_or(0.0)
+            ),
+            Self::TradeCopied {
+                slave_account,
+                symbol,
+                lots,
+                member_id,
+                ..
+            } => format!(
+                "trade_copied:{}:{}:{}:{}",
+                slave_account,
+                symbol.as_deref().unwrap_or("?"),
+                lots.unwrap_or(0.0),
+                member_id
+            ),
+            Self::TradeSkipped {
+                slave_account,
+                ticket,
+                reason,
+                member_id,
+                ..
+            } => format!(
+                "trade_skipped:{}:{}:{}:{}",
+                slave_account, ticket, reason, member_id
+            ),
+            Self::PositionSnapshot {
+                source_account,
+                positions,
+            } => format!("position_snapshot:{}:{}", source_account, positions),
+            Self::SyncRequest {
+                slave_account,
+                master_account,
+            } => format!("sync_request:{}:{}", slave_account, master_account),
+            Self::ConfigDeliveryFailed {
+                trade_group_id,
+                account_id,
+                detail,
+            } => format!(
+                "config_delivery_failed:{}:{}:{}",
+                trade_group_id, account_id, detail
+            ),
+            Self::TimeoutActionApplied {
+                account_id,
+                ea_type,
+                action,
+                members,
+            } => format!(
+                "timeout_action_applied:{}:{:?}:{}:{}",
+                account_id,
+                ea_type,
+                json(action).trim_matches('"'),
+                members.len()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SlaveSettings;
+
+    fn settings_updated() -> ServerEvent {
+        ServerEvent::SettingsUpdated {
+            data: SlaveConfigWithMaster {
+                master_account: "MASTER_001".to_string(),
+                slave_account: "SLAVE_001".to_string(),
+                status: 2,
+                enabled_flag: true,
+                warning_codes: vec![],
+                slave_settings: SlaveSettings::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_update_event_json_shape() {
+        let json: serde_json::Value = serde_json::from_str(&settings_updated().to_json()).unwrap();
+
+        assert_eq!(json["type"], "settings_updated");
+        assert_eq!(json["data"]["master_account"], "MASTER_001");
+        assert_eq!(json["data"]["slave_account"], "SLAVE_001");
+        assert_eq!(json["data"]["status"], 2);
+        assert_eq!(json["data"]["enabled_flag"], true);
+
+        let copied = ServerEvent::TradeCopied {
+            master_account: "MASTER_001".to_string(),
+            slave_account: "SLAVE_001".to_string(),
+            symbol: Some("EURUSD".to_string()),
+            lots: Some(0.1),
+            member_id: 5,
+        };
+        let json: serde_json::Value = serde_json::from_str(&copied.to_json()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "trade_copied",
+                "master_account": "MASTER_001",
+                "slave_account": "SLAVE_001",
+                "symbol": "EURUSD",
+                "lots": 0.1,
+                "member_id": 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_rendering() {
+        let copied = ServerEvent::TradeCopied {
+            master_account: "MASTER_001".to_string(),
+            slave_account: "SLAVE_001".to_string(),
+            symbol: Some("EURUSD".to_string()),
+            lots: Some(0.1),
+            member_id: 5,
+        };
+        assert_eq!(copied.render(true), "trade_copied:SLAVE_001:EURUSD:0.1:5");
+        assert!(copied.render(false).starts_with('{'));
+
+        let legacy = settings_updated().to_legacy();
+        let json = legacy.strip_prefix("settings_updated:").unwrap();
+        let payload: SlaveConfigWithMaster = serde_json::from_str(json).unwrap();
+        assert_eq!(payload.slave_account, "SLAVE_001");
+    }
+}
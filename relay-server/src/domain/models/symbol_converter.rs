@@ -1,7 +1,7 @@
 use crate::domain::models::{MasterSettings, SlaveSettings, SymbolMapping};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SymbolConverter {
     pub prefix_remove: Option<String>,
     pub suffix_remove: Option<String>,
@@ -9,6 +9,9 @@ pub struct SymbolConverter {
     pub suffix_add: Option<String>,
     pub synonym_groups: Vec<Vec<String>>,
     pub detected_symbols: Option<Vec<String>>,
+    /// Strip prefix/suffix and look up mappings ignoring ASCII case
+    #[serde(default)]
+    pub case_insensitive: bool,
 }
 
 impl SymbolConverter {
@@ -20,6 +23,7 @@ impl SymbolConverter {
             suffix_add: slave_settings.symbol_suffix.clone(),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: master_settings.case_insensitive_symbols,
         }
     }
 
@@ -58,21 +62,27 @@ impl SymbolConverter {
 
         // 1. Remove Master's prefix/suffix
         if let Some(prefix) = &self.prefix_remove {
-            result = result
-                .strip_prefix(prefix.as_str())
+            result = self
+                .strip_prefix(&result, prefix)
                 .unwrap_or(&result)
                 .to_string();
         }
 
         if let Some(suffix) = &self.suffix_remove {
-            result = result
-                .strip_suffix(suffix.as_str())
+            result = self
+                .strip_suffix(&result, suffix)
                 .unwrap_or(&result)
                 .to_string();
         }
 
-        // 2. Apply Mapping (on the clean symbol)
-        if let Some(mapping) = mappings.iter().find(|m| m.source_symbol == result) {
+        // 2. Apply Mapping (on the clean symbol; target_symbol is used verbatim).
+        // When several mappings match, the lowest `priority` wins and ties go to
+        // the earliest in the list (min_by_key keeps the first minimum).
+        if let Some(mapping) = mappings
+            .iter()
+            .filter(|m| self.symbols_equal(&m.source_symbol, &result))
+            .min_by_key(|m| m.priority)
+        {
             result = mapping.target_symbol.clone();
         } else {
             // Auto-mapping: Try to find a match in detected symbols via synonym groups
@@ -101,6 +111,32 @@ impl SymbolConverter {
 
         result
     }
+
+    fn symbols_equal(&self, a: &str, b: &str) -> bool {
+        if self.case_insensitive {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    }
+
+    fn strip_prefix<'a>(&self, symbol: &'a str, prefix: &str) -> Option<&'a str> {
+        if !self.case_insensitive {
+            return symbol.strip_prefix(prefix);
+        }
+        let head = symbol.get(..prefix.len())?;
+        head.eq_ignore_ascii_case(prefix)
+            .then(|| &symbol[prefix.len()..])
+    }
+
+    fn strip_suffix<'a>(&self, symbol: &'a str, suffix: &str) -> Option<&'a str> {
+        if !self.case_insensitive {
+            return symbol.strip_suffix(suffix);
+        }
+        let split = symbol.len().checked_sub(suffix.len())?;
+        let tail = symbol.get(split..)?;
+        tail.eq_ignore_ascii_case(suffix).then(|| &symbol[..split])
+    }
 }
 
 #[cfg(test)]
@@ -116,11 +152,13 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let mappings = vec![SymbolMapping {
             source_symbol: "EURUSD".to_string(),
             target_symbol: "EURUSD.fx".to_string(),
+            priority: 0,
         }];
 
         let result = converter.convert("EURUSD", &mappings);
@@ -136,6 +174,7 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = converter.convert("MT5_EURUSD", &[]);
@@ -151,6 +190,7 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = converter.convert("EURUSD.fx", &[]);
@@ -166,6 +206,7 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = converter.convert("EURUSD", &[]);
@@ -181,6 +222,7 @@ mod tests {
             suffix_add: Some(".pro".to_string()),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = converter.convert("EURUSD", &[]);
@@ -196,6 +238,7 @@ mod tests {
             suffix_add: Some(".pro".to_string()),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = converter.convert("MT5_EURUSD.fx", &[]);
@@ -211,12 +254,14 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         // Mapping should match the CLEANED symbol
         let mappings = vec![SymbolMapping {
             source_symbol: "EURUSD".to_string(),
             target_symbol: "CUSTOM_EURUSD".to_string(),
+            priority: 0,
         }];
 
         // 1. Remove MT5_ -> EURUSD
@@ -224,6 +269,112 @@ mod tests {
         let result = converter.convert("MT5_EURUSD", &mappings);
         assert_eq!(result, "CUSTOM_EURUSD");
     }
+
+    #[test]
+    fn test_symbol_converter_mapping_priority_field_wins() {
+        let converter = SymbolConverter {
+            prefix_remove: None,
+            suffix_remove: None,
+            prefix_add: None,
+            suffix_add: None,
+            synonym_groups: Vec::new(),
+            detected_symbols: None,
+            case_insensitive: false,
+        };
+
+        // Same source: the lower priority value wins regardless of list order
+        let mappings = vec![
+            SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD".to_string(),
+                priority: 10,
+            },
+            SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD.pro".to_string(),
+                priority: 1,
+            },
+        ];
+
+        assert_eq!(converter.convert("XAUUSD", &mappings), "GOLD.pro");
+    }
+
+    #[test]
+    fn test_symbol_converter_mapping_priority_tie_keeps_list_order() {
+        let converter = SymbolConverter {
+            prefix_remove: None,
+            suffix_remove: None,
+            prefix_add: None,
+            suffix_add: None,
+            synonym_groups: Vec::new(),
+            detected_symbols: None,
+            case_insensitive: false,
+        };
+
+        let mappings = vec![
+            SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD".to_string(),
+                priority: 0,
+            },
+            SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD.pro".to_string(),
+                priority: 0,
+            },
+        ];
+
+        assert_eq!(converter.convert("XAUUSD", &mappings), "GOLD");
+    }
+
+    fn case_insensitive_converter() -> SymbolConverter {
+        SymbolConverter {
+            prefix_remove: Some("mt5_".to_string()),
+            suffix_remove: Some(".FX".to_string()),
+            prefix_add: None,
+            suffix_add: None,
+            synonym_groups: Vec::new(),
+            detected_symbols: None,
+            case_insensitive: true,
+        }
+    }
+
+    #[test]
+    fn test_symbol_converter_case_insensitive_mapping() {
+        let mappings = vec![SymbolMapping {
+            source_symbol: "EURUSD".to_string(),
+            target_symbol: "EURUSD.raw".to_string(),
+            priority: 0,
+        }];
+
+        let converter = case_insensitive_converter();
+        assert_eq!(converter.convert("eurusd", &mappings), "EURUSD.raw");
+        assert_eq!(converter.convert("MT5_eurusd.fx", &mappings), "EURUSD.raw");
+    }
+
+    #[test]
+    fn test_symbol_converter_case_insensitive_unmapped_passthrough() {
+        let converter = case_insensitive_converter();
+        assert_eq!(converter.convert("gBpUsD", &[]), "gBpUsD");
+        assert_eq!(converter.convert("Mt5_gBpUsD.fx", &[]), "gBpUsD");
+    }
+
+    #[test]
+    fn test_symbol_converter_case_sensitive_by_default() {
+        let converter = SymbolConverter {
+            case_insensitive: false,
+            ..case_insensitive_converter()
+        };
+        let mappings = vec![SymbolMapping {
+            source_symbol: "EURUSD".to_string(),
+            target_symbol: "EURUSD.raw".to_string(),
+            priority: 0,
+        }];
+
+        assert_eq!(converter.convert("eurusd", &mappings), "eurusd");
+        assert_eq!(converter.convert("MT5_EURUSD.FX", &mappings), "MT5_EURUSD");
+    }
+
     #[test]
     fn test_symbol_converter_from_settings() {
         let master_settings = MasterSettings {
@@ -246,6 +397,14 @@ mod tests {
         assert_eq!(converter.suffix_add, Some(".s".to_string()));
         assert!(converter.synonym_groups.is_empty());
         assert!(converter.detected_symbols.is_none());
+        assert!(!converter.case_insensitive);
+
+        let master_settings = MasterSettings {
+            case_insensitive_symbols: true,
+            ..master_settings
+        };
+        let converter = SymbolConverter::from_settings(&master_settings, &slave_settings);
+        assert!(converter.case_insensitive);
     }
 
     #[test]
@@ -273,11 +432,13 @@ mod tests {
             suffix_add: Some("-ECN".to_string()),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let mappings = vec![SymbolMapping {
             source_symbol: "XAUUSD".to_string(),
             target_symbol: "GOLD".to_string(),
+            priority: 0,
         }];
 
         let result = converter.convert("XAUUSD.raw", &mappings);
@@ -306,6 +467,9 @@ mod tests {
             timestamp: chrono::Utc::now(),
             source_account: "master_account".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         };
 
         let slave_settings = SlaveSettings {
@@ -314,6 +478,7 @@ mod tests {
             symbol_mappings: vec![SymbolMapping {
                 source_symbol: "XAUUSD".to_string(),
                 target_symbol: "GOLD".to_string(),
+                priority: 0,
             }],
             lot_multiplier: Some(1.0),
             reverse_trade: false,
@@ -328,6 +493,7 @@ mod tests {
             suffix_add: slave_settings.symbol_suffix.clone(),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let transformed_symbol = converter.convert(
@@ -350,6 +516,7 @@ mod tests {
             suffix_add: None,
             synonym_groups: vec![vec!["XAUUSD".to_string(), "GOLD".to_string()]],
             detected_symbols: Some(vec!["GOLD".to_string()]),
+            case_insensitive: false,
         };
 
         let result = converter.convert("XAUUSD", &[]);
@@ -365,6 +532,7 @@ mod tests {
             suffix_add: Some(".pro".to_string()),
             synonym_groups: vec![vec!["XAUUSD".to_string(), "GOLD".to_string()]],
             detected_symbols: Some(vec!["GOLD".to_string()]),
+            case_insensitive: false,
         };
 
         // mXAUUSD -> XAUUSD -> GOLD -> GOLD.pro
@@ -16,12 +16,15 @@ fn test_config_message_size_benchmark() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![],
         filters: TradeFilters {
             allowed_symbols: None,
             blocked_symbols: None,
             allowed_magic_numbers: None,
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: None,
@@ -29,6 +32,7 @@ fn test_config_message_size_benchmark() {
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -59,18 +63,22 @@ fn test_config_message_size_benchmark() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![
             SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "GBPUSD".to_string(),
                 target_symbol: "GBPUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "USDJPY".to_string(),
                 target_symbol: "USDJPYm".to_string(),
+                priority: 0,
             },
         ],
         filters: TradeFilters {
@@ -82,6 +90,8 @@ fn test_config_message_size_benchmark() {
             blocked_symbols: Some(vec!["XAUUSD".to_string()]),
             allowed_magic_numbers: Some(vec![123, 456, 789]),
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: None,
@@ -89,6 +99,7 @@ fn test_config_message_size_benchmark() {
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -119,46 +130,57 @@ fn test_config_message_size_benchmark() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(2.5),
         reverse_trade: true,
+        reverse_symbols: None,
         symbol_mappings: vec![
             SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "GBPUSD".to_string(),
                 target_symbol: "GBPUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "USDJPY".to_string(),
                 target_symbol: "USDJPYm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "AUDUSD".to_string(),
                 target_symbol: "AUDUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "USDCAD".to_string(),
                 target_symbol: "USDCADm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "NZDUSD".to_string(),
                 target_symbol: "NZDUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "USDCHF".to_string(),
                 target_symbol: "USDCHFm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "EURGBP".to_string(),
                 target_symbol: "EURGBPm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "EURJPY".to_string(),
                 target_symbol: "EURJPYm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "GBPJPY".to_string(),
                 target_symbol: "GBPJPYm".to_string(),
+                priority: 0,
             },
         ],
         filters: TradeFilters {
@@ -181,6 +203,8 @@ fn test_config_message_size_benchmark() {
             ]),
             allowed_magic_numbers: Some(vec![100, 200, 300, 400, 500, 600, 700, 800, 900, 1000]),
             blocked_magic_numbers: Some(vec![999, 666, 111]),
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: None,
@@ -188,6 +212,7 @@ fn test_config_message_size_benchmark() {
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -249,14 +274,17 @@ fn test_estimate_parsing_performance() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_mappings: vec![
             SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "GBPUSD".to_string(),
                 target_symbol: "GBPUSDm".to_string(),
+                priority: 0,
             },
         ],
         filters: TradeFilters {
@@ -264,6 +292,8 @@ fn test_estimate_parsing_performance() {
             blocked_symbols: Some(vec!["USDJPY".to_string()]),
             allowed_magic_numbers: Some(vec![123, 456]),
             blocked_magic_numbers: None,
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 1,
         symbol_prefix: None,
@@ -271,6 +301,7 @@ fn test_estimate_parsing_performance() {
         source_lot_min: None,
         source_lot_max: None,
         master_equity: None,
+        fixed_lot: None,
         sync_mode: SyncMode::default(),
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
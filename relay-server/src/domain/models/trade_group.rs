@@ -6,7 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::WarningCode;
+use crate::domain::models::{TradeAction, WarningCode};
 
 /// Default value for enabled field (used when deserializing old DB records)
 fn default_enabled() -> bool {
@@ -55,10 +55,43 @@ pub struct MasterSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol_suffix: Option<String>,
 
+    /// Match Master symbols case-insensitively (prefix/suffix removal and symbol mappings)
+    #[serde(default)]
+    pub case_insensitive_symbols: bool,
+
+    /// Wind-down mode: forward Close/Modify to members but skip new Opens
+    #[serde(default)]
+    pub closes_only: bool,
+
+    /// Drop Open signals older than this (ms, by signal timestamp); Close/Modify always pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_signal_age_ms: Option<i64>,
+
+    /// Drop Open/Modify signals above this rate (token bucket per Master); Close always passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_signals_per_sec: Option<u32>,
+
+    /// Slack/Discord-compatible webhook receiving Master online/offline and new
+    /// member warning alerts for this TradeGroup (None = no alerts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    /// Cap on the pooled lots split across weighted members (see
+    /// `SlaveSettings::portfolio_weight`); None = split the full Master lots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_lot_budget: Option<f64>,
+
     /// Configuration version for tracking updates
     pub config_version: u32,
 }
 
+impl MasterSettings {
+    /// Whether signals with this action should be copied to members at all
+    pub fn allows_action(&self, action: &TradeAction) -> bool {
+        !(self.closes_only && *action == TradeAction::Open)
+    }
+}
+
 #[allow(dead_code)]
 impl TradeGroup {
     /// Create a new TradeGroup with default settings
@@ -105,6 +138,17 @@ mod tests {
         assert_eq!(tg.master_settings.config_version, initial_version + 1);
     }
 
+    #[test]
+    fn test_closes_only_blocks_opens_only() {
+        let mut settings = MasterSettings::default();
+        assert!(settings.allows_action(&TradeAction::Open));
+
+        settings.closes_only = true;
+        assert!(!settings.allows_action(&TradeAction::Open));
+        assert!(settings.allows_action(&TradeAction::Close));
+        assert!(settings.allows_action(&TradeAction::Modify));
+    }
+
     #[test]
     fn test_master_settings_serialization() {
         let settings = MasterSettings {
@@ -112,6 +156,12 @@ mod tests {
             symbol_prefix: Some("pro.".to_string()),
             symbol_suffix: Some(".m".to_string()),
             config_version: 1,
+            case_insensitive_symbols: false,
+            closes_only: false,
+            max_signal_age_ms: None,
+            max_signals_per_sec: None,
+            webhook_url: None,
+            total_lot_budget: None,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -130,6 +180,12 @@ mod tests {
             symbol_prefix: None,
             symbol_suffix: None,
             config_version: 0,
+            case_insensitive_symbols: false,
+            closes_only: false,
+            max_signal_age_ms: None,
+            max_signals_per_sec: None,
+            webhook_url: None,
+            total_lot_budget: None,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
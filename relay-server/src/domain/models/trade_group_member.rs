@@ -3,9 +3,11 @@
 // TradeGroupMember model: Represents a Slave account connected to a Master (TradeGroup).
 // Each member has Slave-specific configuration and connection status.
 
-use super::WarningCode;
+use super::{TimeoutAction, WarningCode};
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
 use sankey_copier_zmq::{SymbolMapping, TradeFilters, STATUS_CONNECTED, STATUS_DISABLED};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// TradeGroupMember represents a Slave account connected to a TradeGroup (Master)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +32,14 @@ pub struct TradeGroupMember {
     #[serde(default)]
     pub warning_codes: Vec<WarningCode>,
 
+    /// Warning codes the user acknowledged (persisted, dropped once the warning clears)
+    #[serde(default)]
+    pub acknowledged_warnings: Vec<WarningCode>,
+
+    /// `warning_codes` annotated with their mute state (runtime only)
+    #[serde(default)]
+    pub warnings: Vec<MemberWarning>,
+
     /// User intent flag (true when the Web UI toggle is ON)
     #[serde(default)]
     pub enabled_flag: bool,
@@ -41,6 +51,25 @@ pub struct TradeGroupMember {
     pub updated_at: String,
 }
 
+/// A runtime warning with its acknowledgment state
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MemberWarning {
+    pub code: WarningCode,
+    /// True when the user acknowledged this warning
+    pub muted: bool,
+}
+
+/// Filters for listing members across all TradeGroups (ordered by Master, then Slave)
+#[derive(Debug, Clone, Default)]
+pub struct MemberListFilter {
+    /// Runtime status last persisted by the status engine
+    pub status: Option<i32>,
+    /// Some(true) = only Slaves in `online_slaves`, Some(false) = only the others
+    pub connected: Option<bool>,
+    /// Slave accounts whose EA is currently online (used by `connected`)
+    pub online_slaves: Vec<String>,
+}
+
 /// Lot calculation mode
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -48,6 +77,24 @@ pub enum LotCalculationMode {
     #[default]
     Multiplier,
     MarginRatio,
+    /// Relay-server scales lots by slave_equity / master_equity (from latest heartbeats)
+    EquityRatio,
+    /// Relay-server replaces Open lots with `fixed_lot` regardless of the Master's lot
+    FixedLot,
+    /// Relay-server sizes lots so the stop loss risks `risk_percent` of the slave balance
+    RiskPercent,
+}
+
+impl LotCalculationMode {
+    /// Whether the final lot is calculated by the relay-server (otherwise by the Slave EA)
+    pub fn is_relay_calculated(&self) -> bool {
+        matches!(
+            self,
+            LotCalculationMode::EquityRatio
+                | LotCalculationMode::FixedLot
+                | LotCalculationMode::RiskPercent
+        )
+    }
 }
 
 impl From<LotCalculationMode> for sankey_copier_zmq::LotCalculationMode {
@@ -55,6 +102,9 @@ impl From<LotCalculationMode> for sankey_copier_zmq::LotCalculationMode {
         match mode {
             LotCalculationMode::Multiplier => sankey_copier_zmq::LotCalculationMode::Multiplier,
             LotCalculationMode::MarginRatio => sankey_copier_zmq::LotCalculationMode::MarginRatio,
+            LotCalculationMode::EquityRatio => sankey_copier_zmq::LotCalculationMode::EquityRatio,
+            LotCalculationMode::FixedLot => sankey_copier_zmq::LotCalculationMode::FixedLot,
+            LotCalculationMode::RiskPercent => sankey_copier_zmq::LotCalculationMode::RiskPercent,
         }
     }
 }
@@ -82,10 +132,86 @@ pub enum SyncMode {
     MarketOrder,
 }
 
+/// Which side of the Master's new positions a slave copies
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyDirection {
+    /// Copy both buy and sell Opens
+    #[default]
+    Both,
+    /// Copy only buy-side Opens (Buy, BuyLimit, BuyStop)
+    BuyOnly,
+    /// Copy only sell-side Opens (Sell, SellLimit, SellStop)
+    SellOnly,
+}
+
+/// How a slave following several Masters handles Opens on the same (converted) symbol
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Copy every Master's Opens independently (one slave position per Master position)
+    #[default]
+    CopyAll,
+    /// The Master that opened the symbol first keeps it: Opens from other Masters are
+    /// skipped while its copied position is open
+    PriorityFirst,
+}
+
+/// How relay-calculated lots are rounded to `lot_decimals`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round half away from zero
+    #[default]
+    Nearest,
+    /// Always round toward zero (never exceeds the computed lot)
+    Down,
+    /// Always round away from zero
+    Up,
+}
+
+/// Trading-hours window: Open signals are copied only on `days` between `start` and `end`
+/// (local time in `tz`). A window with `start > end` wraps past midnight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradingWindow {
+    /// Weekdays the window applies to (local day of the signal)
+    pub days: Vec<Weekday>,
+    /// Window start, inclusive (e.g. "08:00:00")
+    pub start: NaiveTime,
+    /// Window end, exclusive (e.g. "17:00:00")
+    pub end: NaiveTime,
+    /// IANA timezone name (e.g. "UTC", "Asia/Tokyo")
+    pub tz: String,
+}
+
+impl TradingWindow {
+    /// Whether `at` falls inside the window. Fails if `tz` is not a valid IANA timezone.
+    pub fn contains(&self, at: DateTime<Utc>) -> Result<bool, String> {
+        let tz: chrono_tz::Tz = self
+            .tz
+            .parse()
+            .map_err(|_| format!("invalid timezone: {}", self.tz))?;
+        let local = at.with_timezone(&tz);
+        let time = local.time();
+
+        let in_time = if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        };
+        Ok(in_time && self.days.contains(&local.weekday()))
+    }
+}
+
+/// Window of `SlaveSettings::active_schedule`; same shape as the trading-hours window
+pub type ScheduleWindow = TradingWindow;
+
 /// Slave-specific settings
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SlaveSettings {
-    /// Lot calculation mode: "multiplier" (fixed) or "margin_ratio" (equity-based)
+    /// Lot calculation mode: "multiplier" (fixed), "margin_ratio" (equity-based, Slave EA),
+    /// "equity_ratio" (equity-based, relay-server), "fixed_lot" (constant lot, relay-server)
+    /// or "risk_percent" (stop-loss based, relay-server)
     #[serde(default)]
     pub lot_calculation_mode: LotCalculationMode,
 
@@ -93,10 +219,22 @@ pub struct SlaveSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lot_multiplier: Option<f64>,
 
+    /// Fixed lot size for every Open signal (used when mode is "fixed_lot")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed_lot: Option<f64>,
+
+    /// Percent of slave balance risked per trade (used when mode is "risk_percent")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_percent: Option<f64>,
+
     /// Reverse trade direction (buy → sell, sell → buy)
     #[serde(default)]
     pub reverse_trade: bool,
 
+    /// Limit reversal to these (converted Slave) symbols when reverse_trade is true (None = all)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_symbols: Option<Vec<String>>,
+
     /// Symbol prefix (currently in DB but not used by Slave EA - TODO Phase 2)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol_prefix: Option<String>,
@@ -109,10 +247,28 @@ pub struct SlaveSettings {
     #[serde(default)]
     pub symbol_mappings: Vec<SymbolMapping>,
 
+    /// Send the Master's symbol to the Slave verbatim, skipping prefix/suffix
+    /// removal/addition and mappings (Master and Slave on the same broker)
+    #[serde(default)]
+    pub disable_symbol_conversion: bool,
+
     /// Trade filters (allowed/blocked symbols and magic numbers)
     #[serde(default)]
     pub filters: TradeFilters,
 
+    /// Magic number remapping (Master magic -> Slave magic), applied after filtering
+    #[serde(default)]
+    pub magic_number_map: HashMap<i64, i64>,
+
+    /// Magic number for signals not found in `magic_number_map` (None = keep the Master's)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_magic: Option<i64>,
+
+    /// Comment for copied Opens; `{master}`, `{orig_comment}` and `{ticket}` are
+    /// replaced from the Master signal (None = keep the Master's comment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_template: Option<String>,
+
     /// Configuration version for tracking updates
     #[serde(default)]
     pub config_version: u32,
@@ -125,6 +281,50 @@ pub struct SlaveSettings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_lot_max: Option<f64>,
 
+    /// Whether a lot exactly equal to `source_lot_min`/`source_lot_max` is copied
+    /// (false = the bounds themselves are skipped too)
+    #[serde(default = "default_source_lot_bounds_inclusive")]
+    pub source_lot_bounds_inclusive: bool,
+
+    // === Broker Volume Constraints (applied to relay-calculated lots) ===
+    /// Volume step of the slave broker: computed lots are rounded down to a multiple of it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_step: Option<f64>,
+
+    /// Minimum volume accepted by the slave broker (trades below it are skipped)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_min: Option<f64>,
+
+    /// Maximum volume accepted by the slave broker (larger lots are clamped)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lot_max: Option<f64>,
+
+    /// Maximum number of concurrently open positions on the slave (new Opens are skipped at the limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_open_positions: Option<u32>,
+
+    /// Daily loss limit in account currency: once today's loss (from the balance at the
+    /// first heartbeat of the UTC day) exceeds it, new Opens are skipped until UTC midnight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_daily_loss: Option<f64>,
+
+    /// Skip Opens whose Master spread exceeds this many points (signals without spread pass)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_spread_points: Option<u32>,
+
+    /// Direction filter for new positions (judged on the Master's order type, before reversal)
+    #[serde(default)]
+    pub copy_direction: CopyDirection,
+
+    /// Trading-hours window for new positions (Open signals outside it are skipped)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trading_hours: Option<TradingWindow>,
+
+    /// Windows in which the member copies at all (None = always). Outside them the
+    /// member is reported DISABLED to the Slave EA until the next window opens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_schedule: Option<Vec<ScheduleWindow>>,
+
     // === Open Sync Policy Settings ===
     /// Sync mode for existing positions when slave connects
     #[serde(default)]
@@ -160,6 +360,142 @@ pub struct SlaveSettings {
     /// Use pending order for delayed signals instead of skipping
     #[serde(default)]
     pub use_pending_order_for_delayed: bool,
+
+    /// Send SL/TP as pip offsets from the Master entry instead of absolute prices;
+    /// the Slave EA places them at the same distance from its own fill price
+    #[serde(default)]
+    pub sl_tp_as_offset: bool,
+
+    /// Send partial closes as slave-side lots (remaining copied lots × close_ratio)
+    /// instead of forwarding close_ratio; for Slave EAs that close by volume
+    #[serde(default)]
+    pub partial_close_by_lots: bool,
+
+    /// Copy pending Opens (limit/stop) as market orders of the same direction.
+    /// Converted signals are not subject to `copy_pending_orders`.
+    #[serde(default)]
+    pub convert_pending_to_market: bool,
+
+    /// Let Close signals through every filter (symbol/magic/comment), so narrowing
+    /// a filter after an Open cannot leave an orphan position on the Slave
+    #[serde(default = "default_always_allow_close")]
+    pub always_allow_close: bool,
+
+    /// Broker profile supplying symbol prefix/suffix and mappings the member leaves unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub broker_profile: Option<String>,
+
+    /// Decimal places of relay-calculated lots (default: 2, i.e. 0.01 lot precision)
+    #[serde(default = "default_lot_decimals")]
+    pub lot_decimals: u8,
+
+    /// Rounding applied at `lot_decimals`
+    #[serde(default)]
+    pub lot_rounding: RoundingMode,
+
+    /// Conflict handling when this slave also follows other Masters
+    /// (judged with the settings of the Master whose Open arrives)
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+
+    /// Random delay `[min, max]` in milliseconds before a copied signal is published
+    /// (None = publish immediately). Signals for the same ticket keep their order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy_delay_ms: Option<(u64, u64)>,
+
+    /// Publish Close signals without `copy_delay_ms` (still after earlier signals of the ticket)
+    #[serde(default)]
+    pub copy_delay_skip_closes: bool,
+
+    /// Share of the Master's tickets to copy, 0.0–1.0 (None = all). Each ticket is
+    /// sampled by a hash of (ticket, slave_account), so its Close follows its Open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy_sample_rate: Option<f64>,
+
+    /// Reaction to a heartbeat timeout of this member's Master or Slave EA
+    /// (None = `global_settings.timeout_action`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_action: Option<TimeoutAction>,
+
+    /// Relative share of the Master lots when splitting across the group
+    /// (lots = master_lots * weight / sum_of_weights; None = not weighted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub portfolio_weight: Option<f64>,
+}
+
+impl Default for SlaveSettings {
+    fn default() -> Self {
+        Self {
+            lot_calculation_mode: LotCalculationMode::default(),
+            lot_multiplier: None,
+            fixed_lot: None,
+            risk_percent: None,
+            reverse_trade: false,
+            reverse_symbols: None,
+            symbol_prefix: None,
+            symbol_suffix: None,
+            symbol_mappings: Vec::new(),
+            disable_symbol_conversion: false,
+            filters: TradeFilters::default(),
+            magic_number_map: HashMap::new(),
+            default_magic: None,
+            config_version: 0,
+            source_lot_min: None,
+            source_lot_max: None,
+            source_lot_bounds_inclusive: true,
+            lot_step: None,
+            lot_min: None,
+            lot_max: None,
+            max_open_positions: None,
+            max_daily_loss: None,
+            max_spread_points: None,
+            copy_direction: CopyDirection::default(),
+            trading_hours: None,
+            active_schedule: None,
+            comment_template: None,
+            copy_sample_rate: None,
+            timeout_action: None,
+            portfolio_weight: None,
+            sync_mode: SyncMode::default(),
+            limit_order_expiry_min: None,
+            market_sync_max_pips: None,
+            max_slippage: None,
+            copy_pending_orders: false,
+            max_retries: 0,
+            max_signal_delay_ms: 0,
+            use_pending_order_for_delayed: false,
+            sl_tp_as_offset: false,
+            partial_close_by_lots: false,
+            convert_pending_to_market: false,
+            always_allow_close: true,
+            broker_profile: None,
+            lot_decimals: default_lot_decimals(),
+            lot_rounding: RoundingMode::default(),
+            merge_strategy: MergeStrategy::default(),
+            copy_delay_ms: None,
+            copy_delay_skip_closes: false,
+        }
+    }
+}
+
+/// Overlay `overlay` onto `base`, recursing into objects present in both
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge_json(existing, value)
+                    }
+                    _ => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (_, serde_json::Value::Null) => {}
+        (base, overlay) => *base = overlay,
+    }
 }
 
 fn default_max_retries() -> i32 {
@@ -170,6 +506,131 @@ fn default_max_signal_delay_ms() -> i32 {
     5000
 }
 
+fn default_source_lot_bounds_inclusive() -> bool {
+    true
+}
+
+fn default_always_allow_close() -> bool {
+    true
+}
+
+fn default_lot_decimals() -> u8 {
+    2
+}
+
+/// Upper bound for `lot_decimals` (10^8 keeps the rounding factor exact in f64)
+const MAX_LOT_DECIMALS: u8 = 8;
+
+impl SlaveSettings {
+    /// Settings for a new member: `overrides` (a partial SlaveSettings JSON
+    /// object) merged over `defaults`. Nested objects such as `filters` are
+    /// merged field by field; arrays and explicit nulls replace the default.
+    pub fn with_defaults(
+        defaults: &SlaveSettings,
+        overrides: serde_json::Value,
+    ) -> serde_json::Result<SlaveSettings> {
+        let mut merged = serde_json::to_value(defaults)?;
+        merge_json(&mut merged, overrides);
+        serde_json::from_value(merged)
+    }
+
+    /// Validate setting combinations that cannot be expressed by the type system.
+    /// Returns a human-readable reason on failure (surfaced as a 400 by the API).
+    pub fn validate(&self) -> Result<(), String> {
+        if self.lot_calculation_mode == LotCalculationMode::FixedLot
+            && !self.fixed_lot.is_some_and(|lot| lot > 0.0)
+        {
+            return Err(
+                "fixed_lot must be greater than 0 when lot_calculation_mode is fixed_lot".into(),
+            );
+        }
+        if self.lot_calculation_mode == LotCalculationMode::RiskPercent
+            && !self.risk_percent.is_some_and(|r| r > 0.0 && r <= 100.0)
+        {
+            return Err(
+                "risk_percent must be in (0, 100] when lot_calculation_mode is risk_percent".into(),
+            );
+        }
+        if let Some(window) = &self.trading_hours {
+            if window.days.is_empty() {
+                return Err("trading_hours.days must not be empty".into());
+            }
+            if window.tz.parse::<chrono_tz::Tz>().is_err() {
+                return Err(format!(
+                    "trading_hours.tz is not a valid timezone: {}",
+                    window.tz
+                ));
+            }
+        }
+        if let Some(schedule) = &self.active_schedule {
+            if schedule.is_empty() {
+                return Err("active_schedule must contain at least one window".into());
+            }
+            for window in schedule {
+                if window.days.is_empty() {
+                    return Err("active_schedule days must not be empty".into());
+                }
+                if window.tz.parse::<chrono_tz::Tz>().is_err() {
+                    return Err(format!(
+                        "active_schedule tz is not a valid timezone: {}",
+                        window.tz
+                    ));
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (self.source_lot_min, self.source_lot_max) {
+            if min > max {
+                return Err("source_lot_min must not be greater than source_lot_max".into());
+            }
+        }
+        if self.lot_step.is_some_and(|step| step <= 0.0) {
+            return Err("lot_step must be greater than 0".into());
+        }
+        if let (Some(min), Some(max)) = (self.lot_min, self.lot_max) {
+            if min > max {
+                return Err("lot_min must not be greater than lot_max".into());
+            }
+        }
+        if self.lot_decimals > MAX_LOT_DECIMALS {
+            return Err(format!("lot_decimals must be at most {}", MAX_LOT_DECIMALS));
+        }
+        if self.copy_delay_ms.is_some_and(|(min, max)| min > max) {
+            return Err("copy_delay_ms min must not be greater than max".into());
+        }
+        if self
+            .copy_sample_rate
+            .is_some_and(|rate| !(0.0..=1.0).contains(&rate))
+        {
+            return Err("copy_sample_rate must be between 0.0 and 1.0".into());
+        }
+        if self
+            .portfolio_weight
+            .is_some_and(|weight| !weight.is_finite() || weight <= 0.0)
+        {
+            return Err("portfolio_weight must be greater than 0".into());
+        }
+        Ok(())
+    }
+
+    /// Whether `other` holds the same settings, ignoring `config_version`
+    pub fn same_config(&self, other: &SlaveSettings) -> bool {
+        Self {
+            config_version: other.config_version,
+            ..self.clone()
+        } == *other
+    }
+
+    /// Whether `at` falls in one of the `active_schedule` windows (always true
+    /// without a schedule). Windows with an invalid timezone never match.
+    pub fn in_active_schedule(&self, at: DateTime<Utc>) -> bool {
+        self.active_schedule.as_ref().is_none_or(|schedule| {
+            schedule
+                .iter()
+                .any(|window| window.contains(at).unwrap_or(false))
+        })
+    }
+}
+
 #[allow(dead_code)]
 impl TradeGroupMember {
     /// Create a new TradeGroupMember with default settings
@@ -182,6 +643,8 @@ impl TradeGroupMember {
             slave_settings: SlaveSettings::default(),
             status: STATUS_DISABLED,
             warning_codes: Vec::new(),
+            acknowledged_warnings: Vec::new(),
+            warnings: Vec::new(),
             enabled_flag: false,
             created_at: chrono::Utc::now().to_rfc3339(),
             updated_at: chrono::Utc::now().to_rfc3339(),
@@ -199,6 +662,28 @@ impl TradeGroupMember {
         self.enabled_flag
     }
 
+    /// Set the runtime warning codes and mark acknowledged ones as muted.
+    ///
+    /// Acknowledgments for warnings that are no longer active are dropped, so
+    /// a warning that clears and later comes back is raised again.
+    /// Returns true when `acknowledged_warnings` changed and should be persisted.
+    pub fn apply_warning_codes(&mut self, warning_codes: Vec<WarningCode>) -> bool {
+        let before = self.acknowledged_warnings.len();
+        self.acknowledged_warnings
+            .retain(|code| warning_codes.contains(code));
+
+        self.warnings = warning_codes
+            .iter()
+            .map(|code| MemberWarning {
+                code: code.clone(),
+                muted: self.acknowledged_warnings.contains(code),
+            })
+            .collect();
+        self.warning_codes = warning_codes;
+
+        self.acknowledged_warnings.len() != before
+    }
+
     /// Check if the member is connected (status == 2)
     pub fn is_connected(&self) -> bool {
         self.status == STATUS_CONNECTED
@@ -270,21 +755,26 @@ mod tests {
             lot_calculation_mode: LotCalculationMode::Multiplier,
             lot_multiplier: Some(1.5),
             reverse_trade: true,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             }],
             filters: TradeFilters {
                 allowed_symbols: Some(vec!["EURUSD".to_string()]),
                 blocked_symbols: None,
                 allowed_magic_numbers: None,
                 blocked_magic_numbers: None,
+                allowed_comment_substrings: None,
+                blocked_comment_substrings: None,
             },
             config_version: 1,
             source_lot_min: Some(0.01),
             source_lot_max: Some(10.0),
+            source_lot_bounds_inclusive: true,
             sync_mode: SyncMode::Skip,
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -293,6 +783,34 @@ mod tests {
             max_retries: 5,
             max_signal_delay_ms: 3000,
             use_pending_order_for_delayed: true,
+            fixed_lot: None,
+            risk_percent: None,
+            lot_max: None,
+            lot_min: None,
+            lot_step: None,
+            max_open_positions: None,
+            max_daily_loss: None,
+            max_spread_points: None,
+            trading_hours: None,
+            active_schedule: None,
+            comment_template: None,
+            copy_sample_rate: None,
+            timeout_action: None,
+            portfolio_weight: None,
+            sl_tp_as_offset: false,
+            partial_close_by_lots: false,
+            convert_pending_to_market: false,
+            always_allow_close: true,
+            broker_profile: None,
+            lot_decimals: 2,
+            lot_rounding: RoundingMode::Nearest,
+            merge_strategy: MergeStrategy::CopyAll,
+            copy_delay_ms: None,
+            copy_delay_skip_closes: false,
+            disable_symbol_conversion: false,
+            default_magic: None,
+            magic_number_map: HashMap::new(),
+            copy_direction: CopyDirection::Both,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -315,6 +833,7 @@ mod tests {
             lot_calculation_mode: LotCalculationMode::default(),
             lot_multiplier: None,
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![],
@@ -322,6 +841,7 @@ mod tests {
             config_version: 0,
             source_lot_min: None,
             source_lot_max: None,
+            source_lot_bounds_inclusive: true,
             sync_mode: SyncMode::Skip,
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -330,6 +850,34 @@ mod tests {
             max_retries: 3,
             max_signal_delay_ms: 5000,
             use_pending_order_for_delayed: false,
+            fixed_lot: None,
+            risk_percent: None,
+            lot_max: None,
+            lot_min: None,
+            lot_step: None,
+            max_open_positions: None,
+            max_daily_loss: None,
+            max_spread_points: None,
+            trading_hours: None,
+            active_schedule: None,
+            comment_template: None,
+            copy_sample_rate: None,
+            timeout_action: None,
+            portfolio_weight: None,
+            sl_tp_as_offset: false,
+            partial_close_by_lots: false,
+            convert_pending_to_market: false,
+            always_allow_close: true,
+            broker_profile: None,
+            lot_decimals: 2,
+            lot_rounding: RoundingMode::Nearest,
+            merge_strategy: MergeStrategy::CopyAll,
+            copy_delay_ms: None,
+            copy_delay_skip_closes: false,
+            disable_symbol_conversion: false,
+            default_magic: None,
+            magic_number_map: HashMap::new(),
+            copy_direction: CopyDirection::Both,
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -358,6 +906,230 @@ mod tests {
         let mode2 = LotCalculationMode::Multiplier;
         let json2 = serde_json::to_string(&mode2).unwrap();
         assert_eq!(json2, "\"multiplier\"");
+
+        let mode3 = LotCalculationMode::EquityRatio;
+        let json3 = serde_json::to_string(&mode3).unwrap();
+        assert_eq!(json3, "\"equity_ratio\"");
+    }
+
+    #[test]
+    fn test_validate_relay_lot_modes_require_values() {
+        let mut settings = SlaveSettings {
+            lot_calculation_mode: LotCalculationMode::FixedLot,
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+
+        settings.fixed_lot = Some(0.0);
+        assert!(settings.validate().is_err());
+
+        settings.fixed_lot = Some(0.01);
+        assert!(settings.validate().is_ok());
+
+        settings.lot_calculation_mode = LotCalculationMode::RiskPercent;
+        assert!(settings.validate().is_err());
+
+        settings.risk_percent = Some(1.5);
+        assert!(settings.validate().is_ok());
+
+        // fixed_lot / risk_percent are not required for other modes
+        let multiplier = SlaveSettings::default();
+        assert!(multiplier.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_lot_constraints() {
+        let mut settings = SlaveSettings {
+            lot_step: Some(0.0),
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+
+        settings.lot_step = Some(0.01);
+        settings.lot_min = Some(1.0);
+        settings.lot_max = Some(0.5);
+        assert!(settings.validate().is_err());
+
+        settings.lot_max = Some(50.0);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_source_lot_bounds_default_and_validation() {
+        // Stored settings without the flag keep the inclusive bounds
+        let settings: SlaveSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.source_lot_bounds_inclusive);
+
+        let mut settings = SlaveSettings {
+            source_lot_min: Some(1.0),
+            source_lot_max: Some(0.5),
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+
+        settings.source_lot_max = Some(1.0);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_copy_delay_serialization_and_validation() {
+        let settings: SlaveSettings =
+            serde_json::from_str(r#"{"copy_delay_ms":[200,500]}"#).unwrap();
+        assert_eq!(settings.copy_delay_ms, Some((200, 500)));
+        assert!(!settings.copy_delay_skip_closes);
+        assert!(settings.validate().is_ok());
+
+        let settings = SlaveSettings {
+            copy_delay_ms: Some((500, 200)),
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_copy_sample_rate_validation() {
+        for (rate, valid) in [
+            (0.0, true),
+            (0.25, true),
+            (1.0, true),
+            (-0.1, false),
+            (1.5, false),
+        ] {
+            let settings = SlaveSettings {
+                copy_sample_rate: Some(rate),
+                ..SlaveSettings::default()
+            };
+            assert_eq!(settings.validate().is_ok(), valid, "rate {}", rate);
+        }
+        // NaN is outside every range
+        let settings = SlaveSettings {
+            copy_sample_rate: Some(f64::NAN),
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_with_defaults_merges_overrides_over_defaults() {
+        let defaults = SlaveSettings {
+            lot_multiplier: Some(2.5),
+            filters: TradeFilters {
+                blocked_symbols: Some(vec!["XAUUSD".to_string()]),
+                ..TradeFilters::default()
+            },
+            ..SlaveSettings::default()
+        };
+
+        let inherited = SlaveSettings::with_defaults(&defaults, serde_json::Value::Null).unwrap();
+        assert_eq!(inherited, defaults);
+
+        let merged = SlaveSettings::with_defaults(
+            &defaults,
+            serde_json::json!({
+                "lot_multiplier": null,
+                "fixed_lot": 0.1,
+                "filters": { "allowed_symbols": ["EURUSD"] }
+            }),
+        )
+        .unwrap();
+        assert_eq!(merged.lot_multiplier, None);
+        assert_eq!(merged.fixed_lot, Some(0.1));
+        // Nested objects merge field by field
+        assert_eq!(
+            merged.filters.allowed_symbols,
+            Some(vec!["EURUSD".to_string()])
+        );
+        assert_eq!(
+            merged.filters.blocked_symbols,
+            Some(vec!["XAUUSD".to_string()])
+        );
+
+        assert!(SlaveSettings::with_defaults(
+            &defaults,
+            serde_json::json!({"lot_multiplier": "x"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_lot_decimals_default_and_validation() {
+        // Stored settings without the new fields keep 0.01 lot precision
+        let settings: SlaveSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.lot_decimals, 2);
+        assert_eq!(settings.lot_rounding, RoundingMode::Nearest);
+        assert_eq!(SlaveSettings::default().lot_decimals, 2);
+
+        let settings: SlaveSettings =
+            serde_json::from_str(r#"{"lot_decimals":3,"lot_rounding":"down"}"#).unwrap();
+        assert_eq!(settings.lot_rounding, RoundingMode::Down);
+        assert!(settings.validate().is_ok());
+
+        let settings = SlaveSettings {
+            lot_decimals: 9,
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_trading_window_timezone_and_wrap() {
+        let json = r#"{"days":["Mon"],"start":"22:00:00","end":"06:00:00","tz":"Asia/Tokyo"}"#;
+        let window: TradingWindow = serde_json::from_str(json).unwrap();
+
+        // Monday 23:30 JST (= Monday 14:30 UTC) is inside the overnight window
+        let inside = DateTime::parse_from_rfc3339("2024-06-03T14:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(window.contains(inside), Ok(true));
+
+        // Monday 12:00 JST is outside
+        let outside = DateTime::parse_from_rfc3339("2024-06-03T03:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(window.contains(outside), Ok(false));
+
+        let invalid = TradingWindow {
+            tz: "Mars/Olympus".to_string(),
+            ..window.clone()
+        };
+        assert!(invalid.contains(inside).is_err());
+        let settings = SlaveSettings {
+            trading_hours: Some(invalid.clone()),
+            ..SlaveSettings::default()
+        };
+        assert!(settings.validate().is_err());
+
+        let schedule = |windows: Vec<ScheduleWindow>| SlaveSettings {
+            active_schedule: Some(windows),
+            ..SlaveSettings::default()
+        };
+        assert!(schedule(vec![window.clone()]).validate().is_ok());
+        assert!(schedule(vec![window, invalid]).validate().is_err());
+        assert!(schedule(Vec::new()).validate().is_err());
+    }
+
+    #[test]
+    fn test_magic_number_map_json_roundtrip() {
+        // JSON object keys are strings; they must round-trip through the DB column
+        let settings: SlaveSettings =
+            serde_json::from_str(r#"{"magic_number_map":{"777":9777},"default_magic":1}"#).unwrap();
+        assert_eq!(settings.magic_number_map.get(&777), Some(&9777));
+        assert_eq!(settings.default_magic, Some(1));
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: SlaveSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.magic_number_map, settings.magic_number_map);
+    }
+
+    #[test]
+    fn test_copy_direction_serialization() {
+        assert_eq!(
+            serde_json::to_string(&CopyDirection::SellOnly).unwrap(),
+            "\"sell_only\""
+        );
+        // Settings stored before the field existed default to copying both sides
+        let settings: SlaveSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.copy_direction, CopyDirection::Both);
     }
 
     #[test]
@@ -374,4 +1146,49 @@ mod tests {
         let json3 = serde_json::to_string(&mode3).unwrap();
         assert_eq!(json3, "\"market_order\"");
     }
+
+    #[test]
+    fn test_acknowledge_one_of_two_warnings() {
+        let mut member = TradeGroupMember::new(1, "MASTER_001".into(), "SLAVE_001".into());
+        member.acknowledged_warnings = vec![WarningCode::MasterOffline];
+
+        let changed =
+            member.apply_warning_codes(vec![WarningCode::SlaveOffline, WarningCode::MasterOffline]);
+        assert!(!changed);
+        assert_eq!(
+            member.warnings,
+            vec![
+                MemberWarning {
+                    code: WarningCode::SlaveOffline,
+                    muted: false
+                },
+                MemberWarning {
+                    code: WarningCode::MasterOffline,
+                    muted: true
+                },
+            ]
+        );
+        // Acked warnings are still reported, not dropped
+        assert_eq!(member.warning_codes.len(), 2);
+    }
+
+    #[test]
+    fn test_acknowledged_warning_is_raised_again_after_it_clears() {
+        let mut member = TradeGroupMember::new(1, "MASTER_001".into(), "SLAVE_001".into());
+        member.acknowledged_warnings = vec![WarningCode::MasterOffline];
+        member.apply_warning_codes(vec![WarningCode::MasterOffline]);
+
+        // A new warning appears: it is not muted, the acked one stays muted
+        member.apply_warning_codes(vec![WarningCode::MasterOffline, WarningCode::SlaveOffline]);
+        assert!(member.warnings[0].muted);
+        assert!(!member.warnings[1].muted);
+
+        // Master recovers: the acknowledgment is dropped
+        assert!(member.apply_warning_codes(vec![WarningCode::SlaveOffline]));
+        assert!(member.acknowledged_warnings.is_empty());
+
+        // Master goes offline again: raised unmuted
+        member.apply_warning_codes(vec![WarningCode::MasterOffline]);
+        assert!(!member.warnings[0].muted);
+    }
 }
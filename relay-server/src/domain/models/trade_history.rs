@@ -0,0 +1,65 @@
+// relay-server/src/domain/models/trade_history.rs
+//
+// Trade history model: one row per (trade signal, slave) copy decision.
+// Written after the copy decision so users can see why a trade was not copied.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::TradeAction;
+
+/// Default page size for trade history queries
+pub const TRADE_HISTORY_DEFAULT_LIMIT: u32 = 100;
+/// Upper bound for a single trade history page
+pub const TRADE_HISTORY_MAX_LIMIT: u32 = 1000;
+
+/// A recorded copy decision for one Master signal and one Slave
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradeHistoryEntry {
+    /// Row ID (assigned by the database; ignored on insert)
+    #[serde(default)]
+    pub id: i64,
+    pub master_account: String,
+    pub slave_account: String,
+    pub ticket: i64,
+    pub action: TradeAction,
+    pub symbol: Option<String>,
+    /// Lots as sent by the Master
+    pub lots: Option<f64>,
+    /// Signal timestamp reported by the Master
+    pub timestamp: DateTime<Utc>,
+    pub copied: bool,
+    /// Why the signal was not copied (skip reason or warning code, snake_case)
+    pub filter_reason: Option<String>,
+}
+
+/// Filters for trade history queries (newest first)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeHistoryQuery {
+    pub master: Option<String>,
+    pub slave: Option<String>,
+    /// Inclusive lower bound on the signal timestamp
+    pub from: Option<DateTime<Utc>>,
+    /// Inclusive upper bound on the signal timestamp
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+impl TradeHistoryQuery {
+    /// Page size clamped to 1..=TRADE_HISTORY_MAX_LIMIT
+    pub fn effective_limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(TRADE_HISTORY_DEFAULT_LIMIT)
+            .clamp(1, TRADE_HISTORY_MAX_LIMIT)
+    }
+}
+
+/// One page of trade history with the total number of matching rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeHistoryPage {
+    pub items: Vec<TradeHistoryEntry>,
+    pub total: i64,
+    pub limit: u32,
+    pub offset: u32,
+}
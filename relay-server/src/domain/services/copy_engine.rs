@@ -1,17 +1,162 @@
+use super::symbol_cache::SymbolConversionCache;
+use super::symbol_specs::SymbolSpecRegistry;
 use crate::domain::models::{
-    OrderType, SymbolConverter, TradeAction, TradeGroupMember, TradeSignal,
+    CopyDirection, LotCalculationMode, OrderType, RoundingMode, SlaveSettings, SymbolConverter,
+    TradeAction, TradeGroupMember, TradeSignal, WarningCode,
 };
 use anyhow::Result;
+use sankey_copier_zmq::SymbolPatterns;
+use std::sync::Arc;
+
+/// Longest order comment MetaTrader keeps (longer comments are cut or rejected)
+const MT_COMMENT_MAX_CHARS: usize = 31;
+
+/// Runtime account data used by the engine for relay-side lot calculation.
+/// Values come from the latest heartbeats tracked in ConnectionManager;
+/// `None` means the EA is not connected or has not reported yet.
+#[derive(Debug, Clone, Default)]
+pub struct CopyContext {
+    pub master_equity: Option<f64>,
+    pub slave_equity: Option<f64>,
+    pub slave_balance: Option<f64>,
+    /// Open positions held by the slave (from its latest heartbeat)
+    pub slave_open_positions: Option<u32>,
+    /// Largest loss of the slave today (UTC), None if no heartbeat yet today
+    pub slave_daily_loss: Option<f64>,
+    /// Lots the relay copied to the slave for this ticket and still open
+    /// (reduced by earlier partial closes); None if the Open was not tracked
+    pub slave_position_lots: Option<f64>,
+    /// Symbol and order type the relay sent with the copied Open of this ticket
+    /// (before the EA applies `reverse_trade`); None if the Open was not tracked
+    pub slave_position_symbol: Option<String>,
+    pub slave_position_order_type: Option<OrderType>,
+    /// Pip value per 1.0 lot in the slave's account currency (from the symbol specs)
+    pub pip_value: Option<f64>,
+    /// Relay-wide cap on `lot_multiplier` (global settings), None = no cap
+    pub max_lot_multiplier: Option<f64>,
+    /// Relay-wide blocked symbols (global settings), checked before member filters
+    pub global_blocked_symbols: Vec<String>,
+    /// Sum of `portfolio_weight` over the TradeGroup's members (see
+    /// `portfolio_weight_total`), None if no member is weighted
+    pub portfolio_weight_total: Option<f64>,
+    /// Cap on the pooled Master lots split by weight (`total_lot_budget`)
+    pub total_lot_budget: Option<f64>,
+}
+
+/// Sum of the members' `portfolio_weight`, disabled members included so a
+/// member's share does not change when a sibling is switched off
+pub fn portfolio_weight_total(members: &[TradeGroupMember]) -> Option<f64> {
+    let total: f64 = members
+        .iter()
+        .filter_map(|m| m.slave_settings.portfolio_weight)
+        .filter(|w| *w > 0.0)
+        .sum();
+    (total > 0.0).then_some(total)
+}
+
+/// Result of applying the copy filters to a single member
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyDecision {
+    /// Signal passes all filters
+    Copy,
+    /// Signal filtered out by user settings (expected, logged at debug level)
+    Skip(SkipReason),
+    /// Signal skipped because it cannot be executed as configured (reported to the UI)
+    SkipWithWarning(WarningCode),
+}
+
+/// Which filter rejected a signal (recorded in the trade history)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    NotConnected,
+    PendingOrdersDisabled,
+    CopyDirection,
+    OutsideTradingHours,
+    InvalidTradingHours,
+    InvalidFixedLot,
+    SourceLotBelowMin,
+    SourceLotAboveMax,
+    SymbolNotAllowed,
+    SymbolBlocked,
+    MagicNotAllowed,
+    MagicBlocked,
+    CommentNotAllowed,
+    CommentBlocked,
+    NotSampled,
+}
+
+impl SkipReason {
+    /// Stable snake_case identifier used in the trade history
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkipReason::NotConnected => "not_connected",
+            SkipReason::PendingOrdersDisabled => "pending_orders_disabled",
+            SkipReason::CopyDirection => "copy_direction",
+            SkipReason::OutsideTradingHours => "outside_trading_hours",
+            SkipReason::InvalidTradingHours => "invalid_trading_hours",
+            SkipReason::InvalidFixedLot => "invalid_fixed_lot",
+            SkipReason::SourceLotBelowMin => "source_lot_below_min",
+            SkipReason::SourceLotAboveMax => "source_lot_above_max",
+            SkipReason::SymbolNotAllowed => "symbol_not_allowed",
+            SkipReason::SymbolBlocked => "symbol_blocked",
+            SkipReason::MagicNotAllowed => "magic_not_allowed",
+            SkipReason::MagicBlocked => "magic_blocked",
+            SkipReason::CommentNotAllowed => "comment_not_allowed",
+            SkipReason::CommentBlocked => "comment_blocked",
+            SkipReason::NotSampled => "not_sampled",
+        }
+    }
+}
+
+impl CopyDecision {
+    pub fn is_copy(&self) -> bool {
+        matches!(self, CopyDecision::Copy)
+    }
+}
 
-pub struct CopyEngine;
+pub struct CopyEngine {
+    /// Converted symbols per member, reused across signals
+    symbol_cache: SymbolConversionCache,
+    /// Contract specifications for symbol-aware sizing
+    symbol_specs: Arc<SymbolSpecRegistry>,
+}
 
 impl CopyEngine {
     pub fn new() -> Self {
-        Self
+        Self {
+            symbol_cache: SymbolConversionCache::default(),
+            symbol_specs: Arc::new(SymbolSpecRegistry::default()),
+        }
+    }
+
+    /// Use the specifications loaded from `[symbol_specs]`
+    pub fn with_symbol_specs(mut self, symbol_specs: Arc<SymbolSpecRegistry>) -> Self {
+        self.symbol_specs = symbol_specs;
+        self
+    }
+
+    /// Contract specifications shared with sizing features
+    pub fn symbol_specs(&self) -> &SymbolSpecRegistry {
+        &self.symbol_specs
     }
 
     /// Apply filters to determine if a trade should be copied
-    pub fn should_copy_trade(&self, signal: &TradeSignal, member: &TradeGroupMember) -> bool {
+    pub fn should_copy_trade(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        context: &CopyContext,
+    ) -> CopyDecision {
+        // Never copy a Master's trades back onto its own account
+        if signal.source_account == member.slave_account {
+            tracing::warn!(
+                "Member {} is the signal's own Master account, refusing to copy ticket {}",
+                member.slave_account,
+                signal.ticket
+            );
+            return CopyDecision::SkipWithWarning(WarningCode::SelfCopyDetected);
+        }
+
         // Check if copying is enabled and master is connected (STATUS_CONNECTED = 2)
         if !member.is_connected() {
             tracing::debug!(
@@ -19,57 +164,155 @@ impl CopyEngine {
                 member.slave_account,
                 member.status
             );
-            return false;
+            return CopyDecision::Skip(SkipReason::NotConnected);
+        }
+
+        // Relay-wide blacklist applies to every member regardless of its own filters;
+        // only Opens are blocked so existing positions can still exit
+        if signal.action == TradeAction::Open && !context.global_blocked_symbols.is_empty() {
+            if let Some(ref symbol) = signal.symbol {
+                if SymbolPatterns::compile(&context.global_blocked_symbols).matches(symbol) {
+                    tracing::debug!("Symbol {} is blocked globally", symbol);
+                    return CopyDecision::SkipWithWarning(WarningCode::GloballyBlockedSymbol);
+                }
+            }
+        }
+
+        // Sampled out tickets were never opened, so their Close/Modify are skipped too
+        if let Some(rate) = member.slave_settings.copy_sample_rate {
+            if sample_value(signal.ticket, &member.slave_account) >= rate {
+                tracing::debug!(
+                    "Ticket {} not sampled for {} (copy_sample_rate={})",
+                    signal.ticket,
+                    member.slave_account,
+                    rate
+                );
+                return CopyDecision::Skip(SkipReason::NotSampled);
+            }
+        }
+
+        // Closes of positions opened before a filter change must still exit them
+        if signal.action == TradeAction::Close && member.slave_settings.always_allow_close {
+            return CopyDecision::Copy;
+        }
+
+        // Check pending order filter (only applies to Open signals;
+        // pending Opens converted to market orders are not pending on the Slave)
+        if signal.action == TradeAction::Open && !member.slave_settings.convert_pending_to_market {
+            if let Some(ref order_type) = signal.order_type {
+                if order_type.is_pending() && !member.slave_settings.copy_pending_orders {
+                    tracing::debug!("Pending orders disabled for this member");
+                    return CopyDecision::Skip(SkipReason::PendingOrdersDisabled);
+                }
+            }
         }
 
-        // Check pending order filter (only applies to Open signals)
+        // Check copy direction (only for Open signals; Close/Modify of copied tickets must pass)
         if signal.action == TradeAction::Open {
             if let Some(ref order_type) = signal.order_type {
-                let is_pending = matches!(
+                let is_buy = matches!(
                     order_type,
-                    OrderType::BuyLimit
-                        | OrderType::SellLimit
-                        | OrderType::BuyStop
-                        | OrderType::SellStop
+                    OrderType::Buy | OrderType::BuyLimit | OrderType::BuyStop
                 );
-                if is_pending && !member.slave_settings.copy_pending_orders {
-                    tracing::debug!("Pending orders disabled for this member");
-                    return false;
+                let allowed = match member.slave_settings.copy_direction {
+                    CopyDirection::Both => true,
+                    CopyDirection::BuyOnly => is_buy,
+                    CopyDirection::SellOnly => !is_buy,
+                };
+                if !allowed {
+                    tracing::debug!(
+                        "Order type {:?} filtered by copy_direction {:?}",
+                        order_type,
+                        member.slave_settings.copy_direction
+                    );
+                    return CopyDecision::Skip(SkipReason::CopyDirection);
+                }
+            }
+        }
+
+        // Check trading-hours window (only for Open signals; Close must still exit positions)
+        if signal.action == TradeAction::Open {
+            if let Some(ref window) = member.slave_settings.trading_hours {
+                match window.contains(signal.timestamp) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        tracing::debug!(
+                            "Signal at {} outside trading hours for slave {}",
+                            signal.timestamp,
+                            member.slave_account
+                        );
+                        return CopyDecision::Skip(SkipReason::OutsideTradingHours);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Invalid trading_hours for slave {}: {}, skipping",
+                            member.slave_account,
+                            e
+                        );
+                        return CopyDecision::Skip(SkipReason::InvalidTradingHours);
+                    }
                 }
             }
         }
 
+        // Check fixed lot configuration (only for Open signals)
+        if signal.action == TradeAction::Open
+            && member.slave_settings.lot_calculation_mode == LotCalculationMode::FixedLot
+            && !member.slave_settings.fixed_lot.is_some_and(|lot| lot > 0.0)
+        {
+            tracing::warn!(
+                "fixed_lot mode selected but fixed_lot is not a positive value ({:?}) for slave {}, skipping",
+                member.slave_settings.fixed_lot,
+                member.slave_account
+            );
+            return CopyDecision::Skip(SkipReason::InvalidFixedLot);
+        }
+
+        // Risk-based sizing needs the stop loss distance (only for Open signals)
+        if signal.action == TradeAction::Open
+            && member.slave_settings.lot_calculation_mode == LotCalculationMode::RiskPercent
+            && signal.stop_loss.is_none()
+        {
+            tracing::warn!(
+                "risk_percent mode requires a stop loss, skipping ticket {} for slave {}",
+                signal.ticket,
+                member.slave_account
+            );
+            return CopyDecision::SkipWithWarning(WarningCode::StopLossRequired);
+        }
+
         // Check source lot limits (only for Open signals with lots)
         if signal.action == TradeAction::Open {
             if let Some(lots) = signal.lots {
+                let inclusive = member.slave_settings.source_lot_bounds_inclusive;
                 if let Some(min) = member.slave_settings.source_lot_min {
-                    if lots < min {
+                    if lots < min || (!inclusive && lots == min) {
                         tracing::debug!("Lots {} below minimum {}", lots, min);
-                        return false;
+                        return CopyDecision::Skip(SkipReason::SourceLotBelowMin);
                     }
                 }
                 if let Some(max) = member.slave_settings.source_lot_max {
-                    if lots > max {
+                    if lots > max || (!inclusive && lots == max) {
                         tracing::debug!("Lots {} above maximum {}", lots, max);
-                        return false;
+                        return CopyDecision::Skip(SkipReason::SourceLotAboveMax);
                     }
                 }
             }
         }
 
-        // Check symbol filters (only if signal has symbol)
+        // Check symbol filters (only if signal has symbol); entries with `*`/`?` are globs
         if let Some(ref symbol) = signal.symbol {
             if let Some(ref allowed) = member.slave_settings.filters.allowed_symbols {
-                if !allowed.contains(symbol) {
+                if !SymbolPatterns::compile(allowed).matches(symbol) {
                     tracing::debug!("Symbol {} not in allowed list", symbol);
-                    return false;
+                    return CopyDecision::Skip(SkipReason::SymbolNotAllowed);
                 }
             }
 
             if let Some(ref blocked) = member.slave_settings.filters.blocked_symbols {
-                if blocked.contains(symbol) {
+                if SymbolPatterns::compile(blocked).matches(symbol) {
                     tracing::debug!("Symbol {} is blocked", symbol);
-                    return false;
+                    return CopyDecision::Skip(SkipReason::SymbolBlocked);
                 }
             }
         }
@@ -79,40 +322,464 @@ impl CopyEngine {
             if let Some(ref allowed) = member.slave_settings.filters.allowed_magic_numbers {
                 if !allowed.contains(&magic_number) {
                     tracing::debug!("Magic number {} not in allowed list", magic_number);
-                    return false;
+                    return CopyDecision::Skip(SkipReason::MagicNotAllowed);
                 }
             }
 
             if let Some(ref blocked) = member.slave_settings.filters.blocked_magic_numbers {
                 if blocked.contains(&magic_number) {
                     tracing::debug!("Magic number {} is blocked", magic_number);
-                    return false;
+                    return CopyDecision::Skip(SkipReason::MagicBlocked);
+                }
+            }
+        }
+
+        // Check comment keyword filters (case-insensitive substring match)
+        let comment = signal.comment.as_deref().unwrap_or("").to_lowercase();
+        if let Some(ref allowed) = member.slave_settings.filters.allowed_comment_substrings {
+            if !allowed.is_empty()
+                && !allowed
+                    .iter()
+                    .any(|keyword| comment.contains(&keyword.to_lowercase()))
+            {
+                tracing::debug!(
+                    "Comment {:?} does not match allowed keywords",
+                    signal.comment
+                );
+                return CopyDecision::Skip(SkipReason::CommentNotAllowed);
+            }
+        }
+        if let Some(ref blocked) = member.slave_settings.filters.blocked_comment_substrings {
+            if blocked
+                .iter()
+                .any(|keyword| !keyword.is_empty() && comment.contains(&keyword.to_lowercase()))
+            {
+                tracing::debug!("Comment {:?} matches a blocked keyword", signal.comment);
+                return CopyDecision::Skip(SkipReason::CommentBlocked);
+            }
+        }
+
+        // Check the slave's concurrent position limit (Close/Modify always pass)
+        if signal.action == TradeAction::Open {
+            if let (Some(limit), Some(open)) = (
+                member.slave_settings.max_open_positions,
+                context.slave_open_positions,
+            ) {
+                if open >= limit {
+                    tracing::warn!(
+                        "Slave {} holds {} positions (max_open_positions={}), skipping ticket {}",
+                        member.slave_account,
+                        open,
+                        limit,
+                        signal.ticket
+                    );
+                    return CopyDecision::SkipWithWarning(WarningCode::MaxPositionsReached);
+                }
+            }
+        }
+
+        // Daily loss circuit breaker (Close/Modify always pass so positions can still be exited)
+        if signal.action == TradeAction::Open {
+            if let (Some(limit), Some(loss)) = (
+                member.slave_settings.max_daily_loss,
+                context.slave_daily_loss,
+            ) {
+                if loss > limit {
+                    tracing::warn!(
+                        "Slave {} lost {:.2} today (max_daily_loss={:.2}), skipping ticket {}",
+                        member.slave_account,
+                        loss,
+                        limit,
+                        signal.ticket
+                    );
+                    return CopyDecision::SkipWithWarning(WarningCode::DailyLossLimitHit);
+                }
+            }
+        }
+
+        // Spread filter (Open only; senders that do not report a spread always pass)
+        if signal.action == TradeAction::Open {
+            if let (Some(limit), Some(spread)) = (
+                member.slave_settings.max_spread_points,
+                signal.spread_points,
+            ) {
+                if spread > limit {
+                    tracing::warn!(
+                        "Spread {} points exceeds max_spread_points={} for slave {}, skipping ticket {}",
+                        spread,
+                        limit,
+                        member.slave_account,
+                        signal.ticket
+                    );
+                    return CopyDecision::SkipWithWarning(WarningCode::SpreadTooWide);
+                }
+            }
+        }
+
+        // Check the relay-calculated lot against the broker minimum (only for Open signals)
+        let settings = &member.slave_settings;
+        if signal.action == TradeAction::Open && settings.lot_calculation_mode.is_relay_calculated()
+        {
+            if let (Some(lots), Some(lot_min)) = (signal.lots, settings.lot_min) {
+                let lots = portfolio_share(lots, member, context);
+                let stepped = floor_to_step(
+                    self.calculate_lots(lots, signal, member, context),
+                    settings.lot_step,
+                );
+                if stepped < lot_min {
+                    tracing::warn!(
+                        "Calculated lot {} is below lot_min {} for slave {}, skipping ticket {}",
+                        stepped,
+                        lot_min,
+                        member.slave_account,
+                        signal.ticket
+                    );
+                    return CopyDecision::SkipWithWarning(WarningCode::LotBelowMinimum);
                 }
             }
         }
 
-        true
+        CopyDecision::Copy
     }
 
     /// Transform trade signal for slave account
-    /// Relay Server handles symbol transformations only
-    /// Lot calculation and trade reversal are handled by Slave EA
+    /// Relay Server handles symbol transformations and relay-side lot modes
+    /// (equity_ratio, fixed_lot, risk_percent).
+    /// Multiplier/margin_ratio lot calculation and trade reversal are handled by Slave EA
     pub fn transform_signal(
         &self,
         signal: TradeSignal,
         member: &TradeGroupMember,
         converter: &SymbolConverter,
+        context: &CopyContext,
     ) -> Result<TradeSignal> {
         let mut transformed = signal.clone();
 
         // Apply symbol transformation (Master prefix/suffix removal + Slave mapping/prefix/suffix)
         if let Some(ref symbol) = signal.symbol {
-            transformed.symbol =
-                Some(converter.convert(symbol, &member.slave_settings.symbol_mappings));
+            if !member.slave_settings.disable_symbol_conversion {
+                transformed.symbol = Some(self.symbol_cache.convert(member, converter, symbol));
+            }
+        }
+
+        // Remap magic number (after filtering, so magic filters match the Master's value)
+        if let Some(magic_number) = signal.magic_number {
+            let settings = &member.slave_settings;
+            if let Some(mapped) = settings.magic_number_map.get(&magic_number) {
+                transformed.magic_number = Some(*mapped);
+            } else if let Some(default_magic) = settings.default_magic {
+                transformed.magic_number = Some(default_magic);
+            }
+        }
+
+        // Replace the comment of Opens from the member's template
+        if signal.action == TradeAction::Open {
+            if let Some(template) = &member.slave_settings.comment_template {
+                transformed.comment = Some(expand_comment_template(template, &signal));
+            }
+        }
+
+        // Copy pending Opens as market orders of the same direction
+        if signal.action == TradeAction::Open && member.slave_settings.convert_pending_to_market {
+            transformed.order_type = signal.order_type.as_ref().map(OrderType::to_market);
+        }
+
+        // Apply relay-side lot calculation (only Open signals carry a lot to scale)
+        if signal.action == TradeAction::Open {
+            if let Some(lots) = signal.lots {
+                let lots = portfolio_share(lots, member, context);
+                let lots = self.calculate_lots(lots, &signal, member, context);
+                // Normalize to the broker's volume constraints; EA-side modes are sized by the EA
+                transformed.lots = if member
+                    .slave_settings
+                    .lot_calculation_mode
+                    .is_relay_calculated()
+                {
+                    Some(normalize_lots(lots, &member.slave_settings))
+                } else {
+                    Some(lots)
+                };
+            }
+        }
+
+        // Under reverse_trade the Slave holds the opposite side of the Master ticket.
+        // Master Closes carry no symbol/order type, so name the copied Open's side here;
+        // the EA reverses it like the Open, and the Close (and its close_ratio) applies to
+        // the Slave ticket mapped from this Master ticket.
+        if signal.action == TradeAction::Close && member.slave_settings.reverse_trade {
+            if transformed.symbol.is_none() {
+                transformed.symbol = context.slave_position_symbol.clone();
+            }
+            if transformed.order_type.is_none() {
+                transformed.order_type = context.slave_position_order_type.clone();
+            }
+        }
+
+        // Express partial closes as slave-side lots instead of a ratio
+        if signal.action == TradeAction::Close && member.slave_settings.partial_close_by_lots {
+            if let (Some(ratio), Some(open_lots)) = (
+                signal.close_ratio.filter(|r| *r > 0.0 && *r < 1.0),
+                context.slave_position_lots,
+            ) {
+                transformed.lots = Some(round_lots(open_lots * ratio, &member.slave_settings));
+                transformed.close_ratio = None;
+            }
+        }
+
+        // Send SL/TP as pip offsets from the Master entry; the Slave EA adds them to
+        // its own fill price (Close signals carry no stops to place)
+        if member.slave_settings.sl_tp_as_offset && signal.action != TradeAction::Close {
+            match (signal.open_price, transformed.symbol.as_deref()) {
+                (Some(open_price), Some(symbol)) => {
+                    let pip = self.symbol_specs.pip_size(symbol);
+                    transformed.stop_loss =
+                        signal.stop_loss.map(|sl| price_offset(sl, open_price, pip));
+                    transformed.take_profit = signal
+                        .take_profit
+                        .map(|tp| price_offset(tp, open_price, pip));
+                    transformed.sl_tp_offset = true;
+                }
+                _ => {
+                    tracing::debug!(
+                        "SL/TP offset conversion skipped for ticket {} (no open_price/symbol)",
+                        signal.ticket
+                    );
+                }
+            }
         }
 
         Ok(transformed)
     }
+
+    /// Lot size the Slave should end up holding for an Open signal, including the
+    /// EA-side multiplier. None for margin_ratio, which depends on the Slave's live margin.
+    pub fn expected_slave_lots(
+        &self,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        context: &CopyContext,
+    ) -> Option<f64> {
+        let lots = portfolio_share(signal.lots?, member, context);
+        let settings = &member.slave_settings;
+        match settings.lot_calculation_mode {
+            LotCalculationMode::MarginRatio => None,
+            LotCalculationMode::Multiplier => {
+                Some(round_lots(lots * lot_multiplier(member, context), settings))
+            }
+            _ => Some(normalize_lots(
+                self.calculate_lots(lots, signal, member, context),
+                settings,
+            )),
+        }
+    }
+
+    /// Calculate the slave lot size for modes handled by the relay-server.
+    /// Modes handled by the Slave EA (multiplier, margin_ratio) pass through unchanged,
+    /// except that multiplier lots are pre-scaled when the EA's multiplier exceeds the cap.
+    fn calculate_lots(
+        &self,
+        lots: f64,
+        signal: &TradeSignal,
+        member: &TradeGroupMember,
+        context: &CopyContext,
+    ) -> f64 {
+        let settings = &member.slave_settings;
+        match settings.lot_calculation_mode {
+            LotCalculationMode::Multiplier => {
+                let configured = settings.lot_multiplier.unwrap_or(1.0);
+                let effective = lot_multiplier(member, context);
+                // Only a clamp needs pre-scaling; multiplying back and forth adds float noise
+                if configured > 0.0 && effective != configured {
+                    lots * effective / configured
+                } else {
+                    lots
+                }
+            }
+            LotCalculationMode::MarginRatio => lots,
+            LotCalculationMode::EquityRatio => {
+                match (context.master_equity, context.slave_equity) {
+                    // Equity of 0 means the EA registered but has not sent a heartbeat yet
+                    (Some(master_equity), Some(slave_equity))
+                        if master_equity > 0.0 && slave_equity > 0.0 =>
+                    {
+                        round_lots(lots * slave_equity / master_equity, settings)
+                    }
+                    _ => {
+                        tracing::warn!(
+                            "Equity unavailable for equity_ratio (master={:?}, slave={:?}), falling back to multiplier for slave {}",
+                            context.master_equity,
+                            context.slave_equity,
+                            member.slave_account
+                        );
+                        round_lots(lots * lot_multiplier(member, context), settings)
+                    }
+                }
+            }
+            // Validated in should_copy_trade; keep the Master's lot if it is somehow missing
+            LotCalculationMode::FixedLot => settings.fixed_lot.unwrap_or(lots),
+            LotCalculationMode::RiskPercent => {
+                match risk_lots(signal, settings, context, &self.symbol_specs) {
+                    Some(risk_lots) => risk_lots,
+                    None => {
+                        tracing::warn!(
+                        "Cannot calculate risk_percent lots (risk={:?}, balance={:?}, pip_value={:?}, open={:?}, sl={:?}), falling back to multiplier for slave {}",
+                        settings.risk_percent,
+                        context.slave_balance,
+                        context.pip_value,
+                        signal.open_price,
+                        signal.stop_loss,
+                        member.slave_account
+                    );
+                        round_lots(lots * lot_multiplier(member, context), settings)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The member's weighted share of the Master lots (capped at `total_lot_budget`):
+/// lots * weight / sum_of_weights. Unweighted members get the Master lots unchanged.
+fn portfolio_share(lots: f64, member: &TradeGroupMember, context: &CopyContext) -> f64 {
+    let settings = &member.slave_settings;
+    match (
+        settings.portfolio_weight.filter(|w| *w > 0.0),
+        context.portfolio_weight_total,
+    ) {
+        (Some(weight), Some(total)) if total > 0.0 => {
+            let pooled = context
+                .total_lot_budget
+                .filter(|budget| *budget > 0.0)
+                .map_or(lots, |budget| lots.min(budget));
+            round_lots(pooled * weight / total, settings)
+        }
+        _ => lots,
+    }
+}
+
+/// `lot_multiplier` (default 1.0) capped at the relay-wide `max_lot_multiplier`.
+/// A value above the cap was saved before the cap was lowered; clamp and warn.
+fn lot_multiplier(member: &TradeGroupMember, context: &CopyContext) -> f64 {
+    let multiplier = member.slave_settings.lot_multiplier.unwrap_or(1.0);
+    match context.max_lot_multiplier {
+        Some(max) if multiplier > max => {
+            tracing::warn!(
+                slave_account = %member.slave_account,
+                lot_multiplier = multiplier,
+                max_lot_multiplier = max,
+                "Clamping lot_multiplier to the global max_lot_multiplier"
+            );
+            max
+        }
+        _ => multiplier,
+    }
+}
+
+/// Lots that risk `risk_percent` of the slave balance if the stop loss is hit:
+/// lots = (balance * risk% / 100) / (stop distance in pips * pip value per lot).
+/// Always rounded down at `lot_decimals` so the configured risk is never exceeded.
+fn risk_lots(
+    signal: &TradeSignal,
+    settings: &SlaveSettings,
+    context: &CopyContext,
+    symbol_specs: &SymbolSpecRegistry,
+) -> Option<f64> {
+    let risk_percent = settings.risk_percent.filter(|r| *r > 0.0)?;
+    let balance = context.slave_balance.filter(|b| *b > 0.0)?;
+    let pip_value = context.pip_value.filter(|v| *v > 0.0)?;
+    let symbol = signal.symbol.as_deref()?;
+    let open_price = signal.open_price?;
+    let stop_loss = signal.stop_loss?;
+
+    // Round to fractional-pip precision to absorb floating point noise in the price delta
+    let stop_pips =
+        ((open_price - stop_loss).abs() / symbol_specs.pip_size(symbol) * 10.0).round() / 10.0;
+    if stop_pips <= 0.0 {
+        return None;
+    }
+
+    let risk_amount = balance * risk_percent / 100.0;
+    let factor = lot_factor(settings.lot_decimals);
+    Some((risk_amount / (stop_pips * pip_value) * factor + 1e-9).floor() / factor)
+}
+
+/// Deterministic value in [0, 1) for a (ticket, slave) pair: FNV-1a over both,
+/// mixed with the SplitMix64 finalizer. Stable across restarts and builds, so a
+/// ticket keeps its sampling decision for its whole life.
+fn sample_value(ticket: i64, slave_account: &str) -> f64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in ticket.to_le_bytes().iter().chain(slave_account.as_bytes()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    // Top 53 bits fill an f64 mantissa exactly
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Expand `{master}`, `{orig_comment}` and `{ticket}` in a comment template,
+/// cut to MetaTrader's comment length
+fn expand_comment_template(template: &str, signal: &TradeSignal) -> String {
+    template
+        .replace("{master}", &signal.source_account)
+        .replace("{ticket}", &signal.ticket.to_string())
+        .replace("{orig_comment}", signal.comment.as_deref().unwrap_or(""))
+        .chars()
+        .take(MT_COMMENT_MAX_CHARS)
+        .collect()
+}
+
+/// Signed price distance of `price` from `entry`, snapped to 0.1 pip
+fn price_offset(price: f64, entry: f64, pip: f64) -> f64 {
+    let points = ((price - entry) / pip * 10.0).round();
+    // Scale by an integer so the result carries no float noise (e.g. 500 × 0.00001)
+    points / (10.0 / pip)
+}
+
+/// Round lots down to a multiple of the broker volume step (no-op without a step)
+fn floor_to_step(lots: f64, lot_step: Option<f64>) -> f64 {
+    match lot_step.filter(|step| *step > 0.0) {
+        Some(step) => {
+            // Epsilon absorbs floating point noise (e.g. 0.29 / 0.01 = 28.999...)
+            let steps = (lots / step + 1e-9).floor();
+            (steps * step * 1e8).round() / 1e8
+        }
+        None => lots,
+    }
+}
+
+/// Apply broker volume constraints: round down to `lot_step`, then clamp into `[lot_min, lot_max]`
+fn normalize_lots(lots: f64, settings: &SlaveSettings) -> f64 {
+    let mut lots = floor_to_step(lots, settings.lot_step);
+    if let Some(max) = settings.lot_max {
+        lots = lots.min(max);
+    }
+    if let Some(min) = settings.lot_min {
+        lots = lots.max(min);
+    }
+    lots
+}
+
+/// Scale factor for `decimals` places of lot precision (2 -> 100.0)
+fn lot_factor(decimals: u8) -> f64 {
+    10f64.powi(i32::from(decimals))
+}
+
+/// Round lots to `lot_decimals` places using `lot_rounding`
+fn round_lots(lots: f64, settings: &SlaveSettings) -> f64 {
+    let factor = lot_factor(settings.lot_decimals);
+    let scaled = lots * factor;
+    // Epsilon absorbs floating point noise (e.g. 0.29 * 100 = 28.999...)
+    let rounded = match settings.lot_rounding {
+        RoundingMode::Nearest => scaled.round(),
+        RoundingMode::Down => (scaled + 1e-9).floor(),
+        RoundingMode::Up => (scaled - 1e-9).ceil(),
+    };
+    rounded / factor
 }
 
 impl Default for CopyEngine {
@@ -123,7 +790,7 @@ impl Default for CopyEngine {
 
 #[cfg(test)]
 mod tests {
-    use super::CopyEngine;
+    use super::{CopyContext, CopyDecision, CopyEngine, SkipReason};
     use crate::domain::models::*;
     use chrono::Utc;
 
@@ -146,6 +813,9 @@ mod tests {
             timestamp: Utc::now(),
             source_account: "MASTER_001".to_string(),
             close_ratio: None,
+            seq: 0,
+            spread_points: None,
+            sl_tp_offset: false,
         }
     }
 
@@ -157,6 +827,8 @@ mod tests {
             slave_settings: SlaveSettings::default(),
             status: 2, // STATUS_CONNECTED
             warning_codes: Vec::new(),
+            acknowledged_warnings: Vec::new(),
+            warnings: Vec::new(),
             enabled_flag: true,
             created_at: Utc::now().to_rfc3339(),
             updated_at: Utc::now().to_rfc3339(),
@@ -171,6 +843,7 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         }
     }
 
@@ -184,7 +857,9 @@ mod tests {
         let signal = create_test_signal();
         let member = create_test_member(); // status = 2 (CONNECTED)
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -194,7 +869,10 @@ mod tests {
         let mut member = create_test_member();
         member.status = 0; // STATUS_DISABLED
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::NotConnected)
+        );
     }
 
     // =============================================================================
@@ -209,7 +887,9 @@ mod tests {
         member.slave_settings.filters.allowed_symbols =
             Some(vec!["EURUSD".to_string(), "GBPUSD".to_string()]);
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -219,7 +899,52 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.filters.allowed_symbols = Some(vec!["GBPUSD".to_string()]);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_close_bypasses_narrowed_allow_list() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.filters.allowed_symbols = Some(vec!["EURUSD".to_string()]);
+        assert!(engine
+            .should_copy_trade(&create_test_signal(), &member, &CopyContext::default())
+            .is_copy());
+
+        // EURUSD removed from the allow-list while the position is open
+        member.slave_settings.filters.allowed_symbols = Some(vec!["GBPUSD".to_string()]);
+        member.slave_settings.filters.blocked_magic_numbers = Some(vec![0]);
+        let mut close = create_test_signal();
+        close.action = TradeAction::Close;
+        close.magic_number = Some(0);
+        assert!(engine
+            .should_copy_trade(&close, &member, &CopyContext::default())
+            .is_copy());
+
+        member.slave_settings.always_allow_close = false;
+        assert_eq!(
+            engine.should_copy_trade(&close, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::SymbolNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_self_copy_member_is_never_copied() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_account = "MASTER_001".to_string();
+
+        // Even with a stale CONNECTED status and a Close signal
+        let mut close = create_test_signal();
+        close.action = TradeAction::Close;
+        for signal in [create_test_signal(), close] {
+            assert_eq!(
+                engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+                CopyDecision::SkipWithWarning(WarningCode::SelfCopyDetected)
+            );
+        }
     }
 
     #[test]
@@ -229,7 +954,97 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.filters.blocked_symbols = Some(vec!["EURUSD".to_string()]);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::SymbolBlocked)
+        );
+    }
+
+    #[test]
+    fn test_filter_globally_blocked_symbol_overrides_member_allow_list() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.filters.allowed_symbols =
+            Some(vec!["USDTRY".to_string(), "EURUSD".to_string()]);
+        let context = CopyContext {
+            global_blocked_symbols: vec!["USDTRY".to_string(), "*ZAR".to_string()],
+            ..CopyContext::default()
+        };
+
+        for (symbol, blocked) in [("USDTRY", true), ("USDZAR", true), ("EURUSD", false)] {
+            let mut signal = create_test_signal();
+            signal.symbol = Some(symbol.to_string());
+            let expected = if blocked {
+                CopyDecision::SkipWithWarning(WarningCode::GloballyBlockedSymbol)
+            } else {
+                CopyDecision::Copy
+            };
+            assert_eq!(
+                engine.should_copy_trade(&signal, &member, &context),
+                expected,
+                "{}",
+                symbol
+            );
+        }
+
+        // Existing positions can still exit or be modified
+        for action in [TradeAction::Close, TradeAction::Modify] {
+            let mut signal = create_test_signal();
+            signal.action = action;
+            signal.symbol = Some("USDTRY".to_string());
+            assert_eq!(
+                engine.should_copy_trade(&signal, &member, &context),
+                CopyDecision::Copy
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_blocked_symbol_glob() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.filters.blocked_symbols =
+            Some(vec!["XAU*".to_string(), "GBPJPY".to_string()]);
+
+        for (symbol, blocked) in [
+            ("XAUUSD", true),
+            ("XAUEUR", true),
+            ("EURUSD", false),
+            ("GBPJPY", true),    // exact entries still work
+            ("GBPJPY.m", false), // and stay exact
+        ] {
+            let mut signal = create_test_signal();
+            signal.symbol = Some(symbol.to_string());
+            let expected = if blocked {
+                CopyDecision::Skip(SkipReason::SymbolBlocked)
+            } else {
+                CopyDecision::Copy
+            };
+            assert_eq!(
+                engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+                expected,
+                "{}",
+                symbol
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_allowed_symbol_glob() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // EURUSD
+        let mut member = create_test_member();
+        member.slave_settings.filters.allowed_symbols = Some(vec!["EUR???".to_string()]);
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+
+        member.slave_settings.filters.allowed_symbols = Some(vec!["XAU*".to_string()]);
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::SymbolNotAllowed)
+        );
     }
 
     // =============================================================================
@@ -243,7 +1058,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.filters.allowed_magic_numbers = Some(vec![0, 123]);
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -253,7 +1070,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.filters.allowed_magic_numbers = Some(vec![123, 456]);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -263,7 +1082,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.filters.blocked_magic_numbers = Some(vec![0]);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     // =============================================================================
@@ -278,7 +1099,9 @@ mod tests {
         member.slave_settings.source_lot_min = Some(0.05);
         member.slave_settings.source_lot_max = Some(1.0);
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -288,7 +1111,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.source_lot_min = Some(0.5);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -298,7 +1123,44 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.source_lot_max = Some(0.05);
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_lots_equal_to_max_inclusive() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.source_lot_max = Some(0.1);
+        assert!(member.slave_settings.source_lot_bounds_inclusive); // default
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_lots_equal_to_max_exclusive() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.source_lot_max = Some(0.1);
+        member.slave_settings.source_lot_bounds_inclusive = false;
+
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::SourceLotAboveMax)
+        );
+
+        // The min bound is exclusive as well
+        member.slave_settings.source_lot_max = None;
+        member.slave_settings.source_lot_min = Some(0.1);
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::SourceLotBelowMin)
+        );
     }
 
     #[test]
@@ -310,7 +1172,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.source_lot_min = Some(0.5); // Would reject if Open
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     // =============================================================================
@@ -325,7 +1189,9 @@ mod tests {
         let mut member = create_test_member();
         member.slave_settings.copy_pending_orders = true;
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -335,7 +1201,9 @@ mod tests {
         signal.order_type = Some(OrderType::BuyLimit);
         let member = create_test_member(); // copy_pending_orders = false by default
 
-        assert!(!engine.should_copy_trade(&signal, &member));
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -344,7 +1212,9 @@ mod tests {
         let signal = create_test_signal(); // Buy (market order)
         let member = create_test_member(); // copy_pending_orders = false
 
-        assert!(engine.should_copy_trade(&signal, &member));
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
     }
 
     #[test]
@@ -361,13 +1231,55 @@ mod tests {
             let mut signal = create_test_signal();
             signal.order_type = Some(order_type.clone());
             assert!(
-                !engine.should_copy_trade(&signal, &member),
+                !engine
+                    .should_copy_trade(&signal, &member, &CopyContext::default())
+                    .is_copy(),
                 "{:?} should be blocked",
                 order_type
             );
         }
     }
 
+    #[test]
+    fn test_convert_pending_to_market_copies_buy_limit_as_buy() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.order_type = Some(OrderType::BuyLimit);
+        let mut member = create_test_member(); // copy_pending_orders = false
+        member.slave_settings.convert_pending_to_market = true;
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+        let transformed = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+        assert_eq!(transformed.order_type, Some(OrderType::Buy));
+    }
+
+    #[test]
+    fn test_convert_pending_to_market_leaves_market_order_unchanged() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // Buy
+        let mut member = create_test_member();
+        member.slave_settings.convert_pending_to_market = true;
+
+        let transformed = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+        assert_eq!(transformed.order_type, Some(OrderType::Buy));
+    }
+
     // =============================================================================
     // Transform Tests: Lot Passthrough (Slave EA handles calculation)
     // =============================================================================
@@ -380,46 +1292,183 @@ mod tests {
         member.slave_settings.lot_multiplier = Some(2.0); // Ignored
 
         let result = engine
-            .transform_signal(signal, &member, &create_converter())
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
             .unwrap();
 
         assert_eq!(result.lots, Some(0.1));
     }
 
     #[test]
-    fn test_transform_lots_unchanged_for_close() {
+    fn test_transform_unclamped_lot_multiplier_keeps_exact_lots() {
         let engine = CopyEngine::new();
         let mut signal = create_test_signal();
-        signal.action = TradeAction::Close;
-        signal.lots = Some(1.0);
-        signal.close_ratio = Some(0.5);
+        signal.lots = Some(0.03);
         let mut member = create_test_member();
-        member.slave_settings.lot_multiplier = Some(2.0); // Ignored
+        // 0.03 * 4.3 / 4.3 would be 0.030000000000000002
+        member.slave_settings.lot_multiplier = Some(4.3);
+        let context = CopyContext {
+            max_lot_multiplier: Some(10.0),
+            ..CopyContext::default()
+        };
 
         let result = engine
-            .transform_signal(signal, &member, &create_converter())
+            .transform_signal(signal, &member, &create_converter(), &context)
             .unwrap();
 
-        assert_eq!(result.lots, Some(1.0));
-        assert_eq!(result.close_ratio, Some(0.5));
+        assert_eq!(result.lots, Some(0.03));
     }
 
-    // =============================================================================
-    // Transform Tests: Order Type Passthrough (Slave EA handles reversal)
-    // =============================================================================
-
     #[test]
-    fn test_transform_order_type_unchanged() {
+    fn test_transform_clamps_grandfathered_lot_multiplier() {
         let engine = CopyEngine::new();
-        let signal = create_test_signal(); // Buy
         let mut member = create_test_member();
-        member.slave_settings.reverse_trade = true; // Ignored
+        // Saved before the global cap was lowered to 2.0
+        member.slave_settings.lot_multiplier = Some(5.0);
+        let context = CopyContext {
+            max_lot_multiplier: Some(2.0),
+            ..CopyContext::default()
+        };
 
+        // Multiplier mode: pre-scaled so the EA's 5x lands on 2x of the Master lot
         let result = engine
-            .transform_signal(signal, &member, &create_converter())
+            .transform_signal(create_test_signal(), &member, &create_converter(), &context)
             .unwrap();
+        let lots = result.lots.unwrap();
+        assert!((lots * 5.0 - 0.2).abs() < 1e-9, "lots = {}", lots);
+        assert_eq!(
+            engine.expected_slave_lots(&create_test_signal(), &member, &context),
+            Some(0.2)
+        );
+
+        // Relay-side fallback to the multiplier is capped too
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        let result = engine
+            .transform_signal(create_test_signal(), &member, &create_converter(), &context)
+            .unwrap();
+        assert_eq!(result.lots, Some(0.2));
+    }
 
-        assert!(matches!(result.order_type, Some(OrderType::Buy)));
+    fn weighted_members(weights: &[f64]) -> Vec<TradeGroupMember> {
+        weights
+            .iter()
+            .enumerate()
+            .map(|(i, weight)| {
+                let mut member = create_test_member();
+                member.slave_account = format!("SLAVE_{}", i + 1);
+                member.slave_settings.portfolio_weight = Some(*weight);
+                member
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_transform_splits_master_lots_by_portfolio_weight() {
+        let engine = CopyEngine::new();
+        let members = weighted_members(&[1.0, 3.0]);
+        let context = CopyContext {
+            portfolio_weight_total: super::portfolio_weight_total(&members),
+            ..CopyContext::default()
+        };
+        let mut signal = create_test_signal();
+        signal.lots = Some(0.4);
+
+        let lots: Vec<_> = members
+            .iter()
+            .map(|member| {
+                engine
+                    .transform_signal(signal.clone(), member, &create_converter(), &context)
+                    .unwrap()
+                    .lots
+            })
+            .collect();
+        assert_eq!(lots, vec![Some(0.1), Some(0.3)]);
+        assert_eq!(
+            engine.expected_slave_lots(&signal, &members[1], &context),
+            Some(0.3)
+        );
+
+        // Unweighted members keep the Master lots
+        let unweighted = create_test_member();
+        let result = engine
+            .transform_signal(signal, &unweighted, &create_converter(), &context)
+            .unwrap();
+        assert_eq!(result.lots, Some(0.4));
+    }
+
+    #[test]
+    fn test_transform_portfolio_weight_capped_at_total_lot_budget() {
+        let engine = CopyEngine::new();
+        let members = weighted_members(&[1.0, 3.0]);
+        let context = CopyContext {
+            portfolio_weight_total: super::portfolio_weight_total(&members),
+            total_lot_budget: Some(0.2),
+            ..CopyContext::default()
+        };
+        let mut signal = create_test_signal();
+        signal.lots = Some(0.4);
+
+        let result = engine
+            .transform_signal(signal.clone(), &members[1], &create_converter(), &context)
+            .unwrap();
+        assert_eq!(result.lots, Some(0.15));
+
+        // Below the budget the Master lots are split as-is
+        signal.lots = Some(0.08);
+        let result = engine
+            .transform_signal(signal, &members[1], &create_converter(), &context)
+            .unwrap();
+        assert_eq!(result.lots, Some(0.06));
+    }
+
+    #[test]
+    fn test_transform_lots_unchanged_for_close() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Close;
+        signal.lots = Some(1.0);
+        signal.close_ratio = Some(0.5);
+        let mut member = create_test_member();
+        member.slave_settings.lot_multiplier = Some(2.0); // Ignored
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(1.0));
+        assert_eq!(result.close_ratio, Some(0.5));
+    }
+
+    // =============================================================================
+    // Transform Tests: Order Type Passthrough (Slave EA handles reversal)
+    // =============================================================================
+
+    #[test]
+    fn test_transform_order_type_unchanged() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // Buy
+        let mut member = create_test_member();
+        member.slave_settings.reverse_trade = true; // Ignored
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert!(matches!(result.order_type, Some(OrderType::Buy)));
     }
 
     #[test]
@@ -431,12 +1480,145 @@ mod tests {
         let member = create_test_member();
 
         let result = engine
-            .transform_signal(signal, &member, &create_converter())
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
             .unwrap();
 
         assert_eq!(result.close_ratio, Some(0.5));
     }
 
+    #[test]
+    fn test_transform_reversed_partial_close_targets_copied_side() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.reverse_trade = true;
+        member.slave_settings.reverse_symbols = Some(vec!["EURUSD".to_string()]);
+
+        // Open is forwarded as the Master's Buy; the EA reverses it to a Sell
+        let open = engine
+            .transform_signal(
+                create_test_signal(),
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+        assert!(matches!(open.order_type, Some(OrderType::Buy)));
+
+        // Master partial Close: no symbol/order type of its own
+        let mut close = create_test_signal();
+        close.action = TradeAction::Close;
+        close.symbol = None;
+        close.order_type = None;
+        close.close_ratio = Some(0.4);
+        let context = CopyContext {
+            slave_position_lots: open.lots,
+            slave_position_symbol: open.symbol.clone(),
+            slave_position_order_type: open.order_type.clone(),
+            ..CopyContext::default()
+        };
+
+        let result = engine
+            .transform_signal(close.clone(), &member, &create_converter(), &context)
+            .unwrap();
+        // Same side/symbol as the Open, so reverse_symbols matches and the EA
+        // reverses it to the Slave's Sell; the ratio is untouched
+        assert_eq!(result.symbol.as_deref(), Some("EURUSD"));
+        assert!(matches!(result.order_type, Some(OrderType::Buy)));
+        assert_eq!(result.close_ratio, Some(0.4));
+        assert_eq!(result.ticket, close.ticket);
+
+        // Without reverse_trade the Close passes through unchanged
+        member.slave_settings.reverse_trade = false;
+        let plain = engine
+            .transform_signal(close, &member, &create_converter(), &context)
+            .unwrap();
+        assert_eq!(plain.symbol, None);
+        assert_eq!(plain.order_type, None);
+        assert_eq!(plain.close_ratio, Some(0.4));
+    }
+
+    fn partial_close_signal(ratio: f64) -> TradeSignal {
+        let mut signal = create_test_signal(); // master lots = 0.1
+        signal.action = TradeAction::Close;
+        signal.close_ratio = Some(ratio);
+        signal
+    }
+
+    fn open_lots_context(lots: f64) -> CopyContext {
+        CopyContext {
+            slave_position_lots: Some(lots),
+            ..CopyContext::default()
+        }
+    }
+
+    #[test]
+    fn test_transform_partial_close_ratio_mode_vs_lots_mode() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        // Slave opened 0.3 lots for the master's 0.1
+        let context = open_lots_context(0.3);
+
+        // Ratio mode (default): ratio forwarded, master lots left untouched
+        let ratio_mode = engine
+            .transform_signal(
+                partial_close_signal(0.5),
+                &member,
+                &create_converter(),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(ratio_mode.close_ratio, Some(0.5));
+        assert_eq!(ratio_mode.lots, Some(0.1));
+
+        // Lots mode: slave-side lots, ratio cleared
+        member.slave_settings.partial_close_by_lots = true;
+        let lots_mode = engine
+            .transform_signal(
+                partial_close_signal(0.5),
+                &member,
+                &create_converter(),
+                &context,
+            )
+            .unwrap();
+        assert_eq!(lots_mode.close_ratio, None);
+        assert_eq!(lots_mode.lots, Some(0.15));
+    }
+
+    #[test]
+    fn test_transform_partial_close_lots_mode_falls_back_to_ratio() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.partial_close_by_lots = true;
+
+        // Untracked Open (e.g. relay restarted): keep the ratio
+        let untracked = engine
+            .transform_signal(
+                partial_close_signal(0.5),
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+        assert_eq!(untracked.close_ratio, Some(0.5));
+
+        // Full close is never converted
+        let full = engine
+            .transform_signal(
+                partial_close_signal(1.0),
+                &member,
+                &create_converter(),
+                &open_lots_context(0.3),
+            )
+            .unwrap();
+        assert_eq!(full.close_ratio, Some(1.0));
+        assert_eq!(full.lots, Some(0.1));
+    }
+
     // =============================================================================
     // Transform Tests: Symbol Conversion
     // =============================================================================
@@ -449,10 +1631,16 @@ mod tests {
         member.slave_settings.symbol_mappings = vec![SymbolMapping {
             source_symbol: "EURUSD".to_string(),
             target_symbol: "EURUSD.fx".to_string(),
+            priority: 0,
         }];
 
         let result = engine
-            .transform_signal(signal, &member, &create_converter())
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
             .unwrap();
 
         assert_eq!(result.symbol.as_deref(), Some("EURUSD.fx"));
@@ -471,16 +1659,46 @@ mod tests {
             suffix_add: Some(".micro".to_string()),
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = engine
-            .transform_signal(signal, &member, &converter)
+            .transform_signal(signal, &member, &converter, &CopyContext::default())
             .unwrap();
 
         // pro.EURUSD.m -> EURUSD -> fx.EURUSD.micro
         assert_eq!(result.symbol.as_deref(), Some("fx.EURUSD.micro"));
     }
 
+    #[test]
+    fn test_transform_symbol_passthrough_when_conversion_disabled() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.symbol = Some("pro.EURUSD.m".to_string());
+        let mut member = create_test_member();
+        member.slave_settings.disable_symbol_conversion = true;
+        member.slave_settings.symbol_mappings = vec![SymbolMapping {
+            source_symbol: "EURUSD".to_string(),
+            target_symbol: "EURUSD.fx".to_string(),
+            priority: 0,
+        }];
+        let converter = SymbolConverter {
+            prefix_remove: Some("pro.".to_string()),
+            suffix_remove: Some(".m".to_string()),
+            prefix_add: Some("fx.".to_string()),
+            suffix_add: Some(".micro".to_string()),
+            synonym_groups: Vec::new(),
+            detected_symbols: None,
+            case_insensitive: false,
+        };
+
+        let result = engine
+            .transform_signal(signal, &member, &converter, &CopyContext::default())
+            .unwrap();
+
+        assert_eq!(result.symbol.as_deref(), Some("pro.EURUSD.m"));
+    }
+
     #[test]
     fn test_transform_no_symbol_handled_gracefully() {
         let engine = CopyEngine::new();
@@ -494,12 +1712,1064 @@ mod tests {
             suffix_add: None,
             synonym_groups: Vec::new(),
             detected_symbols: None,
+            case_insensitive: false,
         };
 
         let result = engine
-            .transform_signal(signal, &member, &converter)
+            .transform_signal(signal, &member, &converter, &CopyContext::default())
             .unwrap();
 
         assert_eq!(result.symbol, None);
     }
+
+    // =============================================================================
+    // Transform Tests: Equity Ratio (relay-side lot calculation)
+    // =============================================================================
+
+    fn equity_context(master: f64, slave: f64) -> CopyContext {
+        CopyContext {
+            master_equity: Some(master),
+            slave_equity: Some(slave),
+            ..CopyContext::default()
+        }
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_scales_lots() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &equity_context(10000.0, 25000.0),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(0.25));
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_rounds_to_two_decimals() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &equity_context(30000.0, 10000.0),
+            )
+            .unwrap();
+
+        // 0.1 * 1/3 = 0.0333... -> 0.03
+        assert_eq!(result.lots, Some(0.03));
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_rounds_to_lot_decimals() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        member.slave_settings.lot_decimals = 3;
+
+        let result = engine
+            .transform_signal(
+                create_test_signal(), // lots = 0.1
+                &member,
+                &create_converter(),
+                &equity_context(30000.0, 10000.0),
+            )
+            .unwrap();
+
+        // 0.1 * 1/3 = 0.0333... -> 0.033
+        assert_eq!(result.lots, Some(0.033));
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_rounding_mode() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.lots = Some(0.25);
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        // 0.25 * 10000 / 20000 = 0.125
+        let context = equity_context(20000.0, 10000.0);
+
+        let lots_with = |mode: RoundingMode| {
+            let mut member = member.clone();
+            member.slave_settings.lot_rounding = mode;
+            engine
+                .transform_signal(signal.clone(), &member, &create_converter(), &context)
+                .unwrap()
+                .lots
+        };
+
+        assert_eq!(lots_with(RoundingMode::Nearest), Some(0.13));
+        assert_eq!(lots_with(RoundingMode::Down), Some(0.12));
+        assert_eq!(lots_with(RoundingMode::Up), Some(0.13));
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_falls_back_to_multiplier() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        member.slave_settings.lot_multiplier = Some(3.0);
+        let context = CopyContext {
+            slave_equity: Some(10000.0),
+            ..CopyContext::default()
+        };
+
+        let result = engine
+            .transform_signal(signal, &member, &create_converter(), &context)
+            .unwrap();
+
+        assert_eq!(result.lots, Some(0.3));
+    }
+
+    #[test]
+    fn test_transform_equity_ratio_not_applied_to_close() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Close;
+        signal.lots = Some(1.0);
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &equity_context(10000.0, 5000.0),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(1.0));
+    }
+
+    // =============================================================================
+    // Filter/Transform Tests: Fixed Lot
+    // =============================================================================
+
+    fn fixed_lot_member(fixed_lot: Option<f64>) -> TradeGroupMember {
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::FixedLot;
+        member.slave_settings.fixed_lot = fixed_lot;
+        member
+    }
+
+    #[test]
+    fn test_transform_fixed_lot_replaces_open_lots() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.lots = Some(2.5);
+        let member = fixed_lot_member(Some(0.01));
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(0.01));
+    }
+
+    #[test]
+    fn test_transform_fixed_lot_not_applied_to_close() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Close;
+        signal.lots = Some(2.5);
+        let member = fixed_lot_member(Some(0.01));
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(2.5));
+    }
+
+    #[test]
+    fn test_filter_fixed_lot_without_value_skipped() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+
+        assert!(!engine
+            .should_copy_trade(&signal, &fixed_lot_member(None), &CopyContext::default())
+            .is_copy());
+        assert!(!engine
+            .should_copy_trade(
+                &signal,
+                &fixed_lot_member(Some(0.0)),
+                &CopyContext::default()
+            )
+            .is_copy());
+        assert!(engine
+            .should_copy_trade(
+                &signal,
+                &fixed_lot_member(Some(0.01)),
+                &CopyContext::default()
+            )
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_fixed_lot_without_value_allows_close() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Close;
+
+        assert!(engine
+            .should_copy_trade(&signal, &fixed_lot_member(None), &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Filter/Transform Tests: Risk Percent
+    // =============================================================================
+
+    fn risk_member(risk_percent: f64) -> TradeGroupMember {
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::RiskPercent;
+        member.slave_settings.risk_percent = Some(risk_percent);
+        member
+    }
+
+    fn risk_context(balance: f64) -> CopyContext {
+        CopyContext {
+            slave_balance: Some(balance),
+            pip_value: Some(10.0),
+            ..CopyContext::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_risk_percent_requires_stop_loss() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.stop_loss = None;
+
+        assert_eq!(
+            engine.should_copy_trade(&signal, &risk_member(1.0), &CopyContext::default()),
+            CopyDecision::SkipWithWarning(WarningCode::StopLossRequired)
+        );
+    }
+
+    #[test]
+    fn test_transform_risk_percent_from_stop_distance() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // EURUSD open 1.1000, SL 1.0950 = 50 pips
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &risk_member(1.0),
+                &create_converter(),
+                &risk_context(10000.0),
+            )
+            .unwrap();
+
+        // Risk 100 USD / (50 pips * 10 USD) = 0.2 lots
+        assert_eq!(result.lots, Some(0.2));
+    }
+
+    #[test]
+    fn test_transform_risk_percent_jpy_pip_size() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.symbol = Some("USDJPY".to_string());
+        signal.open_price = Some(150.00);
+        signal.stop_loss = Some(149.75); // 25 pips
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &risk_member(2.0),
+                &create_converter(),
+                &risk_context(5000.0),
+            )
+            .unwrap();
+
+        // Risk 100 USD / (25 pips * 10 USD) = 0.4 lots
+        assert_eq!(result.lots, Some(0.4));
+    }
+
+    #[test]
+    fn test_transform_risk_percent_without_balance_falls_back() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = risk_member(1.0);
+        member.slave_settings.lot_multiplier = Some(2.0);
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(0.2));
+    }
+
+    // =============================================================================
+    // Filter/Transform Tests: Broker Volume Constraints
+    // =============================================================================
+
+    fn constrained_member(fixed_lot: f64) -> TradeGroupMember {
+        let mut member = fixed_lot_member(Some(fixed_lot));
+        member.slave_settings.lot_step = Some(0.01);
+        member.slave_settings.lot_min = Some(0.01);
+        member.slave_settings.lot_max = Some(1.0);
+        member
+    }
+
+    #[test]
+    fn test_transform_lot_rounded_down_to_step() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &constrained_member(0.333),
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(0.33));
+    }
+
+    #[test]
+    fn test_transform_lot_clamped_to_max() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &constrained_member(2.5),
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result.lots, Some(1.0));
+    }
+
+    #[test]
+    fn test_filter_lot_below_minimum_skipped() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        member.slave_settings.lot_step = Some(0.01);
+        member.slave_settings.lot_min = Some(0.01);
+
+        // 0.1 * 1000 / 100000 = 0.001 -> rounds below lot_min
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &equity_context(100000.0, 1000.0)),
+            CopyDecision::SkipWithWarning(WarningCode::LotBelowMinimum)
+        );
+
+        // Large enough slave equity passes
+        assert!(engine
+            .should_copy_trade(&signal, &member, &equity_context(10000.0, 10000.0))
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_portfolio_share_below_minimum_skipped() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let members = weighted_members(&[1.0, 9.0]);
+        let mut member = members[0].clone();
+        member.slave_settings.lot_calculation_mode = LotCalculationMode::EquityRatio;
+        member.slave_settings.lot_step = Some(0.01);
+        member.slave_settings.lot_min = Some(0.05);
+        let context = CopyContext {
+            portfolio_weight_total: super::portfolio_weight_total(&members),
+            ..equity_context(10000.0, 10000.0)
+        };
+
+        // 0.1 * 1 / 10 = 0.01: below lot_min although the Master's 0.1 lots are not
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &context),
+            CopyDecision::SkipWithWarning(WarningCode::LotBelowMinimum)
+        );
+
+        // The heavier sibling's 0.09 share passes
+        let mut heavy = members[1].clone();
+        heavy.slave_settings = SlaveSettings {
+            portfolio_weight: Some(9.0),
+            ..member.slave_settings.clone()
+        };
+        assert!(engine
+            .should_copy_trade(&signal, &heavy, &context)
+            .is_copy());
+    }
+
+    #[test]
+    fn test_lot_constraints_ignored_for_ea_side_modes() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // lots = 0.1
+        let mut member = create_test_member();
+        member.slave_settings.lot_step = Some(0.5);
+        member.slave_settings.lot_min = Some(0.5);
+
+        // Multiplier lots are sized by the Slave EA; the relay keeps the Master lot as-is
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+        assert_eq!(result.lots, Some(0.1));
+    }
+
+    // =============================================================================
+    // Filter Tests: Max Open Positions
+    // =============================================================================
+
+    fn positions_context(open: u32) -> CopyContext {
+        CopyContext {
+            slave_open_positions: Some(open),
+            ..CopyContext::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_max_open_positions_reached() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+        let mut member = create_test_member();
+        member.slave_settings.max_open_positions = Some(3);
+
+        // Opens are copied until the slave holds the limit
+        for open in 0..3 {
+            assert!(engine
+                .should_copy_trade(&signal, &member, &positions_context(open))
+                .is_copy());
+        }
+
+        // The next Open is filtered
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &positions_context(3)),
+            CopyDecision::SkipWithWarning(WarningCode::MaxPositionsReached)
+        );
+    }
+
+    #[test]
+    fn test_filter_max_open_positions_close_and_modify_pass() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.max_open_positions = Some(1);
+
+        for action in [TradeAction::Close, TradeAction::Modify] {
+            let mut signal = create_test_signal();
+            signal.action = action;
+            assert!(engine
+                .should_copy_trade(&signal, &member, &positions_context(5))
+                .is_copy());
+        }
+    }
+
+    #[test]
+    fn test_filter_max_open_positions_unknown_count_passes() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+        let mut member = create_test_member();
+        member.slave_settings.max_open_positions = Some(1);
+
+        // Slave not connected yet: no position count to compare against
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Filter Tests: Max Daily Loss
+    // =============================================================================
+
+    fn daily_loss_context(loss: f64) -> CopyContext {
+        CopyContext {
+            slave_daily_loss: Some(loss),
+            ..CopyContext::default()
+        }
+    }
+
+    #[test]
+    fn test_filter_max_daily_loss_hit() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal();
+        let mut member = create_test_member();
+        member.slave_settings.max_daily_loss = Some(500.0);
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &daily_loss_context(500.0))
+            .is_copy());
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &daily_loss_context(500.01)),
+            CopyDecision::SkipWithWarning(WarningCode::DailyLossLimitHit)
+        );
+    }
+
+    #[test]
+    fn test_filter_max_daily_loss_close_and_modify_pass() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.max_daily_loss = Some(100.0);
+
+        for action in [TradeAction::Close, TradeAction::Modify] {
+            let mut signal = create_test_signal();
+            signal.action = action;
+            assert!(engine
+                .should_copy_trade(&signal, &member, &daily_loss_context(1000.0))
+                .is_copy());
+        }
+    }
+
+    // =============================================================================
+    // Filter Tests: Max Spread
+    // =============================================================================
+
+    fn spread_member(limit: u32) -> TradeGroupMember {
+        let mut member = create_test_member();
+        member.slave_settings.max_spread_points = Some(limit);
+        member
+    }
+
+    #[test]
+    fn test_filter_max_spread_within_limit() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.spread_points = Some(20);
+
+        assert!(engine
+            .should_copy_trade(&signal, &spread_member(20), &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_max_spread_over_limit() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.spread_points = Some(35);
+
+        assert_eq!(
+            engine.should_copy_trade(&signal, &spread_member(20), &CopyContext::default()),
+            CopyDecision::SkipWithWarning(WarningCode::SpreadTooWide)
+        );
+
+        // Close/Modify are never filtered by spread
+        for action in [TradeAction::Close, TradeAction::Modify] {
+            signal.action = action;
+            assert!(engine
+                .should_copy_trade(&signal, &spread_member(20), &CopyContext::default())
+                .is_copy());
+        }
+    }
+
+    #[test]
+    fn test_filter_max_spread_missing_spread_passes() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // older sender: spread_points = None
+
+        assert!(engine
+            .should_copy_trade(&signal, &spread_member(1), &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Filter Tests: Trading Hours
+    // =============================================================================
+
+    fn weekday_window_member() -> TradeGroupMember {
+        use chrono::{NaiveTime, Weekday};
+
+        let mut member = create_test_member();
+        member.slave_settings.trading_hours = Some(TradingWindow {
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            tz: "UTC".to_string(),
+        });
+        member
+    }
+
+    fn signal_at(rfc3339: &str) -> TradeSignal {
+        let mut signal = create_test_signal();
+        signal.timestamp = chrono::DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc);
+        signal
+    }
+
+    #[test]
+    fn test_filter_trading_hours_rejects_saturday() {
+        let engine = CopyEngine::new();
+        let signal = signal_at("2024-06-08T12:00:00Z"); // Saturday
+
+        assert!(!engine
+            .should_copy_trade(&signal, &weekday_window_member(), &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_trading_hours_accepts_wednesday_noon() {
+        let engine = CopyEngine::new();
+        let signal = signal_at("2024-06-05T12:00:00Z"); // Wednesday
+
+        assert!(engine
+            .should_copy_trade(&signal, &weekday_window_member(), &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_trading_hours_allows_close_outside_window() {
+        let engine = CopyEngine::new();
+        let mut signal = signal_at("2024-06-08T12:00:00Z"); // Saturday
+        signal.action = TradeAction::Close;
+
+        assert!(engine
+            .should_copy_trade(&signal, &weekday_window_member(), &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Filter Tests: Comment Keywords
+    // =============================================================================
+
+    #[test]
+    fn test_filter_comment_allowed_match() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.comment = Some("Scalper-v2 #1".to_string());
+        let mut member = create_test_member();
+        member.slave_settings.filters.allowed_comment_substrings =
+            Some(vec!["SCALPER".to_string()]);
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+
+        signal.comment = Some("Grid bot".to_string());
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_comment_blocked_match() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.comment = Some("manual hedge".to_string());
+        let mut member = create_test_member();
+        member.slave_settings.filters.blocked_comment_substrings = Some(vec!["Hedge".to_string()]);
+
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_comment_none_passes_with_empty_allow_list() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.comment = None;
+        let mut member = create_test_member();
+        member.slave_settings.filters.allowed_comment_substrings = Some(vec![]);
+        member.slave_settings.filters.blocked_comment_substrings = Some(vec!["hedge".to_string()]);
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Transform Tests: SL/TP as Offsets
+    // =============================================================================
+
+    fn assert_price_eq(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("price should be set");
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_transform_sl_tp_offset_buy() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal(); // Buy EURUSD @ 1.1000
+        signal.stop_loss = Some(1.094996); // -50.04 pips
+        signal.take_profit = Some(1.110003); // +100.03 pips
+        let mut member = create_test_member();
+        member.slave_settings.sl_tp_as_offset = true;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        // Signed distances from the Master entry, snapped to 0.1 pip; the Slave EA
+        // adds them to its own fill price
+        assert!(result.sl_tp_offset);
+        assert_price_eq(result.stop_loss, -0.0050);
+        assert_price_eq(result.take_profit, 0.0100);
+    }
+
+    #[test]
+    fn test_transform_sl_tp_offset_jpy_sell() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.symbol = Some("USDJPY".to_string());
+        signal.order_type = Some(OrderType::Sell);
+        signal.open_price = Some(150.000);
+        signal.stop_loss = Some(150.253); // +25.3 pips
+        signal.take_profit = None;
+        let mut member = create_test_member();
+        member.slave_settings.sl_tp_as_offset = true;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert!(result.sl_tp_offset);
+        assert_price_eq(result.stop_loss, 0.253);
+        assert_eq!(result.take_profit, None);
+    }
+
+    #[test]
+    fn test_transform_sl_tp_absolute_by_default() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.stop_loss = Some(1.094996);
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &create_test_member(),
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert!(!result.sl_tp_offset);
+        assert_eq!(result.stop_loss, Some(1.094996));
+    }
+
+    #[test]
+    fn test_transform_sl_tp_offset_skipped_for_modify_without_open_price() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Modify;
+        signal.open_price = None;
+        signal.stop_loss = Some(1.094996);
+        signal.take_profit = Some(1.110003);
+        let mut member = create_test_member();
+        member.slave_settings.sl_tp_as_offset = true;
+
+        let result = engine
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap();
+
+        assert!(!result.sl_tp_offset);
+        assert_eq!(result.stop_loss, Some(1.094996));
+        assert_eq!(result.take_profit, Some(1.110003));
+    }
+
+    // =============================================================================
+    // Transform Tests: Magic Number Remapping
+    // =============================================================================
+
+    fn magic_map_member(default_magic: Option<i64>) -> TradeGroupMember {
+        let mut member = create_test_member();
+        member.slave_settings.magic_number_map.insert(0, 9001);
+        member.slave_settings.magic_number_map.insert(777, 9777);
+        member.slave_settings.default_magic = default_magic;
+        member
+    }
+
+    fn transform_magic(magic_number: i64, member: &TradeGroupMember) -> Option<i64> {
+        let mut signal = create_test_signal();
+        signal.magic_number = Some(magic_number);
+        CopyEngine::new()
+            .transform_signal(signal, member, &create_converter(), &CopyContext::default())
+            .unwrap()
+            .magic_number
+    }
+
+    #[test]
+    fn test_transform_magic_number_mapped() {
+        assert_eq!(transform_magic(777, &magic_map_member(Some(1))), Some(9777));
+    }
+
+    #[test]
+    fn test_transform_magic_number_unmapped_uses_default() {
+        assert_eq!(transform_magic(555, &magic_map_member(Some(1))), Some(1));
+    }
+
+    #[test]
+    fn test_transform_magic_number_unmapped_without_default() {
+        assert_eq!(transform_magic(555, &magic_map_member(None)), Some(555));
+    }
+
+    #[test]
+    fn test_filter_magic_number_matches_master_value() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.magic_number = Some(777);
+        let mut member = magic_map_member(None);
+        member.slave_settings.filters.allowed_magic_numbers = Some(vec![777]);
+
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    // =============================================================================
+    // Transform Tests: Comment Template
+    // =============================================================================
+
+    fn transform_comment(template: Option<&str>, signal: TradeSignal) -> Option<String> {
+        let mut member = create_test_member();
+        member.slave_settings.comment_template = template.map(str::to_string);
+        CopyEngine::new()
+            .transform_signal(
+                signal,
+                &member,
+                &create_converter(),
+                &CopyContext::default(),
+            )
+            .unwrap()
+            .comment
+    }
+
+    #[test]
+    fn test_transform_comment_template_expands_placeholders() {
+        assert_eq!(
+            transform_comment(
+                Some("{master}#{ticket} {orig_comment}"),
+                create_test_signal()
+            ),
+            Some("MASTER_001#12345 Test trade".to_string())
+        );
+
+        // Missing Master comment expands to nothing
+        let mut signal = create_test_signal();
+        signal.comment = None;
+        assert_eq!(
+            transform_comment(Some("[{orig_comment}]"), signal),
+            Some("[]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transform_comment_template_truncated_to_mt_limit() {
+        let mut signal = create_test_signal();
+        signal.comment = Some("a very long comment from the master EA".to_string());
+        let comment = transform_comment(Some("copy:{master}:{orig_comment}"), signal).unwrap();
+        assert_eq!(comment.chars().count(), 31);
+        assert_eq!(comment, "copy:MASTER_001:a very long com");
+    }
+
+    #[test]
+    fn test_transform_comment_without_template_or_for_close() {
+        assert_eq!(
+            transform_comment(None, create_test_signal()),
+            Some("Test trade".to_string())
+        );
+
+        // Only Opens are renamed
+        let mut signal = create_test_signal();
+        signal.action = TradeAction::Close;
+        assert_eq!(
+            transform_comment(Some("{master}"), signal),
+            Some("Test trade".to_string())
+        );
+    }
+
+    // =============================================================================
+    // Filter Tests: Sampling
+    // =============================================================================
+
+    #[test]
+    fn test_filter_sample_rate_copies_about_a_quarter() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.copy_sample_rate = Some(0.25);
+
+        let copied = (1..=4000)
+            .filter(|ticket| {
+                let mut signal = create_test_signal();
+                signal.ticket = *ticket;
+                engine
+                    .should_copy_trade(&signal, &member, &CopyContext::default())
+                    .is_copy()
+            })
+            .count();
+        assert!((900..=1100).contains(&copied), "copied {} of 4000", copied);
+
+        // The bounds copy nothing / everything
+        let mut signal = create_test_signal();
+        member.slave_settings.copy_sample_rate = Some(0.0);
+        assert_eq!(
+            engine.should_copy_trade(&signal, &member, &CopyContext::default()),
+            CopyDecision::Skip(SkipReason::NotSampled)
+        );
+        member.slave_settings.copy_sample_rate = Some(1.0);
+        signal.ticket = 7;
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_sample_rate_close_follows_open() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.copy_sample_rate = Some(0.5);
+        // Closes of skipped tickets must not bypass sampling
+        member.slave_settings.always_allow_close = true;
+
+        for ticket in 1..=200 {
+            let decide = |action| {
+                let mut signal = create_test_signal();
+                signal.ticket = ticket;
+                signal.action = action;
+                engine
+                    .should_copy_trade(&signal, &member, &CopyContext::default())
+                    .is_copy()
+            };
+            let open = decide(TradeAction::Open);
+            assert_eq!(decide(TradeAction::Close), open, "ticket {}", ticket);
+            assert_eq!(decide(TradeAction::Modify), open, "ticket {}", ticket);
+        }
+
+        // Another Slave samples a different set of tickets
+        let mut other = member.clone();
+        other.slave_account = "SLAVE_002".to_string();
+        let differs = (1..=200).any(|ticket| {
+            let mut signal = create_test_signal();
+            signal.ticket = ticket;
+            engine
+                .should_copy_trade(&signal, &member, &CopyContext::default())
+                .is_copy()
+                != engine
+                    .should_copy_trade(&signal, &other, &CopyContext::default())
+                    .is_copy()
+        });
+        assert!(differs);
+    }
+
+    // =============================================================================
+    // Filter Tests: Copy Direction
+    // =============================================================================
+
+    fn sell_only_member() -> TradeGroupMember {
+        let mut member = create_test_member();
+        member.slave_settings.copy_direction = CopyDirection::SellOnly;
+        member
+    }
+
+    #[test]
+    fn test_filter_sell_only_rejects_buy_open() {
+        let engine = CopyEngine::new();
+        let signal = create_test_signal(); // Buy
+
+        assert!(!engine
+            .should_copy_trade(&signal, &sell_only_member(), &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_sell_only_accepts_sell_open() {
+        let engine = CopyEngine::new();
+        let mut signal = create_test_signal();
+        signal.order_type = Some(OrderType::Sell);
+
+        assert!(engine
+            .should_copy_trade(&signal, &sell_only_member(), &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_buy_only_treats_pending_buy_as_buy_side() {
+        let engine = CopyEngine::new();
+        let mut member = create_test_member();
+        member.slave_settings.copy_direction = CopyDirection::BuyOnly;
+        member.slave_settings.copy_pending_orders = true;
+        let mut signal = create_test_signal();
+
+        signal.order_type = Some(OrderType::BuyStop);
+        assert!(engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+
+        signal.order_type = Some(OrderType::SellLimit);
+        assert!(!engine
+            .should_copy_trade(&signal, &member, &CopyContext::default())
+            .is_copy());
+    }
+
+    #[test]
+    fn test_filter_copy_direction_allows_close_and_modify() {
+        let engine = CopyEngine::new();
+
+        for action in [TradeAction::Close, TradeAction::Modify] {
+            let mut signal = create_test_signal(); // Buy
+            signal.action = action;
+            assert!(engine
+                .should_copy_trade(&signal, &sell_only_member(), &CopyContext::default())
+                .is_copy());
+        }
+    }
 }
@@ -1,2 +1,5 @@
 pub mod copy_engine;
+pub mod position_reconciler;
 pub mod status_calculator;
+pub mod symbol_cache;
+pub mod symbol_specs;
@@ -0,0 +1,356 @@
+//! Position reconciliation between a Master and one of its Slaves.
+//!
+//! Compares the latest PositionSnapshot of each side. Slave copies are matched to
+//! Master positions through the `M{master_ticket}` comment written by the Slave EA;
+//! Slave positions without that comment (manual trades) are ignored.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{
+    OrderType, PositionInfo, SymbolConverter, TradeAction, TradeGroupMember, TradeSignal,
+    STATUS_CONNECTED,
+};
+use crate::domain::services::copy_engine::{CopyContext, CopyEngine};
+
+/// Comment prefix the Slave EA writes on market positions copied from a Master
+const COPY_COMMENT_PREFIX: &str = "M";
+
+/// Lot differences up to one volume step are rounding, not drift
+const DEFAULT_LOT_TOLERANCE: f64 = 0.01;
+
+/// Master position the Slave should hold but does not
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MissingPosition {
+    pub master_ticket: i64,
+    /// Symbol as it should appear on the Slave (after symbol conversion)
+    pub symbol: String,
+    pub order_type: String,
+    /// None when the lot is sized by the Slave EA from live margin (margin_ratio)
+    pub expected_lots: Option<f64>,
+}
+
+/// Slave copy whose lot differs from the lot the Master position translates to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LotMismatch {
+    pub master_ticket: i64,
+    pub slave_ticket: i64,
+    pub symbol: String,
+    pub expected_lots: f64,
+    pub actual_lots: f64,
+}
+
+/// Differences between the Master and Slave position lists
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    pub missing: Vec<MissingPosition>,
+    /// Slave copies whose Master position no longer exists
+    pub extra: Vec<PositionInfo>,
+    pub lot_mismatch: Vec<LotMismatch>,
+}
+
+/// Master ticket encoded in a Slave copy's comment ("M12345" -> 12345)
+pub fn copied_master_ticket(comment: Option<&str>) -> Option<i64> {
+    let digits: String = comment?
+        .strip_prefix(COPY_COMMENT_PREFIX)?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+/// Compare a Master's positions with a Slave's positions for one member.
+///
+/// Master positions excluded by the member's filters are not expected on the Slave.
+/// Filters are evaluated as if the member were connected, so positions opened while
+/// the Slave was offline are reported as missing. `pip_value` resolves the per-symbol
+/// pip value used by risk_percent sizing.
+pub fn reconcile_positions(
+    engine: &CopyEngine,
+    master_positions: &[PositionInfo],
+    slave_positions: &[PositionInfo],
+    member: &TradeGroupMember,
+    converter: &SymbolConverter,
+    context: &CopyContext,
+    pip_value: impl Fn(&str) -> f64,
+) -> ReconcileReport {
+    let mut member = member.clone();
+    member.status = STATUS_CONNECTED;
+
+    let tolerance = member
+        .slave_settings
+        .lot_step
+        .filter(|step| *step > 0.0)
+        .unwrap_or(DEFAULT_LOT_TOLERANCE);
+
+    let mut report = ReconcileReport::default();
+
+    for master in master_positions {
+        let signal = open_signal(master, &member);
+        // Open-position limits describe the moment of copying, not the steady state
+        let context = CopyContext {
+            slave_open_positions: None,
+            slave_daily_loss: None,
+            slave_position_lots: None,
+            slave_position_symbol: None,
+            slave_position_order_type: None,
+            pip_value: Some(pip_value(&master.symbol)),
+            ..context.clone()
+        };
+        let expected_lots = engine.expected_slave_lots(&signal, &member, &context);
+        let copy = slave_positions
+            .iter()
+            .find(|slave| copied_master_ticket(slave.comment.as_deref()) == Some(master.ticket));
+
+        match copy {
+            Some(copy) => {
+                if let Some(expected) = expected_lots {
+                    if (copy.lots - expected).abs() > tolerance + 1e-9 {
+                        report.lot_mismatch.push(LotMismatch {
+                            master_ticket: master.ticket,
+                            slave_ticket: copy.ticket,
+                            symbol: copy.symbol.clone(),
+                            expected_lots: expected,
+                            actual_lots: copy.lots,
+                        });
+                    }
+                }
+            }
+            None => {
+                if engine
+                    .should_copy_trade(&signal, &member, &context)
+                    .is_copy()
+                {
+                    report.missing.push(MissingPosition {
+                        master_ticket: master.ticket,
+                        symbol: if member.slave_settings.disable_symbol_conversion {
+                            master.symbol.clone()
+                        } else {
+                            converter
+                                .convert(&master.symbol, &member.slave_settings.symbol_mappings)
+                        },
+                        order_type: master.order_type.clone(),
+                        expected_lots,
+                    });
+                }
+            }
+        }
+    }
+
+    report.extra = slave_positions
+        .iter()
+        .filter(|slave| {
+            copied_master_ticket(slave.comment.as_deref()).is_some_and(|ticket| {
+                !master_positions
+                    .iter()
+                    .any(|master| master.ticket == ticket)
+            })
+        })
+        .cloned()
+        .collect();
+
+    report
+}
+
+/// Rebuild the Open signal a Master position was copied from
+fn open_signal(position: &PositionInfo, member: &TradeGroupMember) -> TradeSignal {
+    TradeSignal {
+        action: TradeAction::Open,
+        ticket: position.ticket,
+        symbol: Some(position.symbol.clone()),
+        order_type: OrderType::try_parse(&position.order_type),
+        lots: Some(position.lots),
+        open_price: Some(position.open_price),
+        stop_loss: position.stop_loss,
+        take_profit: position.take_profit,
+        magic_number: position.magic_number,
+        comment: position.comment.clone(),
+        // Trading-hours filters are judged at the time the position was opened
+        timestamp: chrono::DateTime::parse_from_rfc3339(&position.open_time)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now()),
+        source_account: member.trade_group_id.clone(),
+        close_ratio: None,
+        seq: 0,
+        spread_points: None,
+        sl_tp_offset: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{LotCalculationMode, SlaveSettings, SymbolMapping};
+
+    fn position(ticket: i64, symbol: &str, lots: f64, comment: Option<&str>) -> PositionInfo {
+        PositionInfo {
+            ticket,
+            symbol: symbol.to_string(),
+            order_type: "Buy".to_string(),
+            lots,
+            open_price: 1.1,
+            open_time: "2025-01-01T00:00:00Z".to_string(),
+            stop_loss: None,
+            take_profit: None,
+            magic_number: None,
+            comment: comment.map(str::to_string),
+            profit: 0.0,
+        }
+    }
+
+    fn member(settings: SlaveSettings) -> TradeGroupMember {
+        TradeGroupMember {
+            id: 1,
+            trade_group_id: "MASTER_001".to_string(),
+            slave_account: "SLAVE_001".to_string(),
+            slave_settings: settings,
+            // Offline members are still reconciled as if connected
+            status: 0,
+            warning_codes: Vec::new(),
+            acknowledged_warnings: Vec::new(),
+            warnings: Vec::new(),
+            enabled_flag: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn converter(suffix_remove: Option<&str>, suffix_add: Option<&str>) -> SymbolConverter {
+        SymbolConverter {
+            prefix_remove: None,
+            suffix_remove: suffix_remove.map(str::to_string),
+            prefix_add: None,
+            suffix_add: suffix_add.map(str::to_string),
+            synonym_groups: Vec::new(),
+            detected_symbols: None,
+            case_insensitive: false,
+        }
+    }
+
+    #[test]
+    fn test_copied_master_ticket() {
+        assert_eq!(copied_master_ticket(Some("M12345")), Some(12345));
+        assert_eq!(copied_master_ticket(Some("P12345")), None);
+        assert_eq!(copied_master_ticket(Some("Manual")), None);
+        assert_eq!(copied_master_ticket(None), None);
+    }
+
+    #[test]
+    fn test_reconcile_reports_missing_extra_and_lot_mismatch() {
+        let settings = SlaveSettings {
+            lot_calculation_mode: LotCalculationMode::Multiplier,
+            lot_multiplier: Some(2.0),
+            symbol_mappings: vec![SymbolMapping {
+                source_symbol: "XAUUSD".to_string(),
+                target_symbol: "GOLD".to_string(),
+                priority: 0,
+            }],
+            ..SlaveSettings::default()
+        };
+        let master = vec![
+            position(1, "EURUSD.m", 0.1, None), // copied correctly (0.2)
+            position(2, "GBPUSD.m", 0.3, None), // copied with the wrong lot
+            position(3, "XAUUSD.m", 0.5, None), // never copied
+        ];
+        let slave = vec![
+            position(101, "EURUSD-pro", 0.2, Some("M1")),
+            position(102, "GBPUSD-pro", 0.3, Some("M2")),
+            position(104, "USDJPY-pro", 0.1, Some("M4")), // Master already closed
+            position(200, "EURUSD-pro", 1.0, Some("manual")), // not a copy
+        ];
+
+        let report = reconcile_positions(
+            &CopyEngine::new(),
+            &master,
+            &slave,
+            &member(settings),
+            &converter(Some(".m"), Some("-pro")),
+            &CopyContext::default(),
+            |_| 10.0,
+        );
+
+        assert_eq!(
+            report.missing,
+            vec![MissingPosition {
+                master_ticket: 3,
+                symbol: "GOLD-pro".to_string(),
+                order_type: "Buy".to_string(),
+                expected_lots: Some(1.0),
+            }]
+        );
+        assert_eq!(report.extra.len(), 1);
+        assert_eq!(report.extra[0].ticket, 104);
+        assert_eq!(
+            report.lot_mismatch,
+            vec![LotMismatch {
+                master_ticket: 2,
+                slave_ticket: 102,
+                symbol: "GBPUSD-pro".to_string(),
+                expected_lots: 0.6,
+                actual_lots: 0.3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reconcile_skips_filtered_positions_and_rounding() {
+        let mut settings = SlaveSettings {
+            lot_calculation_mode: LotCalculationMode::FixedLot,
+            fixed_lot: Some(0.05),
+            lot_step: Some(0.01),
+            ..SlaveSettings::default()
+        };
+        settings.filters.blocked_symbols = Some(vec!["USDJPY".to_string()]);
+        let master = vec![
+            position(1, "EURUSD", 1.0, None),
+            position(2, "USDJPY", 1.0, None), // blocked: not expected on the Slave
+        ];
+        // Within one lot step of the fixed lot
+        let slave = vec![position(101, "EURUSD", 0.04, Some("M1"))];
+
+        let report = reconcile_positions(
+            &CopyEngine::new(),
+            &master,
+            &slave,
+            &member(settings),
+            &converter(None, None),
+            &CopyContext::default(),
+            |_| 10.0,
+        );
+
+        assert!(report.missing.is_empty());
+        assert!(report.extra.is_empty());
+        assert!(report.lot_mismatch.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_margin_ratio_has_no_expected_lot() {
+        let settings = SlaveSettings {
+            lot_calculation_mode: LotCalculationMode::MarginRatio,
+            ..SlaveSettings::default()
+        };
+        let master = vec![position(1, "EURUSD", 1.0, None)];
+        let slave = vec![position(101, "EURUSD", 0.37, Some("M1"))];
+
+        let report = reconcile_positions(
+            &CopyEngine::new(),
+            &master,
+            &slave,
+            &member(settings.clone()),
+            &converter(None, None),
+            &CopyContext::default(),
+            |_| 10.0,
+        );
+        assert!(report.lot_mismatch.is_empty());
+
+        let report = reconcile_positions(
+            &CopyEngine::new(),
+            &master,
+            &[],
+            &member(settings),
+            &converter(None, None),
+            &CopyContext::default(),
+            |_| 10.0,
+        );
+        assert_eq!(report.missing[0].expected_lots, None);
+    }
+}
@@ -3,6 +3,8 @@
 // Next-generation status evaluation engine for Master/Slave EAs.
 // Provides a single source of truth for status and allow_new_orders logic.
 
+use chrono::{DateTime, Utc};
+
 use crate::domain::models::{
     ConnectionStatus, SlaveSettings, WarningCode, STATUS_CONNECTED, STATUS_DISABLED, STATUS_ENABLED,
 };
@@ -33,6 +35,8 @@ pub struct SlaveIntent {
 pub struct ConnectionSnapshot {
     pub connection_status: Option<ConnectionStatus>,
     pub is_trade_allowed: bool,
+    /// Margin level below the configured threshold (advisory, Slave only)
+    pub low_margin_level: bool,
 }
 
 /// Aggregated status information about the Masters linked to a Slave.
@@ -281,6 +285,10 @@ pub fn evaluate_member_status(
     if slave_online && !slave_conn.is_trade_allowed {
         warning_codes.push(WarningCode::SlaveAutoTradingDisabled);
     }
+    // Advisory only: does not affect status or allow_new_orders
+    if slave_online && slave_conn.low_margin_level {
+        warning_codes.push(WarningCode::LowMarginLevel);
+    }
 
     // Slave is DISABLED if Web UI is OFF, Slave is offline, or (online but auto-trading disabled)
     let slave_disabled = !slave_web_ui_enabled || !slave_online || !slave_conn.is_trade_allowed;
@@ -316,6 +324,64 @@ pub fn evaluate_member_status(
     }
 }
 
+/// Attach `CurrencyMismatch` when both account currencies are known and differ.
+///
+/// Balance-based lot ratios are meaningless across currencies, so the UI
+/// surfaces this. Advisory only: `status` and `allow_new_orders` are unchanged.
+pub fn apply_currency_check(
+    result: &mut MemberStatusResult,
+    master_currency: Option<&str>,
+    slave_currency: Option<&str>,
+) {
+    let (Some(master), Some(slave)) = (master_currency, slave_currency) else {
+        return;
+    };
+    if master.is_empty() || slave.is_empty() || master.eq_ignore_ascii_case(slave) {
+        return;
+    }
+    push_warning(&mut result.warning_codes, WarningCode::CurrencyMismatch);
+    WarningCode::sort_by_priority(&mut result.warning_codes);
+}
+
+/// Block a member whose Slave account is the TradeGroup's own Master account.
+///
+/// Copying a Master's trades back onto the same account would loop, so the
+/// member is forced to DISABLED with `SelfCopyDetected` and new orders are refused.
+pub fn apply_self_copy_check(
+    result: &mut MemberStatusResult,
+    master_account: &str,
+    slave_account: &str,
+) {
+    if master_account != slave_account {
+        return;
+    }
+    result.status = STATUS_DISABLED;
+    result.allow_new_orders = false;
+    push_warning(&mut result.warning_codes, WarningCode::SelfCopyDetected);
+    WarningCode::sort_by_priority(&mut result.warning_codes);
+}
+
+/// Pause a member outside its `active_schedule` windows.
+///
+/// The member is reported DISABLED with `OutsideActiveSchedule` so the Slave EA
+/// stops opening positions; Close signals still reach it.
+pub fn apply_schedule_check(
+    result: &mut MemberStatusResult,
+    settings: &SlaveSettings,
+    now: DateTime<Utc>,
+) {
+    if settings.in_active_schedule(now) {
+        return;
+    }
+    result.status = STATUS_DISABLED;
+    result.allow_new_orders = false;
+    push_warning(
+        &mut result.warning_codes,
+        WarningCode::OutsideActiveSchedule,
+    );
+    WarningCode::sort_by_priority(&mut result.warning_codes);
+}
+
 fn is_connection_online(status: Option<ConnectionStatus>) -> bool {
     matches!(status, Some(ConnectionStatus::Online))
 }
@@ -338,6 +404,7 @@ mod tests {
         let conn = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         assert_eq!(evaluate_master_status(intent, conn).status, STATUS_DISABLED);
     }
@@ -350,6 +417,7 @@ mod tests {
         let conn = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         assert_eq!(
             evaluate_master_status(intent, conn).status,
@@ -368,6 +436,7 @@ mod tests {
             ConnectionSnapshot {
                 connection_status: Some(ConnectionStatus::Online),
                 is_trade_allowed: false,
+                low_margin_level: false,
             },
             MasterClusterSnapshot::new(vec![STATUS_CONNECTED]),
         );
@@ -389,6 +458,7 @@ mod tests {
             ConnectionSnapshot {
                 connection_status: Some(ConnectionStatus::Online),
                 is_trade_allowed: true,
+                low_margin_level: false,
             },
             MasterClusterSnapshot::new(vec![STATUS_CONNECTED, STATUS_CONNECTED]),
         );
@@ -408,6 +478,7 @@ mod tests {
             ConnectionSnapshot {
                 connection_status: Some(ConnectionStatus::Online),
                 is_trade_allowed: true,
+                low_margin_level: false,
             },
             MasterClusterSnapshot::new(vec![STATUS_CONNECTED, STATUS_ENABLED]),
         );
@@ -443,6 +514,7 @@ mod tests {
             ConnectionSnapshot {
                 connection_status: Some(ConnectionStatus::Online),
                 is_trade_allowed: true,
+                low_margin_level: false,
             },
             MasterClusterSnapshot::default(),
         );
@@ -464,6 +536,7 @@ mod tests {
             ConnectionSnapshot {
                 connection_status: Some(ConnectionStatus::Offline),
                 is_trade_allowed: true,
+                low_margin_level: false,
             },
             MasterClusterSnapshot::new(vec![STATUS_CONNECTED, STATUS_CONNECTED]),
         );
@@ -624,6 +697,7 @@ mod tests {
                 ConnectionSnapshot {
                     connection_status: case.connection,
                     is_trade_allowed: case.is_trade_allowed,
+                    low_margin_level: false,
                 },
             );
 
@@ -780,6 +854,7 @@ mod tests {
                 ConnectionSnapshot {
                     connection_status: case.connection,
                     is_trade_allowed: case.is_trade_allowed,
+                    low_margin_level: false,
                 },
                 healthy_cluster.clone(),
             );
@@ -807,6 +882,7 @@ mod tests {
         let snapshot = ConnectionSnapshot {
             connection_status: Some(Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
 
         let healthy_cluster = MasterClusterSnapshot::new(vec![STATUS_CONNECTED]);
@@ -859,6 +935,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_CONNECTED,
@@ -879,6 +956,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_DISABLED,
@@ -901,6 +979,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Offline),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_CONNECTED,
@@ -921,6 +1000,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_CONNECTED,
@@ -943,6 +1023,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: false, // generates SlaveAutoTradingDisabled
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_DISABLED,
@@ -969,6 +1050,7 @@ mod tests {
         let slave_snapshot = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: false,
+            low_margin_level: false,
         };
         let master_result = MasterStatusResult {
             status: STATUS_CONNECTED,
@@ -1097,6 +1179,7 @@ mod tests {
         let master_conn = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Offline),
             is_trade_allowed: false,
+            low_margin_level: false,
         };
 
         let master_result = evaluate_master_status(master_intent, master_conn);
@@ -1118,6 +1201,7 @@ mod tests {
         let slave_conn = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: false, // Auto-trading disabled
+            low_margin_level: false,
         };
 
         let master_intent = MasterIntent {
@@ -1126,6 +1210,7 @@ mod tests {
         let master_conn = ConnectionSnapshot {
             connection_status: Some(ConnectionStatus::Online),
             is_trade_allowed: true,
+            low_margin_level: false,
         };
         let master_result = evaluate_master_status(master_intent, master_conn);
 
@@ -1143,4 +1228,159 @@ mod tests {
             "Status should be DISABLED when auto-trading is off"
         );
     }
+
+    #[test]
+    fn test_currency_mismatch_is_advisory() {
+        let online = ConnectionSnapshot {
+            connection_status: Some(ConnectionStatus::Online),
+            is_trade_allowed: true,
+            low_margin_level: false,
+        };
+        let master_result = evaluate_master_status(
+            MasterIntent {
+                web_ui_enabled: true,
+            },
+            online,
+        );
+        let slave_intent = SlaveIntent {
+            web_ui_enabled: true,
+        };
+
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_currency_check(&mut result, Some("JPY"), Some("USD"));
+        assert_eq!(result.warning_codes, vec![WarningCode::CurrencyMismatch]);
+        // Copying is not blocked
+        assert_eq!(result.status, STATUS_CONNECTED);
+        assert!(result.allow_new_orders);
+
+        // Same currency (case-insensitive) or unknown currency: no warning
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_currency_check(&mut result, Some("usd"), Some("USD"));
+        apply_currency_check(&mut result, None, Some("USD"));
+        apply_currency_check(&mut result, Some(""), Some("USD"));
+        assert!(result.warning_codes.is_empty());
+    }
+
+    #[test]
+    fn test_self_copy_member_is_disabled() {
+        let master_result = MasterStatusResult {
+            status: STATUS_CONNECTED,
+            warning_codes: Vec::new(),
+        };
+        let online = ConnectionSnapshot {
+            connection_status: Some(ConnectionStatus::Online),
+            is_trade_allowed: true,
+            low_margin_level: false,
+        };
+        let slave_intent = SlaveIntent {
+            web_ui_enabled: true,
+        };
+
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_self_copy_check(&mut result, "ACCOUNT_001", "ACCOUNT_001");
+        assert_eq!(result.status, STATUS_DISABLED);
+        assert!(!result.allow_new_orders);
+        assert_eq!(result.warning_codes, vec![WarningCode::SelfCopyDetected]);
+
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_self_copy_check(&mut result, "MASTER_001", "SLAVE_001");
+        assert_eq!(result.status, STATUS_CONNECTED);
+        assert!(result.warning_codes.is_empty());
+    }
+
+    #[test]
+    fn test_member_paused_outside_active_schedule() {
+        use crate::domain::models::ScheduleWindow;
+        use chrono::{NaiveTime, TimeZone, Weekday};
+
+        let master_result = MasterStatusResult {
+            status: STATUS_CONNECTED,
+            warning_codes: Vec::new(),
+        };
+        let online = ConnectionSnapshot {
+            connection_status: Some(ConnectionStatus::Online),
+            is_trade_allowed: true,
+            low_margin_level: false,
+        };
+        let slave_intent = SlaveIntent {
+            web_ui_enabled: true,
+        };
+        // 08:00-17:00 Tokyo on Wednesdays
+        let settings = SlaveSettings {
+            active_schedule: Some(vec![ScheduleWindow {
+                days: vec![Weekday::Wed],
+                start: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+                tz: "Asia/Tokyo".to_string(),
+            }]),
+            ..SlaveSettings::default()
+        };
+
+        // Wednesday 10:00 JST
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_schedule_check(
+            &mut result,
+            &settings,
+            Utc.with_ymd_and_hms(2025, 1, 15, 1, 0, 0).unwrap(),
+        );
+        assert_eq!(result.status, STATUS_CONNECTED);
+        assert!(result.allow_new_orders);
+
+        // Wednesday 18:00 JST
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_schedule_check(
+            &mut result,
+            &settings,
+            Utc.with_ymd_and_hms(2025, 1, 15, 9, 0, 0).unwrap(),
+        );
+        assert_eq!(result.status, STATUS_DISABLED);
+        assert!(!result.allow_new_orders);
+        assert_eq!(
+            result.warning_codes,
+            vec![WarningCode::OutsideActiveSchedule]
+        );
+
+        // No schedule: always active
+        let mut result = evaluate_member_status(slave_intent, online, &master_result);
+        apply_schedule_check(&mut result, &SlaveSettings::default(), Utc::now());
+        assert_eq!(result.status, STATUS_CONNECTED);
+    }
+
+    #[test]
+    fn test_low_margin_level_is_advisory() {
+        let master_result = MasterStatusResult {
+            status: STATUS_CONNECTED,
+            warning_codes: Vec::new(),
+        };
+        let slave_conn = ConnectionSnapshot {
+            connection_status: Some(ConnectionStatus::Online),
+            is_trade_allowed: true,
+            low_margin_level: true,
+        };
+
+        let result = evaluate_member_status(
+            SlaveIntent {
+                web_ui_enabled: true,
+            },
+            slave_conn,
+            &master_result,
+        );
+        assert_eq!(result.warning_codes, vec![WarningCode::LowMarginLevel]);
+        assert_eq!(result.status, STATUS_CONNECTED);
+        assert!(result.allow_new_orders);
+
+        // Offline Slave reports no stale margin warning
+        let offline = ConnectionSnapshot {
+            connection_status: Some(ConnectionStatus::Offline),
+            ..slave_conn
+        };
+        let result = evaluate_member_status(
+            SlaveIntent {
+                web_ui_enabled: true,
+            },
+            offline,
+            &master_result,
+        );
+        assert!(!result.warning_codes.contains(&WarningCode::LowMarginLevel));
+    }
 }
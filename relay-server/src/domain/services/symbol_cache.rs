@@ -0,0 +1,220 @@
+//! Memoized symbol conversion
+//!
+//! `SymbolConverter::convert` allocates several `String`s per call, and every
+//! signal is converted once per member. Results are kept per member and Master
+//! symbol; a member's entries are dropped as soon as its `config_version` or the
+//! converter inputs (prefix/suffix, mappings, detected symbols) differ from the
+//! ones they were computed with.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use crate::domain::models::{SymbolConverter, SymbolMapping, TradeGroupMember};
+
+/// Symbols remembered per member before the least recently used one is evicted
+pub const DEFAULT_SYMBOLS_PER_MEMBER: usize = 256;
+
+/// Bounded per-member LRU cache of converted symbols
+pub struct SymbolConversionCache {
+    capacity: usize,
+    members: Mutex<HashMap<i32, MemberEntries>>,
+}
+
+/// Conversions for one member, valid for the inputs they were computed with
+struct MemberEntries {
+    config_version: u32,
+    converter: SymbolConverter,
+    mappings: Vec<SymbolMapping>,
+    tick: u64,
+    /// Master symbol -> (Slave symbol, last used tick)
+    symbols: HashMap<String, (String, u64)>,
+}
+
+impl MemberEntries {
+    fn new(config_version: u32, converter: &SymbolConverter, mappings: &[SymbolMapping]) -> Self {
+        Self {
+            config_version,
+            converter: converter.clone(),
+            mappings: mappings.to_vec(),
+            tick: 0,
+            symbols: HashMap::new(),
+        }
+    }
+
+    fn is_valid_for(
+        &self,
+        config_version: u32,
+        converter: &SymbolConverter,
+        mappings: &[SymbolMapping],
+    ) -> bool {
+        self.config_version == config_version
+            && self.converter == *converter
+            && self.mappings == mappings
+    }
+}
+
+impl SymbolConversionCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            members: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Same result as `converter.convert(symbol, &member.slave_settings.symbol_mappings)`
+    pub fn convert(
+        &self,
+        member: &TradeGroupMember,
+        converter: &SymbolConverter,
+        symbol: &str,
+    ) -> String {
+        let settings = &member.slave_settings;
+        let mut members = self.members.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = members.entry(member.id).or_insert_with(|| {
+            MemberEntries::new(
+                settings.config_version,
+                converter,
+                &settings.symbol_mappings,
+            )
+        });
+        if !entries.is_valid_for(
+            settings.config_version,
+            converter,
+            &settings.symbol_mappings,
+        ) {
+            *entries = MemberEntries::new(
+                settings.config_version,
+                converter,
+                &settings.symbol_mappings,
+            );
+        }
+
+        entries.tick += 1;
+        let tick = entries.tick;
+        if let Some((converted, last_used)) = entries.symbols.get_mut(symbol) {
+            *last_used = tick;
+            return converted.clone();
+        }
+
+        let converted = converter.convert(symbol, &settings.symbol_mappings);
+        if entries.symbols.len() >= self.capacity {
+            if let Some(oldest) = entries
+                .symbols
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone())
+            {
+                entries.symbols.remove(&oldest);
+            }
+        }
+        entries
+            .symbols
+            .insert(symbol.to_string(), (converted.clone(), tick));
+        converted
+    }
+
+    /// Number of cached symbols for a member (for tests and diagnostics)
+    pub fn cached_symbols(&self, member_id: i32) -> usize {
+        self.members
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&member_id)
+            .map_or(0, |entries| entries.symbols.len())
+    }
+}
+
+impl Default for SymbolConversionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SYMBOLS_PER_MEMBER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::SlaveSettings;
+
+    fn member(mappings: Vec<SymbolMapping>) -> TradeGroupMember {
+        let mut member =
+            TradeGroupMember::new(7, "MASTER_001".to_string(), "SLAVE_001".to_string());
+        member.slave_settings = SlaveSettings {
+            symbol_mappings: mappings,
+            ..SlaveSettings::default()
+        };
+        member
+    }
+
+    fn converter() -> SymbolConverter {
+        SymbolConverter {
+            prefix_remove: Some("pro.".to_string()),
+            suffix_remove: Some(".m".to_string()),
+            prefix_add: None,
+            suffix_add: Some("-ecn".to_string()),
+            synonym_groups: vec![vec!["XAUUSD".to_string(), "GOLD".to_string()]],
+            detected_symbols: Some(vec!["GOLD-ecn".to_string(), "GOLD".to_string()]),
+            case_insensitive: false,
+        }
+    }
+
+    fn mapping(source: &str, target: &str) -> SymbolMapping {
+        SymbolMapping {
+            source_symbol: source.to_string(),
+            target_symbol: target.to_string(),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_cached_conversions_match_uncached_over_1000_signals() {
+        let cache = SymbolConversionCache::new(8);
+        let member = member(vec![mapping("US30", "DJ30")]);
+        let converter = converter();
+        let symbols = [
+            "pro.EURUSD.m",
+            "pro.XAUUSD.m",
+            "US30",
+            "pro.US30.m",
+            "GBPJPY",
+            "pro.USDJPY.m",
+            "XAUUSD",
+            "pro.AUDUSD.m",
+            "pro.NZDUSD.m",
+            "pro.USDCAD.m",
+            "EURUSD.m",
+        ];
+
+        let start = std::time::Instant::now();
+        for i in 0..1000 {
+            // Varying stride so the 8-entry cache both hits and evicts
+            let symbol = symbols[(i * 7 + i / 13) % symbols.len()];
+            assert_eq!(
+                cache.convert(&member, &converter, symbol),
+                converter.convert(symbol, &member.slave_settings.symbol_mappings),
+                "{}",
+                symbol
+            );
+        }
+        tracing::debug!("1000 cached conversions took {:?}", start.elapsed());
+        assert_eq!(cache.cached_symbols(member.id), 8);
+    }
+
+    #[test]
+    fn test_config_version_bump_invalidates_member_entries() {
+        let cache = SymbolConversionCache::default();
+        let converter = converter();
+        let mut member = member(vec![mapping("US30", "DJ30")]);
+        assert_eq!(cache.convert(&member, &converter, "US30"), "DJ30-ecn");
+        assert_eq!(cache.convert(&member, &converter, "EURUSD"), "EURUSD-ecn");
+        assert_eq!(cache.cached_symbols(member.id), 2);
+
+        member.slave_settings.symbol_mappings = vec![mapping("US30", "WS30")];
+        member.slave_settings.config_version += 1;
+        assert_eq!(cache.convert(&member, &converter, "US30"), "WS30-ecn");
+        assert_eq!(cache.cached_symbols(member.id), 1);
+
+        // Heartbeat-driven converter changes invalidate too
+        let mut other = converter.clone();
+        other.suffix_add = Some(".r".to_string());
+        assert_eq!(cache.convert(&member, &other, "US30"), "WS30.r");
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-symbol contract specifications
+//!
+//! Sizing features need the contract size, pip value and price precision of the
+//! symbol being traded. Built-in values cover the common FX pairs and XAUUSD;
+//! `[symbol_specs]` in config.toml adds symbols or replaces built-in entries.
+//! Broker decorations such as `EURUSD.pro` or `EURUSDm` resolve to the base symbol.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Contract specification of one symbol (values per 1.0 lot, USD account)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SymbolSpec {
+    /// Units of the base asset in 1.0 lot
+    pub contract_size: f64,
+    /// Account currency value of a one pip move for 1.0 lot
+    pub pip_value: f64,
+    /// Price decimal places quoted by the broker
+    pub digits: u32,
+}
+
+impl SymbolSpec {
+    pub const fn new(contract_size: f64, pip_value: f64, digits: u32) -> Self {
+        Self {
+            contract_size,
+            pip_value,
+            digits,
+        }
+    }
+}
+
+impl Default for SymbolSpec {
+    /// Standard FX lot quoted in USD (EURUSD-like)
+    fn default() -> Self {
+        Self::new(100_000.0, 10.0, 5)
+    }
+}
+
+/// Broker suffixes stripped when a symbol has no entry of its own, longest first
+const KNOWN_SUFFIXES: &[&str] = &[
+    "MICRO", ".PRO", ".ECN", ".RAW", ".STD", "-ECN", "-PRO", "_I", ".M", ".I", ".", "#", "+", "M",
+    "I",
+];
+
+/// Built-in specifications. JPY-quoted pip values assume USDJPY around 150.
+fn builtin_specs() -> HashMap<String, SymbolSpec> {
+    let fx_usd_quote = SymbolSpec::new(100_000.0, 10.0, 5);
+    let fx_jpy_quote = SymbolSpec::new(100_000.0, 6.7, 3);
+
+    [
+        ("EURUSD", fx_usd_quote),
+        ("GBPUSD", fx_usd_quote),
+        ("AUDUSD", fx_usd_quote),
+        ("NZDUSD", fx_usd_quote),
+        ("USDJPY", fx_jpy_quote),
+        ("EURJPY", fx_jpy_quote),
+        ("GBPJPY", fx_jpy_quote),
+        ("USDCHF", SymbolSpec::new(100_000.0, 11.0, 5)),
+        ("USDCAD", SymbolSpec::new(100_000.0, 7.3, 5)),
+        ("XAUUSD", SymbolSpec::new(100.0, 1.0, 2)),
+    ]
+    .into_iter()
+    .map(|(symbol, spec)| (symbol.to_string(), spec))
+    .collect()
+}
+
+/// Symbol -> specification lookup shared by the sizing code
+#[derive(Debug, Clone)]
+pub struct SymbolSpecRegistry {
+    specs: HashMap<String, SymbolSpec>,
+    fallback: SymbolSpec,
+}
+
+impl SymbolSpecRegistry {
+    /// Built-in specifications overlaid with `overrides` (symbols are case-insensitive)
+    pub fn new(overrides: &HashMap<String, SymbolSpec>) -> Self {
+        let mut specs = builtin_specs();
+        specs.extend(
+            overrides
+                .iter()
+                .map(|(symbol, spec)| (symbol.to_uppercase(), *spec)),
+        );
+        Self {
+            specs,
+            fallback: SymbolSpec::default(),
+        }
+    }
+
+    /// Specification of `symbol`, trying the exact name and then the name without
+    /// a known broker suffix. None when neither is registered.
+    pub fn get(&self, symbol: &str) -> Option<SymbolSpec> {
+        let symbol = symbol.trim().to_uppercase();
+        if let Some(spec) = self.specs.get(&symbol) {
+            return Some(*spec);
+        }
+        KNOWN_SUFFIXES.iter().find_map(|suffix| {
+            symbol
+                .strip_suffix(suffix)
+                .and_then(|base| self.specs.get(base))
+                .copied()
+        })
+    }
+
+    /// Specification of `symbol`, or a standard FX lot when it is unknown
+    pub fn spec(&self, symbol: &str) -> SymbolSpec {
+        self.get(symbol).unwrap_or(self.fallback)
+    }
+
+    /// Price size of one pip: ten points for 3/5-digit quotes, one point otherwise.
+    /// Unknown symbols assume 0.01 for JPY-quoted pairs and 0.0001 for the rest.
+    pub fn pip_size(&self, symbol: &str) -> f64 {
+        match self.get(symbol) {
+            Some(spec) => {
+                let pip_digits = if spec.digits % 2 == 1 {
+                    spec.digits as i32 - 1
+                } else {
+                    spec.digits as i32
+                };
+                1.0 / 10f64.powi(pip_digits)
+            }
+            None if symbol.to_uppercase().contains("JPY") => 0.01,
+            None => 0.0001,
+        }
+    }
+}
+
+impl Default for SymbolSpecRegistry {
+    fn default() -> Self {
+        Self::new(&HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direct_lookup() {
+        let registry = SymbolSpecRegistry::default();
+
+        assert_eq!(registry.get("XAUUSD"), Some(SymbolSpec::new(100.0, 1.0, 2)));
+        assert_eq!(registry.get("usdjpy").map(|spec| spec.digits), Some(3));
+    }
+
+    #[test]
+    fn test_suffix_stripped_lookup() {
+        let registry = SymbolSpecRegistry::default();
+        let eurusd = registry.get("EURUSD");
+
+        assert!(eurusd.is_some());
+        assert_eq!(registry.get("EURUSD.pro"), eurusd);
+        assert_eq!(registry.get("EURUSDm"), eurusd);
+        assert_eq!(registry.get("EURUSD#"), eurusd);
+        assert_eq!(registry.get("XAUUSD.ecn"), registry.get("XAUUSD"));
+    }
+
+    #[test]
+    fn test_unknown_symbol_falls_back_to_default() {
+        let registry = SymbolSpecRegistry::default();
+
+        assert_eq!(registry.get("US30"), None);
+        assert_eq!(registry.spec("US30"), SymbolSpec::default());
+    }
+
+    #[test]
+    fn test_pip_size_follows_digits() {
+        let overrides = HashMap::from([("US30".to_string(), SymbolSpec::new(1.0, 1.0, 1))]);
+        let registry = SymbolSpecRegistry::new(&overrides);
+
+        assert_eq!(registry.pip_size("EURUSD.pro"), 0.0001);
+        assert_eq!(registry.pip_size("USDJPY"), 0.01);
+        assert_eq!(registry.pip_size("XAUUSD"), 0.01);
+        assert_eq!(registry.pip_size("US30"), 1.0);
+        // Unregistered symbols
+        assert_eq!(registry.pip_size("CHFJPY"), 0.01);
+        assert_eq!(registry.pip_size("EURGBP"), 0.0001);
+    }
+
+    #[test]
+    fn test_config_overrides_builtin_and_adds_symbols() {
+        let overrides = HashMap::from([
+            ("xauusd".to_string(), SymbolSpec::new(100.0, 10.0, 2)),
+            ("US30".to_string(), SymbolSpec::new(1.0, 1.0, 1)),
+        ]);
+        let registry = SymbolSpecRegistry::new(&overrides);
+
+        assert_eq!(registry.spec("XAUUSD").pip_value, 10.0);
+        assert_eq!(registry.spec("US30").contract_size, 1.0);
+    }
+}
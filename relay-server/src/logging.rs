@@ -1,4 +1,5 @@
 use crate::adapters::infrastructure::log_buffer::{LogBuffer, LogBufferLayer};
+use crate::adapters::infrastructure::rolling_file::{file_index, SizeRollingWriter};
 use crate::adapters::outbound::observability::victoria_logs::VictoriaLogsLayer;
 use crate::config::LoggingConfig;
 use std::time::Duration;
@@ -30,14 +31,39 @@ pub fn init(config: &LoggingConfig, log_buffer: LogBuffer, vlogs_layer: Option<V
         cleanup_old_logs(config);
 
         // Create file appender based on rotation strategy
-        let file_appender = match config.rotation.as_str() {
-            "hourly" => rolling::hourly(&config.directory, &config.file_prefix),
-            "never" => rolling::never(&config.directory, &config.file_prefix),
-            _ => rolling::daily(&config.directory, &config.file_prefix), // default to daily
+        let (non_blocking, _guard) = match config.rotation.as_str() {
+            "size" => match SizeRollingWriter::new(config) {
+                Ok(writer) => tracing_appender::non_blocking(writer),
+                Err(e) => {
+                    eprintln!(
+                        "Failed to open size-rotated log in {}: {}; using daily rotation",
+                        config.directory, e
+                    );
+                    tracing_appender::non_blocking(rolling::daily(
+                        &config.directory,
+                        &config.file_prefix,
+                    ))
+                }
+            },
+            "minutely" => tracing_appender::non_blocking(rolling::minutely(
+                &config.directory,
+                &config.file_prefix,
+            )),
+            "hourly" => tracing_appender::non_blocking(rolling::hourly(
+                &config.directory,
+                &config.file_prefix,
+            )),
+            "never" => tracing_appender::non_blocking(rolling::never(
+                &config.directory,
+                &config.file_prefix,
+            )),
+            // default to daily
+            _ => tracing_appender::non_blocking(rolling::daily(
+                &config.directory,
+                &config.file_prefix,
+            )),
         };
 
-        let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
-
         subscriber
             .with(
                 tracing_subscriber::fmt::layer()
@@ -94,7 +120,9 @@ pub fn cleanup_old_logs(logging_config: &LoggingConfig) {
                 }
 
                 let modified = metadata.modified().ok()?;
-                Some((entry.path(), modified))
+                // Size-rotated files written within the same mtime tick sort by number
+                let index = file_index(name, &logging_config.file_prefix).unwrap_or(0);
+                Some((entry.path(), modified, index))
             })
             .collect(),
         Err(e) => {
@@ -104,14 +132,14 @@ pub fn cleanup_old_logs(logging_config: &LoggingConfig) {
     };
 
     // Sort by modified time (newest first)
-    log_files.sort_by(|a, b| b.1.cmp(&a.1));
+    log_files.sort_by_key(|b| std::cmp::Reverse((b.1, b.2)));
 
     let now = SystemTime::now();
     let max_age_duration = Duration::from_secs((logging_config.max_age_days as u64) * 24 * 60 * 60);
     let mut deleted_count = 0;
 
     // Delete old files based on retention policy
-    for (idx, (path, modified)) in log_files.iter().enumerate() {
+    for (idx, (path, modified, _)) in log_files.iter().enumerate() {
         let mut should_delete = false;
 
         // Check if exceeds max file count
@@ -1,17 +1,49 @@
 use anyhow::Result;
+use sankey_copier_relay_server::adapters::infrastructure::curve_keys;
 use sankey_copier_relay_server::bootstrap;
+use sankey_copier_relay_server::bootstrap::shutdown::shutdown_signal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long each background task may take to drain on shutdown
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `sankey-copier-server keygen [out_dir]` writes a CURVE key pair for [zeromq.security]
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("keygen") {
+        let out_dir = args
+            .get(2)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("keys"));
+        let pair = curve_keys::generate_key_files(&out_dir, "zmq_server")?;
+        println!(
+            "CURVE key pair written to {}",
+            out_dir.join("zmq_server.{pub,key}").display()
+        );
+        println!("Server public key: {}", pair.public_key);
+        return Ok(());
+    }
+
     // Bootstrap the application (setup logging, DB, ZMQ tasks, API router)
     let app = bootstrap::setup().await?;
 
     // Start HTTPS server
     tracing::info!("HTTPS server listening on https://{}", app.bind_address);
 
-    axum_server::bind_rustls(app.socket_addr, app.tls_config)
-        .serve(app.router.into_make_service())
-        .await?;
+    let server = axum_server::bind_rustls(app.socket_addr, app.tls_config).serve(
+        app.router
+            .into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    );
+
+    let result = tokio::select! {
+        result = server => result,
+        () = shutdown_signal() => Ok(()),
+    };
+
+    app.background.shutdown(SHUTDOWN_TIMEOUT).await;
+    tracing::info!("Shutdown complete");
 
-    Ok(())
+    Ok(result?)
 }
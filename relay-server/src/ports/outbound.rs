@@ -1,14 +1,15 @@
 use crate::domain::models::{
-    EaConnection, HeartbeatMessage, SlaveConfigWithMaster, TradeGroup, VLogsGlobalSettings,
+    BrokerProfile, EaConnection, GlobalSettings, HeartbeatMessage, HeartbeatOutcome,
+    SlaveConfigWithMaster, TradeGroup, VLogsGlobalSettings, WarningCode,
 };
 use async_trait::async_trait;
-use sankey_copier_zmq::{MasterConfigMessage, SlaveConfigMessage};
+use sankey_copier_zmq::{CloseAllMessage, MasterConfigMessage, SlaveConfigMessage};
 
 #[async_trait]
 pub trait ConnectionManager: Send + Sync {
     async fn get_master(&self, account_id: &str) -> Option<EaConnection>;
     async fn get_slave(&self, account_id: &str) -> Option<EaConnection>;
-    async fn update_heartbeat(&self, msg: HeartbeatMessage) -> bool;
+    async fn update_heartbeat(&self, msg: HeartbeatMessage) -> HeartbeatOutcome;
 }
 
 #[async_trait]
@@ -35,6 +36,10 @@ pub trait TradeGroupRepository: Send + Sync {
         status: i32,
     ) -> anyhow::Result<()>;
     async fn get_masters_for_slave(&self, slave_account: &str) -> anyhow::Result<Vec<String>>;
+    async fn get_global_settings(&self) -> anyhow::Result<GlobalSettings>;
+    /// Enable a disabled, not explicitly disabled member; true when it was enabled
+    async fn auto_enable_member(&self, master_id: &str, slave_id: &str) -> anyhow::Result<bool>;
+    async fn get_broker_profile(&self, name: &str) -> anyhow::Result<Option<BrokerProfile>>;
 }
 
 #[async_trait]
@@ -42,6 +47,8 @@ pub trait ConfigPublisher: Send + Sync {
     async fn send_master_config(&self, config: &MasterConfigMessage) -> anyhow::Result<()>;
     async fn send_slave_config(&self, config: &SlaveConfigMessage) -> anyhow::Result<()>;
     async fn broadcast_vlogs_config(&self, config: &VLogsGlobalSettings) -> anyhow::Result<()>;
+    /// Ask a Slave EA to close the positions it copied (on its config topic)
+    async fn send_close_all(&self, message: &CloseAllMessage) -> anyhow::Result<()>;
 }
 
 // Notification trait for broadcasting updates (WebSocket)
@@ -49,7 +56,7 @@ pub trait ConfigPublisher: Send + Sync {
 pub trait UpdateBroadcaster: Send + Sync {
     async fn broadcast_snapshot(&self);
     async fn broadcast_ea_disconnected(&self, account_id: &str);
-    async fn broadcast_settings_updated(&self, json: &str);
+    async fn broadcast_settings_updated(&self, settings: &SlaveConfigWithMaster);
 }
 
 /// Trait for handling EA disconnection events
@@ -63,4 +70,28 @@ pub trait DisconnectionService: Send + Sync {
     /// Handle Slave EA going offline
     /// Updates runtime status for all trade groups the Slave belongs to
     async fn handle_slave_offline(&self, slave_account: &str);
+
+    /// `handle_master_offline` limited to the listed Slaves; the other members
+    /// keep their status (per-member `timeout_action` of MarkOnly)
+    async fn handle_master_offline_for(&self, master_account: &str, slave_accounts: &[String]);
+
+    /// `handle_slave_offline` limited to the listed trade groups
+    async fn handle_slave_offline_for(&self, slave_account: &str, trade_group_ids: &[String]);
+}
+
+/// Alerts on status transitions (e.g. webhook to Slack/Discord)
+///
+/// Callers report the current state; implementations detect the transitions.
+#[async_trait]
+pub trait StatusNotifier: Send + Sync {
+    /// Current connection state of a Master
+    async fn master_connection(&self, master_account: &str, online: bool);
+
+    /// Current warnings of a member (Master-Slave connection)
+    async fn member_warnings(
+        &self,
+        master_account: &str,
+        slave_account: &str,
+        warning_codes: &[WarningCode],
+    );
 }
@@ -0,0 +1,143 @@
+// relay-server/tests/admin_backup_api_test.rs
+//
+// Tests for the online database backup endpoint (POST /api/admin/backup).
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use sqlx::sqlite::SqlitePool;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::config::Config;
+use sankey_copier_relay_server::domain::models::ServerEvent;
+
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Helper function to create a test app with a file database in `dir`
+/// (VACUUM INTO from an in-memory database does not produce a file)
+async fn create_test_app(dir: &Path, backup_dir: &Path) -> (axum::Router, Arc<Database>) {
+    let url = format!("sqlite://{}?mode=rwc", dir.join("live.db").display());
+    let db = Arc::new(Database::new(&url).await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+
+    // Create a dummy ZMQ config sender
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let mut config = Config::default();
+    config.database.backup_dir = backup_dir.to_string_lossy().into_owned();
+
+    let app_state = AppState {
+        db: db.clone(),
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer,
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
+        cors_disabled: false,
+        config: Arc::new(config),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    };
+
+    (create_router(app_state), db)
+}
+
+async fn post_backup(app: axum::Router) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/backup")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_backup_creates_valid_sqlite_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let backup_dir = dir.path().join("backups");
+    let (app, db) = create_test_app(dir.path(), &backup_dir).await;
+    db.create_trade_group("MASTER_001").await.unwrap();
+
+    let (status, body) = post_backup(app).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let filename = body["filename"].as_str().unwrap();
+    let path = backup_dir.join(filename);
+    assert!(path.exists(), "backup file should be created");
+    assert_eq!(
+        body["size_bytes"].as_u64().unwrap(),
+        std::fs::metadata(&path).unwrap().len()
+    );
+
+    // The copy opens as a SQLite database and contains the live data
+    let pool = SqlitePool::connect(&format!("sqlite://{}?mode=ro", path.display()))
+        .await
+        .unwrap();
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(integrity, "ok");
+    let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM trade_groups")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(ids, vec!["MASTER_001".to_string()]);
+}
+
+#[tokio::test]
+async fn test_backup_rejects_unwritable_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    // A regular file cannot be used as (or contain) a directory
+    let blocker = dir.path().join("not-a-dir");
+    std::fs::write(&blocker, b"").unwrap();
+    let (app, _db) = create_test_app(dir.path(), &blocker.join("backups")).await;
+
+    let (status, body) = post_backup(app).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["instance"], "/api/admin/backup");
+    assert!(body["detail"].as_str().unwrap().contains("not writable"));
+}
@@ -0,0 +1,236 @@
+// relay-server/tests/api_auth_test.rs
+//
+// Tests for the optional bearer-token authentication ([security] api_token).
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::config::Config;
+use sankey_copier_relay_server::domain::models::ServerEvent;
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+const TOKEN: &str = "s3cret-token";
+
+async fn create_test_app(api_token: &str) -> axum::Router {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let mut config = Config::default();
+    config.security.api_token = api_token.to_string();
+
+    create_router(AppState {
+        db,
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer: create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY),
+        allowed_origins: CorsOrigins::default(),
+        cors_disabled: true,
+        config: Arc::new(config),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    })
+}
+
+async fn send(
+    app: axum::Router,
+    request: Request<Body>,
+) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, headers, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_missing_or_wrong_token_is_401() {
+    let app = create_test_app(TOKEN).await;
+
+    let (status, headers, body) = send(
+        app.clone(),
+        Request::builder()
+            .uri("/api/trade-groups")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(headers[header::WWW_AUTHENTICATE], "Bearer");
+    let problem: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["status"], 401);
+    assert_eq!(problem["instance"], "/api/trade-groups");
+
+    let (status, _, _) = send(
+        app,
+        Request::builder()
+            .method("DELETE")
+            .uri("/api/trade-groups/MASTER_001")
+            .header(header::AUTHORIZATION, "Bearer wrong-token")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_valid_token_is_accepted() {
+    let app = create_test_app(TOKEN).await;
+
+    let (status, _, _) = send(
+        app,
+        Request::builder()
+            .uri("/api/trade-groups")
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_empty_token_disables_auth() {
+    let app = create_test_app("").await;
+
+    let (status, _, _) = send(
+        app,
+        Request::builder()
+            .uri("/api/trade-groups")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_websocket_accepts_token_query_param() {
+    let app = create_test_app(TOKEN).await;
+
+    let (status, _, _) = send(
+        app.clone(),
+        Request::builder().uri("/ws").body(Body::empty()).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _, _) = send(
+        app.clone(),
+        Request::builder()
+            .uri("/ws?token=wrong-token")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Authenticated: reaches the WebSocket handler, which rejects the non-upgrade request
+    let (status, _, _) = send(
+        app.clone(),
+        Request::builder()
+            .uri(format!("/ws?token={}", TOKEN))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_ne!(status, StatusCode::UNAUTHORIZED);
+
+    // The query param is only honoured for the WebSocket endpoint
+    let (status, _, _) = send(
+        app,
+        Request::builder()
+            .uri(format!("/api/trade-groups?token={}", TOKEN))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+/// Collects formatted trace output in memory
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_token_is_not_written_to_trace_logs() {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = create_test_app(TOKEN).await;
+    send(
+        app.clone(),
+        Request::builder()
+            .uri("/api/trade-groups")
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    send(
+        app,
+        Request::builder()
+            .uri(format!("/ws?token={}", TOKEN))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("/api/trade-groups"), "{}", output);
+    assert!(output.contains("/ws"), "{}", output);
+    assert!(!output.contains(TOKEN), "{}", output);
+}
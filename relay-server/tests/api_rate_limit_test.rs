@@ -0,0 +1,140 @@
+// relay-server/tests/api_rate_limit_test.rs
+//
+// Tests for the per-client rate limit on mutating API requests
+// ([security] requests_per_minute).
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::config::Config;
+use sankey_copier_relay_server::domain::models::ServerEvent;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+async fn create_test_app(requests_per_minute: u32) -> axum::Router {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let mut config = Config::default();
+    config.security.requests_per_minute = requests_per_minute;
+
+    create_router(AppState {
+        db,
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer: create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY),
+        allowed_origins: CorsOrigins::default(),
+        cors_disabled: true,
+        config: Arc::new(config),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    })
+}
+
+fn create_group(client: &str, id: &str) -> Request<Body> {
+    let addr: SocketAddr = format!("{}:50000", client).parse().unwrap();
+    Request::builder()
+        .method("POST")
+        .uri("/api/trade-groups")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(ConnectInfo(addr))
+        .body(Body::from(format!(r#"{{"id":"{}"}}"#, id)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_request_over_limit_gets_429() {
+    let app = create_test_app(3).await;
+
+    for i in 0..3 {
+        let response = app
+            .clone()
+            .oneshot(create_group("192.0.2.1", &format!("MASTER_{}", i)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = app
+        .clone()
+        .oneshot(create_group("192.0.2.1", "MASTER_4"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "20");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let problem: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem["status"], 429);
+
+    // Other clients and read-only requests are unaffected
+    let response = app
+        .clone()
+        .oneshot(create_group("192.0.2.2", "MASTER_5"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/trade-groups")
+                .extension(ConnectInfo(
+                    "192.0.2.1:50000".parse::<SocketAddr>().unwrap(),
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_zero_disables_rate_limit() {
+    let app = create_test_app(0).await;
+
+    for i in 0..20 {
+        let response = app
+            .clone()
+            .oneshot(create_group("192.0.2.1", &format!("MASTER_{}", i)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,197 @@
+// relay-server/tests/config_transfer_api_test.rs
+//
+// Unit tests for the config export/import REST API endpoints.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::domain::models::{MasterSettings, ServerEvent, SlaveSettings};
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Helper function to create a test app with in-memory database
+async fn create_test_app() -> (axum::Router, Arc<Database>) {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+
+    // Create a dummy ZMQ config sender
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+
+    // 2-port architecture: receiver and unified publisher
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let app_state = AppState {
+        db: db.clone(),
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer,
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
+        cors_disabled: false,
+        config: Arc::new(sankey_copier_relay_server::config::Config::default()),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    };
+
+    (create_router(app_state), db)
+}
+
+async fn seed(db: &Database) {
+    db.create_trade_group("MASTER_001").await.unwrap();
+    db.update_master_settings(
+        "MASTER_001",
+        MasterSettings {
+            symbol_suffix: Some(".m".to_string()),
+            ..MasterSettings::default()
+        },
+    )
+    .await
+    .unwrap();
+    db.add_member(
+        "MASTER_001",
+        "SLAVE_001",
+        SlaveSettings {
+            lot_multiplier: Some(0.5),
+            ..SlaveSettings::default()
+        },
+        2,
+    )
+    .await
+    .unwrap();
+}
+
+async fn send(app: axum::Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn export(app: axum::Router) -> Value {
+    let (status, json) = send(
+        app,
+        Request::builder()
+            .uri("/api/config/export")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    json
+}
+
+async fn import(app: axum::Router, query: &str, body: &Value) -> (StatusCode, Value) {
+    send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/config/import{}", query))
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let (app, db) = create_test_app().await;
+    seed(&db).await;
+    let exported = export(app).await;
+    assert_eq!(exported["version"], 1);
+    assert_eq!(exported["trade_groups"][0]["id"], "MASTER_001");
+    assert_eq!(
+        exported["trade_groups"][0]["members"][0]["enabled_flag"],
+        true
+    );
+
+    // Fresh database stands in for a wiped one
+    let (app, _db) = create_test_app().await;
+    let (status, result) = import(app.clone(), "", &exported).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(result["created"][0], "MASTER_001");
+
+    let reimported = export(app).await;
+    assert_eq!(reimported["trade_groups"], exported["trade_groups"]);
+}
+
+#[tokio::test]
+async fn test_import_dry_run_does_not_write() {
+    let (app, db) = create_test_app().await;
+    seed(&db).await;
+    let exported = export(app).await;
+
+    let (app, db) = create_test_app().await;
+    let (status, result) = import(app, "?dry_run=true", &exported).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(result["created"][0], "MASTER_001");
+    assert!(db.list_trade_groups().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_conflict_returns_409_unless_overwrite() {
+    let (app, db) = create_test_app().await;
+    seed(&db).await;
+    let exported = export(app.clone()).await;
+
+    let (status, problem) = import(app.clone(), "", &exported).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(problem["detail"].as_str().unwrap().contains("MASTER_001"));
+
+    let (status, result) = import(app, "?overwrite=true", &exported).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(result["updated"][0], "MASTER_001");
+}
+
+#[tokio::test]
+async fn test_import_rejects_invalid_document() {
+    let (app, _db) = create_test_app().await;
+    let settings = serde_json::to_value(MasterSettings::default()).unwrap();
+    let body = serde_json::json!({
+        "version": 1,
+        "exported_at": "2025-01-15T10:00:00Z",
+        "trade_groups": [
+            { "id": "MASTER_001", "master_settings": settings, "members": [] },
+            { "id": "MASTER_001", "master_settings": settings, "members": [] }
+        ]
+    });
+
+    let (status, problem) = import(app, "", &body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["detail"].as_str().unwrap().contains("Duplicate"));
+}
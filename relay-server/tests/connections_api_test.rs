@@ -0,0 +1,554 @@
+// relay-server/tests/connections_api_test.rs
+//
+// Unit tests for EA connection REST API endpoints.
+// Tests the per-EA heartbeat timeout override and cached positions endpoints.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::domain::models::{
+    ConnectionStatus, EaType, HeartbeatMessage, MasterSettings, PositionInfo, ServerEvent,
+    SlaveSettings, STATUS_CONNECTED, STATUS_ENABLED,
+};
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Helper function to create a test app with a shared connection manager
+async fn create_test_app() -> (axum::Router, Arc<ConnectionManager>) {
+    let (app, connection_manager, _, _) = create_test_app_with_db().await;
+    (app, connection_manager)
+}
+
+/// Test app exposing the database and WebSocket broadcast channel as well
+async fn create_test_app_with_db() -> (
+    axum::Router,
+    Arc<ConnectionManager>,
+    Arc<Database>,
+    broadcast::Sender<ServerEvent>,
+) {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(10));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+
+    // Create a dummy ZMQ config sender
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+
+    // 2-port architecture: receiver and unified publisher
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let app_state = AppState {
+        db: db.clone(),
+        tx: broadcast_tx.clone(),
+        connection_manager: connection_manager.clone(),
+        config_sender,
+        log_buffer,
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
+        cors_disabled: false,
+        config: Arc::new(sankey_copier_relay_server::config::Config::default()),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    };
+
+    (
+        create_router(app_state),
+        connection_manager,
+        db,
+        broadcast_tx,
+    )
+}
+
+fn heartbeat(account_id: &str, ea_type: &str) -> HeartbeatMessage {
+    HeartbeatMessage {
+        message_type: "Heartbeat".to_string(),
+        account_id: account_id.to_string(),
+        balance: 10000.0,
+        equity: 10000.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
+        open_positions: 0,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        version: "test".to_string(),
+        ea_type: ea_type.to_string(),
+        platform: "MT5".to_string(),
+        account_number: 12345,
+        broker: "Test Broker".to_string(),
+        account_name: "Test Account".to_string(),
+        server: "Test-Server".to_string(),
+        currency: "USD".to_string(),
+        leverage: 100,
+        is_trade_allowed: true,
+        symbol_prefix: None,
+        symbol_suffix: None,
+        symbol_map: None,
+    }
+}
+
+fn put_timeout(account_id: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/api/connections/{}/timeout", account_id))
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_update_connection_timeout() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+
+    let response = app
+        .oneshot(put_timeout(
+            "SLAVE_001",
+            json!({ "timeout_seconds": 60, "ea_type": "Slave" }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json[0]["timeout_override"], 60);
+
+    let slave = cm.get_slave("SLAVE_001").await.unwrap();
+    assert_eq!(slave.timeout_override, Some(60));
+}
+
+#[tokio::test]
+async fn test_update_connection_timeout_clear() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+    cm.set_timeout_override("SLAVE_001", None, Some(60)).await;
+
+    let response = app
+        .oneshot(put_timeout("SLAVE_001", json!({ "timeout_seconds": null })))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let slave = cm.get_slave("SLAVE_001").await.unwrap();
+    assert_eq!(slave.timeout_override, None);
+}
+
+#[tokio::test]
+async fn test_update_connection_timeout_rejects_non_positive() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+
+    let response = app
+        .oneshot(put_timeout("SLAVE_001", json!({ "timeout_seconds": 0 })))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let slave = cm.get_slave("SLAVE_001").await.unwrap();
+    assert_eq!(slave.timeout_override, None);
+}
+
+#[tokio::test]
+async fn test_update_connection_timeout_not_found() {
+    let (app, _cm) = create_test_app().await;
+
+    let response = app
+        .oneshot(put_timeout("UNKNOWN", json!({ "timeout_seconds": 60 })))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+fn get_positions(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn position(ticket: i64, symbol: &str, order_type: &str, profit: f64) -> PositionInfo {
+    PositionInfo {
+        ticket,
+        symbol: symbol.to_string(),
+        order_type: order_type.to_string(),
+        lots: 0.1,
+        open_price: 1.085,
+        open_time: chrono::Utc::now().to_rfc3339(),
+        stop_loss: None,
+        take_profit: None,
+        magic_number: None,
+        comment: None,
+        profit,
+    }
+}
+
+#[tokio::test]
+async fn test_get_connection_positions() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("MASTER_001", "Master")).await;
+    cm.record_position_snapshot(
+        "MASTER_001",
+        EaType::Master,
+        vec![
+            position(1001, "EURUSD", "Buy", 15.5),
+            position(1002, "GBPUSD", "Sell", -4.0),
+        ],
+    )
+    .await;
+
+    let response = app
+        .oneshot(get_positions("/api/connections/MASTER_001/positions"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["account_id"], "MASTER_001");
+    assert_eq!(json["ea_type"], "Master");
+    assert!(json["received_at"].is_string());
+    let positions = json["positions"].as_array().unwrap();
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0]["ticket"], 1001);
+    assert_eq!(positions[0]["symbol"], "EURUSD");
+    assert_eq!(positions[0]["order_type"], "Buy");
+    assert_eq!(positions[0]["lots"], 0.1);
+    assert_eq!(positions[0]["open_price"], 1.085);
+    assert_eq!(positions[0]["profit"], 15.5);
+    assert_eq!(positions[1]["profit"], -4.0);
+}
+
+#[tokio::test]
+async fn test_get_connection_positions_without_snapshot() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("MASTER_001", "Master")).await;
+
+    let response = app
+        .oneshot(get_positions("/api/connections/MASTER_001/positions"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["received_at"].is_null());
+    assert_eq!(json["positions"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_get_connection_positions_not_found() {
+    let (app, cm) = create_test_app().await;
+    // Only a Slave EA exists on this account
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+
+    let response = app
+        .clone()
+        .oneshot(get_positions("/api/connections/UNKNOWN/positions"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(get_positions("/api/connections/SLAVE_001/positions"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn list_page(app: &axum::Router, query: &str) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/connections{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn page_keys(page: &Value) -> Vec<String> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|conn| {
+            format!(
+                "{}:{}",
+                conn["account_id"].as_str().unwrap(),
+                conn["ea_type"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_connections_pages_in_stable_order() {
+    let (app, connection_manager) = create_test_app().await;
+    for (account_id, ea_type) in [
+        ("ACC_B", "Master"),
+        ("ACC_A", "Slave"),
+        ("ACC_C", "Slave"),
+        ("ACC_A", "Master"),
+    ] {
+        connection_manager
+            .update_heartbeat(heartbeat(account_id, ea_type))
+            .await;
+    }
+
+    let (status, page) = list_page(&app, "?limit=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page_keys(&page), vec!["ACC_A:Master", "ACC_A:Slave"]);
+    assert_eq!(page["next_cursor"], "ACC_A:Slave");
+
+    // Connections appearing mid-iteration: one before the cursor, one after
+    connection_manager
+        .update_heartbeat(heartbeat("ACC_0", "Master"))
+        .await;
+    connection_manager
+        .update_heartbeat(heartbeat("ACC_B", "Slave"))
+        .await;
+
+    let (_, page) = list_page(&app, "?limit=2&after=ACC_A:Slave").await;
+    assert_eq!(page_keys(&page), vec!["ACC_B:Master", "ACC_B:Slave"]);
+    assert_eq!(page["next_cursor"], "ACC_B:Slave");
+
+    let (_, page) = list_page(&app, "?limit=2&after=ACC_B:Slave").await;
+    assert_eq!(page_keys(&page), vec!["ACC_C:Slave"]);
+    assert!(page["next_cursor"].is_null());
+
+    // A bare account_id skips every EA of that account
+    let (_, page) = list_page(&app, "?after=ACC_B").await;
+    assert_eq!(page_keys(&page), vec!["ACC_C:Slave"]);
+
+    // Without limit everything is returned on one page
+    let (_, page) = list_page(&app, "").await;
+    assert_eq!(page_keys(&page).len(), 6);
+    assert!(page["next_cursor"].is_null());
+
+    // Connection stability fields are included with each item
+    let first = &page["items"][0];
+    assert!(first["uptime_seconds"]
+        .as_i64()
+        .is_some_and(|secs| (0..5).contains(&secs)));
+    assert_eq!(first["flap_count"], 0);
+}
+
+#[tokio::test]
+async fn test_list_connections_rejects_zero_limit() {
+    let (app, _) = create_test_app().await;
+
+    let (status, problem) = list_page(&app, "?limit=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["status"], 400);
+}
+
+fn force_disconnect(uri: &str) -> Request<Body> {
+    Request::builder()
+        .method("DELETE")
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_force_disconnect_master_reevaluates_slaves() {
+    let (app, connection_manager, db, tx) = create_test_app_with_db().await;
+    let mut ws_rx = tx.subscribe();
+
+    db.create_trade_group("MASTER_001").await.unwrap();
+    db.update_master_settings(
+        "MASTER_001",
+        MasterSettings {
+            enabled: true,
+            config_version: 1,
+            ..MasterSettings::default()
+        },
+    )
+    .await
+    .unwrap();
+    db.add_member(
+        "MASTER_001",
+        "SLAVE_001",
+        SlaveSettings::default(),
+        STATUS_CONNECTED,
+    )
+    .await
+    .unwrap();
+    connection_manager
+        .update_heartbeat(heartbeat("MASTER_001", "Master"))
+        .await;
+    connection_manager
+        .update_heartbeat(heartbeat("SLAVE_001", "Slave"))
+        .await;
+
+    let response = app
+        .clone()
+        .oneshot(force_disconnect(
+            "/api/connections/MASTER_001?ea_type=Master",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let conn: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(conn["status"], "Offline");
+    assert_eq!(conn["uptime_seconds"], 0);
+
+    let master = connection_manager.get_master("MASTER_001").await.unwrap();
+    assert_eq!(master.status, ConnectionStatus::Offline);
+    // The Slave EA itself is untouched
+    let slave = connection_manager.get_slave("SLAVE_001").await.unwrap();
+    assert_eq!(slave.status, ConnectionStatus::Online);
+
+    // Dependent member falls back to ENABLED as on a heartbeat timeout
+    let member = db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.status, STATUS_ENABLED);
+
+    let mut events = Vec::new();
+    while let Ok(event) = ws_rx.try_recv() {
+        events.push(event);
+    }
+    assert!(events.iter().any(
+        |e| matches!(e, ServerEvent::EaDisconnected { account_id } if account_id == "MASTER_001")
+    ));
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, ServerEvent::SettingsUpdated { .. })),
+        "expected a member update broadcast, got {:?}",
+        events
+    );
+}
+
+#[tokio::test]
+async fn test_force_disconnect_requires_known_ea_and_type() {
+    let (app, connection_manager) = create_test_app().await;
+    connection_manager
+        .update_heartbeat(heartbeat("MASTER_001", "Master"))
+        .await;
+
+    let response = app
+        .clone()
+        .oneshot(force_disconnect("/api/connections/MASTER_001"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Registered as Master only
+    let response = app
+        .clone()
+        .oneshot(force_disconnect(
+            "/api/connections/MASTER_001?ea_type=Slave",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(force_disconnect("/api/connections/UNKNOWN?ea_type=Master"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        connection_manager
+            .get_master("MASTER_001")
+            .await
+            .unwrap()
+            .status,
+        ConnectionStatus::Online
+    );
+}
+
+#[tokio::test]
+async fn test_get_connection_history_filters_by_ea_type() {
+    let (app, cm) = create_test_app().await;
+    cm.update_heartbeat(heartbeat("HIST_001", "Master")).await;
+    cm.update_heartbeat(heartbeat("HIST_001", "Slave")).await;
+    cm.force_disconnect("HIST_001", EaType::Slave)
+        .await
+        .unwrap();
+
+    let history = |uri: &str| {
+        let app = app.clone();
+        let uri = uri.to_string();
+        async move {
+            let response = app.oneshot(get_positions(&uri)).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        }
+    };
+
+    let (status, json) = history("/api/connections/HIST_001/history?ea_type=Slave").await;
+    assert_eq!(status, StatusCode::OK);
+    let events: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(events, vec!["registered", "unregistered"]);
+    assert_eq!(json[1]["ea_type"], "Slave");
+    assert_eq!(json[1]["reason"], "Force-disconnected via API");
+
+    // Without ea_type both EAs of the account are merged
+    let (status, json) = history("/api/connections/HIST_001/history").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json.as_array().unwrap().len(), 3);
+
+    let (status, _) = history("/api/connections/UNKNOWN/history").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
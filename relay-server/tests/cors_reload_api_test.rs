@@ -0,0 +1,143 @@
+// relay-server/tests/cors_reload_api_test.rs
+//
+// Tests for reloading the CORS origin set at runtime (POST /api/admin/reload-cors).
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::config::Config;
+use sankey_copier_relay_server::domain::models::ServerEvent;
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+const UI_ORIGIN: &str = "https://localhost:8080";
+const NEW_ORIGIN: &str = "https://copier.example.com";
+
+/// Helper function to create a test app with CORS restricted to UI_ORIGIN
+async fn create_test_app() -> axum::Router {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    create_router(AppState {
+        db,
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer: create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY),
+        allowed_origins: CorsOrigins::new(vec![UI_ORIGIN.to_string()]),
+        cors_disabled: false,
+        config: Arc::new(Config::default()),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    })
+}
+
+/// Send a CORS preflight and return the Access-Control-Allow-Origin header
+async fn preflight(app: &axum::Router, origin: &str) -> Option<String> {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/api/trade-groups")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+async fn reload_cors(app: &axum::Router, body: Value) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/reload-cors")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_reloaded_origin_accepted_on_next_preflight() {
+    let app = create_test_app().await;
+    assert_eq!(preflight(&app, UI_ORIGIN).await.as_deref(), Some(UI_ORIGIN));
+    assert_eq!(preflight(&app, NEW_ORIGIN).await, None);
+
+    let (status, body) = reload_cors(&app, json!({ "origins": [UI_ORIGIN, NEW_ORIGIN] })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["origins"], json!([UI_ORIGIN, NEW_ORIGIN]));
+
+    assert_eq!(
+        preflight(&app, NEW_ORIGIN).await.as_deref(),
+        Some(NEW_ORIGIN)
+    );
+    assert_eq!(preflight(&app, UI_ORIGIN).await.as_deref(), Some(UI_ORIGIN));
+}
+
+#[tokio::test]
+async fn test_reload_rejects_invalid_origin() {
+    let app = create_test_app().await;
+
+    let (status, _) = reload_cors(
+        &app,
+        json!({ "origins": [NEW_ORIGIN, "copier.example.com/path"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The previous set stays in effect
+    assert_eq!(preflight(&app, NEW_ORIGIN).await, None);
+    assert_eq!(preflight(&app, UI_ORIGIN).await.as_deref(), Some(UI_ORIGIN));
+}
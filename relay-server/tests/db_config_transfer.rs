@@ -0,0 +1,99 @@
+// relay-server/tests/db_config_transfer.rs
+//
+// Tests for config export/import database operations.
+
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::domain::models::{ConfigExport, MasterSettings, SlaveSettings};
+
+/// Helper to create an in-memory test database
+async fn create_test_db() -> Database {
+    Database::new("sqlite::memory:").await.unwrap()
+}
+
+/// Seed two TradeGroups with members and non-default settings
+async fn seed(db: &Database) {
+    db.create_trade_group("MASTER_A").await.unwrap();
+    db.update_master_settings(
+        "MASTER_A",
+        MasterSettings {
+            symbol_prefix: Some("pre.".to_string()),
+            config_version: 3,
+            ..MasterSettings::default()
+        },
+    )
+    .await
+    .unwrap();
+    db.add_member(
+        "MASTER_A",
+        "SLAVE_1",
+        SlaveSettings {
+            lot_multiplier: Some(2.5),
+            ..SlaveSettings::default()
+        },
+        2,
+    )
+    .await
+    .unwrap();
+    db.add_member("MASTER_A", "SLAVE_2", SlaveSettings::default(), 0)
+        .await
+        .unwrap();
+
+    db.create_trade_group("MASTER_B").await.unwrap();
+}
+
+/// Export without the timestamp, for equality checks
+fn comparable(export: &ConfigExport) -> serde_json::Value {
+    serde_json::to_value(&export.trade_groups).unwrap()
+}
+
+#[tokio::test]
+async fn test_export_import_round_trip() {
+    let source = create_test_db().await;
+    seed(&source).await;
+
+    let export = source.export_config().await.unwrap();
+    assert_eq!(export.trade_groups.len(), 2);
+    assert_eq!(export.trade_groups[0].id, "MASTER_A");
+    assert_eq!(export.trade_groups[0].members.len(), 2);
+    assert!(export.trade_groups[0].members[0].enabled_flag);
+    assert!(!export.trade_groups[0].members[1].enabled_flag);
+
+    let target = create_test_db().await;
+    let result = target.import_config(&export, false, false).await.unwrap();
+    assert_eq!(result.created, vec!["MASTER_A", "MASTER_B"]);
+    assert!(result.conflicts.is_empty());
+
+    let reimported = target.export_config().await.unwrap();
+    assert_eq!(comparable(&reimported), comparable(&export));
+}
+
+#[tokio::test]
+async fn test_import_conflict_and_overwrite() {
+    let db = create_test_db().await;
+    seed(&db).await;
+    let mut export = db.export_config().await.unwrap();
+
+    // Modify the document: drop SLAVE_2 and change MASTER_A settings
+    export.trade_groups[0].members.truncate(1);
+    export.trade_groups[0].master_settings.symbol_suffix = Some(".m".to_string());
+
+    // Without overwrite: conflicts reported and nothing written
+    let result = db.import_config(&export, false, false).await.unwrap();
+    assert_eq!(result.conflicts, vec!["MASTER_A", "MASTER_B"]);
+    assert_eq!(db.get_members("MASTER_A").await.unwrap().len(), 2);
+
+    // Dry run with overwrite: outcome reported but nothing written
+    let result = db.import_config(&export, true, true).await.unwrap();
+    assert!(result.dry_run);
+    assert_eq!(result.updated, vec!["MASTER_A", "MASTER_B"]);
+    assert_eq!(db.get_members("MASTER_A").await.unwrap().len(), 2);
+
+    // Overwrite: settings and member list replaced
+    let result = db.import_config(&export, true, false).await.unwrap();
+    assert_eq!(result.updated.len(), 2);
+    let members = db.get_members("MASTER_A").await.unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].slave_account, "SLAVE_1");
+    let group = db.get_trade_group("MASTER_A").await.unwrap().unwrap();
+    assert_eq!(group.master_settings.symbol_suffix.as_deref(), Some(".m"));
+}
@@ -4,12 +4,17 @@
 // Validates that the new tables and indexes are created correctly.
 
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sqlx::sqlite::SqlitePool;
 
 /// Helper to create an in-memory test database
 async fn create_test_db() -> Database {
     Database::new("sqlite::memory:").await.unwrap()
 }
 
+fn pool(db: &Database) -> &SqlitePool {
+    db.sqlite_pool().expect("test database is SQLite")
+}
+
 #[tokio::test]
 async fn test_migration_creates_trade_groups_table() {
     let db = create_test_db().await;
@@ -17,7 +22,7 @@ async fn test_migration_creates_trade_groups_table() {
     // Query sqlite_master to check if trade_groups table exists
     let result =
         sqlx::query("SELECT name FROM sqlite_master WHERE type='table' AND name='trade_groups'")
-            .fetch_optional(db.pool())
+            .fetch_optional(pool(&db))
             .await
             .unwrap();
 
@@ -32,7 +37,7 @@ async fn test_migration_creates_trade_group_members_table() {
     let result = sqlx::query(
         "SELECT name FROM sqlite_master WHERE type='table' AND name='trade_group_members'",
     )
-    .fetch_optional(db.pool())
+    .fetch_optional(pool(&db))
     .await
     .unwrap();
 
@@ -47,7 +52,7 @@ async fn test_migration_creates_indexes() {
     let result1 = sqlx::query(
         "SELECT name FROM sqlite_master WHERE type='index' AND name='idx_trade_group_members_slave'"
     )
-    .fetch_optional(db.pool())
+    .fetch_optional(pool(&db))
     .await
     .unwrap();
 
@@ -60,7 +65,7 @@ async fn test_migration_creates_indexes() {
     let result2 = sqlx::query(
         "SELECT name FROM sqlite_master WHERE type='index' AND name='idx_trade_group_members_status'"
     )
-    .fetch_optional(db.pool())
+    .fetch_optional(pool(&db))
     .await
     .unwrap();
 
@@ -77,7 +82,7 @@ async fn test_migration_drops_old_connections_table() {
     // Query sqlite_master to check if connections table does NOT exist
     let result =
         sqlx::query("SELECT name FROM sqlite_master WHERE type='table' AND name='connections'")
-            .fetch_optional(db.pool())
+            .fetch_optional(pool(&db))
             .await
             .unwrap();
 
@@ -94,7 +99,7 @@ async fn test_trade_groups_table_schema() {
     // Validate table schema by attempting to query with expected columns
     let result =
         sqlx::query("SELECT id, master_settings, created_at, updated_at FROM trade_groups LIMIT 0")
-            .fetch_optional(db.pool())
+            .fetch_optional(pool(&db))
             .await;
 
     assert!(
@@ -112,7 +117,7 @@ async fn test_trade_group_members_table_schema() {
         "SELECT trade_group_id, slave_account, slave_settings, status, created_at, updated_at
          FROM trade_group_members LIMIT 0",
     )
-    .fetch_optional(db.pool())
+    .fetch_optional(pool(&db))
     .await;
 
     assert!(
@@ -127,7 +132,7 @@ async fn test_foreign_key_constraint() {
 
     // Enable foreign key enforcement (disabled by default in SQLite)
     sqlx::query("PRAGMA foreign_keys = ON")
-        .execute(db.pool())
+        .execute(pool(&db))
         .await
         .unwrap();
 
@@ -136,7 +141,7 @@ async fn test_foreign_key_constraint() {
         "INSERT INTO trade_group_members (trade_group_id, slave_account, slave_settings, status)
          VALUES ('NONEXISTENT_MASTER', 'SLAVE_001', '{}', 1)",
     )
-    .execute(db.pool())
+    .execute(pool(&db))
     .await;
 
     // Should fail due to foreign key constraint
@@ -152,13 +157,13 @@ async fn test_cascade_delete() {
 
     // Enable foreign key enforcement
     sqlx::query("PRAGMA foreign_keys = ON")
-        .execute(db.pool())
+        .execute(pool(&db))
         .await
         .unwrap();
 
     // Create a trade_group
     sqlx::query("INSERT INTO trade_groups (id, master_settings) VALUES ('MASTER_001', '{}')")
-        .execute(db.pool())
+        .execute(pool(&db))
         .await
         .unwrap();
 
@@ -167,20 +172,20 @@ async fn test_cascade_delete() {
         "INSERT INTO trade_group_members (trade_group_id, slave_account, slave_settings, status)
          VALUES ('MASTER_001', 'SLAVE_001', '{}', 1)",
     )
-    .execute(db.pool())
+    .execute(pool(&db))
     .await
     .unwrap();
 
     // Delete the trade_group
     sqlx::query("DELETE FROM trade_groups WHERE id = 'MASTER_001'")
-        .execute(db.pool())
+        .execute(pool(&db))
         .await
         .unwrap();
 
     // Check that the member was also deleted (cascade)
     let result =
         sqlx::query("SELECT * FROM trade_group_members WHERE trade_group_id = 'MASTER_001'")
-            .fetch_optional(db.pool())
+            .fetch_optional(pool(&db))
             .await
             .unwrap();
 
@@ -189,3 +194,69 @@ async fn test_cascade_delete() {
         "CASCADE DELETE should remove associated members"
     );
 }
+
+#[tokio::test]
+async fn test_fresh_database_reaches_latest_version() {
+    let db = create_test_db().await;
+
+    assert!(Database::latest_schema_version() >= 1);
+    assert_eq!(
+        db.schema_version().await.unwrap(),
+        Database::latest_schema_version()
+    );
+
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(pool(&db))
+        .await
+        .unwrap();
+    assert_eq!(recorded, Database::latest_schema_version());
+}
+
+#[tokio::test]
+async fn test_running_migrations_twice_is_idempotent() {
+    let db = create_test_db().await;
+
+    sqlx::query("INSERT INTO trade_groups (id, master_settings) VALUES ('MASTER_001', '{}')")
+        .execute(pool(&db))
+        .await
+        .unwrap();
+
+    // Everything was applied by Database::new, so nothing is pending
+    assert_eq!(db.run_migrations().await.unwrap(), 0);
+    assert_eq!(db.run_migrations().await.unwrap(), 0);
+    assert_eq!(
+        db.schema_version().await.unwrap(),
+        Database::latest_schema_version()
+    );
+
+    // Existing data survives a re-run
+    let groups: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trade_groups")
+        .fetch_one(pool(&db))
+        .await
+        .unwrap();
+    assert_eq!(groups, 1);
+}
+
+#[tokio::test]
+async fn test_reopening_file_database_keeps_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!(
+        "sqlite://{}?mode=rwc",
+        dir.path().join("migration.db").display()
+    );
+
+    let db = Database::new(&url).await.unwrap();
+    db.create_trade_group("MASTER_001").await.unwrap();
+    pool(&db).close().await;
+
+    let reopened = Database::new(&url).await.unwrap();
+    assert!(reopened
+        .get_trade_group("MASTER_001")
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        reopened.schema_version().await.unwrap(),
+        Database::latest_schema_version()
+    );
+}
@@ -5,7 +5,8 @@
 
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::domain::models::{
-    LotCalculationMode, MasterSettings, SlaveSettings, SyncMode,
+    CopyDirection, LotCalculationMode, MasterSettings, MemberListFilter, MergeStrategy,
+    RoundingMode, SlaveSettings, SyncMode,
 };
 use sankey_copier_zmq::{SymbolMapping, TradeFilters};
 
@@ -82,6 +83,12 @@ async fn test_update_master_settings() {
         symbol_prefix: Some("pro.".to_string()),
         symbol_suffix: Some(".m".to_string()),
         config_version: 1,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     db.update_master_settings("MASTER_001", new_settings.clone())
@@ -133,6 +140,7 @@ async fn test_add_member() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.5),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -140,6 +148,7 @@ async fn test_add_member() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -149,6 +158,34 @@ async fn test_add_member() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     let result = db.add_member("MASTER_001", "SLAVE_001", settings, 0).await;
@@ -256,6 +293,7 @@ async fn test_update_member_settings() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(2.0),
         reverse_trade: true,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -263,6 +301,7 @@ async fn test_update_member_settings() {
         config_version: 1,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -272,6 +311,34 @@ async fn test_update_member_settings() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     db.update_member_settings("MASTER_001", "SLAVE_001", new_settings)
@@ -359,6 +426,12 @@ async fn test_get_settings_for_master() {
         symbol_prefix: Some("pro.".to_string()),
         symbol_suffix: Some(".m".to_string()),
         config_version: 1,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     db.create_trade_group("MASTER_001").await.unwrap();
@@ -393,6 +466,7 @@ async fn test_get_settings_for_slave() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -400,6 +474,7 @@ async fn test_get_settings_for_slave() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -409,12 +484,41 @@ async fn test_get_settings_for_slave() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     let settings2 = SlaveSettings {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(2.0),
         reverse_trade: true,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -422,6 +526,7 @@ async fn test_get_settings_for_slave() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -431,6 +536,34 @@ async fn test_get_settings_for_slave() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     db.add_member("MASTER_001", "SLAVE_001", settings1, 0)
@@ -535,22 +668,26 @@ async fn test_member_with_symbol_mappings() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![
             SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             },
             SymbolMapping {
                 source_symbol: "GBPUSD".to_string(),
                 target_symbol: "GBPUSDm".to_string(),
+                priority: 0,
             },
         ],
         filters: TradeFilters::default(),
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -560,6 +697,34 @@ async fn test_member_with_symbol_mappings() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     db.add_member("MASTER_001", "SLAVE_001", settings, 0)
@@ -588,6 +753,7 @@ async fn test_member_with_filters() {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -596,10 +762,13 @@ async fn test_member_with_filters() {
             blocked_symbols: Some(vec!["USDJPY".to_string()]),
             allowed_magic_numbers: Some(vec![100, 200]),
             blocked_magic_numbers: Some(vec![999]),
+            allowed_comment_substrings: None,
+            blocked_comment_substrings: None,
         },
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -609,6 +778,34 @@ async fn test_member_with_filters() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     db.add_member("MASTER_001", "SLAVE_001", settings, 0)
@@ -629,3 +826,82 @@ async fn test_member_with_filters() {
         Some(vec![999])
     );
 }
+
+#[tokio::test]
+async fn test_get_members_page_filters_and_paginates_across_groups() {
+    let db = create_test_db().await;
+    for master in ["MASTER_001", "MASTER_002"] {
+        db.create_trade_group(master).await.unwrap();
+    }
+    for (master, slave, status) in [
+        ("MASTER_001", "SLAVE_001", 2),
+        ("MASTER_001", "SLAVE_002", 0),
+        ("MASTER_001", "SLAVE_003", 2),
+        ("MASTER_002", "SLAVE_001", 2),
+        ("MASTER_002", "SLAVE_004", 1),
+    ] {
+        db.add_member(master, slave, SlaveSettings::default(), 0)
+            .await
+            .unwrap();
+        set_member_status(&db, master, slave, status).await;
+    }
+    let keys = |members: &[sankey_copier_relay_server::domain::models::TradeGroupMember]| {
+        members
+            .iter()
+            .map(|m| format!("{}/{}", m.trade_group_id, m.slave_account))
+            .collect::<Vec<_>>()
+    };
+
+    // Status filter, two per page, resuming after the last key
+    let connected = MemberListFilter {
+        status: Some(2),
+        ..Default::default()
+    };
+    let (page, has_more) = db.get_members_page(&connected, None, 2).await.unwrap();
+    assert_eq!(
+        keys(&page),
+        vec!["MASTER_001/SLAVE_001", "MASTER_001/SLAVE_003"]
+    );
+    assert!(has_more);
+    let last = page.last().unwrap();
+    let (page, has_more) = db
+        .get_members_page(
+            &connected,
+            Some((&last.trade_group_id, &last.slave_account)),
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(keys(&page), vec!["MASTER_002/SLAVE_001"]);
+    assert!(!has_more);
+
+    // Connection filter
+    let online = |connected| MemberListFilter {
+        connected: Some(connected),
+        online_slaves: vec!["SLAVE_001".to_string(), "SLAVE_004".to_string()],
+        ..Default::default()
+    };
+    let (page, _) = db.get_members_page(&online(true), None, 10).await.unwrap();
+    assert_eq!(
+        keys(&page),
+        vec![
+            "MASTER_001/SLAVE_001",
+            "MASTER_002/SLAVE_001",
+            "MASTER_002/SLAVE_004"
+        ]
+    );
+    let (page, _) = db.get_members_page(&online(false), None, 10).await.unwrap();
+    assert_eq!(
+        keys(&page),
+        vec!["MASTER_001/SLAVE_002", "MASTER_001/SLAVE_003"]
+    );
+
+    // Nobody online: connected=true matches nothing
+    let nobody = MemberListFilter {
+        connected: Some(true),
+        ..Default::default()
+    };
+    let (page, has_more) = db.get_members_page(&nobody, None, 10).await.unwrap();
+    assert!(page.is_empty());
+    assert!(!has_more);
+}
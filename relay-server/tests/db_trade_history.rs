@@ -0,0 +1,116 @@
+// relay-server/tests/db_trade_history.rs
+//
+// Tests for trade history database operations.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::domain::models::{
+    TradeAction, TradeHistoryEntry, TradeHistoryQuery,
+};
+
+/// Helper to create an in-memory test database
+async fn create_test_db() -> Database {
+    Database::new("sqlite::memory:").await.unwrap()
+}
+
+fn base_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 15, 10, 0, 0).unwrap()
+}
+
+fn entry(master: &str, slave: &str, ticket: i64, minutes: i64) -> TradeHistoryEntry {
+    TradeHistoryEntry {
+        id: 0,
+        master_account: master.to_string(),
+        slave_account: slave.to_string(),
+        ticket,
+        action: TradeAction::Open,
+        symbol: Some("EURUSD".to_string()),
+        lots: Some(0.1),
+        timestamp: base_time() + Duration::minutes(minutes),
+        copied: true,
+        filter_reason: None,
+    }
+}
+
+#[tokio::test]
+async fn test_insert_and_query_trade_history() {
+    let db = create_test_db().await;
+
+    let mut skipped = entry("MASTER_001", "SLAVE_001", 1001, 0);
+    skipped.action = TradeAction::Close;
+    skipped.copied = false;
+    skipped.filter_reason = Some("lot_below_minimum".to_string());
+    let id = db.insert_trade_history(&skipped).await.unwrap();
+    assert!(id > 0);
+
+    let page = db
+        .query_trade_history(&TradeHistoryQuery::default())
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.limit, 100);
+    assert_eq!(page.offset, 0);
+
+    let stored = &page.items[0];
+    assert_eq!(stored.id, id);
+    assert_eq!(stored.action, TradeAction::Close);
+    assert_eq!(stored.timestamp, base_time());
+    assert!(!stored.copied);
+    assert_eq!(stored.filter_reason.as_deref(), Some("lot_below_minimum"));
+}
+
+#[tokio::test]
+async fn test_query_trade_history_filters() {
+    let db = create_test_db().await;
+
+    db.insert_trade_history(&entry("MASTER_001", "SLAVE_001", 1, 0))
+        .await
+        .unwrap();
+    db.insert_trade_history(&entry("MASTER_001", "SLAVE_002", 2, 10))
+        .await
+        .unwrap();
+    db.insert_trade_history(&entry("MASTER_001", "SLAVE_001", 3, 20))
+        .await
+        .unwrap();
+    db.insert_trade_history(&entry("MASTER_002", "SLAVE_001", 4, 30))
+        .await
+        .unwrap();
+
+    // Master + slave filter, newest first
+    let page = db
+        .query_trade_history(&TradeHistoryQuery {
+            master: Some("MASTER_001".to_string()),
+            slave: Some("SLAVE_001".to_string()),
+            ..TradeHistoryQuery::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 2);
+    let tickets: Vec<i64> = page.items.iter().map(|e| e.ticket).collect();
+    assert_eq!(tickets, vec![3, 1]);
+
+    // Inclusive time range
+    let page = db
+        .query_trade_history(&TradeHistoryQuery {
+            from: Some(base_time() + Duration::minutes(10)),
+            to: Some(base_time() + Duration::minutes(20)),
+            ..TradeHistoryQuery::default()
+        })
+        .await
+        .unwrap();
+    let tickets: Vec<i64> = page.items.iter().map(|e| e.ticket).collect();
+    assert_eq!(tickets, vec![3, 2]);
+
+    // Pagination keeps the total of all matching rows
+    let page = db
+        .query_trade_history(&TradeHistoryQuery {
+            limit: Some(2),
+            offset: Some(1),
+            ..TradeHistoryQuery::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 4);
+    let tickets: Vec<i64> = page.items.iter().map(|e| e.ticket).collect();
+    assert_eq!(tickets, vec![3, 2]);
+}
@@ -0,0 +1,107 @@
+// relay-server/tests/health_api_test.rs
+//
+// Tests for the liveness (/health) and readiness (/ready) probes.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::health_router;
+use sankey_copier_relay_server::adapters::infrastructure::readiness::Readiness;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+
+use std::sync::Arc;
+
+async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_health_is_always_ok() {
+    let app = health_router(Arc::new(Readiness::default()));
+
+    let (status, body) = get_json(app, "/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_ready_waits_for_zmq_and_database() {
+    let readiness = Arc::new(Readiness::default());
+    let app = health_router(readiness.clone());
+
+    // Nothing is up yet
+    let (status, body) = get_json(app.clone(), "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["ready"], false);
+
+    // ZMQ bound but database not initialized
+    readiness.mark_zmq_receiver_bound();
+    let (status, body) = get_json(app.clone(), "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["zmq_receiver"], true);
+    assert_eq!(body["database"], false);
+
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    readiness.set_database(db);
+    let (status, body) = get_json(app, "/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["ready"], true);
+    assert_eq!(body["database"], true);
+}
+
+#[tokio::test]
+async fn test_degraded_database_recovers_when_url_becomes_available() {
+    use sankey_copier_relay_server::bootstrap::database;
+    use sankey_copier_relay_server::config::Config;
+    use std::time::Duration;
+
+    // SQLite cannot create the file until its directory exists
+    let dir = tempfile::tempdir().unwrap();
+    let db_dir = dir.path().join("later");
+    let url = format!("sqlite://{}?mode=rwc", db_dir.join("relay.db").display());
+    let mut config = Config::default().database;
+    config.connect_attempts = 2;
+    config.connect_backoff_ms = 10;
+    config.reconnect_interval_ms = 20;
+
+    let readiness = Arc::new(Readiness::default());
+    readiness.mark_zmq_receiver_bound();
+    let app = health_router(readiness.clone());
+    let (db, recovery) = database::open(&url, &config, readiness.clone())
+        .await
+        .unwrap();
+    let recovery = recovery.expect("startup should fall back to degraded mode");
+
+    let (status, body) = get_json(app.clone(), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "degraded");
+    let (status, body) = get_json(app.clone(), "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["database"], false);
+    assert!(db.get_global_settings().await.is_err());
+
+    std::fs::create_dir(&db_dir).unwrap();
+    tokio::time::timeout(Duration::from_secs(5), recovery)
+        .await
+        .expect("recovery should finish once the database is reachable")
+        .unwrap();
+
+    let (_, body) = get_json(app.clone(), "/health").await;
+    assert_eq!(body["status"], "ok");
+    let (status, body) = get_json(app, "/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["database"], true);
+    assert!(db.get_global_settings().await.is_ok());
+}
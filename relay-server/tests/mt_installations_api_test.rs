@@ -6,23 +6,39 @@ use serde_json::Value;
 use tower::util::ServiceExt;
 
 use sankey_copier_relay_server::adapters::inbound::http::create_router;
-use sankey_copier_relay_server::adapters::inbound::http::{AppState, SnapshotBroadcaster};
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
 use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
-use sankey_copier_relay_server::adapters::infrastructure::log_buffer::create_log_buffer;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::mt_catalog::{
+    InstallationDetector, MtInstallationCatalog,
+};
 use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
 use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::domain::models::{
+    Architecture, InstalledComponents, MtInstallation, MtType, ServerEvent,
+};
 
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
 /// Helper function to create a test app
 async fn create_test_app() -> axum::Router {
+    create_test_app_with_catalog(MtInstallationCatalog::default()).await
+}
+
+/// Test app listing installations from `catalog`
+async fn create_test_app_with_catalog(catalog: MtInstallationCatalog) -> axum::Router {
     let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
     let connection_manager = Arc::new(ConnectionManager::new(30));
-    let (broadcast_tx, _) = broadcast::channel::<String>(100);
-    let log_buffer = create_log_buffer();
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
 
     // Create a dummy ZMQ config sender
     let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
@@ -46,13 +62,15 @@ async fn create_test_app() -> axum::Router {
         connection_manager,
         config_sender,
         log_buffer,
-        allowed_origins: vec!["http://localhost:8080".to_string()],
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
         cors_disabled: false, // Use strict CORS for tests
         config: Arc::new(sankey_copier_relay_server::config::Config::default()),
         resolved_ports,
         vlogs_controller: None,
         runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
         snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Arc::new(catalog),
     };
 
     create_router(app_state)
@@ -159,3 +177,77 @@ async fn test_mt_installations_response_structure() {
         assert!(platform == "32-bit" || platform == "64-bit");
     }
 }
+
+fn installation(name: &str, path: &str) -> MtInstallation {
+    MtInstallation {
+        id: MtInstallation::generate_id(&MtType::MT4, path),
+        name: name.to_string(),
+        mt_type: MtType::MT4,
+        platform: Architecture::Bit32,
+        path: path.to_string(),
+        executable: format!("{}\\terminal.exe", path),
+        version: None,
+        components: InstalledComponents::default(),
+        port_config: None,
+        port_mismatch: None,
+    }
+}
+
+/// Detects two registry installations; any manual path is a portable terminal
+struct MockDetector;
+
+impl InstallationDetector for MockDetector {
+    fn detect(&self) -> anyhow::Result<Vec<MtInstallation>> {
+        Ok(vec![
+            installation("Broker A MT4", "C:\\Broker A"),
+            installation("Broker B MT4", "C:\\Broker B"),
+        ])
+    }
+
+    fn inspect(&self, install_path: &Path) -> Option<MtInstallation> {
+        Some(installation(
+            "Portable MT4",
+            &install_path.to_string_lossy(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_rescan_merges_manual_installations() {
+    let catalog =
+        MtInstallationCatalog::new(Arc::new(MockDetector), vec!["D:\\Portable MT4".to_string()]);
+    let app = create_test_app_with_catalog(catalog).await;
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/mt-installations/rescan")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let names: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|installation| installation["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Broker A MT4", "Broker B MT4", "Portable MT4"]);
+    assert_eq!(json["detection_summary"]["total_found"], 3);
+
+    // The listing serves the rescanned entries
+    let request = Request::builder()
+        .uri("/api/mt-installations")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 3);
+}
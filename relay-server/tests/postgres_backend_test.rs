@@ -0,0 +1,234 @@
+// relay-server/tests/postgres_backend_test.rs
+//
+// Integration tests for the PostgreSQL backend.
+// Only built with `--features postgres`; each test is skipped unless
+// TEST_POSTGRES_URL points at a database the tests may create schemas in, e.g.
+//   TEST_POSTGRES_URL=postgres://postgres@127.0.0.1/postgres \
+//     cargo test -p sankey-copier-relay-server --features postgres --test postgres_backend_test
+#![cfg(feature = "postgres")]
+
+use chrono::{TimeZone, Utc};
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::domain::models::{
+    ConfigExport, MasterSettings, SlaveSettings, TradeAction, TradeHistoryEntry, TradeHistoryQuery,
+    WarningCode,
+};
+use sqlx::postgres::PgPool;
+
+/// A Database in a fresh schema, dropped again by `cleanup`
+struct TestDb {
+    db: Database,
+    admin: PgPool,
+    schema: String,
+}
+
+impl TestDb {
+    async fn cleanup(self) {
+        if let Some(pool) = self.db.postgres_pool() {
+            pool.close().await;
+        }
+        sqlx::query(&format!("DROP SCHEMA {} CASCADE", self.schema))
+            .execute(&self.admin)
+            .await
+            .unwrap();
+    }
+}
+
+async fn create_test_db() -> Option<TestDb> {
+    let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+        eprintln!("TEST_POSTGRES_URL not set, skipping PostgreSQL test");
+        return None;
+    };
+
+    let admin = PgPool::connect(&url).await.unwrap();
+    let schema = format!("sankey_test_{}", uuid::Uuid::new_v4().simple());
+    sqlx::query(&format!("CREATE SCHEMA {}", schema))
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let schema_url = format!("{}{}options=-c%20search_path%3D{}", url, separator, schema);
+    let db = Database::new(&schema_url).await.unwrap();
+
+    Some(TestDb { db, admin, schema })
+}
+
+fn history_entry(slave: &str, ticket: i64, copied: bool) -> TradeHistoryEntry {
+    TradeHistoryEntry {
+        id: 0,
+        master_account: "MASTER_001".to_string(),
+        slave_account: slave.to_string(),
+        ticket,
+        action: TradeAction::Open,
+        symbol: Some("EURUSD".to_string()),
+        lots: Some(0.25),
+        timestamp: Utc
+            .with_ymd_and_hms(2025, 1, 1, 0, 0, ticket as u32)
+            .unwrap(),
+        copied,
+        filter_reason: (!copied).then(|| "symbol_blocked".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_postgres_migrations_are_idempotent() {
+    let Some(test) = create_test_db().await else {
+        return;
+    };
+    let db = &test.db;
+
+    assert!(db.postgres_pool().is_some());
+    assert!(db.sqlite_pool().is_none());
+    db.ping().await.unwrap();
+    assert_eq!(
+        db.schema_version().await.unwrap(),
+        Database::latest_schema_version()
+    );
+    assert_eq!(db.run_migrations().await.unwrap(), 0);
+
+    test.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_postgres_trade_group_and_member_round_trip() {
+    let Some(test) = create_test_db().await else {
+        return;
+    };
+    let db = &test.db;
+
+    db.create_trade_group("MASTER_001").await.unwrap();
+    db.update_master_settings(
+        "MASTER_001",
+        MasterSettings {
+            enabled: true,
+            symbol_suffix: Some(".m".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let group = db.get_trade_group("MASTER_001").await.unwrap().unwrap();
+    assert!(group.master_settings.enabled);
+    assert_eq!(group.master_settings.symbol_suffix.as_deref(), Some(".m"));
+    assert_eq!(db.list_trade_groups().await.unwrap().len(), 1);
+
+    let settings = SlaveSettings {
+        lot_multiplier: Some(2.0),
+        ..Default::default()
+    };
+    db.add_member("MASTER_001", "SLAVE_001", settings.clone(), 2)
+        .await
+        .unwrap();
+    db.add_member("MASTER_001", "SLAVE_002", settings, 0)
+        .await
+        .unwrap();
+
+    let members = db.get_members("MASTER_001").await.unwrap();
+    assert_eq!(members.len(), 2);
+    assert!(members[0].enabled_flag);
+    assert_eq!(members[0].status, 2);
+    assert!(!members[1].enabled_flag);
+
+    db.update_member_enabled_flag("MASTER_001", "SLAVE_002", true)
+        .await
+        .unwrap();
+    db.update_member_runtime_status("MASTER_001", "SLAVE_002", 1)
+        .await
+        .unwrap();
+    db.update_member_acknowledged_warnings(
+        "MASTER_001",
+        "SLAVE_002",
+        &[WarningCode::MasterOffline],
+    )
+    .await
+    .unwrap();
+    let member = db
+        .get_member("MASTER_001", "SLAVE_002")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(member.enabled_flag);
+    assert_eq!(member.status, 1);
+    assert_eq!(
+        member.acknowledged_warnings,
+        vec![WarningCode::MasterOffline]
+    );
+    assert!(db
+        .update_member_runtime_status("MASTER_001", "MISSING", 1)
+        .await
+        .is_err());
+
+    let configs = db.get_settings_for_slave("SLAVE_001").await.unwrap();
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs[0].slave_settings.lot_multiplier, Some(2.0));
+    assert_eq!(
+        db.get_masters_for_slave("SLAVE_001").await.unwrap(),
+        vec!["MASTER_001".to_string()]
+    );
+    assert_eq!(
+        db.update_master_statuses_enabled("MASTER_001")
+            .await
+            .unwrap(),
+        1
+    );
+
+    // Foreign keys are always enforced on PostgreSQL
+    db.delete_member("MASTER_001", "SLAVE_001").await.unwrap();
+    db.delete_trade_group("MASTER_001").await.unwrap();
+    assert!(db.get_all_members().await.unwrap().is_empty());
+
+    test.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_postgres_trade_history_and_config_transfer() {
+    let Some(test) = create_test_db().await else {
+        return;
+    };
+    let db = &test.db;
+
+    let first = db
+        .insert_trade_history(&history_entry("SLAVE_001", 1, true))
+        .await
+        .unwrap();
+    let second = db
+        .insert_trade_history(&history_entry("SLAVE_002", 2, false))
+        .await
+        .unwrap();
+    assert!(second > first);
+
+    let page = db
+        .query_trade_history(&TradeHistoryQuery {
+            slave: Some("SLAVE_002".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id, second);
+    assert!(!page.items[0].copied);
+    assert_eq!(page.items[0].lots, Some(0.25));
+    assert_eq!(
+        page.items[0].filter_reason.as_deref(),
+        Some("symbol_blocked")
+    );
+
+    db.create_trade_group("MASTER_001").await.unwrap();
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 2)
+        .await
+        .unwrap();
+    let export: ConfigExport = db.export_config().await.unwrap();
+
+    let conflict = db.import_config(&export, false, false).await.unwrap();
+    assert_eq!(conflict.conflicts, vec!["MASTER_001".to_string()]);
+    let overwritten = db.import_config(&export, true, false).await.unwrap();
+    assert_eq!(overwritten.updated, vec!["MASTER_001".to_string()]);
+    assert_eq!(db.get_members("MASTER_001").await.unwrap().len(), 1);
+
+    // VACUUM INTO is SQLite-only
+    let dir = tempfile::tempdir().unwrap();
+    assert!(db.backup_to(&dir.path().join("backup.db")).await.is_err());
+
+    test.cleanup().await;
+}
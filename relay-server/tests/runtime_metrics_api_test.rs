@@ -5,16 +5,19 @@ use http_body_util::BodyExt;
 use tower::ServiceExt;
 
 use sankey_copier_relay_server::adapters::inbound::http::{
-    get_runtime_metrics, AppState, SnapshotBroadcaster,
+    get_runtime_metrics, AppState, CorsOrigins, SnapshotBroadcaster,
 };
 use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
-use sankey_copier_relay_server::adapters::infrastructure::log_buffer::create_log_buffer;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
 use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
 use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::application::runtime_status_updater::{
     RuntimeStatusMetrics, RuntimeStatusMetricsSnapshot,
 };
+use sankey_copier_relay_server::domain::models::ServerEvent;
 
 use std::sync::Arc;
 use tokio::sync::broadcast;
@@ -23,8 +26,8 @@ use tokio::sync::broadcast;
 async fn create_test_app_state() -> AppState {
     let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
     let connection_manager = Arc::new(ConnectionManager::new(30));
-    let (broadcast_tx, _) = broadcast::channel::<String>(100);
-    let log_buffer = create_log_buffer();
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
 
     // Create a dummy ZMQ config sender with ephemeral port
     let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
@@ -48,13 +51,15 @@ async fn create_test_app_state() -> AppState {
         connection_manager,
         config_sender,
         log_buffer,
-        allowed_origins: vec![],
+        allowed_origins: CorsOrigins::default(),
         cors_disabled: true,
         config: Arc::new(sankey_copier_relay_server::config::Config::default()),
         resolved_ports,
         vlogs_controller: None,
         runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
         snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
     }
 }
 
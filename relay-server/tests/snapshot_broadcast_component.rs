@@ -11,7 +11,7 @@ use sankey_copier_relay_server::adapters::inbound::http::SnapshotBroadcaster;
 use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::domain::models::{
-    EaConnection, HeartbeatMessage, SystemStateSnapshot,
+    EaConnection, HeartbeatMessage, ServerEvent, SystemStateSnapshot,
 };
 
 /// Create a test HeartbeatMessage with configurable parameters
@@ -27,6 +27,9 @@ fn create_heartbeat(
         account_id: account_id.to_string(),
         balance,
         equity,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
         open_positions: 0,
         timestamp: chrono::Utc::now().to_rfc3339(),
         version: "1.0.0".to_string(),
@@ -45,18 +48,18 @@ fn create_heartbeat(
     }
 }
 
-/// Parse connections from a system snapshot message
-fn parse_snapshot(message: &str) -> Vec<EaConnection> {
-    let json_part = message
-        .strip_prefix("system_snapshot:")
-        .expect("Message should start with system_snapshot:");
-    let snapshot: SystemStateSnapshot =
-        serde_json::from_str(json_part).expect("Should parse as SystemStateSnapshot");
+/// Parse connections from a system snapshot event as sent to WebSocket clients
+fn parse_snapshot(event: &ServerEvent) -> Vec<EaConnection> {
+    let mut message: serde_json::Value =
+        serde_json::from_str(&event.to_json()).expect("Event should serialize as JSON");
+    assert_eq!(message["type"], "system_snapshot");
+    let snapshot: SystemStateSnapshot = serde_json::from_value(message["data"].take())
+        .expect("Should parse as SystemStateSnapshot");
     snapshot.connections
 }
 
 /// Wait for the next snapshot and return it
-async fn wait_for_snapshot(rx: &mut broadcast::Receiver<String>) -> Vec<EaConnection> {
+async fn wait_for_snapshot(rx: &mut broadcast::Receiver<ServerEvent>) -> Vec<EaConnection> {
     let result = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await;
     let message = result
         .expect("Should receive snapshot within timeout")
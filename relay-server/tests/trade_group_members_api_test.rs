@@ -8,31 +8,54 @@ use tower::ServiceExt;
 
 use sankey_copier_relay_server::adapters::inbound::http::{
     create_router,
-    dtos::{AddMemberRequest, ToggleStatusRequest},
-    AppState, SnapshotBroadcaster,
+    dtos::{AddMemberRequest, MembersPage, ToggleStatusRequest},
+    AppState, CorsOrigins, SnapshotBroadcaster,
 };
 use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
-use sankey_copier_relay_server::adapters::infrastructure::log_buffer::create_log_buffer;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
 use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
 use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
 use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
 use sankey_copier_relay_server::domain::models::{
-    LotCalculationMode, MasterSettings, SlaveSettings, SymbolMapping, TradeFilters,
+    EaType, GlobalSettings, HeartbeatMessage, LotCalculationMode, MasterSettings, PositionInfo,
+    ServerEvent, SlaveSettings, SymbolMapping, TradeFilters,
 };
 
+use sankey_copier_relay_server::ports::ConfigPublisher;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
 /// Helper function to create a test app with in-memory database
 async fn create_test_app() -> (axum::Router, Arc<Database>) {
-    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
-    let connection_manager = Arc::new(ConnectionManager::new(30));
-    let (broadcast_tx, _) = broadcast::channel::<String>(100);
-    let log_buffer = create_log_buffer();
+    let (app, db, _) = create_test_app_with_connections().await;
+    (app, db)
+}
 
+/// Same as `create_test_app`, also returning the shared connection manager
+async fn create_test_app_with_connections() -> (axum::Router, Arc<Database>, Arc<ConnectionManager>)
+{
     // Create a dummy ZMQ config sender with ephemeral port
     let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+    let (app, db, connection_manager, _) = create_test_app_with_publisher(config_sender).await;
+    (app, db, connection_manager)
+}
+
+/// Test app using the given config publisher, also returning the WebSocket event channel
+async fn create_test_app_with_publisher(
+    config_sender: Arc<dyn ConfigPublisher>,
+) -> (
+    axum::Router,
+    Arc<Database>,
+    Arc<ConnectionManager>,
+    broadcast::Sender<ServerEvent>,
+) {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
 
     // 2-port architecture
     let resolved_ports = Arc::new(ResolvedPorts {
@@ -49,20 +72,27 @@ async fn create_test_app() -> (axum::Router, Arc<Database>) {
 
     let app_state = AppState {
         db: db.clone(),
-        tx: broadcast_tx,
-        connection_manager,
+        tx: broadcast_tx.clone(),
+        connection_manager: connection_manager.clone(),
         config_sender,
         log_buffer,
-        allowed_origins: vec![],
+        allowed_origins: CorsOrigins::default(),
         cors_disabled: true,
         config: Arc::new(sankey_copier_relay_server::config::Config::default()),
         resolved_ports,
         vlogs_controller: None,
         runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
         snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
     };
 
-    (create_router(app_state), db)
+    (
+        create_router(app_state),
+        db,
+        connection_manager,
+        broadcast_tx,
+    )
 }
 
 /// Helper function to create a test TradeGroup (Master)
@@ -76,6 +106,12 @@ async fn setup_test_trade_group(db: &Arc<Database>, master_account: &str) {
         symbol_prefix: Some("pro.".to_string()),
         symbol_suffix: Some(".m".to_string()),
         config_version: 1,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     db.update_master_settings(master_account, settings)
@@ -120,21 +156,26 @@ async fn test_add_member_success() {
             lot_calculation_mode: LotCalculationMode::default(),
             lot_multiplier: Some(1.5),
             reverse_trade: false,
+            reverse_symbols: None,
             symbol_prefix: None,
             symbol_suffix: None,
             symbol_mappings: vec![SymbolMapping {
                 source_symbol: "EURUSD".to_string(),
                 target_symbol: "EURUSDm".to_string(),
+                priority: 0,
             }],
             filters: TradeFilters {
                 allowed_symbols: Some(vec!["EURUSD".to_string()]),
                 blocked_symbols: None,
                 allowed_magic_numbers: None,
                 blocked_magic_numbers: None,
+                allowed_comment_substrings: None,
+                blocked_comment_substrings: None,
             },
             config_version: 0,
             source_lot_min: None,
             source_lot_max: None,
+            source_lot_bounds_inclusive: true,
             sync_mode: sankey_copier_relay_server::domain::models::SyncMode::Skip,
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -143,6 +184,34 @@ async fn test_add_member_success() {
             max_retries: 3,
             max_signal_delay_ms: 5000,
             use_pending_order_for_delayed: false,
+            fixed_lot: None,
+            risk_percent: None,
+            lot_max: None,
+            lot_min: None,
+            lot_step: None,
+            max_open_positions: None,
+            max_daily_loss: None,
+            max_spread_points: None,
+            trading_hours: None,
+            active_schedule: None,
+            comment_template: None,
+            copy_sample_rate: None,
+            timeout_action: None,
+            portfolio_weight: None,
+            sl_tp_as_offset: false,
+            partial_close_by_lots: false,
+            convert_pending_to_market: false,
+            always_allow_close: true,
+            broker_profile: None,
+            lot_decimals: 2,
+            lot_rounding: sankey_copier_relay_server::domain::models::RoundingMode::Nearest,
+            merge_strategy: sankey_copier_relay_server::domain::models::MergeStrategy::CopyAll,
+            copy_delay_ms: None,
+            copy_delay_skip_closes: false,
+            disable_symbol_conversion: false,
+            default_magic: None,
+            magic_number_map: std::collections::HashMap::new(),
+            copy_direction: sankey_copier_relay_server::domain::models::CopyDirection::Both,
         },
 
         enabled: false,
@@ -295,7 +364,13 @@ async fn test_update_member_success() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"]["changed"], true);
+    assert_eq!(json["data"]["config_version"], 1);
 
     // Verify the update
     let member = db
@@ -342,7 +417,7 @@ async fn test_toggle_member_status_disable() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.status(), StatusCode::OK);
 
     // Verify status changed
     let member = db
@@ -388,7 +463,7 @@ async fn test_toggle_member_status_enable() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.status(), StatusCode::OK);
 
     // Verify status changed
     let member = db
@@ -470,6 +545,77 @@ async fn test_list_members_with_multiple_members() {
     assert_eq!(members[2].slave_account, "SLAVE_003");
 }
 
+#[tokio::test]
+async fn test_list_all_members_filters_and_paginates() {
+    let (app, db, cm) = create_test_app_with_connections().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    setup_test_trade_group(&db, "MASTER_002").await;
+    for (master, slave) in [
+        ("MASTER_001", "SLAVE_001"),
+        ("MASTER_001", "SLAVE_002"),
+        ("MASTER_002", "SLAVE_003"),
+    ] {
+        db.add_member(master, slave, SlaveSettings::default(), 2)
+            .await
+            .unwrap();
+    }
+    db.update_member_runtime_status("MASTER_001", "SLAVE_002", 0)
+        .await
+        .unwrap();
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+    cm.update_heartbeat(heartbeat("SLAVE_003", "Slave")).await;
+
+    let get_page = |uri: String| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<MembersPage>(&body).unwrap()
+        }
+    };
+
+    // One member per page across both groups
+    let first = get_page("/api/members?connected=true&limit=1".to_string()).await;
+    assert_eq!(first.items.len(), 1);
+    assert_eq!(first.items[0].master_account, "MASTER_001");
+    assert_eq!(first.items[0].member.slave_account, "SLAVE_001");
+    assert!(first.items[0].connected);
+    let cursor = first.next_cursor.expect("more members");
+
+    let second = get_page(format!(
+        "/api/members?connected=true&limit=1&after={}",
+        cursor
+    ))
+    .await;
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items[0].master_account, "MASTER_002");
+    assert_eq!(second.items[0].member.slave_account, "SLAVE_003");
+    assert_eq!(second.next_cursor, None);
+
+    // Status filter
+    let disabled = get_page("/api/members?status=0".to_string()).await;
+    assert_eq!(disabled.items.len(), 1);
+    assert_eq!(disabled.items[0].member.slave_account, "SLAVE_002");
+    assert!(!disabled.items[0].connected);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/members?limit=0")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_member_with_complex_settings() {
     let (app, db) = create_test_app().await;
@@ -481,16 +627,19 @@ async fn test_member_with_complex_settings() {
             lot_calculation_mode: LotCalculationMode::default(),
             lot_multiplier: Some(2.5),
             reverse_trade: true,
+            reverse_symbols: None,
             symbol_prefix: Some("FX_".to_string()),
             symbol_suffix: Some(".m".to_string()),
             symbol_mappings: vec![
                 SymbolMapping {
                     source_symbol: "EURUSD".to_string(),
                     target_symbol: "EUR.USD".to_string(),
+                    priority: 0,
                 },
                 SymbolMapping {
                     source_symbol: "GBPUSD".to_string(),
                     target_symbol: "GBP.USD".to_string(),
+                    priority: 0,
                 },
             ],
             filters: TradeFilters {
@@ -498,10 +647,13 @@ async fn test_member_with_complex_settings() {
                 blocked_symbols: Some(vec!["USDJPY".to_string()]),
                 allowed_magic_numbers: Some(vec![12345, 67890]),
                 blocked_magic_numbers: Some(vec![99999]),
+                allowed_comment_substrings: None,
+                blocked_comment_substrings: None,
             },
             config_version: 0,
             source_lot_min: None,
             source_lot_max: None,
+            source_lot_bounds_inclusive: true,
             sync_mode: sankey_copier_relay_server::domain::models::SyncMode::Skip,
             limit_order_expiry_min: None,
             market_sync_max_pips: None,
@@ -510,6 +662,34 @@ async fn test_member_with_complex_settings() {
             max_retries: 3,
             max_signal_delay_ms: 5000,
             use_pending_order_for_delayed: false,
+            fixed_lot: None,
+            risk_percent: None,
+            lot_max: None,
+            lot_min: None,
+            lot_step: None,
+            max_open_positions: None,
+            max_daily_loss: None,
+            max_spread_points: None,
+            trading_hours: None,
+            active_schedule: None,
+            comment_template: None,
+            copy_sample_rate: None,
+            timeout_action: None,
+            portfolio_weight: None,
+            sl_tp_as_offset: false,
+            partial_close_by_lots: false,
+            convert_pending_to_market: false,
+            always_allow_close: true,
+            broker_profile: None,
+            lot_decimals: 2,
+            lot_rounding: sankey_copier_relay_server::domain::models::RoundingMode::Nearest,
+            merge_strategy: sankey_copier_relay_server::domain::models::MergeStrategy::CopyAll,
+            copy_delay_ms: None,
+            copy_delay_skip_closes: false,
+            disable_symbol_conversion: false,
+            default_magic: None,
+            magic_number_map: std::collections::HashMap::new(),
+            copy_direction: sankey_copier_relay_server::domain::models::CopyDirection::Both,
         },
 
         enabled: false,
@@ -681,3 +861,860 @@ async fn test_toggle_member_status_not_found() {
     // Should return 404 Not Found
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn test_add_member_rejects_fixed_lot_without_value() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    let request_body = serde_json::json!({
+        "slave_account": "SLAVE_001",
+        "slave_settings": {
+            "lot_calculation_mode": "fixed_lot",
+            "reverse_trade": false,
+            "symbol_mappings": [],
+            "filters": {},
+            "config_version": 0
+        },
+        "enabled": true
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/trade-groups/MASTER_001/members")
+                .header("content-type", "application/json")
+                .body(Body::from(request_body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_lot_multiplier_above_global_cap_is_rejected() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    db.update_global_settings(&GlobalSettings {
+        max_lot_multiplier: Some(3.0),
+        ..GlobalSettings::default()
+    })
+    .await
+    .unwrap();
+
+    let add_body = |multiplier: f64| {
+        serde_json::json!({
+            "slave_account": "SLAVE_001",
+            "slave_settings": {
+                "lot_multiplier": multiplier,
+                "reverse_trade": false,
+                "symbol_mappings": [],
+                "filters": {},
+                "config_version": 0
+            },
+            "enabled": true
+        })
+        .to_string()
+    };
+    let post = |body: String| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/trade-groups/MASTER_001/members")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(post(add_body(5.0))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .is_none());
+
+    // At the cap is allowed
+    let response = app.clone().oneshot(post(add_body(3.0))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let settings = SlaveSettings {
+        lot_multiplier: Some(3.5),
+        ..SlaveSettings::default()
+    };
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&settings).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let member = db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.slave_settings.lot_multiplier, Some(3.0));
+}
+
+#[tokio::test]
+async fn test_acknowledge_one_of_two_warnings() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    // Enabled member with neither EA connected: slave_offline + master_offline
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 2)
+        .await
+        .unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/warnings/ack")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"warning_codes":["master_offline","max_positions_reached"]}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    // Inactive codes are not acknowledged
+    assert_eq!(
+        json["acknowledged_warnings"],
+        serde_json::json!(["master_offline"])
+    );
+    // Muted warnings are still reported
+    assert_eq!(
+        json["warnings"],
+        serde_json::json!([
+            {"code": "slave_offline", "muted": false},
+            {"code": "master_offline", "muted": true}
+        ])
+    );
+
+    // The acknowledgment is persisted and applied on subsequent reads
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["warnings"][1]["muted"], serde_json::json!(true));
+}
+
+#[tokio::test]
+async fn test_acknowledge_warnings_member_not_found() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/trade-groups/MASTER_001/members/MISSING/warnings/ack")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"warning_codes":["master_offline"]}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+fn heartbeat(account_id: &str, ea_type: &str) -> HeartbeatMessage {
+    HeartbeatMessage {
+        message_type: "Heartbeat".to_string(),
+        account_id: account_id.to_string(),
+        balance: 10000.0,
+        equity: 10000.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
+        open_positions: 0,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        version: "test".to_string(),
+        ea_type: ea_type.to_string(),
+        platform: "MT5".to_string(),
+        account_number: 12345,
+        broker: "Test Broker".to_string(),
+        account_name: "Test Account".to_string(),
+        server: "Test-Server".to_string(),
+        currency: "USD".to_string(),
+        leverage: 100,
+        is_trade_allowed: true,
+        symbol_prefix: None,
+        symbol_suffix: None,
+        symbol_map: None,
+    }
+}
+
+fn position(ticket: i64, symbol: &str, lots: f64, comment: Option<&str>) -> PositionInfo {
+    PositionInfo {
+        ticket,
+        symbol: symbol.to_string(),
+        order_type: "Buy".to_string(),
+        lots,
+        open_price: 1.1,
+        open_time: chrono::Utc::now().to_rfc3339(),
+        stop_loss: None,
+        take_profit: None,
+        magic_number: None,
+        comment: comment.map(str::to_string),
+        profit: 0.0,
+    }
+}
+
+fn reconcile_request() -> Request<Body> {
+    Request::builder()
+        .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/reconcile")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_reconcile_member_positions() {
+    let (app, db, cm) = create_test_app_with_connections().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 2)
+        .await
+        .unwrap();
+    cm.update_heartbeat(heartbeat("MASTER_001", "Master")).await;
+    cm.update_heartbeat(heartbeat("SLAVE_001", "Slave")).await;
+
+    // Slave snapshot not received yet
+    let response = app.clone().oneshot(reconcile_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    cm.record_position_snapshot(
+        "MASTER_001",
+        EaType::Master,
+        vec![
+            position(1, "EURUSD", 0.1, None),
+            position(2, "GBPUSD", 0.2, None),
+        ],
+    )
+    .await;
+    cm.record_position_snapshot(
+        "SLAVE_001",
+        EaType::Slave,
+        vec![
+            position(101, "EURUSD", 0.5, Some("M1")),
+            position(103, "USDJPY", 0.1, Some("M3")),
+        ],
+    )
+    .await;
+
+    let response = app.oneshot(reconcile_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["missing"][0]["master_ticket"], 2);
+    assert_eq!(json["missing"][0]["expected_lots"], 0.2);
+    assert_eq!(json["extra"][0]["ticket"], 103);
+    assert_eq!(json["lot_mismatch"][0]["slave_ticket"], 101);
+    assert_eq!(json["lot_mismatch"][0]["expected_lots"], 0.1);
+    assert_eq!(json["lot_mismatch"][0]["actual_lots"], 0.5);
+}
+
+#[tokio::test]
+async fn test_reconcile_member_not_found() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    let response = app.oneshot(reconcile_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_effective_config_uses_heartbeat_suffix() {
+    let (app, db, cm) = create_test_app_with_connections().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    // symbol_suffix left unset in the stored settings
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 2)
+        .await
+        .unwrap();
+    cm.update_heartbeat(HeartbeatMessage {
+        symbol_suffix: Some(".m".to_string()),
+        ..heartbeat("SLAVE_001", "Slave")
+    })
+    .await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/effective-config")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let config: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        config["slave_settings"]["symbol_suffix"],
+        serde_json::Value::Null
+    );
+    assert_eq!(config["symbol_suffix"], ".m");
+    assert_eq!(config["symbol_converter"]["suffix_add"], ".m");
+    assert_eq!(config["slave_online"], true);
+    // Defaults are applied to the stored settings
+    assert_eq!(config["slave_settings"]["lot_decimals"], 2);
+}
+
+fn simulate_request(signal: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/simulate")
+        .header("content-type", "application/json")
+        .body(Body::from(signal.to_string()))
+        .unwrap()
+}
+
+fn open_signal(symbol: &str) -> serde_json::Value {
+    serde_json::json!({
+        "action": "Open",
+        "ticket": 1001,
+        "symbol": symbol,
+        "order_type": "Buy",
+        "lots": 0.1,
+        "open_price": 1.1,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "source_account": "MASTER_001"
+    })
+}
+
+async fn simulate(app: axum::Router, signal: serde_json::Value) -> serde_json::Value {
+    let response = app.oneshot(simulate_request(signal)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_simulate_filtered_symbol() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    let settings = SlaveSettings {
+        filters: TradeFilters {
+            blocked_symbols: Some(vec!["GBPUSD".to_string()]),
+            ..TradeFilters::default()
+        },
+        ..SlaveSettings::default()
+    };
+    // Disabled member: the preview evaluates it as if it were enabled
+    db.add_member("MASTER_001", "SLAVE_001", settings, 0)
+        .await
+        .unwrap();
+
+    let result = simulate(app, open_signal("GBPUSD")).await;
+    assert_eq!(result["would_copy"], false);
+    assert_eq!(result["reason"], "symbol_blocked");
+    assert_eq!(result["transformed"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_simulate_copy_shows_multiplied_lots() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    let settings = SlaveSettings {
+        lot_calculation_mode: LotCalculationMode::Multiplier,
+        lot_multiplier: Some(2.0),
+        symbol_suffix: Some(".m".to_string()),
+        ..SlaveSettings::default()
+    };
+    db.add_member("MASTER_001", "SLAVE_001", settings, 0)
+        .await
+        .unwrap();
+
+    let result = simulate(app, open_signal("EURUSD")).await;
+    assert_eq!(result["would_copy"], true);
+    assert_eq!(result["reason"], serde_json::Value::Null);
+    assert_eq!(result["transformed"]["symbol"], "EURUSD.m");
+    // The Slave EA applies the multiplier to the forwarded lot
+    assert_eq!(result["transformed"]["lots"], 0.1);
+    assert_eq!(result["expected_lots"], 0.2);
+}
+
+/// Publisher whose sends always fail (e.g. the ZMQ send task has stopped)
+struct FailingPublisher;
+
+#[async_trait::async_trait]
+impl ConfigPublisher for FailingPublisher {
+    async fn send_master_config(
+        &self,
+        _config: &sankey_copier_zmq::MasterConfigMessage,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("publisher closed")
+    }
+
+    async fn send_slave_config(
+        &self,
+        _config: &sankey_copier_zmq::SlaveConfigMessage,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("publisher closed")
+    }
+
+    async fn broadcast_vlogs_config(
+        &self,
+        _config: &sankey_copier_relay_server::domain::models::VLogsGlobalSettings,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("publisher closed")
+    }
+
+    async fn send_close_all(
+        &self,
+        _message: &sankey_copier_zmq::CloseAllMessage,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("publisher closed")
+    }
+}
+
+#[tokio::test]
+async fn test_toggle_member_reports_config_delivery_failure() {
+    let (app, db, _, tx) = create_test_app_with_publisher(Arc::new(FailingPublisher)).await;
+    let mut events = tx.subscribe();
+    setup_test_trade_group(&db, "MASTER_001").await;
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+        .await
+        .unwrap();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/toggle")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"enabled":true}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // The toggle itself is saved; the failed publish comes back as a warning
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["success"], false);
+    assert_eq!(json["data"]["slave_account"], "SLAVE_001");
+    assert_eq!(json["warnings"][0]["status"], 502);
+    assert!(json["warnings"][0]["detail"]
+        .as_str()
+        .unwrap()
+        .contains("publisher closed"));
+    assert!(
+        db.get_member("MASTER_001", "SLAVE_001")
+            .await
+            .unwrap()
+            .unwrap()
+            .enabled_flag
+    );
+
+    let mut delivery_failed = None;
+    while let Ok(event) = events.try_recv() {
+        if let ServerEvent::ConfigDeliveryFailed { account_id, .. } = event {
+            delivery_failed = Some(account_id);
+        }
+    }
+    assert_eq!(delivery_failed.as_deref(), Some("SLAVE_001"));
+}
+
+/// Counts published Slave configs and records close-all commands
+#[derive(Default)]
+struct CountingPublisher {
+    slave_configs: std::sync::atomic::AtomicUsize,
+    close_all: std::sync::Mutex<Vec<sankey_copier_zmq::CloseAllMessage>>,
+}
+
+#[async_trait::async_trait]
+impl ConfigPublisher for CountingPublisher {
+    async fn send_master_config(
+        &self,
+        _config: &sankey_copier_zmq::MasterConfigMessage,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn send_slave_config(
+        &self,
+        _config: &sankey_copier_zmq::SlaveConfigMessage,
+    ) -> anyhow::Result<()> {
+        self.slave_configs
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn broadcast_vlogs_config(
+        &self,
+        _config: &sankey_copier_relay_server::domain::models::VLogsGlobalSettings,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn send_close_all(
+        &self,
+        message: &sankey_copier_zmq::CloseAllMessage,
+    ) -> anyhow::Result<()> {
+        self.close_all.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_update_member_with_identical_settings_is_noop() {
+    let publisher = Arc::new(CountingPublisher::default());
+    let (app, db, _, _) = create_test_app_with_publisher(publisher.clone()).await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+        .await
+        .unwrap();
+
+    let settings = SlaveSettings {
+        lot_multiplier: Some(2.0),
+        ..SlaveSettings::default()
+    };
+    let put = || {
+        Request::builder()
+            .method("PUT")
+            .uri("/api/trade-groups/MASTER_001/members/SLAVE_001")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&settings).unwrap()))
+            .unwrap()
+    };
+    let changed = |response: axum::response::Response| async move {
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["data"]["changed"].as_bool().unwrap()
+    };
+
+    assert!(changed(app.clone().oneshot(put()).await.unwrap()).await);
+    let published = publisher
+        .slave_configs
+        .load(std::sync::atomic::Ordering::SeqCst);
+    assert_eq!(published, 1);
+
+    // Same settings again: no version bump and nothing sent to the EA
+    assert!(!changed(app.clone().oneshot(put()).await.unwrap()).await);
+    assert!(!changed(app.oneshot(put()).await.unwrap()).await);
+    let member = db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.slave_settings.config_version, 1);
+    assert_eq!(
+        publisher
+            .slave_configs
+            .load(std::sync::atomic::Ordering::SeqCst),
+        published
+    );
+}
+
+#[tokio::test]
+async fn test_broker_profile_applies_to_members_and_redistributes() {
+    let publisher = Arc::new(CountingPublisher::default());
+    let (app, db, _, _) = create_test_app_with_publisher(publisher.clone()).await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/broker-profiles")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "name": "xm",
+                        "symbol_suffix": "#",
+                        "symbol_map": [
+                            {"source_symbol": "XAUUSD", "target_symbol": "GOLD"}
+                        ]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Unknown profiles are rejected when adding a member
+    let add = |profile: &str| {
+        let request = AddMemberRequest {
+            slave_account: "SLAVE_001".to_string(),
+            slave_settings: SlaveSettings {
+                broker_profile: Some(profile.to_string()),
+                ..SlaveSettings::default()
+            },
+            enabled: false,
+        };
+        Request::builder()
+            .method("POST")
+            .uri("/api/trade-groups/MASTER_001/members")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&request).unwrap()))
+            .unwrap()
+    };
+    let response = app.clone().oneshot(add("missing")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app.clone().oneshot(add("xm")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/trade-groups/MASTER_001/members/SLAVE_001/effective-config")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let config: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(config["slave_settings"]["symbol_suffix"], "#");
+    assert_eq!(
+        config["slave_settings"]["symbol_mappings"][0]["target_symbol"],
+        "GOLD"
+    );
+
+    // Updating the profile re-sends the config of every member using it
+    let sent_before = publisher
+        .slave_configs
+        .load(std::sync::atomic::Ordering::SeqCst);
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/broker-profiles/xm")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({"symbol_suffix": ".x"}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        publisher
+            .slave_configs
+            .load(std::sync::atomic::Ordering::SeqCst),
+        sent_before + 1
+    );
+
+    // Referenced profiles cannot be deleted
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/api/broker-profiles/xm")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_close_all_sends_command_to_member() {
+    let publisher = Arc::new(CountingPublisher::default());
+    let (app, db, _, _) = create_test_app_with_publisher(publisher.clone()).await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+    db.add_member("MASTER_001", "SLAVE_001", SlaveSettings::default(), 0)
+        .await
+        .unwrap();
+
+    let close_all = |slave: &str, body: Body| {
+        Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/api/trade-groups/MASTER_001/members/{}/close-all",
+                slave
+            ))
+            .header("content-type", "application/json")
+            .body(body)
+            .unwrap()
+    };
+
+    // No body: everything is closed
+    let response = app
+        .clone()
+        .oneshot(close_all("SLAVE_001", Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    let response = app
+        .clone()
+        .oneshot(close_all("SLAVE_001", Body::from(r#"{"symbol":"EURUSD"}"#)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["message_type"], "CloseAll");
+    assert_eq!(json["symbol"], "EURUSD");
+
+    // Unknown members are rejected without publishing
+    let response = app
+        .oneshot(close_all("SLAVE_404", Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let sent = publisher.close_all.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    assert!(sent.iter().all(|m| m.slave_account == "SLAVE_001"));
+    assert_eq!(sent[0].symbol, None);
+    assert_eq!(sent[1].symbol.as_deref(), Some("EURUSD"));
+}
+
+#[tokio::test]
+async fn test_add_member_inherits_default_slave_settings() {
+    let (app, db) = create_test_app().await;
+    setup_test_trade_group(&db, "MASTER_001").await;
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/global-settings/default-slave-settings")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"lot_calculation_mode": "multiplier", "lot_multiplier": 2.5,
+                        "filters": {"blocked_symbols": ["XAUUSD"]}}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Replacing the other global settings keeps the defaults
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/api/global-settings")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"auto_enable_on_connect": true}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let add = |body: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/trade-groups/MASTER_001/members")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    // Only the slave account: everything comes from the defaults
+    let response = app
+        .clone()
+        .oneshot(add(r#"{"slave_account": "SLAVE_001"}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let member = db
+        .get_member("MASTER_001", "SLAVE_001")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.slave_settings.lot_multiplier, Some(2.5));
+    assert_eq!(
+        member.slave_settings.filters.blocked_symbols,
+        Some(vec!["XAUUSD".to_string()])
+    );
+
+    // Given fields win; the rest is still inherited
+    let response = app
+        .clone()
+        .oneshot(add(
+            r#"{"slave_account": "SLAVE_002", "slave_settings": {"lot_multiplier": 1.5}}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let member = db
+        .get_member("MASTER_001", "SLAVE_002")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(member.slave_settings.lot_multiplier, Some(1.5));
+    assert_eq!(
+        member.slave_settings.filters.blocked_symbols,
+        Some(vec!["XAUUSD".to_string()])
+    );
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/global-settings/default-slave-settings")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let defaults: SlaveSettings = serde_json::from_slice(&body).unwrap();
+    assert_eq!(defaults.lot_multiplier, Some(2.5));
+}
@@ -11,24 +11,39 @@ use serde_json::Value;
 use tower::util::ServiceExt;
 
 use sankey_copier_relay_server::adapters::inbound::http::create_router;
-use sankey_copier_relay_server::adapters::inbound::http::{AppState, SnapshotBroadcaster};
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::inbound::zmq::MessageHandler;
 use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
-use sankey_copier_relay_server::adapters::infrastructure::log_buffer::create_log_buffer;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
 use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
-use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::messaging::{
+    WebsocketBroadcaster, ZmqConfigPublisher, ZmqMessage,
+};
 use sankey_copier_relay_server::adapters::outbound::persistence::Database;
-use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
-use sankey_copier_relay_server::domain::models::{LotCalculationMode, MasterSettings};
+use sankey_copier_relay_server::application::disconnection_service::RealDisconnectionService;
+use sankey_copier_relay_server::application::runtime_status_updater::{
+    RuntimeStatusMetrics, RuntimeStatusUpdater,
+};
+use sankey_copier_relay_server::application::StatusService;
+use sankey_copier_relay_server::domain::models::{
+    HeartbeatMessage, LotCalculationMode, MasterSettings, ServerEvent, SlaveSettings,
+    STATUS_CONNECTED,
+};
+use sankey_copier_relay_server::domain::services::copy_engine::CopyEngine;
 
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 
 /// Helper function to create a test app with in-memory database
 async fn create_test_app() -> (axum::Router, Arc<Database>) {
     let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
     let connection_manager = Arc::new(ConnectionManager::new(30));
-    let (broadcast_tx, _) = broadcast::channel::<String>(100);
-    let log_buffer = create_log_buffer();
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
 
     // Create a dummy ZMQ config sender
     let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
@@ -52,13 +67,15 @@ async fn create_test_app() -> (axum::Router, Arc<Database>) {
         connection_manager,
         config_sender,
         log_buffer,
-        allowed_origins: vec!["http://localhost:8080".to_string()],
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
         cors_disabled: false,
         config: Arc::new(sankey_copier_relay_server::config::Config::default()),
         resolved_ports,
         vlogs_controller: None,
         runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
         snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
     };
 
     (create_router(app_state), db)
@@ -200,6 +217,12 @@ async fn test_update_trade_group_settings_success() {
         symbol_prefix: Some("pro.".to_string()),
         symbol_suffix: Some(".m".to_string()),
         config_version: 0, // Will be incremented by the API
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     let request = Request::builder()
@@ -243,6 +266,12 @@ async fn test_update_trade_group_settings_increments_version() {
         symbol_prefix: Some("v1.".to_string()),
         symbol_suffix: None,
         config_version: 0,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     let request1 = Request::builder()
@@ -264,6 +293,12 @@ async fn test_update_trade_group_settings_increments_version() {
         symbol_prefix: Some("v2.".to_string()),
         symbol_suffix: Some(".v2".to_string()),
         config_version: 1, // API will increment this
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     let request2 = Request::builder()
@@ -336,6 +371,12 @@ async fn test_update_trade_group_settings_not_found() {
         symbol_prefix: Some("test.".to_string()),
         symbol_suffix: None,
         config_version: 0,
+        case_insensitive_symbols: false,
+        closes_only: false,
+        max_signal_age_ms: None,
+        max_signals_per_sec: None,
+        webhook_url: None,
+        total_lot_budget: None,
     };
 
     let request = Request::builder()
@@ -399,11 +440,14 @@ async fn test_delete_trade_group_cascade_deletes_members() {
     db.create_trade_group("MASTER_CASCADE_TEST").await.unwrap();
 
     // Add members to this trade group
-    use sankey_copier_relay_server::domain::models::{SlaveSettings, SyncMode, TradeFilters};
+    use sankey_copier_relay_server::domain::models::{
+        CopyDirection, MergeStrategy, RoundingMode, SlaveSettings, SyncMode, TradeFilters,
+    };
     let slave_settings = SlaveSettings {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -411,6 +455,7 @@ async fn test_delete_trade_group_cascade_deletes_members() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -420,6 +465,34 @@ async fn test_delete_trade_group_cascade_deletes_members() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     db.add_member(
@@ -470,11 +543,14 @@ async fn test_add_member_fails_if_no_trade_group() {
     );
 
     // Add a member via API (without creating TradeGroup first)
-    use sankey_copier_relay_server::domain::models::{SlaveSettings, SyncMode, TradeFilters};
+    use sankey_copier_relay_server::domain::models::{
+        CopyDirection, MergeStrategy, RoundingMode, SlaveSettings, SyncMode, TradeFilters,
+    };
     let slave_settings = SlaveSettings {
         lot_calculation_mode: LotCalculationMode::default(),
         lot_multiplier: Some(1.0),
         reverse_trade: false,
+        reverse_symbols: None,
         symbol_prefix: None,
         symbol_suffix: None,
         symbol_mappings: vec![],
@@ -482,6 +558,7 @@ async fn test_add_member_fails_if_no_trade_group() {
         config_version: 0,
         source_lot_min: None,
         source_lot_max: None,
+        source_lot_bounds_inclusive: true,
         sync_mode: SyncMode::Skip,
         limit_order_expiry_min: None,
         market_sync_max_pips: None,
@@ -491,6 +568,34 @@ async fn test_add_member_fails_if_no_trade_group() {
         max_retries: 3,
         max_signal_delay_ms: 5000,
         use_pending_order_for_delayed: false,
+        fixed_lot: None,
+        risk_percent: None,
+        lot_max: None,
+        lot_min: None,
+        lot_step: None,
+        max_open_positions: None,
+        max_daily_loss: None,
+        max_spread_points: None,
+        trading_hours: None,
+        active_schedule: None,
+        comment_template: None,
+        copy_sample_rate: None,
+        timeout_action: None,
+        portfolio_weight: None,
+        sl_tp_as_offset: false,
+        partial_close_by_lots: false,
+        convert_pending_to_market: false,
+        always_allow_close: true,
+        broker_profile: None,
+        lot_decimals: 2,
+        lot_rounding: RoundingMode::Nearest,
+        merge_strategy: MergeStrategy::CopyAll,
+        copy_delay_ms: None,
+        copy_delay_skip_closes: false,
+        disable_symbol_conversion: false,
+        default_magic: None,
+        magic_number_map: std::collections::HashMap::new(),
+        copy_direction: CopyDirection::Both,
     };
 
     let request_body = serde_json::json!({
@@ -649,3 +754,298 @@ async fn test_toggle_master_not_found() {
     assert!(json["type"].is_string());
     assert_eq!(json["status"], 404);
 }
+
+/// App wired to a real MessageHandler through the test signal queue
+struct SignalTestApp {
+    app: axum::Router,
+    db: Arc<Database>,
+    connection_manager: Arc<ConnectionManager>,
+    handler: MessageHandler,
+    queue: mpsc::UnboundedReceiver<ZmqMessage>,
+    // Keeps the queue open (the app only holds a weak sender)
+    _queue_tx: mpsc::UnboundedSender<ZmqMessage>,
+    events: broadcast::Receiver<ServerEvent>,
+}
+
+async fn create_signal_test_app() -> SignalTestApp {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, events) = broadcast::channel::<ServerEvent>(100);
+    let publisher = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:*").unwrap());
+    let metrics = Arc::new(RuntimeStatusMetrics::default());
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let status_service = StatusService::new(
+        connection_manager.clone(),
+        db.clone(),
+        publisher.clone(),
+        Arc::new(RuntimeStatusUpdater::with_metrics(
+            db.clone(),
+            connection_manager.clone(),
+            metrics.clone(),
+        )),
+        Some(Arc::new(snapshot_broadcaster.clone())),
+        None,
+    );
+    let disconnection_service = Arc::new(RealDisconnectionService::new(
+        connection_manager.clone(),
+        db.clone(),
+        publisher.clone(),
+        Arc::new(WebsocketBroadcaster::new(broadcast_tx.clone())),
+        metrics.clone(),
+    ));
+    let handler = MessageHandler::new(
+        connection_manager.clone(),
+        Arc::new(CopyEngine::new()),
+        broadcast_tx.clone(),
+        db.clone(),
+        publisher.clone(),
+        None,
+        metrics.clone(),
+        status_service,
+        disconnection_service,
+        Arc::new(sankey_copier_relay_server::config::Config::default()),
+    );
+
+    let (queue_tx, queue) = mpsc::unbounded_channel();
+    let app_state = AppState {
+        db: db.clone(),
+        tx: broadcast_tx,
+        connection_manager: connection_manager.clone(),
+        config_sender: publisher,
+        log_buffer: create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY),
+        allowed_origins: CorsOrigins::default(),
+        cors_disabled: true,
+        config: Arc::new(sankey_copier_relay_server::config::Config::default()),
+        resolved_ports: Arc::new(ResolvedPorts {
+            http_port: 3000,
+            receiver_port: 5555,
+            sender_port: 5556,
+            is_dynamic: false,
+            generated_at: None,
+        }),
+        vlogs_controller: None,
+        runtime_status_metrics: metrics,
+        snapshot_broadcaster,
+        mt_installations: Default::default(),
+        signal_injector: Some(queue_tx.downgrade()),
+    };
+
+    SignalTestApp {
+        app: create_router(app_state),
+        db,
+        connection_manager,
+        handler,
+        queue,
+        _queue_tx: queue_tx,
+        events,
+    }
+}
+
+fn slave_heartbeat(account_id: &str) -> HeartbeatMessage {
+    HeartbeatMessage {
+        message_type: "Heartbeat".to_string(),
+        account_id: account_id.to_string(),
+        balance: 10000.0,
+        equity: 10000.0,
+        margin: 0.0,
+        free_margin: 0.0,
+        margin_level: 0.0,
+        open_positions: 0,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        version: "test".to_string(),
+        ea_type: "Slave".to_string(),
+        platform: "MT5".to_string(),
+        account_number: 12345,
+        broker: "Test Broker".to_string(),
+        account_name: "Test Account".to_string(),
+        server: "Test-Server".to_string(),
+        currency: "USD".to_string(),
+        leverage: 100,
+        is_trade_allowed: true,
+        symbol_prefix: None,
+        symbol_suffix: None,
+        symbol_map: None,
+    }
+}
+
+#[tokio::test]
+async fn test_test_signal_is_copied_to_connected_slave() {
+    let mut ctx = create_signal_test_app().await;
+    ctx.db.create_trade_group("MASTER_001").await.unwrap();
+    let settings = SlaveSettings {
+        symbol_suffix: Some(".m".to_string()),
+        lot_calculation_mode: LotCalculationMode::FixedLot,
+        fixed_lot: Some(0.05),
+        ..SlaveSettings::default()
+    };
+    ctx.db
+        .add_member("MASTER_001", "SLAVE_001", settings, STATUS_CONNECTED)
+        .await
+        .unwrap();
+    ctx.connection_manager
+        .update_heartbeat(slave_heartbeat("SLAVE_001"))
+        .await;
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/trade-groups/MASTER_001/test-signal")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"symbol": "GBPUSD", "lots": 0.2}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["source_account"], "MASTER_001");
+    assert_eq!(json["comment"], "TEST");
+
+    // The queued message takes the same path as a real Master signal
+    let message = ctx.queue.try_recv().expect("signal queued");
+    let ZmqMessage::TradeSignal(signal) = &message else {
+        panic!("expected a trade signal");
+    };
+    assert_eq!(signal.comment.as_deref(), Some("TEST"));
+    ctx.handler.handle_message(message).await;
+
+    let mut copied = None;
+    while let Ok(event) = ctx.events.try_recv() {
+        if let ServerEvent::TradeCopied {
+            slave_account,
+            symbol,
+            lots,
+            ..
+        } = event
+        {
+            copied = Some((slave_account, symbol, lots));
+        }
+    }
+    let (slave_account, symbol, lots) = copied.expect("test signal should be copied");
+    assert_eq!(slave_account, "SLAVE_001");
+    assert_eq!(symbol.as_deref(), Some("GBPUSD.m"));
+    assert_eq!(lots, Some(0.05));
+}
+
+#[tokio::test]
+async fn test_test_signal_unknown_group_or_no_processor() {
+    let ctx = create_signal_test_app().await;
+    let request = |uri: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(request("/api/trade-groups/NONEXISTENT/test-signal"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Without a message handler queue the signal cannot be processed
+    let (app, db) = create_test_app().await;
+    db.create_trade_group("MASTER_001").await.unwrap();
+    let response = app
+        .oneshot(request("/api/trade-groups/MASTER_001/test-signal"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_trade_group_stats_count_received_and_copied() {
+    let mut ctx = create_signal_test_app().await;
+    ctx.db.create_trade_group("MASTER_001").await.unwrap();
+    let mut settings = SlaveSettings::default();
+    settings.filters.blocked_symbols = Some(vec!["EURUSD".to_string()]);
+    ctx.db
+        .add_member("MASTER_001", "SLAVE_001", settings, STATUS_CONNECTED)
+        .await
+        .unwrap();
+    ctx.connection_manager
+        .update_heartbeat(slave_heartbeat("SLAVE_001"))
+        .await;
+
+    // One signal is filtered by the member, the other is copied
+    for symbol in ["EURUSD", "GBPUSD"] {
+        let response = ctx
+            .app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/trade-groups/MASTER_001/test-signal")
+                    .header("content-type", "application/json")
+                    .body(Body::from(format!(r#"{{"symbol": "{}"}}"#, symbol)))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let message = ctx.queue.try_recv().expect("signal queued");
+        ctx.handler.handle_message(message).await;
+    }
+
+    let get_stats = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(get_stats("/api/trade-groups/MASTER_001/stats"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["trade_group_id"], "MASTER_001");
+    assert_eq!(json["signals_received"], 2);
+    assert_eq!(json["signals_copied"], 1);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/reset-stats")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(get_stats("/api/trade-groups/MASTER_001/stats"))
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["signals_received"], 0);
+    assert_eq!(json["signals_copied"], 0);
+
+    let response = ctx
+        .app
+        .clone()
+        .oneshot(get_stats("/api/trade-groups/NONEXISTENT/stats"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
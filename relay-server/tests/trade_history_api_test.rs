@@ -0,0 +1,146 @@
+// relay-server/tests/trade_history_api_test.rs
+//
+// Unit tests for the trade history REST API endpoint.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+use tower::util::ServiceExt;
+
+use sankey_copier_relay_server::adapters::inbound::http::create_router;
+use sankey_copier_relay_server::adapters::inbound::http::{
+    AppState, CorsOrigins, SnapshotBroadcaster,
+};
+use sankey_copier_relay_server::adapters::infrastructure::connection_manager::ConnectionManager;
+use sankey_copier_relay_server::adapters::infrastructure::log_buffer::{
+    create_log_buffer, DEFAULT_LOG_BUFFER_CAPACITY,
+};
+use sankey_copier_relay_server::adapters::infrastructure::port_resolver::ResolvedPorts;
+use sankey_copier_relay_server::adapters::outbound::messaging::ZmqConfigPublisher;
+use sankey_copier_relay_server::adapters::outbound::persistence::Database;
+use sankey_copier_relay_server::application::runtime_status_updater::RuntimeStatusMetrics;
+use sankey_copier_relay_server::domain::models::{ServerEvent, TradeAction, TradeHistoryEntry};
+
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Helper function to create a test app with in-memory database
+async fn create_test_app() -> (axum::Router, Arc<Database>) {
+    let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+    let connection_manager = Arc::new(ConnectionManager::new(30));
+    let (broadcast_tx, _) = broadcast::channel::<ServerEvent>(100);
+    let log_buffer = create_log_buffer(DEFAULT_LOG_BUFFER_CAPACITY);
+
+    // Create a dummy ZMQ config sender
+    let config_sender = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:0").unwrap());
+
+    // 2-port architecture: receiver and unified publisher
+    let resolved_ports = Arc::new(ResolvedPorts {
+        http_port: 3000,
+        receiver_port: 5555,
+        sender_port: 5556,
+        is_dynamic: false,
+        generated_at: None,
+    });
+
+    let snapshot_broadcaster =
+        SnapshotBroadcaster::new(broadcast_tx.clone(), connection_manager.clone(), db.clone());
+
+    let app_state = AppState {
+        db: db.clone(),
+        tx: broadcast_tx,
+        connection_manager,
+        config_sender,
+        log_buffer,
+        allowed_origins: CorsOrigins::new(vec!["http://localhost:8080".to_string()]),
+        cors_disabled: false,
+        config: Arc::new(sankey_copier_relay_server::config::Config::default()),
+        resolved_ports,
+        vlogs_controller: None,
+        runtime_status_metrics: Arc::new(RuntimeStatusMetrics::default()),
+        snapshot_broadcaster,
+        signal_injector: None,
+        mt_installations: Default::default(),
+    };
+
+    (create_router(app_state), db)
+}
+
+async fn seed(db: &Database, slave: &str, ticket: i64, hour: u32, copied: bool) {
+    db.insert_trade_history(&TradeHistoryEntry {
+        id: 0,
+        master_account: "MASTER_001".to_string(),
+        slave_account: slave.to_string(),
+        ticket,
+        action: TradeAction::Open,
+        symbol: Some("EURUSD".to_string()),
+        lots: Some(0.1),
+        timestamp: Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+        copied,
+        filter_reason: (!copied).then(|| "symbol_blocked".to_string()),
+    })
+    .await
+    .unwrap();
+}
+
+async fn get_json(app: axum::Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_get_trade_history_with_filters() {
+    let (app, db) = create_test_app().await;
+    seed(&db, "SLAVE_001", 1, 9, true).await;
+    seed(&db, "SLAVE_002", 2, 10, false).await;
+    seed(&db, "SLAVE_002", 3, 11, true).await;
+
+    let (status, json) = get_json(
+        app,
+        "/api/trade-history?master=MASTER_001&slave=SLAVE_002&from=2025-01-15T10:00:00Z&limit=1",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["total"], 2);
+    assert_eq!(json["limit"], 1);
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["ticket"], 3);
+    assert_eq!(items[0]["copied"], true);
+}
+
+#[tokio::test]
+async fn test_get_trade_history_includes_filter_reason() {
+    let (app, db) = create_test_app().await;
+    seed(&db, "SLAVE_002", 2, 10, false).await;
+
+    let (status, json) = get_json(app, "/api/trade-history").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["items"][0]["filter_reason"], "symbol_blocked");
+    assert_eq!(json["items"][0]["action"], "Open");
+}
+
+#[tokio::test]
+async fn test_get_trade_history_rejects_inverted_range() {
+    let (app, _db) = create_test_app().await;
+
+    let (status, _json) = get_json(
+        app,
+        "/api/trade-history?from=2025-01-16T00:00:00Z&to=2025-01-15T00:00:00Z",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,91 @@
+// relay-server/tests/zmq_curve_test.rs
+//
+// Integration tests for CurveZMQ on the relay's PULL socket.
+// Tests that need CURVE return early when libzmq was built without it.
+
+use sankey_copier_relay_server::adapters::outbound::messaging::{ZmqMessage, ZmqServer};
+use sankey_copier_zmq::curve::{self, CurveClientKeys, CurveKeyPair};
+use sankey_copier_zmq::UnregisterMessage;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn unregister_bytes(account_id: &str) -> Vec<u8> {
+    rmp_serde::to_vec_named(&UnregisterMessage {
+        message_type: "Unregister".to_string(),
+        account_id: account_id.to_string(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        ea_type: None,
+    })
+    .unwrap()
+}
+
+/// Send one message from a PUSH client, optionally with CURVE keys
+fn push_once(endpoint: &str, keys: Option<&CurveClientKeys>, payload: &[u8]) {
+    let ctx = zmq::Context::new();
+    let push = ctx.socket(zmq::PUSH).unwrap();
+    if let Some(keys) = keys {
+        curve::apply_client(&push, keys).unwrap();
+    }
+    push.set_linger(0).unwrap();
+    push.connect(endpoint).unwrap();
+    // PUSH queues until the handshake completes; a rejected handshake drops it
+    push.send(payload, zmq::DONTWAIT).ok();
+    std::thread::sleep(Duration::from_millis(300));
+}
+
+async fn recv_account(rx: &mut mpsc::UnboundedReceiver<ZmqMessage>) -> Option<String> {
+    match tokio::time::timeout(Duration::from_millis(700), rx.recv()).await {
+        Ok(Some(ZmqMessage::Unregister(msg))) => Some(msg.account_id),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_curve_server_rejects_plaintext_client() {
+    if !curve::is_supported() {
+        eprintln!("libzmq built without CURVE; skipping");
+        return;
+    }
+
+    let server_keys = CurveKeyPair::generate().unwrap();
+    let endpoint = format!("tcp://127.0.0.1:{}", free_port());
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let server = ZmqServer::new(tx)
+        .unwrap()
+        .with_curve_secret_key(Some(server_keys.secret_key.clone()));
+    let handle = server.start_receiver(&endpoint).await.unwrap();
+
+    // Plaintext client: handshake fails, nothing is delivered
+    push_once(&endpoint, None, &unregister_bytes("PLAINTEXT"));
+    assert_eq!(recv_account(&mut rx).await, None);
+
+    // CURVE client with the server's public key is accepted
+    let keys = CurveClientKeys::for_server(&server_keys.public_key).unwrap();
+    push_once(&endpoint, Some(&keys), &unregister_bytes("ENCRYPTED"));
+    assert_eq!(recv_account(&mut rx).await.as_deref(), Some("ENCRYPTED"));
+
+    server.shutdown();
+    handle.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_curve_server_fails_fast_without_curve_support() {
+    if curve::is_supported() {
+        return;
+    }
+
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let server = ZmqServer::new(tx)
+        .unwrap()
+        .with_curve_secret_key(Some("rq:rM>}U?@Lns47E1%kR.o@n%FcmmsL/@{H8]yf7".to_string()));
+    let endpoint = format!("tcp://127.0.0.1:{}", free_port());
+    assert!(server.start_receiver(&endpoint).await.is_err());
+}
@@ -13,8 +13,8 @@ use sankey_copier_relay_server::application::runtime_status_updater::{
 };
 use sankey_copier_relay_server::application::StatusService;
 use sankey_copier_relay_server::domain::models::{
-    MasterSettings, PositionSnapshotMessage, RegisterMessage, SlaveSettings, SyncRequestMessage,
-    STATUS_CONNECTED,
+    MasterSettings, PositionSnapshotMessage, RegisterMessage, ServerEvent, SlaveSettings,
+    SyncRequestMessage, STATUS_CONNECTED,
 };
 use sankey_copier_relay_server::domain::services::copy_engine::CopyEngine;
 use std::sync::Arc;
@@ -32,7 +32,7 @@ impl TestContext {
     async fn new() -> Self {
         let connection_manager = Arc::new(ConnectionManager::new(30));
         let copy_engine = Arc::new(CopyEngine::new());
-        let (broadcast_tx, _rx) = broadcast::channel::<String>(100);
+        let (broadcast_tx, _rx) = broadcast::channel::<ServerEvent>(100);
         let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
         let publisher = Arc::new(ZmqConfigPublisher::new("tcp://127.0.0.1:*").unwrap());
 
@@ -122,6 +122,7 @@ fn build_position_snapshot(source_account: &str) -> PositionSnapshotMessage {
         source_account: source_account.to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         positions: vec![],
+        ea_type: None,
     }
 }
 
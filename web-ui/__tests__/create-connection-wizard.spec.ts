@@ -84,7 +84,7 @@ test.describe('Create Connection Wizard', () => {
       await route.fulfill({
         status: 200,
         contentType: 'application/json',
-        body: JSON.stringify(mockConnections),
+        body: JSON.stringify({ items: mockConnections, next_cursor: null }),
       });
     });
 
@@ -356,7 +356,7 @@ test.describe('Create Connection Wizard - Error Handling', () => {
       await route.fulfill({
         status: 200,
         contentType: 'application/json',
-        body: JSON.stringify(mockConnections),
+        body: JSON.stringify({ items: mockConnections, next_cursor: null }),
       });
     });
 
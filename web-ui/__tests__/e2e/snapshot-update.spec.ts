@@ -59,7 +59,7 @@ test('UI updates immediately when system_snapshot is received', async ({ page })
   // Navigate to page
   // Mock API responses first to ensure page loads even if backend is offline
   await page.route('/api/connections', async route => {
-    await route.fulfill({ json: [] });
+    await route.fulfill({ json: { items: [], next_cursor: null } });
   });
   await page.route('/api/trade-groups', async route => {
     await route.fulfill({ json: [] });
@@ -149,7 +149,7 @@ test('UI updates immediately when system_snapshot is received', async ({ page })
   await page.evaluate((snapshot) => {
     const ws = (window as any).__mockWebSocket;
     if (ws && ws.onmessage) {
-      ws.onmessage({ data: `system_snapshot:${JSON.stringify(snapshot)}` });
+      ws.onmessage({ data: JSON.stringify({ type: 'system_snapshot', data: snapshot }) });
     }
   }, initialSnapshot);
 
@@ -191,7 +191,7 @@ test('UI updates immediately when system_snapshot is received', async ({ page })
   await page.evaluate((snapshot) => {
     const ws = (window as any).__mockWebSocket;
     if (ws && ws.onmessage) {
-      ws.onmessage({ data: `system_snapshot:${JSON.stringify(snapshot)}` });
+      ws.onmessage({ data: JSON.stringify({ type: 'system_snapshot', data: snapshot }) });
     }
   }, autoTradingOffSnapshot);
 
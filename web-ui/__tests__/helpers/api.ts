@@ -54,7 +54,7 @@ export async function setupDefaultApiMocks(page: Page): Promise<void> {
     if (shouldLogApi) {
       console.log('[MockAPI] connections', route.request().url());
     }
-    await fulfillJson(route, mockConnections);
+    await fulfillJson(route, { items: mockConnections, next_cursor: null });
   });
 
   await page.route('**/api/trade-groups', async (route) => {
@@ -286,6 +286,8 @@ export const mockVictoriaLogsConfig = {
     source: 'playwright-tests',
   },
   enabled: true,
+  buffered_batches: 0,
+  dropped_batches: 0,
 };
 
 export const mockVictoriaLogsSettings = {
@@ -210,7 +210,7 @@ test.describe('Runtime vs intent toggles', () => {
     }
 
     await page.route('**/api/connections', async (route) => {
-      await fulfillJson(route, connections);
+      await fulfillJson(route, { items: connections, next_cursor: null });
     });
 
     await page.route('**/api/trade-groups/*/members', async (route) => {
@@ -243,7 +243,7 @@ test.describe('Runtime vs intent toggles', () => {
     await setupMockWebSocket(page);
 
     await page.route('**/api/connections', async (route) => {
-      await fulfillJson(route, mockConnections);
+      await fulfillJson(route, { items: mockConnections, next_cursor: null });
     });
 
     await page.route('**/api/trade-groups/*/members/*/toggle', async (route) => {
@@ -327,14 +327,14 @@ test.describe('Runtime vs intent toggles', () => {
       masterGroup.master_runtime_status = 2;
     }
 
-    // Build a proper system_snapshot message (the implementation expects this format)
+    // Build a system_snapshot event (JSON frame tagged by type)
     const snapshotData = {
       connections: mockConnections,
       trade_groups: Array.from(tradeGroups.values()),
       members: Array.from(members.values()).flat(),
     };
     await page.evaluate((snapshot) => {
-      (window as unknown as { __emitWsMessage?: (message: string) => void }).__emitWsMessage?.(`system_snapshot:${JSON.stringify(snapshot)}`);
+      (window as unknown as { __emitWsMessage?: (message: string) => void }).__emitWsMessage?.(JSON.stringify({ type: 'system_snapshot', data: snapshot }));
     }, snapshotData);
 
     await expect(receiverCard.getByText('Receiving')).toBeVisible();
@@ -5,6 +5,7 @@ interface LogEntry {
   timestamp: string;
   level: string;
   message: string;
+  account_id?: string;
 }
 
 interface ApiClient {
@@ -166,7 +166,10 @@ export function SlaveSettingsDrawer({
         use_pending_order_for_delayed: formData.use_pending_order_for_delayed,
       };
 
-      await apiClient.updateTradeGroupMember(masterAccount, member.slave_account, settings);
+      const result = await apiClient.updateTradeGroupMember(masterAccount, member.slave_account, settings);
+      if (!result.success) {
+        console.warn(`Settings saved but config was not delivered to ${member.slave_account}`, result.warnings);
+      }
       setMessage({ type: 'success', text: content.settingsSavedSuccess.value });
 
       // Notify parent and close after short delay
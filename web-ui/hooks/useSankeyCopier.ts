@@ -2,13 +2,15 @@ import { useState, useEffect, useCallback, useRef } from 'react';
 import { useAtom, useAtomValue } from 'jotai';
 import { debounce } from 'lodash-es';
 import type {
+  ApiResponse,
   CopySettings,
-  EaConnection,
+  ConnectionsPage,
   CreateSettingsRequest,
   TradeGroup,
   TradeGroupMember,
   WarningCode,
   SystemStateSnapshot,
+  ServerEvent,
 } from '@/types';
 import { selectedSiteAtom, apiClientAtom } from '@/lib/atoms/site';
 import { settingsAtom } from '@/lib/atoms/settings';
@@ -32,10 +34,10 @@ export function useSankeyCopier() {
   const fetchConnections = useCallback(async () => {
     if (!apiClient) return;
     try {
-      // Rust API returns Vec<EaConnection> directly (not wrapped)
-      const data = await apiClient.get<EaConnection[]>('/connections');
+      // Without `limit` the API returns every connection on a single page
+      const data = await apiClient.get<ConnectionsPage>('/connections');
       if (data) {
-        setConnections(data);
+        setConnections(data.items);
       }
     } catch (err) {
       if (err instanceof TypeError && err.message.includes('fetch')) {
@@ -105,48 +107,31 @@ export function useSankeyCopier() {
   // Using useEffectEvent to stabilize the message handler and remove dependencies from the effect
   const handleMessage = useEffectEvent((event: MessageEvent) => {
     const message = event.data as string;
+    const serverEvent = parseServerEvent(message);
 
     // Handle system_snapshot (Full State Replace)
-    if (message.startsWith('system_snapshot:')) {
-      try {
-        const jsonPart = message.slice('system_snapshot:'.length);
-        const snapshot = JSON.parse(jsonPart) as SystemStateSnapshot;
-
-        // 1. Update Connections
-        setConnections(snapshot.connections);
-
-        // 2. Update TradeGroups (Masters)
-        setTradeGroups(snapshot.trade_groups);
+    if (serverEvent?.type === 'system_snapshot') {
+      const snapshot = serverEvent.data;
 
-        // 3. Update Settings (Members)
-        // Convert flat members list to Map for adapter
-        const membersMap = new Map<string, TradeGroupMember[]>();
+      // 1. Update Connections
+      setConnections(snapshot.connections);
 
-        snapshot.members.forEach(member => {
-          const existing = membersMap.get(member.trade_group_id) || [];
-          existing.push(member);
-          membersMap.set(member.trade_group_id, existing);
-        });
+      // 2. Update TradeGroups (Masters)
+      setTradeGroups(snapshot.trade_groups);
 
-        // Convert to CopySettings format
-        const copySettings = convertMembersToCopySettings(snapshot.trade_groups, membersMap);
-        setSettings(copySettings);
+      // 3. Update Settings (Members)
+      // Convert flat members list to Map for adapter
+      const membersMap = new Map<string, TradeGroupMember[]>();
 
-      } catch (err) {
-        console.debug('Failed to parse system snapshot', err);
-      }
-      return;
-    }
+      snapshot.members.forEach(member => {
+        const existing = membersMap.get(member.trade_group_id) || [];
+        existing.push(member);
+        membersMap.set(member.trade_group_id, existing);
+      });
 
-    // Legacy support for older server versions or partial updates
-    if (message.startsWith('connections_snapshot:')) {
-      try {
-        const jsonPart = message.slice('connections_snapshot:'.length);
-        const snapshot = JSON.parse(jsonPart) as EaConnection[];
-        setConnections(snapshot);
-      } catch {
-        console.debug('Failed to parse connections snapshot');
-      }
+      // Convert to CopySettings format
+      const copySettings = convertMembersToCopySettings(snapshot.trade_groups, membersMap);
+      setSettings(copySettings);
       return;
     }
 
@@ -154,10 +139,8 @@ export function useSankeyCopier() {
     setWsMessages((prev) => [message, ...prev].slice(0, 20));
 
     // Only refresh for member deletion (need full refresh to remove from list)
-    if (message.startsWith('member_deleted:')) {
-      fetchSettings(); // This now refers to the latest scope thanks to useEffectEvent logic wrapper? 
-      // Wait, useEffectEvent captures the scope when called. 
-      // Actually fetchSettings is stable (useCallback) but logic using it inside here is cleaner.
+    if (serverEvent?.type === 'member_deleted') {
+      fetchSettings();
     }
   });
 
@@ -346,10 +329,13 @@ export function useSankeyCopier() {
     if (!debouncedFn) {
       debouncedFn = debounce(async (masterAccount: string, slaveAccount: string, enabled: boolean) => {
         try {
-          await apiClient.post<void>(
+          const result = await apiClient.post<ApiResponse<TradeGroupMember>>(
             `/trade-groups/${encodeURIComponent(masterAccount)}/members/${encodeURIComponent(slaveAccount)}/toggle`,
             { enabled }
           );
+          if (result && !result.success) {
+            console.warn(`Toggle saved but config was not delivered to ${slaveAccount}`, result.warnings);
+          }
         } catch (err) {
           console.error(`Failed to toggle setting for ${slaveAccount}`, err);
           fetchSettings(); // Refresh on error
@@ -575,3 +561,27 @@ export function useSankeyCopier() {
     deleteSetting,
   };
 }
+
+/**
+ * Parse a WebSocket frame into a ServerEvent.
+ * JSON events are the default; `system_snapshot:` / `member_deleted:` prefixed
+ * frames are still accepted from relays running with `server.legacy_ws_events = true`.
+ */
+function parseServerEvent(message: string): ServerEvent | null {
+  try {
+    if (message.startsWith('{')) {
+      return JSON.parse(message) as ServerEvent;
+    }
+    if (message.startsWith('system_snapshot:')) {
+      const data = JSON.parse(message.slice('system_snapshot:'.length)) as SystemStateSnapshot;
+      return { type: 'system_snapshot', data };
+    }
+    if (message.startsWith('member_deleted:')) {
+      const data = JSON.parse(message.slice('member_deleted:'.length));
+      return { type: 'member_deleted', ...data };
+    }
+  } catch {
+    return null;
+  }
+  return null;
+}
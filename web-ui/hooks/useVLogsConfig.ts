@@ -13,6 +13,7 @@ export interface VLogsConfigInfo {
   flush_interval_secs: number;
   source: string;
   log_level: string;
+  max_buffered_batches: number;
 }
 
 // Full config response from GET /api/victoria-logs-config
@@ -20,6 +21,10 @@ export interface VLogsConfigResponse {
   configured: boolean;
   config: VLogsConfigInfo | null;
   enabled: boolean;
+  /** Failed batches waiting for retry */
+  buffered_batches: number;
+  /** Batches dropped because the retry buffer overflowed */
+  dropped_batches: number;
 }
 
 // Update request payload
@@ -33,6 +38,8 @@ const DEFAULT_CONFIG: VLogsConfigResponse = {
   configured: false,
   config: null,
   enabled: false,
+  buffered_batches: 0,
+  dropped_batches: 0,
 };
 
 export function useVLogsConfig() {
@@ -103,6 +110,8 @@ export function useVLogsConfig() {
     configured: configData.configured,
     config: configData.config,
     enabled: configData.enabled,
+    bufferedBatches: configData.buffered_batches,
+    droppedBatches: configData.dropped_batches,
     // State
     loading,
     updating, // renamed from toggling
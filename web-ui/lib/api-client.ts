@@ -222,7 +222,7 @@ export class ApiClient {
     masterAccount: string,
     slaveAccount: string,
     settings: import('@/types').SlaveSettings
-  ): Promise<void> {
+  ): Promise<import('@/types').ApiResponse<import('@/types').UpdateMemberResult>> {
     return this.put(`/trade-groups/${encodeURIComponent(masterAccount)}/members/${encodeURIComponent(slaveAccount)}`, settings);
   }
 
@@ -243,7 +243,7 @@ export class ApiClient {
     masterAccount: string,
     slaveAccount: string,
     enabled: boolean
-  ): Promise<void> {
+  ): Promise<import('@/types').ApiResponse<import('@/types').TradeGroupMember>> {
     return this.post(`/trade-groups/${encodeURIComponent(masterAccount)}/members/${encodeURIComponent(slaveAccount)}/toggle`, { enabled });
   }
 }
@@ -1,5 +1,5 @@
 // Lot calculation mode type
-export type LotCalculationMode = 'multiplier' | 'margin_ratio';
+export type LotCalculationMode = 'multiplier' | 'margin_ratio' | 'equity_ratio' | 'fixed_lot' | 'risk_percent';
 
 // Sync mode for existing positions when slave connects
 export type SyncMode = 'skip' | 'limit_order' | 'market_order';
@@ -7,13 +7,24 @@ export type SyncMode = 'skip' | 'limit_order' | 'market_order';
 // Warning codes from Status Engine (snake_case to match API response)
 export type WarningCode =
   | 'slave_web_ui_disabled'
+  | 'outside_active_schedule'
   | 'slave_offline'
   | 'slave_auto_trading_disabled'
+  | 'low_margin_level'
   | 'no_master_assigned'
   | 'master_web_ui_disabled'
   | 'master_offline'
   | 'master_auto_trading_disabled'
-  | 'master_cluster_degraded';
+  | 'master_cluster_degraded'
+  | 'self_copy_detected'
+  | 'stop_loss_required'
+  | 'lot_below_minimum'
+  | 'max_positions_reached'
+  | 'daily_loss_limit_hit'
+  | 'globally_blocked_symbol'
+  | 'spread_too_wide'
+  | 'master_signal_rate_exceeded'
+  | 'currency_mismatch';
 
 export interface CopySettings {
   id: number;
@@ -47,6 +58,8 @@ export interface CopySettings {
 export interface SymbolMapping {
   source_symbol: string;
   target_symbol: string;
+  /** Lower values are matched first; ties keep list order */
+  priority?: number;
 }
 
 export interface TradeFilters {
@@ -54,6 +67,8 @@ export interface TradeFilters {
   blocked_symbols: string[] | null;
   allowed_magic_numbers: number[] | null;
   blocked_magic_numbers: number[] | null;
+  allowed_comment_substrings?: string[] | null; // Case-insensitive keyword match on trade comment
+  blocked_comment_substrings?: string[] | null;
 }
 
 export interface EaConnection {
@@ -66,6 +81,10 @@ export interface EaConnection {
   server: string;
   balance: number;
   equity: number;
+  margin?: number;           // Used margin (0 for EAs that do not report margin)
+  free_margin?: number;
+  margin_level?: number;     // Equity / margin * 100 (0 without open positions)
+  low_margin_level?: boolean; // Below the relay's low_margin_level_threshold
   currency: string;
   leverage: number;
   last_heartbeat: string;
@@ -76,6 +95,156 @@ export interface EaConnection {
   role?: 'master' | 'slave';
   is_online?: boolean;
   symbol_context?: SymbolContext;
+  symbol_prefix?: string | null; // Reported by the latest heartbeat
+  symbol_suffix?: string | null;
+  symbol_map?: string | null;
+  timeout_override?: number | null; // Per-EA heartbeat timeout in seconds
+  missed_heartbeats?: number;        // Consecutive timeout checks without a heartbeat
+  last_latency_ms?: number | null;   // Latest ping round-trip time (null if the EA never echoed)
+  flap_count?: number;               // Reconnections within the grace period (reset with connected_at)
+  uptime_seconds?: number;           // Seconds since connected_at while up (connections API only)
+}
+
+// GET /api/connections (?limit=&after=<next_cursor>)
+export interface ConnectionsPage {
+  items: EaConnection[];        // Sorted by account_id, then ea_type (Master first)
+  next_cursor: string | null;   // "{account_id}:{ea_type}"; null on the last page
+}
+
+// GET /api/connections/:id/positions
+export interface PositionInfo {
+  ticket: number;
+  symbol: string;
+  order_type: string; // "Buy", "Sell", "BuyLimit", etc.
+  lots: number;
+  open_price: number;
+  open_time: string;
+  stop_loss?: number;
+  take_profit?: number;
+  magic_number?: number;
+  comment?: string;
+  profit: number; // Floating P/L in account currency
+}
+
+export interface ConnectionPositionsResponse {
+  account_id: string;
+  ea_type: 'Master' | 'Slave';
+  received_at: string | null; // null when no snapshot arrived within the staleness window
+  positions: PositionInfo[];
+}
+
+// GET /api/connections/:id/history
+export type ConnectionEventKind =
+  | 'registered'
+  | 'heartbeat_gap'
+  | 'timeout'
+  | 'reconnected'
+  | 'unregistered';
+
+export interface ConnectionEvent {
+  at: string;
+  ea_type: 'Master' | 'Slave';
+  event: ConnectionEventKind;
+  reason: string;            // What triggered the event (elapsed seconds, missed count, ...)
+}
+
+// GET /api/trade-groups/:id/members/:slave_id/reconcile
+export interface ReconcileMissingPosition {
+  master_ticket: number;
+  symbol: string;                // Symbol expected on the Slave (after conversion)
+  order_type: string;
+  expected_lots: number | null;  // null for margin_ratio (sized by the Slave EA)
+}
+
+export interface ReconcileLotMismatch {
+  master_ticket: number;
+  slave_ticket: number;
+  symbol: string;
+  expected_lots: number;
+  actual_lots: number;
+}
+
+export interface ReconcileResponse {
+  master_account: string;
+  slave_account: string;
+  master_snapshot_at: string;
+  slave_snapshot_at: string;
+  missing: ReconcileMissingPosition[];
+  extra: PositionInfo[];         // Slave copies whose Master position is gone
+  lot_mismatch: ReconcileLotMismatch[];
+}
+
+// POST /api/trade-groups/:id/members/:slave_id/close-all
+export interface CloseAllRequest {
+  symbol?: string | null;        // omitted = close copies on every symbol
+}
+
+export interface CloseAllMessage {
+  message_type: 'CloseAll';
+  slave_account: string;
+  symbol?: string;
+  timestamp: string;             // ISO 8601
+}
+
+// POST /api/trade-groups/:id/test-signal (responds 202 with the injected TradeSignal)
+export interface TestSignalRequest {
+  symbol?: string | null;        // Master-side symbol, default "EURUSD"
+  lots?: number | null;          // Master-side lots, default 0.01
+}
+
+// GET /api/trade-groups/:id/stats (reset with POST /api/admin/reset-stats)
+export interface TradeGroupStats {
+  trade_group_id: string;
+  signals_received: number;      // Signals accepted from the Master
+  signals_copied: number;        // Signals sent to at least one member
+}
+
+// GET /api/trade-groups/:id/members/:slave_id/effective-config
+export interface EffectiveSymbolConverter {
+  prefix_remove: string | null;  // Master prefix stripped before conversion
+  suffix_remove: string | null;
+  prefix_add: string | null;     // Slave prefix added (after heartbeat/detected fallbacks)
+  suffix_add: string | null;
+  synonym_groups: string[][];
+  detected_symbols: string[] | null;
+  case_insensitive: boolean;
+}
+
+export interface EffectiveConfigResponse {
+  master_account: string;
+  slave_account: string;
+  status: number;
+  warning_codes: WarningCode[];
+  slave_settings: SlaveSettings;   // Stored settings with defaults applied
+  symbol_prefix: string | null;    // Effective prefix/suffix added to Slave symbols
+  symbol_suffix: string | null;
+  symbol_converter: EffectiveSymbolConverter;
+  slave_online: boolean;           // Fallbacks require a connected Slave EA
+}
+
+// POST /api/trade-groups/:id/members/:slave_id/simulate
+export interface SimulatedTradeSignal {
+  action: 'Open' | 'Close' | 'Modify';
+  ticket: number;
+  symbol?: string;
+  order_type?: string; // "Buy", "Sell", "BuyLimit", etc.
+  lots?: number;
+  open_price?: number;
+  stop_loss?: number;
+  take_profit?: number;
+  magic_number?: number;
+  comment?: string;
+  timestamp: string;
+  source_account: string; // Replaced with the trade group ID by the relay
+  close_ratio?: number;
+}
+
+export interface SimulateTradeResponse {
+  would_copy: boolean;
+  reason: string | null;               // Skip reason, e.g. "symbol_blocked"
+  warning: WarningCode | null;         // Set when the skip is reported as a warning
+  transformed: SimulatedTradeSignal | null;
+  expected_lots: number | null;        // Includes the EA-side multiplier
 }
 
 export interface SymbolContext {
@@ -116,7 +285,7 @@ export interface CreateSettingsRequest {
 
 export interface AddMemberRequest {
   slave_account: string;
-  slave_settings: SlaveSettings;
+  slave_settings?: Partial<SlaveSettings>; // Merged over GlobalSettings.default_slave_settings
   enabled?: boolean;
 }
 
@@ -205,6 +374,12 @@ export interface MasterSettings {
   enabled: boolean;
   symbol_prefix?: string | null;
   symbol_suffix?: string | null;
+  case_insensitive_symbols?: boolean; // Ignore case in prefix/suffix removal and mappings
+  closes_only?: boolean; // Forward Close/Modify only; new Opens are not copied
+  max_signal_age_ms?: number | null; // Drop Opens older than this (Close/Modify always pass)
+  max_signals_per_sec?: number | null; // Drop Open/Modify above this rate (Close always passes)
+  webhook_url?: string | null; // POST master online/offline and new warnings here
+  total_lot_budget?: number | null; // Cap on the Master lots split by portfolio_weight
   config_version: number;
 }
 
@@ -218,6 +393,27 @@ export interface TradeGroup {
   members?: TradeGroupMember[];
 }
 
+// Relay-wide settings (GET/PUT /api/global-settings)
+export interface GlobalSettings {
+  auto_enable_on_connect: boolean; // Enable disabled members when their Slave EA connects
+  max_lot_multiplier?: number | null; // Upper bound for members' lot_multiplier (null = no cap)
+  blocked_symbols?: string[];         // Master symbols (globs allowed) no member may open
+  timeout_action?: TimeoutAction;     // Reaction to an EA heartbeat timeout (default 'pause_copying')
+  default_slave_settings?: SlaveSettings; // New members start from these (GET/PUT /api/global-settings/default-slave-settings)
+}
+
+// Symbol defaults shared by members on one broker (/api/broker-profiles)
+export interface BrokerProfile {
+  name: string;
+  symbol_prefix?: string | null;  // Used when the member sets no prefix
+  symbol_suffix?: string | null;  // Used when the member sets no suffix
+  symbol_map: SymbolMapping[];    // Appended after the member's own mappings
+  created_at: string;
+  updated_at: string;
+}
+
+export type TimeoutAction = 'mark_only' | 'pause_copying' | 'disable_member';
+
 // Full System State Snapshot
 export interface SystemStateSnapshot {
   connections: EaConnection[];
@@ -225,19 +421,91 @@ export interface SystemStateSnapshot {
   members: TradeGroupMember[];
 }
 
+// Slave config re-evaluated by the relay (settings_updated event payload)
+export interface SlaveConfigWithMaster {
+  master_account: string;
+  slave_account: string;
+  status: number;
+  enabled_flag: boolean;
+  warning_codes: WarningCode[];
+  slave_settings: SlaveSettings;
+}
+
+// RFC 9457 Problem Details (errors and response warnings)
+export interface ProblemDetailsWarning {
+  type: string;
+  title: string;
+  status: number;
+  detail?: string;
+  instance?: string;
+}
+
+// Envelope for actions that also send config to EAs (e.g. member toggle)
+export interface ApiResponse<T> {
+  success: boolean;                   // false when the action was saved but config delivery failed
+  data: T;
+  warnings: ProblemDetailsWarning[];
+}
+
+// PUT /api/trade-groups/:id/members/:slave_id result
+export interface UpdateMemberResult {
+  changed: boolean;        // false when the settings matched the stored ones (nothing sent to the EA)
+  config_version: number;
+}
+
+// WebSocket events (JSON objects tagged by `type`)
+export type ServerEvent =
+  | { type: 'system_snapshot'; data: SystemStateSnapshot }
+  | { type: 'settings_updated'; data: SlaveConfigWithMaster }
+  | { type: 'vlogs_settings_updated'; data: Record<string, unknown> } // VLogsGlobalSettings (hooks/useVLogsSettings)
+  | { type: 'member_added' | 'member_updated' | 'member_status_changed'; data: TradeGroupMember }
+  | { type: 'member_deleted'; trade_group_id: string; slave_account: string }
+  | { type: 'ea_disconnected'; account_id: string }
+  | { type: 'trade_received'; master_account: string; symbol: string | null; lots: number | null }
+  | { type: 'trade_copied'; master_account: string; slave_account: string; symbol: string | null; lots: number | null; member_id: number }
+  | { type: 'trade_skipped'; master_account: string; slave_account: string; ticket: number; reason: string; member_id: number }
+  | { type: 'position_snapshot'; source_account: string; positions: number }
+  | { type: 'sync_request'; slave_account: string; master_account: string }
+  | { type: 'config_delivery_failed'; trade_group_id: string; account_id: string; detail: string }
+  | { type: 'timeout_action_applied'; account_id: string; ea_type: 'Master' | 'Slave'; action: TimeoutAction; members: { trade_group_id: string; slave_account: string; action: TimeoutAction }[] };
+
+// WebSocket subscribe message (client -> relay); empty object receives all events
+export interface WsSubscribeMessage {
+  subscribe: { trade_group?: string; account?: string };
+}
+
 // TradeGroupMember (Slave settings) types
 export interface SlaveSettings {
   lot_calculation_mode: LotCalculationMode;
   lot_multiplier: number | null;
+  fixed_lot?: number | null;              // Used when lot_calculation_mode = fixed_lot
+  risk_percent?: number | null;           // Used when lot_calculation_mode = risk_percent
   reverse_trade: boolean;
+  reverse_symbols?: string[] | null;      // Reverse only these slave symbols (null = all)
   symbol_prefix?: string | null;
   symbol_suffix?: string | null;
   symbol_mappings: SymbolMapping[];
   filters: TradeFilters;
+  magic_number_map?: Record<string, number>; // Master magic -> Slave magic
+  default_magic?: number | null;             // Magic for unmapped signals
+  comment_template?: string | null;          // Open comment; {master}, {orig_comment}, {ticket} (max 31 chars)
   config_version: number;
   // Lot filtering: min/max lot size from master to copy
   source_lot_min?: number | null;
   source_lot_max?: number | null;
+  source_lot_bounds_inclusive?: boolean;  // Lots equal to min/max are copied (default: true)
+  // Broker volume constraints for relay-calculated lots
+  lot_step?: number | null;
+  lot_min?: number | null;
+  lot_max?: number | null;
+  lot_decimals?: number;                  // Decimal places of relay-calculated lots (default: 2)
+  lot_rounding?: RoundingMode;            // Rounding at lot_decimals (default: nearest)
+  copy_direction?: CopyDirection;          // Copy only one side of new positions
+  max_open_positions?: number | null;     // Skip new Opens at this many open positions
+  max_daily_loss?: number | null;         // Stop new Opens for the UTC day past this loss
+  max_spread_points?: number | null;      // Skip Opens when the Master spread is wider
+  trading_hours?: TradingWindow | null;   // Copy new Opens only inside this window
+  active_schedule?: ScheduleWindow[] | null; // Member is paused (DISABLED) outside these windows
   // Open Sync Policy settings
   sync_mode?: SyncMode;                  // Sync mode: skip, limit_order, market_order
   limit_order_expiry_min?: number | null; // minutes (0 = GTC)
@@ -248,6 +516,39 @@ export interface SlaveSettings {
   max_retries?: number;                   // Max order retry count (default: 3)
   max_signal_delay_ms?: number;           // Max signal delay in ms (default: 5000)
   use_pending_order_for_delayed?: boolean; // Use pending order for delayed signals
+  sl_tp_as_offset?: boolean;              // SL/TP keep the Master's distance from the Slave's fill price
+  partial_close_by_lots?: boolean;        // Send partial closes as slave lots, not close_ratio
+  convert_pending_to_market?: boolean;    // Copy pending Opens as market orders of the same direction
+  always_allow_close?: boolean;           // Close signals bypass all filters (default: true)
+  broker_profile?: string;                // Shared symbol prefix/suffix/mappings (GET /api/broker-profiles)
+  merge_strategy?: MergeStrategy;         // Same-symbol Opens from several masters (default: copy_all)
+  copy_delay_ms?: [number, number] | null; // Random publish delay [min, max] ms (per-slave order kept)
+  copy_delay_skip_closes?: boolean;       // Closes are published without copy_delay_ms
+  copy_sample_rate?: number | null;       // Share of Master tickets to copy, 0.0-1.0 (per ticket, Close follows Open)
+  timeout_action?: TimeoutAction | null;  // Overrides global_settings.timeout_action for this member
+  portfolio_weight?: number | null;       // Share of the Master lots: weight / sum of the group's weights
+  disable_symbol_conversion?: boolean;    // Send the Master's symbol verbatim (no prefix/suffix/mappings)
+}
+
+export type CopyDirection = 'both' | 'buy_only' | 'sell_only';
+
+export type RoundingMode = 'nearest' | 'down' | 'up';
+
+export type MergeStrategy = 'copy_all' | 'priority_first';
+
+export interface TradingWindow {
+  days: ('Mon' | 'Tue' | 'Wed' | 'Thu' | 'Fri' | 'Sat' | 'Sun')[];
+  start: string; // "HH:MM:SS", inclusive
+  end: string;   // "HH:MM:SS", exclusive (start > end wraps past midnight)
+  tz: string;    // IANA timezone, e.g. "UTC", "Asia/Tokyo"
+}
+
+// Window of slave_settings.active_schedule (same shape as TradingWindow)
+export type ScheduleWindow = TradingWindow;
+
+export interface MemberWarning {
+  code: WarningCode;
+  muted: boolean; // true when acknowledged via POST .../warnings/ack
 }
 
 export interface TradeGroupMember {
@@ -257,7 +558,84 @@ export interface TradeGroupMember {
   slave_settings: SlaveSettings;
   status: number; // Runtime status evaluated by server (0=DISABLED,1=ENABLED,2=CONNECTED)
   warning_codes: WarningCode[]; // Warning codes from Status Engine
+  acknowledged_warnings?: WarningCode[]; // Warning codes muted by the user
+  warnings?: MemberWarning[]; // warning_codes with mute state
   enabled_flag: boolean; // User intent flag (true when switch is ON)
   created_at: string;
   updated_at: string;
 }
+
+// GET /api/members (?status=&connected=&limit=&after=<next_cursor>)
+export interface MemberListItem extends TradeGroupMember {
+  master_account: string; // Same as trade_group_id
+  connected: boolean;     // Slave EA online
+}
+
+export interface MembersPage {
+  items: MemberListItem[];      // Sorted by master_account, then slave_account
+  next_cursor: string | null;   // "{master_account}:{slave_account}"; null on the last page
+}
+
+// Trade history (one row per signal and slave copy decision)
+export interface TradeHistoryEntry {
+  id: number;
+  master_account: string;
+  slave_account: string;
+  ticket: number;
+  action: 'Open' | 'Close' | 'Modify';
+  symbol: string | null;
+  lots: number | null;            // Lots sent by the Master
+  timestamp: string;              // Master signal time (RFC 3339)
+  copied: boolean;
+  filter_reason: string | null;   // Skip reason / warning code / copy failure (null when copied)
+}
+
+export interface TradeHistoryPage {
+  items: TradeHistoryEntry[];
+  total: number;                  // Matching rows before pagination
+  limit: number;
+  offset: number;
+}
+
+// Config export/import (GET /api/config/export, POST /api/config/import)
+export interface MemberExport {
+  slave_account: string;
+  slave_settings: SlaveSettings;
+  enabled_flag: boolean;
+}
+
+export interface TradeGroupExport {
+  id: string;                     // Master account ID
+  master_settings: MasterSettings;
+  members: MemberExport[];
+}
+
+export interface ConfigExport {
+  version: number;
+  exported_at: string;            // RFC 3339
+  trade_groups: TradeGroupExport[];
+}
+
+export interface ConfigImportResult {
+  dry_run: boolean;
+  created: string[];              // TradeGroup IDs created (or to be created)
+  updated: string[];              // TradeGroup IDs overwritten (or to be overwritten)
+  conflicts: string[];            // Existing TradeGroup IDs not overwritten
+}
+
+// POST /api/admin/backup
+export interface BackupResult {
+  filename: string;               // File name inside [database] backup_dir
+  size_bytes: number;
+}
+
+// GET /api/tls-status
+export interface TlsStatus {
+  not_before: string;             // RFC 3339
+  not_after: string;              // RFC 3339
+  days_remaining: number;         // Negative once expired
+  expiring_soon: boolean;         // Expires within expiry_warning_days
+  expired: boolean;
+  self_signed: boolean;
+  expiry_warning_days: number;
+}